@@ -0,0 +1,70 @@
+//! Headless Bevy app serving BRP, used by the integration tests in `tests/`
+//!
+//! Runs `MinimalPlugins` with `RemotePlugin` and `RemoteHttpPlugin` on the port in `BRP_PORT`
+//! (default: 15702), the variable the launch tools set. It registers a few reflected types and
+//! spawns a named `Player` entity so spawn, get, mutate and format discovery can be exercised
+//! without a window or GPU.
+
+use std::time::Duration;
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::prelude::*;
+use bevy::remote::RemotePlugin;
+use bevy::remote::http::RemoteHttpPlugin;
+use serde::{Deserialize, Serialize};
+
+/// Port used when `BRP_PORT` is not set
+const DEFAULT_PORT: u16 = 15702;
+
+/// A component with plain fields
+///
+/// BRP can only spawn and insert types that reflect `Serialize` and `Deserialize`.
+#[derive(Component, Reflect, Default, Serialize, Deserialize)]
+#[reflect(Component, Default, Serialize, Deserialize)]
+struct Health {
+    current: f32,
+    max:     f32,
+}
+
+/// A resource with a plain field
+#[derive(Resource, Reflect, Default, Serialize, Deserialize)]
+#[reflect(Resource, Default, Serialize, Deserialize)]
+struct Score {
+    points: u32,
+}
+
+fn main() {
+    let port = std::env::var("BRP_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+
+    App::new()
+        .add_plugins(
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+                1.0 / 60.0,
+            ))),
+        )
+        .add_plugins((
+            RemotePlugin::default(),
+            RemoteHttpPlugin::default().with_port(port),
+        ))
+        .register_type::<Health>()
+        .register_type::<Score>()
+        .register_type::<Transform>()
+        .register_type::<Name>()
+        .init_resource::<Score>()
+        .add_systems(Startup, spawn_player)
+        .run();
+}
+
+fn spawn_player(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Player"),
+        Transform::default(),
+        Health {
+            current: 100.0,
+            max:     100.0,
+        },
+    ));
+}
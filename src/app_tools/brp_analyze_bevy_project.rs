@@ -0,0 +1,117 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::support::cargo_detector::ProjectAnalysis;
+use super::support::scanning;
+use crate::BrpMcpService;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, service};
+
+pub async fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    service::handle_with_request_and_paths(
+        service,
+        request,
+        context,
+        |req, search_paths| async move {
+            let workspace = params::extract_optional_workspace(&req);
+            let workspaces = scanning::analyze_workspaces(&search_paths, workspace.as_deref());
+            let ready = workspaces
+                .iter()
+                .filter(|analysis| analysis.is_ready())
+                .count();
+            let projects: Vec<Value> = workspaces.iter().map(project_report).collect();
+
+            let response = ResponseBuilder::success()
+                .message(format!(
+                    "Analyzed {} workspaces, {ready} ready for BRP",
+                    projects.len()
+                ))
+                .data(json!({
+                    "projects": projects,
+                    "ready_count": ready,
+                }))
+                .map_or_else(
+                    |_| {
+                        ResponseBuilder::error()
+                            .message("Failed to serialize response data")
+                            .build()
+                    },
+                    ResponseBuilder::build,
+                );
+
+            Ok(json_response_to_result(&response))
+        },
+    )
+    .await
+}
+
+/// The analysis of a workspace with its readiness verdict
+fn project_report(analysis: &ProjectAnalysis) -> Value {
+    let mut report = json!(analysis);
+    report["workspace"] = json!(scanning::extract_workspace_name(&analysis.workspace_root));
+    report["ready"] = json!(analysis.is_ready());
+    report["issues"] = json!(analysis.issues());
+    report["manifest"] = json!(
+        analysis
+            .workspace_root
+            .join("Cargo.toml")
+            .display()
+            .to_string()
+    );
+    if analysis.brp_extras_version.is_none() && !analysis.packages.is_empty() {
+        report["note"] = json!(
+            "bevy_brp_extras is not a dependency, so screenshots, input simulation, format discovery from the app and clean shutdown are unavailable"
+        );
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::super::support::cargo_detector::PackageAnalysis;
+    use super::*;
+
+    #[test]
+    fn test_reports_list_what_keeps_a_workspace_from_brp() {
+        let package = PackageAnalysis {
+            name:             "game".to_string(),
+            manifest_path:    Path::new("/work/game/Cargo.toml").to_path_buf(),
+            uses_brp_plugins: true,
+            binaries:         vec!["game".to_string()],
+            examples:         Vec::new(),
+        };
+        let mut analysis = ProjectAnalysis {
+            workspace_root:     Path::new("/work/game").to_path_buf(),
+            cargo_lock:         true,
+            bevy_version:       Some("0.16.1".to_string()),
+            bevy_remote:        true,
+            brp_extras_version: Some("0.2.0".to_string()),
+            packages:           vec![package],
+        };
+        let report = project_report(&analysis);
+        assert_eq!(report["ready"], true);
+        assert_eq!(report["workspace"], "game");
+        assert_eq!(report["issues"], json!([]));
+        assert!(report.get("note").is_none());
+
+        analysis.bevy_remote = false;
+        analysis.brp_extras_version = None;
+        let report = project_report(&analysis);
+        assert_eq!(report["ready"], false);
+        assert!(
+            report["issues"][0]
+                .as_str()
+                .is_some_and(|issue| issue.contains("bevy_remote"))
+        );
+        assert!(report.get("note").is_some());
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+
+use super::support::managed_apps::{self, AppOrigin, ManagedApp};
+use crate::brp_tools::brp_status::{check_brp_on_port, process_matches_app};
+use crate::constants::{PARAM_APP_NAME, PARAM_GROUP, PARAM_LOG_FILE, PARAM_PID, PARAM_PORT};
+use crate::error::{Error, report_to_mcp_error};
+use crate::log_tools::support::{LOG_EXTENSION, LOG_PREFIX, create_log_directory};
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::{BrpMcpService, config};
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: rmcp::model::CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let app_name = params::extract_required_string(&request, PARAM_APP_NAME)?;
+    let pid = params::extract_optional_number(&request, PARAM_PID, 0)?;
+    let port = params::extract_optional_u16_from_request(&request, PARAM_PORT)?
+        .unwrap_or_else(config::default_port);
+    let log_file = params::extract_optional_string(&request, PARAM_LOG_FILE, "");
+    let group = params::extract_optional_string(&request, PARAM_GROUP, "");
+
+    // Resolve the process, either by the given PID or by name
+    let pid = if pid == 0 {
+        find_pid_by_name(app_name)?
+    } else {
+        let pid = u32::try_from(pid).map_err(|_| {
+            report_to_mcp_error(
+                &error_stack::Report::new(Error::invalid("pid", "value too large"))
+                    .attach_printable(format!("Provided value: {pid}")),
+            )
+        })?;
+        if !managed_apps::is_pid_running(pid) {
+            return Err(report_to_mcp_error(
+                &error_stack::Report::new(Error::ProcessManagement(format!(
+                    "No running process with PID {pid}"
+                )))
+                .attach_printable(format!("App name: {app_name}")),
+            ));
+        }
+        pid
+    };
+
+    let brp_responsive = check_brp_on_port(port).await?;
+
+    // Bridge an existing log file into the log directory so list_logs/read_log can see it
+    let bridged_log = if log_file.is_empty() {
+        None
+    } else {
+        Some(bridge_log_file(app_name, Path::new(log_file))?)
+    };
+
+    let mut app = ManagedApp::new(app_name, pid, Some(port), AppOrigin::Attached);
+    app.log_file = bridged_log;
+    app.group = (!group.is_empty()).then(|| group.to_string());
+
+    let replaced =
+        managed_apps::register(app.clone()).map_err(|report| report_to_mcp_error(&report))?;
+
+    let mut message = format!("Attached to '{app_name}' (PID: {pid}) on port {port}");
+    if !brp_responsive {
+        message.push_str(
+            ". Warning: BRP is not responding on this port - make sure RemotePlugin is added",
+        );
+    }
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "app": app,
+            "brp_responsive": brp_responsive,
+            "replaced_pid": replaced.map(|previous| previous.pid),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Find the PID of a running process by app name
+fn find_pid_by_name(app_name: &str) -> Result<u32, McpError> {
+    let mut system = System::new_all();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    let matches: Vec<Pid> = system
+        .processes()
+        .values()
+        .filter(|process| process_matches_app(process, app_name))
+        .map(sysinfo::Process::pid)
+        .collect();
+
+    match matches.as_slice() {
+        [pid] => Ok(pid.as_u32()),
+        [] => Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(format!(
+                "No running process matches '{app_name}'"
+            )))
+            .attach_printable("Pass the pid parameter to attach to a specific process"),
+        )),
+        _ => Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(format!(
+                "Multiple processes match '{app_name}': {}",
+                matches
+                    .iter()
+                    .map(|pid| pid.as_u32().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )))
+            .attach_printable("Pass the pid parameter to choose one"),
+        )),
+    }
+}
+
+/// Link an existing log file into the log directory using the standard log file naming
+fn bridge_log_file(app_name: &str, log_file: &Path) -> Result<PathBuf, McpError> {
+    let log_file = log_file.canonicalize().map_err(|e| {
+        report_to_mcp_error(
+            &error_stack::Report::new(Error::LogOperation(
+                "Cannot access log file to attach".to_string(),
+            ))
+            .attach_printable(format!("Path: {}", log_file.display()))
+            .attach_printable(format!("Error: {e}")),
+        )
+    })?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let link_path =
+        create_log_directory().join(format!("{LOG_PREFIX}{app_name}_{timestamp}{LOG_EXTENSION}"));
+
+    std::os::unix::fs::symlink(&log_file, &link_path).map_err(|e| {
+        report_to_mcp_error(
+            &error_stack::Report::new(Error::LogOperation(
+                "Failed to link log file into log directory".to_string(),
+            ))
+            .attach_printable(format!("Source: {}", log_file.display()))
+            .attach_printable(format!("Link: {}", link_path.display()))
+            .attach_printable(format!("Error: {e}")),
+        )
+    })?;
+
+    Ok(link_path)
+}
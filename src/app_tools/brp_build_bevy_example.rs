@@ -0,0 +1,47 @@
+//! Build an example without running it
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+
+use super::support::cargo_build::{self, ArtifactKey};
+use super::support::launch_common::{self, LaunchOptions};
+use super::support::managed_apps::LaunchTarget;
+use super::support::scanning;
+use crate::constants::{PARAM_EXAMPLE_NAME, PARAM_PROFILE};
+use crate::support::{params, service};
+use crate::{BrpMcpService, config};
+
+pub async fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let example_name = params::extract_required_string(&request, PARAM_EXAMPLE_NAME)?;
+    let default_profile = config::default_profile();
+    let profile = params::extract_optional_string(&request, PARAM_PROFILE, &default_profile);
+    let workspace = params::extract_optional_workspace(&request);
+    let options = LaunchOptions::from_request(&request)?;
+
+    let search_paths = service::fetch_roots_and_get_paths(service, context).await?;
+    let example = scanning::find_required_example_with_workspace(
+        example_name,
+        workspace.as_deref(),
+        &search_paths,
+    )?;
+    let manifest_dir = launch_common::validate_manifest_directory(&example.manifest_path)?;
+
+    let key = ArtifactKey::new(
+        &example.manifest_path,
+        LaunchTarget::Example,
+        example_name,
+        profile,
+        &options,
+    );
+    let report = cargo_build::build(&key, manifest_dir, &options).await?;
+    Ok(cargo_build::build_response(
+        example_name,
+        "example_name",
+        &report,
+    ))
+}
@@ -0,0 +1,263 @@
+use std::path::{Path, PathBuf};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::support::cargo_detector::PackageAnalysis;
+use super::support::{brp_setup, line_diff, scanning};
+use crate::BrpMcpService;
+use crate::brp_tools::constants::DEFAULT_BRP_PORT;
+use crate::constants::{PARAM_DRY_RUN, PARAM_PACKAGE, PARAM_WITH_EXTRAS};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, service};
+
+/// A file of the project and the edit made to it
+struct FileEdit {
+    path:     PathBuf,
+    original: String,
+    edited:   Option<String>,
+    /// Why the file is left as it is
+    skipped:  Option<&'static str>,
+}
+
+pub async fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    service::handle_with_request_and_paths(
+        service,
+        request,
+        context,
+        |req, search_paths| async move {
+            let package_name = params::extract_any_value(&req, PARAM_PACKAGE)
+                .and_then(Value::as_str)
+                .map(String::from);
+            let workspace = params::extract_optional_workspace(&req);
+            let flag = |name: &str| {
+                params::extract_any_value(&req, name)
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+            };
+            let with_extras = flag(PARAM_WITH_EXTRAS);
+            let dry_run = flag(PARAM_DRY_RUN);
+
+            let package = scanning::find_required_package_with_workspace(
+                package_name.as_deref(),
+                workspace.as_deref(),
+                &search_paths,
+            )?;
+            let edits = plan_edits(&package, with_extras).map_err(|e| report_to_mcp_error(&e))?;
+            if !dry_run {
+                write_edits(&edits).map_err(|e| report_to_mcp_error(&e))?;
+            }
+
+            let changed = edits.iter().filter(|edit| edit.edited.is_some()).count();
+            let message = match (changed, dry_run) {
+                (0, _) => format!("{} already has BRP set up", package.name),
+                (_, true) => format!("Would edit {changed} files of {}", package.name),
+                (_, false) => format!("Edited {changed} files of {}", package.name),
+            };
+            let response = ResponseBuilder::success()
+                .message(message)
+                .data(setup_report(&package, &edits, with_extras, dry_run))
+                .map_or_else(
+                    |_| {
+                        ResponseBuilder::error()
+                            .message("Failed to serialize response data")
+                            .build()
+                    },
+                    ResponseBuilder::build,
+                );
+
+            Ok(json_response_to_result(&response))
+        },
+    )
+    .await
+}
+
+/// The edits of a package's `Cargo.toml` and `main.rs`, without writing them
+fn plan_edits(package: &PackageAnalysis, with_extras: bool) -> Result<Vec<FileEdit>> {
+    let original = read_file(&package.manifest_path)?;
+    let mut manifest = brp_setup::enable_remote_feature(&original)?;
+    if with_extras {
+        let current = manifest.as_deref().unwrap_or(&original);
+        if let Some(edited) = brp_setup::add_brp_extras(current) {
+            manifest = Some(edited);
+        }
+    }
+    let manifest_edit = FileEdit {
+        path: package.manifest_path.clone(),
+        skipped: manifest
+            .is_none()
+            .then_some("bevy_remote is already enabled"),
+        edited: manifest,
+        original,
+    };
+
+    let main_path = package
+        .manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("src")
+        .join("main.rs");
+    let main_edit = if package.uses_brp_plugins {
+        // Added by hand or by an earlier run, which `add_setup_block` would only recognize by
+        // its marker
+        FileEdit {
+            path:     main_path,
+            original: String::new(),
+            edited:   None,
+            skipped:  Some("the package already adds RemotePlugin or BrpExtrasPlugin"),
+        }
+    } else {
+        let original = read_file(&main_path)?;
+        let edited = brp_setup::add_setup_block(&original, with_extras)?;
+        FileEdit {
+            path: main_path,
+            skipped: edited
+                .is_none()
+                .then_some("the setup block is already present"),
+            edited,
+            original,
+        }
+    };
+    Ok(vec![manifest_edit, main_edit])
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| {
+        error_stack::Report::new(Error::FileOperation(format!(
+            "Failed to read {}: {e}",
+            path.display()
+        )))
+    })
+}
+
+/// Write the edited files
+fn write_edits(edits: &[FileEdit]) -> Result<()> {
+    for edit in edits {
+        if let Some(edited) = &edit.edited {
+            std::fs::write(&edit.path, edited).map_err(|e| {
+                error_stack::Report::new(Error::FileOperation(format!(
+                    "Failed to write {}: {e}",
+                    edit.path.display()
+                )))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// The diffs of the edits and what to do next
+fn setup_report(
+    package: &PackageAnalysis,
+    edits: &[FileEdit],
+    with_extras: bool,
+    dry_run: bool,
+) -> Value {
+    let root = package
+        .manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    let changes: Vec<Value> = edits
+        .iter()
+        .filter_map(|edit| {
+            edit.edited.as_ref().map(|edited| {
+                json!({
+                    "file": edit.path.display().to_string(),
+                    "diff": line_diff::unified_diff(&relative(&edit.path), &edit.original, edited),
+                })
+            })
+        })
+        .collect();
+    let unchanged: Vec<Value> = edits
+        .iter()
+        .filter_map(|edit| {
+            edit.skipped.map(|reason| {
+                json!({
+                    "file": edit.path.display().to_string(),
+                    "reason": reason,
+                })
+            })
+        })
+        .collect();
+
+    let next_steps = if changes.is_empty() {
+        format!(
+            "Launch {} with brp_launch_bevy_app; the app listens for BRP on port {DEFAULT_BRP_PORT}",
+            package.name
+        )
+    } else if dry_run {
+        "Review the diffs, then call again without dry_run to apply them".to_string()
+    } else {
+        format!(
+            "Launch {} with brp_launch_bevy_app, which builds it first; the app listens for BRP on port {DEFAULT_BRP_PORT}",
+            package.name
+        )
+    };
+    json!({
+        "package": package.name,
+        "manifest_path": package.manifest_path.display().to_string(),
+        "with_extras": with_extras,
+        "dry_run": dry_run,
+        "changes": changes,
+        "unchanged": unchanged,
+        "next_steps": next_steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_report_shows_relative_diffs_and_skipped_files() {
+        let package = PackageAnalysis {
+            name:             "game".to_string(),
+            manifest_path:    Path::new("/work/game/Cargo.toml").to_path_buf(),
+            uses_brp_plugins: true,
+            binaries:         vec!["game".to_string()],
+            examples:         Vec::new(),
+        };
+        let original = "[dependencies]\nbevy = \"0.16\"\n".to_string();
+        let edits = [
+            FileEdit {
+                path: package.manifest_path.clone(),
+                edited: brp_setup::enable_remote_feature(&original).unwrap(),
+                original,
+                skipped: None,
+            },
+            FileEdit {
+                path:     Path::new("/work/game/src/main.rs").to_path_buf(),
+                original: String::new(),
+                edited:   None,
+                skipped:  Some("the package already adds RemotePlugin or BrpExtrasPlugin"),
+            },
+        ];
+
+        let report = setup_report(&package, &edits, false, true);
+        let diff = report["changes"][0]["diff"].as_str().unwrap();
+        assert!(diff.starts_with("--- a/Cargo.toml\n"));
+        assert!(diff.contains(
+            "-bevy = \"0.16\"\n+bevy = { version = \"0.16\", features = [\"bevy_remote\"] }"
+        ));
+        assert_eq!(report["unchanged"][0]["file"], "/work/game/src/main.rs");
+        assert!(
+            report["next_steps"]
+                .as_str()
+                .is_some_and(|steps| steps.contains("without dry_run"))
+        );
+    }
+}
@@ -4,14 +4,15 @@ use rmcp::{Error as McpError, RoleServer};
 use serde_json::json;
 use sysinfo::{Signal, System};
 
-use crate::BrpMcpService;
+use crate::app_tools::support::managed_apps;
 use crate::brp_tools::brp_set_debug_mode::is_debug_enabled;
-use crate::brp_tools::constants::DEFAULT_BRP_PORT;
-use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::support::brp_client::{self, BrpResult, execute_brp_method};
+use crate::brp_tools::support::destructive_ops::{self, Confirmation};
 use crate::error::{Error, Result, report_to_mcp_error};
 use crate::support::params;
 use crate::support::response::ResponseBuilder;
 use crate::support::serialization::json_response_to_result;
+use crate::{BrpMcpService, config};
 
 /// Helper function to build shutdown response with debug info
 fn build_shutdown_response(
@@ -54,8 +55,17 @@ enum ShutdownResult {
     Error { message: String },
 }
 
-/// Attempt to shutdown a Bevy app, first trying graceful shutdown then falling back to kill
+/// Shutdown a Bevy app and drop it from the managed app registry once it is no longer running
 async fn shutdown_app(app_name: &str, port: u16) -> (ShutdownResult, Vec<String>) {
+    let (result, debug_info) = shutdown_process(app_name, port).await;
+    if !matches!(result, ShutdownResult::Error { .. }) {
+        managed_apps::remove(app_name);
+    }
+    (result, debug_info)
+}
+
+/// Attempt to shutdown a Bevy app, first trying graceful shutdown then falling back to kill
+async fn shutdown_process(app_name: &str, port: u16) -> (ShutdownResult, Vec<String>) {
     let mut debug_info = Vec::new();
 
     if is_debug_enabled() {
@@ -162,22 +172,19 @@ fn handle_kill_process_fallback(
 
 pub async fn handle(
     _service: &BrpMcpService,
-    request: rmcp::model::CallToolRequestParam,
+    mut request: rmcp::model::CallToolRequestParam,
     _context: RequestContext<RoleServer>,
 ) -> std::result::Result<CallToolResult, McpError> {
     // Get parameters
+    let confirmation = destructive_ops::take_confirmation(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
     let app_name = params::extract_required_string(&request, "app_name")?;
-    let port = params::extract_optional_number(&request, "port", u64::from(DEFAULT_BRP_PORT))?;
 
-    let port = u16::try_from(port).map_err(|_| -> McpError {
-        report_to_mcp_error(
-            &error_stack::Report::new(Error::ParameterExtraction(
-                "Invalid port parameter".to_string(),
-            ))
-            .attach_printable("Port must be a valid u16")
-            .attach_printable(format!("Provided value: {port}")),
-        )
-    })?;
+    let port = extract_port(&request, app_name)?;
+
+    if let Some(preview) = preview_shutdown(app_name, port, &confirmation) {
+        return Ok(preview);
+    }
 
     // Shutdown the app
     let (result, debug_info) = shutdown_app(app_name, port).await;
@@ -273,6 +280,51 @@ pub async fn handle(
     }
 }
 
+/// The port of the app, defaulting to the one a managed app was launched or attached with
+fn extract_port(
+    request: &rmcp::model::CallToolRequestParam,
+    app_name: &str,
+) -> std::result::Result<u16, McpError> {
+    let default_port = managed_apps::get(app_name)
+        .and_then(|app| app.port)
+        .unwrap_or_else(config::default_port);
+    let port = params::extract_optional_number(request, "port", u64::from(default_port))?;
+
+    u16::try_from(port).map_err(|_| -> McpError {
+        report_to_mcp_error(
+            &error_stack::Report::new(Error::ParameterExtraction(
+                "Invalid port parameter".to_string(),
+            ))
+            .attach_printable("Port must be a valid u16")
+            .attach_printable(format!("Provided value: {port}")),
+        )
+    })
+}
+
+/// Preview what shutting an app down would stop instead of doing it, unless it is confirmed
+fn preview_shutdown(
+    app_name: &str,
+    port: u16,
+    confirmation: &Confirmation,
+) -> Option<CallToolResult> {
+    let operation = format!("shutdown {app_name} {}:{port}", brp_client::current_host());
+    let reason = destructive_ops::preview_reason(&operation, confirmation)?;
+    let managed = managed_apps::get(app_name);
+    let preview = json!({
+        "app_name": app_name,
+        "port": port,
+        "running": is_process_running(app_name),
+        "managed": managed.is_some(),
+        "pid": managed.map(|app| app.pid),
+    });
+    Some(destructive_ops::preview_response(
+        &operation,
+        &format!("shutting down '{app_name}' on port {port}"),
+        &reason,
+        &preview,
+    ))
+}
+
 /// Try to gracefully shutdown via `bevy_brp_extras`
 async fn try_graceful_shutdown(port: u16) -> Result<(bool, Vec<String>)> {
     let mut debug_info = Vec::new();
@@ -325,6 +377,10 @@ async fn try_graceful_shutdown(port: u16) -> Result<(bool, Vec<String>)> {
 
 /// Check if a process with the given name is currently running
 fn is_process_running(app_name: &str) -> bool {
+    if let Some(app) = managed_apps::get(app_name) {
+        return app.is_running();
+    }
+
     let mut system = System::new_all();
     system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
 
@@ -342,7 +398,17 @@ fn kill_process(app_name: &str) -> Result<Option<u32>> {
     let mut system = System::new_all();
     system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
 
+    // Managed apps are identified by PID rather than by name, as long as it is still theirs
+    let managed = managed_apps::get(app_name);
+    if let Some(app) = &managed {
+        app.verify_process()?;
+    }
+    let managed_pid = managed.map(|app| sysinfo::Pid::from_u32(app.pid));
+
     let running_process = system.processes().values().find(|process| {
+        if let Some(pid) = managed_pid {
+            return process.pid() == pid;
+        }
         let process_name = process.name().to_string_lossy();
         // Match exact name or with common variations (.exe suffix, etc.)
         process_name == app_name
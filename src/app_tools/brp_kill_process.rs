@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+
+use super::support::managed_apps::{self, ManagedApp};
+use super::support::process;
+use crate::BrpMcpService;
+use crate::brp_tools::support::destructive_ops::{self, Confirmation};
+use crate::constants::{PARAM_APP_NAME, PARAM_FORCE, PARAM_GROUP};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+
+/// How long to wait for a killed process to exit
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    mut request: rmcp::model::CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let confirmation = destructive_ops::take_confirmation(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let force = params::extract_any_value(&request, PARAM_FORCE)
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    let group = params::extract_optional_string(&request, PARAM_GROUP, "");
+    if !group.is_empty() {
+        if params::extract_any_value(&request, PARAM_APP_NAME).is_some() {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::invalid(PARAM_GROUP, "give either app_name or group, not both"),
+            )));
+        }
+        return kill_group(group, force, &confirmation).await;
+    }
+    let app_name = params::extract_required_string(&request, PARAM_APP_NAME)?;
+
+    let app = require_managed_app(app_name)?;
+    app.verify_process()
+        .map_err(|report| report_to_mcp_error(&report))?;
+    if let Some(preview) = preview_kill(
+        std::slice::from_ref(&app),
+        &format!("'{app_name}' (PID: {})", app.pid),
+        force,
+        &confirmation,
+    ) {
+        return Ok(preview);
+    }
+    let signalled = process::terminate_process(app.pid, force)?;
+    let exited = !signalled || process::wait_for_exit(app.pid, EXIT_TIMEOUT).await;
+
+    // Keep the entry while the process is still around so the kill can be retried
+    if exited {
+        managed_apps::remove(app_name);
+    }
+
+    let message = match (signalled, exited) {
+        (false, _) => format!("'{app_name}' (PID: {}) had already exited", app.pid),
+        (true, true) => format!("Killed '{app_name}' (PID: {})", app.pid),
+        (true, false) => format!(
+            "Sent {} to '{app_name}' (PID: {}) but it is still running after {}s. Retry with force: true",
+            if force { "SIGKILL" } else { "SIGTERM" },
+            app.pid,
+            EXIT_TIMEOUT.as_secs()
+        ),
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "app_name": app_name,
+            "pid": app.pid,
+            "port": app.port,
+            "force": force,
+            "exited": exited
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Kill every app of a group, signalling them all before waiting for them to exit
+async fn kill_group(
+    group: &str,
+    force: bool,
+    confirmation: &Confirmation,
+) -> Result<CallToolResult, McpError> {
+    let apps = managed_apps::list_group(group);
+    if apps.is_empty() {
+        return Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(format!(
+                "No managed apps in group '{group}'"
+            )))
+            .attach_printable("Add apps to a group with brp_attach_app"),
+        ));
+    }
+    // One reused PID refuses the whole group, before anything is signalled
+    for app in &apps {
+        app.verify_process()
+            .map_err(|report| report_to_mcp_error(&report))?;
+    }
+    if let Some(preview) = preview_kill(&apps, &format!("group '{group}'"), force, confirmation) {
+        return Ok(preview);
+    }
+
+    let mut signalled = Vec::with_capacity(apps.len());
+    for app in &apps {
+        signalled.push(process::terminate_process(app.pid, force)?);
+    }
+    let mut results = Vec::with_capacity(apps.len());
+    for (app, signalled) in apps.iter().zip(signalled) {
+        let exited = !signalled || process::wait_for_exit(app.pid, EXIT_TIMEOUT).await;
+        if exited {
+            managed_apps::remove(&app.name);
+        }
+        results.push(json!({
+            "app_name": app.name,
+            "pid": app.pid,
+            "port": app.port,
+            "exited": exited,
+        }));
+    }
+
+    let exited = results
+        .iter()
+        .filter(|result| result["exited"] == true)
+        .count();
+    let message = if exited < apps.len() {
+        format!(
+            "Killed {exited} of {} apps in group '{group}'; the others are still running after \
+             {}s. Retry with force: true",
+            apps.len(),
+            EXIT_TIMEOUT.as_secs()
+        )
+    } else {
+        format!("Killed {exited} of {} apps in group '{group}'", apps.len())
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "group": group,
+            "force": force,
+            "apps": results,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Preview the processes a kill would stop instead of killing them, unless it is confirmed
+fn preview_kill(
+    apps: &[ManagedApp],
+    target: &str,
+    force: bool,
+    confirmation: &Confirmation,
+) -> Option<CallToolResult> {
+    let pids: Vec<u32> = apps.iter().map(|app| app.pid).collect();
+    let operation = format!("kill {target} {pids:?} force={force}");
+    let reason = destructive_ops::preview_reason(&operation, confirmation)?;
+    let preview = json!({
+        "apps": apps
+            .iter()
+            .map(|app| json!({ "app_name": app.name, "pid": app.pid, "port": app.port }))
+            .collect::<Vec<_>>(),
+        "force": force,
+    });
+    Some(destructive_ops::preview_response(
+        &operation,
+        &format!("killing {target}"),
+        &reason,
+        &preview,
+    ))
+}
+
+/// Look up a managed app, failing with a hint to list them if it isn't managed
+pub fn require_managed_app(app_name: &str) -> Result<ManagedApp, McpError> {
+    managed_apps::get(app_name).ok_or_else(|| {
+        report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(format!(
+                "'{app_name}' is not managed by this server"
+            )))
+            .attach_printable("Use brp_list_launched_processes to see managed apps"),
+        )
+    })
+}
@@ -6,35 +6,57 @@ use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 use serde_json::json;
 
+use super::support::brp_readiness::{self, WaitOptions};
+use super::support::cargo_build::{self, ArtifactKey};
+use super::support::cargo_detector::BinaryInfo;
+use super::support::launch_common::LaunchOptions;
+use super::support::managed_apps::{LaunchRecord, LaunchTarget};
 use super::support::{launch_common, logging, process, scanning};
-use crate::BrpMcpService;
-use crate::constants::{
-    DEFAULT_PROFILE, PARAM_APP_NAME, PARAM_PORT, PARAM_PROFILE, PROFILE_RELEASE,
-};
+use crate::constants::{PARAM_APP_NAME, PARAM_PORT, PARAM_PROFILE, PROFILE_RELEASE};
 use crate::error::{Error, report_to_mcp_error};
+use crate::support::progress::Progress;
 use crate::support::{params, service};
+use crate::{BrpMcpService, config};
 
 pub async fn handle(
     service: &BrpMcpService,
     request: rmcp::model::CallToolRequestParam,
     context: RequestContext<RoleServer>,
 ) -> Result<CallToolResult, McpError> {
-    service::handle_with_request_and_paths(
+    let wait = WaitOptions::from_request(&request)?;
+    let progress = Progress::new(&context);
+    let app_name = params::extract_required_string(&request, PARAM_APP_NAME)?.to_string();
+
+    let result = service::handle_with_request_and_paths(
         service,
         request,
         context,
         |req, search_paths| async move {
             // Get parameters
             let app_name = params::extract_required_string(&req, PARAM_APP_NAME)?;
-            let profile = params::extract_optional_string(&req, PARAM_PROFILE, DEFAULT_PROFILE);
+            let default_profile = config::default_profile();
+            let profile = params::extract_optional_string(&req, PARAM_PROFILE, &default_profile);
             let workspace = params::extract_optional_workspace(&req);
             let port = params::extract_optional_u16_from_request(&req, PARAM_PORT)?;
+            let options = LaunchOptions::from_request(&req)?;
 
             // Launch the app
-            launch_bevy_app(app_name, profile, workspace.as_deref(), port, &search_paths)
+            launch_bevy_app(
+                app_name,
+                profile,
+                workspace.as_deref(),
+                port,
+                &options,
+                &search_paths,
+            )
         },
     )
-    .await
+    .await?;
+
+    if !wait.wait {
+        return Ok(result);
+    }
+    Ok(brp_readiness::wait_after_launch(result, &app_name, wait.timeout, &progress).await)
 }
 
 pub fn launch_bevy_app(
@@ -42,13 +64,38 @@ pub fn launch_bevy_app(
     profile: &str,
     workspace: Option<&str>,
     port: Option<u16>,
+    options: &LaunchOptions,
     search_paths: &[PathBuf],
 ) -> Result<CallToolResult, McpError> {
     // Find the app
     let app = scanning::find_required_app_with_workspace(app_name, workspace, search_paths)?;
 
+    let launch = LaunchRecord {
+        target: LaunchTarget::App,
+        profile: profile.to_string(),
+        workspace: workspace.map(String::from),
+        port,
+        options: options.clone(),
+        search_paths: search_paths.to_vec(),
+    };
+
+    // The artifact of an earlier brp_build_bevy_app has the requested features built in
+    let artifact = cargo_build::cached_artifact(&ArtifactKey::new(
+        &app.manifest_path,
+        LaunchTarget::App,
+        app_name,
+        profile,
+        options,
+    ));
+
+    // The prebuilt binary doesn't reflect requested features, so those launches go through
+    // cargo run instead
+    if options.changes_build() && artifact.is_none() {
+        return launch_with_cargo(app_name, &app, profile, port, options, launch);
+    }
+
     // Build the binary path
-    let binary_path = app.get_binary_path(profile);
+    let binary_path = artifact.unwrap_or_else(|| app.get_binary_path(profile));
 
     // Check if the binary exists
     if !binary_path.exists() {
@@ -84,10 +131,11 @@ pub fn launch_bevy_app(
     // Open log file for stdout/stderr redirection
     let log_file_for_redirect = logging::open_log_file_for_redirect(&log_file_path)?;
 
-    // Launch the binary
-    let mut cmd = Command::new(&binary_path);
+    // Launch the binary, under the wrapper if one was requested
+    let (mut cmd, artifacts) = options.wrap(Command::new(&binary_path), &log_file_path);
 
-    // Set BRP-related environment variables
+    // Set user and BRP-related environment variables
+    options.apply_env(&mut cmd);
     launch_common::set_brp_env_vars(&mut cmd, port);
 
     let pid = process::launch_detached_process(
@@ -99,9 +147,84 @@ pub fn launch_bevy_app(
     )?;
 
     // Create additional app-specific data
-    let additional_data = json!({
-        "binary_path": binary_path.display().to_string()
+    let mut additional_data = json!({
+        "binary_path": binary_path.display().to_string(),
+        "launch_options": options.to_json()
     });
+    launch_common::add_wrapper_data(&mut additional_data, options, &artifacts);
+
+    launch_common::register_launched_app(app_name, pid, &log_file_path, artifacts, launch);
+
+    Ok(launch_common::build_launch_success_response(
+        launch_common::LaunchResponseParams {
+            name: app_name,
+            name_field: "app_name",
+            pid,
+            manifest_dir,
+            profile,
+            log_file_path: &log_file_path,
+            additional_data: Some(additional_data),
+            workspace_root: Some(&app.workspace_root),
+        },
+    ))
+}
+
+/// Launch an app through `cargo run` so the requested features are built in
+fn launch_with_cargo(
+    app_name: &str,
+    app: &BinaryInfo,
+    profile: &str,
+    port: Option<u16>,
+    options: &LaunchOptions,
+    launch: LaunchRecord,
+) -> Result<CallToolResult, McpError> {
+    let manifest_dir = launch_common::validate_manifest_directory(&app.manifest_path)?;
+
+    // Build cargo command
+    let mut cmd = Command::new("cargo");
+    cmd.arg("run").arg("--bin").arg(app_name);
+    if profile == PROFILE_RELEASE {
+        cmd.arg("--release");
+    }
+    options.add_cargo_args(&mut cmd);
+
+    let cargo_command = launch_common::command_line(&cmd);
+
+    launch_common::print_launch_debug_info(app_name, "app", manifest_dir, &cargo_command, profile);
+
+    let (log_file_path, _) = logging::create_log_file(
+        app_name,
+        "App",
+        profile,
+        &PathBuf::from(&cargo_command),
+        manifest_dir,
+        port,
+    )?;
+    let log_file_for_redirect = logging::open_log_file_for_redirect(&log_file_path)?;
+
+    // Cargo runs the built app under the wrapper, if one was requested
+    let (mut cmd, artifacts) = options.wrap(cmd, &log_file_path);
+
+    // Set user and BRP-related environment variables
+    options.apply_env(&mut cmd);
+    launch_common::set_brp_env_vars(&mut cmd, port);
+
+    let pid = process::launch_detached_process(
+        &cmd,
+        manifest_dir,
+        log_file_for_redirect,
+        app_name,
+        "launch",
+    )?;
+
+    let mut additional_data = json!({
+        "command": launch_common::command_line(&cmd),
+        "launch_options": options.to_json(),
+        "note": "Cargo will build the app with the requested features before running"
+    });
+    launch_common::add_wrapper_data(&mut additional_data, options, &artifacts);
+
+    launch_common::register_launched_app(app_name, pid, &log_file_path, artifacts, launch);
 
     Ok(launch_common::build_launch_success_response(
         launch_common::LaunchResponseParams {
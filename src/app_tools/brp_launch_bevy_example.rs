@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use rmcp::model::CallToolResult;
@@ -6,12 +6,18 @@ use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 use serde_json::json;
 
+use super::support::brp_readiness::{self, WaitOptions};
+use super::support::cargo_build::{self, ArtifactKey};
+use super::support::launch_common::LaunchOptions;
+use super::support::managed_apps::{LaunchRecord, LaunchTarget};
+use super::support::process::BuildOutcome;
 use super::support::{launch_common, logging, process, scanning};
-use crate::BrpMcpService;
-use crate::constants::{
-    DEFAULT_PROFILE, PARAM_EXAMPLE_NAME, PARAM_PORT, PARAM_PROFILE, PROFILE_RELEASE,
-};
+use crate::constants::{PARAM_EXAMPLE_NAME, PARAM_PORT, PARAM_PROFILE, PROFILE_RELEASE};
+use crate::support::progress::Progress;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
 use crate::support::{params, service};
+use crate::{BrpMcpService, config};
 
 pub async fn handle(
     service: &BrpMcpService,
@@ -20,28 +26,41 @@ pub async fn handle(
 ) -> Result<CallToolResult, McpError> {
     // Get parameters
     let example_name = params::extract_required_string(&request, PARAM_EXAMPLE_NAME)?;
-    let profile = params::extract_optional_string(&request, PARAM_PROFILE, DEFAULT_PROFILE);
+    let default_profile = config::default_profile();
+    let profile = params::extract_optional_string(&request, PARAM_PROFILE, &default_profile);
     let workspace = params::extract_optional_workspace(&request);
     let port = params::extract_optional_u16_from_request(&request, PARAM_PORT)?;
+    let options = LaunchOptions::from_request(&request)?;
+    let wait = WaitOptions::from_request(&request)?;
+    let progress = Progress::new(&context);
 
     // Fetch current roots
     let search_paths = service::fetch_roots_and_get_paths(service, context).await?;
 
     // Launch the example
-    launch_bevy_example(
+    let result = launch_bevy_example(
         example_name,
         profile,
         workspace.as_deref(),
         port,
+        &options,
         &search_paths,
     )
+    .await?;
+
+    if !wait.wait {
+        return Ok(result);
+    }
+    Ok(brp_readiness::wait_after_launch(result, example_name, wait.timeout, &progress).await)
 }
 
-pub fn launch_bevy_example(
+/// Launch an example, building it first so build errors can be reported
+pub async fn launch_bevy_example(
     example_name: &str,
     profile: &str,
     workspace: Option<&str>,
     port: Option<u16>,
+    options: &LaunchOptions,
     search_paths: &[PathBuf],
 ) -> Result<CallToolResult, McpError> {
     // Find the example
@@ -51,23 +70,27 @@ pub fn launch_bevy_example(
     // Get the manifest directory (parent of Cargo.toml)
     let manifest_dir = launch_common::validate_manifest_directory(&example.manifest_path)?;
 
+    // The artifact of an earlier brp_build_bevy_example runs directly, skipping cargo
+    let artifact = cargo_build::cached_artifact(&ArtifactKey::new(
+        &example.manifest_path,
+        LaunchTarget::Example,
+        example_name,
+        profile,
+        options,
+    ));
+    let cmd = artifact.as_ref().map_or_else(
+        || cargo_command(&["run", "--example", example_name], profile, options),
+        Command::new,
+    );
+
     // Build cargo command string for debug output
-    let cargo_command = format!(
-        "cargo run --example {example_name} {}",
-        if profile == PROFILE_RELEASE {
-            "--release"
-        } else {
-            ""
-        }
-    )
-    .trim()
-    .to_string();
+    let command_line = launch_common::command_line(&cmd);
 
     launch_common::print_launch_debug_info(
         example_name,
         "example",
         manifest_dir,
-        &cargo_command,
+        &command_line,
         profile,
     );
     eprintln!("Package: {}", example.package_name);
@@ -78,7 +101,7 @@ pub fn launch_bevy_example(
         example_name,
         "Example",
         profile,
-        &PathBuf::from(&cargo_command),
+        &PathBuf::from(&command_line),
         manifest_dir,
         port,
     )?;
@@ -89,19 +112,30 @@ pub fn launch_bevy_example(
         &format!("Package: {}\n", example.package_name),
     )?;
 
+    // Build before running, so the build's output can be streamed and its errors returned
+    if artifact.is_none() {
+        let mut build_cmd = cargo_command(&["build", "--example", example_name], profile, options);
+        options.apply_env(&mut build_cmd);
+        let build =
+            process::run_build(&build_cmd, manifest_dir, &log_file_path, example_name).await?;
+        if !build.success {
+            return Ok(build_failure_response(
+                example_name,
+                &launch_common::command_line(&build_cmd),
+                &log_file_path,
+                &build,
+            ));
+        }
+    }
+
     // Open log file for stdout/stderr redirection
     let log_file_for_redirect = logging::open_log_file_for_redirect(&log_file_path)?;
 
-    // Build cargo command
-    let mut cmd = Command::new("cargo");
-    cmd.arg("run").arg("--example").arg(example_name);
-
-    // Add profile flag if release
-    if profile == PROFILE_RELEASE {
-        cmd.arg("--release");
-    }
+    // Run the example under the wrapper, if one was requested
+    let (mut cmd, artifacts) = options.wrap(cmd, &log_file_path);
 
-    // Set BRP-related environment variables
+    // Set user and BRP-related environment variables
+    options.apply_env(&mut cmd);
     launch_common::set_brp_env_vars(&mut cmd, port);
 
     // Launch the process
@@ -113,11 +147,27 @@ pub fn launch_bevy_example(
         "spawn",
     )?;
 
+    let launch = LaunchRecord {
+        target: LaunchTarget::Example,
+        profile: profile.to_string(),
+        workspace: workspace.map(String::from),
+        port,
+        options: options.clone(),
+        search_paths: search_paths.to_vec(),
+    };
     // Create additional example-specific data
-    let additional_data = json!({
+    let mut additional_data = json!({
         "package_name": example.package_name,
-        "note": "Cargo will build the example if needed before running"
+        "launch_options": options.to_json(),
+        "note": if artifact.is_some() {
+            "Ran the artifact of an earlier brp_build_bevy_example with the same profile and features"
+        } else {
+            "The example was built before running"
+        }
     });
+    launch_common::add_wrapper_data(&mut additional_data, options, &artifacts);
+
+    launch_common::register_launched_app(example_name, pid, &log_file_path, artifacts, launch);
 
     // Get workspace info
     let workspace_root =
@@ -136,3 +186,44 @@ pub fn launch_bevy_example(
         },
     ))
 }
+
+/// A cargo command for an example with the profile and feature flags of a launch
+fn cargo_command(args: &[&str], profile: &str, options: &LaunchOptions) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args(args);
+    if profile == PROFILE_RELEASE {
+        cmd.arg("--release");
+    }
+    options.add_cargo_args(&mut cmd);
+    cmd
+}
+
+/// Response to a launch whose build failed, with the end of the build output
+fn build_failure_response(
+    example_name: &str,
+    build_command: &str,
+    log_file_path: &Path,
+    build: &BuildOutcome,
+) -> CallToolResult {
+    let response = ResponseBuilder::error()
+        .message(format!(
+            "Failed to build example '{example_name}'; see build_errors or {}",
+            log_file_path.display()
+        ))
+        .data(json!({
+            "example_name": example_name,
+            "command": build_command,
+            "exit_code": build.exit_code,
+            "log_file": log_file_path.display().to_string(),
+            "build_errors": build.error_tail,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+    json_response_to_result(&response)
+}
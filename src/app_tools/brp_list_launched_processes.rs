@@ -0,0 +1,57 @@
+use rmcp::RoleServer;
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use serde_json::json;
+
+use super::support::managed_apps;
+use crate::BrpMcpService;
+use crate::constants::PARAM_GROUP;
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: &CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> CallToolResult {
+    let group = params::extract_optional_string(request, PARAM_GROUP, "");
+    let apps = if group.is_empty() {
+        managed_apps::list()
+    } else {
+        managed_apps::list_group(group)
+    };
+    let processes: Vec<serde_json::Value> = apps
+        .into_iter()
+        .map(|app| {
+            let running = app.is_running();
+            let mut process = json!(app);
+            process["running"] = json!(running);
+            process
+        })
+        .collect();
+    let running = processes
+        .iter()
+        .filter(|process| process["running"] == true)
+        .count();
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Found {} managed processes ({running} running)",
+            processes.len()
+        ))
+        .data(json!({
+            "processes": processes,
+            "count": processes.len()
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    json_response_to_result(&response)
+}
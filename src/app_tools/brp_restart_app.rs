@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+
+use super::brp_kill_process::require_managed_app;
+use super::brp_launch_bevy_app::launch_bevy_app;
+use super::brp_launch_bevy_example::launch_bevy_example;
+use super::support::managed_apps::{LaunchRecord, LaunchTarget, ManagedApp};
+use super::support::process;
+use crate::BrpMcpService;
+use crate::brp_tools::support::destructive_ops::{self, Confirmation};
+use crate::constants::PARAM_APP_NAME;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+
+/// How long to wait for the old process to exit before launching again
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    mut request: rmcp::model::CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let confirmation = destructive_ops::take_confirmation(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let app_name = params::extract_required_string(&request, PARAM_APP_NAME)?;
+
+    let app = require_managed_app(app_name)?;
+    let Some(launch) = &app.launch else {
+        return Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(format!(
+                "'{app_name}' was attached rather than launched and cannot be restarted"
+            )))
+            .attach_printable("Launch it with brp_launch_bevy_app or brp_launch_bevy_example"),
+        ));
+    };
+    app.verify_process()
+        .map_err(|report| report_to_mcp_error(&report))?;
+    if let Some(preview) = preview_restart(&app, launch, &confirmation) {
+        return Ok(preview);
+    }
+
+    // A hung app won't react to SIGTERM, so the old process is always killed outright
+    if process::terminate_process(app.pid, true)?
+        && !process::wait_for_exit(app.pid, EXIT_TIMEOUT).await
+    {
+        return Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(format!(
+                "'{app_name}' (PID: {}) did not exit",
+                app.pid
+            )))
+            .attach_printable(format!("Waited {}s after SIGKILL", EXIT_TIMEOUT.as_secs())),
+        ));
+    }
+
+    // Launching registers the new process under the same name, replacing the old entry
+    match launch.target {
+        LaunchTarget::App => launch_bevy_app(
+            app_name,
+            &launch.profile,
+            launch.workspace.as_deref(),
+            launch.port,
+            &launch.options,
+            &launch.search_paths,
+        ),
+        LaunchTarget::Example => {
+            launch_bevy_example(
+                app_name,
+                &launch.profile,
+                launch.workspace.as_deref(),
+                launch.port,
+                &launch.options,
+                &launch.search_paths,
+            )
+            .await
+        }
+    }
+}
+
+/// Preview the process a restart would kill instead of restarting it, unless it is confirmed
+fn preview_restart(
+    app: &ManagedApp,
+    launch: &LaunchRecord,
+    confirmation: &Confirmation,
+) -> Option<CallToolResult> {
+    let operation = format!("restart {} {}", app.name, app.pid);
+    let reason = destructive_ops::preview_reason(&operation, confirmation)?;
+    let preview = json!({
+        "app_name": app.name,
+        "pid": app.pid,
+        "port": app.port,
+        "target": launch.target,
+        "profile": launch.profile,
+    });
+    Some(destructive_ops::preview_response(
+        &operation,
+        &format!("restarting '{}' (PID: {})", app.name, app.pid),
+        &reason,
+        &preview,
+    ))
+}
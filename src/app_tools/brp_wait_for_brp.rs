@@ -0,0 +1,66 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+
+use super::brp_kill_process::require_managed_app;
+use super::support::brp_readiness;
+use crate::constants::{PARAM_APP_NAME, PARAM_PORT};
+use crate::error::ErrorCode;
+use crate::support::params;
+use crate::support::progress::Progress;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::{BrpMcpService, config};
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let timeout = brp_readiness::wait_timeout(&request)?;
+    let port = params::extract_optional_u16_from_request(&request, PARAM_PORT)?;
+
+    // A managed app supplies the port, and its PID ends the wait early if it exits
+    let app = params::extract_any_value(&request, PARAM_APP_NAME)
+        .and_then(serde_json::Value::as_str)
+        .map(require_managed_app)
+        .transpose()?;
+
+    let port = port
+        .or_else(|| app.as_ref().and_then(|app| app.port))
+        .unwrap_or_else(config::default_port);
+    let readiness = brp_readiness::wait_for_brp(
+        port,
+        app.as_ref().map(|app| app.pid),
+        app.as_ref().and_then(|app| app.log_file.as_deref()),
+        timeout,
+        &Progress::new(&context),
+    )
+    .await;
+
+    let response = if readiness.ready {
+        ResponseBuilder::success()
+            .message(readiness.summary())
+            .data(&readiness)
+            .map_or_else(
+                |_| {
+                    ResponseBuilder::error()
+                        .message("Failed to serialize response data")
+                        .build()
+                },
+                ResponseBuilder::build,
+            )
+    } else {
+        let error_code = if readiness.process_exited {
+            ErrorCode::ProcessError
+        } else {
+            ErrorCode::Timeout
+        };
+        ResponseBuilder::error()
+            .message(readiness.summary())
+            .error_code(error_code)
+            .details(serde_json::json!(readiness))
+            .build()
+    };
+    Ok(json_response_to_result(&response))
+}
@@ -1,10 +1,19 @@
 // App tools module
 
+pub mod brp_analyze_bevy_project;
+pub mod brp_attach_app;
+pub mod brp_build_bevy_app;
+pub mod brp_build_bevy_example;
+pub mod brp_enable_brp_in_project;
 pub mod brp_extras_shutdown;
+pub mod brp_kill_process;
 pub mod brp_launch_bevy_app;
 pub mod brp_launch_bevy_example;
 pub mod brp_list_bevy_apps;
 pub mod brp_list_bevy_examples;
 pub mod brp_list_brp_apps;
+pub mod brp_list_launched_processes;
+pub mod brp_restart_app;
+pub mod brp_wait_for_brp;
 
 pub mod support;
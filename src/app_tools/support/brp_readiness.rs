@@ -0,0 +1,351 @@
+//! Waiting for an app's BRP server to answer
+//!
+//! A launch returns as soon as the process starts, but the app may still be building (when
+//! launched through cargo) or setting up its `RemotePlugin`. Waiting polls `rpc.discover` until
+//! it answers, the process exits or the timeout passes, sending a progress notification after
+//! every failed attempt. Lines appended to the app's log file meanwhile, such as cargo's build
+//! output, are forwarded to the client as log messages.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use rmcp::Error as McpError;
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+
+use super::managed_apps;
+use crate::brp_tools::support::brp_client::{BrpResult, current_host, execute_brp_method};
+use crate::brp_tools::support::request_policy::{self, RequestPolicy};
+use crate::config;
+use crate::constants::{PARAM_WAIT_FOR_BRP, PARAM_WAIT_TIMEOUT_MS};
+use crate::error::ErrorCode;
+use crate::support::params;
+use crate::support::progress::Progress;
+use crate::support::response::{JsonResponse, ResponseStatus};
+use crate::support::serialization::{json_response_to_result, result_to_json};
+use crate::tools::BRP_METHOD_RPC_DISCOVER;
+
+/// How long to wait when no timeout is given
+pub const DEFAULT_WAIT_TIMEOUT_MS: u64 = 60_000;
+
+/// Longest wait allowed
+pub const MAX_WAIT_TIMEOUT_MS: u64 = 600_000;
+
+/// Pause between attempts
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Time an attempt may take, from connecting to the `rpc.discover` answer
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Logger of the output lines forwarded while waiting
+const OUTPUT_LOGGER: &str = "bevy_brp_mcp::launch";
+
+/// Response field holding the outcome of a wait after a launch
+const FIELD_BRP_READINESS: &str = "brp_readiness";
+
+/// Response field telling whether BRP answered during a wait after a launch
+const FIELD_BRP_READY: &str = "brp_ready";
+
+/// Whether and how long a launch waits for BRP
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitOptions {
+    pub wait:    bool,
+    pub timeout: Duration,
+}
+
+impl WaitOptions {
+    /// Extract the `wait_for_brp` and `wait_timeout_ms` parameters
+    pub fn from_request(request: &CallToolRequestParam) -> Result<Self, McpError> {
+        let wait = params::extract_any_value(request, PARAM_WAIT_FOR_BRP)
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        Ok(Self {
+            wait,
+            timeout: wait_timeout(request)?,
+        })
+    }
+}
+
+/// Extract the `wait_timeout_ms` parameter
+pub fn wait_timeout(request: &CallToolRequestParam) -> Result<Duration, McpError> {
+    let timeout_ms =
+        params::extract_optional_number(request, PARAM_WAIT_TIMEOUT_MS, DEFAULT_WAIT_TIMEOUT_MS)?;
+    Ok(Duration::from_millis(timeout_ms.min(MAX_WAIT_TIMEOUT_MS)))
+}
+
+/// Outcome of waiting for BRP
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Readiness {
+    pub ready:            bool,
+    pub port:             u16,
+    /// Time until `rpc.discover` answered
+    pub time_to_ready_ms: Option<u64>,
+    pub waited_ms:        u64,
+    pub attempts:         u32,
+    /// Whether the process exited before BRP answered
+    pub process_exited:   bool,
+    /// Whether the client cancelled the call
+    pub cancelled:        bool,
+    pub log_file:         Option<String>,
+}
+
+impl Readiness {
+    /// One sentence describing the outcome
+    pub fn summary(&self) -> String {
+        let log_hint = self
+            .log_file
+            .as_ref()
+            .map_or_else(String::new, |log_file| format!("; see {log_file}"));
+        if self.ready {
+            format!("BRP ready on port {} after {}ms", self.port, self.waited_ms)
+        } else if self.process_exited {
+            format!(
+                "The process exited before BRP answered on port {}{log_hint}",
+                self.port
+            )
+        } else if self.cancelled {
+            format!(
+                "Stopped waiting for BRP on port {} after {}ms",
+                self.port, self.waited_ms
+            )
+        } else {
+            format!(
+                "BRP did not answer on port {} within {}ms{log_hint}",
+                self.port, self.waited_ms
+            )
+        }
+    }
+}
+
+/// Poll a port until its BRP server answers `rpc.discover`
+///
+/// With a PID, waiting stops early when the process exits.
+pub async fn wait_for_brp(
+    port: u16,
+    pid: Option<u32>,
+    log_file: Option<&Path>,
+    timeout: Duration,
+    progress: &Progress,
+) -> Readiness {
+    let started = Instant::now();
+    let mut attempts = 0;
+    let mut output = log_file.map(LogTail::new);
+    loop {
+        attempts += 1;
+        let ready = brp_answers(port).await;
+        let waited = started.elapsed();
+        let process_exited = !ready && pid.is_some_and(|pid| !managed_apps::is_pid_running(pid));
+        let cancelled = progress.is_cancelled();
+        if ready || process_exited || cancelled || waited >= timeout {
+            let waited_ms = u64::try_from(waited.as_millis()).unwrap_or(u64::MAX);
+            return Readiness {
+                ready,
+                port,
+                time_to_ready_ms: ready.then_some(waited_ms),
+                waited_ms,
+                attempts,
+                process_exited,
+                cancelled,
+                log_file: log_file.map(|path| path.display().to_string()),
+            };
+        }
+        for line in output.iter_mut().flat_map(LogTail::new_lines) {
+            progress.log(OUTPUT_LOGGER, &line).await;
+        }
+        progress.report(millis(waited), Some(millis(timeout))).await;
+        tokio::time::sleep(POLL_INTERVAL.min(timeout.saturating_sub(waited))).await;
+    }
+}
+
+fn millis(duration: Duration) -> u32 {
+    u32::try_from(duration.as_millis()).unwrap_or(u32::MAX)
+}
+
+/// Reads the lines an app, or cargo building it, appends to its log file
+struct LogTail<'a> {
+    path:   &'a Path,
+    offset: u64,
+}
+
+impl<'a> LogTail<'a> {
+    /// Start at the current end of the file, past the header written at launch
+    fn new(path: &'a Path) -> Self {
+        Self {
+            path,
+            offset: std::fs::metadata(path).map_or(0, |metadata| metadata.len()),
+        }
+    }
+
+    /// Complete, non-empty lines appended since the last read
+    fn new_lines(&mut self) -> Vec<String> {
+        let Ok(mut file) = File::open(self.path) else {
+            return Vec::new();
+        };
+        let mut appended = Vec::new();
+        if file.seek(SeekFrom::Start(self.offset)).is_err()
+            || file.read_to_end(&mut appended).is_err()
+        {
+            return Vec::new();
+        }
+        // A partly written line is read again next time
+        let Some(end) = appended.iter().rposition(|&byte| byte == b'\n') else {
+            return Vec::new();
+        };
+        self.offset += end as u64 + 1;
+        String::from_utf8_lossy(&appended[..end])
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(ToString::to_string)
+            .collect()
+    }
+}
+
+/// Whether the BRP server on a port answers `rpc.discover`, without retries
+async fn brp_answers(port: u16) -> bool {
+    let host = current_host();
+    let host = host.trim_matches(['[', ']']);
+    // A closed port fails fast here instead of probing other hosts for a remediation
+    let connected = tokio::time::timeout(ATTEMPT_TIMEOUT, TcpStream::connect((host, port))).await;
+    if !matches!(connected, Ok(Ok(_))) {
+        return false;
+    }
+    let policy = RequestPolicy {
+        timeout: ATTEMPT_TIMEOUT,
+        retries: 0,
+        ..RequestPolicy::default()
+    };
+    let discovered = request_policy::scope(
+        policy,
+        execute_brp_method(BRP_METHOD_RPC_DISCOVER, None, Some(port)),
+    )
+    .await;
+    matches!(discovered, Ok(BrpResult::Success(_)))
+}
+
+/// Wait for a just-launched managed app and add the outcome to its launch response
+pub async fn wait_after_launch(
+    result: CallToolResult,
+    app_name: &str,
+    timeout: Duration,
+    progress: &Progress,
+) -> CallToolResult {
+    let Some(app) = managed_apps::get(app_name) else {
+        return result;
+    };
+    let readiness = wait_for_brp(
+        app.port.unwrap_or_else(config::default_port),
+        Some(app.pid),
+        app.log_file.as_deref(),
+        timeout,
+        progress,
+    )
+    .await;
+    add_readiness(result, &readiness)
+}
+
+/// Add the outcome of a wait to a launch response
+///
+/// A process that exited while waiting turns the launch into an error, since nothing is left to
+/// talk to. A process that is still running but whose BRP never answered stays a success with
+/// `brp_ready: false`, as it may still be building or starting up.
+fn add_readiness(result: CallToolResult, readiness: &Readiness) -> CallToolResult {
+    let Ok(mut response) = serde_json::from_value::<JsonResponse>(result_to_json(&result)) else {
+        return result;
+    };
+    if let Some(Value::Object(data)) = response.data.as_mut() {
+        data.insert(FIELD_BRP_READY.to_string(), json!(readiness.ready));
+        data.insert(FIELD_BRP_READINESS.to_string(), json!(readiness));
+    }
+    if readiness.process_exited {
+        response.status = ResponseStatus::Error;
+        response.error_code = Some(ErrorCode::ProcessSpawnError);
+        response.details = Some(json!(readiness));
+    }
+    response.message = format!("{}. {}", response.message, readiness.summary());
+    json_response_to_result(&response)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::support::response::ResponseBuilder;
+
+    #[test]
+    fn test_readiness_is_added_to_the_launch_response() {
+        let launched = ResponseBuilder::success()
+            .message("Successfully launched 'game' (PID: 42)")
+            .data(json!({ "app_name": "game", "pid": 42 }))
+            .map_or_else(|_| ResponseBuilder::error().build(), ResponseBuilder::build);
+        let readiness = Readiness {
+            ready:            false,
+            port:             15702,
+            time_to_ready_ms: None,
+            waited_ms:        1200,
+            attempts:         3,
+            process_exited:   true,
+            cancelled:        false,
+            log_file:         Some("/tmp/bevy_brp_mcp_game.log".to_string()),
+        };
+
+        let result = result_to_json(&add_readiness(
+            json_response_to_result(&launched),
+            &readiness,
+        ));
+        assert_eq!(result["status"], "error");
+        assert_eq!(result["error_code"], "process_spawn_error");
+        assert_eq!(result["data"]["pid"], 42);
+        assert_eq!(result["data"][FIELD_BRP_READY], false);
+        assert_eq!(result["data"][FIELD_BRP_READINESS]["process_exited"], true);
+        assert_eq!(
+            result["message"],
+            "Successfully launched 'game' (PID: 42). The process exited before BRP answered on port 15702; see /tmp/bevy_brp_mcp_game.log"
+        );
+
+        // A running app whose BRP never answered stays launched, but not ready
+        let still_running = Readiness {
+            process_exited: false,
+            ..readiness
+        };
+        let result = result_to_json(&add_readiness(
+            json_response_to_result(&launched),
+            &still_running,
+        ));
+        assert_eq!(result["status"], "success");
+        assert!(result.get("error_code").is_none());
+        assert_eq!(result["data"][FIELD_BRP_READY], false);
+        assert_eq!(
+            result["message"],
+            "Successfully launched 'game' (PID: 42). BRP did not answer on port 15702 within 1200ms; see /tmp/bevy_brp_mcp_game.log"
+        );
+    }
+
+    #[test]
+    fn test_log_tail_reads_complete_appended_lines() {
+        use std::io::Write;
+
+        let path =
+            std::env::temp_dir().join(format!("bevy_brp_mcp_tail_{}.log", std::process::id()));
+        std::fs::write(&path, "=== header ===\n").unwrap();
+        let mut tail = LogTail::new(&path);
+        assert!(tail.new_lines().is_empty());
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        write!(file, "   Compiling game v0.1.0\n\n    Finished dev").unwrap();
+        assert_eq!(tail.new_lines(), vec!["   Compiling game v0.1.0"]);
+        writeln!(file, " profile").unwrap();
+        assert_eq!(tail.new_lines(), vec!["    Finished dev profile"]);
+        assert!(tail.new_lines().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
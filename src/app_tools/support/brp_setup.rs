@@ -0,0 +1,301 @@
+//! Edits that enable the Bevy Remote Protocol in a project
+//!
+//! `Cargo.toml` is edited as text so its formatting and comments are kept: the `bevy_remote`
+//! feature is added to the `bevy` dependency in whichever form it's written, and
+//! `bevy_brp_extras` is added to `[dependencies]` on request. `main.rs` gets a marked block
+//! defining `brp_app()`, an `App` with the remote plugins added, which replaces the first
+//! `App::new()`. Every edit returns `None` when the project already has it.
+
+use super::cargo_detector::BEVY_REMOTE_FEATURE;
+use crate::error::{Error, Result};
+
+/// First line of the block added to `main.rs`
+pub const SETUP_BEGIN: &str = "// >>> bevy_brp_mcp: Bevy Remote Protocol setup";
+
+/// Last line of the block added to `main.rs`
+pub const SETUP_END: &str = "// <<< bevy_brp_mcp";
+
+/// Version of `bevy_brp_extras` matching the supported Bevy version
+pub const BRP_EXTRAS_VERSION: &str = "0.1";
+
+/// Name of the function the block in `main.rs` defines
+const SETUP_FN: &str = "brp_app()";
+
+/// Constructor call the setup function replaces
+const APP_NEW: &str = "App::new()";
+
+/// Key of a `key = value` line, `None` for headers, comments and blank lines
+fn line_key(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with(['[', '#']) {
+        return None;
+    }
+    trimmed
+        .split_once('=')
+        .map(|(key, _)| key.trim())
+        .filter(|key| !key.is_empty())
+}
+
+/// Table name of a `[table]` header line
+fn header_name(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .filter(|name| !name.starts_with('['))
+        .map(str::trim)
+}
+
+/// Insert a quoted feature into the first `features = [...]` array of a text
+fn add_to_features(text: &str, feature: &str) -> Option<String> {
+    let features = text.find("features")?;
+    let open = features + text[features..].find('[')?;
+    let empty = text[open + 1..].trim_start().starts_with(']');
+    let inserted = if empty {
+        format!("\"{feature}\"")
+    } else {
+        format!("\"{feature}\", ")
+    };
+    Some(format!("{}{inserted}{}", &text[..=open], &text[open + 1..]))
+}
+
+/// The line of a `bevy = ...` dependency with the remote feature added
+fn enable_in_dependency_line(line: &str) -> Result<String> {
+    let (key, value) = line.split_once('=').unwrap_or((line, ""));
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let value = value.trim();
+    let feature_list = format!("features = [\"{BEVY_REMOTE_FEATURE}\"]");
+
+    if key.trim() == "bevy.workspace" {
+        return Ok(format!(
+            "{indent}bevy = {{ workspace = {value}, {feature_list} }}"
+        ));
+    }
+    if value.starts_with('"') {
+        return Ok(format!(
+            "{indent}bevy = {{ version = {value}, {feature_list} }}"
+        ));
+    }
+    if value.starts_with('{') {
+        if let Some(edited) = add_to_features(line, BEVY_REMOTE_FEATURE) {
+            return Ok(edited);
+        }
+        if let Some(close) = line.rfind('}') {
+            let body = line[..close].trim_end();
+            return Ok(format!("{body}, {feature_list} }}{}", &line[close + 1..]));
+        }
+    }
+    Err(error_stack::Report::new(Error::Configuration(format!(
+        "Can't add the {BEVY_REMOTE_FEATURE} feature to the bevy dependency '{}'; add it by hand",
+        line.trim()
+    ))))
+}
+
+/// `Cargo.toml` with the `bevy_remote` feature enabled on the `bevy` dependency
+pub fn enable_remote_feature(manifest: &str) -> Result<Option<String>> {
+    let mut lines: Vec<String> = manifest.lines().map(String::from).collect();
+    let mut table = "";
+    let mut edit = None;
+
+    for (index, line) in manifest.lines().enumerate() {
+        if let Some(name) = header_name(line) {
+            table = name;
+            if table == "dependencies.bevy" {
+                // Dependency written as its own table: its lines up to the next header
+                let section: Vec<&str> = manifest
+                    .lines()
+                    .skip(index + 1)
+                    .take_while(|line| header_name(line).is_none())
+                    .collect();
+                if section
+                    .iter()
+                    .any(|line| line.contains(BEVY_REMOTE_FEATURE))
+                {
+                    return Ok(None);
+                }
+                let features = section
+                    .iter()
+                    .position(|line| line_key(line) == Some("features"));
+                edit = Some(features.map_or_else(
+                    || {
+                        Some((
+                            index + 1,
+                            format!("features = [\"{BEVY_REMOTE_FEATURE}\"]"),
+                            true,
+                        ))
+                    },
+                    |offset| {
+                        let row = index + 1 + offset;
+                        add_to_features(&lines[row], BEVY_REMOTE_FEATURE)
+                            .map(|edited| (row, edited, false))
+                    },
+                ));
+                break;
+            }
+            continue;
+        }
+        if table == "dependencies" && matches!(line_key(line), Some("bevy" | "bevy.workspace")) {
+            if line.contains(BEVY_REMOTE_FEATURE) {
+                return Ok(None);
+            }
+            edit = Some(Some((index, enable_in_dependency_line(line)?, false)));
+            break;
+        }
+    }
+
+    match edit {
+        Some(Some((row, text, insert))) => {
+            if insert {
+                lines.insert(row, text);
+            } else {
+                lines[row] = text;
+            }
+            Ok(Some(join_lines(&lines, manifest)))
+        }
+        Some(None) => Err(error_stack::Report::new(Error::Configuration(format!(
+            "Can't read the features of [dependencies.bevy]; add {BEVY_REMOTE_FEATURE} by hand"
+        )))),
+        None => Err(error_stack::Report::new(Error::Configuration(
+            "No bevy dependency in [dependencies]".to_string(),
+        ))),
+    }
+}
+
+/// `Cargo.toml` with `bevy_brp_extras` added to `[dependencies]`
+pub fn add_brp_extras(manifest: &str) -> Option<String> {
+    if manifest
+        .lines()
+        .any(|line| line_key(line).is_some_and(|key| key.starts_with("bevy_brp_extras")))
+    {
+        return None;
+    }
+    let dependency = format!("bevy_brp_extras = \"{BRP_EXTRAS_VERSION}\"");
+    let mut lines: Vec<String> = manifest.lines().map(String::from).collect();
+    let header = manifest
+        .lines()
+        .position(|line| header_name(line) == Some("dependencies"));
+    if let Some(header) = header {
+        // After the last entry of the table
+        let last_entry = manifest
+            .lines()
+            .enumerate()
+            .skip(header + 1)
+            .take_while(|(_, line)| header_name(line).is_none())
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, _)| index)
+            .last()
+            .unwrap_or(header);
+        lines.insert(last_entry + 1, dependency);
+    } else {
+        lines.push(String::new());
+        lines.push("[dependencies]".to_string());
+        lines.push(dependency);
+    }
+    Some(join_lines(&lines, manifest))
+}
+
+/// `main.rs` with the setup block added and the first `App::new()` replaced by `brp_app()`
+pub fn add_setup_block(main: &str, with_extras: bool) -> Result<Option<String>> {
+    if main.contains(SETUP_BEGIN) {
+        return Ok(None);
+    }
+    let Some(call) = main.find(APP_NEW) else {
+        return Err(error_stack::Report::new(Error::Configuration(format!(
+            "No {APP_NEW} in main.rs; add the remote plugins to the app by hand"
+        ))));
+    };
+    // Include a path such as `bevy::prelude::` in front of the call
+    let start = main[..call]
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .map_or(0, |position| position + 1);
+
+    let plugins = if with_extras {
+        "    use bevy_brp_extras::BrpExtrasPlugin;\n\n    let mut app = bevy::prelude::App::new();\n    app.add_plugins(BrpExtrasPlugin);\n"
+    } else {
+        "    use bevy::remote::{RemotePlugin, http::RemoteHttpPlugin};\n\n    let mut app = bevy::prelude::App::new();\n    app.add_plugins((RemotePlugin::default(), RemoteHttpPlugin::default()));\n"
+    };
+    let block = format!(
+        "{SETUP_BEGIN}. `main` calls `{SETUP_FN}` instead of `{APP_NEW}`;\n\
+         // remove this block and call `{APP_NEW}` again to undo.\n\
+         /// An `App` with the Bevy Remote Protocol enabled\n\
+         fn brp_app() -> bevy::prelude::App {{\n{plugins}    app\n}}\n{SETUP_END}\n"
+    );
+
+    let mut patched = format!(
+        "{}{SETUP_FN}{}",
+        &main[..start],
+        &main[call + APP_NEW.len()..]
+    );
+    if !patched.ends_with('\n') {
+        patched.push('\n');
+    }
+    patched.push('\n');
+    patched.push_str(&block);
+    Ok(Some(patched))
+}
+
+/// Join edited lines, keeping whether the original text ended with a newline
+fn join_lines(lines: &[String], original: &str) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_remote_feature_is_added_to_every_dependency_form() {
+        let cases = [
+            (
+                "[dependencies]\nbevy = \"0.16\"\n",
+                "bevy = { version = \"0.16\", features = [\"bevy_remote\"] }",
+            ),
+            (
+                "[dependencies]\nbevy = { version = \"0.16\", default-features = false }\n",
+                "bevy = { version = \"0.16\", default-features = false, features = [\"bevy_remote\"] }",
+            ),
+            (
+                "[dependencies]\nbevy = { workspace = true, features = [\"dynamic_linking\"] }\n",
+                "bevy = { workspace = true, features = [\"bevy_remote\", \"dynamic_linking\"] }",
+            ),
+            (
+                "[dependencies]\nbevy.workspace = true\n",
+                "bevy = { workspace = true, features = [\"bevy_remote\"] }",
+            ),
+            (
+                "[dependencies.bevy]\nversion = \"0.16\"\n",
+                "[dependencies.bevy]\nfeatures = [\"bevy_remote\"]\nversion = \"0.16\"",
+            ),
+        ];
+        for (manifest, expected) in cases {
+            let edited = enable_remote_feature(manifest).unwrap().unwrap();
+            assert!(edited.contains(expected), "{edited}");
+        }
+        assert!(
+            enable_remote_feature(
+                "[dependencies]\nbevy = { version = \"0.16\", features = [\"bevy_remote\"] }\n"
+            )
+            .unwrap()
+            .is_none()
+        );
+        assert!(enable_remote_feature("[dev-dependencies]\nbevy = \"0.16\"\n").is_err());
+
+        let extras = add_brp_extras("[dependencies]\nbevy = \"0.16\"\n\n[features]\n").unwrap();
+        assert_eq!(
+            extras,
+            "[dependencies]\nbevy = \"0.16\"\nbevy_brp_extras = \"0.1\"\n\n[features]\n"
+        );
+
+        let main =
+            "use bevy::prelude::*;\n\nfn main() {\n    bevy::prelude::App::new().run();\n}\n";
+        let patched = add_setup_block(main, false).unwrap().unwrap();
+        assert!(patched.contains("    brp_app().run();"));
+        assert!(patched.contains("use bevy::remote::{RemotePlugin, http::RemoteHttpPlugin};"));
+        assert!(add_setup_block(&patched, false).unwrap().is_none());
+    }
+}
@@ -0,0 +1,401 @@
+//! Building apps and examples without running them
+//!
+//! Builds run `cargo build --message-format=json`: compiler diagnostics and the built artifact
+//! are read from the JSON messages on stdout, while cargo's progress lines on stderr go to the
+//! client as log messages. The artifact of a successful build is remembered, so a later launch
+//! with the same profile and features can run it directly instead of going through cargo.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Instant, SystemTime};
+
+use rmcp::Error as McpError;
+use rmcp::model::CallToolResult;
+use serde::Serialize;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use super::launch_common::{self, LaunchOptions};
+use super::managed_apps::LaunchTarget;
+use super::process::{BUILD_LOGGER, ErrorTail};
+use crate::constants::PROFILE_RELEASE;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::progress;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+
+/// Most diagnostics included in a build report
+const MAX_DIAGNOSTICS: usize = 50;
+
+/// Artifacts of successful builds
+static BUILT_ARTIFACTS: LazyLock<Mutex<HashMap<ArtifactKey, BuiltArtifact>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Identifies what a build produced: the target, its package, profile and feature flags
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArtifactKey {
+    manifest_path:       PathBuf,
+    target:              LaunchTarget,
+    name:                String,
+    profile:             String,
+    features:            Vec<String>,
+    no_default_features: bool,
+}
+
+impl ArtifactKey {
+    pub fn new(
+        manifest_path: &Path,
+        target: LaunchTarget,
+        name: &str,
+        profile: &str,
+        options: &LaunchOptions,
+    ) -> Self {
+        let mut features = options.features.clone();
+        features.sort();
+        features.dedup();
+        Self {
+            manifest_path: manifest_path.to_path_buf(),
+            target,
+            name: name.to_string(),
+            profile: profile.to_string(),
+            features,
+            no_default_features: options.no_default_features,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BuiltArtifact {
+    path:     PathBuf,
+    /// Modification time of the artifact when it was built
+    modified: SystemTime,
+}
+
+/// A compiler error or warning
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub level:    String,
+    pub message:  String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code:     Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file:     Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line:     Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column:   Option<u64>,
+    /// The diagnostic as rustc prints it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
+}
+
+/// Outcome of a build
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildReport {
+    pub success:     bool,
+    pub command:     String,
+    pub duration_ms: u64,
+    pub errors:      usize,
+    pub warnings:    usize,
+    /// Errors and warnings, up to `MAX_DIAGNOSTICS`
+    pub diagnostics: Vec<Diagnostic>,
+    /// The built executable, on success
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable:  Option<PathBuf>,
+    /// The end of cargo's own output, when a build failed without compiler errors
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub output_tail: Vec<String>,
+}
+
+/// A JSON message from cargo that a build report uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CargoMessage {
+    Diagnostic(Diagnostic),
+    Artifact {
+        name:       String,
+        executable: PathBuf,
+    },
+}
+
+/// Build a target, remembering its artifact on success
+pub async fn build(
+    key: &ArtifactKey,
+    manifest_dir: &Path,
+    options: &LaunchOptions,
+) -> Result<BuildReport, McpError> {
+    let mut std_cmd = std::process::Command::new("cargo");
+    std_cmd
+        .arg("build")
+        .arg(match key.target {
+            LaunchTarget::App => "--bin",
+            LaunchTarget::Example => "--example",
+        })
+        .arg(&key.name)
+        .arg("--message-format=json");
+    if key.profile == PROFILE_RELEASE {
+        std_cmd.arg("--release");
+    }
+    options.add_cargo_args(&mut std_cmd);
+    let command = launch_common::command_line(&std_cmd);
+    options.apply_env(&mut std_cmd);
+
+    let mut cmd = tokio::process::Command::from(std_cmd);
+    cmd.current_dir(manifest_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let started = Instant::now();
+    let mut child = cmd.spawn().map_err(|e| {
+        report_to_mcp_error(
+            &error_stack::Report::new(e)
+                .change_context(Error::ProcessSpawn(format!(
+                    "Failed to build '{}'",
+                    key.name
+                )))
+                .attach_printable(format!("Command: {command}"))
+                .attach_printable(format!("Working directory: {}", manifest_dir.display())),
+        )
+    })?;
+
+    let (messages, output_tail) = tokio::join!(
+        read_messages(child.stdout.take()),
+        forward_output(child.stderr.take())
+    );
+    let status =
+        child.wait().await.map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(e).change_context(
+                Error::ProcessManagement(format!("Failed to wait for the build of '{}'", key.name)),
+            ))
+        })?;
+
+    let report = build_report(
+        key,
+        command,
+        status.success(),
+        messages,
+        output_tail,
+        started.elapsed().as_millis(),
+    );
+    if let Some(executable) = &report.executable {
+        remember(key, executable);
+    }
+    Ok(report)
+}
+
+/// Response describing a build, with the target's name under `name_field`
+pub fn build_response(name: &str, name_field: &str, report: &BuildReport) -> CallToolResult {
+    let builder = if report.success {
+        ResponseBuilder::success().message(format!(
+            "Built '{name}' in {}ms with {} warnings",
+            report.duration_ms, report.warnings
+        ))
+    } else {
+        ResponseBuilder::error().message(format!(
+            "Failed to build '{name}' with {} errors",
+            report.errors
+        ))
+    };
+    let mut data = json!(report);
+    data[name_field] = json!(name);
+    let response = builder.data(data).map_or_else(
+        |_| {
+            ResponseBuilder::error()
+                .message("Failed to serialize response data")
+                .build()
+        },
+        ResponseBuilder::build,
+    );
+    json_response_to_result(&response)
+}
+
+/// The artifact of an earlier build with the same key, unless it has been rebuilt since
+pub fn cached_artifact(key: &ArtifactKey) -> Option<PathBuf> {
+    let artifact = BUILT_ARTIFACTS.lock().ok()?.get(key).cloned()?;
+    let modified = std::fs::metadata(&artifact.path).ok()?.modified().ok()?;
+    (modified == artifact.modified).then_some(artifact.path)
+}
+
+fn remember(key: &ArtifactKey, executable: &Path) {
+    let Ok(modified) = std::fs::metadata(executable).and_then(|metadata| metadata.modified())
+    else {
+        return;
+    };
+    if let Ok(mut artifacts) = BUILT_ARTIFACTS.lock() {
+        artifacts.insert(
+            key.clone(),
+            BuiltArtifact {
+                path: executable.to_path_buf(),
+                modified,
+            },
+        );
+    }
+}
+
+/// Read cargo's JSON messages, counting built artifacts as progress
+async fn read_messages(stdout: Option<impl AsyncRead + Unpin>) -> Vec<CargoMessage> {
+    let mut messages = Vec::new();
+    let Some(stdout) = stdout else {
+        return messages;
+    };
+    let mut lines = BufReader::new(stdout).lines();
+    let mut artifacts = 0;
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.contains("\"reason\":\"compiler-artifact\"") {
+            artifacts += 1;
+            progress::report(artifacts, None).await;
+        }
+        messages.extend(parse_message(&line));
+    }
+    messages
+}
+
+/// Send cargo's progress lines to the client, keeping the end of them
+async fn forward_output(stderr: Option<impl AsyncRead + Unpin>) -> ErrorTail {
+    let mut tail = ErrorTail::default();
+    let Some(stderr) = stderr else {
+        return tail;
+    };
+    let progress = progress::current();
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Some(progress) = &progress {
+            progress.log(BUILD_LOGGER, &line).await;
+        }
+        tail.push(line);
+    }
+    tail
+}
+
+/// Parse one line of cargo's JSON output
+fn parse_message(line: &str) -> Option<CargoMessage> {
+    let message: Value = serde_json::from_str(line).ok()?;
+    match message["reason"].as_str()? {
+        "compiler-message" => {
+            let diagnostic = &message["message"];
+            let level = diagnostic["level"].as_str()?;
+            if !matches!(level, "error" | "warning") {
+                return None;
+            }
+            let span = diagnostic["spans"]
+                .as_array()
+                .and_then(|spans| spans.iter().find(|span| span["is_primary"] == true));
+            Some(CargoMessage::Diagnostic(Diagnostic {
+                level:    level.to_string(),
+                message:  diagnostic["message"].as_str()?.to_string(),
+                code:     diagnostic["code"]["code"].as_str().map(String::from),
+                file:     span.and_then(|span| span["file_name"].as_str().map(String::from)),
+                line:     span.and_then(|span| span["line_start"].as_u64()),
+                column:   span.and_then(|span| span["column_start"].as_u64()),
+                rendered: diagnostic["rendered"].as_str().map(String::from),
+            }))
+        }
+        "compiler-artifact" => Some(CargoMessage::Artifact {
+            name:       message["target"]["name"].as_str()?.to_string(),
+            executable: PathBuf::from(message["executable"].as_str()?),
+        }),
+        _ => None,
+    }
+}
+
+/// Summarize a finished build from its messages
+fn build_report(
+    key: &ArtifactKey,
+    command: String,
+    success: bool,
+    messages: Vec<CargoMessage>,
+    output_tail: ErrorTail,
+    duration_ms: u128,
+) -> BuildReport {
+    let mut diagnostics = Vec::new();
+    let mut executable = None;
+    for message in messages {
+        match message {
+            CargoMessage::Diagnostic(diagnostic) => diagnostics.push(diagnostic),
+            CargoMessage::Artifact {
+                name,
+                executable: path,
+            } if name == key.name => {
+                executable = Some(path);
+            }
+            CargoMessage::Artifact { .. } => {}
+        }
+    }
+    let errors = diagnostics.iter().filter(|d| d.level == "error").count();
+    let warnings = diagnostics.len() - errors;
+    // Errors come first, so they survive the cap
+    diagnostics.sort_by_key(|diagnostic| diagnostic.level != "error");
+    diagnostics.truncate(MAX_DIAGNOSTICS);
+
+    BuildReport {
+        success,
+        command,
+        duration_ms: u64::try_from(duration_ms).unwrap_or(u64::MAX),
+        errors,
+        warnings,
+        diagnostics,
+        executable: executable.filter(|_| success),
+        output_tail: if success || errors > 0 {
+            Vec::new()
+        } else {
+            output_tail.into_lines()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_build_report_from_cargo_messages() {
+        let lines = [
+            r#"{"reason":"compiler-artifact","target":{"name":"bevy_ecs","kind":["lib"]},"executable":null}"#,
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused variable: `speed`","code":{"code":"unused_variables"},"spans":[{"file_name":"src/main.rs","line_start":12,"column_start":9,"is_primary":true}],"rendered":"warning: unused variable: `speed`\n"}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"mismatched types","code":{"code":"E0308"},"spans":[{"file_name":"src/main.rs","line_start":3,"column_start":1,"is_primary":false},{"file_name":"src/main.rs","line_start":20,"column_start":5,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"note","message":"aborting","spans":[]}}"#,
+            r#"{"reason":"compiler-artifact","target":{"name":"game","kind":["bin"]},"executable":"/work/target/debug/game"}"#,
+            r#"{"reason":"build-finished","success":true}"#,
+            "not json",
+        ];
+        let messages: Vec<CargoMessage> = lines.iter().filter_map(|l| parse_message(l)).collect();
+        assert_eq!(messages.len(), 3);
+
+        let key = ArtifactKey::new(
+            Path::new("/work/Cargo.toml"),
+            LaunchTarget::App,
+            "game",
+            "debug",
+            &LaunchOptions::default(),
+        );
+        let report = build_report(
+            &key,
+            "cargo build --bin game".to_string(),
+            false,
+            messages.clone(),
+            ErrorTail::default(),
+            1500,
+        );
+        assert_eq!((report.errors, report.warnings), (1, 1));
+        assert_eq!(report.diagnostics[0].code.as_deref(), Some("E0308"));
+        assert_eq!(report.diagnostics[0].line, Some(20));
+        assert_eq!(report.executable, None);
+
+        let report = build_report(
+            &key,
+            "cargo build --bin game".to_string(),
+            true,
+            messages,
+            ErrorTail::default(),
+            1500,
+        );
+        assert_eq!(
+            report.executable,
+            Some(PathBuf::from("/work/target/debug/game"))
+        );
+    }
+}
@@ -4,6 +4,13 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use cargo_metadata::{Metadata, MetadataCommand, Package};
+use serde::Serialize;
+
+/// Bevy's cargo feature that provides the remote protocol
+pub const BEVY_REMOTE_FEATURE: &str = "bevy_remote";
+
+/// Name of the crate providing the extra BRP methods
+const BRP_EXTRAS_CRATE: &str = "bevy_brp_extras";
 
 /// Information about a binary target
 #[derive(Debug, Clone)]
@@ -37,6 +44,82 @@ pub struct ExampleInfo {
     pub manifest_path: PathBuf,
 }
 
+/// Bevy setup of a workspace, as resolved by cargo
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectAnalysis {
+    /// Workspace root
+    pub workspace_root:     PathBuf,
+    /// Whether the workspace has a `Cargo.lock`
+    pub cargo_lock:         bool,
+    /// Resolved version of the `bevy` crate
+    pub bevy_version:       Option<String>,
+    /// Whether `bevy` is resolved with the `bevy_remote` feature
+    pub bevy_remote:        bool,
+    /// Resolved version of `bevy_brp_extras`, if a member depends on it
+    pub brp_extras_version: Option<String>,
+    /// Workspace members depending on Bevy
+    pub packages:           Vec<PackageAnalysis>,
+}
+
+/// Bevy setup of one workspace member
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageAnalysis {
+    /// Package name
+    pub name:             String,
+    /// Path to the package's Cargo.toml
+    pub manifest_path:    PathBuf,
+    /// Whether the sources import `RemotePlugin` or `BrpExtrasPlugin`
+    pub uses_brp_plugins: bool,
+    /// Binary targets
+    pub binaries:         Vec<String>,
+    /// Example targets
+    pub examples:         Vec<String>,
+}
+
+impl ProjectAnalysis {
+    /// What keeps the workspace from being launched and inspected over BRP
+    pub fn issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.packages.is_empty() {
+            issues.push("No workspace member depends on bevy".to_string());
+            return issues;
+        }
+        if !self.bevy_remote {
+            issues.push(format!(
+                "bevy is resolved without the {BEVY_REMOTE_FEATURE} feature; add it to the bevy dependency's features"
+            ));
+        }
+        if !self.packages.iter().any(|package| package.uses_brp_plugins) {
+            issues.push(
+                "No package imports RemotePlugin or BrpExtrasPlugin; add one of them to the app"
+                    .to_string(),
+            );
+        }
+        if self
+            .packages
+            .iter()
+            .all(|package| package.binaries.is_empty() && package.examples.is_empty())
+        {
+            issues.push("No binary or example to launch".to_string());
+        }
+        if !self.cargo_lock {
+            issues.push(
+                "No Cargo.lock; versions are resolved from scratch on the first build".to_string(),
+            );
+        }
+        issues
+    }
+
+    /// Whether an app of the workspace can be launched and reached over BRP
+    pub fn is_ready(&self) -> bool {
+        self.bevy_remote
+            && self.packages.iter().any(|package| {
+                package.uses_brp_plugins
+                    && !(package.binaries.is_empty() && package.examples.is_empty())
+            })
+    }
+}
+
 /// Detects binary targets in a project or workspace
 pub struct CargoDetector {
     metadata: Metadata,
@@ -130,6 +213,76 @@ impl CargoDetector {
             .collect()
     }
 
+    /// Resolved version of the first package with the given name
+    fn resolved_version(&self, name: &str) -> Option<String> {
+        self.metadata
+            .packages
+            .iter()
+            .find(|package| package.name.as_str() == name)
+            .map(|package| package.version.to_string())
+    }
+
+    /// Whether `bevy` is resolved with the `bevy_remote` feature
+    fn bevy_remote_enabled(&self) -> bool {
+        self.metadata.resolve.as_ref().is_some_and(|resolve| {
+            resolve.nodes.iter().any(|node| {
+                self.metadata
+                    .packages
+                    .iter()
+                    .any(|package| package.id == node.id && package.name.as_str() == "bevy")
+                    && node
+                        .features
+                        .iter()
+                        .any(|feature| feature.as_str() == BEVY_REMOTE_FEATURE)
+            })
+        })
+    }
+
+    /// Report the Bevy version, BRP support and launchable targets of the workspace
+    pub fn analyze(&self) -> ProjectAnalysis {
+        let workspace_root: PathBuf = self.metadata.workspace_root.clone().into();
+        let packages: Vec<PackageAnalysis> = self
+            .find_packages_with_filter(Self::bevy_app_filter)
+            .map(|package| {
+                let targets = |is_kind: fn(&cargo_metadata::Target) -> bool| {
+                    package
+                        .targets
+                        .iter()
+                        .filter(|target| is_kind(target))
+                        .map(|target| target.name.clone())
+                        .collect()
+                };
+                PackageAnalysis {
+                    name:             package.name.to_string(),
+                    manifest_path:    package.manifest_path.clone().into(),
+                    uses_brp_plugins: Self::package_uses_brp_plugins(package),
+                    binaries:         targets(cargo_metadata::Target::is_bin),
+                    examples:         targets(cargo_metadata::Target::is_example),
+                }
+            })
+            .collect();
+        let uses_extras = self
+            .find_packages_with_filter(|package| {
+                package
+                    .dependencies
+                    .iter()
+                    .any(|dep| dep.name == BRP_EXTRAS_CRATE)
+            })
+            .next()
+            .is_some();
+
+        ProjectAnalysis {
+            cargo_lock: workspace_root.join("Cargo.lock").exists(),
+            workspace_root,
+            bevy_version: self.resolved_version("bevy"),
+            bevy_remote: self.bevy_remote_enabled(),
+            brp_extras_version: uses_extras
+                .then(|| self.resolved_version(BRP_EXTRAS_CRATE))
+                .flatten(),
+            packages,
+        }
+    }
+
     fn package_depends_on_bevy(package: &Package) -> bool {
         // Check direct dependencies (including workspace dependencies)
         package.dependencies.iter().any(|dep| dep.name == "bevy")
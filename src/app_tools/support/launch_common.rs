@@ -2,13 +2,21 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use rmcp::Error as McpError;
-use rmcp::model::CallToolResult;
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use serde::Serialize;
 use serde_json::{Value, json};
 
+use super::launch_wrapper::LaunchWrapper;
+use super::managed_apps::{self, AppOrigin, LaunchRecord, ManagedApp};
+use crate::config;
+use crate::constants::{
+    LOG_FORMAT_JSON, LOG_FORMAT_TEXT, PARAM_ENV, PARAM_FEATURES, PARAM_LOG_FORMAT,
+    PARAM_NO_DEFAULT_FEATURES, PARAM_RUST_LOG, PARAM_WRAPPER,
+};
 use crate::error::{Error, report_to_mcp_error};
-use crate::support::response;
 use crate::support::response::ResponseBuilder;
 use crate::support::serialization::json_response_to_result;
+use crate::support::{params, response};
 
 /// Parameters for building a launch success response
 pub struct LaunchResponseParams<'a> {
@@ -22,6 +30,163 @@ pub struct LaunchResponseParams<'a> {
     pub workspace_root:  Option<&'a PathBuf>,
 }
 
+/// Environment variable telling the launched app which log format to write, read by the app's
+/// `LogPlugin` setup as shown in the README
+pub const LOG_FORMAT_ENV: &str = "BRP_LOG_FORMAT";
+
+/// Cargo feature flags, extra environment variables and wrapper for a launch
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LaunchOptions {
+    /// Cargo features to enable
+    pub features:            Vec<String>,
+    /// Whether to disable the package's default features
+    pub no_default_features: bool,
+    /// Extra environment variables for the launched process; only names are serialized
+    #[serde(serialize_with = "serialize_env_names")]
+    pub env:                 Vec<(String, String)>,
+    /// `RUST_LOG` filter for the launched process
+    pub rust_log:            Option<String>,
+    /// Log format the app is asked to write, `text` or `json`
+    pub log_format:          Option<String>,
+    /// Debugger or profiler the app runs under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrapper:             Option<LaunchWrapper>,
+}
+
+fn serialize_env_names<S: serde::Serializer>(
+    env: &[(String, String)],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(env.iter().map(|(name, _)| name))
+}
+
+impl LaunchOptions {
+    /// Extract the `features`, `no_default_features`, `env`, `rust_log`, `log_format` and
+    /// `wrapper` parameters
+    pub fn from_request(request: &CallToolRequestParam) -> Result<Self, McpError> {
+        let features = params::extract_optional_string_array_from_request(request, PARAM_FEATURES)?
+            .unwrap_or_default();
+        let no_default_features = params::extract_any_value(request, PARAM_NO_DEFAULT_FEATURES)
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let env = match params::extract_any_value(request, PARAM_ENV) {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Object(vars)) => vars
+                .iter()
+                .map(|(name, value)| env_var(name, value))
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(invalid_env(
+                    "expected an object of variable names to values",
+                ));
+            }
+        };
+
+        let rust_log = Some(params::extract_optional_string(request, PARAM_RUST_LOG, ""))
+            .filter(|filter| !filter.is_empty())
+            .map(str::to_string);
+        let log_format = Some(params::extract_optional_string(
+            request,
+            PARAM_LOG_FORMAT,
+            "",
+        ))
+        .filter(|format| !format.is_empty())
+        .map(|format| {
+            if [LOG_FORMAT_TEXT, LOG_FORMAT_JSON].contains(&format) {
+                Ok(format.to_string())
+            } else {
+                Err(report_to_mcp_error(&error_stack::Report::new(
+                    Error::invalid(
+                        "parameter 'log_format'",
+                        format!("expected text or json, got '{format}'"),
+                    ),
+                )))
+            }
+        })
+        .transpose()?;
+        let wrapper =
+            LaunchWrapper::parse(params::extract_optional_string(request, PARAM_WRAPPER, ""))?;
+
+        Ok(Self {
+            features,
+            no_default_features,
+            env,
+            rust_log,
+            log_format,
+            wrapper,
+        })
+    }
+
+    /// Whether the options change how the package is built
+    pub const fn changes_build(&self) -> bool {
+        !self.features.is_empty() || self.no_default_features
+    }
+
+    /// Add `--features` / `--no-default-features` to a cargo command
+    pub fn add_cargo_args(&self, cmd: &mut Command) {
+        if !self.features.is_empty() {
+            cmd.arg("--features").arg(self.features.join(","));
+        }
+        if self.no_default_features {
+            cmd.arg("--no-default-features");
+        }
+    }
+
+    /// Set the extra environment variables on a command
+    ///
+    /// `rust_log` and `log_format` take precedence over the same variables in `env`.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        cmd.envs(self.env.iter().map(|(name, value)| (name, value)));
+        if let Some(rust_log) = &self.rust_log {
+            cmd.env("RUST_LOG", rust_log);
+        }
+        if let Some(log_format) = &self.log_format {
+            cmd.env(LOG_FORMAT_ENV, log_format);
+        }
+    }
+
+    /// Run a launch command under the wrapper, if any, returning the files the wrapper will write
+    ///
+    /// Output files are named after the log file; environment variables must be applied to the
+    /// returned command.
+    pub fn wrap(&self, cmd: Command, log_file_path: &Path) -> (Command, Vec<PathBuf>) {
+        match &self.wrapper {
+            Some(wrapper) => wrapper.wrap(cmd, log_file_path),
+            None => (cmd, Vec::new()),
+        }
+    }
+
+    /// Describe the options for the launch response; variable values are omitted
+    pub fn to_json(&self) -> Value {
+        json!(self)
+    }
+}
+
+fn env_var(name: &str, value: &Value) -> Result<(String, String), McpError> {
+    if name.is_empty() || name.contains(['=', '\0']) {
+        return Err(invalid_env(&format!(
+            "'{name}' is not a valid variable name"
+        )));
+    }
+    let value = match value {
+        Value::String(value) => value.clone(),
+        Value::Number(_) | Value::Bool(_) => value.to_string(),
+        _ => {
+            return Err(invalid_env(&format!(
+                "value of '{name}' must be a string, number or boolean"
+            )));
+        }
+    };
+    Ok((name.to_string(), value))
+}
+
+fn invalid_env(details: &str) -> McpError {
+    report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+        "parameter 'env'",
+        details,
+    )))
+}
+
 /// Validates and extracts the manifest directory from a manifest path
 pub fn validate_manifest_directory(manifest_path: &Path) -> Result<&Path, McpError> {
     manifest_path.parent().ok_or_else(|| -> McpError {
@@ -70,10 +235,10 @@ pub fn build_launch_success_response(params: LaunchResponseParams) -> CallToolRe
     });
 
     // Add any additional data specific to the launch type
-    if let Some(Value::Object(additional_map)) = params.additional_data {
-        if let Value::Object(ref mut response_map) = response_data {
-            response_map.extend(additional_map);
-        }
+    if let Some(Value::Object(additional_map)) = params.additional_data
+        && let Value::Object(ref mut response_map) = response_data
+    {
+        response_map.extend(additional_map);
     }
 
     // Add workspace info
@@ -97,6 +262,65 @@ pub fn build_launch_success_response(params: LaunchResponseParams) -> CallToolRe
     json_response_to_result(&response)
 }
 
+/// Records a launched app in the managed app registry
+///
+/// Registration failures are logged rather than failing the launch, since the process is
+/// already running at this point.
+pub fn register_launched_app(
+    name: &str,
+    pid: u32,
+    log_file_path: &Path,
+    artifacts: Vec<PathBuf>,
+    launch: LaunchRecord,
+) {
+    let mut app = ManagedApp::new(
+        name,
+        pid,
+        launch.port.or_else(|| Some(config::default_port())),
+        AppOrigin::Launched,
+    );
+    app.log_file = Some(log_file_path.to_path_buf());
+    app.artifacts = artifacts;
+    app.launch = Some(launch);
+
+    if let Err(e) = managed_apps::register(app) {
+        tracing::warn!("Failed to register launched app '{}': {}", name, e);
+    }
+}
+
+/// Adds the wrapper and the files it will write to a launch response's data
+pub fn add_wrapper_data(data: &mut Value, options: &LaunchOptions, artifacts: &[PathBuf]) {
+    let (Some(wrapper), Value::Object(map)) = (&options.wrapper, data) else {
+        return;
+    };
+    map.insert("wrapper".to_string(), json!(wrapper));
+    map.insert(
+        "artifacts".to_string(),
+        json!(
+            artifacts
+                .iter()
+                .map(|artifact| artifact.display().to_string())
+                .collect::<Vec<_>>()
+        ),
+    );
+    map.insert(
+        "wrapper_note".to_string(),
+        json!(
+            "Wrapper output files are written when the app exits; stop it with \
+             brp_extras_shutdown rather than a forced kill so they are complete"
+        ),
+    );
+}
+
+/// Renders a command and its arguments for logs and responses
+pub fn command_line(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Sets BRP-related environment variables on a command
 ///
 /// Currently sets:
@@ -107,3 +331,66 @@ pub fn set_brp_env_vars(cmd: &mut Command, port: Option<u16>) {
         cmd.env("BRP_PORT", port.to_string());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn request(arguments: &Value) -> CallToolRequestParam {
+        CallToolRequestParam {
+            name:      "brp_launch_bevy_app".into(),
+            arguments: arguments.as_object().cloned(),
+        }
+    }
+
+    #[test]
+    fn test_launch_options_from_request() {
+        let options = LaunchOptions::from_request(&request(&json!({
+            "features": ["bevy/bevy_remote", "debug_ui"],
+            "no_default_features": true,
+            "env": { "RUST_LOG": "debug", "WORKERS": 4 }
+        })))
+        .unwrap();
+
+        let mut cmd = Command::new("cargo");
+        options.add_cargo_args(&mut cmd);
+        assert_eq!(
+            command_line(&cmd),
+            "cargo --features bevy/bevy_remote,debug_ui --no-default-features"
+        );
+        assert!(
+            options
+                .env
+                .contains(&("WORKERS".to_string(), "4".to_string()))
+        );
+
+        let options = LaunchOptions::from_request(&request(&json!({}))).unwrap();
+        assert!(!options.changes_build() && options.env.is_empty());
+
+        let options = LaunchOptions::from_request(&request(&json!({
+            "env": { "RUST_LOG": "debug" },
+            "rust_log": "warn,game=trace",
+            "log_format": "json"
+        })))
+        .unwrap();
+        let mut cmd = Command::new("game");
+        options.apply_env(&mut cmd);
+        let env: Vec<_> = cmd.get_envs().collect();
+        assert!(env.contains(&("RUST_LOG".as_ref(), Some("warn,game=trace".as_ref()))));
+        assert!(env.contains(&(LOG_FORMAT_ENV.as_ref(), Some("json".as_ref()))));
+        assert!(LaunchOptions::from_request(&request(&json!({ "log_format": "xml" }))).is_err());
+    }
+
+    #[test]
+    fn test_launch_options_reject_invalid_env() {
+        for env in [
+            json!(["RUST_LOG=debug"]),
+            json!({ "A=B": "c" }),
+            json!({ "A": {} }),
+        ] {
+            assert!(LaunchOptions::from_request(&request(&json!({ "env": env }))).is_err());
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Running launched apps under a debugger or profiler
+//!
+//! A wrapper is a preset (`lldb`, `gdb`, `perf`, `flamegraph`) or a custom wrapper named in
+//! `BRP_MCP_LAUNCH_WRAPPERS`, a command line that takes the program and its arguments last.
+//! Custom command lines come only from the server's environment, never from a tool argument, so
+//! a client can't run arbitrary programs through the `wrapper` parameter. Presets that write
+//! output files put them next to the app's log file, and a custom wrapper can do the same with an
+//! `{output}` placeholder.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use rmcp::Error as McpError;
+use serde::Serialize;
+
+use crate::error::{Error, report_to_mcp_error};
+
+/// Environment variable defining custom wrappers as `name=command line` pairs separated by `;`
+pub const CUSTOM_WRAPPERS_ENV_VAR: &str = "BRP_MCP_LAUNCH_WRAPPERS";
+
+/// Placeholder in a custom wrapper replaced by an output file next to the log
+const OUTPUT_PLACEHOLDER: &str = "{output}";
+
+/// Program a launched app runs under
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchWrapper {
+    /// `lldb --batch`, printing every thread's backtrace if the app crashes
+    Lldb,
+    /// `gdb --batch`, printing every thread's backtrace if the app crashes
+    Gdb,
+    /// `perf record -g`, writing a `.perf.data` file
+    Perf,
+    /// `flamegraph` from `cargo-flamegraph`, writing an `.svg` file
+    Flamegraph,
+    /// A custom wrapper's command line, split on whitespace
+    Command(Vec<String>),
+}
+
+impl LaunchWrapper {
+    /// Parse the `wrapper` parameter, a preset or a custom wrapper from
+    /// [`CUSTOM_WRAPPERS_ENV_VAR`]; an empty value means no wrapper
+    pub fn parse(value: &str) -> Result<Option<Self>, McpError> {
+        Self::parse_with(
+            value,
+            &std::env::var(CUSTOM_WRAPPERS_ENV_VAR).unwrap_or_default(),
+        )
+    }
+
+    /// Parse the `wrapper` parameter against custom wrapper definitions
+    fn parse_with(value: &str, custom: &str) -> Result<Option<Self>, McpError> {
+        let wrapper = match value.trim() {
+            "" => return Ok(None),
+            "lldb" => Self::Lldb,
+            "gdb" => Self::Gdb,
+            "perf" => Self::Perf,
+            "flamegraph" => Self::Flamegraph,
+            name => {
+                let command = custom
+                    .split(';')
+                    .filter_map(|definition| definition.split_once('='))
+                    .find_map(|(custom_name, command)| {
+                        (custom_name.trim() == name).then_some(command)
+                    });
+                let parts: Vec<String> = command
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect();
+                if parts.first().is_none_or(|program| program.starts_with('-')) {
+                    return Err(report_to_mcp_error(&error_stack::Report::new(
+                        Error::invalid(
+                            "parameter 'wrapper'",
+                            format!(
+                                "expected lldb, gdb, perf, flamegraph or a wrapper defined in \
+                                 {CUSTOM_WRAPPERS_ENV_VAR}, got '{value}'"
+                            ),
+                        ),
+                    )));
+                }
+                Self::Command(parts)
+            }
+        };
+        Ok(Some(wrapper))
+    }
+
+    /// The wrapper's command line up to the program, and the files it will write
+    fn runner(&self, log_file: &Path) -> (Vec<String>, Vec<PathBuf>) {
+        let owned = |args: &[&str]| args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let artifact = |extension: &str| log_file.with_extension(extension);
+        match self {
+            Self::Lldb => (
+                owned(&[
+                    "lldb",
+                    "--batch",
+                    "-o",
+                    "run",
+                    "-k",
+                    "thread backtrace all",
+                    "-k",
+                    "quit",
+                    "--",
+                ]),
+                Vec::new(),
+            ),
+            Self::Gdb => (
+                owned(&[
+                    "gdb",
+                    "--batch",
+                    "-ex",
+                    "run",
+                    "-ex",
+                    "thread apply all bt",
+                    "--args",
+                ]),
+                Vec::new(),
+            ),
+            Self::Perf => {
+                let output = artifact("perf.data");
+                let mut runner = owned(&["perf", "record", "-g", "-o"]);
+                runner.push(output.display().to_string());
+                runner.push("--".to_string());
+                (runner, vec![output])
+            }
+            Self::Flamegraph => {
+                let output = artifact("svg");
+                let mut runner = owned(&["flamegraph", "-o"]);
+                runner.push(output.display().to_string());
+                runner.push("--".to_string());
+                (runner, vec![output])
+            }
+            Self::Command(parts) => {
+                let output = artifact("out");
+                let uses_output = parts.iter().any(|part| part.contains(OUTPUT_PLACEHOLDER));
+                let runner = parts
+                    .iter()
+                    .map(|part| part.replace(OUTPUT_PLACEHOLDER, &output.display().to_string()))
+                    .collect();
+                (
+                    runner,
+                    if uses_output {
+                        vec![output]
+                    } else {
+                        Vec::new()
+                    },
+                )
+            }
+        }
+    }
+
+    /// Wrap a launch command, returning the wrapped command and the files the wrapper will write
+    ///
+    /// A `cargo run` command gets the wrapper as its target runner, so cargo builds as usual and
+    /// only the app runs under the wrapper. Any other command is run as the wrapper's program.
+    /// Environment variables must be set on the returned command.
+    pub fn wrap(&self, cmd: Command, log_file: &Path) -> (Command, Vec<PathBuf>) {
+        let (runner, artifacts) = self.runner(log_file);
+        if cmd.get_program() == "cargo" {
+            let mut cmd = cmd;
+            // A JSON array of strings is also a TOML array, so the runner keeps its quoting
+            let runner = serde_json::to_string(&runner).unwrap_or_default();
+            cmd.arg("--config")
+                .arg(format!("target.'cfg(all())'.runner = {runner}"));
+            return (cmd, artifacts);
+        }
+
+        let (program, args) = runner.split_first().map_or_else(
+            || (cmd.get_program().to_owned(), Vec::new()),
+            |(program, args)| (program.into(), args.to_vec()),
+        );
+        let mut wrapped = Command::new(program);
+        wrapped
+            .args(args)
+            .arg(cmd.get_program())
+            .args(cmd.get_args());
+        (wrapped, artifacts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::app_tools::support::launch_common::command_line;
+
+    #[test]
+    fn test_wraps_binaries_and_cargo_runs() {
+        let log_file = Path::new("/tmp/bevy_brp_mcp_game_1.log");
+
+        let preset = LaunchWrapper::parse("perf").unwrap().unwrap();
+        let mut cmd = Command::new("/target/debug/game");
+        cmd.arg("--level").arg("2");
+        let (cmd, artifacts) = preset.wrap(cmd, log_file);
+        assert_eq!(
+            command_line(&cmd),
+            "perf record -g -o /tmp/bevy_brp_mcp_game_1.perf.data -- /target/debug/game --level 2"
+        );
+        assert_eq!(
+            artifacts,
+            vec![PathBuf::from("/tmp/bevy_brp_mcp_game_1.perf.data")]
+        );
+
+        let preset = LaunchWrapper::parse("lldb").unwrap().unwrap();
+        let mut cmd = Command::new("cargo");
+        cmd.arg("run").arg("--bin").arg("game");
+        let (cmd, artifacts) = preset.wrap(cmd, log_file);
+        assert_eq!(
+            command_line(&cmd),
+            "cargo run --bin game --config target.'cfg(all())'.runner = [\"lldb\",\"--batch\",\
+             \"-o\",\"run\",\"-k\",\"thread backtrace all\",\"-k\",\"quit\",\"--\"]"
+        );
+        assert!(artifacts.is_empty());
+
+        let custom = LaunchWrapper::parse_with(
+            "massif",
+            "heaptrack=heaptrack -o {output}; massif = valgrind --tool=massif \
+             --massif-out-file={output}",
+        )
+        .unwrap()
+        .unwrap();
+        let (cmd, artifacts) = custom.wrap(Command::new("game"), log_file);
+        assert_eq!(
+            command_line(&cmd),
+            "valgrind --tool=massif --massif-out-file=/tmp/bevy_brp_mcp_game_1.out game"
+        );
+        assert_eq!(
+            artifacts,
+            vec![PathBuf::from("/tmp/bevy_brp_mcp_game_1.out")]
+        );
+
+        assert_eq!(LaunchWrapper::parse_with("  ", "").unwrap(), None);
+        assert!(LaunchWrapper::parse_with("--batch", "").is_err());
+        // Command lines in the argument are refused, only defined names are run
+        assert!(LaunchWrapper::parse_with("sh -c 'rm -rf ~'", "").is_err());
+        assert!(LaunchWrapper::parse_with("massif", "").is_err());
+        assert!(LaunchWrapper::parse_with("bad", "bad=--flag").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Unified diffs of edited files
+//!
+//! Edits made to a user's project are shown as a unified diff, both to preview them and to
+//! record what was changed. Lines common to the start and end are skipped before the longest
+//! common subsequence of the rest is computed; edits too large for that are shown as one hunk
+//! replacing all the lines in between.
+
+use std::fmt::Write as _;
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Largest table the longest common subsequence is computed with
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// One line of a diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Unified diff of a file's old and new text, empty if they are the same
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+    if lines.iter().all(|line| matches!(line, DiffLine::Same(_))) {
+        return String::new();
+    }
+
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+    for (start, end) in hunk_ranges(&lines) {
+        // Line numbers of the hunk's first line in the old and new text
+        let (mut old_start, mut new_start) = (1, 1);
+        for line in &lines[..start] {
+            match line {
+                DiffLine::Same(_) => {
+                    old_start += 1;
+                    new_start += 1;
+                }
+                DiffLine::Removed(_) => old_start += 1,
+                DiffLine::Added(_) => new_start += 1,
+            }
+        }
+        let hunk = &lines[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(_)))
+            .count();
+        let _ = writeln!(
+            diff,
+            "@@ -{},{old_count} +{},{new_count} @@",
+            if old_count == 0 {
+                old_start - 1
+            } else {
+                old_start
+            },
+            if new_count == 0 {
+                new_start - 1
+            } else {
+                new_start
+            },
+        );
+        for line in hunk {
+            let (prefix, text) = match line {
+                DiffLine::Same(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            diff.push(prefix);
+            diff.push_str(text);
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+/// Every line of both texts, marked as kept, removed or added
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut lines: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line))
+        .collect();
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_LCS_CELLS {
+        lines.extend(old_middle.iter().map(|line| DiffLine::Removed(line)));
+        lines.extend(new_middle.iter().map(|line| DiffLine::Added(line)));
+    } else {
+        lines.extend(lcs_diff(old_middle, new_middle));
+    }
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line)),
+    );
+    lines
+}
+
+/// Diff of two line lists through their longest common subsequence
+fn lcs_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let width = new.len() + 1;
+    // `common[i * width + j]`: length of the common subsequence of `old[i..]` and `new[j..]`
+    let mut common = vec![0_usize; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i * width + j] = if old[i] == new[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len() + new.len());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if common[(i + 1) * width + j] >= common[i * width + j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+    lines
+}
+
+/// Ranges of the diff's lines shown as hunks: the changes with their context, merged when they
+/// overlap
+fn hunk_ranges(lines: &[DiffLine]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if matches!(line, DiffLine::Same(_)) {
+            continue;
+        }
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + 1 + CONTEXT_LINES).min(lines.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_are_shown_as_hunks_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+        assert_eq!(
+            unified_diff("Cargo.toml", old, new),
+            "--- a/Cargo.toml\n+++ b/Cargo.toml\n\
+             @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+             @@ -10,3 +10,4 @@\n j\n k\n l\n+m\n"
+        );
+        assert_eq!(
+            unified_diff("main.rs", "", "fn main() {}\n"),
+            "--- a/main.rs\n+++ b/main.rs\n@@ -0,0 +1,1 @@\n+fn main() {}\n"
+        );
+        assert!(unified_diff("main.rs", old, old).is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use error_stack::Report;
 use rmcp::Error as McpError;
 
 use crate::error::{Error, report_to_mcp_error};
+use crate::log_tools::support::{LOG_EXTENSION, LOG_PREFIX, create_log_directory};
 
 /// Create a log file for a Bevy app launch
 pub fn create_log_file(
@@ -16,7 +17,7 @@ pub fn create_log_file(
     working_dir: &Path,
     port: Option<u16>,
 ) -> Result<(PathBuf, File), McpError> {
-    // Generate unique log file name in temp directory
+    // Generate unique log file name in the log directory
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|e| {
@@ -26,9 +27,14 @@ pub fn create_log_file(
             )
         })?
         .as_millis();
+    let log_dir = create_log_directory();
     let log_file_path = port.map_or_else(
-        || std::env::temp_dir().join(format!("bevy_brp_mcp_{name}_{timestamp}.log")),
-        |port| std::env::temp_dir().join(format!("bevy_brp_mcp_{name}_port{port}_{timestamp}.log")),
+        || log_dir.join(format!("{LOG_PREFIX}{name}_{timestamp}{LOG_EXTENSION}")),
+        |port| {
+            log_dir.join(format!(
+                "{LOG_PREFIX}{name}_port{port}_{timestamp}{LOG_EXTENSION}"
+            ))
+        },
     );
 
     // Create log file
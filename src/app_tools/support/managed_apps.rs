@@ -0,0 +1,344 @@
+//! Registry of Bevy apps managed by this server
+//!
+//! Apps enter the registry either by being launched through `brp_launch_bevy_app` /
+//! `brp_launch_bevy_example` or by being adopted with `brp_attach_app`. Other tools consult it to
+//! resolve an app name to its PID, BRP port and log file instead of guessing from process names.
+//! Launched apps also remember how they were launched so `brp_restart_app` can launch them again.
+//!
+//! The OS reuses PIDs, so every entry also records the start time of its process. Tools check it
+//! before signalling a PID and refuse if the PID now belongs to another process. The executable
+//! name is recorded for reports only: `cargo run` and launch wrappers replace themselves with the
+//! app binary, which renames the process but keeps its PID and start time.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessStatus, ProcessesToUpdate, System};
+
+use super::launch_common::LaunchOptions;
+use crate::error::{Error, Result};
+
+/// Global registry of managed apps, keyed by name
+static MANAGED_APPS: LazyLock<Mutex<HashMap<String, ManagedApp>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// How an app came to be managed by this server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppOrigin {
+    /// Launched by this server
+    Launched,
+    /// Already running and adopted with `brp_attach_app`
+    Attached,
+}
+
+/// What kind of target an app was launched from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchTarget {
+    /// A binary launched with `brp_launch_bevy_app`
+    App,
+    /// An example launched with `brp_launch_bevy_example`
+    Example,
+}
+
+/// How an app was launched, so it can be launched again
+#[derive(Debug, Clone, Serialize)]
+pub struct LaunchRecord {
+    /// App or example
+    pub target:       LaunchTarget,
+    /// Build profile
+    pub profile:      String,
+    /// Workspace used to disambiguate the target, if any
+    pub workspace:    Option<String>,
+    /// Port passed to the launch, if any
+    pub port:         Option<u16>,
+    /// Features and environment passed to the launch
+    pub options:      LaunchOptions,
+    /// Search paths the target was found in
+    #[serde(skip)]
+    pub search_paths: Vec<PathBuf>,
+}
+
+/// What tells a process apart from a later one given the same PID
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessIdentity {
+    /// Executable name when the identity was read, which changes when the process execs
+    pub executable: String,
+    /// Start time in seconds since the Unix epoch
+    pub start_time: u64,
+}
+
+impl ProcessIdentity {
+    /// Identity of the live process with a PID, or `None` if there is none
+    pub fn of(pid: u32) -> Option<Self> {
+        let pid = Pid::from_u32(pid);
+        let mut system = System::new();
+        system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        system
+            .process(pid)
+            .filter(|process| process.status() != ProcessStatus::Zombie)
+            .map(|process| Self {
+                executable: process.name().to_string_lossy().into_owned(),
+                start_time: process.start_time(),
+            })
+    }
+
+    /// Whether both identities are of the same process
+    ///
+    /// Only the start time is compared, since exec changes the executable name of a process.
+    pub const fn is_same_process(&self, other: &Self) -> bool {
+        self.start_time == other.start_time
+    }
+}
+
+/// A Bevy app managed by this server
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagedApp {
+    /// Name the app is managed under
+    pub name:      String,
+    /// Process ID
+    pub pid:       u32,
+    /// The process the PID belonged to when the app was registered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process:   Option<ProcessIdentity>,
+    /// BRP port the app listens on, if known
+    pub port:      Option<u16>,
+    /// Log file for the app's output, if any
+    pub log_file:  Option<PathBuf>,
+    /// Files written by the launch wrapper, such as `perf.data` or a flamegraph
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<PathBuf>,
+    /// Optional group the app belongs to
+    pub group:     Option<String>,
+    /// Whether the app was launched or attached
+    pub origin:    AppOrigin,
+    /// When the app entered the registry
+    pub since:     String,
+    /// How the app was launched, for apps launched by this server
+    pub launch:    Option<LaunchRecord>,
+}
+
+impl ManagedApp {
+    /// Create an entry timestamped now, recording the identity of the process with the PID
+    pub fn new(name: &str, pid: u32, port: Option<u16>, origin: AppOrigin) -> Self {
+        Self {
+            name: name.to_string(),
+            pid,
+            process: ProcessIdentity::of(pid),
+            port,
+            log_file: None,
+            artifacts: Vec::new(),
+            group: None,
+            origin,
+            since: chrono::Local::now().to_rfc3339(),
+            launch: None,
+        }
+    }
+
+    /// Whether the managed process is still alive, and not replaced by another with its PID
+    pub fn is_running(&self) -> bool {
+        ProcessIdentity::of(self.pid).is_some_and(|current| self.is_registered_process(&current))
+    }
+
+    /// Fail if the PID now belongs to a process other than the one registered
+    ///
+    /// A PID without a live process passes, since there is nothing to signal.
+    pub fn verify_process(&self) -> Result<()> {
+        match ProcessIdentity::of(self.pid) {
+            Some(current) if !self.is_registered_process(&current) => {
+                Err(error_stack::Report::new(Error::ProcessManagement(format!(
+                    "PID {} of '{}' now belongs to another process ('{}', started at {}); the \
+                     app has exited and its PID was reused",
+                    self.pid, self.name, current.executable, current.start_time
+                )))
+                .attach_printable(format!("Registered process: {:?}", self.process)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_registered_process(&self, current: &ProcessIdentity) -> bool {
+        self.process
+            .as_ref()
+            .is_some_and(|registered| registered.is_same_process(current))
+    }
+}
+
+/// Add or replace a managed app, returning the entry it replaced
+pub fn register(app: ManagedApp) -> Result<Option<ManagedApp>> {
+    let mut apps = lock()?;
+    Ok(apps.insert(app.name.clone(), app))
+}
+
+/// Look up a managed app by name
+pub fn get(name: &str) -> Option<ManagedApp> {
+    lock().ok().and_then(|apps| apps.get(name).cloned())
+}
+
+/// All managed apps ordered by name
+pub fn list() -> Vec<ManagedApp> {
+    let mut apps: Vec<ManagedApp> = lock()
+        .map(|apps| apps.values().cloned().collect())
+        .unwrap_or_default();
+    apps.sort_by(|a, b| a.name.cmp(&b.name));
+    apps
+}
+
+/// The managed apps of a group ordered by name
+pub fn list_group(group: &str) -> Vec<ManagedApp> {
+    list()
+        .into_iter()
+        .filter(|app| app.group.as_deref() == Some(group))
+        .collect()
+}
+
+/// Look up the managed app listening on a BRP port
+///
+/// If several apps claim the port, the most recently registered one wins.
+pub fn find_by_port(port: u16) -> Option<ManagedApp> {
+    lock().ok().and_then(|apps| {
+        apps.values()
+            .filter(|app| app.port == Some(port))
+            .max_by(|a, b| a.since.cmp(&b.since))
+            .cloned()
+    })
+}
+
+/// Remove a managed app by name
+pub fn remove(name: &str) -> Option<ManagedApp> {
+    lock().ok().and_then(|mut apps| apps.remove(name))
+}
+
+/// Check whether a process with the given PID is running
+///
+/// A zombie has exited and only waits to be reaped by its parent, so it doesn't count.
+pub fn is_pid_running(pid: u32) -> bool {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    system
+        .process(pid)
+        .is_some_and(|process| process.status() != ProcessStatus::Zombie)
+}
+
+fn lock() -> Result<std::sync::MutexGuard<'static, HashMap<String, ManagedApp>>> {
+    MANAGED_APPS.lock().map_err(|e| {
+        error_stack::Report::new(Error::MutexPoisoned(format!("managed apps lock: {e}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_apps_are_registered_looked_up_and_removed() {
+        let app = ManagedApp::new(
+            "registry_test_game",
+            4242,
+            Some(45_001),
+            AppOrigin::Launched,
+        );
+        assert!(register(app).unwrap().is_none());
+        assert_eq!(get("registry_test_game").unwrap().pid, 4242);
+        assert!(list().iter().any(|app| app.name == "registry_test_game"));
+        assert!(list_group("registry_test_group").is_empty());
+
+        // Registering under the same name replaces the entry
+        let relaunched = ManagedApp::new(
+            "registry_test_game",
+            4343,
+            Some(45_001),
+            AppOrigin::Launched,
+        );
+        assert_eq!(register(relaunched).unwrap().unwrap().pid, 4242);
+        assert_eq!(get("registry_test_game").unwrap().pid, 4343);
+
+        let mut grouped = ManagedApp::new(
+            "registry_test_grouped",
+            4444,
+            Some(45_003),
+            AppOrigin::Attached,
+        );
+        grouped.group = Some("registry_test_group".to_string());
+        register(grouped).unwrap();
+        let group = list_group("registry_test_group");
+        assert_eq!(group.len(), 1);
+        assert_eq!(group[0].name, "registry_test_grouped");
+        remove("registry_test_grouped");
+
+        assert_eq!(remove("registry_test_game").unwrap().pid, 4343);
+        assert!(get("registry_test_game").is_none());
+        assert!(remove("registry_test_game").is_none());
+    }
+
+    #[test]
+    fn test_most_recently_registered_app_wins_its_port() {
+        let mut older =
+            ManagedApp::new("registry_test_old", 5001, Some(45_002), AppOrigin::Attached);
+        older.since = "2026-01-01T10:00:00+00:00".to_string();
+        let mut newer =
+            ManagedApp::new("registry_test_new", 5002, Some(45_002), AppOrigin::Launched);
+        newer.since = "2026-01-01T10:00:01+00:00".to_string();
+        register(newer).unwrap();
+        register(older).unwrap();
+
+        assert_eq!(find_by_port(45_002).unwrap().name, "registry_test_new");
+        remove("registry_test_new");
+        assert_eq!(find_by_port(45_002).unwrap().name, "registry_test_old");
+        remove("registry_test_old");
+        assert!(find_by_port(45_002).is_none());
+    }
+
+    #[test]
+    fn test_reused_pids_are_refused() {
+        let mut app = ManagedApp::new(
+            "registry_test_self",
+            std::process::id(),
+            None,
+            AppOrigin::Attached,
+        );
+        assert!(app.process.is_some());
+        assert!(app.is_running());
+        assert!(app.verify_process().is_ok());
+
+        // The same PID started later by another program is not the registered process
+        app.process = Some(ProcessIdentity {
+            executable: "some_other_game".to_string(),
+            start_time: 0,
+        });
+        assert!(!app.is_running());
+        assert!(app.verify_process().is_err());
+    }
+
+    #[test]
+    fn test_apps_renamed_by_exec_still_count_as_running() {
+        // Like `cargo run`, the shell replaces itself with another program under the same PID
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "exec sleep 30"])
+            .spawn()
+            .unwrap();
+        let app = ManagedApp::new("registry_test_exec", child.id(), None, AppOrigin::Launched);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while ProcessIdentity::of(child.id()).unwrap().executable != "sleep" {
+            assert!(std::time::Instant::now() < deadline, "process never exec'd");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        let mut renamed = app.clone();
+        renamed.process.as_mut().unwrap().executable = "cargo".to_string();
+        for app in [app, renamed] {
+            assert!(app.is_running());
+            assert!(app.verify_process().is_ok());
+        }
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+}
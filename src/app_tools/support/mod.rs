@@ -1,7 +1,13 @@
 // Local support modules for app_tools
 
+pub mod brp_readiness;
+pub mod brp_setup;
+pub mod cargo_build;
 pub mod cargo_detector;
 pub mod launch_common;
+pub mod launch_wrapper;
+pub mod line_diff;
 pub mod logging;
+pub mod managed_apps;
 pub mod process;
 pub mod scanning;
@@ -1,11 +1,23 @@
-use std::fs::File;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
 use std::os::unix::process::CommandExt;
 use std::path::Path;
 use std::process::Stdio;
+use std::time::Duration;
 
 use rmcp::Error as McpError;
+use sysinfo::{Pid, ProcessesToUpdate, Signal, System};
+use tokio::io::{AsyncBufReadExt, BufReader};
 
 use crate::error::{Error, report_to_mcp_error};
+use crate::support::progress;
+
+/// Most lines of build output included in the response to a failed build
+const BUILD_ERROR_TAIL_LINES: usize = 40;
+
+/// Logger of the build output lines sent to the client
+pub const BUILD_LOGGER: &str = "bevy_brp_mcp::build";
 
 /// Launch a detached process with proper setup
 pub fn launch_detached_process(
@@ -18,32 +30,14 @@ pub fn launch_detached_process(
     // Clone the log file handle for stderr
     let log_file_for_stderr = log_file.try_clone().map_err(|e| {
         let error_report = error_stack::Report::new(e)
-            .change_context(Error::ProcessManagement(
+            .change_context(Error::ProcessSpawn(
                 "Failed to clone log file handle".to_string(),
             ))
             .attach_printable(format!("Process: {process_name}, Operation: {operation}"));
         report_to_mcp_error(&error_report)
     })?;
 
-    // Create a new command from the provided one
-    let mut new_cmd = std::process::Command::new(cmd.get_program());
-
-    // Copy args
-    for arg in cmd.get_args() {
-        new_cmd.arg(arg);
-    }
-
-    // Set working directory and CARGO_MANIFEST_DIR
-    new_cmd
-        .current_dir(working_dir)
-        .env("CARGO_MANIFEST_DIR", working_dir);
-
-    // Copy other environment variables
-    for (key, value) in cmd.get_envs() {
-        if let Some(value) = value {
-            new_cmd.env(key, value);
-        }
-    }
+    let mut new_cmd = copy_command(cmd, working_dir);
 
     // Set stdio
     new_cmd
@@ -63,19 +57,25 @@ pub fn launch_detached_process(
 
     // Spawn the process
     match new_cmd.spawn() {
-        Ok(child) => {
+        Ok(mut child) => {
             // Get the PID
             let pid = child.id();
 
-            // The process is now detached and will continue running
-            // independently even after this program exits
+            // The process is now detached and will continue running independently even after
+            // this program exits. Until then, reap it when it exits so it doesn't linger as a
+            // zombie that still looks like a running process.
+            std::thread::spawn(move || {
+                if let Err(e) = child.wait() {
+                    tracing::debug!("Failed to wait for process {}: {}", pid, e);
+                }
+            });
             Ok(pid)
         }
         Err(e) => {
             let error_report = error_stack::Report::new(e)
-                .change_context(Error::ProcessManagement(
-                    "Failed to spawn process".to_string(),
-                ))
+                .change_context(Error::ProcessSpawn(format!(
+                    "Failed to spawn process '{process_name}'"
+                )))
                 .attach_printable(format!("Process: {process_name}"))
                 .attach_printable(format!("Operation: {operation}"))
                 .attach_printable(format!("Working directory: {}", working_dir.display()));
@@ -83,3 +83,231 @@ pub fn launch_detached_process(
         }
     }
 }
+
+/// Copy a command to run in a working directory, with `CARGO_MANIFEST_DIR` set to it
+fn copy_command(cmd: &std::process::Command, working_dir: &Path) -> std::process::Command {
+    // Create a new command from the provided one
+    let mut new_cmd = std::process::Command::new(cmd.get_program());
+
+    // Copy args
+    for arg in cmd.get_args() {
+        new_cmd.arg(arg);
+    }
+
+    // Set working directory and CARGO_MANIFEST_DIR
+    new_cmd
+        .current_dir(working_dir)
+        .env("CARGO_MANIFEST_DIR", working_dir);
+
+    // Copy other environment variables
+    for (key, value) in cmd.get_envs() {
+        if let Some(value) = value {
+            new_cmd.env(key, value);
+        }
+    }
+
+    new_cmd
+}
+
+/// Outcome of a build run before a launch
+#[derive(Debug)]
+pub struct BuildOutcome {
+    pub success:    bool,
+    pub exit_code:  Option<i32>,
+    /// The end of the build output, from its first error when there is one
+    pub error_tail: Vec<String>,
+}
+
+/// Run a cargo build to completion, streaming its output into a launch log as it arrives
+///
+/// Cargo reports progress and errors on stderr, which is read line by line; each line is also
+/// sent to the client as a log message when the tool call reports progress.
+pub async fn run_build(
+    cmd: &std::process::Command,
+    working_dir: &Path,
+    log_file_path: &Path,
+    process_name: &str,
+) -> Result<BuildOutcome, McpError> {
+    let spawn_error = |e: std::io::Error, message: &str| {
+        report_to_mcp_error(
+            &error_stack::Report::new(e)
+                .change_context(Error::ProcessSpawn(message.to_string()))
+                .attach_printable(format!("Process: {process_name}, Operation: build"))
+                .attach_printable(format!("Working directory: {}", working_dir.display())),
+        )
+    };
+    let mut log_file = OpenOptions::new()
+        .append(true)
+        .open(log_file_path)
+        .map_err(|e| spawn_error(e, "Failed to open the log file for the build"))?;
+    let stdout = log_file
+        .try_clone()
+        .map_err(|e| spawn_error(e, "Failed to clone log file handle"))?;
+
+    let mut build = tokio::process::Command::from(copy_command(cmd, working_dir));
+    build
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(stdout))
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = build
+        .spawn()
+        .map_err(|e| spawn_error(e, &format!("Failed to build '{process_name}'")))?;
+
+    let progress = progress::current();
+    let mut tail = ErrorTail::default();
+    if let Some(stderr) = child.stderr.take() {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Err(e) = writeln!(log_file, "{line}") {
+                tracing::debug!("Failed to write build output to the log: {}", e);
+            }
+            if let Some(progress) = &progress {
+                progress.log(BUILD_LOGGER, &line).await;
+            }
+            tail.push(line);
+        }
+    }
+
+    let status = child.wait().await.map_err(|e| {
+        spawn_error(
+            e,
+            &format!("Failed to wait for the build of '{process_name}'"),
+        )
+    })?;
+    Ok(BuildOutcome {
+        success:    status.success(),
+        exit_code:  status.code(),
+        error_tail: tail.into_lines(),
+    })
+}
+
+/// The last lines of build output, dropping those before the first error
+#[derive(Default)]
+pub struct ErrorTail {
+    lines:        VecDeque<String>,
+    found_errors: bool,
+}
+
+impl ErrorTail {
+    pub fn push(&mut self, line: String) {
+        if !self.found_errors && line.starts_with("error") {
+            self.found_errors = true;
+            self.lines.clear();
+        }
+        self.lines.push_back(line);
+        if self.lines.len() > BUILD_ERROR_TAIL_LINES {
+            self.lines.pop_front();
+        }
+    }
+
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines.into()
+    }
+}
+
+/// Send a termination signal to a process, `SIGKILL` when `force` is set
+///
+/// Returns `false` if no process with the PID exists.
+pub fn terminate_process(pid: u32, force: bool) -> Result<bool, McpError> {
+    let sys_pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[sys_pid]), true);
+    let Some(process) = system.process(sys_pid) else {
+        return Ok(false);
+    };
+
+    let signal = if force { Signal::Kill } else { Signal::Term };
+    if process.kill_with(signal).unwrap_or(false) {
+        Ok(true)
+    } else {
+        Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::ProcessManagement(
+                "Failed to terminate process".to_string(),
+            ))
+            .attach_printable(format!("PID: {pid}"))
+            .attach_printable(format!("Signal: {signal:?}")),
+        ))
+    }
+}
+
+/// Wait until a process has exited, returning whether it did within `timeout`
+pub async fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    while super::managed_apps::is_pid_running(pid) {
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_error_tail_starts_at_the_first_error() {
+        let mut tail = ErrorTail::default();
+        tail.push("   Compiling game v0.1.0".to_string());
+        tail.push("error[E0425]: cannot find value `speed` in this scope".to_string());
+        for n in 0..BUILD_ERROR_TAIL_LINES {
+            tail.push(format!("  line {n}"));
+        }
+        assert_eq!(tail.lines.len(), BUILD_ERROR_TAIL_LINES);
+        assert_eq!(tail.lines.front().map(String::as_str), Some("  line 0"));
+
+        let mut tail = ErrorTail::default();
+        tail.push("   Compiling game v0.1.0".to_string());
+        tail.push("error: could not compile `game`".to_string());
+        assert_eq!(
+            Vec::from(tail.lines),
+            vec!["error: could not compile `game`"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_killed_process_is_seen_to_exit() {
+        let log_path =
+            std::env::temp_dir().join(format!("bevy_brp_mcp_sleep_{}.log", std::process::id()));
+        let log_file = File::create(&log_path).unwrap();
+        let mut cmd = std::process::Command::new("sleep");
+        cmd.arg("30");
+
+        let pid = launch_detached_process(&cmd, &std::env::temp_dir(), log_file, "sleep", "test")
+            .unwrap();
+        assert!(super::super::managed_apps::is_pid_running(pid));
+        assert!(terminate_process(pid, false).unwrap());
+        assert!(wait_for_exit(pid, Duration::from_secs(5)).await);
+
+        std::fs::remove_file(&log_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_build_streams_output_into_the_log() {
+        let log_path =
+            std::env::temp_dir().join(format!("bevy_brp_mcp_build_{}.log", std::process::id()));
+        std::fs::write(&log_path, "=== header ===\n").unwrap();
+        let mut cmd = std::process::Command::new("sh");
+        cmd.arg("-c")
+            .arg("echo '   Compiling game' >&2; echo 'error: could not compile' >&2; exit 101");
+
+        let build = run_build(&cmd, &std::env::temp_dir(), &log_path, "game")
+            .await
+            .unwrap();
+        assert!(!build.success);
+        assert_eq!(build.exit_code, Some(101));
+        assert_eq!(build.error_tail, vec!["error: could not compile"]);
+        assert_eq!(
+            std::fs::read_to_string(&log_path).unwrap(),
+            "=== header ===\n   Compiling game\nerror: could not compile\n"
+        );
+
+        std::fs::remove_file(&log_path).unwrap();
+    }
+}
@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use rmcp::Error as McpError;
 
-use super::cargo_detector::{BinaryInfo, CargoDetector, ExampleInfo};
+use super::cargo_detector::{
+    BinaryInfo, CargoDetector, ExampleInfo, PackageAnalysis, ProjectAnalysis,
+};
 use crate::error::{Error, report_to_mcp_error};
 
 /// Iterator over all valid Cargo project paths found in the given search paths
@@ -56,10 +59,10 @@ pub fn get_workspace_root_from_manifest(manifest_path: &Path) -> Option<PathBuf>
         let cargo_toml = path.join("Cargo.toml");
         if cargo_toml.exists() {
             // Check if this Cargo.toml defines a workspace
-            if let Ok(content) = std::fs::read_to_string(&cargo_toml) {
-                if content.contains("[workspace]") {
-                    return Some(path.to_path_buf());
-                }
+            if let Ok(content) = std::fs::read_to_string(&cargo_toml)
+                && content.contains("[workspace]")
+            {
+                return Some(path.to_path_buf());
             }
         }
 
@@ -151,6 +154,75 @@ pub fn find_required_example_with_workspace(
     )
 }
 
+/// Analyze every workspace under the search paths once, optionally only the named one
+pub fn analyze_workspaces(
+    search_paths: &[PathBuf],
+    workspace: Option<&str>,
+) -> Vec<ProjectAnalysis> {
+    let mut by_root = BTreeMap::new();
+    for path in iter_cargo_project_paths(search_paths) {
+        if let Ok(detector) = CargoDetector::from_path(&path) {
+            let analysis = detector.analyze();
+            let wanted = workspace.is_none_or(|workspace| {
+                extract_workspace_name(&analysis.workspace_root).as_deref() == Some(workspace)
+            });
+            if wanted {
+                by_root
+                    .entry(analysis.workspace_root.clone())
+                    .or_insert(analysis);
+            }
+        }
+    }
+    by_root.into_values().collect()
+}
+
+/// Find the Bevy package with binaries to work on, by name or as the only one
+/// Returns an error listing the candidates if there are several and none is named
+pub fn find_required_package_with_workspace(
+    package_name: Option<&str>,
+    workspace: Option<&str>,
+    search_paths: &[PathBuf],
+) -> Result<PackageAnalysis, McpError> {
+    let packages: Vec<PackageAnalysis> = analyze_workspaces(search_paths, workspace)
+        .into_iter()
+        .flat_map(|analysis| analysis.packages)
+        .filter(|package| !package.binaries.is_empty())
+        .collect();
+
+    if let Some(package_name) = package_name {
+        let named = packages
+            .into_iter()
+            .filter(|package| package.name == package_name)
+            .collect();
+        return validate_single_result_or_error(
+            named,
+            package_name,
+            "package",
+            "package",
+            |package| get_workspace_root_from_manifest(&package.manifest_path),
+        );
+    }
+
+    if let [package] = packages.as_slice() {
+        return Ok(package.clone());
+    }
+    let message = if packages.is_empty() {
+        "No Bevy package with a binary found in search paths".to_string()
+    } else {
+        let names = packages
+            .iter()
+            .map(|package| format!("- Package: {}", package.name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "Found several Bevy packages with binaries:\n\n{names}\n\nPlease specify which package to use:\n{{\"package\": \"package_name\"}}"
+        )
+    };
+    Err(report_to_mcp_error(&error_stack::Report::new(
+        Error::Configuration(message),
+    )))
+}
+
 /// Build error message for duplicate items across workspaces
 fn build_workspace_selection_error(
     item_type: &str,
@@ -179,10 +251,10 @@ fn find_and_filter_by_workspace<T>(
         all_items
             .into_iter()
             .filter(|item| {
-                if let Some(root) = get_workspace_root(item) {
-                    if let Some(item_workspace) = extract_workspace_name(&root) {
-                        return item_workspace == workspace_name;
-                    }
+                if let Some(root) = get_workspace_root(item)
+                    && let Some(item_workspace) = extract_workspace_name(&root)
+                {
+                    return item_workspace == workspace_name;
                 }
                 false
             })
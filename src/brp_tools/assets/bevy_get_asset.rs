@@ -0,0 +1,126 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::{GET_ASSET_METHODS, PARAM_ASSET_TYPE, PARAM_ID, load_state_name};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PATH, JSON_FIELD_PORT,
+};
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::support::capabilities;
+use crate::error::{Error, ErrorCode, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_GET_ASSET, TOOL_BEVY_GET_ASSET};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_GET_ASSET.into(),
+        description:  DESC_BEVY_GET_ASSET.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_any_property(
+                PARAM_ID,
+                "The AssetId, as listed by bevy_list_assets or held by a component's handle",
+                false,
+            )
+            .add_string_property(
+                JSON_FIELD_PATH,
+                "The asset path instead of an ID, e.g. models/ship.glb#Mesh0",
+                false,
+            )
+            .add_string_property(
+                PARAM_ASSET_TYPE,
+                "Fully-qualified asset type, for apps that need it to find the asset",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    // The app's method takes the same parameters, `asset_type` named `type_path`
+    let mut asset = Map::new();
+    for (param, field) in [
+        (PARAM_ID, PARAM_ID),
+        (JSON_FIELD_PATH, JSON_FIELD_PATH),
+        (PARAM_ASSET_TYPE, "type_path"),
+    ] {
+        if let Some(value) = arguments.get(param).filter(|value| !value.is_null()) {
+            asset.insert(field.to_string(), value.clone());
+        }
+    }
+    if !asset.contains_key(PARAM_ID) && !asset.contains_key(JSON_FIELD_PATH) {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                "parameters",
+                format!("give the '{PARAM_ID}' or the '{JSON_FIELD_PATH}' of the asset"),
+            ),
+        )));
+    }
+
+    let methods = capabilities::supported_methods(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let Some(method) = capabilities::first_supported(&methods, GET_ASSET_METHODS) else {
+        let response =
+            capabilities::unsupported_response("asset inspection", GET_ASSET_METHODS, &methods);
+        return Ok(json_response_to_result(&response));
+    };
+
+    let params = Value::Object(asset);
+    let response = match execute_brp_method(method, Some(params.clone()), Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    {
+        BrpResult::Success(result) => {
+            let result = result.unwrap_or(Value::Null);
+            let load_state = ["load_state", "state"]
+                .iter()
+                .find_map(|field| result.get(*field))
+                .and_then(load_state_name);
+            ResponseBuilder::success()
+                .message(format!(
+                    "Asset {params} from {method}{}",
+                    load_state
+                        .as_ref()
+                        .map_or_else(String::new, |state| format!(", {state}"))
+                ))
+                .data(json!({
+                    "method": method,
+                    "asset": result,
+                    "load_state": load_state,
+                }))
+                .map_or_else(
+                    |_| {
+                        ResponseBuilder::error()
+                            .message("Failed to serialize response data")
+                            .build()
+                    },
+                    ResponseBuilder::build,
+                )
+        }
+        BrpResult::Error(e) => ResponseBuilder::error()
+            .message(format!("{method} failed: {}", e.message))
+            .error_code(ErrorCode::from_brp_code(e.code, false))
+            .details(json!({ "method": method, "params": params }))
+            .build(),
+    };
+
+    Ok(json_response_to_result(&response))
+}
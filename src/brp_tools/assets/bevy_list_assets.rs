@@ -0,0 +1,255 @@
+use std::collections::BTreeMap;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::{LIST_ASSETS_METHODS, PARAM_ASSET_TYPE, asset_references, read_assets};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PORT,
+};
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::support::capabilities;
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_GET, BRP_METHOD_LIST, DESC_BEVY_LIST_ASSETS, TOOL_BEVY_LIST_ASSETS};
+use crate::{BrpMcpService, config};
+
+/// Parameter restricting the list to one load state
+const PARAM_LOAD_STATE: &str = "load_state";
+
+/// Filters of a listing
+#[derive(Debug, Default)]
+struct AssetFilter<'a> {
+    asset_type: Option<&'a str>,
+    load_state: Option<&'a str>,
+    entity:     Option<u64>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_LIST_ASSETS.into(),
+        description:  DESC_BEVY_LIST_ASSETS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_ASSET_TYPE,
+                "Only assets whose type contains this text, e.g. Mesh or StandardMaterial",
+                false,
+            )
+            .add_string_property(
+                PARAM_LOAD_STATE,
+                "Only assets in this load state, e.g. Loaded, Loading or Failed",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_ENTITY,
+                "Only assets whose handles are held by this entity's components",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let filter = AssetFilter {
+        asset_type: arguments.get(PARAM_ASSET_TYPE).and_then(Value::as_str),
+        load_state: arguments.get(PARAM_LOAD_STATE).and_then(Value::as_str),
+        entity:     arguments.get(JSON_FIELD_ENTITY).and_then(Value::as_u64),
+    };
+
+    let response = list_assets(&filter, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// List the assets through the first listing method the app supports
+async fn list_assets(filter: &AssetFilter<'_>, port: u16) -> Result<JsonResponse> {
+    let methods = capabilities::supported_methods(port).await?;
+    let Some(method) = capabilities::first_supported(&methods, LIST_ASSETS_METHODS) else {
+        return Ok(capabilities::unsupported_response(
+            "asset listing",
+            LIST_ASSETS_METHODS,
+            &methods,
+        ));
+    };
+
+    let result = match execute_brp_method(method, None, Some(port)).await? {
+        BrpResult::Success(result) => result.unwrap_or(Value::Null),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "{method} failed: {}",
+                e.message
+            ))));
+        }
+    };
+    let all = read_assets(&result);
+    let total = all.len();
+
+    let references = match filter.entity {
+        Some(entity) => Some(asset_references(&entity_components(entity, port).await?)),
+        None => None,
+    };
+    let assets: Vec<_> = all
+        .into_iter()
+        .filter(|asset| {
+            filter.asset_type.is_none_or(|text| {
+                asset
+                    .type_path
+                    .as_deref()
+                    .is_some_and(|type_path| type_path.contains(text))
+            })
+        })
+        .filter(|asset| {
+            filter.load_state.is_none_or(|state| {
+                asset
+                    .load_state
+                    .as_deref()
+                    .is_some_and(|load_state| load_state.eq_ignore_ascii_case(state))
+            })
+        })
+        .filter(|asset| {
+            references.as_ref().is_none_or(|references| {
+                references.iter().any(|reference| reference.id == asset.id)
+            })
+        })
+        .collect();
+
+    let mut load_states = BTreeMap::new();
+    for asset in &assets {
+        if let Some(state) = &asset.load_state {
+            *load_states.entry(state.clone()).or_insert(0_usize) += 1;
+        }
+    }
+
+    let mut data = json!({
+        "method": method,
+        "assets": assets,
+        "count": assets.len(),
+        "total": total,
+        "load_states": load_states,
+    });
+    if let Some(references) = references {
+        data["references"] = json!(references);
+    }
+
+    Ok(ResponseBuilder::success()
+        .message(format!("{} of {total} assets from {method}", assets.len()))
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// The serialized components of an entity, leaving out those that can't be read
+async fn entity_components(entity: u64, port: u16) -> Result<Map<String, Value>> {
+    let names = match execute_brp_method(
+        BRP_METHOD_LIST,
+        Some(json!({ JSON_FIELD_ENTITY: entity })),
+        Some(port),
+    )
+    .await?
+    {
+        BrpResult::Success(Some(names)) => names,
+        BrpResult::Success(None) => json!([]),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to list components of entity {entity}: {}",
+                e.message
+            ))));
+        }
+    };
+
+    let params = json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: names });
+    match execute_brp_method(BRP_METHOD_GET, Some(params), Some(port)).await? {
+        BrpResult::Success(data) => Ok(data
+            .as_ref()
+            .and_then(|data| data.get(JSON_FIELD_COMPONENTS))
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to get components of entity {entity}: {}",
+            e.message
+        )))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::BRP_METHOD_RPC_DISCOVER;
+
+    const MESH3D: &str = "bevy_render::mesh::components::Mesh3d";
+
+    #[tokio::test]
+    async fn test_assets_held_by_an_entity_are_listed() {
+        let server = MockBrpServer::start().await;
+        let cube = json!({ "Index": { "index": 3, "generation": 0 } });
+        server
+            .respond(
+                BRP_METHOD_RPC_DISCOVER,
+                json!({ "methods": [{ "name": "brp_extras/list_assets" }] }),
+            )
+            .respond(
+                "brp_extras/list_assets",
+                json!([
+                    { "id": cube, "type_path": "bevy_render::mesh::mesh::Mesh", "load_state": "Loaded" },
+                    { "id": { "Index": { "index": 4, "generation": 0 } }, "load_state": "Loading" },
+                ]),
+            )
+            .respond(BRP_METHOD_LIST, json!([MESH3D]))
+            .respond(
+                BRP_METHOD_GET,
+                json!({ "components": { MESH3D: { "Strong": cube } }, "errors": {} }),
+            );
+
+        let filter = AssetFilter {
+            entity: Some(12),
+            ..AssetFilter::default()
+        };
+        let response = list_assets(&filter, server.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        let data = &response["data"];
+        assert_eq!(data["method"], "brp_extras/list_assets");
+        assert_eq!(data["total"], 2);
+        assert_eq!(data["assets"][0]["id"], cube);
+        assert_eq!(data["count"], 1);
+        assert_eq!(data["load_states"], json!({ "Loaded": 1 }));
+        assert_eq!(data["references"][0]["component"], MESH3D);
+
+        let unsupported = MockBrpServer::start().await;
+        unsupported.respond(BRP_METHOD_RPC_DISCOVER, json!({ "methods": [] }));
+        let response = list_assets(&AssetFilter::default(), unsupported.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        assert_eq!(response["error_code"], "method_not_supported");
+    }
+}
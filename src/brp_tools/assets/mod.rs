@@ -0,0 +1,208 @@
+//! Asset introspection
+//!
+//! BRP has no asset methods of its own, so `bevy_list_assets` and `bevy_get_asset` pass through to
+//! the first asset method `rpc.discover` reports, from `bevy_brp_extras` or a newer Bevy, like
+//! `bevy_list_systems`. Listings are read into assets with their type, path and load state.
+//! Handles stored in an entity's components, such as a `Mesh3d` or a material, serialize as the
+//! `AssetId` they point at, so the assets an entity uses are found by matching those IDs.
+
+pub mod bevy_get_asset;
+pub mod bevy_list_assets;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Methods that list assets, in order of preference
+pub const LIST_ASSETS_METHODS: &[&str] = &["bevy/list_assets", "brp_extras/list_assets"];
+
+/// Methods that get one asset, in order of preference
+pub const GET_ASSET_METHODS: &[&str] = &["bevy/get_asset", "brp_extras/get_asset"];
+
+/// Parameter identifying an asset by its `AssetId`
+pub const PARAM_ID: &str = "id";
+
+/// Parameter restricting assets to a type
+pub const PARAM_ASSET_TYPE: &str = "asset_type";
+
+/// Variants of a serialized `AssetId`
+const ASSET_ID_VARIANTS: [&str; 2] = ["Index", "Uuid"];
+
+/// Variants of a serialized `Handle`, wrapping the `AssetId`
+const HANDLE_VARIANTS: [&str; 2] = ["Strong", "Weak"];
+
+/// An asset as reported by the listing method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssetInfo {
+    pub id:         Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_path:  Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path:       Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_state: Option<String>,
+}
+
+/// An asset handle found in a component of an entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssetReference {
+    pub component: String,
+    /// Path of the handle within the component, as accepted by `bevy_mutate_component`
+    pub path:      String,
+    pub id:        Value,
+}
+
+/// The assets of a listing result
+///
+/// Accepts a list of assets, `{"assets": [..]}`, or assets grouped by type as
+/// `{"<type>": [..]}`. An asset is an object with an `id`, and optionally its `type_path` (or
+/// `type`), `path` and `load_state` (or `state`).
+pub fn read_assets(result: &Value) -> Vec<AssetInfo> {
+    match result {
+        Value::Array(assets) => assets
+            .iter()
+            .filter_map(|asset| read_asset(asset, None))
+            .collect(),
+        Value::Object(fields) => fields.get("assets").map_or_else(
+            || {
+                fields
+                    .iter()
+                    .flat_map(|(type_path, assets)| {
+                        assets
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|asset| read_asset(asset, Some(type_path)))
+                    })
+                    .collect()
+            },
+            read_assets,
+        ),
+        _ => Vec::new(),
+    }
+}
+
+fn read_asset(asset: &Value, type_path: Option<&str>) -> Option<AssetInfo> {
+    let field = |names: &[&str]| names.iter().find_map(|name| asset.get(*name));
+    let text = |names: &[&str]| field(names).and_then(Value::as_str).map(String::from);
+    Some(AssetInfo {
+        id:         asset.get(PARAM_ID)?.clone(),
+        type_path:  text(&["type_path", "type"]).or_else(|| type_path.map(String::from)),
+        path:       text(&["path"]),
+        load_state: field(&["load_state", "state"]).and_then(load_state_name),
+    })
+}
+
+/// A load state given as a variant name or as an enum with data, such as `{"Failed": ".."}`
+pub fn load_state_name(state: &Value) -> Option<String> {
+    match state {
+        Value::String(name) => Some(name.clone()),
+        Value::Object(fields) if fields.len() == 1 => fields.keys().next().cloned(),
+        _ => None,
+    }
+}
+
+/// Every asset handle in the serialized components of an entity, in component order
+pub fn asset_references(components: &Map<String, Value>) -> Vec<AssetReference> {
+    let mut references = Vec::new();
+    for (component, value) in components {
+        collect_references(component, value, String::new(), &mut references);
+    }
+    references
+}
+
+fn collect_references(
+    component: &str,
+    value: &Value,
+    path: String,
+    references: &mut Vec<AssetReference>,
+) {
+    if let Some(id) = asset_id(value) {
+        references.push(AssetReference {
+            component: component.to_string(),
+            path,
+            id,
+        });
+        return;
+    }
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                collect_references(component, value, format!("{path}.{field}"), references);
+            }
+        }
+        Value::Array(items) => {
+            for (index, value) in items.iter().enumerate() {
+                collect_references(component, value, format!("{path}[{index}]"), references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `AssetId` a value is or holds, if it's an `AssetId` or a `Handle`
+///
+/// A handle wraps the ID, as a newtype struct or in one of the `Strong`/`Weak` variants.
+fn asset_id(value: &Value) -> Option<Value> {
+    let Value::Object(fields) = value else {
+        return None;
+    };
+    let (variant, inner) = fields.iter().next().filter(|_| fields.len() == 1)?;
+    if ASSET_ID_VARIANTS.contains(&variant.as_str()) && inner.is_object() {
+        return Some(value.clone());
+    }
+    if HANDLE_VARIANTS.contains(&variant.as_str()) || variant == PARAM_ID {
+        return asset_id(inner);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    const MESH: &str = "bevy_render::mesh::mesh::Mesh";
+
+    #[test]
+    fn test_listings_and_references_are_read() {
+        let cube = json!({ "Index": { "index": 3, "generation": 0 } });
+        let listed = json!([
+            { "id": cube, "type_path": MESH, "path": "models/cube.glb#Mesh0", "load_state": "Loaded" },
+            { "id": { "Uuid": { "uuid": "0a8b" } }, "type": "Image", "state": { "Failed": "not found" } },
+            { "path": "no_id.png" },
+        ]);
+        let assets = read_assets(&listed);
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[0].load_state.as_deref(), Some("Loaded"));
+        assert_eq!(assets[1].type_path.as_deref(), Some("Image"));
+        assert_eq!(assets[1].load_state.as_deref(), Some("Failed"));
+        assert_eq!(read_assets(&json!({ "assets": listed })).len(), 2);
+        let grouped = read_assets(&json!({ MESH: [{ "id": cube }] }));
+        assert_eq!(grouped[0].type_path.as_deref(), Some(MESH));
+
+        let components = json!({
+            "bevy_render::mesh::components::Mesh3d": { "Strong": cube },
+            "my_game::Skins": { "variants": [{ "Weak": { "Uuid": { "uuid": "0a8b" } } }] },
+            "bevy_transform::components::transform::Transform": { "translation": [0.0, 0.0, 0.0] },
+        });
+        let references = asset_references(components.as_object().unwrap());
+        assert_eq!(
+            references,
+            vec![
+                AssetReference {
+                    component: "bevy_render::mesh::components::Mesh3d".to_string(),
+                    path:      String::new(),
+                    id:        cube,
+                },
+                AssetReference {
+                    component: "my_game::Skins".to_string(),
+                    path:      ".variants[0]".to_string(),
+                    id:        json!({ "Uuid": { "uuid": "0a8b" } }),
+                },
+            ]
+        );
+    }
+}
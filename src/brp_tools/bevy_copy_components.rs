@@ -0,0 +1,422 @@
+//! Copy components from one entity onto others
+//!
+//! Gets the selected components of a source entity with `bevy/get` and inserts them onto each
+//! target with format discovery, optionally adjusting fields of the copies first: `offset` adds
+//! to a number or, element by element, to a math type such as a `Vec3` translation, and `set`
+//! replaces the value at a path.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY,
+    JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_HOST, JSON_FIELD_PORT, JSON_FIELD_STATUS,
+    PARAM_ENTITIES,
+};
+use super::request_handler::{
+    execute_brp_method_with_format_discovery, parse_path, resolve_type_names, value_at_mut,
+};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::response_formatter::corrections_json;
+use crate::error::{Error, ErrorCode, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_GET, BRP_METHOD_INSERT, DESC_BEVY_COPY_COMPONENTS, TOOL_BEVY_COPY_COMPONENTS,
+};
+use crate::{BrpMcpService, config};
+
+/// Parameter listing the field adjustments
+const PARAM_ADJUST: &str = "adjust";
+
+/// A change to a field of the copied components
+#[derive(Debug, Clone, Deserialize)]
+struct Adjustment {
+    component: String,
+    /// Path of the field within the component, e.g. `.translation`; the whole value if empty
+    #[serde(default)]
+    path:      String,
+    /// Number, or array of numbers, added to the field
+    offset:    Option<Value>,
+    /// Value replacing the field
+    set:       Option<Value>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_COPY_COMPONENTS.into(),
+        description:  DESC_BEVY_COPY_COMPONENTS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(JSON_FIELD_ENTITY, "The entity to copy components from", true)
+            .add_number_array_property(
+                PARAM_ENTITIES,
+                "The entities to insert the copies onto",
+                true,
+            )
+            .add_string_array_property(
+                JSON_FIELD_COMPONENTS,
+                "Component types to copy, fully-qualified or short names",
+                true,
+            )
+            .add_any_property(
+                PARAM_ADJUST,
+                "Array of changes to the copies before they are inserted: {component, path, offset} adds a number or an array of numbers to the field at path, {component, path, set} replaces it, e.g. {\"component\": \"Transform\", \"path\": \".translation\", \"offset\": [2, 0, 0]}",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let source = params::extract_required_number(&request, JSON_FIELD_ENTITY)?;
+    let components =
+        params::extract_optional_string_array_from_request(&request, JSON_FIELD_COMPONENTS)?
+            .unwrap_or_default();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let targets: Vec<u64> = arguments
+        .get(PARAM_ENTITIES)
+        .and_then(Value::as_array)
+        .map(|targets| targets.iter().filter_map(Value::as_u64).collect())
+        .unwrap_or_default();
+    let adjustments: Vec<Adjustment> = arguments
+        .get(PARAM_ADJUST)
+        .filter(|adjust| !adjust.is_null())
+        .map(|adjust| serde_json::from_value(adjust.clone()))
+        .transpose()
+        .map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+                &format!("parameter '{PARAM_ADJUST}'"),
+                format!("expected an array of {{component, path, offset or set}}: {e}"),
+            )))
+        })?
+        .unwrap_or_default();
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let invalid = |parameter: &str, details: &str| {
+        report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+            &format!("parameter '{parameter}'"),
+            details,
+        )))
+    };
+    if components.is_empty() {
+        return Err(invalid(
+            JSON_FIELD_COMPONENTS,
+            "list at least one component type",
+        ));
+    }
+    if targets.is_empty() {
+        return Err(invalid(PARAM_ENTITIES, "list at least one target entity"));
+    }
+
+    let response = copy_components(source, &targets, components, &adjustments, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Get the components from the source, adjust them and insert them onto every target
+async fn copy_components(
+    source: u64,
+    targets: &[u64],
+    components: Vec<String>,
+    adjustments: &[Adjustment],
+    port: u16,
+) -> Result<JsonResponse> {
+    let mut params = json!({ JSON_FIELD_ENTITY: source, JSON_FIELD_COMPONENTS: components });
+    let resolved = resolve_type_names(BRP_METHOD_GET, &mut params, port).await;
+    if !resolved.ambiguous.is_empty() {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{JSON_FIELD_COMPONENTS}'"),
+            resolved.ambiguity_message(),
+        )));
+    }
+
+    let (mut values, missing) =
+        match execute_brp_method(BRP_METHOD_GET, Some(params), Some(port)).await? {
+            BrpResult::Success(data) => {
+                let data = data.unwrap_or_default();
+                let object = |field: &str| {
+                    data.get(field)
+                        .and_then(Value::as_object)
+                        .cloned()
+                        .unwrap_or_default()
+                };
+                (object(JSON_FIELD_COMPONENTS), object("errors"))
+            }
+            BrpResult::Error(e) => {
+                return Ok(ResponseBuilder::error()
+                    .message(format!(
+                        "Failed to get components from entity {source}: {}",
+                        e.message
+                    ))
+                    .error_code(ErrorCode::from_brp_code(e.code, false))
+                    .details(json!({ JSON_FIELD_ENTITY: source }))
+                    .build());
+            }
+        };
+    if values.is_empty() {
+        return Ok(ResponseBuilder::error()
+            .message(format!(
+                "Entity {source} has none of the components to copy"
+            ))
+            .error_code(ErrorCode::InvalidParameters)
+            .details(json!({ JSON_FIELD_ENTITY: source, "missing": missing }))
+            .build());
+    }
+
+    for adjustment in adjustments {
+        let component = resolved
+            .expanded
+            .get(&adjustment.component)
+            .unwrap_or(&adjustment.component);
+        apply_adjustment(&mut values, component, adjustment)?;
+    }
+
+    let mut results = Vec::new();
+    for target in targets {
+        let params = json!({ JSON_FIELD_ENTITY: target, JSON_FIELD_COMPONENTS: values });
+        let outcome = execute_brp_method_with_format_discovery(
+            BRP_METHOD_INSERT,
+            Some(params),
+            Some(port),
+            Vec::new(),
+        )
+        .await;
+        results.push(target_result(*target, outcome));
+    }
+
+    let copied = results
+        .iter()
+        .filter(|result| result[JSON_FIELD_STATUS] == "success")
+        .count();
+    let mut data = json!({
+        JSON_FIELD_ENTITY: source,
+        JSON_FIELD_COMPONENTS: values.keys().collect::<Vec<_>>(),
+        "results": results,
+        "copied": copied,
+        "failed": targets.len() - copied,
+    });
+    if !missing.is_empty() {
+        data["missing"] = Value::Object(missing);
+    }
+    if let Some((field, value)) = resolved.response_field() {
+        data[field] = value;
+    }
+
+    Ok(ResponseBuilder::success()
+        .message(format!(
+            "Copied {} components from entity {source} onto {copied} of {} entities",
+            values.len(),
+            targets.len()
+        ))
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// Result entry of the insert onto one target
+fn target_result(target: u64, outcome: Result<super::request_handler::EnhancedBrpResult>) -> Value {
+    let mut result = json!({ JSON_FIELD_ENTITY: target });
+    match outcome {
+        Ok(enhanced) => {
+            match enhanced.result {
+                BrpResult::Success(_) => result[JSON_FIELD_STATUS] = json!("success"),
+                BrpResult::Error(e) => {
+                    result[JSON_FIELD_STATUS] = json!("error");
+                    result["error"] = json!(e.message);
+                }
+            }
+            if !enhanced.format_corrections.is_empty() {
+                result[JSON_FIELD_FORMAT_CORRECTIONS] =
+                    corrections_json(&enhanced.format_corrections);
+            }
+        }
+        Err(report) => {
+            result[JSON_FIELD_STATUS] = json!("error");
+            result["error"] = json!(report.to_string());
+        }
+    }
+    result
+}
+
+/// Apply one adjustment to the copied component values
+fn apply_adjustment(
+    values: &mut Map<String, Value>,
+    component: &str,
+    adjustment: &Adjustment,
+) -> Result<()> {
+    let invalid = |details: String| {
+        error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_ADJUST}'"),
+            details,
+        ))
+    };
+
+    let value = values.get_mut(component).ok_or_else(|| {
+        invalid(format!(
+            "'{}' is not one of the copied components",
+            adjustment.component
+        ))
+    })?;
+    let segments = if adjustment.path.is_empty() {
+        Vec::new()
+    } else {
+        parse_path(&adjustment.path)
+            .ok_or_else(|| invalid(format!("'{}' is not a valid path", adjustment.path)))?
+    };
+    let field = value_at_mut(value, &segments)
+        .ok_or_else(|| invalid(format!("{component} has no field at '{}'", adjustment.path)))?;
+
+    match (&adjustment.set, &adjustment.offset) {
+        (Some(set), None) => {
+            *field = set.clone();
+            Ok(())
+        }
+        (None, Some(offset)) => add_offset(field, offset).ok_or_else(|| {
+            invalid(format!(
+                "can't add {offset} to {field} at '{}' of {component}",
+                adjustment.path
+            ))
+        }),
+        _ => Err(invalid(format!(
+            "give either offset or set for '{}' of {component}",
+            adjustment.path
+        ))),
+    }
+}
+
+/// Add a number to a number, or numbers element by element to an array of the same length
+fn add_offset(field: &mut Value, offset: &Value) -> Option<()> {
+    match (field, offset) {
+        (Value::Array(items), Value::Array(offsets)) if items.len() == offsets.len() => {
+            // Check every element before changing any
+            let sums: Option<Vec<Value>> = items
+                .iter()
+                .zip(offsets)
+                .map(|(item, offset)| sum(item, offset))
+                .collect();
+            *items = sums?;
+            Some(())
+        }
+        (field, offset) => {
+            *field = sum(field, offset)?;
+            Some(())
+        }
+    }
+}
+
+fn sum(value: &Value, offset: &Value) -> Option<Value> {
+    match (value.as_i64(), offset.as_i64()) {
+        (Some(value), Some(offset)) => value.checked_add(offset).map(Value::from),
+        _ => Some(json!(value.as_f64()? + offset.as_f64()?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    const TRANSFORM: &str = "bevy_transform::components::transform::Transform";
+    const HEALTH: &str = "my_game::Health";
+
+    fn adjustment(path: &str, offset: Option<Value>, set: Option<Value>) -> Adjustment {
+        Adjustment {
+            component: TRANSFORM.to_string(),
+            path: path.to_string(),
+            offset,
+            set,
+        }
+    }
+
+    #[test]
+    fn test_adjustments_offset_and_set_fields() {
+        let mut values = Map::new();
+        values.insert(
+            TRANSFORM.to_string(),
+            json!({ "translation": [1.0, 2.0, 3.0], "scale": [1.0, 1.0, 1.0] }),
+        );
+
+        let offset = adjustment(".translation", Some(json!([10, 0, -3])), None);
+        apply_adjustment(&mut values, TRANSFORM, &offset).unwrap();
+        let offset_y = adjustment(".translation.y", Some(json!(0.5)), None);
+        apply_adjustment(&mut values, TRANSFORM, &offset_y).unwrap();
+        let set = adjustment(".scale", None, Some(json!([2.0, 2.0, 2.0])));
+        apply_adjustment(&mut values, TRANSFORM, &set).unwrap();
+        assert_eq!(
+            values[TRANSFORM],
+            json!({ "translation": [11.0, 2.5, 0.0], "scale": [2.0, 2.0, 2.0] })
+        );
+
+        for bad in [
+            adjustment(".translation", Some(json!([1, 2])), None),
+            adjustment(".rotation", Some(json!(1)), None),
+            adjustment("translation", Some(json!(1)), None),
+            adjustment(".scale", Some(json!(1)), Some(json!(1))),
+        ] {
+            assert!(apply_adjustment(&mut values, TRANSFORM, &bad).is_err());
+        }
+        assert!(apply_adjustment(&mut values, HEALTH, &set).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_components_are_copied_onto_every_target() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_GET,
+                json!({
+                    "components": { TRANSFORM: { "translation": [1.0, 0.0, 0.0] } },
+                    "errors": { HEALTH: { "code": -23402, "message": "Component not present" } },
+                }),
+            )
+            .respond(BRP_METHOD_INSERT, Value::Null);
+
+        let adjustments = [adjustment(".translation.x", Some(json!(2)), None)];
+        let response = copy_components(
+            1,
+            &[2, 3],
+            vec![TRANSFORM.to_string(), HEALTH.to_string()],
+            &adjustments,
+            server.port(),
+        )
+        .await
+        .map(|response| serde_json::to_value(response).unwrap())
+        .unwrap();
+        assert_eq!(response["data"]["copied"], 2);
+        assert_eq!(response["data"][JSON_FIELD_COMPONENTS], json!([TRANSFORM]));
+        assert!(response["data"]["missing"].get(HEALTH).is_some());
+
+        let inserts = server.requests_for(BRP_METHOD_INSERT);
+        assert_eq!(inserts.len(), 2);
+        assert_eq!(
+            inserts[1].params,
+            Some(json!({
+                "entity": 3,
+                "components": { TRANSFORM: { "translation": [3.0, 0.0, 0.0] } },
+            }))
+        );
+    }
+}
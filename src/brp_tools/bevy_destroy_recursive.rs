@@ -0,0 +1,144 @@
+//! Destroy an entity together with all of its descendants
+//!
+//! Resolves the descendant tree through the `Children` components, like `bevy_get_hierarchy`,
+//! and destroys it bottom-up: the deepest level first, each level's entities concurrently, so no
+//! entity is left orphaned and nothing depends on how the app handles the hierarchy.
+
+use futures::future::join_all;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::bevy_get_hierarchy;
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_CONFIRM, DESC_HOST, JSON_FIELD_COUNT, JSON_FIELD_ENTITY,
+    JSON_FIELD_HOST, JSON_FIELD_PORT, PARAM_CONFIRM,
+};
+use super::support::brp_client::{self, BrpResult, execute_brp_method};
+use super::support::destructive_ops;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_DESTROY, DESC_BEVY_DESTROY_RECURSIVE, TOOL_BEVY_DESTROY_RECURSIVE};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_DESTROY_RECURSIVE.into(),
+        description:  DESC_BEVY_DESTROY_RECURSIVE.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_ENTITY,
+                "The entity ID to destroy with its descendants",
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .add_any_property(PARAM_CONFIRM, DESC_CONFIRM, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    mut request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let confirmation = destructive_ops::take_confirmation(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+
+    let entity_id = params::extract_required_u64(&arguments, JSON_FIELD_ENTITY, "entity")?;
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let operation = format!(
+        "{TOOL_BEVY_DESTROY_RECURSIVE} {}:{port} {entity_id}",
+        brp_client::current_host()
+    );
+    if let Some(reason) = destructive_ops::preview_reason(&operation, &confirmation) {
+        let params = json!({ JSON_FIELD_ENTITY: entity_id });
+        let preview = destructive_ops::preview(BRP_METHOD_DESTROY, Some(&params), port).await;
+        return Ok(destructive_ops::preview_response(
+            &operation,
+            &format!("destroying entity {entity_id} and its descendants"),
+            &reason,
+            &preview,
+        ));
+    }
+
+    let hierarchy = bevy_get_hierarchy::get_hierarchy(entity_id, u64::MAX, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    // Destroying part of a tree would leave the rest orphaned
+    if hierarchy.truncated() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                JSON_FIELD_ENTITY,
+                format!(
+                    "entity {entity_id} has more descendants than can be walked; destroy it with bevy_destroy"
+                ),
+            ),
+        )));
+    }
+
+    let mut destroyed = Vec::new();
+    let mut failed = Map::new();
+    for level in hierarchy.levels_bottom_up(entity_id) {
+        let outcomes = join_all(level.iter().map(|entity| {
+            execute_brp_method(
+                BRP_METHOD_DESTROY,
+                Some(json!({ JSON_FIELD_ENTITY: entity })),
+                Some(port),
+            )
+        }))
+        .await;
+        for (entity, outcome) in level.into_iter().zip(outcomes) {
+            match outcome {
+                Ok(BrpResult::Success(_)) => destroyed.push(entity),
+                Ok(BrpResult::Error(e)) => {
+                    failed.insert(entity.to_string(), json!(e.message));
+                }
+                Err(e) => {
+                    failed.insert(entity.to_string(), json!(e.to_string()));
+                }
+            }
+        }
+    }
+
+    let message = if failed.is_empty() {
+        format!(
+            "Destroyed entity {entity_id} and {} descendants",
+            destroyed.len().saturating_sub(1)
+        )
+    } else {
+        format!(
+            "Destroyed {} of {} entities in the tree of entity {entity_id}",
+            destroyed.len(),
+            destroyed.len() + failed.len()
+        )
+    };
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            JSON_FIELD_ENTITY: entity_id,
+            "destroyed": destroyed,
+            JSON_FIELD_COUNT: destroyed.len(),
+            "failed": failed,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,519 @@
+//! Explain an entity in plain words
+//!
+//! Reads every component of an entity and summarizes the ones whose types are known: where its
+//! `Transform` puts it, whether it's visible, what a `Camera` renders to and which assets its
+//! handles point at. Its place in the hierarchy and a camera's target window are returned as
+//! relationships to other entities. The summaries are built here from the component values, so
+//! they cost one `bevy/list` and one `bevy/get`; components without a summary are listed as
+//! unexplained.
+
+use std::fmt::Write as _;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use super::assets::asset_references;
+use super::bevy_find_entity_by_name::list_components;
+use super::bevy_get_hierarchy::{CHILDREN_COMPONENT_SUFFIX, is_parent_component, relationships};
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PORT, PARAM_STRICT,
+};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::spawn_tagging::NAME_COMPONENT;
+use super::transform::{TRANSFORM_TYPE, to_euler_degrees, vector};
+use super::visibility::VISIBILITY_TYPE;
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::fuzzy::short_name;
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_GET, DESC_BEVY_EXPLAIN_ENTITY, TOOL_BEVY_EXPLAIN_ENTITY};
+use crate::{BrpMcpService, config};
+
+const GLOBAL_TRANSFORM_TYPE: &str = "bevy_transform::components::global_transform::GlobalTransform";
+const INHERITED_VISIBILITY_TYPE: &str = "bevy_render::view::visibility::InheritedVisibility";
+const VIEW_VISIBILITY_TYPE: &str = "bevy_render::view::visibility::ViewVisibility";
+const CAMERA_TYPE: &str = "bevy_render::camera::camera::Camera";
+const CAMERA_3D_TYPE: &str = "bevy_core_pipeline::core_3d::camera_3d::Camera3d";
+const CAMERA_2D_TYPE: &str = "bevy_core_pipeline::core_2d::camera_2d::Camera2d";
+
+/// A component explained in words
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Fact {
+    component: String,
+    summary:   String,
+}
+
+/// What a camera renders to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct CameraTarget {
+    /// `window`, `image` or `texture_view`
+    kind:    String,
+    /// The window entity, unset for the primary window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity:  Option<u64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    primary: bool,
+    /// The render target asset, for images
+    #[serde(skip_serializing_if = "Option::is_none")]
+    asset:   Option<Value>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_EXPLAIN_ENTITY.into(),
+        description:  DESC_BEVY_EXPLAIN_ENTITY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(JSON_FIELD_ENTITY, "The entity ID to explain", true)
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let Some(entity) = arguments.get(JSON_FIELD_ENTITY).and_then(Value::as_u64) else {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::missing(JSON_FIELD_ENTITY),
+        )));
+    };
+
+    let response = explain_entity(entity, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Read every component of an entity and explain them
+async fn explain_entity(entity: u64, port: u16) -> Result<JsonResponse> {
+    let components = list_components(entity, port).await;
+    if components.is_empty() {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{JSON_FIELD_ENTITY}'"),
+            format!("entity {entity} doesn't exist or has no components"),
+        )));
+    }
+
+    // Non-strict, so components that can't be serialized don't fail the whole read
+    let params = json!({
+        JSON_FIELD_ENTITY: entity,
+        JSON_FIELD_COMPONENTS: components,
+        PARAM_STRICT: false,
+    });
+    let values = match execute_brp_method(BRP_METHOD_GET, Some(params), Some(port)).await? {
+        BrpResult::Success(Some(result)) => result
+            .get(JSON_FIELD_COMPONENTS)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default(),
+        BrpResult::Success(None) => Map::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to get the components of entity {entity}: {}",
+                e.message
+            ))));
+        }
+    };
+
+    let explanation = explain(entity, &components, &values);
+    Ok(ResponseBuilder::success()
+        .message(
+            explanation["summary"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        )
+        .data(json!({ JSON_FIELD_ENTITY: entity, "explanation": explanation }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// The explanation of an entity with these components and serialized values
+fn explain(entity: u64, components: &[String], values: &Map<String, Value>) -> Value {
+    let name = values.get(NAME_COMPONENT).and_then(name_of);
+    let camera_target = values
+        .get(CAMERA_TYPE)
+        .and_then(|camera| camera.get("target"))
+        .and_then(camera_target);
+    let known = [
+        (NAME_COMPONENT, name.clone()),
+        (
+            TRANSFORM_TYPE,
+            values.get(TRANSFORM_TYPE).and_then(transform_summary),
+        ),
+        (
+            GLOBAL_TRANSFORM_TYPE,
+            values
+                .get(GLOBAL_TRANSFORM_TYPE)
+                .and_then(world_position)
+                .map(|position| format!("world position {}", format_vector(&position))),
+        ),
+        (VISIBILITY_TYPE, visibility_summary(values)),
+        (
+            CAMERA_TYPE,
+            values
+                .get(CAMERA_TYPE)
+                .map(|camera| camera_summary(camera, values, camera_target.as_ref())),
+        ),
+    ];
+
+    let mut facts = Vec::new();
+    let mut explained: Vec<&str> = Vec::new();
+    for (component, summary) in known {
+        if let Some(summary) = summary {
+            facts.push(Fact {
+                component: short_name(component).to_string(),
+                summary,
+            });
+            explained.push(component);
+        }
+    }
+    // Summarized together with the component they qualify
+    if explained.contains(&VISIBILITY_TYPE) {
+        explained.extend([INHERITED_VISIBILITY_TYPE, VIEW_VISIBILITY_TYPE]);
+    }
+    if explained.contains(&CAMERA_TYPE) {
+        explained.extend([CAMERA_3D_TYPE, CAMERA_2D_TYPE]);
+    }
+
+    let assets = asset_references(values);
+    for reference in &assets {
+        facts.push(Fact {
+            component: short_name(&reference.component).to_string(),
+            summary:   format!(
+                "uses {} asset {}",
+                asset_kind(&reference.component),
+                reference.id
+            ),
+        });
+    }
+
+    let (parent, children) = relationships(values);
+    let unexplained: Vec<&String> = components
+        .iter()
+        .filter(|component| {
+            !explained.contains(&component.as_str())
+                && !assets
+                    .iter()
+                    .any(|reference| &reference.component == *component)
+                && !is_relationship(component)
+        })
+        .collect();
+
+    json!({
+        "summary": summary(entity, name.as_deref(), &facts, parent, children.len()),
+        "facts": facts,
+        "relationships": {
+            "parent": parent,
+            "children": children,
+            "camera_target": camera_target,
+        },
+        "assets": assets,
+        "unexplained": unexplained,
+    })
+}
+
+/// One sentence describing the entity
+fn summary(
+    entity: u64,
+    name: Option<&str>,
+    facts: &[Fact],
+    parent: Option<u64>,
+    children: usize,
+) -> String {
+    let mut parts: Vec<String> = facts
+        .iter()
+        .filter(|fact| fact.component != short_name(NAME_COMPONENT))
+        .map(|fact| fact.summary.clone())
+        .collect();
+    match (parent, children) {
+        (Some(parent), 0) => parts.push(format!("child of {parent}")),
+        (Some(parent), children) => {
+            parts.push(format!("child of {parent} with {children} children"));
+        }
+        (None, 0) => {}
+        (None, children) => parts.push(format!("root of {children} children")),
+    }
+    let label = name.map_or_else(
+        || format!("Entity {entity}"),
+        |name| format!("Entity {entity} \"{name}\""),
+    );
+    if parts.is_empty() {
+        format!("{label} has no components with a known meaning")
+    } else {
+        format!("{label}: {}", parts.join("; "))
+    }
+}
+
+/// `Name` serializes as a plain string, older apps may send `{"name": ..}`
+fn name_of(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .or_else(|| value.get("name").and_then(Value::as_str))
+        .map(String::from)
+}
+
+/// Translation, rotation and scale, leaving out the parts that are identity
+fn transform_summary(transform: &Value) -> Option<String> {
+    let translation = vector(transform.get("translation")?, 3)?;
+    let rotation = transform
+        .get("rotation")
+        .and_then(|rotation| vector(rotation, 4))
+        .map_or_else(|| vec![0.0; 3], |rotation| to_euler_degrees(&rotation));
+    let scale = transform
+        .get("scale")
+        .and_then(|scale| vector(scale, 3))
+        .unwrap_or_else(|| vec![1.0; 3]);
+
+    let mut parts = vec![format!("at {}", format_vector(&translation))];
+    if rotation.iter().any(|angle| format_number(*angle) != "0") {
+        let degrees: Vec<String> = rotation
+            .iter()
+            .map(|angle| format!("{}°", format_number(*angle)))
+            .collect();
+        parts.push(format!("rotated ({}) about x, y, z", degrees.join(", ")));
+    }
+    if scale.iter().any(|factor| format_number(*factor) != "1") {
+        parts.push(format!("scaled {}", format_vector(&scale)));
+    }
+    Some(parts.join(", "))
+}
+
+/// World position of a `GlobalTransform`, serialized as its affine matrix of 12 floats with the
+/// translation last, or as a struct with a `translation`
+fn world_position(global: &Value) -> Option<Vec<f64>> {
+    match global {
+        Value::Array(items) if items.len() == 12 => items[9..].iter().map(Value::as_f64).collect(),
+        _ => vector(global.get("translation")?, 3),
+    }
+}
+
+/// `Visibility` combined with whether the entity inherited visibility and is in view
+fn visibility_summary(values: &Map<String, Value>) -> Option<String> {
+    let visibility = values.get(VISIBILITY_TYPE)?.as_str()?;
+    let mut summary = match visibility {
+        "Hidden" => "hidden".to_string(),
+        "Visible" => "always visible, whatever its parent".to_string(),
+        "Inherited" => "visible if its parent is".to_string(),
+        other => other.to_string(),
+    };
+    match values
+        .get(INHERITED_VISIBILITY_TYPE)
+        .and_then(Value::as_bool)
+    {
+        Some(false) if visibility != "Hidden" => summary.push_str(", but hidden by an ancestor"),
+        _ => {}
+    }
+    if let Some(in_view) = values.get(VIEW_VISIBILITY_TYPE).and_then(Value::as_bool) {
+        summary.push_str(if in_view {
+            ", rendered last frame"
+        } else {
+            ", not rendered last frame"
+        });
+    }
+    Some(summary)
+}
+
+/// What a `RenderTarget` renders to
+fn camera_target(target: &Value) -> Option<CameraTarget> {
+    let (kind, value) = match target {
+        Value::Object(fields) if fields.len() == 1 => fields.iter().next()?,
+        _ => return None,
+    };
+    let target = CameraTarget {
+        kind:    to_snake_case(kind),
+        entity:  None,
+        primary: false,
+        asset:   None,
+    };
+    Some(match kind.as_str() {
+        // `WindowRef` is `"Primary"` or `{"Entity": id}`
+        "Window" => CameraTarget {
+            entity: value.get("Entity").and_then(Value::as_u64),
+            primary: value.as_str() == Some("Primary"),
+            ..target
+        },
+        _ => CameraTarget {
+            asset: Some(value.clone()),
+            ..target
+        },
+    })
+}
+
+fn camera_summary(
+    camera: &Value,
+    values: &Map<String, Value>,
+    target: Option<&CameraTarget>,
+) -> String {
+    let active = camera
+        .get("is_active")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    let dimensions = if values.contains_key(CAMERA_3D_TYPE) {
+        "3D camera"
+    } else if values.contains_key(CAMERA_2D_TYPE) {
+        "2D camera"
+    } else {
+        "camera"
+    };
+    let mut summary = format!(
+        "{} {dimensions}",
+        if active { "active" } else { "inactive" }
+    );
+    if let Some(order) = camera.get("order").and_then(Value::as_i64) {
+        let _ = write!(summary, ", order {order}");
+    }
+    if let Some(target) = target {
+        let rendered_to = match (target.kind.as_str(), target.primary, target.entity) {
+            ("window", true, _) => "the primary window".to_string(),
+            ("window", _, Some(window)) => format!("window {window}"),
+            ("image", ..) => "an image".to_string(),
+            (kind, ..) => format!("a {}", kind.replace('_', " ")),
+        };
+        let _ = write!(summary, ", rendering to {rendered_to}");
+    }
+    summary
+}
+
+/// What a component's asset handle is for
+fn asset_kind(component: &str) -> &'static str {
+    match short_name(component) {
+        "Mesh3d" | "Mesh2d" => "mesh",
+        name if name.starts_with("MeshMaterial") => "material",
+        "SceneRoot" => "scene",
+        "Sprite" | "ImageNode" => "image",
+        _ => "an",
+    }
+}
+
+fn is_relationship(component: &str) -> bool {
+    component.ends_with(CHILDREN_COMPONENT_SUFFIX) || is_parent_component(component)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, character) in name.chars().enumerate() {
+        if character.is_uppercase() && index > 0 {
+            snake.push('_');
+        }
+        snake.push(character.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// A number rounded to 3 decimals
+fn round(number: f64) -> f64 {
+    (number * 1000.0).round() / 1000.0
+}
+
+/// A number rounded to 3 decimals without trailing zeros
+fn format_number(number: f64) -> String {
+    let text = format!("{:.3}", round(number) + 0.0);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+fn format_vector(values: &[f64]) -> String {
+    let values: Vec<String> = values.iter().map(|value| format_number(*value)).collect();
+    format!("({})", values.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+
+    #[test]
+    fn test_known_components_are_explained() {
+        let values = json!({
+            NAME_COMPONENT: "Main Camera",
+            TRANSFORM_TYPE: {
+                "translation": [0.0, 5.0, 10.0],
+                "rotation": [0.0, FRAC_1_SQRT_2, 0.0, FRAC_1_SQRT_2],
+                "scale": [1.0, 1.0, 1.0],
+            },
+            GLOBAL_TRANSFORM_TYPE: [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.5, 5.0, 10.0],
+            VISIBILITY_TYPE: "Inherited",
+            INHERITED_VISIBILITY_TYPE: false,
+            CAMERA_TYPE: { "is_active": true, "order": 1, "target": { "Window": { "Entity": 3 } } },
+            CAMERA_3D_TYPE: {},
+            "bevy_ecs::hierarchy::ChildOf": 7,
+            "bevy_render::mesh::components::Mesh3d": { "Strong": { "Index": { "index": 4, "generation": 0 } } },
+            "my_game::Player": { "speed": 2.0 },
+        });
+        let values = values.as_object().unwrap();
+        let mut components: Vec<String> = values.keys().cloned().collect();
+        components.push("my_game::Unreflected".to_string());
+
+        let explanation = explain(12, &components, values);
+        let facts = explanation["facts"].as_array().unwrap();
+        let fact = |component: &str| {
+            facts
+                .iter()
+                .find(|fact| fact["component"] == component)
+                .unwrap()["summary"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            fact("Transform"),
+            "at (0, 5, 10), rotated (0°, 90°, 0°) about x, y, z"
+        );
+        assert_eq!(fact("GlobalTransform"), "world position (0.5, 5, 10)");
+        assert_eq!(
+            fact("Visibility"),
+            "visible if its parent is, but hidden by an ancestor"
+        );
+        assert_eq!(
+            fact("Camera"),
+            "active 3D camera, order 1, rendering to window 3"
+        );
+        assert!(fact("Mesh3d").starts_with("uses mesh asset"));
+        assert_eq!(
+            explanation["relationships"],
+            json!({
+                "parent": 7,
+                "children": [],
+                "camera_target": { "kind": "window", "entity": 3 },
+            })
+        );
+        assert_eq!(
+            explanation["unexplained"],
+            json!(["my_game::Player", "my_game::Unreflected"])
+        );
+        assert!(
+            explanation["summary"]
+                .as_str()
+                .unwrap()
+                .starts_with("Entity 12 \"Main Camera\": at (0, 5, 10)")
+        );
+    }
+}
@@ -0,0 +1,539 @@
+//! Export entities as a Bevy scene
+//!
+//! Queries every entity (or those matching a filter) with all of their reflected components and
+//! writes them out as a `.scn.ron` document that `DynamicScene` can load. BRP returns component
+//! values as JSON, so each value is converted to RON using the type's registry schema to tell
+//! structs, tuples, lists, maps and enum variants apart.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PATH, JSON_FIELD_PORT, PARAM_FILTER,
+};
+use super::request_handler::registry_schema;
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_LIST, BRP_METHOD_QUERY, DESC_BEVY_EXPORT_SCENE, TOOL_BEVY_EXPORT_SCENE,
+};
+use crate::{BrpMcpService, config};
+
+/// Prefix of the `$ref` pointing at a type's schema
+const SCHEMA_REF_PREFIX: &str = "#/$defs/";
+
+/// Indentation of one nesting level, matching Bevy's own scene output
+const INDENT: &str = "    ";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_EXPORT_SCENE.into(),
+        description:  DESC_BEVY_EXPORT_SCENE.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_any_property(
+                PARAM_FILTER,
+                "Object selecting the entities to export, as in bevy_query. Properties: with (array), without (array). Default: all entities",
+                false,
+            )
+            .add_string_array_property(
+                JSON_FIELD_COMPONENTS,
+                "Fully-qualified component types to export (default: every registered component)",
+                false,
+            )
+            .add_string_property(
+                JSON_FIELD_PATH,
+                "File to write the scene to, e.g. assets/scenes/level.scn.ron. If omitted the scene is returned inline",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+
+    let filter = arguments
+        .get(PARAM_FILTER)
+        .filter(|filter| !filter.is_null());
+    let components = params::extract_optional_string_array(&arguments, JSON_FIELD_COMPONENTS);
+    let path = arguments.get(JSON_FIELD_PATH).and_then(Value::as_str);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let scene = export_scene(filter, components, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let mut data = json!({
+        "entity_count": scene.entity_count,
+        "component_count": scene.component_count,
+    });
+    let message = if let Some(path) = path {
+        let path = PathBuf::from(path);
+        std::fs::write(&path, &scene.ron).map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
+                "write scene file",
+                &path,
+                &e,
+            )))
+        })?;
+        data[JSON_FIELD_PATH] = json!(path.display().to_string());
+        format!(
+            "Exported {} entities to {}",
+            scene.entity_count,
+            path.display()
+        )
+    } else {
+        data["scene"] = json!(scene.ron);
+        format!("Exported {} entities", scene.entity_count)
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// An exported scene document
+struct ExportedScene {
+    ron:             String,
+    entity_count:    usize,
+    /// Number of distinct component types in the scene
+    component_count: usize,
+}
+
+/// Query the entities and render them as a scene
+async fn export_scene(
+    filter: Option<&Value>,
+    components: Option<Vec<String>>,
+    port: u16,
+) -> Result<ExportedScene> {
+    let rows = query_entities(filter, components, port).await?;
+
+    let schemas = match registry_schema(json!({}), Some(port)).await? {
+        BrpResult::Success(Some(schemas)) => schemas,
+        // Without schemas values are still converted, just by their JSON shape
+        _ => Value::Null,
+    };
+
+    Ok(render_scene(&rows, &SchemaIndex::new(&schemas)))
+}
+
+/// Query the entities matching `filter` with the given (default: all registered) components
+///
+/// Rows are returned in entity order.
+pub async fn query_entities(
+    filter: Option<&Value>,
+    components: Option<Vec<String>>,
+    port: u16,
+) -> Result<Vec<Value>> {
+    let types = match components {
+        Some(types) => types,
+        None => list_components(port).await?,
+    };
+
+    let mut query = json!({ "data": { "option": types } });
+    if let Some(filter) = filter {
+        query[PARAM_FILTER] = filter.clone();
+    }
+    let mut rows = match execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => rows,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to query entities: {}",
+                e.message
+            ))));
+        }
+    };
+    rows.sort_by_key(|row| row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64));
+    Ok(rows)
+}
+
+/// Every component type registered in the app
+async fn list_components(port: u16) -> Result<Vec<String>> {
+    match execute_brp_method(BRP_METHOD_LIST, None, Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(names))) => Ok(names
+            .iter()
+            .filter_map(|name| name.as_str().map(String::from))
+            .collect()),
+        BrpResult::Success(_) => Ok(Vec::new()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to list component types: {}",
+            e.message
+        )))),
+    }
+}
+
+/// Registry schemas by type path
+struct SchemaIndex<'a> {
+    schemas: HashMap<&'a str, &'a Value>,
+}
+
+impl<'a> SchemaIndex<'a> {
+    /// Index a registry schema response, which is an object keyed by type path or an array
+    fn new(schemas: &'a Value) -> Self {
+        let schemas = match schemas {
+            Value::Object(schemas) => schemas
+                .iter()
+                .map(|(type_path, schema)| (type_path.as_str(), schema))
+                .collect(),
+            Value::Array(schemas) => schemas
+                .iter()
+                .filter_map(|schema| Some((schema["typePath"].as_str()?, schema)))
+                .collect(),
+            _ => HashMap::new(),
+        };
+        Self { schemas }
+    }
+
+    fn get(&self, type_path: Option<&str>) -> Option<&'a Value> {
+        type_path.and_then(|type_path| self.schemas.get(type_path).copied())
+    }
+}
+
+/// Type path referenced by a field, item or variant entry of a schema
+fn referenced_type(entry: &Value) -> Option<&str> {
+    entry["type"]["$ref"]
+        .as_str()
+        .map(|reference| reference.trim_start_matches(SCHEMA_REF_PREFIX))
+}
+
+/// Render query rows as a `.scn.ron` document
+fn render_scene(rows: &[Value], schemas: &SchemaIndex) -> ExportedScene {
+    let mut writer = RonWriter {
+        out: String::new(),
+        schemas,
+    };
+    let mut component_types = std::collections::BTreeSet::new();
+
+    writer.out.push_str("(\n");
+    writer.out.push_str(INDENT);
+    writer.out.push_str("resources: {},\n");
+    writer.out.push_str(INDENT);
+    writer.out.push_str("entities: {\n");
+    for row in rows {
+        let Some(entity) = row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64) else {
+            continue;
+        };
+        let empty = Map::new();
+        let components = row
+            .get(JSON_FIELD_COMPONENTS)
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+
+        writer.line(2, &format!("{entity}: ("));
+        writer.line(3, "components: {");
+        for (type_path, value) in components {
+            component_types.insert(type_path.clone());
+            writer.indent(4);
+            writer.string(type_path);
+            writer.out.push_str(": ");
+            writer.value(value, Some(type_path), 4);
+            writer.out.push_str(",\n");
+        }
+        writer.line(3, "},");
+        writer.line(2, "),");
+    }
+    writer.out.push_str(INDENT);
+    writer.out.push_str("},\n)\n");
+
+    ExportedScene {
+        ron:             writer.out,
+        entity_count:    rows.len(),
+        component_count: component_types.len(),
+    }
+}
+
+/// Writes JSON values as RON, guided by registry schemas
+struct RonWriter<'a> {
+    out:     String,
+    schemas: &'a SchemaIndex<'a>,
+}
+
+impl RonWriter<'_> {
+    fn indent(&mut self, level: usize) {
+        self.out.push_str(&INDENT.repeat(level));
+    }
+
+    fn line(&mut self, level: usize, text: &str) {
+        self.indent(level);
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn string(&mut self, text: &str) {
+        // RON strings use the same escapes as JSON
+        self.out.push_str(&Value::from(text).to_string());
+    }
+
+    /// Write a value of the given type, continuing the current line at `level`
+    fn value(&mut self, value: &Value, type_path: Option<&str>, level: usize) {
+        let schema = self.schemas.get(type_path);
+        let kind = schema.and_then(|schema| schema["kind"].as_str());
+        match value {
+            Value::Null => self.out.push_str("None"),
+            Value::Bool(_) | Value::Number(_) => self.out.push_str(&value.to_string()),
+            Value::String(text) if kind == Some("Enum") && is_identifier(text) => {
+                self.out.push_str(text);
+            }
+            Value::String(text) => self.string(text),
+            Value::Array(items) => self.array(items, schema, kind, level),
+            Value::Object(map) => match (kind, schema) {
+                (Some("Enum"), Some(schema)) if map.len() == 1 => {
+                    self.variant(map, Some(schema), level);
+                }
+                (Some("Map"), Some(schema)) => self.map(map, schema, level),
+                (None, _) if map.len() == 1 && map.keys().all(|key| is_variant_name(key)) => {
+                    self.variant(map, None, level);
+                }
+                _ => self.fields(map, schema, level),
+            },
+        }
+    }
+
+    fn array(&mut self, items: &[Value], schema: Option<&Value>, kind: Option<&str>, level: usize) {
+        match kind {
+            Some("List" | "Array" | "Set") => {
+                let item_type = schema.and_then(|schema| referenced_type(&schema["items"]));
+                self.sequence(items, |_| item_type, ('[', ']'), level);
+            }
+            Some(_) => {
+                let prefix_items = schema.map(|schema| &schema["prefixItems"]);
+                self.sequence(
+                    items,
+                    |index| prefix_items.and_then(|prefix| referenced_type(&prefix[index])),
+                    ('(', ')'),
+                    level,
+                );
+            }
+            // Without a schema, short runs of numbers are most likely vectors or colors
+            None if (2..=4).contains(&items.len()) && items.iter().all(Value::is_number) => {
+                self.sequence(items, |_| None, ('(', ')'), level);
+            }
+            None => self.sequence(items, |_| None, ('[', ']'), level),
+        }
+    }
+
+    /// Write a list or tuple, on one line if it only holds scalars
+    fn sequence<'t>(
+        &mut self,
+        items: &[Value],
+        item_type: impl Fn(usize) -> Option<&'t str>,
+        (open, close): (char, char),
+        level: usize,
+    ) {
+        self.out.push(open);
+        if items.iter().all(is_scalar) {
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    self.out.push_str(", ");
+                }
+                self.value(item, item_type(index), level);
+            }
+        } else {
+            self.out.push('\n');
+            for (index, item) in items.iter().enumerate() {
+                self.indent(level + 1);
+                self.value(item, item_type(index), level + 1);
+                self.out.push_str(",\n");
+            }
+            self.indent(level);
+        }
+        self.out.push(close);
+    }
+
+    /// Write a struct as `(field: value, ...)`
+    fn fields(&mut self, map: &Map<String, Value>, schema: Option<&Value>, level: usize) {
+        if map.is_empty() {
+            self.out.push_str("()");
+            return;
+        }
+        self.out.push_str("(\n");
+        for (field, value) in map {
+            let field_type =
+                schema.and_then(|schema| referenced_type(&schema["properties"][field]));
+            self.indent(level + 1);
+            let _ = write!(self.out, "{field}: ");
+            self.value(value, field_type, level + 1);
+            self.out.push_str(",\n");
+        }
+        self.indent(level);
+        self.out.push(')');
+    }
+
+    /// Write a map as `{key: value, ...}`
+    fn map(&mut self, map: &Map<String, Value>, schema: &Value, level: usize) {
+        let key_type = referenced_type(&schema["keyType"]);
+        let value_type = referenced_type(&schema["valueType"]);
+        // JSON object keys are always strings, but numeric map keys must stay numbers in RON
+        let numeric_keys = self.schemas.get(key_type).is_some_and(|key_schema| {
+            matches!(key_schema["type"].as_str(), Some("uint" | "int" | "float"))
+        });
+
+        if map.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push_str("{\n");
+        for (key, value) in map {
+            self.indent(level + 1);
+            if numeric_keys {
+                self.out.push_str(key);
+            } else {
+                self.string(key);
+            }
+            self.out.push_str(": ");
+            self.value(value, value_type, level + 1);
+            self.out.push_str(",\n");
+        }
+        self.indent(level);
+        self.out.push('}');
+    }
+
+    /// Write an externally tagged enum value `{"Variant": payload}` as `Variant(...)`
+    fn variant(&mut self, map: &Map<String, Value>, schema: Option<&Value>, level: usize) {
+        let Some((name, payload)) = map.iter().next() else {
+            return;
+        };
+        let variant = schema.and_then(|schema| {
+            schema["oneOf"]
+                .as_array()?
+                .iter()
+                .find(|variant| variant["shortPath"].as_str() == Some(name))
+        });
+        self.out.push_str(name);
+
+        match payload {
+            // Struct variants: `Variant(field: value)`
+            Value::Object(fields) if variant.is_none_or(|v| v["kind"] == "Struct") => {
+                self.fields(fields, variant, level);
+            }
+            // Tuple variants with several fields: `Variant(a, b)`
+            Value::Array(items)
+                if variant
+                    .is_some_and(|v| v["prefixItems"].as_array().is_some_and(|p| p.len() > 1)) =>
+            {
+                let prefix_items = variant.map(|v| &v["prefixItems"]);
+                self.sequence(
+                    items,
+                    |index| prefix_items.and_then(|prefix| referenced_type(&prefix[index])),
+                    ('(', ')'),
+                    level,
+                );
+            }
+            // Newtype variants: `Variant(value)`
+            _ => {
+                let inner_type = variant.and_then(|v| referenced_type(&v["prefixItems"][0]));
+                self.out.push('(');
+                self.value(payload, inner_type, level);
+                self.out.push(')');
+            }
+        }
+    }
+}
+
+const fn is_scalar(value: &Value) -> bool {
+    !matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether an object key looks like an enum variant rather than a struct field
+fn is_variant_name(key: &str) -> bool {
+    is_identifier(key) && key.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values_follow_schema_kinds() {
+        let schemas = json!({
+            "game::Player": {
+                "kind": "Struct",
+                "properties": {
+                    "mode": { "type": { "$ref": "#/$defs/game::Mode" } },
+                    "scores": { "type": { "$ref": "#/$defs/alloc::vec::Vec<u32>" } },
+                    "pos": { "type": { "$ref": "#/$defs/glam::Vec3" } }
+                }
+            },
+            "game::Mode": {
+                "kind": "Enum",
+                "oneOf": [
+                    { "shortPath": "Idle" },
+                    { "shortPath": "Moving", "kind": "Struct", "properties": {} }
+                ]
+            },
+            "alloc::vec::Vec<u32>": { "kind": "List" },
+            "glam::Vec3": { "kind": "Struct" }
+        });
+        let rows = [json!({
+            "entity": 5,
+            "components": {
+                "game::Player": { "mode": "Idle", "pos": [1.0, 2.0, 3.0], "scores": [4, 5] },
+                "game::Tag": { "Loaded": { "speed": 2 } }
+            }
+        })];
+
+        let scene = render_scene(&rows, &SchemaIndex::new(&schemas));
+        assert_eq!(scene.entity_count, 1);
+        assert_eq!(scene.component_count, 2);
+        assert_eq!(
+            scene.ron,
+            r#"(
+    resources: {},
+    entities: {
+        5: (
+            components: {
+                "game::Player": (
+                    mode: Idle,
+                    pos: (1.0, 2.0, 3.0),
+                    scores: [4, 5],
+                ),
+                "game::Tag": Loaded(
+                    speed: 2,
+                ),
+            },
+        ),
+    },
+)
+"#
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Find entities by their `Name`
+//!
+//! Queries every entity with a `Name`, matches the names against an exact name or a regex and
+//! lists the components of each match, which otherwise takes a query, a filter and a
+//! `bevy/list` per entity.
+
+use futures::future::join_all;
+use regex::Regex;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITIES, JSON_FIELD_ENTITY,
+    JSON_FIELD_HOST, JSON_FIELD_PORT,
+};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::component_filter;
+use super::support::spawn_tagging::NAME_COMPONENT;
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_LIST, BRP_METHOD_QUERY, DESC_BEVY_FIND_ENTITY_BY_NAME, TOOL_BEVY_FIND_ENTITY_BY_NAME,
+};
+use crate::{BrpMcpService, config};
+
+/// Parameter holding the name or pattern
+const PARAM_NAME: &str = "name";
+
+/// Parameter making the name a regular expression
+const PARAM_REGEX: &str = "regex";
+
+/// Most matches whose components are listed
+const MAX_MATCHES: usize = 100;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_FIND_ENTITY_BY_NAME.into(),
+        description:  DESC_BEVY_FIND_ENTITY_BY_NAME.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_NAME,
+                "The exact name to find, or a regular expression if regex is true",
+                true,
+            )
+            .add_boolean_property(
+                PARAM_REGEX,
+                "Match names against name as a regular expression, e.g. ^Enemy \\d+$ (default: false)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let name = params::extract_required_string(&request, PARAM_NAME)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let regex = arguments
+        .get(PARAM_REGEX)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let name_matcher =
+        NameMatcher::new(&name, regex).map_err(|report| report_to_mcp_error(&report))?;
+    let candidates = named_entities(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let matches: Vec<(u64, String)> = candidates
+        .into_iter()
+        .filter(|(_, entity_name)| name_matcher.matches(entity_name))
+        .collect();
+    let truncated = matches.len() > MAX_MATCHES;
+
+    let entities = join_all(matches.iter().take(MAX_MATCHES).map(
+        |(entity, entity_name)| async move {
+            let components = list_components(*entity, port).await;
+            json!({
+                JSON_FIELD_ENTITY: entity,
+                PARAM_NAME: entity_name,
+                JSON_FIELD_COMPONENTS: components,
+            })
+        },
+    ))
+    .await;
+
+    let message = if matches.is_empty() {
+        format!("No entity has a name matching '{name}'")
+    } else {
+        format!(
+            "Found {} entities with a name matching '{name}'{}",
+            matches.len(),
+            if truncated {
+                format!(", listing the first {MAX_MATCHES}")
+            } else {
+                String::new()
+            }
+        )
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            JSON_FIELD_ENTITIES: entities,
+            "count": matches.len(),
+            "truncated": truncated,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// An exact name or a regex
+enum NameMatcher {
+    Exact(String),
+    Regex(Regex),
+}
+
+impl NameMatcher {
+    fn new(name: &str, regex: bool) -> Result<Self> {
+        if !regex {
+            return Ok(Self::Exact(name.to_string()));
+        }
+        Regex::new(name).map(Self::Regex).map_err(|e| {
+            error_stack::Report::new(Error::invalid(
+                &format!("parameter '{PARAM_NAME}'"),
+                format!("'{name}' is not a valid regular expression: {e}"),
+            ))
+        })
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Exact(expected) => expected == name,
+            Self::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// Every entity with a `Name`, with the name, in entity order
+pub async fn named_entities(port: u16) -> Result<Vec<(u64, String)>> {
+    let query = json!({ "data": { JSON_FIELD_COMPONENTS: [NAME_COMPONENT] } });
+    let rows = match execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => rows,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to query named entities: {}",
+                e.message
+            ))));
+        }
+    };
+
+    let mut entities: Vec<(u64, String)> = rows.iter().filter_map(read_name).collect();
+    entities.sort_unstable();
+    Ok(entities)
+}
+
+/// The entity and name of a query row
+///
+/// `Name` serializes as a plain string, older apps may send `{"name": ..}`.
+fn read_name(row: &Value) -> Option<(u64, String)> {
+    let entity = row.get(JSON_FIELD_ENTITY)?.as_u64()?;
+    let name = row.get(JSON_FIELD_COMPONENTS)?.get(NAME_COMPONENT)?;
+    let name = name
+        .as_str()
+        .or_else(|| name.get(PARAM_NAME).and_then(Value::as_str))?;
+    Some((entity, name.to_string()))
+}
+
+/// The sorted component types of an entity, or an empty list if it can't be listed
+pub async fn list_components(entity: u64, port: u16) -> Vec<String> {
+    let params = json!({ JSON_FIELD_ENTITY: entity });
+    let Ok(BrpResult::Success(Some(mut components))) =
+        execute_brp_method(BRP_METHOD_LIST, Some(params), Some(port)).await
+    else {
+        return Vec::new();
+    };
+    component_filter::apply(BRP_METHOD_LIST, &mut components);
+
+    let mut components: Vec<String> = components
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|component| component.as_str().map(String::from))
+        .collect();
+    components.sort_unstable();
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_names_are_read_and_matched() {
+        let row = |entity: u64, name: Value| json!({ "entity": entity, "components": { NAME_COMPONENT: name } });
+        let names: Vec<(u64, String)> = [
+            row(4, json!("Enemy 1")),
+            row(7, json!({ "name": "Enemy 12", "hash": 1 })),
+            row(9, json!("Player")),
+            json!({ "entity": 11, "components": {} }),
+        ]
+        .iter()
+        .filter_map(read_name)
+        .collect();
+        assert_eq!(names.len(), 3);
+        assert_eq!(names[1], (7, "Enemy 12".to_string()));
+
+        let matching = |matcher: &NameMatcher| {
+            names
+                .iter()
+                .filter(|(_, name)| matcher.matches(name))
+                .map(|(entity, _)| *entity)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            matching(&NameMatcher::new("Player", false).unwrap()),
+            vec![9]
+        );
+        assert_eq!(
+            matching(&NameMatcher::new("Enemy", false).unwrap()),
+            Vec::<u64>::new()
+        );
+        assert_eq!(
+            matching(&NameMatcher::new(r"^Enemy \d+$", true).unwrap()),
+            vec![4, 7]
+        );
+        assert!(NameMatcher::new("Enemy (", true).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+//! Performance diagnostics of a running app
+//!
+//! Reads Bevy's `DiagnosticsStore` resource with `bevy/get_resource` and normalizes each
+//! diagnostic, such as `fps`, `frame_time` or `entity_count`, to its latest value, smoothed value
+//! and history average. With a window, the resource is polled and each diagnostic's samples are
+//! reduced to min/avg/max, so a perf regression shows up as numbers an agent can compare.
+//!
+//! `DiagnosticsStore` is only readable over BRP when the app registers it for reflection, or
+//! mirrors it into a reflected resource of its own, which can be read with `resource`.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT, JSON_FIELD_RESOURCE,
+};
+use super::support::brp_client::{BrpError, BrpResult, execute_brp_method};
+use crate::error::{ErrorCode, report_to_mcp_error};
+use crate::support::progress::{self, Progress};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_GET_RESOURCE, DESC_BEVY_GET_DIAGNOSTICS, TOOL_BEVY_GET_DIAGNOSTICS};
+use crate::{BrpMcpService, config};
+
+/// Type path of Bevy's diagnostics resource
+pub const DIAGNOSTICS_STORE: &str = "bevy_diagnostic::diagnostic::DiagnosticsStore";
+
+/// Parameter limiting the result to some diagnostics
+const PARAM_DIAGNOSTICS: &str = "diagnostics";
+
+/// Parameter giving how long to poll, in milliseconds
+const PARAM_WINDOW_MS: &str = "window_ms";
+
+/// Parameter giving the time between polls, in milliseconds
+const PARAM_INTERVAL_MS: &str = "interval_ms";
+
+/// Longest polling window
+const MAX_WINDOW_MS: u64 = 60_000;
+
+/// Time between polls when no interval is given
+const DEFAULT_INTERVAL_MS: u64 = 250;
+
+/// Shortest time between polls
+const MIN_INTERVAL_MS: u64 = 10;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_GET_DIAGNOSTICS.into(),
+        description:  DESC_BEVY_GET_DIAGNOSTICS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_array_property(
+                PARAM_DIAGNOSTICS,
+                "Only these diagnostics, by path, e.g. [\"fps\", \"frame_time\"] (default: all)",
+                false,
+            )
+            .add_number_property(
+                PARAM_WINDOW_MS,
+                &format!(
+                    "Poll for this many milliseconds and return min/avg/max of each diagnostic, up \
+                     to {MAX_WINDOW_MS} (default: 0, a single reading)"
+                ),
+                false,
+            )
+            .add_number_property(
+                PARAM_INTERVAL_MS,
+                &format!(
+                    "Milliseconds between polls within the window (default: {DEFAULT_INTERVAL_MS})"
+                ),
+                false,
+            )
+            .add_string_property(
+                JSON_FIELD_RESOURCE,
+                &format!(
+                    "Resource holding the diagnostics, for apps that mirror them into a reflected \
+                     resource (default: {DIAGNOSTICS_STORE})"
+                ),
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let resource = arguments
+        .get(JSON_FIELD_RESOURCE)
+        .and_then(Value::as_str)
+        .unwrap_or(DIAGNOSTICS_STORE)
+        .to_string();
+    let wanted: Vec<String> = arguments
+        .get(PARAM_DIAGNOSTICS)
+        .and_then(Value::as_array)
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let window_ms = arguments
+        .get(PARAM_WINDOW_MS)
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+        .min(MAX_WINDOW_MS);
+    let interval_ms = arguments
+        .get(PARAM_INTERVAL_MS)
+        .and_then(Value::as_u64)
+        .unwrap_or(DEFAULT_INTERVAL_MS)
+        .max(MIN_INTERVAL_MS);
+
+    let reader = Reader {
+        resource,
+        wanted,
+        port,
+    };
+    let first = match reader.read().await? {
+        Ok(diagnostics) => diagnostics,
+        Err(e) => return Ok(json_response_to_result(&unavailable_response(&reader, &e))),
+    };
+
+    let data = if window_ms == 0 {
+        json!({
+            JSON_FIELD_RESOURCE: reader.resource,
+            PARAM_DIAGNOSTICS: first,
+        })
+    } else {
+        let window = Duration::from_millis(window_ms);
+        let interval = Duration::from_millis(interval_ms);
+        let summaries = progress::scope(
+            Progress::new(&context),
+            Box::pin(reader.poll(first, window, interval)),
+        )
+        .await?;
+        json!({
+            JSON_FIELD_RESOURCE: reader.resource,
+            PARAM_WINDOW_MS: window_ms,
+            PARAM_INTERVAL_MS: interval_ms,
+            PARAM_DIAGNOSTICS: summaries,
+        })
+    };
+
+    let count = data[PARAM_DIAGNOSTICS].as_array().map_or(0, Vec::len);
+    let response = ResponseBuilder::success()
+        .message(if window_ms == 0 {
+            format!("Read {count} diagnostics")
+        } else {
+            format!("Summarized {count} diagnostics over {window_ms}ms")
+        })
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Reads the diagnostics resource of an app
+struct Reader {
+    resource: String,
+    /// Diagnostic paths to keep; empty keeps all
+    wanted:   Vec<String>,
+    port:     u16,
+}
+
+impl Reader {
+    /// The current diagnostics, or the BRP error when the resource can't be read
+    async fn read(&self) -> Result<Result<Vec<DiagnosticReading>, BrpError>, McpError> {
+        let params = json!({ JSON_FIELD_RESOURCE: self.resource });
+        match execute_brp_method(BRP_METHOD_GET_RESOURCE, Some(params), Some(self.port))
+            .await
+            .map_err(|report| report_to_mcp_error(&report))?
+        {
+            BrpResult::Success(data) => {
+                let value = data.map_or(Value::Null, |data| {
+                    data.get("value").cloned().unwrap_or(data)
+                });
+                Ok(Ok(read_diagnostics(&value)
+                    .into_iter()
+                    .filter(|reading| self.wanted.is_empty() || self.wanted.contains(&reading.path))
+                    .collect()))
+            }
+            BrpResult::Error(e) => Ok(Err(e)),
+        }
+    }
+
+    /// Poll until the window ends, reducing each diagnostic's latest values to a summary
+    ///
+    /// Polls that fail are skipped; the first reading counts as a sample.
+    async fn poll(
+        &self,
+        first: Vec<DiagnosticReading>,
+        window: Duration,
+        interval: Duration,
+    ) -> Result<Vec<DiagnosticSummary>, McpError> {
+        let mut summaries: BTreeMap<String, DiagnosticSummary> = BTreeMap::new();
+        let mut add = |readings: Vec<DiagnosticReading>| {
+            for reading in readings {
+                summaries
+                    .entry(reading.path.clone())
+                    .or_insert_with(|| DiagnosticSummary::new(&reading))
+                    .add(reading.value);
+            }
+        };
+        add(first);
+
+        let started = Instant::now();
+        let window_ms = u32::try_from(window.as_millis()).unwrap_or(u32::MAX);
+        while started.elapsed() + interval <= window {
+            tokio::time::sleep(interval).await;
+            if let Ok(readings) = self.read().await? {
+                add(readings);
+            }
+            let elapsed = u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX);
+            if let Some(progress) = progress::current() {
+                progress
+                    .report(elapsed.min(window_ms), Some(window_ms))
+                    .await;
+            }
+        }
+        Ok(summaries.into_values().collect())
+    }
+}
+
+/// Error response for an app whose diagnostics resource can't be read
+fn unavailable_response(reader: &Reader, error: &BrpError) -> JsonResponse {
+    ResponseBuilder::error()
+        .message(format!(
+            "Failed to read diagnostics from {}: {}",
+            reader.resource, error.message
+        ))
+        .error_code(ErrorCode::from_brp_code(error.code, false))
+        .details(json!({
+            JSON_FIELD_RESOURCE: reader.resource,
+            "guidance": "Add FrameTimeDiagnosticsPlugin (and EntityCountDiagnosticsPlugin) to the app and register DiagnosticsStore for reflection, or copy the diagnostics into a reflected resource each frame and pass its type path as resource",
+        }))
+        .build()
+}
+
+/// One diagnostic as read from the resource
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiagnosticReading {
+    pub path:     String,
+    /// Most recent measurement
+    pub value:    Option<f64>,
+    /// Exponential moving average Bevy keeps
+    pub smoothed: Option<f64>,
+    /// Average of the measurements in the history
+    pub average:  Option<f64>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub suffix:   String,
+}
+
+/// Readings of one diagnostic over a window
+#[derive(Debug, Serialize)]
+struct DiagnosticSummary {
+    path:    String,
+    min:     Option<f64>,
+    avg:     Option<f64>,
+    max:     Option<f64>,
+    last:    Option<f64>,
+    samples: usize,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    suffix:  String,
+    #[serde(skip)]
+    sum:     f64,
+}
+
+impl DiagnosticSummary {
+    fn new(reading: &DiagnosticReading) -> Self {
+        Self {
+            path:    reading.path.clone(),
+            min:     None,
+            avg:     None,
+            max:     None,
+            last:    None,
+            samples: 0,
+            suffix:  reading.suffix.clone(),
+            sum:     0.0,
+        }
+    }
+
+    fn add(&mut self, value: Option<f64>) {
+        let Some(value) = value else {
+            return;
+        };
+        self.samples += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self.avg = Some(self.sum / f64::from(u32::try_from(self.samples).unwrap_or(u32::MAX)));
+        self.last = Some(value);
+    }
+}
+
+/// The diagnostics of a diagnostics resource, sorted by path
+///
+/// Accepts the reflected `DiagnosticsStore`, whose `diagnostics` map holds `Diagnostic`s as an
+/// object or a list of entries, and plain objects of paths to numbers from a mirrored resource.
+pub fn read_diagnostics(value: &Value) -> Vec<DiagnosticReading> {
+    let diagnostics = value.get(PARAM_DIAGNOSTICS).unwrap_or(value);
+    let mut readings: Vec<DiagnosticReading> = match diagnostics {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(key, diagnostic)| read_diagnostic(Some(key), diagnostic))
+            .collect(),
+        Value::Array(entries) => entries
+            .iter()
+            .filter_map(|entry| match entry {
+                // A map serialized as `[key, value]` pairs
+                Value::Array(pair) if pair.len() == 2 => {
+                    read_diagnostic(path_name(&pair[0]).as_deref(), &pair[1])
+                }
+                diagnostic => read_diagnostic(None, diagnostic),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    readings.sort_by(|a, b| a.path.cmp(&b.path));
+    readings
+}
+
+/// One diagnostic, from a `Diagnostic` or a plain number
+fn read_diagnostic(key: Option<&str>, diagnostic: &Value) -> Option<DiagnosticReading> {
+    if let Some(value) = diagnostic.as_f64() {
+        return Some(DiagnosticReading {
+            path:     key?.to_string(),
+            value:    Some(value),
+            smoothed: None,
+            average:  None,
+            suffix:   String::new(),
+        });
+    }
+
+    if !diagnostic.is_object() {
+        return None;
+    }
+
+    let path = diagnostic
+        .get("path")
+        .and_then(path_name)
+        .or_else(|| key.map(String::from))?;
+    let history: Vec<f64> = diagnostic
+        .get("history")
+        .and_then(Value::as_array)
+        .map(|history| {
+            history
+                .iter()
+                .filter_map(|measurement| {
+                    measurement
+                        .as_f64()
+                        .or_else(|| measurement.get("value").and_then(Value::as_f64))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let average = (!history.is_empty()).then(|| {
+        history.iter().sum::<f64>() / f64::from(u32::try_from(history.len()).unwrap_or(u32::MAX))
+    });
+
+    Some(DiagnosticReading {
+        path,
+        value: history.last().copied(),
+        smoothed: diagnostic.get("ema").and_then(Value::as_f64),
+        average,
+        suffix: diagnostic
+            .get("suffix")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// The name of a `DiagnosticPath`, serialized as a string or as `{"path": ..}`
+fn path_name(path: &Value) -> Option<String> {
+    path.as_str()
+        .or_else(|| path.get("path").and_then(Value::as_str))
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_are_normalized_and_summarized() {
+        let store = json!({
+            "diagnostics": {
+                "fps": {
+                    "path": { "path": "fps", "hash": 1 },
+                    "suffix": "",
+                    "history": [{ "time": 1.0, "value": 58.0 }, { "time": 1.1, "value": 62.0 }],
+                    "ema": 60.5,
+                },
+                "frame_time": {
+                    "path": "frame_time",
+                    "suffix": "ms",
+                    "history": [],
+                },
+            }
+        });
+        let readings = read_diagnostics(&store);
+        assert_eq!(
+            readings,
+            vec![
+                DiagnosticReading {
+                    path:     "fps".to_string(),
+                    value:    Some(62.0),
+                    smoothed: Some(60.5),
+                    average:  Some(60.0),
+                    suffix:   String::new(),
+                },
+                DiagnosticReading {
+                    path:     "frame_time".to_string(),
+                    value:    None,
+                    smoothed: None,
+                    average:  None,
+                    suffix:   "ms".to_string(),
+                },
+            ]
+        );
+
+        let mirrored = read_diagnostics(&json!({ "entity_count": 120, "label": "x" }));
+        assert_eq!(mirrored.len(), 1);
+        assert_eq!(mirrored[0].value, Some(120.0));
+
+        let mut summary = DiagnosticSummary::new(&readings[0]);
+        for value in [Some(60.0), None, Some(30.0), Some(45.0)] {
+            summary.add(value);
+        }
+        assert_eq!(
+            (
+                summary.min,
+                summary.avg,
+                summary.max,
+                summary.last,
+                summary.samples
+            ),
+            (Some(30.0), Some(45.0), Some(60.0), Some(45.0), 3)
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//! Inspect the hierarchy around an entity
+//!
+//! Walks the parent/children relationship components with repeated `bevy/list` and `bevy/get`
+//! calls and returns the ancestors and a nested tree of descendants, saving agents many
+//! round-trips when they need to understand scene structure.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PORT,
+};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_GET, BRP_METHOD_LIST, DESC_BEVY_GET_HIERARCHY, TOOL_BEVY_GET_HIERARCHY,
+};
+use crate::{BrpMcpService, config};
+
+/// Parameter limiting how many levels of descendants are walked
+const PARAM_MAX_DEPTH: &str = "max_depth";
+
+/// Default number of descendant levels to walk
+const DEFAULT_MAX_DEPTH: u64 = 10;
+
+/// Upper bound on the number of entities inspected in a single call
+const MAX_NODES: usize = 1000;
+
+/// Type name suffixes of the components linking an entity to its parent.
+/// `ChildOf` is the Bevy 0.16 name, `Parent` the name used by earlier versions.
+pub const PARENT_COMPONENT_SUFFIXES: [&str; 2] = ["::ChildOf", "::Parent"];
+
+/// Type name suffix of the component listing an entity's children
+pub const CHILDREN_COMPONENT_SUFFIX: &str = "::Children";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_GET_HIERARCHY.into(),
+        description:  DESC_BEVY_GET_HIERARCHY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_ENTITY,
+                "The entity ID to inspect the hierarchy of",
+                true,
+            )
+            .add_number_property(
+                PARAM_MAX_DEPTH,
+                &format!(
+                    "Maximum number of descendant levels to walk (default: {DEFAULT_MAX_DEPTH})"
+                ),
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+
+    let entity_id = params::extract_required_u64(&arguments, JSON_FIELD_ENTITY, "entity")?;
+    let max_depth =
+        params::extract_optional_numeric(&arguments, PARAM_MAX_DEPTH, DEFAULT_MAX_DEPTH);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let hierarchy = get_hierarchy(entity_id, max_depth, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let message = format!(
+        "Retrieved hierarchy for entity {entity_id}: {} ancestors, {} nodes in tree",
+        hierarchy.ancestors.len(),
+        hierarchy.nodes.len()
+    );
+    let data = json!({
+        JSON_FIELD_ENTITY: entity_id,
+        "ancestors": hierarchy.ancestors_json(),
+        "tree": hierarchy.tree_json(entity_id),
+        "node_count": hierarchy.nodes.len(),
+        "truncated": hierarchy.truncated,
+    });
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Components and relationships of a single entity
+struct HierarchyNode {
+    components: Vec<String>,
+    parent:     Option<u64>,
+    children:   Vec<u64>,
+}
+
+/// The inspected hierarchy around an entity
+pub struct Hierarchy {
+    /// Ancestors ordered from the root down to the immediate parent
+    ancestors: Vec<(u64, Vec<String>)>,
+    /// The inspected entity and its descendants
+    nodes:     HashMap<u64, HierarchyNode>,
+    /// Whether the walk stopped early because of `max_depth` or [`MAX_NODES`]
+    truncated: bool,
+}
+
+impl Hierarchy {
+    fn ancestors_json(&self) -> Value {
+        Value::Array(
+            self.ancestors
+                .iter()
+                .map(|(entity, components)| {
+                    json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: components })
+                })
+                .collect(),
+        )
+    }
+
+    /// Number of descendants walked
+    pub fn descendant_count(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+
+    /// Whether the walk stopped early
+    pub const fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Root of the tree holding the inspected entity, which is the entity itself without ancestors
+    pub fn root(&self, entity: u64) -> u64 {
+        self.ancestors.first().map_or(entity, |(root, _)| *root)
+    }
+
+    /// An entity and its walked descendants by depth, deepest first, so every entity comes
+    /// after its descendants
+    pub fn levels_bottom_up(&self, entity: u64) -> Vec<Vec<u64>> {
+        let mut levels = Vec::new();
+        let mut visited = HashSet::new();
+        let mut level: Vec<u64> = self
+            .nodes
+            .contains_key(&entity)
+            .then_some(entity)
+            .into_iter()
+            .collect();
+        while !level.is_empty() {
+            visited.extend(level.iter().copied());
+            let next = level
+                .iter()
+                .filter_map(|entity| self.nodes.get(entity))
+                .flat_map(|node| node.children.iter().copied())
+                .filter(|child| self.nodes.contains_key(child) && !visited.contains(child))
+                .collect();
+            levels.push(level);
+            level = next;
+        }
+        levels.reverse();
+        levels
+    }
+
+    /// Nested tree of an entity's walked descendants
+    pub fn tree_json(&self, entity: u64) -> Value {
+        let Some(node) = self.nodes.get(&entity) else {
+            return json!({ JSON_FIELD_ENTITY: entity });
+        };
+
+        let children: Vec<Value> = node
+            .children
+            .iter()
+            .filter(|child| self.nodes.contains_key(child))
+            .map(|child| self.tree_json(*child))
+            .collect();
+
+        json!({
+            JSON_FIELD_ENTITY: entity,
+            JSON_FIELD_COMPONENTS: node.components,
+            "children": children,
+        })
+    }
+}
+
+/// Walk the ancestors and descendants of an entity
+pub async fn get_hierarchy(entity_id: u64, max_depth: u64, port: u16) -> Result<Hierarchy> {
+    let mut nodes = HashMap::new();
+    let mut truncated = false;
+
+    // Breadth-first walk of the descendants
+    let mut queue = VecDeque::from([(entity_id, 0_u64)]);
+    while let Some((entity, depth)) = queue.pop_front() {
+        if nodes.contains_key(&entity) {
+            continue;
+        }
+        if nodes.len() >= MAX_NODES {
+            truncated = true;
+            break;
+        }
+
+        let node = fetch_node(entity, port).await?;
+        if depth < max_depth {
+            queue.extend(node.children.iter().map(|child| (*child, depth + 1)));
+        } else if !node.children.is_empty() {
+            truncated = true;
+        }
+        nodes.insert(entity, node);
+    }
+
+    // Walk up the parent chain, guarding against cycles
+    let mut ancestors = Vec::new();
+    let mut visited = HashSet::from([entity_id]);
+    let mut next_parent = nodes.get(&entity_id).and_then(|node| node.parent);
+    while let Some(parent) = next_parent {
+        if !visited.insert(parent) || visited.len() > MAX_NODES {
+            break;
+        }
+        let node = fetch_node(parent, port).await?;
+        next_parent = node.parent;
+        ancestors.push((parent, node.components));
+    }
+    ancestors.reverse();
+
+    Ok(Hierarchy {
+        ancestors,
+        nodes,
+        truncated,
+    })
+}
+
+/// Fetch the component names and hierarchy links of a single entity
+async fn fetch_node(entity: u64, port: u16) -> Result<HierarchyNode> {
+    let components: Vec<String> = match execute_brp_method(
+        BRP_METHOD_LIST,
+        Some(json!({ JSON_FIELD_ENTITY: entity })),
+        Some(port),
+    )
+    .await?
+    {
+        BrpResult::Success(Some(Value::Array(names))) => names
+            .iter()
+            .filter_map(|name| name.as_str().map(String::from))
+            .collect(),
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to list components of entity {entity}: {}",
+                e.message
+            ))));
+        }
+    };
+
+    let parent_component = components.iter().find(|name| is_parent_component(name));
+    let children_component = components
+        .iter()
+        .find(|name| name.ends_with(CHILDREN_COMPONENT_SUFFIX));
+
+    let links: Vec<&String> = parent_component
+        .into_iter()
+        .chain(children_component)
+        .collect();
+    let linked = if links.is_empty() {
+        Value::Null
+    } else {
+        match execute_brp_method(
+            BRP_METHOD_GET,
+            Some(json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: links })),
+            Some(port),
+        )
+        .await?
+        {
+            BrpResult::Success(Some(value)) => value,
+            BrpResult::Success(None) => Value::Null,
+            BrpResult::Error(e) => {
+                return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                    "Failed to get hierarchy components of entity {entity}: {}",
+                    e.message
+                ))));
+            }
+        }
+    };
+
+    // Non-strict `bevy/get` wraps the values in a `components` object
+    let values = linked.get(JSON_FIELD_COMPONENTS).unwrap_or(&linked);
+    let parent = parent_component
+        .and_then(|name| values.get(name))
+        .and_then(|value| entity_ids(value).into_iter().next());
+    let children = children_component
+        .and_then(|name| values.get(name))
+        .map(entity_ids)
+        .unwrap_or_default();
+
+    Ok(HierarchyNode {
+        components,
+        parent,
+        children,
+    })
+}
+
+/// Whether a component type links an entity to its parent
+pub fn is_parent_component(type_name: &str) -> bool {
+    PARENT_COMPONENT_SUFFIXES
+        .iter()
+        .any(|suffix| type_name.ends_with(suffix))
+}
+
+/// The parent link components registered in the app on a port
+pub async fn registered_parent_components(port: u16) -> Result<Vec<String>> {
+    match execute_brp_method(BRP_METHOD_LIST, None, Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(names))) => Ok(names
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|name| is_parent_component(name))
+            .map(String::from)
+            .collect()),
+        BrpResult::Success(_) => Ok(Vec::new()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to list registered components: {}",
+            e.message
+        )))),
+    }
+}
+
+/// The parent and children of an entity, from its serialized components
+pub fn relationships(components: &Map<String, Value>) -> (Option<u64>, Vec<u64>) {
+    let link = |matches: &dyn Fn(&str) -> bool| {
+        components
+            .iter()
+            .find(|(name, _)| matches(name))
+            .map(|(_, value)| entity_ids(value))
+            .unwrap_or_default()
+    };
+    let parent = link(&is_parent_component);
+    let children = link(&|name| name.ends_with(CHILDREN_COMPONENT_SUFFIX));
+    (parent.first().copied(), children)
+}
+
+/// Collect every entity ID in a serialized relationship component.
+///
+/// Handles both the bare forms (`42`, `[1, 2]`) and wrapped forms (`{"parent": 42}`).
+fn entity_ids(value: &Value) -> Vec<u64> {
+    match value {
+        Value::Number(number) => number.as_u64().into_iter().collect(),
+        Value::Array(values) => values.iter().flat_map(entity_ids).collect(),
+        Value::Object(map) => map.values().flat_map(entity_ids).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_ids_from_relationship_shapes() {
+        assert_eq!(entity_ids(&json!(42)), vec![42]);
+        assert_eq!(entity_ids(&json!([1, 2, 3])), vec![1, 2, 3]);
+        assert_eq!(entity_ids(&json!({ "parent": 7 })), vec![7]);
+        assert!(entity_ids(&json!("not an entity")).is_empty());
+    }
+
+    #[test]
+    fn test_tree_json_nests_children() {
+        let nodes = HashMap::from([
+            (
+                1,
+                HierarchyNode {
+                    components: vec!["Name".to_string()],
+                    parent:     None,
+                    children:   vec![2],
+                },
+            ),
+            (
+                2,
+                HierarchyNode {
+                    components: vec!["Transform".to_string()],
+                    parent:     Some(1),
+                    children:   vec![],
+                },
+            ),
+        ]);
+        let hierarchy = Hierarchy {
+            ancestors: vec![],
+            nodes,
+            truncated: false,
+        };
+
+        assert_eq!(hierarchy.levels_bottom_up(1), vec![vec![2], vec![1]]);
+        assert_eq!(
+            hierarchy.tree_json(1),
+            json!({
+                "entity": 1,
+                "components": ["Name"],
+                "children": [{ "entity": 2, "components": ["Transform"], "children": [] }]
+            })
+        );
+    }
+}
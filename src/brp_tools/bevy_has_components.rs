@@ -0,0 +1,209 @@
+//! Check which of a list of components an entity has
+//!
+//! Asks with a query's `has` list instead of `bevy/get`, so a component the entity lacks, or one
+//! that can't be serialized, is reported as `false` rather than failing the call. So are types the
+//! app doesn't have registered, which `bevy/get` rejects.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_ENTITY_CHECK,
+    JSON_FIELD_HOST, JSON_FIELD_PORT, PARAM_DATA,
+};
+use super::request_handler::{check_missing_entity, resolve_type_names};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, ErrorCode, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_QUERY, DESC_BEVY_HAS_COMPONENTS, TOOL_BEVY_HAS_COMPONENTS};
+use crate::{BrpMcpService, config};
+
+/// Field of a query's `data` and of its rows listing component presence
+const FIELD_HAS: &str = "has";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_HAS_COMPONENTS.into(),
+        description:  DESC_BEVY_HAS_COMPONENTS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(JSON_FIELD_ENTITY, "The entity ID to check", true)
+            .add_string_array_property(
+                JSON_FIELD_COMPONENTS,
+                "Component types to check for, fully-qualified or short names",
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let entity = params::extract_required_number(&request, JSON_FIELD_ENTITY)?;
+    let components =
+        params::extract_optional_string_array_from_request(&request, JSON_FIELD_COMPONENTS)?
+            .unwrap_or_default();
+    if components.is_empty() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{JSON_FIELD_COMPONENTS}'"),
+                "list at least one component type",
+            ),
+        )));
+    }
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let response = check(entity, components, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Query the presence of components on an entity and build the response
+async fn check(
+    entity: u64,
+    components: Vec<String>,
+    port: u16,
+) -> crate::error::Result<JsonResponse> {
+    let mut query = json!({ PARAM_DATA: { FIELD_HAS: components } });
+    let resolved = resolve_type_names(BRP_METHOD_QUERY, &mut query, port).await;
+    if !resolved.ambiguous.is_empty() {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{JSON_FIELD_COMPONENTS}'"),
+            resolved.ambiguity_message(),
+        )));
+    }
+    let components: Vec<String> =
+        serde_json::from_value(query[PARAM_DATA][FIELD_HAS].clone()).unwrap_or_default();
+
+    let rows = match execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => rows,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to query components: {}",
+                e.message
+            ))));
+        }
+    };
+
+    let Some(presence) = presence(&rows, entity, &components) else {
+        let params = json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: components });
+        let entity_check = check_missing_entity(entity, Some(&params), port).await;
+        return Ok(ResponseBuilder::error()
+            .message(format!("Entity {entity} doesn't exist"))
+            .error_code(ErrorCode::EntityNotFound)
+            .details(json!({ JSON_FIELD_ENTITY_CHECK: entity_check }))
+            .build());
+    };
+
+    let present = presence
+        .values()
+        .filter(|has| has.as_bool() == Some(true))
+        .count();
+    let mut data = json!({
+        JSON_FIELD_ENTITY: entity,
+        FIELD_HAS: presence,
+        "all": present == components.len(),
+    });
+    if let Some((field, value)) = resolved.response_field() {
+        data[field] = value;
+    }
+
+    Ok(ResponseBuilder::success()
+        .message(format!(
+            "Entity {entity} has {present} of {} components",
+            components.len()
+        ))
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// Whether an entity has each component, from the rows of a `has` query, if the entity exists
+fn presence(rows: &[Value], entity: u64, components: &[String]) -> Option<Map<String, Value>> {
+    let row = rows
+        .iter()
+        .find(|row| row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64) == Some(entity))?;
+    let answered = row.get(FIELD_HAS);
+    Some(
+        components
+            .iter()
+            .map(|component| {
+                let has = answered
+                    .and_then(|answered| answered.get(component))
+                    .and_then(Value::as_bool);
+                (component.clone(), Value::Bool(has == Some(true)))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    const TRANSFORM: &str = "bevy_transform::components::transform::Transform";
+    const SPRITE: &str = "bevy_sprite::sprite::Sprite";
+    const MISSING: &str = "my_game::Unregistered";
+
+    #[tokio::test]
+    async fn test_presence_is_reported_per_component() {
+        let server = MockBrpServer::start().await;
+        server.respond(
+            BRP_METHOD_QUERY,
+            json!([
+                { "entity": 3, "components": {}, "has": { TRANSFORM: false, SPRITE: false } },
+                { "entity": 7, "components": {}, "has": { TRANSFORM: true, SPRITE: false } },
+            ]),
+        );
+        let components = vec![
+            TRANSFORM.to_string(),
+            SPRITE.to_string(),
+            MISSING.to_string(),
+        ];
+
+        let response = check(7, components.clone(), server.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap_or_default())
+            .unwrap_or_default();
+        assert_eq!(response["status"], "success");
+        assert_eq!(
+            response["data"][FIELD_HAS],
+            json!({ TRANSFORM: true, SPRITE: false, MISSING: false })
+        );
+        assert_eq!(response["data"]["all"], false);
+
+        let requests = server.requests_for(BRP_METHOD_QUERY);
+        assert_eq!(
+            requests[0].params,
+            Some(json!({ "data": { "has": components } }))
+        );
+
+        // An entity missing from the rows doesn't exist
+        assert_eq!(
+            presence(&[json!({ "entity": 3, "has": {} })], 7, &components),
+            None
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! List the valid mutation paths of a component or resource type
+//!
+//! Walks the registry schema of the type and its field types and returns every path
+//! `bevy_mutate_component` accepts, with the type at the path, e.g. `.translation.x: f32` or
+//! `.0.1: f32` for tuple structs, so agents can pick a correct path up front instead of learning
+//! it from access errors.
+
+use std::collections::HashMap;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENT, JSON_FIELD_HOST, JSON_FIELD_PATH,
+    JSON_FIELD_PORT,
+};
+use super::support::mutation_paths::{MutationPath, fetch_schemas, mutation_paths, primitive_kind};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_LIST_MUTATION_PATHS, TOOL_BEVY_LIST_MUTATION_PATHS};
+use crate::{BrpMcpService, config};
+
+/// Most paths returned for one type
+const MAX_PATHS: usize = 200;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_LIST_MUTATION_PATHS.into(),
+        description:  DESC_BEVY_LIST_MUTATION_PATHS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                JSON_FIELD_COMPONENT,
+                "Fully-qualified component or resource type name",
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let type_name = params::extract_required_string(&request, JSON_FIELD_COMPONENT)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let schemas = fetch_schemas(&type_name, Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    if !schemas.contains_key(&type_name) {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{JSON_FIELD_COMPONENT}'"),
+                format!(
+                    "`{type_name}` is not in the registry schema on port {port}; check the name with bevy_list"
+                ),
+            ),
+        )));
+    }
+
+    let paths = mutation_paths(&type_name, &schemas, MAX_PATHS);
+    let truncated = paths.len() >= MAX_PATHS;
+    let message = if paths.is_empty() {
+        format!("`{type_name}` has no fields to mutate; replace it as a whole with bevy_insert")
+    } else {
+        format!(
+            "{} mutation paths for `{type_name}`{}",
+            paths.len(),
+            if truncated { " (truncated)" } else { "" }
+        )
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            JSON_FIELD_COMPONENT: type_name,
+            "paths": paths.iter().map(|path| describe(path, &schemas)).collect::<Vec<_>>(),
+            "truncated": truncated,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// A path with its type, the kind of value it holds and, for enums, the unit variants
+fn describe(path: &MutationPath, schemas: &HashMap<String, Value>) -> Value {
+    let schema = schemas.get(&path.type_path);
+    let kind = primitive_kind(&path.type_path)
+        .or_else(|| schema?["kind"].as_str())
+        .unwrap_or("unknown");
+    let mut description = json!({
+        JSON_FIELD_PATH: path.path(),
+        "type": path.type_path,
+        "kind": kind,
+    });
+    if let Some(variants) = schema.and_then(|schema| schema["oneOf"].as_array()) {
+        let unit_variants: Vec<&Value> = variants
+            .iter()
+            .filter(|variant| variant.is_string())
+            .collect();
+        if !unit_variants.is_empty() {
+            description["variants"] = json!(unit_variants);
+        }
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_cover_struct_and_tuple_fields() {
+        let field = |type_path: &str| json!({ "type": { "$ref": format!("#/$defs/{type_path}") } });
+        let schemas = HashMap::from([
+            (
+                "game::Body".to_string(),
+                json!({
+                    "kind": "Struct",
+                    "properties": { "offset": field("game::Offset"), "mode": field("game::Mode") }
+                }),
+            ),
+            (
+                "game::Offset".to_string(),
+                json!({ "kind": "TupleStruct", "prefixItems": [field("f32"), field("f32")] }),
+            ),
+            (
+                "game::Mode".to_string(),
+                json!({ "kind": "Enum", "type": "string", "oneOf": ["Idle", "Running"] }),
+            ),
+        ]);
+
+        let described: Vec<Value> = mutation_paths("game::Body", &schemas, MAX_PATHS)
+            .iter()
+            .map(|path| describe(path, &schemas))
+            .collect();
+        let paths: Vec<&str> = described
+            .iter()
+            .filter_map(|path| path[JSON_FIELD_PATH].as_str())
+            .collect();
+        assert_eq!(paths, vec![".mode", ".offset", ".offset.0", ".offset.1"]);
+        assert_eq!(described[0]["variants"], json!(["Idle", "Running"]));
+        assert_eq!(described[3]["type"], "f32");
+        assert_eq!(described[3]["kind"], "float");
+    }
+}
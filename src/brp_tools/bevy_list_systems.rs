@@ -0,0 +1,234 @@
+//! List the systems of an app
+//!
+//! BRP has no system introspection of its own, so this passes through to the first listing
+//! method `rpc.discover` reports, from `bevy_brp_extras` or a newer Bevy, and reads its result
+//! into a list of systems with their schedule, sets and run conditions. Without one it reports
+//! what's missing instead of calling blind.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::capabilities;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_LIST_SYSTEMS, TOOL_BEVY_LIST_SYSTEMS};
+use crate::{BrpMcpService, config};
+
+/// Methods that list systems, in order of preference
+pub const LIST_SYSTEMS_METHODS: &[&str] = &["bevy/list_systems", "brp_extras/list_systems"];
+
+/// Parameter restricting the list to one schedule
+pub const PARAM_SCHEDULE: &str = "schedule";
+
+/// Parameter restricting the list to systems whose name contains it
+const PARAM_CONTAINS: &str = "contains";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_LIST_SYSTEMS.into(),
+        description:  DESC_BEVY_LIST_SYSTEMS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_SCHEDULE,
+                "Only systems of this schedule, e.g. Update or FixedUpdate",
+                false,
+            )
+            .add_string_property(
+                PARAM_CONTAINS,
+                "Only systems whose name contains this text",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let schedule = arguments.get(PARAM_SCHEDULE).and_then(Value::as_str);
+    let contains = arguments.get(PARAM_CONTAINS).and_then(Value::as_str);
+
+    let methods = capabilities::supported_methods(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let Some(method) = capabilities::first_supported(&methods, LIST_SYSTEMS_METHODS) else {
+        let response =
+            capabilities::unsupported_response("system listing", LIST_SYSTEMS_METHODS, &methods);
+        return Ok(json_response_to_result(&response));
+    };
+
+    let result = match execute_brp_method(method, None, Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    {
+        BrpResult::Success(result) => result.unwrap_or(Value::Null),
+        BrpResult::Error(e) => {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::BrpCommunication(format!("{method} failed: {}", e.message)),
+            )));
+        }
+    };
+
+    let all = read_systems(&result);
+    let total = all.len();
+    let systems: Vec<SystemInfo> = all
+        .into_iter()
+        .filter(|system| {
+            schedule.is_none_or(|schedule| system.schedule.as_deref() == Some(schedule))
+        })
+        .filter(|system| contains.is_none_or(|text| system.name.contains(text)))
+        .collect();
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "{} of {total} systems from {method}",
+            systems.len()
+        ))
+        .data(json!({
+            "method": method,
+            "systems": systems,
+            "count": systems.len(),
+            "total": total,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// A system as reported by the listing method
+#[derive(Debug, Serialize)]
+pub struct SystemInfo {
+    pub name:           String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule:       Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sets:           Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub run_conditions: Vec<String>,
+}
+
+/// The systems of a listing result
+///
+/// Accepts a list of systems, `{"systems": [..]}`, or systems grouped by schedule as
+/// `{"<schedule>": [..]}`; each system is a name or an object with a name.
+fn read_systems(result: &Value) -> Vec<SystemInfo> {
+    match result {
+        Value::Array(systems) => systems
+            .iter()
+            .filter_map(|system| read_system(system, None))
+            .collect(),
+        Value::Object(fields) => fields.get("systems").map_or_else(
+            || {
+                fields
+                    .iter()
+                    .flat_map(|(schedule, systems)| {
+                        systems
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|system| read_system(system, Some(schedule)))
+                    })
+                    .collect()
+            },
+            read_systems,
+        ),
+        _ => Vec::new(),
+    }
+}
+
+fn read_system(system: &Value, schedule: Option<&str>) -> Option<SystemInfo> {
+    let strings = |field: &str| -> Vec<String> {
+        system
+            .get(field)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| system_name(item).map(String::from))
+            .collect()
+    };
+    let run_conditions = match strings("run_conditions") {
+        conditions if conditions.is_empty() => strings("conditions"),
+        conditions => conditions,
+    };
+    Some(SystemInfo {
+        name: system_name(system)?.to_string(),
+        schedule: system
+            .get("schedule")
+            .and_then(Value::as_str)
+            .or(schedule)
+            .map(String::from),
+        sets: strings("sets"),
+        run_conditions,
+    })
+}
+
+/// The name of a system given as a string or an object with a `name`
+pub fn system_name(system: &Value) -> Option<&str> {
+    system
+        .as_str()
+        .or_else(|| system.get("name").and_then(Value::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listings_of_several_shapes_are_read() {
+        let flat = json!([
+            "game::spawn_enemies",
+            {
+                "name": "game::move_player",
+                "schedule": "Update",
+                "sets": ["game::Movement"],
+                "conditions": ["in_state(Playing)"]
+            },
+            { "schedule": "Update" }
+        ]);
+        let systems = read_systems(&flat);
+        assert_eq!(systems.len(), 2);
+        assert_eq!(systems[0].schedule, None);
+        assert_eq!(systems[1].run_conditions, vec!["in_state(Playing)"]);
+        assert_eq!(read_systems(&json!({ "systems": flat })).len(), 2);
+
+        let grouped = read_systems(&json!({
+            "FixedUpdate": ["game::physics"],
+            "Update": [{ "name": "game::animate", "schedule": "PostUpdate" }],
+        }));
+        let scheduled: Vec<(&str, Option<&str>)> = grouped
+            .iter()
+            .map(|system| (system.name.as_str(), system.schedule.as_deref()))
+            .collect();
+        assert_eq!(
+            scheduled,
+            vec![
+                ("game::physics", Some("FixedUpdate")),
+                ("game::animate", Some("PostUpdate")),
+            ]
+        );
+    }
+}
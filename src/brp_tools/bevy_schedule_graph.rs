@@ -0,0 +1,297 @@
+//! Read the schedule graphs of an app
+//!
+//! Like `bevy_list_systems`, this passes through to the first schedule graph method
+//! `rpc.discover` reports. Each graph is read into its systems and ordering edges, and the edges
+//! are sorted into the order the systems can run in, so an agent sees what runs before what and
+//! which systems are caught in an ordering cycle without reading the raw graph.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::bevy_list_systems::{PARAM_SCHEDULE, system_name};
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::capabilities;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_SCHEDULE_GRAPH, TOOL_BEVY_SCHEDULE_GRAPH};
+use crate::{BrpMcpService, config};
+
+/// Methods that return schedule graphs, in order of preference
+pub const SCHEDULE_GRAPH_METHODS: &[&str] = &["bevy/schedule_graph", "brp_extras/schedule_graph"];
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SCHEDULE_GRAPH.into(),
+        description:  DESC_BEVY_SCHEDULE_GRAPH.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_SCHEDULE,
+                "Only the graph of this schedule, e.g. Update (default: all schedules)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let schedule = arguments.get(PARAM_SCHEDULE).and_then(Value::as_str);
+
+    let methods = capabilities::supported_methods(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let Some(method) = capabilities::first_supported(&methods, SCHEDULE_GRAPH_METHODS) else {
+        let response =
+            capabilities::unsupported_response("schedule graphs", SCHEDULE_GRAPH_METHODS, &methods);
+        return Ok(json_response_to_result(&response));
+    };
+
+    let method_params = schedule.map(|schedule| json!({ PARAM_SCHEDULE: schedule }));
+    let result = match execute_brp_method(method, method_params, Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    {
+        BrpResult::Success(result) => result.unwrap_or(Value::Null),
+        BrpResult::Error(e) => {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::BrpCommunication(format!("{method} failed: {}", e.message)),
+            )));
+        }
+    };
+
+    let graphs: Vec<ScheduleGraph> = read_graphs(&result)
+        .into_iter()
+        .filter(|graph| {
+            schedule.is_none_or(|schedule| {
+                graph
+                    .schedule
+                    .as_deref()
+                    .is_none_or(|name| name == schedule)
+            })
+        })
+        .collect();
+    let cyclic = graphs
+        .iter()
+        .filter(|graph| !graph.cycle.is_empty())
+        .count();
+
+    let mut data = json!({
+        "method": method,
+        "schedules": graphs,
+    });
+    // A result that isn't a graph this tool can read is still passed on
+    if graphs.is_empty() {
+        data["result"] = result;
+    }
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "{} schedule graphs from {method}{}",
+            graphs.len(),
+            if cyclic > 0 {
+                format!(", {cyclic} with an ordering cycle")
+            } else {
+                String::new()
+            }
+        ))
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// An ordering constraint: `before` runs before `after`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct Edge {
+    before: String,
+    after:  String,
+}
+
+/// The systems of one schedule with their ordering
+#[derive(Debug, Serialize)]
+struct ScheduleGraph {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schedule: Option<String>,
+    systems:  Vec<String>,
+    edges:    Vec<Edge>,
+    /// The systems in an order satisfying every edge
+    order:    Vec<String>,
+    /// Systems left out of `order` because their edges form a cycle
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cycle:    Vec<String>,
+}
+
+/// The graphs of a schedule graph result
+///
+/// Accepts `{"schedules": {"<schedule>": graph}}`, `{"schedules": [graph]}` with a `name` or
+/// `schedule` per graph, or a single graph.
+fn read_graphs(result: &Value) -> Vec<ScheduleGraph> {
+    match result.get("schedules") {
+        Some(Value::Object(schedules)) => schedules
+            .iter()
+            .filter_map(|(name, graph)| read_graph(graph, Some(name)))
+            .collect(),
+        Some(Value::Array(schedules)) => schedules
+            .iter()
+            .filter_map(|graph| read_graph(graph, None))
+            .collect(),
+        _ => read_graph(result, None).into_iter().collect(),
+    }
+}
+
+/// Read one graph: systems under `systems` or `nodes`, edges under `edges`, `dependencies`
+/// or `ordering`, each as `[before, after]`, `{before, after}` or `{from, to}`
+fn read_graph(graph: &Value, schedule: Option<&str>) -> Option<ScheduleGraph> {
+    let list = |fields: &[&str]| {
+        fields
+            .iter()
+            .find_map(|field| graph.get(*field).and_then(Value::as_array))
+    };
+    let nodes = list(&["systems", "nodes"])?;
+    let edges: BTreeSet<Edge> = list(&["edges", "dependencies", "ordering"])
+        .into_iter()
+        .flatten()
+        .filter_map(read_edge)
+        .collect();
+
+    let mut systems: Vec<String> = nodes
+        .iter()
+        .filter_map(|node| system_name(node).map(String::from))
+        .collect();
+    for edge in &edges {
+        for name in [&edge.before, &edge.after] {
+            if !systems.contains(name) {
+                systems.push(name.clone());
+            }
+        }
+    }
+    let (order, cycle) = run_order(&systems, &edges);
+
+    Some(ScheduleGraph {
+        schedule: graph
+            .get("schedule")
+            .or_else(|| graph.get("name"))
+            .and_then(Value::as_str)
+            .or(schedule)
+            .map(String::from),
+        systems,
+        edges: edges.into_iter().collect(),
+        order,
+        cycle,
+    })
+}
+
+fn read_edge(edge: &Value) -> Option<Edge> {
+    let name = |value: Option<&Value>| value.and_then(system_name).map(String::from);
+    let (before, after) = match edge {
+        Value::Array(pair) if pair.len() == 2 => (name(pair.first()), name(pair.get(1))),
+        _ => (
+            name(edge.get("before").or_else(|| edge.get("from"))),
+            name(edge.get("after").or_else(|| edge.get("to"))),
+        ),
+    };
+    Some(Edge {
+        before: before?,
+        after:  after?,
+    })
+}
+
+/// Sort systems so every edge is respected, keeping the listed order where edges allow it
+///
+/// Returns the order and the systems left over because they're in or behind a cycle.
+fn run_order(systems: &[String], edges: &BTreeSet<Edge>) -> (Vec<String>, Vec<String>) {
+    let mut waiting_on: BTreeMap<&str, usize> =
+        systems.iter().map(|system| (system.as_str(), 0)).collect();
+    for edge in edges {
+        *waiting_on.entry(edge.after.as_str()).or_default() += 1;
+    }
+
+    let mut order: Vec<String> = Vec::with_capacity(systems.len());
+    let mut progressed = true;
+    while progressed {
+        progressed = false;
+        for system in systems {
+            if waiting_on.get(system.as_str()) != Some(&0) {
+                continue;
+            }
+            waiting_on.remove(system.as_str());
+            for edge in edges.iter().filter(|edge| &edge.before == system) {
+                if let Some(count) = waiting_on.get_mut(edge.after.as_str()) {
+                    *count -= 1;
+                }
+            }
+            order.push(system.clone());
+            progressed = true;
+        }
+    }
+
+    let cycle = systems
+        .iter()
+        .filter(|system| waiting_on.contains_key(system.as_str()))
+        .cloned()
+        .collect();
+    (order, cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphs_are_read_and_ordered() {
+        let graphs = read_graphs(&json!({
+            "schedules": {
+                "Update": {
+                    "systems": ["render", { "name": "physics" }, "input"],
+                    "edges": [["input", "physics"], { "before": "physics", "after": "render" }],
+                },
+                "FixedUpdate": {
+                    "nodes": ["a", "b", "c"],
+                    "dependencies": [{ "from": "a", "to": "b" }, ["b", "a"], ["b", "c"]],
+                },
+            }
+        }));
+        assert_eq!(graphs.len(), 2);
+
+        let fixed = &graphs[0];
+        assert_eq!(fixed.schedule.as_deref(), Some("FixedUpdate"));
+        assert!(fixed.order.is_empty());
+        assert_eq!(fixed.cycle, vec!["a", "b", "c"]);
+
+        let update = &graphs[1];
+        assert_eq!(update.order, vec!["input", "physics", "render"]);
+        assert!(update.cycle.is_empty());
+        assert_eq!(update.edges.len(), 2);
+
+        let single = read_graphs(&json!({ "schedule": "Startup", "systems": ["setup"] }));
+        assert_eq!(single[0].schedule.as_deref(), Some("Startup"));
+        assert_eq!(single[0].order, vec!["setup"]);
+        assert!(read_graphs(&json!({ "unexpected": true })).is_empty());
+    }
+}
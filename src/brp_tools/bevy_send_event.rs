@@ -0,0 +1,191 @@
+//! Send an event to the app
+//!
+//! BRP itself can't send events, so this passes through to the first event method `rpc.discover`
+//! reports, from a newer Bevy or `bevy_brp_extras`, like `bevy_list_assets`. The event value goes
+//! through format discovery the way component and resource values do, so a value in the wrong
+//! shape is corrected against the event type's schema before it's sent.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use super::request_handler::execute_brp_method_with_format_discovery;
+use super::support::brp_client::BrpResult;
+use super::support::capabilities;
+use super::support::response_formatter::correction_fields;
+use crate::error::{Error, ErrorCode, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_EXTRAS_SEND_EVENT, BRP_METHOD_SEND_EVENT, DESC_BEVY_SEND_EVENT, TOOL_BEVY_SEND_EVENT,
+};
+use crate::{BrpMcpService, config};
+
+/// Methods that send an event, in order of preference
+const SEND_EVENT_METHODS: &[&str] = &[BRP_METHOD_SEND_EVENT, BRP_METHOD_EXTRAS_SEND_EVENT];
+
+/// Parameter naming the event type
+const PARAM_EVENT: &str = "event";
+
+/// Parameter holding the event value
+const PARAM_VALUE: &str = "value";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SEND_EVENT.into(),
+        description:  DESC_BEVY_SEND_EVENT.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_EVENT,
+                "Fully-qualified type of the event, e.g. my_game::events::SpawnWave",
+                true,
+            )
+            .add_any_property(
+                PARAM_VALUE,
+                "The event value, as it serializes; leave out for unit events",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let event = params::extract_required_string(&request, PARAM_EVENT)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let value = arguments
+        .get(PARAM_VALUE)
+        .filter(|value| !value.is_null())
+        .cloned();
+
+    let response = send_event(&event, value, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Send an event through the first event method the app supports
+async fn send_event(event: &str, value: Option<Value>, port: u16) -> Result<JsonResponse> {
+    if event.is_empty() {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_EVENT}'"),
+            "give the fully-qualified type of the event",
+        )));
+    }
+
+    let methods = capabilities::supported_methods(port).await?;
+    let Some(method) = capabilities::first_supported(&methods, SEND_EVENT_METHODS) else {
+        return Ok(capabilities::unsupported_response(
+            "event sending",
+            SEND_EVENT_METHODS,
+            &methods,
+        ));
+    };
+
+    let mut params = Map::new();
+    params.insert(PARAM_EVENT.to_string(), json!(event));
+    if let Some(value) = value {
+        params.insert(PARAM_VALUE.to_string(), value);
+    }
+    let params = Value::Object(params);
+    let enhanced = execute_brp_method_with_format_discovery(
+        method,
+        Some(params.clone()),
+        Some(port),
+        Vec::new(),
+    )
+    .await?;
+
+    let mut data = json!({ "method": method, PARAM_EVENT: event });
+    for (field, value) in correction_fields(method, &enhanced) {
+        data[field] = value;
+    }
+    Ok(match enhanced.result {
+        BrpResult::Success(result) => {
+            if let Some(result) = result.filter(|result| !result.is_null()) {
+                data["result"] = result;
+            }
+            ResponseBuilder::success()
+                .message(format!("Sent {event} with {method}"))
+                .data(data)
+                .map_or_else(
+                    |_| {
+                        ResponseBuilder::error()
+                            .message("Failed to serialize response data")
+                            .build()
+                    },
+                    ResponseBuilder::build,
+                )
+        }
+        BrpResult::Error(e) => {
+            data["params"] = params;
+            ResponseBuilder::error()
+                .message(format!("{method} failed: {}", e.message))
+                .error_code(ErrorCode::from_brp_code(
+                    e.code,
+                    !enhanced.format_corrections.is_empty(),
+                ))
+                .details(data)
+                .build()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::BRP_METHOD_RPC_DISCOVER;
+
+    const SPAWN_WAVE: &str = "my_game::events::SpawnWave";
+
+    #[tokio::test]
+    async fn test_event_is_sent_through_the_supported_method() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_RPC_DISCOVER,
+                json!({ "methods": [{ "name": BRP_METHOD_EXTRAS_SEND_EVENT }] }),
+            )
+            .respond(BRP_METHOD_EXTRAS_SEND_EVENT, Value::Null);
+
+        let response = send_event(SPAWN_WAVE, Some(json!({ "size": 12 })), server.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        assert_eq!(response["status"], "success");
+        assert_eq!(response["data"]["method"], BRP_METHOD_EXTRAS_SEND_EVENT);
+        assert_eq!(
+            server.requests_for(BRP_METHOD_EXTRAS_SEND_EVENT)[0].params,
+            Some(json!({ "event": SPAWN_WAVE, "value": { "size": 12 } }))
+        );
+
+        let unsupported = MockBrpServer::start().await;
+        unsupported.respond(BRP_METHOD_RPC_DISCOVER, json!({ "methods": [] }));
+        let response = send_event(SPAWN_WAVE, None, unsupported.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        assert_eq!(response["error_code"], "method_not_supported");
+        assert!(
+            unsupported
+                .requests_for(BRP_METHOD_EXTRAS_SEND_EVENT)
+                .is_empty()
+        );
+    }
+}
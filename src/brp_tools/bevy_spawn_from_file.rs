@@ -0,0 +1,412 @@
+//! Spawn entities from a scene or prefab file
+//!
+//! Reads a Bevy `.scn.ron` scene, or the same structure as JSON, from the scene root and spawns
+//! its entities. Each spawn goes through format discovery; when an entity still can't be spawned
+//! with all of its components, it is spawned empty and the components are inserted one at a
+//! time so that a single bad component doesn't lose the rest. Parent links between scene
+//! entities are restored with `bevy/reparent` once everything is spawned.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::bevy_get_hierarchy::{CHILDREN_COMPONENT_SUFFIX, is_parent_component};
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY,
+    JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_HOST, JSON_FIELD_ORIGINAL_PARAMS_MODIFIED,
+    JSON_FIELD_PATH, JSON_FIELD_PORT, PARAM_ENTITIES, PARAM_PARENT,
+};
+use super::request_handler::{EnhancedBrpResult, execute_brp_method_with_format_discovery};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::response_formatter::corrections_json;
+use super::support::{ron_reader, spawn_tagging};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_INSERT, BRP_METHOD_REPARENT, BRP_METHOD_SPAWN, DESC_BEVY_SPAWN_FROM_FILE,
+    TOOL_BEVY_SPAWN_FROM_FILE,
+};
+use crate::{BrpMcpService, config};
+
+/// Environment variable holding the directory scene files are read from
+pub const SCENE_ROOT_ENV_VAR: &str = "BRP_MCP_SCENE_ROOT";
+
+/// Upper bound on the number of entities spawned from a single file
+const MAX_ENTITIES: usize = 1000;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SPAWN_FROM_FILE.into(),
+        description:  DESC_BEVY_SPAWN_FROM_FILE.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                JSON_FIELD_PATH,
+                &format!(
+                    "Scene (.scn.ron) or prefab (.json) file, relative to {SCENE_ROOT_ENV_VAR} (default: the server's working directory)"
+                ),
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+
+    let path = arguments
+        .get(JSON_FIELD_PATH)
+        .and_then(Value::as_str)
+        .ok_or_else(|| {
+            report_to_mcp_error(&error_stack::Report::new(Error::missing(
+                "parameter 'path'",
+            )))
+        })?;
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let path = resolve_scene_path(path).map_err(|report| report_to_mcp_error(&report))?;
+    let entities = read_scene(&path).map_err(|report| report_to_mcp_error(&report))?;
+    let spawned = spawn_scene(entities, port).await;
+
+    let created = spawned
+        .results
+        .iter()
+        .filter(|result| result[JSON_FIELD_ENTITY].is_u64())
+        .count();
+    let message = format!(
+        "Spawned {created} of {} entities from {}",
+        spawned.results.len(),
+        path.display()
+    );
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            JSON_FIELD_PATH: path.display().to_string(),
+            PARAM_ENTITIES: spawned.results,
+            "created_count": created,
+            "reparent_errors": spawned.reparent_errors,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Directory scene files are read from
+fn scene_root() -> Result<PathBuf> {
+    let root = std::env::var(SCENE_ROOT_ENV_VAR)
+        .ok()
+        .filter(|root| !root.is_empty())
+        .map_or_else(std::env::current_dir, |root| Ok(PathBuf::from(root)))
+        .map_err(|e| {
+            error_stack::Report::new(Error::FileOperation(format!(
+                "Failed to determine the scene root: {e}"
+            )))
+        })?;
+    root.canonicalize()
+        .map_err(|e| error_stack::Report::new(Error::io_failed("resolve scene root", &root, &e)))
+}
+
+/// Resolve a scene file path, rejecting anything outside the scene root
+fn resolve_scene_path(path: &str) -> Result<PathBuf> {
+    let root = scene_root()?;
+    let joined = root.join(path);
+    let resolved = joined.canonicalize().map_err(|e| {
+        error_stack::Report::new(Error::io_failed("resolve scene file", &joined, &e))
+    })?;
+    if !resolved.starts_with(&root) {
+        return Err(error_stack::Report::new(Error::invalid(
+            "parameter 'path'",
+            format!(
+                "must be inside the scene root {} (set {SCENE_ROOT_ENV_VAR} to change it)",
+                root.display()
+            ),
+        )));
+    }
+    Ok(resolved)
+}
+
+/// An entity read from a scene file
+#[derive(Debug, PartialEq)]
+struct SceneEntity {
+    /// Entity ID in the file, used to restore parent links
+    scene_id:   Option<u64>,
+    components: Map<String, Value>,
+}
+
+/// Read the entities of a RON or JSON scene file
+fn read_scene(path: &Path) -> Result<Vec<SceneEntity>> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| error_stack::Report::new(Error::io_failed("read scene file", path, &e)))?;
+    let document = if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::from_str(&text).map_err(|e| {
+            error_stack::Report::new(Error::invalid("scene file", format!("invalid JSON: {e}")))
+        })?
+    } else {
+        ron_reader::parse(&text)?
+    };
+
+    let entities = scene_entities(&document);
+    if entities.len() > MAX_ENTITIES {
+        return Err(error_stack::Report::new(Error::invalid(
+            "scene file",
+            format!(
+                "holds {} entities, more than the limit of {MAX_ENTITIES}",
+                entities.len()
+            ),
+        )));
+    }
+    Ok(entities)
+}
+
+/// Pick the entities out of a scene document
+///
+/// Accepts a Bevy scene (`entities` keyed by ID), an `entities` array, a single prefab with a
+/// `components` object, or an array of prefabs.
+fn scene_entities(document: &Value) -> Vec<SceneEntity> {
+    let entity = |scene_id: Option<u64>, value: &Value| SceneEntity {
+        scene_id,
+        components: value
+            .get(JSON_FIELD_COMPONENTS)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default(),
+    };
+    let from_array = |values: &[Value]| {
+        values
+            .iter()
+            .map(|value| entity(value.get(JSON_FIELD_ENTITY).and_then(Value::as_u64), value))
+            .collect()
+    };
+
+    match document.get(PARAM_ENTITIES) {
+        Some(Value::Object(entities)) => {
+            let mut entities: Vec<SceneEntity> = entities
+                .iter()
+                .map(|(id, value)| entity(id.parse().ok(), value))
+                .collect();
+            entities.sort_by_key(|entity| entity.scene_id);
+            entities
+        }
+        Some(Value::Array(entities)) => from_array(entities),
+        _ => match document {
+            Value::Array(prefabs) => from_array(prefabs),
+            prefab if prefab.get(JSON_FIELD_COMPONENTS).is_some() => vec![entity(None, prefab)],
+            _ => Vec::new(),
+        },
+    }
+}
+
+/// Outcome of spawning a scene
+struct SpawnedScene {
+    /// One entry per scene entity, with the spawned entity ID or the error
+    results:         Vec<Value>,
+    reparent_errors: Vec<Value>,
+}
+
+/// Spawn the scene entities and restore the parent links between them
+async fn spawn_scene(entities: Vec<SceneEntity>, port: u16) -> SpawnedScene {
+    let mut results = Vec::new();
+    let mut spawned_ids = HashMap::new();
+    let mut parents = Vec::new();
+
+    for SceneEntity {
+        scene_id,
+        mut components,
+    } in entities
+    {
+        // Bevy maintains `Children` itself from the parent links restored below
+        components.retain(|type_name, _| !type_name.ends_with(CHILDREN_COMPONENT_SUFFIX));
+        let parent_component = components
+            .keys()
+            .find(|type_name| is_parent_component(type_name))
+            .cloned();
+        let parent = parent_component
+            .and_then(|type_name| components.remove(&type_name))
+            .and_then(|link| parent_id(&link));
+
+        let mut result = spawn_entity(components, port).await;
+        result["scene_entity"] = json!(scene_id);
+        if let (Some(entity), Some(scene_id)) = (result[JSON_FIELD_ENTITY].as_u64(), scene_id) {
+            spawned_ids.insert(scene_id, entity);
+        }
+        if let (Some(entity), Some(parent)) = (result[JSON_FIELD_ENTITY].as_u64(), parent) {
+            parents.push((entity, parent));
+        }
+        results.push(result);
+    }
+
+    let mut reparent_errors = Vec::new();
+    for (entity, scene_parent) in parents {
+        // Links to entities outside the file can't be restored
+        let Some(parent) = spawned_ids.get(&scene_parent) else {
+            reparent_errors.push(json!({
+                JSON_FIELD_ENTITY: entity,
+                "error": format!("parent {scene_parent} is not in the scene file"),
+            }));
+            continue;
+        };
+        let params = json!({ PARAM_ENTITIES: [entity], PARAM_PARENT: parent });
+        let error = match execute_brp_method(BRP_METHOD_REPARENT, Some(params), Some(port)).await {
+            Ok(BrpResult::Success(_)) => continue,
+            Ok(BrpResult::Error(e)) => e.message,
+            Err(report) => report.to_string(),
+        };
+        reparent_errors.push(json!({ JSON_FIELD_ENTITY: entity, "error": error }));
+    }
+
+    SpawnedScene {
+        results,
+        reparent_errors,
+    }
+}
+
+/// Parent ID of a serialized `ChildOf`/`Parent`, either bare or wrapped (`{"parent": 42}`)
+fn parent_id(link: &Value) -> Option<u64> {
+    match link {
+        Value::Number(id) => id.as_u64(),
+        Value::Array(values) => values.first().and_then(parent_id),
+        Value::Object(map) => map.values().next().and_then(parent_id),
+        _ => None,
+    }
+}
+
+/// Spawn one entity, falling back to inserting its components one at a time
+async fn spawn_entity(components: Map<String, Value>, port: u16) -> Value {
+    let mut params = json!({ JSON_FIELD_COMPONENTS: components });
+    if spawn_tagging::is_enabled() {
+        spawn_tagging::tag_spawn_params(&mut params);
+    }
+    let components = params[JSON_FIELD_COMPONENTS].clone();
+
+    let spawn = discover(BRP_METHOD_SPAWN, params.clone(), port).await;
+    let mut result = json!({});
+    if let Ok(enhanced) = &spawn {
+        add_corrections(&mut result, enhanced);
+        if let Some(entity) = spawned_entity(&enhanced.result) {
+            result[JSON_FIELD_ENTITY] = json!(entity);
+            return result;
+        }
+    }
+
+    // Spawn empty and insert what can be inserted
+    let empty = json!({ JSON_FIELD_COMPONENTS: {} });
+    let entity = match discover(BRP_METHOD_SPAWN, empty, port).await {
+        Ok(enhanced) => spawned_entity(&enhanced.result),
+        Err(_) => None,
+    };
+    let Some(entity) = entity else {
+        result["error"] = json!(match spawn {
+            Ok(EnhancedBrpResult {
+                result: BrpResult::Error(e),
+                ..
+            }) => e.message,
+            Ok(_) => "spawn returned no entity".to_string(),
+            Err(report) => report.to_string(),
+        });
+        return result;
+    };
+    result[JSON_FIELD_ENTITY] = json!(entity);
+
+    let mut failed = Map::new();
+    for (type_name, value) in components.as_object().into_iter().flatten() {
+        let params = json!({
+            JSON_FIELD_ENTITY: entity,
+            JSON_FIELD_COMPONENTS: { type_name.clone(): value },
+        });
+        let error = match discover(BRP_METHOD_INSERT, params, port).await {
+            Ok(enhanced) => {
+                add_corrections(&mut result, &enhanced);
+                match enhanced.result {
+                    BrpResult::Success(_) => continue,
+                    BrpResult::Error(e) => e.message,
+                }
+            }
+            Err(report) => report.to_string(),
+        };
+        failed.insert(type_name.clone(), json!(error));
+    }
+    if !failed.is_empty() {
+        result["failed_components"] = Value::Object(failed);
+    }
+    result
+}
+
+async fn discover(method: &str, params: Value, port: u16) -> Result<EnhancedBrpResult> {
+    execute_brp_method_with_format_discovery(method, Some(params), Some(port), Vec::new()).await
+}
+
+fn spawned_entity(result: &BrpResult) -> Option<u64> {
+    match result {
+        BrpResult::Success(Some(data)) => data.get(JSON_FIELD_ENTITY).and_then(Value::as_u64),
+        _ => None,
+    }
+}
+
+/// Append the corrections format discovery applied to an entity's result
+fn add_corrections(result: &mut Value, enhanced: &EnhancedBrpResult) {
+    let modified = result[JSON_FIELD_ORIGINAL_PARAMS_MODIFIED].as_bool() == Some(true);
+    result[JSON_FIELD_ORIGINAL_PARAMS_MODIFIED] = json!(modified || enhanced.params_modified);
+    if enhanced.format_corrections.is_empty() {
+        return;
+    }
+    let Value::Array(new) = corrections_json(&enhanced.format_corrections) else {
+        return;
+    };
+    match result[JSON_FIELD_FORMAT_CORRECTIONS].as_array_mut() {
+        Some(corrections) => corrections.extend(new),
+        None => result[JSON_FIELD_FORMAT_CORRECTIONS] = Value::Array(new),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scene_entities_from_scene_and_prefab_shapes() {
+        let scene = json!({
+            "resources": {},
+            "entities": {
+                "10": { "components": { "game::B": 2 } },
+                "9": { "components": { "game::A": 1 } }
+            }
+        });
+        let entities = scene_entities(&scene);
+        assert_eq!(entities[0].scene_id, Some(9));
+        assert_eq!(entities[1].components["game::B"], 2);
+
+        let prefab = json!({ "components": { "game::A": 1 } });
+        assert_eq!(scene_entities(&prefab).len(), 1);
+        assert_eq!(scene_entities(&json!([prefab, prefab])).len(), 2);
+        assert!(scene_entities(&json!({ "something": "else" })).is_empty());
+
+        assert_eq!(parent_id(&json!(7)), Some(7));
+        assert_eq!(parent_id(&json!({ "parent": 7 })), Some(7));
+    }
+}
@@ -0,0 +1,185 @@
+//! Describe a type with an example value
+//!
+//! Returns the registry schema of one type, found by type path or short name, with an example
+//! value in the format BRP expects, generated from the schemas of the type and the types it
+//! refers to. The example generator is the one format discovery uses to show the expected format
+//! when it can't correct a value, so agents can start from a value that serializes.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use super::request_handler::{
+    example_value, fetch_referenced_schemas, known_types, registry_schema,
+};
+use super::support::brp_client::BrpResult;
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{fuzzy, params, schema};
+use crate::tools::{DESC_BEVY_TYPE_SCHEMA, TOOL_BEVY_TYPE_SCHEMA};
+use crate::{BrpMcpService, config};
+
+/// Parameter naming the type
+const PARAM_TYPE: &str = "type";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_TYPE_SCHEMA.into(),
+        description:  DESC_BEVY_TYPE_SCHEMA.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_TYPE,
+                "The type, by type path or short name, e.g. bevy_transform::components::transform::Transform or Transform",
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let type_name = params::extract_required_string(&request, PARAM_TYPE)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let response = describe_type(&type_name, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// The schema of a type with an example value
+async fn describe_type(name: &str, port: u16) -> Result<JsonResponse> {
+    let type_path = resolve_type(name, port).await?;
+    let schemas = fetch_referenced_schemas(&type_path, Some(port)).await?;
+    let Some(type_schema) = schemas.get(&type_path) else {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_TYPE}'"),
+            format!(
+                "{type_path} isn't in the app's type registry; give a short name to search for it"
+            ),
+        )));
+    };
+    let example = example_value(&type_path, &|type_path| schemas.get(type_path).cloned());
+
+    Ok(ResponseBuilder::success()
+        .message(format!("Schema of {type_path} with an example value"))
+        .data(json!({
+            PARAM_TYPE: type_path,
+            "schema": type_schema,
+            "example": example,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// The type path of a type given by path, or by short name if only one registered type has it
+async fn resolve_type(name: &str, port: u16) -> Result<String> {
+    if name.contains("::") {
+        return Ok(name.to_string());
+    }
+
+    // The whole registry, so the name can be searched for
+    if let BrpResult::Error(e) = registry_schema(json!({}), Some(port)).await? {
+        return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to read the registry schema: {}",
+            e.message
+        ))));
+    }
+    let known = known_types(Some(port));
+    let matching: Vec<&String> = known
+        .types
+        .iter()
+        .filter(|type_path| {
+            type_path.ends_with(&format!("::{name}"))
+                || fuzzy::short_name(type_path).eq_ignore_ascii_case(name)
+        })
+        .collect();
+    match matching.as_slice() {
+        [type_path] => Ok((*type_path).clone()),
+        [] => {
+            let suggestions = fuzzy::suggestions(name, known.types.iter().map(String::as_str));
+            Err(error_stack::Report::new(Error::invalid(
+                &format!("parameter '{PARAM_TYPE}'"),
+                format!(
+                    "no registered type is named '{name}'{}",
+                    suggestions
+                        .first()
+                        .map_or_else(String::new, |closest| format!(", did you mean {closest}?"))
+                ),
+            )))
+        }
+        _ => Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_TYPE}'"),
+            format!(
+                "'{name}' names several types, give the full type path of one of: {}",
+                matching
+                    .iter()
+                    .map(|type_path| type_path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::BRP_METHOD_REGISTRY_SCHEMA;
+
+    #[tokio::test]
+    async fn test_type_is_found_by_short_name_with_an_example() {
+        let server = MockBrpServer::start().await;
+        server.respond(
+            BRP_METHOD_REGISTRY_SCHEMA,
+            json!({
+                "my_game::Enemy": {
+                    "typePath": "my_game::Enemy",
+                    "kind": "Struct",
+                    "properties": {
+                        "speed": { "type": { "$ref": "#/$defs/f32" } },
+                        "mood": { "type": { "$ref": "#/$defs/my_game::Mood" } },
+                    },
+                },
+                "my_game::Mood": {
+                    "typePath": "my_game::Mood",
+                    "kind": "Enum",
+                    "oneOf": ["Calm", "Angry"],
+                },
+            }),
+        );
+
+        let response = describe_type("enemy", server.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        let data = &response["data"];
+        assert_eq!(data["type"], "my_game::Enemy");
+        assert_eq!(data["schema"]["kind"], "Struct");
+        assert_eq!(data["example"], json!({ "speed": 0.0, "mood": "Calm" }));
+
+        assert!(describe_type("Player", server.port()).await.is_err());
+    }
+}
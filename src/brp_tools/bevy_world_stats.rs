@@ -0,0 +1,266 @@
+//! Entity and component statistics of a running app
+//!
+//! Queries every entity, lists the components of each and counts entities per component type and
+//! per set of component types, along with the number of resources. Comparing two calls shows
+//! which kind of entity keeps growing, which is how entity leaks usually surface.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use futures::stream::{self, StreamExt};
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::bevy_find_entity_by_name::list_components;
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PORT,
+};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::progress::{self, Progress};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_LIST_RESOURCES, BRP_METHOD_QUERY, DESC_BEVY_WORLD_STATS, TOOL_BEVY_WORLD_STATS,
+};
+use crate::{BrpMcpService, config};
+
+/// Parameter limiting how many entities have their components listed
+const PARAM_MAX_ENTITIES: &str = "max_entities";
+
+/// Parameter limiting how many component sets are returned
+const PARAM_TOP: &str = "top";
+
+/// Entities whose components are listed when no limit is given
+const DEFAULT_MAX_ENTITIES: u64 = 5000;
+
+/// Component sets returned when no limit is given
+const DEFAULT_TOP: u64 = 20;
+
+/// `bevy/list` requests in flight at once
+const MAX_CONCURRENT_LISTS: usize = 16;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_WORLD_STATS.into(),
+        description:  DESC_BEVY_WORLD_STATS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                PARAM_MAX_ENTITIES,
+                &format!(
+                    "Most entities whose components are listed, lowest IDs first (default: \
+                     {DEFAULT_MAX_ENTITIES})"
+                ),
+                false,
+            )
+            .add_number_property(
+                PARAM_TOP,
+                &format!("Most component sets to return (default: {DEFAULT_TOP})"),
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let max_entities = limit(&arguments, PARAM_MAX_ENTITIES, DEFAULT_MAX_ENTITIES);
+    let top = limit(&arguments, PARAM_TOP, DEFAULT_TOP);
+
+    let entities = all_entities(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let inspected = &entities[..entities.len().min(max_entities)];
+    let component_lists = progress::scope(
+        Progress::new(&context),
+        Box::pin(list_all_components(inspected, port)),
+    )
+    .await;
+    let resource_count = count_resources(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let stats = WorldStats::new(component_lists);
+    let truncated = entities.len() > inspected.len();
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "{} entities in {} component sets, {resource_count} resources{}",
+            entities.len(),
+            stats.component_sets.len(),
+            if truncated {
+                format!(", components listed for the first {}", inspected.len())
+            } else {
+                String::new()
+            }
+        ))
+        .data(json!({
+            "entity_count": entities.len(),
+            "inspected_entities": inspected.len(),
+            "truncated": truncated,
+            "resource_count": resource_count,
+            JSON_FIELD_COMPONENTS: stats.component_counts(),
+            "component_set_count": stats.component_sets.len(),
+            "component_sets": stats.top_component_sets(top),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+fn limit(arguments: &Value, field: &str, default: u64) -> usize {
+    let value = arguments
+        .get(field)
+        .and_then(Value::as_u64)
+        .unwrap_or(default);
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+/// Every entity, in entity order
+async fn all_entities(port: u16) -> Result<Vec<u64>> {
+    let query = json!({ "data": {} });
+    let rows = match execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => rows,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to query entities: {}",
+                e.message
+            ))));
+        }
+    };
+
+    let mut entities: Vec<u64> = rows
+        .iter()
+        .filter_map(|row| row.get(JSON_FIELD_ENTITY)?.as_u64())
+        .collect();
+    entities.sort_unstable();
+    Ok(entities)
+}
+
+/// The component types of each entity, reporting progress as entities are listed
+async fn list_all_components(entities: &[u64], port: u16) -> Vec<Vec<String>> {
+    let mut lists = Vec::with_capacity(entities.len());
+    // Owned IDs keep the stream `Send`, as a closure over `&u64` isn't general enough for it
+    let mut listed = stream::iter(entities.to_vec())
+        .map(move |entity| list_components(entity, port))
+        .buffer_unordered(MAX_CONCURRENT_LISTS);
+    while let Some(components) = listed.next().await {
+        lists.push(components);
+        progress::report(lists.len(), Some(entities.len())).await;
+    }
+    lists
+}
+
+/// Number of resources in the app
+async fn count_resources(port: u16) -> Result<usize> {
+    match execute_brp_method(BRP_METHOD_LIST_RESOURCES, None, Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(resources))) => Ok(resources.len()),
+        BrpResult::Success(_) => Ok(0),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to list resources: {}",
+            e.message
+        )))),
+    }
+}
+
+/// Entity counts per component type and per set of component types
+struct WorldStats {
+    components:     BTreeMap<String, usize>,
+    component_sets: BTreeMap<BTreeSet<String>, usize>,
+}
+
+impl WorldStats {
+    fn new(component_lists: Vec<Vec<String>>) -> Self {
+        let mut components = BTreeMap::new();
+        let mut component_sets = BTreeMap::new();
+        for list in component_lists {
+            for component in &list {
+                *components.entry(component.clone()).or_insert(0) += 1;
+            }
+            *component_sets
+                .entry(list.into_iter().collect())
+                .or_insert(0) += 1;
+        }
+        Self {
+            components,
+            component_sets,
+        }
+    }
+
+    /// `{component, count}` per component type, most common first
+    fn component_counts(&self) -> Vec<Value> {
+        let mut counts: Vec<_> = self.components.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        counts
+            .into_iter()
+            .map(|(component, count)| json!({ "component": component, "count": count }))
+            .collect()
+    }
+
+    /// `{components, count}` of the most common component sets
+    fn top_component_sets(&self, top: usize) -> Vec<Value> {
+        let mut sets: Vec<_> = self.component_sets.iter().collect();
+        sets.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        sets.into_iter()
+            .take(top)
+            .map(|(components, count)| json!({ JSON_FIELD_COMPONENTS: components, "count": count }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_components_and_component_sets() {
+        let list = |components: &[&str]| -> Vec<String> {
+            components.iter().map(ToString::to_string).collect()
+        };
+        let stats = WorldStats::new(vec![
+            list(&["Transform", "Enemy"]),
+            list(&["Enemy", "Transform"]),
+            list(&["Transform", "Camera"]),
+            list(&["Enemy", "Transform"]),
+            list(&[]),
+        ]);
+
+        assert_eq!(
+            stats.component_counts(),
+            vec![
+                json!({ "component": "Transform", "count": 4 }),
+                json!({ "component": "Enemy", "count": 3 }),
+                json!({ "component": "Camera", "count": 1 }),
+            ]
+        );
+        assert_eq!(stats.component_sets.len(), 3);
+        assert_eq!(
+            stats.top_component_sets(2),
+            vec![
+                json!({ "components": ["Enemy", "Transform"], "count": 3 }),
+                json!({ "components": [], "count": 1 }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{Bookmark, LABEL_PREFIX, PARAM_LABEL, connection, normalize_label};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_ENTITY, JSON_FIELD_HOST, JSON_FIELD_PORT,
+};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_BOOKMARK_ENTITY, TOOL_BEVY_BOOKMARK_ENTITY};
+use crate::{BrpMcpService, config};
+
+/// Parameter holding a note about the bookmarked entity
+const PARAM_NOTE: &str = "note";
+
+/// Parameter asking for the bookmark to be removed
+const PARAM_REMOVE: &str = "remove";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_BOOKMARK_ENTITY.into(),
+        description:  DESC_BEVY_BOOKMARK_ENTITY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_LABEL,
+                "Label to save the entity under (letters, digits, '-', '_', '.'); use it as @label in place of an entity ID",
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_ENTITY,
+                "The entity ID to bookmark (required unless removing)",
+                false,
+            )
+            .add_string_property(PARAM_NOTE, "What the entity is, for later reference", false)
+            .add_boolean_property(
+                PARAM_REMOVE,
+                "Remove the bookmark instead of saving one (default: false)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let label = normalize_label(params::extract_required_string(&request, PARAM_LABEL)?)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let connection = connection(port);
+    let remove = arguments
+        .get(PARAM_REMOVE)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let lock_error = |e: std::sync::PoisonError<_>| {
+        report_to_mcp_error(&error_stack::Report::new(Error::MutexPoisoned(format!(
+            "bookmark store lock: {e}"
+        ))))
+    };
+
+    let (message, data) = if remove {
+        let removed = service
+            .bookmarks
+            .lock()
+            .map_err(lock_error)?
+            .remove(&connection, &label);
+        let message = if removed.is_some() {
+            format!("Removed bookmark {LABEL_PREFIX}{label}")
+        } else {
+            format!("There was no bookmark {LABEL_PREFIX}{label} on {connection}")
+        };
+        (
+            message,
+            json!({ "connection": connection, "removed": removed }),
+        )
+    } else {
+        let entity = arguments
+            .get(JSON_FIELD_ENTITY)
+            .and_then(Value::as_u64)
+            .ok_or_else(|| {
+                report_to_mcp_error(&error_stack::Report::new(Error::missing(&format!(
+                    "parameter '{JSON_FIELD_ENTITY}'"
+                ))))
+            })?;
+        let bookmark = Bookmark {
+            label: label.clone(),
+            entity,
+            note: arguments
+                .get(PARAM_NOTE)
+                .and_then(Value::as_str)
+                .map(String::from),
+            created_at: chrono::Local::now().to_rfc3339(),
+        };
+        let replaced = service
+            .bookmarks
+            .lock()
+            .map_err(lock_error)?
+            .insert(&connection, bookmark.clone());
+        (
+            format!("Bookmarked entity {entity} as {LABEL_PREFIX}{label}"),
+            json!({
+                "connection": connection,
+                "bookmark": bookmark,
+                "replaced": replaced,
+            }),
+        )
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
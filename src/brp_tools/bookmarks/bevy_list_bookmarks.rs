@@ -0,0 +1,66 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::connection;
+use crate::brp_tools::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_LIST_BOOKMARKS, TOOL_BEVY_LIST_BOOKMARKS};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_LIST_BOOKMARKS.into(),
+        description:  DESC_BEVY_LIST_BOOKMARKS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let connection = connection(port);
+
+    let bookmarks = service
+        .bookmarks
+        .lock()
+        .map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(Error::MutexPoisoned(format!(
+                "bookmark store lock: {e}"
+            ))))
+        })?
+        .list(&connection);
+
+    let response = ResponseBuilder::success()
+        .message(format!("{} bookmarks on {connection}", bookmarks.len()))
+        .data(json!({
+            "connection": connection,
+            "bookmarks": bookmarks,
+            "count": bookmarks.len(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,226 @@
+//! Entity bookmarks for multi-step agent workflows
+//!
+//! Entity IDs are opaque numbers that are easy to lose between steps. A bookmark maps a label
+//! chosen by the agent to an entity ID on one connection (host and port), and any tool taking an
+//! `entity`, `entities` or `parent` parameter accepts `@label` in its place. Labels are resolved
+//! before the tool's parameters are extracted, so every tool sees a plain entity ID. Bookmarks
+//! last for the session.
+
+pub mod bevy_bookmark_entity;
+pub mod bevy_list_bookmarks;
+pub mod bevy_resolve_bookmark;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::constants::{JSON_FIELD_ENTITY, JSON_FIELD_PORT, PARAM_ENTITIES, PARAM_PARENT};
+use super::support::brp_client;
+use crate::config;
+use crate::error::{Error, Result};
+
+/// Parameter naming a bookmark
+pub const PARAM_LABEL: &str = "label";
+
+/// Prefix marking a bookmark label in place of an entity ID
+pub const LABEL_PREFIX: char = '@';
+
+/// Parameters holding entity IDs, which accept `@label` instead
+const ENTITY_FIELDS: &[&str] = &[JSON_FIELD_ENTITY, PARAM_ENTITIES, PARAM_PARENT];
+
+/// An entity saved under a label
+#[derive(Debug, Clone, Serialize)]
+pub struct Bookmark {
+    pub label:      String,
+    pub entity:     u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note:       Option<String>,
+    pub created_at: String,
+}
+
+/// Per-session bookmarks, kept separately for every connection
+pub struct BookmarkStore {
+    connections: HashMap<String, BTreeMap<String, Bookmark>>,
+}
+
+impl BookmarkStore {
+    pub fn new() -> Self {
+        Self {
+            connections: HashMap::new(),
+        }
+    }
+
+    /// Save a bookmark, returning the one it replaced
+    pub fn insert(&mut self, connection: &str, bookmark: Bookmark) -> Option<Bookmark> {
+        self.connections
+            .entry(connection.to_string())
+            .or_default()
+            .insert(bookmark.label.clone(), bookmark)
+    }
+
+    /// Remove a bookmark, returning it if there was one
+    pub fn remove(&mut self, connection: &str, label: &str) -> Option<Bookmark> {
+        self.connections
+            .get_mut(connection)
+            .and_then(|bookmarks| bookmarks.remove(label))
+    }
+
+    /// The bookmark with a label on a connection
+    pub fn get(&self, connection: &str, label: &str) -> Option<&Bookmark> {
+        self.connections
+            .get(connection)
+            .and_then(|bookmarks| bookmarks.get(label))
+    }
+
+    /// The bookmarks of a connection, ordered by label
+    pub fn list(&self, connection: &str) -> Vec<Bookmark> {
+        self.connections
+            .get(connection)
+            .map(|bookmarks| bookmarks.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The bookmark with a label, with the known labels in the error if there's none
+    pub fn find(&self, connection: &str, label: &str) -> Result<&Bookmark> {
+        self.get(connection, label).ok_or_else(|| {
+            let known: Vec<String> = self
+                .list(connection)
+                .into_iter()
+                .map(|bookmark| format!("{LABEL_PREFIX}{}", bookmark.label))
+                .collect();
+            error_stack::Report::new(Error::invalid(
+                &format!("bookmark '{LABEL_PREFIX}{label}'"),
+                if known.is_empty() {
+                    format!("there are no bookmarks for {connection}")
+                } else {
+                    format!("not found for {connection}; known: {}", known.join(", "))
+                },
+            ))
+        })
+    }
+
+    /// Replace `@label` values of the entity parameters with the bookmarked entity IDs
+    pub fn resolve_arguments(
+        &self,
+        connection: &str,
+        arguments: &mut Map<String, Value>,
+    ) -> Result<()> {
+        for field in ENTITY_FIELDS {
+            match arguments.get_mut(*field) {
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        self.resolve_value(connection, item)?;
+                    }
+                }
+                Some(value) => self.resolve_value(connection, value)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_value(&self, connection: &str, value: &mut Value) -> Result<()> {
+        if let Some(label) = value
+            .as_str()
+            .and_then(|text| text.strip_prefix(LABEL_PREFIX))
+        {
+            *value = Value::from(self.find(connection, label)?.entity);
+        }
+        Ok(())
+    }
+}
+
+/// The connection bookmarks are kept for: the current host and a port
+pub fn connection(port: u16) -> String {
+    format!("{}:{port}", brp_client::current_host())
+}
+
+/// Replace `@label` values of a tool call's entity parameters, on the connection of its port
+pub fn resolve_labels(
+    store: &Mutex<BookmarkStore>,
+    arguments: &mut Map<String, Value>,
+) -> Result<()> {
+    let port = arguments
+        .get(JSON_FIELD_PORT)
+        .and_then(Value::as_u64)
+        .and_then(|port| u16::try_from(port).ok())
+        .unwrap_or_else(config::default_port);
+    store
+        .lock()
+        .map_err(|e| {
+            error_stack::Report::new(Error::MutexPoisoned(format!("bookmark store lock: {e}")))
+        })?
+        .resolve_arguments(&connection(port), arguments)
+}
+
+/// A label without its `@`, checked to contain only letters, digits, '-', '_' and '.'
+pub fn normalize_label(label: &str) -> Result<String> {
+    let label = label.strip_prefix(LABEL_PREFIX).unwrap_or(label);
+    if !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        Ok(label.to_string())
+    } else {
+        Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_LABEL}'"),
+            format!("'{label}' may only contain letters, digits, '-', '_' and '.'"),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_labels_resolve_per_connection() {
+        let mut store = BookmarkStore::new();
+        for (label, entity) in [("player", 4_294_967_301_u64), ("camera", 12)] {
+            store.insert(
+                "localhost:15702",
+                Bookmark {
+                    label: normalize_label(label).unwrap(),
+                    entity,
+                    note: None,
+                    created_at: String::new(),
+                },
+            );
+        }
+
+        let mut arguments = json!({
+            "entity": "@player",
+            "entities": ["@camera", 7],
+            "parent": 3,
+            "path": "@player",
+        });
+        store
+            .resolve_arguments("localhost:15702", arguments.as_object_mut().unwrap())
+            .unwrap();
+        assert_eq!(
+            arguments,
+            json!({
+                "entity": 4_294_967_301_u64,
+                "entities": [12, 7],
+                "parent": 3,
+                "path": "@player",
+            })
+        );
+
+        let mut other = json!({ "entity": "@player" });
+        assert!(
+            store
+                .resolve_arguments("localhost:15703", other.as_object_mut().unwrap())
+                .is_err()
+        );
+        assert_eq!(normalize_label("@enemy-1").unwrap(), "enemy-1");
+        assert!(normalize_label("@").is_err());
+        assert!(normalize_label("two words").is_err());
+    }
+}
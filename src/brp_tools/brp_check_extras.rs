@@ -0,0 +1,231 @@
+//! Report which `bevy_brp_extras` features an app supports
+//!
+//! Checks `rpc.discover` once against every method the extras tools call, so an agent can see up
+//! front which of them will work, and what happens without the rest, instead of calling them
+//! blind and reading "method not found".
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::bevy_list_systems::LIST_SYSTEMS_METHODS;
+use super::bevy_schedule_graph::SCHEDULE_GRAPH_METHODS;
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use super::support::capabilities;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_EXTRAS_PREFIX, BRP_METHOD_EXTRAS_DISCOVER_FORMAT, BRP_METHOD_EXTRAS_SCREENSHOT,
+    BRP_METHOD_EXTRAS_SEND_GAMEPAD, BRP_METHOD_EXTRAS_SEND_KEYS, BRP_METHOD_EXTRAS_SEND_MOUSE,
+    BRP_METHOD_EXTRAS_SET_DEBUG_MODE, BRP_METHOD_EXTRAS_SHUTDOWN, DESC_BRP_CHECK_EXTRAS,
+    TOOL_BEVY_LIST_SYSTEMS, TOOL_BEVY_SCHEDULE_GRAPH, TOOL_BRP_CHECK_EXTRAS,
+    TOOL_BRP_EXTRAS_DISCOVER_FORMAT, TOOL_BRP_EXTRAS_SCREENSHOT, TOOL_BRP_EXTRAS_SEND_GAMEPAD,
+    TOOL_BRP_EXTRAS_SEND_KEYS, TOOL_BRP_EXTRAS_SEND_MOUSE, TOOL_BRP_EXTRAS_SET_DEBUG_MODE,
+    TOOL_BRP_EXTRAS_SHUTDOWN,
+};
+use crate::{BrpMcpService, config};
+
+/// A feature that depends on optional methods
+struct ExtrasFeature {
+    name:     &'static str,
+    /// The tool that uses the feature
+    tool:     &'static str,
+    /// Methods providing the feature; any one of them is enough
+    methods:  &'static [&'static str],
+    /// What happens when the app supports none of them
+    fallback: &'static str,
+}
+
+/// Every feature the extras tools depend on
+const FEATURES: &[ExtrasFeature] = &[
+    ExtrasFeature {
+        name:     "screenshot",
+        tool:     TOOL_BRP_EXTRAS_SCREENSHOT,
+        methods:  &[BRP_METHOD_EXTRAS_SCREENSHOT],
+        fallback: "No screenshots",
+    },
+    ExtrasFeature {
+        name:     "send_keys",
+        tool:     TOOL_BRP_EXTRAS_SEND_KEYS,
+        methods:  &[BRP_METHOD_EXTRAS_SEND_KEYS],
+        fallback: "No keyboard input",
+    },
+    ExtrasFeature {
+        name:     "send_mouse",
+        tool:     TOOL_BRP_EXTRAS_SEND_MOUSE,
+        methods:  &[BRP_METHOD_EXTRAS_SEND_MOUSE],
+        fallback: "No pointer input",
+    },
+    ExtrasFeature {
+        name:     "send_gamepad",
+        tool:     TOOL_BRP_EXTRAS_SEND_GAMEPAD,
+        methods:  &[BRP_METHOD_EXTRAS_SEND_GAMEPAD],
+        fallback: "No gamepad input",
+    },
+    ExtrasFeature {
+        name:     "shutdown",
+        tool:     TOOL_BRP_EXTRAS_SHUTDOWN,
+        methods:  &[BRP_METHOD_EXTRAS_SHUTDOWN],
+        fallback: "The app process is killed instead of exiting cleanly",
+    },
+    ExtrasFeature {
+        name:     "discover_format",
+        tool:     TOOL_BRP_EXTRAS_DISCOVER_FORMAT,
+        methods:  &[BRP_METHOD_EXTRAS_DISCOVER_FORMAT],
+        fallback: "Format discovery relies on the registry schema and built-in pattern corrections",
+    },
+    ExtrasFeature {
+        name:     "set_debug_mode",
+        tool:     TOOL_BRP_EXTRAS_SET_DEBUG_MODE,
+        methods:  &[BRP_METHOD_EXTRAS_SET_DEBUG_MODE],
+        fallback: "No debug output from the app's format discovery",
+    },
+    ExtrasFeature {
+        name:     "list_systems",
+        tool:     TOOL_BEVY_LIST_SYSTEMS,
+        methods:  LIST_SYSTEMS_METHODS,
+        fallback: "No system listing",
+    },
+    ExtrasFeature {
+        name:     "schedule_graph",
+        tool:     TOOL_BEVY_SCHEDULE_GRAPH,
+        methods:  SCHEDULE_GRAPH_METHODS,
+        fallback: "No schedule graphs",
+    },
+];
+
+/// Whether the app supports one feature
+#[derive(Debug, Serialize)]
+struct Capability {
+    feature:   &'static str,
+    tool:      &'static str,
+    supported: bool,
+    /// The method the app provides the feature with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method:    Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fallback:  Option<&'static str>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_CHECK_EXTRAS.into(),
+        description:  DESC_BRP_CHECK_EXTRAS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let methods = capabilities::supported_methods(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let matrix = capability_matrix(&methods);
+    let supported = matrix
+        .iter()
+        .filter(|capability| capability.supported)
+        .count();
+    let extras_installed = methods
+        .iter()
+        .any(|method| method.starts_with(BRP_EXTRAS_PREFIX));
+
+    let guidance = if extras_installed {
+        "Call only the tools marked supported. Features still missing need a newer bevy_brp_extras or Bevy version"
+    } else {
+        "bevy_brp_extras isn't installed. Add the bevy_brp_extras crate to the app with the BrpExtrasPlugin to enable these features"
+    };
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "{supported} of {} extras features supported on port {port}",
+            matrix.len()
+        ))
+        .data(json!({
+            "port": port,
+            "extras_installed": extras_installed,
+            "capabilities": matrix,
+            "guidance": guidance,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Check every feature against the methods the app supports
+fn capability_matrix(methods: &[String]) -> Vec<Capability> {
+    FEATURES
+        .iter()
+        .map(|feature| {
+            let method = capabilities::first_supported(methods, feature.methods);
+            Capability {
+                feature: feature.name,
+                tool: feature.tool,
+                supported: method.is_some(),
+                method,
+                fallback: method.is_none().then_some(feature.fallback),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_marks_missing_features_with_their_fallback() {
+        let methods: Vec<String> = ["bevy/list_systems", "bevy/query", "brp_extras/screenshot"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let matrix = capability_matrix(&methods);
+        assert_eq!(matrix.len(), FEATURES.len());
+
+        let find = |name: &str| {
+            matrix
+                .iter()
+                .find(|capability| capability.feature == name)
+                .map(|capability| (capability.supported, capability.method, capability.fallback))
+        };
+        assert_eq!(
+            find("screenshot"),
+            Some((true, Some("brp_extras/screenshot"), None))
+        );
+        assert_eq!(
+            find("list_systems"),
+            Some((true, Some("bevy/list_systems"), None))
+        );
+        assert_eq!(
+            find("shutdown"),
+            Some((
+                false,
+                None,
+                Some("The app process is killed instead of exiting cleanly")
+            ))
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Report of the format corrections made during the session
+//!
+//! Groups every correction and serialization diagnostic format discovery produced by type, and
+//! renders a Markdown report with suggested changes to each type's Rust definition: the missing
+//! `Serialize`/`Deserialize` derives and reflect attributes, and doc comments recording the JSON
+//! BRP expects, so the same corrections aren't needed next session.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::JSON_FIELD_PATH;
+use super::request_handler::corrections_log::{self, LoggedCorrection};
+use crate::BrpMcpService;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{
+    BRP_METHOD_INSERT_RESOURCE, BRP_METHOD_MUTATE_RESOURCE, DESC_BRP_CORRECTIONS_REPORT,
+    TOOL_BRP_CORRECTIONS_REPORT,
+};
+
+/// Parameter clearing the log once the report is made
+const PARAM_CLEAR: &str = "clear";
+
+/// Start of the hint format discovery gives for types lacking serialization support
+const DIAGNOSTIC_MARKER: &str = "cannot be used with BRP because it lacks ";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_CORRECTIONS_REPORT.into(),
+        description:  DESC_BRP_CORRECTIONS_REPORT.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                JSON_FIELD_PATH,
+                "Markdown file to write the report to. Without it the report is returned inline",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_CLEAR,
+                "Forget the recorded corrections after reporting them (default: false)",
+                false,
+            )
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let path = arguments
+        .get(JSON_FIELD_PATH)
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    let clear = arguments
+        .get(PARAM_CLEAR)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let entries = corrections_log::entries();
+    let summaries = summarize(&entries);
+    let report = render_report(&summaries, entries.len());
+
+    if let Some(path) = &path {
+        tokio::fs::write(path, &report).await.map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
+                "write corrections report",
+                path,
+                &e,
+            )))
+        })?;
+    }
+    if clear {
+        corrections_log::clear();
+    }
+
+    let message = path.as_ref().map_or_else(
+        || {
+            format!(
+                "{} corrections across {} types this session",
+                entries.len(),
+                summaries.len()
+            )
+        },
+        |path| {
+            format!(
+                "Wrote the report of {} corrections across {} types to {}",
+                entries.len(),
+                summaries.len(),
+                path.display()
+            )
+        },
+    );
+    let mut data = json!({
+        "corrections": entries.len(),
+        "types": summaries.values().map(TypeSummary::to_json).collect::<Vec<_>>(),
+        "cleared": clear,
+    });
+    match &path {
+        Some(path) => data[JSON_FIELD_PATH] = json!(path.to_string_lossy()),
+        None => data["report"] = json!(report),
+    }
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Everything the session's corrections say about one type
+struct TypeSummary<'a> {
+    type_name:      &'a str,
+    is_resource:    bool,
+    methods:        BTreeSet<&'a str>,
+    corrections:    usize,
+    failed:         usize,
+    /// Serialization traits reported missing, e.g. `Serialize`
+    missing_traits: BTreeSet<&'a str>,
+    hints:          BTreeSet<&'a str>,
+    /// The first correction that made its request succeed
+    example:        Option<&'a LoggedCorrection>,
+}
+
+impl TypeSummary<'_> {
+    fn to_json(&self) -> Value {
+        json!({
+            "type_name": self.type_name,
+            "kind": self.kind(),
+            "methods": self.methods,
+            "corrections": self.corrections,
+            "failed": self.failed,
+            "missing_traits": self.missing_traits,
+            "hints": self.hints,
+        })
+    }
+
+    const fn kind(&self) -> &'static str {
+        if self.is_resource {
+            "Resource"
+        } else {
+            "Component"
+        }
+    }
+}
+
+/// Group the log by type, in type path order
+fn summarize(entries: &[LoggedCorrection]) -> BTreeMap<&str, TypeSummary<'_>> {
+    let mut summaries = BTreeMap::new();
+    for entry in entries {
+        let summary = summaries
+            .entry(entry.type_name.as_str())
+            .or_insert_with(|| TypeSummary {
+                type_name:      &entry.type_name,
+                is_resource:    false,
+                methods:        BTreeSet::new(),
+                corrections:    0,
+                failed:         0,
+                missing_traits: BTreeSet::new(),
+                hints:          BTreeSet::new(),
+                example:        None,
+            });
+        summary.is_resource |= [BRP_METHOD_INSERT_RESOURCE, BRP_METHOD_MUTATE_RESOURCE]
+            .contains(&entry.method.as_str());
+        summary.methods.insert(&entry.method);
+        summary.corrections += 1;
+        if !entry.succeeded {
+            summary.failed += 1;
+        }
+        if let Some(missing) = missing_traits(&entry.hint) {
+            summary.missing_traits.extend(missing);
+            continue;
+        }
+        if !entry.hint.is_empty() {
+            summary.hints.insert(&entry.hint);
+        }
+        if summary.example.is_none() && entry.succeeded {
+            summary.example = Some(entry);
+        }
+    }
+    summaries
+}
+
+/// The traits a serialization diagnostic reports missing, or `None` for other hints
+fn missing_traits(hint: &str) -> Option<Vec<&str>> {
+    let (_, rest) = hint.split_once(DIAGNOSTIC_MARKER)?;
+    let (missing, _) = rest.split_once(" trait")?;
+    Some(missing.split(" and ").collect())
+}
+
+/// Type name without its module path or generic arguments
+fn short_name(type_name: &str) -> &str {
+    let base = type_name.split('<').next().unwrap_or(type_name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+fn render_report(summaries: &BTreeMap<&str, TypeSummary<'_>>, corrections: usize) -> String {
+    let mut report = String::from("# Format corrections learned\n\n");
+    if summaries.is_empty() {
+        report.push_str("No format corrections were needed this session.\n");
+        return report;
+    }
+    let _ = writeln!(
+        report,
+        "{corrections} corrections across {} types. Each section suggests a change to the type's \
+         definition that lets agents send the expected JSON directly.",
+        summaries.len()
+    );
+    for summary in summaries.values() {
+        render_type(&mut report, summary);
+    }
+    report
+}
+
+fn render_type(report: &mut String, summary: &TypeSummary<'_>) {
+    let _ = writeln!(
+        report,
+        "\n## `{}` ({})\n",
+        summary.type_name,
+        summary.kind()
+    );
+    let _ = writeln!(
+        report,
+        "- Corrected {} times in {}",
+        summary.corrections,
+        summary
+            .methods
+            .iter()
+            .map(|method| format!("`{method}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if summary.failed > 0 {
+        let _ = writeln!(
+            report,
+            "- {} requests still failed after correction",
+            summary.failed
+        );
+    }
+    for hint in &summary.hints {
+        let _ = writeln!(report, "- {hint}");
+    }
+
+    if !summary.missing_traits.is_empty() {
+        let kind = summary.kind();
+        let traits = summary
+            .missing_traits
+            .iter()
+            .copied()
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            report,
+            "\nBRP can't read or write this type without {traits}. Derive and reflect them \
+             (requires `serde` with the `derive` feature):\n\n```diff\n\
+             -#[derive({kind}, Reflect)]\n\
+             -#[reflect({kind})]\n\
+             +#[derive({kind}, Reflect, {traits})]\n\
+             +#[reflect({kind}, {traits})]\n \
+             pub struct {} {{\n```",
+            short_name(summary.type_name)
+        );
+    }
+
+    if let Some(example) = summary.example {
+        let _ = writeln!(
+            report,
+            "\nSent:\n\n```json\n{}\n```\n\nExpected:\n\n```json\n{}\n```\n\nDocument the \
+             expected JSON on the type:\n\n```diff\n{}```",
+            example.original_format,
+            example.corrected_format,
+            doc_comment_diff(summary.type_name, example)
+        );
+    }
+}
+
+/// Doc comments recording the expected JSON on the type and on each field that was corrected
+fn doc_comment_diff(type_name: &str, example: &LoggedCorrection) -> String {
+    let mut diff = format!(
+        "+/// BRP JSON: `{}`\n pub struct {} {{\n",
+        example.corrected_format,
+        short_name(type_name)
+    );
+    if let Value::Object(fields) = &example.corrected_format {
+        for (field, expected) in fields {
+            if example.original_format.get(field) != Some(expected) {
+                let _ = write!(
+                    diff,
+                    "+    /// BRP JSON: `{expected}`\n     pub {field}: ...,\n"
+                );
+            }
+        }
+    }
+    diff.push_str(" }\n");
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logged(method: &str, hint: &str, original: Value, corrected: Value) -> LoggedCorrection {
+        LoggedCorrection {
+            method:           method.to_string(),
+            type_name:        "game::stats::Health".to_string(),
+            hint:             hint.to_string(),
+            original_format:  original,
+            corrected_format: corrected,
+            succeeded:        true,
+        }
+    }
+
+    #[test]
+    fn test_corrections_are_grouped_with_suggested_changes() {
+        let entries = [
+            logged(
+                "bevy/spawn",
+                "Converted object to array",
+                json!({ "current": { "x": 1.0 }, "max": 100.0 }),
+                json!({ "current": [1.0], "max": 100.0 }),
+            ),
+            logged(
+                "bevy/insert_resource",
+                "Type `game::stats::Health` cannot be used with BRP because it lacks Serialize and Deserialize trait(s). Available traits: Resource.",
+                Value::Null,
+                Value::Null,
+            ),
+        ];
+        let summaries = summarize(&entries);
+        let health = &summaries["game::stats::Health"];
+        assert_eq!(health.corrections, 2);
+        assert!(health.is_resource);
+        assert_eq!(
+            health.missing_traits.iter().copied().collect::<Vec<_>>(),
+            vec!["Deserialize", "Serialize"]
+        );
+
+        let report = render_report(&summaries, entries.len());
+        assert!(report.contains("+#[reflect(Resource, Deserialize, Serialize)]"));
+        assert!(report.contains("+    /// BRP JSON: `[1.0]`\n     pub current: ...,"));
+        assert!(!report.contains("pub max"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Progressive disclosure of tool descriptions
+//!
+//! The full descriptions of all tools take up a large share of a client's context before any
+//! tool is called. With `BRP_MCP_COMPACT_DESCRIPTIONS` set, `list_tools` returns only the first
+//! sentence of each description, and `brp_describe_tool` returns the full description, the
+//! parameter documentation and the examples of one tool when the agent needs them.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BRP_DESCRIBE_TOOL, TOOL_BRP_DESCRIBE_TOOL};
+use crate::{BrpMcpService, registry};
+
+/// Environment variable enabling one-line tool descriptions in `list_tools`
+pub const COMPACT_DESCRIPTIONS_ENV_VAR: &str = "BRP_MCP_COMPACT_DESCRIPTIONS";
+
+/// Parameter naming the tool to describe
+const PARAM_TOOL: &str = "tool";
+
+/// Abbreviations whose period does not end a sentence
+const ABBREVIATIONS: [&str; 3] = ["e.g.", "i.e.", "etc."];
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_DESCRIBE_TOOL.into(),
+        description:  DESC_BRP_DESCRIBE_TOOL.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(PARAM_TOOL, "Name of the tool to describe", true)
+            .build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: &CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let name = params::extract_required_string(request, PARAM_TOOL)?;
+
+    let tool = registry::all_tools()
+        .into_iter()
+        .find(|tool| tool.name == name)
+        .ok_or_else(|| {
+            report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+                &format!("parameter '{PARAM_TOOL}'"),
+                format!("unknown tool '{name}'"),
+            )))
+        })?;
+
+    let response = ResponseBuilder::success()
+        .message(format!("Description of {name}"))
+        .data(describe(&tool))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Whether `list_tools` should return one-line descriptions
+pub fn compact_descriptions_enabled() -> bool {
+    std::env::var(COMPACT_DESCRIPTIONS_ENV_VAR).is_ok_and(|value| {
+        ["1", "true", "yes", "on"].contains(&value.trim().to_ascii_lowercase().as_str())
+    })
+}
+
+/// Replace every description with its first sentence
+///
+/// `brp_describe_tool` keeps its full description, since it tells the agent where the rest is.
+pub fn compact(tools: &mut [Tool]) {
+    for tool in tools
+        .iter_mut()
+        .filter(|tool| tool.name != TOOL_BRP_DESCRIBE_TOOL)
+    {
+        tool.description = summary(&tool.description).into();
+    }
+}
+
+/// The first sentence of a description
+fn summary(description: &str) -> String {
+    let first_line = description.lines().next().unwrap_or_default().trim();
+    let mut end = first_line.len();
+    for (index, _) in first_line.match_indices(". ") {
+        let sentence = &first_line[..=index];
+        if !ABBREVIATIONS
+            .iter()
+            .any(|abbreviation| sentence.ends_with(abbreviation))
+        {
+            end = index + 1;
+            break;
+        }
+    }
+    first_line[..end].to_string()
+}
+
+/// Full documentation of a tool
+fn describe(tool: &Tool) -> Value {
+    let required: Vec<&str> = tool
+        .input_schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let parameters: Vec<Value> = tool
+        .input_schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| {
+                    json!({
+                        "name": name,
+                        "type": property.get("type"),
+                        "required": required.contains(&name.as_str()),
+                        "description": property.get("description"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    json!({
+        "name": tool.name,
+        "summary": summary(&tool.description),
+        "description": tool.description,
+        "parameters": parameters,
+        "examples": examples(&tool.description),
+    })
+}
+
+/// Paragraphs of a description that start with "Example"
+fn examples(description: &str) -> Vec<&str> {
+    description
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| paragraph.starts_with("Example"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_is_the_first_sentence() {
+        assert_eq!(
+            summary(
+                "Queries entities via bevy/query BRP method. Searches entities.\n\nParameters:"
+            ),
+            "Queries entities via bevy/query BRP method."
+        );
+        assert_eq!(
+            summary("Adds hosts, e.g. localhost. Then probes them."),
+            "Adds hosts, e.g. localhost."
+        );
+        assert_eq!(summary("No period here"), "No period here");
+    }
+
+    #[test]
+    fn test_examples_and_full_descriptions_are_kept() {
+        let description =
+            "Spawns an entity.\n\nParameters:\n- components\n\nExample:\n{\"components\": {}}";
+        assert_eq!(
+            examples(description),
+            vec!["Example:\n{\"components\": {}}"]
+        );
+
+        let mut tools = registry::all_tools();
+        compact(&mut tools);
+        for tool in tools
+            .iter()
+            .filter(|tool| tool.name != TOOL_BRP_DESCRIBE_TOOL)
+        {
+            assert!(
+                !tool.description.contains('\n'),
+                "{} is not compact",
+                tool.name
+            );
+        }
+        let describe_tool = tools
+            .iter()
+            .find(|tool| tool.name == TOOL_BRP_DESCRIBE_TOOL);
+        assert_eq!(
+            describe_tool.map(|tool| tool.description.as_ref()),
+            Some(DESC_BRP_DESCRIBE_TOOL)
+        );
+    }
+}
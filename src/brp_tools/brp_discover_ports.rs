@@ -0,0 +1,218 @@
+//! Find the ports BRP servers listen on
+//!
+//! Every port of a range is checked concurrently: ports that accept a TCP connection are asked
+//! for `rpc.discover`, and the ones that answer are reported with the app registered on them
+//! and the methods they support. `brp_status` uses the same scan when it isn't given a port.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use futures::future::join_all;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST};
+use super::support::brp_client::{BrpResult, current_host, execute_brp_method};
+use super::support::capabilities;
+use crate::BrpMcpService;
+use crate::app_tools::support::managed_apps;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_RPC_DISCOVER, DESC_BRP_DISCOVER_PORTS, TOOL_BRP_DISCOVER_PORTS};
+
+/// Parameter giving the first port of the scanned range
+const PARAM_START_PORT: &str = "start_port";
+
+/// Parameter giving the last port of the scanned range
+const PARAM_END_PORT: &str = "end_port";
+
+/// Last port of the default range, which starts at the default BRP port
+const DEFAULT_END_PORT: u16 = DEFAULT_BRP_PORT + 10;
+
+/// Upper bound on the number of ports one scan may check
+const MAX_PORTS: usize = 256;
+
+/// How long a port may take to accept a connection
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long an open port may take to answer `rpc.discover`
+const DISCOVER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The ports scanned when none are given
+pub const DEFAULT_PORT_RANGE: RangeInclusive<u16> = DEFAULT_BRP_PORT..=DEFAULT_END_PORT;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_DISCOVER_PORTS.into(),
+        description:  DESC_BRP_DISCOVER_PORTS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                PARAM_START_PORT,
+                &format!("First port to scan (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_number_property(
+                PARAM_END_PORT,
+                &format!("Last port to scan (default: {DEFAULT_END_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let start = params::extract_optional_u16(&arguments, PARAM_START_PORT, DEFAULT_BRP_PORT);
+    let end = params::extract_optional_u16(&arguments, PARAM_END_PORT, DEFAULT_END_PORT);
+    let range = port_range(start, end).map_err(|report| report_to_mcp_error(&report))?;
+
+    let discovered = discover_ports(range.clone()).await;
+
+    let message = if discovered.is_empty() {
+        format!(
+            "No BRP server found on {} ports {}-{}",
+            current_host(),
+            range.start(),
+            range.end()
+        )
+    } else {
+        format!(
+            "Found BRP servers on ports {}",
+            discovered
+                .iter()
+                .map(|found| found.port.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            JSON_FIELD_HOST: current_host(),
+            PARAM_START_PORT: range.start(),
+            PARAM_END_PORT: range.end(),
+            "ports": discovered,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// A port with a live BRP server
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredPort {
+    pub port:     u16,
+    /// Name of the managed app registered on the port, if any
+    pub app_name: Option<String>,
+    /// `info.title` of the `OpenRPC` document
+    pub title:    Option<String>,
+    /// `info.version` of the `OpenRPC` document
+    pub version:  Option<String>,
+    pub methods:  Vec<String>,
+}
+
+impl DiscoveredPort {
+    /// Read an `rpc.discover` result
+    fn from_discover(port: u16, document: &Value) -> Self {
+        let info = document.get("info");
+        let info_field = |field: &str| {
+            info.and_then(|info| info.get(field))
+                .and_then(Value::as_str)
+                .map(String::from)
+        };
+        Self {
+            port,
+            app_name: managed_apps::find_by_port(port).map(|app| app.name),
+            title: info_field("title"),
+            version: info_field("version"),
+            methods: capabilities::method_names(document),
+        }
+    }
+}
+
+/// Validate a scan range given by its first and last port
+fn port_range(start: u16, end: u16) -> crate::error::Result<RangeInclusive<u16>> {
+    if start > end {
+        return Err(error_stack::Report::new(Error::invalid(
+            "port range",
+            format!("{PARAM_START_PORT} {start} is after {PARAM_END_PORT} {end}"),
+        )));
+    }
+    let count = usize::from(end - start) + 1;
+    if count > MAX_PORTS {
+        return Err(error_stack::Report::new(Error::invalid(
+            "port range",
+            format!("{count} ports is more than the {MAX_PORTS} one scan may check"),
+        )));
+    }
+    Ok(start..=end)
+}
+
+/// The ports of a range with a live BRP server, in port order
+pub async fn discover_ports(range: RangeInclusive<u16>) -> Vec<DiscoveredPort> {
+    let host = current_host();
+    let host = host.trim_matches(['[', ']']);
+    join_all(range.map(|port| async move {
+        // Closed ports are skipped before the HTTP request, which would probe other hosts
+        tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host, port)))
+            .await
+            .ok()?
+            .ok()?;
+        match tokio::time::timeout(
+            DISCOVER_TIMEOUT,
+            execute_brp_method(BRP_METHOD_RPC_DISCOVER, None, Some(port)),
+        )
+        .await
+        {
+            Ok(Ok(BrpResult::Success(Some(document)))) => {
+                Some(DiscoveredPort::from_discover(port, &document))
+            }
+            _ => None,
+        }
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_documents_and_ranges_are_read() {
+        let document = json!({
+            "openrpc": "1.3.2",
+            "info": { "title": "Bevy Remote Protocol", "version": "0.16.0" },
+            "methods": [{ "name": "bevy/query" }, { "name": "bevy/get" }, { "params": [] }],
+        });
+        let found = DiscoveredPort::from_discover(15703, &document);
+        assert_eq!(found.title.as_deref(), Some("Bevy Remote Protocol"));
+        assert_eq!(found.version.as_deref(), Some("0.16.0"));
+        assert_eq!(found.methods, vec!["bevy/get", "bevy/query"]);
+
+        assert!(port_range(15702, 15712).is_ok());
+        assert!(port_range(15712, 15702).is_err());
+        assert!(port_range(1000, 2000).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+//! Execute several BRP methods in one tool call
+//!
+//! Each sub-request goes through format discovery exactly like `brp_execute`, so type format
+//! errors are corrected per request. Requests run either sequentially (in order, optionally
+//! stopping at the first error) or concurrently, and every request gets its own result entry.
+//! A progress notification counting the finished requests follows every request.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::future::join_all;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::brp_set_debug_mode::is_debug_enabled;
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_DATA, JSON_FIELD_DEBUG_INFO, JSON_FIELD_HOST,
+    JSON_FIELD_METHOD, JSON_FIELD_PORT, JSON_FIELD_STATUS,
+};
+use super::request_handler::{EnhancedBrpResult, execute_brp_method_with_format_discovery};
+use super::support::brp_client::BrpResult;
+use super::support::destructive_ops::{self, DESTRUCTIVE_METHODS, DestructiveOpsPolicy};
+use super::support::response_formatter::correction_fields;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::progress::{self, Progress};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_EXECUTE_BATCH, TOOL_BRP_EXECUTE_BATCH};
+use crate::{BrpMcpService, config};
+
+/// Upper bound on the number of requests in a single batch
+const MAX_BATCH_SIZE: usize = 100;
+
+/// A single method/params pair in a batch
+#[derive(Debug, Deserialize)]
+struct BatchRequest {
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExecuteBatchParams {
+    requests:      Vec<BatchRequest>,
+    #[serde(default)]
+    parallel:      bool,
+    #[serde(default)]
+    stop_on_error: bool,
+    #[serde(default)]
+    port:          Option<u16>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_EXECUTE_BATCH.into(),
+        description:  DESC_BRP_EXECUTE_BATCH.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_any_property(
+                "requests",
+                "Array of requests to execute, each an object with 'method' and optional 'params'",
+                true,
+            )
+            .add_boolean_property(
+                "parallel",
+                "Execute the requests concurrently instead of in order (default: false)",
+                false,
+            )
+            .add_boolean_property(
+                "stop_on_error",
+                "Skip the remaining requests after the first error; sequential mode only (default: false)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let args = request.arguments.unwrap_or_default();
+    let params: ExecuteBatchParams =
+        serde_json::from_value(Value::Object(args)).map_err(|e| -> McpError {
+            report_to_mcp_error(
+                &error_stack::Report::new(Error::ParameterExtraction(
+                    "Invalid parameters for brp_execute_batch".to_string(),
+                ))
+                .attach_printable(format!("Deserialization error: {e}"))
+                .attach_printable("Expected 'requests' as an array of {method, params} objects"),
+            )
+        })?;
+
+    if params.requests.is_empty() || params.requests.len() > MAX_BATCH_SIZE {
+        return Err(report_to_mcp_error(
+            &error_stack::Report::new(Error::invalid(
+                "requests",
+                format!("must contain between 1 and {MAX_BATCH_SIZE} requests"),
+            ))
+            .attach_printable(format!("Provided: {}", params.requests.len())),
+        ));
+    }
+
+    let port = params.port.unwrap_or_else(config::default_port);
+    let execute = async {
+        if params.parallel {
+            execute_parallel(&params.requests, port).await
+        } else {
+            execute_sequential(&params.requests, port, params.stop_on_error).await
+        }
+    };
+    let results = progress::scope(Progress::new(&context), Box::pin(execute)).await;
+
+    let count = |status: &str| {
+        results
+            .iter()
+            .filter(|result| result[JSON_FIELD_STATUS] == status)
+            .count()
+    };
+    let (succeeded, failed, skipped) = (count("success"), count("error"), count("skipped"));
+
+    let mut message = format!(
+        "Executed {} of {} requests: {succeeded} succeeded, {failed} failed",
+        succeeded + failed,
+        results.len()
+    );
+    if skipped > 0 {
+        message = format!("{message}, {skipped} skipped");
+    }
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "results": results,
+            "succeeded": succeeded,
+            "failed": failed,
+            "skipped": skipped,
+            JSON_FIELD_PORT: port,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Execute the requests one after another, in order
+async fn execute_sequential(
+    requests: &[BatchRequest],
+    port: u16,
+    stop_on_error: bool,
+) -> Vec<Value> {
+    let mut results = Vec::with_capacity(requests.len());
+    let mut stopped = false;
+
+    for (index, request) in requests.iter().enumerate() {
+        if stopped {
+            results.push(json!({
+                "index": index,
+                JSON_FIELD_METHOD: request.method,
+                JSON_FIELD_STATUS: "skipped",
+            }));
+            continue;
+        }
+
+        let result = execute_one(index, request, port).await;
+        stopped = stop_on_error && result[JSON_FIELD_STATUS] == "error";
+        results.push(result);
+        progress::report(results.len(), Some(requests.len())).await;
+    }
+
+    results
+}
+
+/// Execute all requests concurrently, keeping results in request order
+async fn execute_parallel(requests: &[BatchRequest], port: u16) -> Vec<Value> {
+    let finished = AtomicUsize::new(0);
+    join_all(requests.iter().enumerate().map(|(index, request)| {
+        let finished = &finished;
+        async move {
+            let result = execute_one(index, request, port).await;
+            let finished = finished.fetch_add(1, Ordering::Relaxed) + 1;
+            progress::report(finished, Some(requests.len())).await;
+            result
+        }
+    }))
+    .await
+}
+
+/// Execute a single request with format discovery and describe its outcome
+async fn execute_one(index: usize, request: &BatchRequest, port: u16) -> Value {
+    // Batches have no way to preview and confirm a request
+    if DESTRUCTIVE_METHODS.contains(&request.method.as_str())
+        && destructive_ops::policy() == DestructiveOpsPolicy::Confirm
+    {
+        return json!({
+            "index": index,
+            JSON_FIELD_METHOD: request.method,
+            JSON_FIELD_STATUS: "error",
+            "error": {
+                "message": "The destructive_ops policy requires confirmation; call the method with brp_execute to preview and confirm it",
+            },
+        });
+    }
+
+    let outcome = execute_brp_method_with_format_discovery(
+        &request.method,
+        request.params.clone(),
+        Some(port),
+        Vec::new(),
+    )
+    .await;
+
+    match outcome {
+        Ok(enhanced) => batch_result(index, &request.method, &enhanced),
+        Err(report) => json!({
+            "index": index,
+            JSON_FIELD_METHOD: request.method,
+            JSON_FIELD_STATUS: "error",
+            "error": { "message": report.to_string() },
+        }),
+    }
+}
+
+/// Build the result entry for one executed request
+fn batch_result(index: usize, method: &str, enhanced: &EnhancedBrpResult) -> Value {
+    let mut result = match &enhanced.result {
+        BrpResult::Success(data) => json!({
+            "index": index,
+            JSON_FIELD_METHOD: method,
+            JSON_FIELD_STATUS: "success",
+            JSON_FIELD_DATA: data,
+        }),
+        BrpResult::Error(error) => json!({
+            "index": index,
+            JSON_FIELD_METHOD: method,
+            JSON_FIELD_STATUS: "error",
+            "error": {
+                "code": error.code,
+                "message": error.message,
+                JSON_FIELD_DATA: error.data,
+            },
+        }),
+    };
+
+    if let Value::Object(map) = &mut result {
+        for (field_name, value) in correction_fields(method, enhanced) {
+            map.insert(field_name.to_string(), value);
+        }
+        if is_debug_enabled() && !enhanced.debug_info.is_empty() {
+            map.insert(
+                JSON_FIELD_DEBUG_INFO.to_string(),
+                json!(enhanced.debug_info),
+            );
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::constants::{
+        JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_ORIGINAL_PARAMS_MODIFIED,
+    };
+    use crate::brp_tools::request_handler::FormatCorrection;
+    use crate::brp_tools::support::brp_client::BrpError;
+
+    #[test]
+    fn test_params_default_to_sequential() {
+        let params: ExecuteBatchParams = serde_json::from_value(json!({
+            "requests": [
+                { "method": "bevy/list" },
+                { "method": "bevy/get", "params": { "entity": 1, "components": [] } }
+            ]
+        }))
+        .unwrap();
+
+        assert!(!params.parallel);
+        assert!(!params.stop_on_error);
+        assert_eq!(params.requests.len(), 2);
+        assert!(params.requests[0].params.is_none());
+    }
+
+    #[test]
+    fn test_batch_result_reports_errors_and_corrections() {
+        let enhanced = EnhancedBrpResult {
+            result:             BrpResult::Error(BrpError {
+                code:    -23402,
+                message: "Unknown component".to_string(),
+                data:    None,
+            }),
+            format_corrections: vec![],
+            debug_info:         vec![],
+            params_modified:    false,
+        };
+        let result = batch_result(3, "bevy/insert", &enhanced);
+        assert_eq!(result[JSON_FIELD_STATUS], "error");
+        assert_eq!(result["index"], 3);
+        assert_eq!(result["error"]["code"], -23402);
+        assert!(result.get(JSON_FIELD_FORMAT_CORRECTIONS).is_none());
+        assert_eq!(result[JSON_FIELD_ORIGINAL_PARAMS_MODIFIED], false);
+
+        let enhanced = EnhancedBrpResult {
+            result:             BrpResult::Success(Some(json!({ "entity": 7 }))),
+            format_corrections: vec![FormatCorrection {
+                component:        "Transform".to_string(),
+                original_format:  json!([1.0, 2.0, 3.0]),
+                corrected_format: json!({ "x": 1.0, "y": 2.0, "z": 3.0 }),
+                hint:             "Vec3 as object".to_string(),
+            }],
+            debug_info:         vec![],
+            params_modified:    true,
+        };
+        let result = batch_result(0, "bevy/spawn", &enhanced);
+        assert_eq!(result[JSON_FIELD_STATUS], "success");
+        assert_eq!(result[JSON_FIELD_DATA]["entity"], 7);
+        assert_eq!(
+            result[JSON_FIELD_FORMAT_CORRECTIONS][0]["component"],
+            "Transform"
+        );
+        assert_eq!(result[JSON_FIELD_ORIGINAL_PARAMS_MODIFIED], true);
+
+        // Reads never report whether their params were modified
+        let enhanced = EnhancedBrpResult {
+            result:             BrpResult::Success(Some(json!({ "components": {} }))),
+            format_corrections: vec![],
+            debug_info:         vec![],
+            params_modified:    false,
+        };
+        let result = batch_result(1, "bevy/get", &enhanced);
+        assert!(result.get(JSON_FIELD_ORIGINAL_PARAMS_MODIFIED).is_none());
+    }
+}
@@ -0,0 +1,117 @@
+//! Inspect or clear the format corrections learned across sessions
+//!
+//! Format discovery saves every correction that let a request through, keyed by the app's Bevy
+//! version, and applies it before its other tiers next time. This lists what has been learned,
+//! optionally for one version or type, and can forget it.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::JSON_FIELD_COUNT;
+use super::request_handler::knowledge_base;
+use crate::BrpMcpService;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_LIST_FORMAT_CORRECTIONS, TOOL_BRP_LIST_FORMAT_CORRECTIONS};
+
+/// Parameter restricting the list to one Bevy version
+const PARAM_BEVY_VERSION: &str = "bevy_version";
+
+/// Parameter restricting the list to one type
+const PARAM_TYPE_NAME: &str = "type_name";
+
+/// Parameter forgetting the listed corrections
+const PARAM_CLEAR: &str = "clear";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_LIST_FORMAT_CORRECTIONS.into(),
+        description:  DESC_BRP_LIST_FORMAT_CORRECTIONS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_BEVY_VERSION,
+                "Only corrections learned from apps on this Bevy version, e.g. 0.16.1",
+                false,
+            )
+            .add_string_property(
+                PARAM_TYPE_NAME,
+                "Only corrections for this fully-qualified type name",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_CLEAR,
+                "Forget the listed corrections (default: false)",
+                false,
+            )
+            .build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let bevy_version = arguments.get(PARAM_BEVY_VERSION).and_then(Value::as_str);
+    let type_name = arguments.get(PARAM_TYPE_NAME).and_then(Value::as_str);
+    let clear = arguments
+        .get(PARAM_CLEAR)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let store = knowledge_base::load().map_err(|report| report_to_mcp_error(&report))?;
+    let corrections: Vec<Value> = store
+        .versions
+        .iter()
+        .filter(|(version, _)| bevy_version.is_none_or(|wanted| wanted == *version))
+        .flat_map(|(version, types)| {
+            types
+                .iter()
+                .filter(|(name, _)| type_name.is_none_or(|wanted| wanted == *name))
+                .flat_map(move |(name, learned)| {
+                    learned.iter().map(move |learned| {
+                        json!({
+                            PARAM_BEVY_VERSION: version,
+                            PARAM_TYPE_NAME: name,
+                            "original_format": learned.original_format,
+                            "corrected_format": learned.corrected_format,
+                            "hint": learned.hint,
+                            "learned_at": learned.learned_at,
+                            "uses": learned.uses,
+                        })
+                    })
+                })
+        })
+        .collect();
+
+    let message = if clear {
+        let forgotten = knowledge_base::clear(bevy_version, type_name)
+            .map_err(|report| report_to_mcp_error(&report))?;
+        format!("Forgot {forgotten} learned format corrections")
+    } else {
+        format!("Found {} learned format corrections", corrections.len())
+    };
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "corrections": corrections,
+            JSON_FIELD_COUNT: corrections.len(),
+            "cleared": clear,
+            "store_path": knowledge_base::store_path(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
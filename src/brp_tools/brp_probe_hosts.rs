@@ -0,0 +1,92 @@
+//! Find the hosts a BRP app is reachable on
+//!
+//! Reports the detected environment (native, WSL or container) and probes the BRP port on
+//! every candidate host, so agents can tell a stopped app from one that is only reachable on
+//! another interface.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use super::support::brp_client::current_host;
+use super::support::host_detection::{self, environment, host_candidates, probe_hosts};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BRP_PROBE_HOSTS, TOOL_BRP_PROBE_HOSTS};
+use crate::{BrpMcpService, config};
+
+/// Parameter adding hosts to the probed candidates
+const PARAM_HOSTS: &str = "hosts";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_PROBE_HOSTS.into(),
+        description:  DESC_BRP_PROBE_HOSTS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port to probe (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_array_property(
+                PARAM_HOSTS,
+                "Additional host names or IP addresses to probe",
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let mut candidates = host_candidates();
+    for host in params::extract_optional_string_array(&arguments, PARAM_HOSTS).unwrap_or_default() {
+        if !candidates.contains(&host) {
+            candidates.push(host);
+        }
+    }
+    let probes = probe_hosts(candidates, port).await;
+
+    let remediation = host_detection::remediation_for(&probes, port);
+    let reachable: Vec<&str> = probes
+        .iter()
+        .filter(|probe| probe.reachable)
+        .map(|probe| probe.host.as_str())
+        .collect();
+
+    let message = if reachable.is_empty() {
+        format!("BRP port {port} is not reachable on any candidate host")
+    } else {
+        format!("BRP port {port} is reachable on {}", reachable.join(", "))
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "environment": environment(),
+            "configured_host": current_host(),
+            JSON_FIELD_PORT: port,
+            "probes": probes,
+            "remediation": remediation,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,450 @@
+//! Assertion suites evaluated against the live world
+//!
+//! A suite is a JSON document of named assertions. Each assertion selects entities with a query
+//! filter and states how many there should be and what every selected entity's components must
+//! look like, for example "exactly one `Player`", "no `Transform` contains NaN" or "the
+//! `MainCamera` is active". Failures come back with the entities and values that broke them.
+
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PATH, JSON_FIELD_PORT, PARAM_FILTER,
+};
+use super::request_handler::{parse_path, value_at};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_QUERY, DESC_BRP_RUN_ASSERTIONS, TOOL_BRP_RUN_ASSERTIONS};
+use crate::{BrpMcpService, config};
+
+/// Parameter carrying an inline suite
+const PARAM_SUITE: &str = "suite";
+
+/// Maximum number of offending entities reported per assertion
+const MAX_EVIDENCE: usize = 10;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_RUN_ASSERTIONS.into(),
+        description:  DESC_BRP_RUN_ASSERTIONS.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                JSON_FIELD_PATH,
+                "JSON file containing the assertion suite",
+                false,
+            )
+            .add_any_property(
+                PARAM_SUITE,
+                "Inline assertion suite: {name, assertions: [{name, filter, count, each}]}. Used when path is not given",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let suite = load_suite(&arguments).map_err(|report| report_to_mcp_error(&report))?;
+    let report = run_suite(&suite, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let failed = report.len() - report.iter().filter(|result| result.passed).count();
+    let name = suite.name.as_deref().unwrap_or("assertions");
+    let message = if failed == 0 {
+        format!("All {} assertions in '{name}' passed", report.len())
+    } else {
+        format!("{failed} of {} assertions in '{name}' failed", report.len())
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            PARAM_SUITE: suite.name,
+            "passed": failed == 0,
+            "passed_count": report.len() - failed,
+            "failed_count": failed,
+            "results": report.iter().map(AssertionResult::to_json).collect::<Vec<_>>(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// A named list of assertions
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suite {
+    #[serde(default)]
+    pub name:       Option<String>,
+    pub assertions: Vec<Assertion>,
+}
+
+/// One predicate over the entities selected by a query filter
+///
+/// With neither `count` nor `each`, the assertion requires at least one matching entity.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Assertion {
+    pub name:   String,
+    /// Query filter as in `bevy_query`: `{with, without}`
+    #[serde(default)]
+    pub filter: Option<Value>,
+    #[serde(default)]
+    pub count:  Option<CountExpectation>,
+    /// Checks every matching entity must pass
+    #[serde(default)]
+    pub each:   Vec<Check>,
+}
+
+/// Expected number of matching entities
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CountExpectation {
+    Exactly(u64),
+    Range {
+        #[serde(default)]
+        min: Option<u64>,
+        #[serde(default)]
+        max: Option<u64>,
+    },
+}
+
+impl CountExpectation {
+    const fn accepts(&self, count: u64) -> bool {
+        match *self {
+            Self::Exactly(expected) => count == expected,
+            Self::Range { min, max } => {
+                (match min {
+                    Some(min) => count >= min,
+                    None => true,
+                }) && (match max {
+                    Some(max) => count <= max,
+                    None => true,
+                })
+            }
+        }
+    }
+}
+
+/// A condition on one component value of an entity
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Check {
+    /// Fully-qualified component type
+    pub component:  String,
+    /// Dot-separated field path inside the component, e.g. `translation.x` or `0`
+    #[serde(default)]
+    pub path:       Option<String>,
+    #[serde(default)]
+    pub equals:     Option<Value>,
+    #[serde(default)]
+    pub not_equals: Option<Value>,
+    #[serde(default)]
+    pub min:        Option<f64>,
+    #[serde(default)]
+    pub max:        Option<f64>,
+    /// Require every number in the value to be finite. BRP writes NaN and infinity as `null`.
+    #[serde(default)]
+    pub finite:     bool,
+}
+
+impl Check {
+    /// Why `value` fails the check, or `None` if it passes
+    fn failure(&self, value: Option<&Value>) -> Option<String> {
+        let Some(value) = value else {
+            return Some(self.path.as_ref().map_or_else(
+                || "component missing".to_string(),
+                |path| format!("field '{path}' not found"),
+            ));
+        };
+        if let Some(expected) = self.equals.as_ref().filter(|expected| value != *expected) {
+            return Some(format!("expected {expected}"));
+        }
+        if let Some(unexpected) = self
+            .not_equals
+            .as_ref()
+            .filter(|unexpected| value == *unexpected)
+        {
+            return Some(format!("expected anything but {unexpected}"));
+        }
+        if self.min.is_some() || self.max.is_some() {
+            let Some(number) = value.as_f64() else {
+                return Some("expected a number".to_string());
+            };
+            if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max)
+            {
+                return Some(format!(
+                    "expected a number in {}..={}",
+                    self.min.map_or_else(String::new, |min| min.to_string()),
+                    self.max.map_or_else(String::new, |max| max.to_string())
+                ));
+            }
+        }
+        if self.finite && !is_finite(value) {
+            return Some("expected finite numbers (null means NaN or infinity)".to_string());
+        }
+        None
+    }
+}
+
+/// Whether a value holds no `null`, which is how BRP writes non-finite floats
+fn is_finite(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Array(items) => items.iter().all(is_finite),
+        Value::Object(fields) => fields.values().all(is_finite),
+        _ => true,
+    }
+}
+
+/// The value at a field path like `translation.x`, where the leading `.` may be left out
+fn field_at<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    let segments = if path.starts_with(['.', '[', '#']) {
+        parse_path(path)
+    } else {
+        parse_path(&format!(".{path}"))
+    };
+    value_at(value, &segments?)
+}
+
+/// Outcome of one assertion
+pub struct AssertionResult {
+    pub name:          String,
+    pub passed:        bool,
+    pub matched_count: usize,
+    /// What was wrong, when the assertion failed
+    pub failures:      Vec<Value>,
+}
+
+impl AssertionResult {
+    fn to_json(&self) -> Value {
+        let mut result = json!({
+            "name": self.name,
+            "passed": self.passed,
+            "matched_count": self.matched_count,
+        });
+        if !self.failures.is_empty() {
+            result["evidence"] = json!(self.failures);
+        }
+        result
+    }
+}
+
+/// Read the suite from `path` or the inline `suite` argument
+fn load_suite(arguments: &Value) -> Result<Suite> {
+    let suite = if let Some(path) = arguments.get(JSON_FIELD_PATH).and_then(Value::as_str) {
+        let path = PathBuf::from(path);
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            error_stack::Report::new(Error::io_failed("read assertion suite", &path, &e))
+        })?;
+        serde_json::from_str(&contents)
+    } else if let Some(suite) = arguments.get(PARAM_SUITE) {
+        serde_json::from_value(suite.clone())
+    } else {
+        return Err(error_stack::Report::new(Error::missing(&format!(
+            "parameter '{JSON_FIELD_PATH}' or '{PARAM_SUITE}'"
+        ))));
+    };
+    suite.map_err(|e| error_stack::Report::new(Error::invalid("assertion suite", e)))
+}
+
+/// Evaluate every assertion of a suite in order
+async fn run_suite(suite: &Suite, port: u16) -> Result<Vec<AssertionResult>> {
+    let mut results = Vec::with_capacity(suite.assertions.len());
+    for assertion in &suite.assertions {
+        let rows = query(assertion, port).await?;
+        results.push(evaluate(assertion, &rows));
+    }
+    Ok(results)
+}
+
+/// The entities an assertion selects, with the components its checks look at
+///
+/// Components named by checks are required, so "no `Transform` contains NaN" only looks at
+/// entities that have a `Transform`.
+async fn query(assertion: &Assertion, port: u16) -> Result<Vec<Value>> {
+    let mut components: Vec<&str> = assertion
+        .each
+        .iter()
+        .map(|check| check.component.as_str())
+        .collect();
+    components.sort_unstable();
+    components.dedup();
+
+    let mut params = json!({ "data": { JSON_FIELD_COMPONENTS: components } });
+    if let Some(filter) = &assertion.filter {
+        params[PARAM_FILTER] = filter.clone();
+    }
+    match execute_brp_method(BRP_METHOD_QUERY, Some(params), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => Ok(rows),
+        BrpResult::Success(_) => Ok(Vec::new()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Assertion '{}' failed to query: {}",
+            assertion.name, e.message
+        )))),
+    }
+}
+
+/// Check the queried rows against an assertion
+fn evaluate(assertion: &Assertion, rows: &[Value]) -> AssertionResult {
+    let mut failures = Vec::new();
+
+    let count = rows.len() as u64;
+    let count_ok = match &assertion.count {
+        Some(expected) => expected.accepts(count),
+        None if assertion.each.is_empty() => count > 0,
+        None => true,
+    };
+    if !count_ok {
+        let entities: Vec<&Value> = rows
+            .iter()
+            .filter_map(|row| row.get(JSON_FIELD_ENTITY))
+            .take(MAX_EVIDENCE)
+            .collect();
+        failures.push(json!({
+            "reason": match &assertion.count {
+                Some(CountExpectation::Exactly(expected)) => format!("expected exactly {expected} entities, found {count}"),
+                Some(CountExpectation::Range { .. }) => format!("{count} entities is outside the expected range"),
+                None => "no entity matched".to_string(),
+            },
+            "entities": entities,
+        }));
+    }
+
+    let mut failing_entities = 0;
+    for row in rows {
+        let components = row.get(JSON_FIELD_COMPONENTS);
+        for check in &assertion.each {
+            let component = components.and_then(|components| components.get(&check.component));
+            let value = check.path.as_ref().map_or(component, |path| {
+                component.and_then(|component| field_at(component, path))
+            });
+            if let Some(reason) = check.failure(value) {
+                failing_entities += 1;
+                if failing_entities <= MAX_EVIDENCE {
+                    failures.push(json!({
+                        JSON_FIELD_ENTITY: row.get(JSON_FIELD_ENTITY),
+                        "component": check.component,
+                        JSON_FIELD_PATH: check.path,
+                        "actual": value,
+                        "reason": reason,
+                    }));
+                }
+                break;
+            }
+        }
+    }
+    if failing_entities > MAX_EVIDENCE {
+        failures.push(json!({
+            "reason": format!("{} more entities failed", failing_entities - MAX_EVIDENCE),
+        }));
+    }
+
+    AssertionResult {
+        name: assertion.name.clone(),
+        passed: failures.is_empty(),
+        matched_count: rows.len(),
+        failures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const TRANSFORM: &str = "bevy_transform::components::transform::Transform";
+    const CAMERA: &str = "bevy_render::camera::camera::Camera";
+
+    fn assertion(value: Value) -> Assertion {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_count_expectations() {
+        let rows = [json!({ "entity": 1 }), json!({ "entity": 2 })];
+
+        let exactly_one = assertion(json!({ "name": "one player", "count": 1 }));
+        let result = evaluate(&exactly_one, &rows);
+        assert!(!result.passed);
+        assert_eq!(result.failures[0]["entities"], json!([1, 2]));
+
+        let range = assertion(json!({ "name": "some enemies", "count": { "min": 1, "max": 3 } }));
+        assert!(evaluate(&range, &rows).passed);
+
+        let exists = assertion(json!({ "name": "anything" }));
+        assert!(evaluate(&exists, &rows).passed);
+        assert!(!evaluate(&exists, &[]).passed);
+    }
+
+    #[test]
+    fn test_checks_report_offending_values() {
+        let rows = [
+            json!({ "entity": 1, "components": {
+                TRANSFORM: { "translation": [0.0, 1.0, 2.0] },
+                CAMERA: { "is_active": true, "order": 0 }
+            }}),
+            json!({ "entity": 2, "components": {
+                TRANSFORM: { "translation": [null, 1.0, 2.0] },
+                CAMERA: { "is_active": false, "order": 1 }
+            }}),
+        ];
+
+        let no_nan = assertion(json!({
+            "name": "no NaN transform",
+            "each": [{ "component": TRANSFORM, "finite": true }]
+        }));
+        let result = evaluate(&no_nan, &rows);
+        assert!(!result.passed);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0]["entity"], 2);
+
+        let active = assertion(json!({
+            "name": "camera active",
+            "each": [
+                { "component": CAMERA, "path": "is_active", "equals": true },
+                { "component": TRANSFORM, "path": "translation.1", "min": 0.5, "max": 1.5 }
+            ]
+        }));
+        let result = evaluate(&active, &rows);
+        assert_eq!(result.failures[0]["actual"], false);
+        assert_eq!(result.failures[0]["path"], "is_active");
+
+        assert!(
+            serde_json::from_value::<Suite>(json!({ "assertions": [{ "nme": "typo" }] })).is_err()
+        );
+    }
+}
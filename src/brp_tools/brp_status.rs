@@ -1,18 +1,29 @@
+use std::time::Instant;
+
 use rmcp::model::{CallToolResult, Tool};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
-use serde_json::json;
+use serde::Serialize;
+use serde_json::{Value, json};
 use sysinfo::System;
 
-use super::constants::{DEFAULT_BRP_PORT, JSON_FIELD_PORT, JSON_FIELD_STATUS};
+use super::brp_discover_ports::{DEFAULT_PORT_RANGE, discover_ports};
+use super::constants::{
+    DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT, JSON_FIELD_STATUS, PARAM_DATA,
+};
 use super::support::brp_client::{BrpResult, execute_brp_method};
-use crate::BrpMcpService;
+use super::support::{capabilities, host_detection};
+use crate::app_tools::support::managed_apps::{self, ManagedApp};
 use crate::constants::{PARAM_APP_NAME, PARAM_PORT};
 use crate::error::{Error, report_to_mcp_error};
 use crate::support::response::ResponseBuilder;
 use crate::support::serialization::json_response_to_result;
 use crate::support::{params, schema};
-use crate::tools::{BRP_METHOD_LIST, DESC_BRP_STATUS, TOOL_BRP_STATUS};
+use crate::tools::{
+    BRP_EXTRAS_PREFIX, BRP_METHOD_LIST, BRP_METHOD_QUERY, BRP_METHOD_RPC_DISCOVER, DESC_BRP_STATUS,
+    TOOL_BRP_STATUS,
+};
+use crate::{BrpMcpService, config};
 
 pub fn register_tool() -> Tool {
     Tool {
@@ -22,9 +33,10 @@ pub fn register_tool() -> Tool {
             .add_string_property(PARAM_APP_NAME, "Name of the process to check for", true)
             .add_number_property(
                 PARAM_PORT,
-                &format!("Port to check for BRP (default: {DEFAULT_BRP_PORT})"),
+                "Port to check for BRP (default: the managed app's port, otherwise a live port found by brp_discover_ports)",
                 false,
             )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
             .build(),
     }
 }
@@ -36,7 +48,19 @@ pub async fn handle(
 ) -> Result<CallToolResult, McpError> {
     // Get parameters
     let app_name = params::extract_required_string(&request, PARAM_APP_NAME)?;
-    let port = params::extract_optional_number(&request, PARAM_PORT, u64::from(DEFAULT_BRP_PORT))?;
+
+    // Managed apps default to the port they were launched or attached with, anything else to
+    // a port found by scanning the default range
+    let port_given = request
+        .arguments
+        .as_ref()
+        .is_some_and(|arguments| arguments.contains_key(PARAM_PORT));
+    let default_port = match managed_apps::get(app_name).and_then(|app| app.port) {
+        Some(port) => port,
+        None if !port_given => discovered_port(app_name).await,
+        None => config::default_port(),
+    };
+    let port = params::extract_optional_number(&request, PARAM_PORT, u64::from(default_port))?;
 
     // Check the app
     check_brp_for_app(
@@ -54,6 +78,19 @@ pub async fn handle(
     .await
 }
 
+/// Port to check when none is given: the scanned port registered to the app, otherwise the
+/// default port if it is live, otherwise the first live port
+async fn discovered_port(app_name: &str) -> u16 {
+    let default_port = config::default_port();
+    let discovered = discover_ports(DEFAULT_PORT_RANGE).await;
+    discovered
+        .iter()
+        .find(|found| found.app_name.as_deref() == Some(app_name))
+        .or_else(|| discovered.iter().find(|found| found.port == default_port))
+        .or_else(|| discovered.first())
+        .map_or(default_port, |found| found.port)
+}
+
 /// Normalize process name for robust matching
 fn normalize_process_name(name: &str) -> String {
     // Convert to lowercase and remove common path separators and extensions
@@ -72,7 +109,7 @@ fn normalize_process_name(name: &str) -> String {
 }
 
 /// Check if process matches the target app name
-fn process_matches_app(process: &sysinfo::Process, target_app: &str) -> bool {
+pub fn process_matches_app(process: &sysinfo::Process, target_app: &str) -> bool {
     let normalized_target = normalize_process_name(target_app);
 
     // Check process name
@@ -108,43 +145,138 @@ fn process_matches_app(process: &sysinfo::Process, target_app: &str) -> bool {
     false
 }
 
-async fn check_brp_for_app(app_name: &str, port: u16) -> Result<CallToolResult, McpError> {
-    // Check if a process with this name is running using sysinfo
-    let mut system = System::new_all();
-    system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+/// The process of a checked app
+#[derive(Debug, Clone, Serialize)]
+struct ProcessReport {
+    pid:         u32,
+    /// Seconds since the process started
+    uptime_secs: u64,
+    cmdline:     Vec<String>,
+}
+
+impl ProcessReport {
+    fn new(process: &sysinfo::Process) -> Self {
+        Self {
+            pid:         process.pid().as_u32(),
+            uptime_secs: process.run_time(),
+            cmdline:     process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+        }
+    }
+}
+
+/// How the BRP server on a port answered
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+struct BrpHealth {
+    reachable:    bool,
+    /// Round trip of `rpc.discover`, or of `bevy/list` for servers without it
+    latency_ms:   Option<u64>,
+    /// `info.version` of the `rpc.discover` document, the version of Bevy's remote crate
+    bevy_version: Option<String>,
+    /// Whether `rpc.discover` lists `bevy_brp_extras` methods
+    brp_extras:   bool,
+    entity_count: Option<usize>,
+}
 
-    let running_process = system
+impl BrpHealth {
+    /// Read an `rpc.discover` result
+    fn from_discover(document: &Value, latency_ms: u64) -> Self {
+        Self {
+            reachable:    true,
+            latency_ms:   Some(latency_ms),
+            bevy_version: document
+                .pointer("/info/version")
+                .and_then(Value::as_str)
+                .map(String::from),
+            brp_extras:   capabilities::method_names(document)
+                .iter()
+                .any(|method| method.starts_with(BRP_EXTRAS_PREFIX)),
+            entity_count: None,
+        }
+    }
+}
+
+/// Find the app's process: managed apps by PID, anything else by matching the process name
+fn find_process(app_name: &str, managed: Option<&ManagedApp>) -> Option<ProcessReport> {
+    let mut system = System::new();
+    if let Some(app) = managed {
+        let pid = sysinfo::Pid::from_u32(app.pid);
+        system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+        return system.process(pid).map(ProcessReport::new);
+    }
+    system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    system
         .processes()
         .values()
-        .find(|process| process_matches_app(process, app_name));
+        .find(|process| process_matches_app(process, app_name))
+        .map(ProcessReport::new)
+}
+
+/// Check the BRP server on a port: latency, version and extras from `rpc.discover`, and the
+/// number of entities
+async fn check_brp_health(port: u16) -> BrpHealth {
+    let started = Instant::now();
+    let discovered = execute_brp_method(BRP_METHOD_RPC_DISCOVER, None, Some(port)).await;
+    let mut health = if let Ok(BrpResult::Success(document)) = discovered {
+        BrpHealth::from_discover(&document.unwrap_or_default(), elapsed_ms(started))
+    } else {
+        // Servers without rpc.discover still answer the other methods
+        let started = Instant::now();
+        let reachable = check_brp_on_port(port).await.unwrap_or(false);
+        BrpHealth {
+            reachable,
+            latency_ms: reachable.then(|| elapsed_ms(started)),
+            ..BrpHealth::default()
+        }
+    };
+    if health.reachable {
+        let query = json!({ PARAM_DATA: {} });
+        if let Ok(BrpResult::Success(Some(Value::Array(entities)))) =
+            execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await
+        {
+            health.entity_count = Some(entities.len());
+        }
+    }
+    health
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+async fn check_brp_for_app(app_name: &str, port: u16) -> Result<CallToolResult, McpError> {
+    let managed = managed_apps::get(app_name);
+    let process = find_process(app_name, managed.as_ref());
+    let running_pid = process.as_ref().map(|process| process.pid);
 
     // Check BRP connectivity
-    let brp_responsive = check_brp_on_port(port).await?;
+    let brp = check_brp_health(port).await;
+    let brp_responsive = brp.reachable;
+    let remediation = if brp_responsive {
+        None
+    } else {
+        host_detection::remediation(port).await
+    };
 
     // Build response based on findings
-    let (status, message, app_running, app_pid) = match (running_process, brp_responsive) {
-        (Some(process), true) => {
-            let pid = process.pid().as_u32();
-            (
-                "running_with_brp",
-                format!(
-                    "Process '{app_name}' (PID: {pid}) is running with BRP enabled on port {port}"
-                ),
-                true,
-                Some(pid),
-            )
-        }
-        (Some(process), false) => {
-            let pid = process.pid().as_u32();
-            (
-                "running_no_brp",
-                format!(
-                    "Process '{app_name}' (PID: {pid}) is running but not responding to BRP on port {port}. Make sure RemotePlugin is added to your Bevy app."
-                ),
-                true,
-                Some(pid),
-            )
-        }
+    let (status, message, app_running, app_pid) = match (running_pid, brp_responsive) {
+        (Some(pid), true) => (
+            "running_with_brp",
+            format!("Process '{app_name}' (PID: {pid}) is running with BRP enabled on port {port}"),
+            true,
+            Some(pid),
+        ),
+        (Some(pid), false) => (
+            "running_no_brp",
+            format!(
+                "Process '{app_name}' (PID: {pid}) is running but not responding to BRP on port {port}. Make sure RemotePlugin is added to your Bevy app."
+            ),
+            true,
+            Some(pid),
+        ),
         (None, true) => {
             // BRP is responding but our specific process isn't found
             (
@@ -164,6 +296,11 @@ async fn check_brp_for_app(app_name: &str, port: u16) -> Result<CallToolResult,
         ),
     };
 
+    let message = match &remediation {
+        Some(remediation) => format!("{message}. {remediation}"),
+        None => message,
+    };
+
     let response = ResponseBuilder::success()
         .message(message)
         .data(json!({
@@ -172,7 +309,11 @@ async fn check_brp_for_app(app_name: &str, port: u16) -> Result<CallToolResult,
             JSON_FIELD_PORT: port,
             "app_running": app_running,
             "brp_responsive": brp_responsive,
-            "app_pid": app_pid
+            "app_pid": app_pid,
+            "managed": managed.map(|app| app.origin),
+            "remediation": remediation,
+            "process": process,
+            "brp": brp,
         }))
         .map_or_else(
             |_| {
@@ -187,7 +328,7 @@ async fn check_brp_for_app(app_name: &str, port: u16) -> Result<CallToolResult,
 }
 
 /// Check if BRP is responding on the given port
-async fn check_brp_on_port(port: u16) -> Result<bool, McpError> {
+pub async fn check_brp_on_port(port: u16) -> Result<bool, McpError> {
     // Try a simple BRP request to check connectivity using bevy/list
     match execute_brp_method(BRP_METHOD_LIST, None, Some(port)).await {
         Ok(BrpResult::Success(_)) => {
@@ -200,3 +341,24 @@ async fn check_brp_on_port(port: u16) -> Result<bool, McpError> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_document_gives_version_and_extras() {
+        let document = json!({
+            "info": { "title": "Bevy Remote Protocol", "version": "0.16.1" },
+            "methods": [{ "name": "bevy/query" }, { "name": "brp_extras/screenshot" }],
+        });
+        let health = BrpHealth::from_discover(&document, 3);
+        assert!(health.reachable && health.brp_extras);
+        assert_eq!(health.bevy_version.as_deref(), Some("0.16.1"));
+        assert_eq!(health.latency_ms, Some(3));
+
+        let plain = BrpHealth::from_discover(&json!({ "methods": [{ "name": "bevy/list" }] }), 1);
+        assert!(!plain.brp_extras);
+        assert_eq!(plain.bevy_version, None);
+    }
+}
@@ -0,0 +1,171 @@
+//! Dry-run format discovery for a BRP payload
+//!
+//! Runs the same tiered analysis as the automatic format discovery but never executes the
+//! request itself, so agents can check spawn/insert/mutate payloads before sending them.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::brp_set_debug_mode::is_debug_enabled;
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_DEBUG_INFO, JSON_FIELD_FORMAT_CORRECTIONS,
+    JSON_FIELD_HOST, JSON_FIELD_METHOD, JSON_FIELD_PORT,
+};
+use super::request_handler::{FormatValidation, validate_format};
+use super::support::response_formatter::corrections_json;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_VALIDATE_FORMAT, TOOL_BRP_VALIDATE_FORMAT};
+use crate::{BrpMcpService, config};
+
+#[derive(Debug, Deserialize)]
+struct ValidateFormatParams {
+    method:        String,
+    params:        Value,
+    #[serde(default)]
+    error_message: Option<String>,
+    #[serde(default)]
+    port:          Option<u16>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_VALIDATE_FORMAT.into(),
+        description:  DESC_BRP_VALIDATE_FORMAT.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                JSON_FIELD_METHOD,
+                "BRP method the payload is for (bevy/spawn, bevy/insert, bevy/mutate_component, bevy/insert_resource or bevy/mutate_resource)",
+                true,
+            )
+            .add_any_property(
+                "params",
+                "The method parameters to validate, exactly as they would be sent",
+                true,
+            )
+            .add_string_property(
+                "error_message",
+                "Error message from a failed attempt, enables pattern-based corrections",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let args = request.arguments.unwrap_or_default();
+    let params: ValidateFormatParams =
+        serde_json::from_value(Value::Object(args)).map_err(|e| -> McpError {
+            report_to_mcp_error(
+                &error_stack::Report::new(Error::ParameterExtraction(
+                    "Invalid parameters for brp_validate_format".to_string(),
+                ))
+                .attach_printable(format!("Deserialization error: {e}")),
+            )
+        })?;
+
+    let port = params.port.unwrap_or_else(config::default_port);
+    let validation = validate_format(
+        &params.method,
+        params.params,
+        Some(port),
+        params.error_message,
+    )
+    .await
+    .map_err(|report| report_to_mcp_error(&report))?;
+
+    let message = if validation.format_corrections.is_empty() {
+        format!("No format corrections found for {}", params.method)
+    } else {
+        format!(
+            "Found {} format corrections for {} (request not executed)",
+            validation.format_corrections.len(),
+            params.method
+        )
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(validation_json(&params.method, port, &validation))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Describe the outcome of a dry run
+fn validation_json(method: &str, port: u16, validation: &FormatValidation) -> Value {
+    let mut data = json!({
+        JSON_FIELD_METHOD: method,
+        "corrected_params": validation.corrected_params,
+        "corrected": !validation.format_corrections.is_empty(),
+        JSON_FIELD_FORMAT_CORRECTIONS: corrections_json(&validation.format_corrections),
+        JSON_FIELD_PORT: port,
+    });
+
+    if is_debug_enabled() && !validation.debug_info.is_empty() {
+        data[JSON_FIELD_DEBUG_INFO] = json!(validation.debug_info);
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::request_handler::FormatCorrection;
+
+    #[tokio::test]
+    async fn test_rejects_methods_without_format_discovery() {
+        let result = validate_format("bevy/list", json!({}), Some(DEFAULT_BRP_PORT), None).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validation_json_reports_corrections() {
+        let validation = FormatValidation {
+            corrected_params:   json!({ "components": { "Transform": { "x": 1.0 } } }),
+            format_corrections: vec![FormatCorrection {
+                component:        "Transform".to_string(),
+                original_format:  json!([1.0]),
+                corrected_format: json!({ "x": 1.0 }),
+                hint:             "Vec3 as object".to_string(),
+            }],
+            debug_info:         vec![],
+        };
+
+        let data = validation_json("bevy/spawn", DEFAULT_BRP_PORT, &validation);
+        assert_eq!(data["corrected"], true);
+        assert_eq!(
+            data["corrected_params"]["components"]["Transform"]["x"],
+            1.0
+        );
+        assert_eq!(
+            data[JSON_FIELD_FORMAT_CORRECTIONS][0]["hint"],
+            "Vec3 as object"
+        );
+    }
+}
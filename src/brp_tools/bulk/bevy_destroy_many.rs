@@ -0,0 +1,111 @@
+//! Destroy many entities in one call
+//!
+//! Honors the `destructive_ops` policy like `bevy_destroy`: the preview lists every entity with
+//! its descendants, and one token confirms the whole list.
+
+use futures::future::join_all;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{BulkParams, DEFAULT_CHUNK_SIZE, PARAM_CHUNK_SIZE};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_CONFIRM, DESC_HOST, JSON_FIELD_ENTITIES, JSON_FIELD_ENTITY,
+    JSON_FIELD_HOST, JSON_FIELD_PORT, PARAM_CONFIRM,
+};
+use crate::brp_tools::support::{brp_client, destructive_ops};
+use crate::error::report_to_mcp_error;
+use crate::support::progress::{self, Progress};
+use crate::support::schema;
+use crate::tools::{BRP_METHOD_DESTROY, DESC_BEVY_DESTROY_MANY, TOOL_BEVY_DESTROY_MANY};
+use crate::{BrpMcpService, config};
+
+#[derive(Debug, Deserialize)]
+struct DestroyManyParams {
+    entities:   Vec<u64>,
+    #[serde(default)]
+    chunk_size: Option<usize>,
+    #[serde(default)]
+    port:       Option<u16>,
+}
+
+impl BulkParams for DestroyManyParams {
+    const ITEMS_FIELD: &'static str = JSON_FIELD_ENTITIES;
+
+    fn item_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    fn chunk_size(&self) -> Option<usize> {
+        self.chunk_size
+    }
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_DESTROY_MANY.into(),
+        description:  DESC_BEVY_DESTROY_MANY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_any_property(JSON_FIELD_ENTITIES, "Array of entity IDs to destroy", true)
+            .add_number_property(PARAM_CHUNK_SIZE, &super::chunk_size_description(), false)
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .add_any_property(PARAM_CONFIRM, DESC_CONFIRM, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    mut request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let confirmation = destructive_ops::take_confirmation(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let params: DestroyManyParams = super::parse_params(request, TOOL_BEVY_DESTROY_MANY)?;
+    let port = params.port.unwrap_or_else(config::default_port);
+    let items: Vec<Value> = params
+        .entities
+        .iter()
+        .map(|entity| json!({ JSON_FIELD_ENTITY: entity }))
+        .collect();
+
+    let operation = format!(
+        "{TOOL_BEVY_DESTROY_MANY} {}:{port} {:?}",
+        brp_client::current_host(),
+        params.entities
+    );
+    if let Some(reason) = destructive_ops::preview_reason(&operation, &confirmation) {
+        let previews = join_all(
+            items
+                .iter()
+                .map(|item| destructive_ops::preview(BRP_METHOD_DESTROY, Some(item), port)),
+        )
+        .await;
+        return Ok(destructive_ops::preview_response(
+            &operation,
+            &format!("destroying {} entities", params.entities.len()),
+            &reason,
+            &json!(previews),
+        ));
+    }
+
+    let results = progress::scope(
+        Progress::new(&context),
+        Box::pin(super::execute_chunked(
+            BRP_METHOD_DESTROY,
+            &items,
+            port,
+            params.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+        )),
+    )
+    .await;
+
+    Ok(super::bulk_response("Destroyed", &results, port, &[]))
+}
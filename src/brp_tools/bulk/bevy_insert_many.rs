@@ -0,0 +1,94 @@
+//! Insert components into many entities in one call
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use super::{BulkParams, DEFAULT_CHUNK_SIZE, PARAM_CHUNK_SIZE, PARAM_ITEMS};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_HOST,
+    JSON_FIELD_PORT,
+};
+use crate::support::progress::{self, Progress};
+use crate::support::schema;
+use crate::tools::{BRP_METHOD_INSERT, DESC_BEVY_INSERT_MANY, TOOL_BEVY_INSERT_MANY};
+use crate::{BrpMcpService, config};
+
+/// Components to insert into one entity
+#[derive(Debug, Deserialize)]
+struct InsertItem {
+    entity:     u64,
+    components: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InsertManyParams {
+    items:      Vec<InsertItem>,
+    #[serde(default)]
+    chunk_size: Option<usize>,
+    #[serde(default)]
+    port:       Option<u16>,
+}
+
+impl BulkParams for InsertManyParams {
+    const ITEMS_FIELD: &'static str = PARAM_ITEMS;
+
+    fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    fn chunk_size(&self) -> Option<usize> {
+        self.chunk_size
+    }
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_INSERT_MANY.into(),
+        description:  DESC_BEVY_INSERT_MANY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_any_property(
+                PARAM_ITEMS,
+                "Array of insertions, each an object with 'entity' and 'components' (fully-qualified component type names to component values)",
+                true,
+            )
+            .add_number_property(PARAM_CHUNK_SIZE, &super::chunk_size_description(), false)
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let params: InsertManyParams = super::parse_params(request, TOOL_BEVY_INSERT_MANY)?;
+    let port = params.port.unwrap_or_else(config::default_port);
+
+    let items = params
+        .items
+        .into_iter()
+        .map(|item| json!({ JSON_FIELD_ENTITY: item.entity, JSON_FIELD_COMPONENTS: item.components }))
+        .collect();
+
+    let results = progress::scope(
+        Progress::new(&context),
+        Box::pin(super::execute_with_discovery(
+            BRP_METHOD_INSERT,
+            items,
+            port,
+            params.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+        )),
+    )
+    .await;
+
+    Ok(super::bulk_response("Inserted into", &results, port, &[]))
+}
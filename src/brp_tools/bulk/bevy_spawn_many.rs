@@ -0,0 +1,104 @@
+//! Spawn many entities in one call
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use super::{BulkParams, DEFAULT_CHUNK_SIZE, PARAM_CHUNK_SIZE, PARAM_ITEMS};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_DATA, JSON_FIELD_ENTITIES,
+    JSON_FIELD_ENTITY, JSON_FIELD_HOST, JSON_FIELD_PORT,
+};
+use crate::brp_tools::support::spawn_tagging;
+use crate::support::progress::{self, Progress};
+use crate::support::schema;
+use crate::tools::{BRP_METHOD_SPAWN, DESC_BEVY_SPAWN_MANY, TOOL_BEVY_SPAWN_MANY};
+use crate::{BrpMcpService, config};
+
+#[derive(Debug, Deserialize)]
+struct SpawnManyParams {
+    items:      Vec<Map<String, Value>>,
+    #[serde(default)]
+    chunk_size: Option<usize>,
+    #[serde(default)]
+    port:       Option<u16>,
+}
+
+impl BulkParams for SpawnManyParams {
+    const ITEMS_FIELD: &'static str = PARAM_ITEMS;
+
+    fn item_count(&self) -> usize {
+        self.items.len()
+    }
+
+    fn chunk_size(&self) -> Option<usize> {
+        self.chunk_size
+    }
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SPAWN_MANY.into(),
+        description:  DESC_BEVY_SPAWN_MANY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_any_property(
+                PARAM_ITEMS,
+                "Array of entities to spawn, each an object of fully-qualified component type names to component values",
+                true,
+            )
+            .add_number_property(PARAM_CHUNK_SIZE, &super::chunk_size_description(), false)
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let params: SpawnManyParams = super::parse_params(request, TOOL_BEVY_SPAWN_MANY)?;
+    let port = params.port.unwrap_or_else(config::default_port);
+
+    let tag = spawn_tagging::is_enabled();
+    let items = params
+        .items
+        .into_iter()
+        .map(|components| {
+            let mut item = json!({ JSON_FIELD_COMPONENTS: components });
+            if tag {
+                spawn_tagging::tag_spawn_params(&mut item);
+            }
+            item
+        })
+        .collect();
+
+    let results = progress::scope(
+        Progress::new(&context),
+        Box::pin(super::execute_with_discovery(
+            BRP_METHOD_SPAWN,
+            items,
+            port,
+            params.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+        )),
+    )
+    .await;
+    let entities: Vec<Value> = results
+        .iter()
+        .filter_map(|result| result[JSON_FIELD_DATA].get(JSON_FIELD_ENTITY).cloned())
+        .collect();
+
+    Ok(super::bulk_response(
+        "Spawned",
+        &results,
+        port,
+        &[(JSON_FIELD_ENTITIES, json!(entities))],
+    ))
+}
@@ -0,0 +1,356 @@
+//! Bulk entity operations: spawning, inserting into and destroying many entities in one call
+//!
+//! Items are sent in chunks of concurrent BRP requests, and every item gets its own result entry.
+//! A progress notification counting the items sent so far follows every chunk.
+//! Spawns and inserts run format discovery once per component type: the first item carrying a
+//! type goes through discovery on its own, and the corrections it needed are applied to values of
+//! the same type and shape in the later items before they're sent.
+
+pub mod bevy_destroy_many;
+pub mod bevy_insert_many;
+pub mod bevy_spawn_many;
+
+use std::collections::HashMap;
+
+use futures::future::join_all;
+use rmcp::Error as McpError;
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use super::constants::{
+    JSON_FIELD_COMPONENTS, JSON_FIELD_DATA, JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_PORT,
+    JSON_FIELD_STATUS,
+};
+use super::request_handler::knowledge_base::LearnedCorrection;
+use super::request_handler::{FormatCorrection, execute_brp_method_with_format_discovery};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::response_formatter::corrections_json;
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::progress;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+
+/// Parameter listing the items of a bulk operation
+pub const PARAM_ITEMS: &str = "items";
+
+/// Parameter giving the number of concurrent requests
+pub const PARAM_CHUNK_SIZE: &str = "chunk_size";
+
+/// Concurrent requests when no chunk size is given
+pub const DEFAULT_CHUNK_SIZE: usize = 25;
+
+/// Most concurrent requests
+pub const MAX_CHUNK_SIZE: usize = 100;
+
+/// Most items one call may carry
+pub const MAX_ITEMS: usize = 1000;
+
+/// Hint of a correction carried over from an earlier item
+const CARRIED_OVER_HINT: &str = "Applied the correction format discovery found for an earlier item";
+
+/// Arguments of a bulk tool
+pub trait BulkParams: DeserializeOwned {
+    /// Parameter listing the items
+    const ITEMS_FIELD: &'static str;
+
+    fn item_count(&self) -> usize;
+
+    fn chunk_size(&self) -> Option<usize>;
+}
+
+/// Parse the arguments of a bulk tool, checking the number of items and the chunk size
+pub fn parse_params<T: BulkParams>(
+    request: CallToolRequestParam,
+    tool: &str,
+) -> std::result::Result<T, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let params: T = serde_json::from_value(arguments).map_err(|e| -> McpError {
+        report_to_mcp_error(
+            &error_stack::Report::new(Error::ParameterExtraction(format!(
+                "Invalid parameters for {tool}"
+            )))
+            .attach_printable(format!("Deserialization error: {e}")),
+        )
+    })?;
+
+    let invalid = |field: &str, details: String| {
+        report_to_mcp_error(&error_stack::Report::new(Error::invalid(field, details)))
+    };
+    if !(1..=MAX_ITEMS).contains(&params.item_count()) {
+        return Err(invalid(
+            T::ITEMS_FIELD,
+            format!("must contain between 1 and {MAX_ITEMS} items"),
+        ));
+    }
+    if params
+        .chunk_size()
+        .is_some_and(|size| !(1..=MAX_CHUNK_SIZE).contains(&size))
+    {
+        return Err(invalid(
+            PARAM_CHUNK_SIZE,
+            format!("must be between 1 and {MAX_CHUNK_SIZE}"),
+        ));
+    }
+    Ok(params)
+}
+
+/// Description of the chunk size parameter
+pub fn chunk_size_description() -> String {
+    format!(
+        "Number of requests sent concurrently, 1 to {MAX_CHUNK_SIZE} (default: {DEFAULT_CHUNK_SIZE})"
+    )
+}
+
+/// Corrections found so far, by component type
+///
+/// A type is resolved once an item carrying it went through format discovery, whether or not
+/// it needed correcting.
+#[derive(Default)]
+struct TypeCorrections {
+    resolved: HashMap<String, Vec<LearnedCorrection>>,
+}
+
+impl TypeCorrections {
+    fn is_resolved(&self, params: &Value) -> bool {
+        component_types(params).all(|type_name| self.resolved.contains_key(type_name))
+    }
+
+    fn record(&mut self, params: &Value, corrections: &[FormatCorrection]) {
+        for type_name in component_types(params) {
+            self.resolved.entry(type_name.to_string()).or_default();
+        }
+        for correction in corrections
+            .iter()
+            .filter(|correction| correction.changes_value())
+        {
+            self.resolved
+                .entry(correction.component.clone())
+                .or_default()
+                .push(LearnedCorrection::new(
+                    &correction.original_format,
+                    &correction.corrected_format,
+                    &correction.hint,
+                ));
+        }
+    }
+
+    /// Rewrite the components of an item the way earlier items were corrected
+    fn apply(&self, params: &mut Value) -> Vec<FormatCorrection> {
+        let Some(Value::Object(components)) = params.get_mut(JSON_FIELD_COMPONENTS) else {
+            return Vec::new();
+        };
+        let mut applied = Vec::new();
+        for (type_name, value) in components.iter_mut() {
+            let corrected = self
+                .resolved
+                .get(type_name)
+                .and_then(|learned| learned.iter().find_map(|learned| learned.apply(value)));
+            if let Some(corrected) = corrected.filter(|corrected| corrected != value) {
+                applied.push(FormatCorrection {
+                    component:        type_name.clone(),
+                    original_format:  std::mem::replace(value, corrected.clone()),
+                    corrected_format: corrected,
+                    hint:             CARRIED_OVER_HINT.to_string(),
+                });
+            }
+        }
+        applied
+    }
+}
+
+/// Component type names of an item
+fn component_types(params: &Value) -> impl Iterator<Item = &str> {
+    params
+        .get(JSON_FIELD_COMPONENTS)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|components| components.keys().map(String::as_str))
+}
+
+/// Send spawn or insert items in chunks, running format discovery once per component type
+///
+/// In every chunk, items carrying a type not seen before are sent first, one at a time with
+/// format discovery; the rest are then corrected from what was found and sent concurrently.
+/// Returns one result per item, in item order.
+pub async fn execute_with_discovery(
+    method: &str,
+    items: Vec<Value>,
+    port: u16,
+    chunk_size: usize,
+) -> Vec<Value> {
+    let mut corrections = TypeCorrections::default();
+    let mut results = vec![Value::Null; items.len()];
+    let items: Vec<(usize, Value)> = items.into_iter().enumerate().collect();
+
+    for chunk in items.chunks(chunk_size) {
+        let mut ready = Vec::new();
+        for (index, params) in chunk {
+            if corrections.is_resolved(params) {
+                let mut params = params.clone();
+                let applied = corrections.apply(&mut params);
+                ready.push((*index, params, applied));
+                continue;
+            }
+            let outcome = execute_brp_method_with_format_discovery(
+                method,
+                Some(params.clone()),
+                Some(port),
+                Vec::new(),
+            )
+            .await;
+            results[*index] = match outcome {
+                Ok(enhanced) => {
+                    corrections.record(params, &enhanced.format_corrections);
+                    item_result(*index, Ok(enhanced.result), &enhanced.format_corrections)
+                }
+                Err(report) => {
+                    corrections.record(params, &[]);
+                    item_result(*index, Err(report), &[])
+                }
+            };
+        }
+
+        let outcomes =
+            join_all(ready.iter().map(|(_, params, _)| {
+                execute_brp_method(method, Some(params.clone()), Some(port))
+            }))
+            .await;
+        for ((index, _, applied), outcome) in ready.into_iter().zip(outcomes) {
+            results[index] = item_result(index, outcome, &applied);
+        }
+        progress::report(
+            chunk.last().map_or(0, |(index, _)| index + 1),
+            Some(items.len()),
+        )
+        .await;
+    }
+
+    results
+}
+
+/// Send items in chunks of concurrent requests, returning one result per item, in item order
+pub async fn execute_chunked(
+    method: &str,
+    items: &[Value],
+    port: u16,
+    chunk_size: usize,
+) -> Vec<Value> {
+    let mut results = Vec::with_capacity(items.len());
+    for chunk in items.chunks(chunk_size) {
+        let outcomes = join_all(
+            chunk
+                .iter()
+                .map(|params| execute_brp_method(method, Some(params.clone()), Some(port))),
+        )
+        .await;
+        for outcome in outcomes {
+            results.push(item_result(results.len(), outcome, &[]));
+        }
+        progress::report(results.len(), Some(items.len())).await;
+    }
+    results
+}
+
+/// Result entry of one item
+fn item_result(
+    index: usize,
+    outcome: Result<BrpResult>,
+    corrections: &[FormatCorrection],
+) -> Value {
+    let mut result = match outcome {
+        Ok(BrpResult::Success(data)) => json!({
+            "index": index,
+            JSON_FIELD_STATUS: "success",
+            JSON_FIELD_DATA: data,
+        }),
+        Ok(BrpResult::Error(error)) => json!({
+            "index": index,
+            JSON_FIELD_STATUS: "error",
+            "error": { "code": error.code, "message": error.message },
+        }),
+        Err(report) => json!({
+            "index": index,
+            JSON_FIELD_STATUS: "error",
+            "error": { "message": report.to_string() },
+        }),
+    };
+    if !corrections.is_empty() {
+        result[JSON_FIELD_FORMAT_CORRECTIONS] = corrections_json(corrections);
+    }
+    result
+}
+
+/// Response summarizing the results of a bulk operation, with any extra fields
+pub fn bulk_response(
+    action: &str,
+    results: &[Value],
+    port: u16,
+    extra_fields: &[(&str, Value)],
+) -> CallToolResult {
+    let succeeded = results
+        .iter()
+        .filter(|result| result[JSON_FIELD_STATUS] == "success")
+        .count();
+    let failed = results.len() - succeeded;
+
+    let mut data = json!({
+        "results": results,
+        "succeeded": succeeded,
+        "failed": failed,
+        JSON_FIELD_PORT: port,
+    });
+    for (field_name, value) in extra_fields {
+        data[*field_name] = value.clone();
+    }
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "{action} {succeeded} of {} items, {failed} failed",
+            succeeded + failed
+        ))
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    json_response_to_result(&response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corrections_carry_over_to_later_items() {
+        let mut corrections = TypeCorrections::default();
+        let first = json!({ "components": { "Transform": { "translation": { "x": 1.0, "y": 2.0, "z": 3.0 } }, "Name": "a" } });
+        assert!(!corrections.is_resolved(&first));
+        corrections.record(
+            &first,
+            &[FormatCorrection {
+                component:        "Transform".to_string(),
+                original_format:  json!({ "translation": { "x": 1.0, "y": 2.0, "z": 3.0 } }),
+                corrected_format: json!({ "translation": [1.0, 2.0, 3.0] }),
+                hint:             "Converted object to array".to_string(),
+            }],
+        );
+        assert!(corrections.is_resolved(&first));
+
+        let mut later = json!({ "components": { "Transform": { "translation": { "x": 4.0, "y": 5.0, "z": 6.0 } }, "Name": "b" } });
+        let applied = corrections.apply(&mut later);
+        assert_eq!(applied.len(), 1);
+        assert_eq!(
+            later,
+            json!({ "components": { "Transform": { "translation": [4.0, 5.0, 6.0] }, "Name": "b" } })
+        );
+
+        let unseen = json!({ "components": { "Visibility": "Hidden" } });
+        assert!(!corrections.is_resolved(&unseen));
+    }
+}
@@ -14,21 +14,34 @@ pub const JSON_FIELD_COMPONENTS: &str = "components";
 pub const JSON_FIELD_COUNT: &str = "count";
 pub const JSON_FIELD_DATA: &str = "data";
 pub const JSON_FIELD_DEBUG_INFO: &str = "debug_info";
+pub const JSON_FIELD_DELTA: &str = "delta";
+pub const JSON_FIELD_DELTA_TOKEN: &str = "delta_token";
+pub const JSON_FIELD_TAGGED_NAME: &str = "tagged_name";
+pub const JSON_FIELD_VERIFICATION: &str = "verification";
 pub const JSON_FIELD_DESTROYED_ENTITY: &str = "destroyed_entity";
 pub const JSON_FIELD_ENTITIES: &str = "entities";
 pub const JSON_FIELD_ENTITY: &str = "entity";
+pub const JSON_FIELD_ENTITY_CHECK: &str = "entity_check";
 pub const JSON_FIELD_ERROR_CODE: &str = "error_code";
+pub const JSON_FIELD_EXAMPLE_FORMATS: &str = "example_formats";
+pub const JSON_FIELD_HOST: &str = "host";
 pub const JSON_FIELD_FORMAT_CORRECTIONS: &str = "format_corrections";
+pub const JSON_FIELD_QUERY_ISSUES: &str = "query_issues";
+pub const JSON_FIELD_RESOLVED_TYPE_NAMES: &str = "resolved_type_names";
+pub const JSON_FIELD_TYPE_CANDIDATES: &str = "type_candidates";
 pub const JSON_FIELD_LOG_PATH: &str = "log_path";
 pub const JSON_FIELD_METADATA: &str = "metadata";
 pub const JSON_FIELD_METHOD: &str = "method";
+pub const JSON_FIELD_NEXT_CURSOR: &str = "next_cursor";
 pub const JSON_FIELD_ORIGINAL_ERROR: &str = "original_error";
+pub const JSON_FIELD_ORIGINAL_PARAMS_MODIFIED: &str = "original_params_modified";
 pub const JSON_FIELD_PARENT: &str = "parent";
 pub const JSON_FIELD_PATH: &str = "path";
 pub const JSON_FIELD_PORT: &str = "port";
 pub const JSON_FIELD_RESOURCE: &str = "resource";
 pub const JSON_FIELD_RESOURCES: &str = "resources";
 pub const JSON_FIELD_STATUS: &str = "status";
+pub const JSON_FIELD_TOTAL_COUNT: &str = "total_count";
 pub const JSON_FIELD_VALUE: &str = "value";
 pub const JSON_FIELD_WATCH_ID: &str = "watch_id";
 pub const JSON_FIELD_WATCHES: &str = "watches";
@@ -43,7 +56,10 @@ pub const PARAM_METHOD: &str = "method";
 pub const PARAM_PARAMS: &str = "params";
 pub const PARAM_DATA: &str = "data";
 pub const PARAM_FILTER: &str = "filter";
+pub const PARAM_LABEL: &str = "label";
 pub const PARAM_STRICT: &str = "strict";
+pub const PARAM_INLINE: &str = "inline";
+pub const PARAM_MAX_IMAGE_BYTES: &str = "max_image_bytes";
 pub const PARAM_FORMATS: &str = "formats";
 pub const PARAM_WITH_CRATES: &str = "with_crates";
 pub const PARAM_WITHOUT_CRATES: &str = "without_crates";
@@ -56,6 +72,19 @@ pub const PARAM_ENTITY_COUNT: &str = "entity_count";
 pub const PARAM_COMPONENT_COUNT: &str = "component_count";
 pub const PARAM_QUERY_PARAMS: &str = "query_params";
 pub const PARAM_SPAWNED_ENTITY: &str = "spawned_entity";
+pub const PARAM_MCP_SPAWNED_ONLY: &str = "mcp_spawned_only";
+pub const PARAM_PORTS: &str = "ports";
+pub const PARAM_LIMIT: &str = "limit";
+pub const PARAM_OFFSET: &str = "offset";
+pub const PARAM_MAX_RESPONSE_BYTES: &str = "max_response_bytes";
+pub const PARAM_CURSOR: &str = "cursor";
+pub const PARAM_CANONICAL_ORDER: &str = "canonical_order";
+pub const PARAM_VERIFY: &str = "verify";
+pub const PARAM_CONFIRM: &str = "confirm";
+pub const PARAM_PROJECTION: &str = "projection";
+pub const PARAM_TIMEOUT_MS: &str = "timeout_ms";
+pub const PARAM_RETRIES: &str = "retries";
+pub const PARAM_BACKOFF_MS: &str = "backoff_ms";
 
 // ============================================================================
 // NETWORK CONSTANTS
@@ -67,11 +96,29 @@ pub const BRP_JSONRPC_PATH: &str = "/jsonrpc";
 /// Default host for BRP connections
 pub const BRP_DEFAULT_HOST: &str = "localhost";
 
+/// Environment variable overriding the default host for BRP connections
+pub const BRP_HOST_ENV_VAR: &str = "BRP_MCP_HOST";
+
 /// HTTP protocol for BRP connections
 pub const BRP_HTTP_PROTOCOL: &str = "http";
 
+/// Protocol for BRP connections with TLS enabled
+pub const BRP_HTTPS_PROTOCOL: &str = "https";
+
 /// Documentation/Help Constants
 pub const DESC_PORT: &str = "The BRP port (default: 15702)";
+pub const DESC_DELTA_TOKEN: &str = "Token from a previous response of the same call; if given, only the changes since that response are returned";
+pub const DESC_CANONICAL_ORDER: &str = "Sort result rows and type lists into a stable order (default true); turn off for very large results where order does not matter";
+pub const DESC_VERIFY: &str = "After format discovery corrects a component, read it back and compare it with the value you sent, reporting mismatches under verification (default false)";
+pub const DESC_CONFIRM: &str = "Confirmation token from a preview of this call, needed to run it when the destructive_ops policy is confirm; false to only preview what would be lost";
+pub const DESC_PROJECTION: &str = "Path expression, or array of them, selecting the parts of the result to return, e.g. $.components[\"Transform\"].translation; applied to each query row, which keeps its entity";
+pub const DESC_PORTS: &str = "Run the call against each of these BRP ports concurrently and return the results keyed by port, with a summary of differences (instead of port)";
+pub const DESC_TIMEOUT_MS: &str =
+    "Milliseconds each BRP request of this call may take before it fails (default: 30000)";
+pub const DESC_RETRIES: &str = "Times a BRP request is retried after a refused connection, or a timeout for methods that only read (default: 2)";
+pub const DESC_BACKOFF_MS: &str =
+    "Milliseconds before the first retry, doubled for every further one (default: 100)";
+pub const DESC_HOST: &str = "The BRP host name or IP address (default: localhost, or the BRP_MCP_HOST environment variable)";
 
 /// Network/Port Constants
 pub const DEFAULT_BRP_PORT: u16 = 15702;
@@ -83,6 +130,9 @@ pub const DEFAULT_BRP_PORT: u16 = 15702;
 /// BRP error code for invalid request
 pub const BRP_ERROR_CODE_INVALID_REQUEST: i32 = -23402;
 
+/// BRP error code for an entity that doesn't exist
+pub const BRP_ERROR_CODE_NO_SUCH_ENTITY: i32 = -23401;
+
 // ============================================================================
 // JSON-RPC CONSTANTS
 // ============================================================================
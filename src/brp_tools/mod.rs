@@ -1,9 +1,44 @@
 // BRP tools module
 
+pub mod assets;
+pub mod bevy_copy_components;
+pub mod bevy_destroy_recursive;
+pub mod bevy_explain_entity;
+pub mod bevy_export_scene;
+pub mod bevy_find_entity_by_name;
+pub mod bevy_get_diagnostics;
+pub mod bevy_get_hierarchy;
+pub mod bevy_has_components;
+pub mod bevy_list_mutation_paths;
+pub mod bevy_list_systems;
+pub mod bevy_schedule_graph;
+pub mod bevy_send_event;
+pub mod bevy_spawn_from_file;
+pub mod bevy_type_schema;
+pub mod bevy_world_stats;
+pub mod bookmarks;
+pub mod brp_check_extras;
+pub mod brp_corrections_report;
+pub mod brp_describe_tool;
+pub mod brp_discover_ports;
+pub mod brp_execute_batch;
+pub mod brp_list_format_corrections;
+pub mod brp_probe_hosts;
+pub mod brp_run_assertions;
 pub mod brp_set_debug_mode;
 pub mod brp_status;
+pub mod brp_validate_format;
+pub mod bulk;
 pub mod constants;
 pub mod request_handler;
+pub mod resource_sets;
+pub mod screenshot_series;
+pub mod session_recording;
+pub mod snapshot;
+pub mod states;
+pub mod time_control;
+pub mod transform;
+pub mod visibility;
 pub mod watch;
 
 pub mod support;
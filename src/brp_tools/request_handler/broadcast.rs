@@ -0,0 +1,231 @@
+//! Fan-out of read-only BRP tool calls to several ports
+//!
+//! A `ports` argument runs the tool once per port, at most `MAX_CONCURRENT_PORTS` at a time, and
+//! combines the per-port responses with a summary of how each port's result differs from the
+//! first successful one.
+
+use futures::stream::{self, StreamExt};
+use rmcp::Error as McpError;
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use serde_json::{Map, Value, json};
+
+use super::config::BrpHandlerConfig;
+use super::handler::handle_single_port_request;
+use crate::BrpMcpService;
+use crate::brp_tools::constants::{
+    JSON_FIELD_DATA, JSON_FIELD_DELTA_TOKEN, JSON_FIELD_PORT, JSON_FIELD_STATUS, PARAM_PORTS,
+};
+use crate::brp_tools::support::delta_tracker::compute_delta;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::{json_response_to_result, result_to_json};
+
+/// Most ports queried at the same time
+const MAX_CONCURRENT_PORTS: usize = 4;
+
+/// Most ports a single call may broadcast to
+const MAX_PORTS: usize = 16;
+
+/// Remove the `ports` argument, returning the deduplicated ports if any were given
+pub fn take_ports(request: &mut CallToolRequestParam) -> Result<Option<Vec<u16>>, McpError> {
+    let Some(ports) = request
+        .arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(PARAM_PORTS))
+        .filter(|ports| !ports.is_null())
+    else {
+        return Ok(None);
+    };
+
+    let mut unique = Vec::new();
+    for port in ports.as_array().into_iter().flatten() {
+        let port = port
+            .as_u64()
+            .and_then(|port| u16::try_from(port).ok())
+            .ok_or_else(|| invalid_ports(&format!("{port} is not a valid port")))?;
+        if !unique.contains(&port) {
+            unique.push(port);
+        }
+    }
+
+    if unique.is_empty() {
+        return Err(invalid_ports("expected a non-empty array of ports"));
+    }
+    if unique.len() > MAX_PORTS {
+        return Err(invalid_ports(&format!(
+            "at most {MAX_PORTS} ports are allowed"
+        )));
+    }
+    if request
+        .arguments
+        .as_ref()
+        .is_some_and(|arguments| arguments.contains_key(JSON_FIELD_DELTA_TOKEN))
+    {
+        return Err(invalid_ports("cannot be combined with delta_token"));
+    }
+    Ok(Some(unique))
+}
+
+fn invalid_ports(details: &str) -> McpError {
+    report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+        "parameter 'ports'",
+        details,
+    )))
+}
+
+/// Run the request against every port and combine the responses
+pub async fn broadcast_request(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    config: &BrpHandlerConfig,
+    ports: Vec<u16>,
+) -> Result<CallToolResult, McpError> {
+    let results: Vec<(u16, Value)> = stream::iter(ports)
+        .map(|port| {
+            let mut request = request.clone();
+            request
+                .arguments
+                .get_or_insert_with(Map::new)
+                .insert(JSON_FIELD_PORT.to_string(), json!(port));
+            async move {
+                let response = handle_single_port_request(service, request, config)
+                    .await
+                    .map_or_else(
+                        |e| {
+                            let mut response =
+                                json!({ JSON_FIELD_STATUS: "error", "message": e.message });
+                            if let (Value::Object(response), Some(Value::Object(data))) =
+                                (&mut response, e.data)
+                            {
+                                response.extend(data);
+                            }
+                            response
+                        },
+                        |result| result_to_json(&result),
+                    );
+                (port, response)
+            }
+        })
+        .buffered(MAX_CONCURRENT_PORTS)
+        .collect()
+        .await;
+
+    let summary = summarize(&results);
+    let succeeded = results
+        .iter()
+        .filter(|(_, response)| succeeded(response))
+        .count();
+
+    let mut by_port = Map::new();
+    for (port, response) in results {
+        by_port.insert(port.to_string(), response);
+    }
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Queried {} ports ({succeeded} succeeded)",
+            by_port.len()
+        ))
+        .data(json!({
+            "results": by_port,
+            "summary": summary
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+fn succeeded(response: &Value) -> bool {
+    response.get(JSON_FIELD_STATUS).and_then(Value::as_str) == Some("success")
+}
+
+/// Compare every successful port's data with the first successful port's
+fn summarize(results: &[(u16, Value)]) -> Value {
+    let failed: Vec<u16> = results
+        .iter()
+        .filter(|(_, response)| !succeeded(response))
+        .map(|(port, _)| *port)
+        .collect();
+    let mut successful = results.iter().filter(|(_, response)| succeeded(response));
+
+    let Some((baseline_port, baseline)) = successful.next() else {
+        return json!({ "failed_ports": failed });
+    };
+
+    let mut differences = Map::new();
+    for (port, response) in successful {
+        if let Some(delta) = compute_delta(&baseline[JSON_FIELD_DATA], &response[JSON_FIELD_DATA]) {
+            differences.insert(port.to_string(), delta);
+        }
+    }
+
+    json!({
+        "baseline_port": baseline_port,
+        "identical": differences.is_empty(),
+        "differences": differences,
+        "failed_ports": failed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn request(arguments: &Value) -> CallToolRequestParam {
+        CallToolRequestParam {
+            name:      "bevy_get_resource".into(),
+            arguments: arguments.as_object().cloned(),
+        }
+    }
+
+    #[test]
+    fn test_take_ports() {
+        let mut with_ports = request(&json!({ "ports": [15702, 15703, 15702] }));
+        assert_eq!(
+            take_ports(&mut with_ports).unwrap(),
+            Some(vec![15702, 15703])
+        );
+        assert!(!with_ports.arguments.unwrap().contains_key(PARAM_PORTS));
+
+        assert_eq!(take_ports(&mut request(&json!({}))).unwrap(), None);
+        assert!(take_ports(&mut request(&json!({ "ports": [] }))).is_err());
+        assert!(
+            take_ports(&mut request(
+                &json!({ "ports": [15702], "delta_token": "delta-1" })
+            ))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_summary_reports_differences_from_baseline() {
+        let ok = |value: Value| json!({ "status": "success", "data": { "data": value } });
+        let results = vec![
+            (15702, ok(json!({ "score": 1 }))),
+            (15703, ok(json!({ "score": 1 }))),
+            (15704, ok(json!({ "score": 2 }))),
+            (
+                15705,
+                json!({ "status": "error", "message": "Connection refused" }),
+            ),
+        ];
+
+        let summary = summarize(&results);
+        assert_eq!(summary["baseline_port"], 15702);
+        assert_eq!(summary["identical"], false);
+        assert_eq!(
+            summary["differences"],
+            json!({ "15704": { "changed": { "data": { "score": 2 } } } })
+        );
+        assert_eq!(summary["failed_ports"], json!([15705]));
+    }
+}
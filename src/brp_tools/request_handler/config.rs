@@ -1,8 +1,10 @@
 use super::traits::ParamExtractor;
+use crate::brp_tools::support::request_policy::RequestPolicy;
 use crate::brp_tools::support::response_formatter::ResponseFormatterFactory;
 
 /// Unified configuration for a BRP handler
 /// Works for both static and dynamic methods
+#[allow(clippy::struct_excessive_bools)]
 pub struct BrpHandlerConfig {
     /// The BRP method to call (static) or None for dynamic methods
     pub method:            Option<&'static str>,
@@ -10,6 +12,25 @@ pub struct BrpHandlerConfig {
     pub param_extractor:   Box<dyn ParamExtractor>,
     /// Function to create the appropriate formatter
     pub formatter_factory: ResponseFormatterFactory,
+    /// Whether results are recorded for `delta_token` requests
+    pub delta_tracking:    bool,
+    /// Whether queries accept `mcp_spawned_only`
+    pub spawned_filter:    bool,
+    /// Whether the call can be broadcast to several `ports`
+    pub broadcast:         bool,
+    /// Whether query rows can be paged with `limit`, `offset` and `cursor`
+    pub pagination:        bool,
+    /// Whether canonical ordering of rows and type lists can be turned off with
+    /// `canonical_order`
+    pub canonical_order:   bool,
+    /// Whether the result can be narrowed down with `projection`
+    pub projection:        bool,
+    /// Whether corrected components can be read back and checked with `verify`
+    pub verify:            bool,
+    /// Whether the saved screenshot can be returned as image content with `inline`
+    pub inline_image:      bool,
+    /// Timeout and retries of the BRP requests, unless the call overrides them
+    pub request_policy:    RequestPolicy,
 }
 
 /// Context passed to formatter factory
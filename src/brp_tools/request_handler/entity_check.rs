@@ -0,0 +1,190 @@
+//! Suggestions for entity IDs that don't exist
+//!
+//! An entity BRP can't find is usually a stale ID: the entity was despawned and its index reused,
+//! or the ID was copied from an earlier run of the app. When a call fails that way, the app's
+//! entities are queried to confirm the entity is gone and to suggest the ones the caller most
+//! likely meant: the entity now holding the same index, the closest indices, and entities with
+//! the components the call was about.
+
+use serde_json::{Value, json};
+
+use crate::brp_tools::constants::{
+    JSON_FIELD_COMPONENT, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, PARAM_PARENT,
+};
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result};
+use crate::tools::BRP_METHOD_QUERY;
+
+/// Number of entities suggested per kind of suggestion
+const MAX_SUGGESTIONS: usize = 5;
+
+/// The entity ID of a call's parameters that BRP may have failed to find
+pub fn requested_entity(params: Option<&Value>) -> Option<u64> {
+    let params = params?;
+    params
+        .get(JSON_FIELD_ENTITY)
+        .or_else(|| params.get(PARAM_PARENT))
+        .and_then(Value::as_u64)
+}
+
+/// Check whether an entity exists and suggest the entities the caller may have meant
+pub async fn check_missing_entity(entity: u64, params: Option<&Value>, port: u16) -> Value {
+    let components = requested_components(params);
+    let checked = async {
+        let all = query_entity_ids(None, port).await?;
+        let with_components = if components.is_empty() {
+            None
+        } else {
+            Some(query_entity_ids(Some(&components), port).await?)
+        };
+        Ok::<_, error_stack::Report<Error>>(suggestions(entity, &all, with_components.as_deref()))
+    };
+    checked.await.unwrap_or_else(|report| {
+        json!({
+            JSON_FIELD_ENTITY: entity,
+            "check_failed": report.to_string(),
+        })
+    })
+}
+
+/// Component types named in a call's parameters, as a list, object keys or a single component
+fn requested_components(params: Option<&Value>) -> Vec<String> {
+    let Some(params) = params else {
+        return Vec::new();
+    };
+    match params.get(JSON_FIELD_COMPONENTS) {
+        Some(Value::Array(names)) => names
+            .iter()
+            .filter_map(|name| name.as_str().map(String::from))
+            .collect(),
+        Some(Value::Object(values)) => values.keys().cloned().collect(),
+        _ => params
+            .get(JSON_FIELD_COMPONENT)
+            .and_then(Value::as_str)
+            .map(String::from)
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// IDs of every entity, or of the entities with all of some components, in ascending order
+async fn query_entity_ids(with: Option<&[String]>, port: u16) -> Result<Vec<u64>> {
+    let mut query = json!({ "data": {} });
+    if let Some(with) = with {
+        query["filter"] = json!({ "with": with });
+    }
+    let rows = match execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => rows,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to query entities: {}",
+                e.message
+            ))));
+        }
+    };
+    let mut ids: Vec<u64> = rows
+        .iter()
+        .filter_map(|row| row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64))
+        .collect();
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+/// Index of an entity ID, the low 32 bits
+const fn entity_index(entity: u64) -> u64 {
+    entity & 0xFFFF_FFFF
+}
+
+/// Generation of an entity ID, the high 32 bits
+const fn entity_generation(entity: u64) -> u64 {
+    entity >> 32
+}
+
+/// The check result for an entity given the IDs the app has
+fn suggestions(entity: u64, all: &[u64], with_components: Option<&[u64]>) -> Value {
+    let index = entity_index(entity);
+    if all.contains(&entity) {
+        return json!({
+            JSON_FIELD_ENTITY: entity,
+            "exists": true,
+            "hint": format!("Entity {entity} exists, so the error is about something else, e.g. a component the entity doesn't have"),
+        });
+    }
+
+    let same_index = all.iter().copied().find(|id| entity_index(*id) == index);
+    let mut nearest: Vec<u64> = all
+        .iter()
+        .copied()
+        .filter(|id| Some(*id) != same_index)
+        .collect();
+    nearest.sort_by_key(|id| (entity_index(*id).abs_diff(index), *id));
+    nearest.truncate(MAX_SUGGESTIONS);
+
+    let hint = same_index.map_or_else(
+        || {
+            format!(
+                "Entity {entity} doesn't exist. Look it up again with bevy_query or bevy_find_entity_by_name, or bookmark it with bevy_bookmark_entity"
+            )
+        },
+        |id| {
+            format!(
+                "Entity {entity} was despawned and its index reused by entity {id} (generation {}); the ID is stale",
+                entity_generation(id)
+            )
+        },
+    );
+
+    let mut result = json!({
+        JSON_FIELD_ENTITY: entity,
+        "exists": false,
+        "index": index,
+        "generation": entity_generation(entity),
+        "entity_count": all.len(),
+        "same_index": same_index,
+        "nearest": nearest,
+        "hint": hint,
+    });
+    if let Some(with_components) = with_components {
+        result["with_components"] = json!(
+            with_components
+                .iter()
+                .take(MAX_SUGGESTIONS)
+                .collect::<Vec<_>>()
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_ids_suggest_the_reused_index_and_neighbours() {
+        let stale = (1 << 32) | 7;
+        let reused = (2 << 32) | 7;
+        let all = [3, 5, 6, reused, 9, 40];
+
+        let result = suggestions(stale, &all, Some(&[5, 40]));
+        assert_eq!(result["exists"], false);
+        assert_eq!(result["index"], 7);
+        assert_eq!(result["same_index"], reused);
+        assert_eq!(result["nearest"], json!([6, 5, 9, 3, 40]));
+        assert_eq!(result["with_components"], json!([5, 40]));
+
+        let missing = suggestions(100, &all, None);
+        assert!(missing["same_index"].is_null());
+        assert!(missing.get("with_components").is_none());
+        assert_eq!(suggestions(6, &all, None)["exists"], true);
+
+        assert_eq!(
+            requested_components(Some(&json!({ "components": { "game::Health": 3 } }))),
+            vec!["game::Health"]
+        );
+        assert_eq!(
+            requested_entity(Some(&json!({ "entities": [1], "parent": 4 }))),
+            Some(4)
+        );
+    }
+}
@@ -4,9 +4,10 @@ use serde_json::{Value, json};
 
 use super::traits::{ExtractedParams, ParamExtractor};
 use crate::brp_tools::constants::{
-    DEFAULT_BRP_PORT, JSON_FIELD_ENTITY, JSON_FIELD_PORT, JSON_FIELD_RESOURCE, PARAM_WITH_CRATES,
-    PARAM_WITH_TYPES, PARAM_WITHOUT_CRATES, PARAM_WITHOUT_TYPES,
+    JSON_FIELD_ENTITY, JSON_FIELD_PORT, JSON_FIELD_RESOURCE, PARAM_WITH_CRATES, PARAM_WITH_TYPES,
+    PARAM_WITHOUT_CRATES, PARAM_WITHOUT_TYPES,
 };
+use crate::config;
 use crate::error::{Error, report_to_mcp_error};
 use crate::support::params::{
     extract_any_value, extract_optional_number, extract_optional_string_array_from_request,
@@ -23,8 +24,8 @@ pub struct BrpExecuteParams {
     pub port:   u16,
 }
 
-const fn default_port() -> u16 {
-    DEFAULT_BRP_PORT
+fn default_port() -> u16 {
+    config::default_port()
 }
 
 /// Helper function to extract and validate port from request
@@ -32,7 +33,7 @@ fn extract_port(request: &rmcp::model::CallToolRequestParam) -> Result<u16, McpE
     u16::try_from(extract_optional_number(
         request,
         JSON_FIELD_PORT,
-        u64::from(DEFAULT_BRP_PORT),
+        u64::from(config::default_port()),
     )?)
     .map_err(|_| {
         report_to_mcp_error(
@@ -0,0 +1,109 @@
+//! Session log of the corrections made by format discovery
+//!
+//! Every correction is recorded with the method it was made for and whether the corrected
+//! request went through, so `brp_corrections_report` can summarize a session per type and
+//! suggest the changes to the Rust definitions that would make the corrections unnecessary.
+//! Each MCP session has its own log, so SSE clients only see the corrections of their calls.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use serde_json::Value;
+
+use super::engine::FormatCorrection;
+use crate::support::session;
+
+/// Oldest entries of a session are dropped beyond this many
+const MAX_LOGGED_CORRECTIONS: usize = 1000;
+
+/// Corrections logs, by session
+static CORRECTIONS_LOG: LazyLock<Mutex<HashMap<u64, Vec<LoggedCorrection>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// One correction (or serialization diagnostic) observed during the session
+#[derive(Debug, Clone)]
+pub struct LoggedCorrection {
+    pub method:           String,
+    pub type_name:        String,
+    pub hint:             String,
+    pub original_format:  Value,
+    pub corrected_format: Value,
+    /// Whether the request succeeded with the corrections applied
+    pub succeeded:        bool,
+}
+
+/// Record the corrections made for one request of the current session
+pub fn record(method: &str, corrections: &[FormatCorrection], succeeded: bool) {
+    if corrections.is_empty() {
+        return;
+    }
+    if let Ok(mut logs) = CORRECTIONS_LOG.lock() {
+        let log = logs.entry(session::current()).or_default();
+        log.extend(corrections.iter().map(|correction| LoggedCorrection {
+            method: method.to_string(),
+            type_name: correction.component.clone(),
+            hint: correction.hint.clone(),
+            original_format: correction.original_format.clone(),
+            corrected_format: correction.corrected_format.clone(),
+            succeeded,
+        }));
+        let excess = log.len().saturating_sub(MAX_LOGGED_CORRECTIONS);
+        log.drain(..excess);
+    }
+}
+
+/// Everything recorded so far in the current session, oldest first
+pub fn entries() -> Vec<LoggedCorrection> {
+    CORRECTIONS_LOG
+        .lock()
+        .ok()
+        .and_then(|logs| logs.get(&session::current()).cloned())
+        .unwrap_or_default()
+}
+
+/// Forget everything recorded so far in the current session
+pub fn clear() {
+    forget_session(session::current());
+}
+
+/// Forget the log of a session, once it has ended
+pub fn forget_session(session: u64) {
+    if let Ok(mut logs) = CORRECTIONS_LOG.lock() {
+        logs.remove(&session);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_each_session_has_its_own_log() {
+        let correction = FormatCorrection {
+            component:        "game::Health".to_string(),
+            original_format:  json!(5),
+            corrected_format: json!({ "value": 5 }),
+            hint:             "wrapped in a struct".to_string(),
+        };
+        let first = session::new_id();
+        let second = session::new_id();
+
+        session::scope(first, async {
+            record("bevy/insert", std::slice::from_ref(&correction), true);
+            assert_eq!(entries().len(), 1);
+        })
+        .await;
+        session::scope(second, async {
+            assert!(entries().is_empty());
+        })
+        .await;
+
+        forget_session(first);
+        session::scope(first, async {
+            assert!(entries().is_empty());
+        })
+        .await;
+    }
+}
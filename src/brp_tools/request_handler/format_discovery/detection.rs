@@ -7,9 +7,9 @@ use super::constants::{
     TRANSFORM_SEQUENCE_REGEX, TUPLE_STRUCT_PATH_REGEX, TYPE_MISMATCH_REGEX,
     UNKNOWN_COMPONENT_REGEX, UNKNOWN_COMPONENT_TYPE_REGEX, VARIANT_TYPE_MISMATCH_REGEX,
 };
-use crate::brp_tools::support::brp_client::{BrpError, BrpResult, execute_brp_method};
+use super::schema_cache;
+use crate::brp_tools::support::brp_client::{BrpError, BrpResult};
 use crate::error::{Error, Result};
-use crate::tools::BRP_METHOD_REGISTRY_SCHEMA;
 
 /// Known error patterns that can be deterministically handled
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -172,12 +172,12 @@ fn match_all_patterns(message: &str) -> Option<ErrorPattern> {
     }
 
     // 5. Transform sequence pattern
-    if let Some(captures) = TRANSFORM_SEQUENCE_REGEX.captures(message) {
-        if let Ok(count) = captures[1].parse::<usize>() {
-            return Some(ErrorPattern::TransformSequence {
-                expected_count: count,
-            });
-        }
+    if let Some(captures) = TRANSFORM_SEQUENCE_REGEX.captures(message)
+        && let Ok(count) = captures[1].parse::<usize>()
+    {
+        return Some(ErrorPattern::TransformSequence {
+            expected_count: count,
+        });
     }
 
     // 6. Expected type pattern
@@ -225,8 +225,7 @@ pub async fn check_type_serialization(
         "with_crates": [extract_crate_name(type_name)]
     });
 
-    let schema_result =
-        execute_brp_method(BRP_METHOD_REGISTRY_SCHEMA, Some(schema_params), port).await?;
+    let schema_result = schema_cache::registry_schema(schema_params, port).await?;
 
     match schema_result {
         BrpResult::Success(Some(schema_data)) => analyze_schema_for_type(type_name, &schema_data),
@@ -243,7 +242,7 @@ pub async fn check_type_serialization(
 }
 
 /// Extract crate name from a fully-qualified type name
-fn extract_crate_name(type_name: &str) -> &str {
+pub fn extract_crate_name(type_name: &str) -> &str {
     // Extract the first part before :: for crate name
     // e.g., "bevy_transform::components::transform::Transform" -> "bevy_transform"
     type_name.split("::").next().unwrap_or(type_name)
@@ -261,10 +260,10 @@ fn analyze_schema_for_type(type_name: &str, schema_data: &Value) -> Result<Seria
     } else if let Some(schemas) = schema_data.as_array() {
         // Fall back to array format (old format)
         for schema in schemas {
-            if let Some(type_path) = schema.get("typePath").and_then(Value::as_str) {
-                if type_path == type_name {
-                    return Ok(analyze_single_type_schema(type_name, schema));
-                }
+            if let Some(type_path) = schema.get("typePath").and_then(Value::as_str)
+                && type_path == type_name
+            {
+                return Ok(analyze_single_type_schema(type_name, schema));
             }
         }
     } else {
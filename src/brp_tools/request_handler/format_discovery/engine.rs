@@ -1,10 +1,28 @@
 //! Orchestration and retry logic for format discovery
 
-use serde_json::Value;
+use serde_json::{Value, json};
 
-use super::constants::FORMAT_DISCOVERY_METHODS;
-use crate::brp_tools::support::brp_client::BrpResult;
-use crate::error::Result;
+use super::constants::{
+    COMPONENT_FORMAT_ERROR_CODE, FORMAT_DISCOVERY_METHODS, RESOURCE_FORMAT_ERROR_CODE,
+};
+use super::phases::context::DiscoveryContext;
+use super::{corrections_log, knowledge_base};
+use crate::brp_tools::constants::JSON_FIELD_ORIGINAL_ERROR;
+use crate::brp_tools::support::brp_client::{BrpError, BrpResult};
+use crate::config;
+use crate::error::{Error, Result};
+use crate::tools::{BRP_METHOD_INSERT_RESOURCE, BRP_METHOD_MUTATE_RESOURCE};
+
+/// Whether rejected payloads are corrected, from the `format_discovery` configuration key
+pub fn format_discovery_enabled() -> bool {
+    config::current().format_discovery.unwrap_or(true)
+}
+
+/// Whether payloads needing corrections fail with the corrections instead of being corrected,
+/// from the `strict_format_corrections` configuration key
+pub fn strict_corrections_enabled() -> bool {
+    config::current().strict_format_corrections.unwrap_or(false)
+}
 
 /// Location of type items in method parameters
 #[derive(Debug, Clone, Copy)]
@@ -15,6 +33,8 @@ pub enum ParameterLocation {
     ComponentValue,
     /// Single type value in "value" field (`insert_resource`, `mutate_resource`)
     ResourceValue,
+    /// Whole event value in "value" field, its type in "event" (`send_event`)
+    EventValue,
 }
 
 /// Format correction information for a type (component or resource)
@@ -26,12 +46,71 @@ pub struct FormatCorrection {
     pub hint:             String,
 }
 
+impl FormatCorrection {
+    /// Whether the correction rewrites the value, unlike a serialization diagnostic
+    pub fn changes_value(&self) -> bool {
+        self.corrected_format != self.original_format
+    }
+}
+
 /// Enhanced response with format corrections
 #[derive(Debug, Clone)]
 pub struct EnhancedBrpResult {
     pub result:             BrpResult,
     pub format_corrections: Vec<FormatCorrection>,
     pub debug_info:         Vec<String>,
+    /// Whether the params sent to BRP differ from the ones given
+    pub params_modified:    bool,
+}
+
+/// Outcome of a format discovery dry run
+#[derive(Debug, Clone)]
+pub struct FormatValidation {
+    pub corrected_params:   Value,
+    pub format_corrections: Vec<FormatCorrection>,
+    pub debug_info:         Vec<String>,
+}
+
+/// Run format discovery on a payload without executing it
+///
+/// Only read-only BRP calls are made (registry schema, `bevy_brp_extras/discover_format`);
+/// spawn validation is skipped. Pattern-based fixes (tier 3) need the error message from a
+/// failed attempt, so without `error_message` only the serialization and direct discovery
+/// tiers can produce corrections.
+pub async fn validate_format(
+    method: &str,
+    params: Value,
+    port: Option<u16>,
+    error_message: Option<String>,
+) -> Result<FormatValidation> {
+    use crate::brp_tools::request_handler::format_discovery::phases::context::DiscoveryContext;
+    use crate::brp_tools::request_handler::format_discovery::phases::{
+        initial_attempt, result_building, tier_execution,
+    };
+
+    if !FORMAT_DISCOVERY_METHODS.contains(&method) {
+        return Err(error_stack::Report::new(Error::invalid(
+            "method",
+            format!(
+                "'{method}' does not support format discovery (expected one of: {})",
+                FORMAT_DISCOVERY_METHODS.join(", ")
+            ),
+        )));
+    }
+
+    let mut context = DiscoveryContext::new(method, Some(params), port, Vec::new());
+    context.dry_run = true;
+    initial_attempt::apply_preflight_corrections(&mut context);
+
+    // Discovery is error-driven; stand in for the error the request would have produced
+    context.set_error(BrpError {
+        code:    format_error_code(method),
+        message: error_message.unwrap_or_default(),
+        data:    None,
+    });
+
+    let discovery_data = tier_execution::run_discovery_tiers(&mut context).await?;
+    result_building::build_dry_run_result(&mut context, discovery_data)
 }
 
 /// Execute a BRP method with automatic format discovery
@@ -48,12 +127,17 @@ pub async fn execute_brp_method_with_format_discovery(
 
     // Initialize the discovery context
     let mut context = DiscoveryContext::new(method, params, port, initial_debug_info);
+    if strict_corrections_enabled() {
+        return execute_strict(context).await;
+    }
 
     // Phase 1: Execute initial attempt
     let initial_result = initial_attempt::execute(&mut context).await?;
 
     // Phase 2: Check if error analysis indicates recovery is possible
-    if let Some(error) = error_analysis::needs_format_discovery(&initial_result, method) {
+    if let Some(error) = error_analysis::needs_format_discovery(&initial_result, method)
+        .filter(|_| format_discovery_enabled())
+    {
         context.add_debug(format!(
             "Format Discovery: Got error code {}, checking if method '{}' supports format discovery",
             error.code, method
@@ -71,7 +155,11 @@ pub async fn execute_brp_method_with_format_discovery(
         let discovery_data = tier_execution::run_discovery_tiers(&mut context).await?;
 
         // Phase 4: Build final result with corrections
-        return result_building::build_final_result(&mut context, discovery_data).await;
+        let result = result_building::build_final_result(&mut context, discovery_data).await?;
+        let succeeded = matches!(result.result, BrpResult::Success(_));
+        corrections_log::record(method, &result.format_corrections, succeeded);
+        knowledge_base::learn(port, &result.format_corrections, succeeded).await;
+        return Ok(result);
     }
 
     // Log appropriate message based on the result
@@ -98,9 +186,103 @@ pub async fn execute_brp_method_with_format_discovery(
         context.debug_info.len()
     ));
 
+    if !context.preflight_corrections.is_empty() {
+        corrections_log::record(
+            method,
+            &context.preflight_corrections,
+            matches!(initial_result, BrpResult::Success(_)),
+        );
+    }
+
     Ok(EnhancedBrpResult {
         result:             initial_result,
-        format_corrections: Vec::new(),
+        params_modified:    context
+            .preflight_corrections
+            .iter()
+            .any(FormatCorrection::changes_value),
+        format_corrections: context.preflight_corrections,
         debug_info:         context.debug_info,
     })
 }
+
+/// Execute a BRP method, failing with the corrections its params need instead of applying them
+///
+/// Values the pre-flight corrections would rewrite fail before anything is sent. Otherwise the
+/// params are sent as given, and a format error is answered with the corrections discovery finds,
+/// without test spawns or a retry.
+async fn execute_strict(mut context: DiscoveryContext) -> Result<EnhancedBrpResult> {
+    use crate::brp_tools::request_handler::format_discovery::phases::{
+        error_analysis, initial_attempt, result_building, tier_execution,
+    };
+
+    initial_attempt::apply_preflight_corrections(&mut context);
+    let corrections = context
+        .preflight_corrections
+        .iter()
+        .filter(|correction| correction.changes_value())
+        .count();
+    if corrections > 0 {
+        context.add_debug("Format Discovery: Strict mode, not sending params needing correction");
+        return Ok(EnhancedBrpResult {
+            result:             BrpResult::Error(strict_error(&context.method, None, corrections)),
+            format_corrections: context.preflight_corrections,
+            debug_info:         context.debug_info,
+            params_modified:    false,
+        });
+    }
+
+    let result = initial_attempt::execute(&mut context).await?;
+    let Some(error) = error_analysis::needs_format_discovery(&result, &context.method)
+        .filter(|_| format_discovery_enabled())
+        .cloned()
+    else {
+        return Ok(EnhancedBrpResult {
+            result,
+            format_corrections: Vec::new(),
+            debug_info: context.debug_info,
+            params_modified: false,
+        });
+    };
+
+    context.dry_run = true;
+    let discovery_data = tier_execution::run_discovery_tiers(&mut context).await?;
+    let validation = result_building::build_dry_run_result(&mut context, discovery_data)?;
+    let corrections = validation
+        .format_corrections
+        .iter()
+        .filter(|correction| correction.changes_value())
+        .count();
+    let error = if corrections == 0 {
+        error
+    } else {
+        strict_error(&context.method, Some(&error), corrections)
+    };
+    Ok(EnhancedBrpResult {
+        result:             BrpResult::Error(error),
+        format_corrections: validation.format_corrections,
+        debug_info:         validation.debug_info,
+        params_modified:    false,
+    })
+}
+
+/// Error for params that strict mode refuses to correct
+fn strict_error(method: &str, original: Option<&BrpError>, corrections: usize) -> BrpError {
+    BrpError {
+        code:    original.map_or_else(|| format_error_code(method), |original| original.code),
+        message: format!(
+            "Strict format corrections are enabled and {corrections} values need correcting. \
+             Resend with the corrected_format of each format_corrections entry, or set \
+             strict_format_corrections to false to correct them automatically"
+        ),
+        data:    original.map(|original| json!({ JSON_FIELD_ORIGINAL_ERROR: original.message })),
+    }
+}
+
+/// BRP error code of a rejected value for a method
+fn format_error_code(method: &str) -> i32 {
+    if method == BRP_METHOD_INSERT_RESOURCE || method == BRP_METHOD_MUTATE_RESOURCE {
+        RESOURCE_FORMAT_ERROR_CODE
+    } else {
+        COMPONENT_FORMAT_ERROR_CODE
+    }
+}
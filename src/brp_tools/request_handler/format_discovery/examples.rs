@@ -0,0 +1,304 @@
+//! Example values of types, generated from their registry schemas
+//!
+//! An example is a value BRP accepts for the type, with zeroes, empty strings and first variants
+//! as placeholders: math types are arrays, `Transform` and colors follow the known formats,
+//! `Option`s are `null`, newtype tuple structs are their field and enum variants carrying data
+//! are `{"Variant": ..}`. Field types are looked up through a schema callback, the way the
+//! corrector looks them up, usually over the schemas [`fetch_referenced_schemas`] collected.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::{Map, Value, json};
+
+use super::known_formats::{KnownFormat, known_format};
+use super::schema_cache::type_schema;
+use crate::brp_tools::support::mutation_paths::primitive_kind;
+use crate::error::Result;
+
+/// How many levels of nested types are filled in; deeper values are `null`
+const MAX_EXAMPLE_DEPTH: usize = 8;
+
+/// Most schemas fetched for one type
+const MAX_SCHEMAS: usize = 32;
+
+/// Type path prefix of `Option`, which serializes as `null` or its value
+const OPTION_PREFIX: &str = "core::option::Option<";
+
+/// An example value of a type, `null` where nothing is known about a type
+pub fn example_value(type_path: &str, schema: &dyn Fn(&str) -> Option<Value>) -> Value {
+    example_at_depth(type_path, schema, 0)
+}
+
+/// Fetch the schema of the type and, breadth first, of the types it refers to
+pub async fn fetch_referenced_schemas(
+    type_path: &str,
+    port: Option<u16>,
+) -> Result<HashMap<String, Value>> {
+    let mut schemas = HashMap::new();
+    let mut pending = VecDeque::from([type_path.to_string()]);
+
+    while let Some(type_path) = pending.pop_front() {
+        if schemas.len() >= MAX_SCHEMAS
+            || primitive_kind(&type_path).is_some()
+            || schemas.contains_key(&type_path)
+        {
+            continue;
+        }
+        let Some(schema) = type_schema(&type_path, port).await? else {
+            continue;
+        };
+        pending.extend(referenced_types(&schema));
+        schemas.insert(type_path, schema);
+    }
+
+    Ok(schemas)
+}
+
+/// The types a schema refers to, such as its field, variant and item types, in order
+fn referenced_types(schema: &Value) -> Vec<String> {
+    let mut types = Vec::new();
+    collect_references(schema, &mut types);
+    types
+}
+
+fn collect_references(value: &Value, types: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            if let Some(type_path) = fields.get("$ref").and_then(Value::as_str) {
+                let type_path = type_path.trim_start_matches("#/$defs/").to_string();
+                if !types.contains(&type_path) {
+                    types.push(type_path);
+                }
+            }
+            for value in fields.values() {
+                collect_references(value, types);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_references(item, types);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn example_at_depth(
+    type_path: &str,
+    schema: &dyn Fn(&str) -> Option<Value>,
+    depth: usize,
+) -> Value {
+    if depth > MAX_EXAMPLE_DEPTH || type_path.starts_with(OPTION_PREFIX) {
+        return Value::Null;
+    }
+    if let Some(kind) = primitive_kind(type_path) {
+        return primitive_example(kind);
+    }
+    if let Some(format) = known_format(type_path) {
+        return known_example(type_path, format, schema, depth);
+    }
+    schema(type_path).map_or(Value::Null, |type_schema| {
+        schema_example(&type_schema, schema, depth)
+    })
+}
+
+fn primitive_example(kind: &str) -> Value {
+    match kind {
+        "float" => json!(0.0),
+        "int" | "uint" => json!(0),
+        "boolean" => json!(false),
+        "string" => json!(""),
+        _ => Value::Null,
+    }
+}
+
+fn known_example(
+    type_path: &str,
+    format: KnownFormat,
+    schema: &dyn Fn(&str) -> Option<Value>,
+    depth: usize,
+) -> Value {
+    let white = || json!({ "red": 1.0, "green": 1.0, "blue": 1.0, "alpha": 1.0 });
+    match format {
+        // The identity rotation rather than the zero quaternion
+        KnownFormat::Array("Quat", _) => json!([0.0, 0.0, 0.0, 1.0]),
+        KnownFormat::Array(_, components) => {
+            let zero = if type_path.contains("::IVec") || type_path.contains("::UVec") {
+                json!(0)
+            } else {
+                json!(0.0)
+            };
+            Value::Array(vec![zero; components.len()])
+        }
+        KnownFormat::Struct(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, field_type)| {
+                    let value = match *field_type {
+                        "glam::Vec3" if *name == "scale" => json!([1.0, 1.0, 1.0]),
+                        _ => example_at_depth(field_type, schema, depth + 1),
+                    };
+                    ((*name).to_string(), value)
+                })
+                .collect(),
+        ),
+        KnownFormat::String => json!(""),
+        KnownFormat::Color => json!({ "Srgba": white() }),
+        KnownFormat::ColorChannels => white(),
+    }
+}
+
+/// An example built from a type's schema, by its kind
+fn schema_example(
+    type_schema: &Value,
+    schema: &dyn Fn(&str) -> Option<Value>,
+    depth: usize,
+) -> Value {
+    let example_of = |field: &Value| {
+        type_ref(field).map_or(Value::Null, |field_type| {
+            example_at_depth(field_type, schema, depth + 1)
+        })
+    };
+    match type_schema["kind"].as_str() {
+        Some("Struct") => Value::Object(struct_fields(type_schema, &example_of)),
+        Some("TupleStruct" | "Tuple") => tuple_fields(type_schema, &example_of),
+        Some("Enum") => type_schema["oneOf"]
+            .as_array()
+            .and_then(|variants| variants.first())
+            .map_or(Value::Null, |variant| variant_example(variant, &example_of)),
+        Some("Array" | "List" | "Set") => Value::Array(
+            type_schema
+                .get("items")
+                .map(&example_of)
+                .into_iter()
+                .collect(),
+        ),
+        Some("Map") => Value::Object(Map::new()),
+        _ => type_schema["type"]
+            .as_str()
+            .map_or(Value::Null, primitive_example),
+    }
+}
+
+/// A unit variant is its name, a variant with data `{"Variant": data}`
+fn variant_example(variant: &Value, example_of: &dyn Fn(&Value) -> Value) -> Value {
+    if let Some(name) = variant.as_str() {
+        return json!(name);
+    }
+    let Some(name) = variant["shortPath"].as_str() else {
+        return Value::Null;
+    };
+    let data = match variant["kind"].as_str() {
+        Some("Struct") => Value::Object(struct_fields(variant, example_of)),
+        Some("Tuple") => tuple_fields(variant, example_of),
+        _ => return json!(name),
+    };
+    json!({ name: data })
+}
+
+fn struct_fields(schema: &Value, example_of: &dyn Fn(&Value) -> Value) -> Map<String, Value> {
+    schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, field)| (name.clone(), example_of(field)))
+        .collect()
+}
+
+/// Tuple fields as an array, or the single field of a newtype as itself
+fn tuple_fields(schema: &Value, example_of: &dyn Fn(&Value) -> Value) -> Value {
+    let mut fields: Vec<Value> = schema["prefixItems"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(example_of)
+        .collect();
+    if fields.len() == 1 {
+        fields.remove(0)
+    } else {
+        Value::Array(fields)
+    }
+}
+
+fn type_ref(field: &Value) -> Option<&str> {
+    field["type"]["$ref"]
+        .as_str()
+        .map(|reference| reference.trim_start_matches("#/$defs/"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn field(type_path: &str) -> Value {
+        json!({ "type": { "$ref": format!("#/$defs/{type_path}") } })
+    }
+
+    #[test]
+    fn test_examples_follow_the_brp_formats() {
+        let schemas: HashMap<&str, Value> = HashMap::from([
+            (
+                "my_game::Enemy",
+                json!({
+                    "kind": "Struct",
+                    "properties": {
+                        "health": field("f32"),
+                        "velocity": field("glam::Vec3"),
+                        "target": field("core::option::Option<bevy_ecs::entity::Entity>"),
+                        "mood": field("my_game::Mood"),
+                        "tags": field("alloc::vec::Vec<alloc::string::String>"),
+                        "id": field("my_game::EnemyId"),
+                    },
+                }),
+            ),
+            (
+                "my_game::Mood",
+                json!({
+                    "kind": "Enum",
+                    "oneOf": [
+                        { "shortPath": "Angry", "kind": "Struct", "properties": { "level": field("u8") } },
+                        "Calm",
+                    ],
+                }),
+            ),
+            (
+                "alloc::vec::Vec<alloc::string::String>",
+                json!({ "kind": "List", "items": field("alloc::string::String") }),
+            ),
+            (
+                "my_game::EnemyId",
+                json!({ "kind": "TupleStruct", "prefixItems": [field("u32")] }),
+            ),
+        ]);
+        let schema = |type_path: &str| schemas.get(type_path).cloned();
+
+        assert_eq!(
+            example_value("my_game::Enemy", &schema),
+            json!({
+                "health": 0.0,
+                "velocity": [0.0, 0.0, 0.0],
+                "target": null,
+                "mood": { "Angry": { "level": 0 } },
+                "tags": [""],
+                "id": 0,
+            })
+        );
+        assert_eq!(
+            example_value("bevy_transform::components::transform::Transform", &schema),
+            json!({
+                "translation": [0.0, 0.0, 0.0],
+                "rotation": [0.0, 0.0, 0.0, 1.0],
+                "scale": [1.0, 1.0, 1.0],
+            })
+        );
+        assert_eq!(example_value("my_game::Unknown", &schema), Value::Null);
+        assert_eq!(
+            referenced_types(&schemas["my_game::Mood"]),
+            vec!["u8".to_string()]
+        );
+    }
+}
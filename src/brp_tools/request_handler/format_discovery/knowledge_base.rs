@@ -0,0 +1,408 @@
+//! Format corrections learned across sessions
+//!
+//! Format discovery would otherwise find the same corrections every session. A correction that
+//! lets a request through is saved to the state directory under the app's Bevy version (the
+//! `info.version` of its `rpc.discover` document), as the type, the shape of the rejected value
+//! and the corrected value. Discovery consults the saved corrections before its other tiers: a
+//! value shaped like a rejected one is rewritten the same way, with its numbers, strings and
+//! booleans carried over to where the correction put them. A learned correction that fails is
+//! forgotten. Apps whose version can't be read are never learned from.
+//!
+//! The store is read from disk once and kept in memory, and the version of each app instance is
+//! cached, so consulting the knowledge base costs no requests while nothing has been learned and
+//! one `rpc.discover` per app instance after that.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::engine::FormatCorrection;
+use crate::brp_tools::support::app_instance::AppInstance;
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::config;
+use crate::error::{Error, Result};
+use crate::state::{StateDir, StateFormat};
+use crate::tools::BRP_METHOD_RPC_DISCOVER;
+
+/// Start of the hint of a correction applied from the knowledge base
+pub const LEARNED_HINT_PREFIX: &str = "Learned correction";
+
+/// Oldest corrections of a type are dropped beyond this many
+const MAX_CORRECTIONS_PER_TYPE: usize = 20;
+
+/// How long the Bevy version read from an app instance is trusted
+const VERSION_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// The store as last loaded or saved; `None` until first used
+static STORE: Mutex<Option<LearnedCorrections>> = Mutex::new(None);
+
+/// Bevy versions read from app instances, with when they were read
+static VERSIONS: LazyLock<Mutex<HashMap<AppInstance, (String, Instant)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Every learned correction, by Bevy version and then type name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LearnedCorrections {
+    pub versions: BTreeMap<String, BTreeMap<String, Vec<LearnedCorrection>>>,
+}
+
+impl StateFormat for LearnedCorrections {
+    const FILE_NAME: &'static str = "format_corrections.json";
+    const VERSION: u32 = 1;
+}
+
+/// One correction that let a request through
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LearnedCorrection {
+    /// The rejected value with every scalar replaced by the name of its JSON type
+    pub original_shape:   Value,
+    pub original_format:  Value,
+    pub corrected_format: Value,
+    /// For every scalar of the corrected value, in order, the position of the scalar of the
+    /// original it came from. `None` when a scalar appears more than once, so only the exact
+    /// original value is corrected.
+    pub sources:          Option<Vec<Option<usize>>>,
+    pub hint:             String,
+    pub learned_at:       String,
+    /// Times the correction was applied and the request went through
+    pub uses:             u64,
+}
+
+impl LearnedCorrection {
+    /// Learn how a value was corrected
+    pub fn new(original: &Value, corrected: &Value, hint: &str) -> Self {
+        let original_scalars = scalars(original);
+        let positions: Vec<Option<usize>> = scalars(corrected)
+            .iter()
+            .map(|scalar| original_scalars.iter().position(|source| source == scalar))
+            .collect();
+        // A scalar found twice on either side can't be traced to one source
+        let repeated = original_scalars
+            .iter()
+            .enumerate()
+            .any(|(i, scalar)| original_scalars[..i].contains(scalar))
+            || positions
+                .iter()
+                .enumerate()
+                .any(|(i, position)| position.is_some() && positions[..i].contains(position));
+        let sources = (!repeated).then_some(positions);
+        Self {
+            original_shape: shape(original),
+            original_format: original.clone(),
+            corrected_format: corrected.clone(),
+            sources,
+            hint: hint.to_string(),
+            learned_at: chrono::Local::now().to_rfc3339(),
+            uses: 0,
+        }
+    }
+
+    /// Rewrite a value the way this correction rewrote its original, if it has the same shape
+    pub fn apply(&self, value: &Value) -> Option<Value> {
+        if shape(value) != self.original_shape {
+            return None;
+        }
+        let Some(sources) = &self.sources else {
+            return (*value == self.original_format).then(|| self.corrected_format.clone());
+        };
+        let values = scalars(value);
+        let mut corrected = self.corrected_format.clone();
+        for (slot, source) in scalars_mut(&mut corrected).into_iter().zip(sources) {
+            if let Some(source) = source {
+                slot.clone_from(values.get(*source)?);
+            }
+        }
+        Some(corrected)
+    }
+}
+
+/// A value with every scalar replaced by the name of its JSON type
+fn shape(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), shape(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(shape).collect()),
+        Value::Null => Value::from("null"),
+        Value::Bool(_) => Value::from("boolean"),
+        Value::Number(_) => Value::from("number"),
+        Value::String(_) => Value::from("string"),
+    }
+}
+
+/// The scalars of a value, depth first
+fn scalars(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Object(map) => map.values().flat_map(scalars).collect(),
+        Value::Array(items) => items.iter().flat_map(scalars).collect(),
+        scalar => vec![scalar],
+    }
+}
+
+/// The scalars of a value, depth first, for rewriting
+fn scalars_mut(value: &mut Value) -> Vec<&mut Value> {
+    match value {
+        Value::Object(map) => map.values_mut().flat_map(scalars_mut).collect(),
+        Value::Array(items) => items.iter_mut().flat_map(scalars_mut).collect(),
+        scalar => vec![scalar],
+    }
+}
+
+/// The Bevy version of the app on a port, from its `rpc.discover` document
+///
+/// Versions are cached per app instance; a failed read is not cached.
+pub async fn bevy_version(port: Option<u16>) -> Option<String> {
+    let app = AppInstance::on_port(port.unwrap_or_else(config::default_port));
+    let now = Instant::now();
+    if let Some(version) = VERSIONS.lock().ok().and_then(|versions| {
+        versions
+            .get(&app)
+            .filter(|(_, read)| now.duration_since(*read) < VERSION_CACHE_TTL)
+            .map(|(version, _)| version.clone())
+    }) {
+        return Some(version);
+    }
+
+    let version = match execute_brp_method(BRP_METHOD_RPC_DISCOVER, None, port).await {
+        Ok(BrpResult::Success(Some(document))) => document
+            .pointer("/info/version")
+            .and_then(Value::as_str)
+            .map(String::from),
+        _ => None,
+    }?;
+    if let Ok(mut versions) = VERSIONS.lock() {
+        // Earlier instances on the same port are gone
+        versions.retain(|cached, _| !cached.same_connection(&app));
+        versions.insert(app, (version.clone(), now));
+    }
+    Some(version)
+}
+
+/// File the knowledge base is saved to, or `None` when persistence is disabled
+pub fn store_path() -> Option<PathBuf> {
+    StateDir::global()
+        .path()
+        .map(|dir| dir.join(LearnedCorrections::FILE_NAME))
+}
+
+/// Everything learned so far
+pub fn load() -> Result<LearnedCorrections> {
+    with_store(|store| store.clone())
+}
+
+/// Run a closure on the store, loading it from disk on first use
+fn with_store<R>(f: impl FnOnce(&mut LearnedCorrections) -> R) -> Result<R> {
+    let mut guard = STORE.lock().map_err(|e| {
+        error_stack::Report::new(Error::MutexPoisoned(format!(
+            "format corrections lock: {e}"
+        )))
+    })?;
+    let store = match guard.take() {
+        Some(store) => store,
+        None => StateDir::global().load()?,
+    };
+    Ok(f(guard.insert(store)))
+}
+
+/// Find a learned correction for a value of a type, returning the corrected value and a hint
+pub async fn lookup(type_name: &str, value: &Value, port: Option<u16>) -> Option<(Value, String)> {
+    let nothing_learned = with_store(|store| store.versions.is_empty())
+        .inspect_err(|e| warn!("Failed to load learned format corrections: {}", e))
+        .ok()?;
+    if nothing_learned {
+        return None;
+    }
+
+    let version = bevy_version(port).await?;
+    with_store(|store| {
+        store
+            .versions
+            .get(&version)?
+            .get(type_name)?
+            .iter()
+            .find_map(|learned| {
+                learned.apply(value).map(|corrected| {
+                    let hint =
+                        format!("{LEARNED_HINT_PREFIX} for Bevy {version}: {}", learned.hint);
+                    (corrected, hint)
+                })
+            })
+    })
+    .ok()
+    .flatten()
+}
+
+/// Learn from the corrections made for one request
+///
+/// New corrections are saved when the request went through; learned ones are counted when it
+/// did and forgotten when it didn't. Serialization diagnostics, which leave the value as it was,
+/// aren't saved.
+pub async fn learn(port: Option<u16>, corrections: &[FormatCorrection], succeeded: bool) {
+    if corrections.is_empty() {
+        return;
+    }
+    let Some(version) = bevy_version(port).await else {
+        debug!("Not learning format corrections: the app's Bevy version is unknown");
+        return;
+    };
+
+    let saved = with_store(|store| {
+        let learned = store.versions.entry(version).or_default();
+        for correction in corrections {
+            record(learned, correction, succeeded);
+        }
+        store.versions.retain(|_, types| {
+            types.retain(|_, corrections| !corrections.is_empty());
+            !types.is_empty()
+        });
+        StateDir::global().save(store)
+    });
+    if let Err(e) = saved.and_then(|saved| saved) {
+        warn!("Failed to update learned format corrections: {}", e);
+    }
+}
+
+/// Apply the outcome of one correction to the corrections learned for a Bevy version
+fn record(
+    learned: &mut BTreeMap<String, Vec<LearnedCorrection>>,
+    correction: &FormatCorrection,
+    succeeded: bool,
+) {
+    let entries = learned.entry(correction.component.clone()).or_default();
+    let original_shape = shape(&correction.original_format);
+    let matching = |entry: &LearnedCorrection| {
+        entry.original_shape == original_shape
+            && entry.apply(&correction.original_format).as_ref()
+                == Some(&correction.corrected_format)
+    };
+
+    if correction.hint.starts_with(LEARNED_HINT_PREFIX) {
+        if succeeded {
+            entries
+                .iter_mut()
+                .filter(|entry| matching(entry))
+                .for_each(|entry| entry.uses += 1);
+        } else {
+            entries.retain(|entry| !matching(entry));
+        }
+    } else if succeeded && correction.corrected_format != correction.original_format {
+        entries.retain(|entry| entry.original_shape != original_shape);
+        entries.push(LearnedCorrection::new(
+            &correction.original_format,
+            &correction.corrected_format,
+            &correction.hint,
+        ));
+        let excess = entries.len().saturating_sub(MAX_CORRECTIONS_PER_TYPE);
+        entries.drain(..excess);
+    }
+}
+
+/// Forget learned corrections, optionally only those of one Bevy version or type, returning how
+/// many were forgotten
+pub fn clear(version: Option<&str>, type_name: Option<&str>) -> Result<usize> {
+    with_store(|store| {
+        let mut cleared = 0;
+        store.versions.retain(|store_version, types| {
+            if version.is_some_and(|version| version != store_version) {
+                return true;
+            }
+            types.retain(|name, corrections| {
+                let keep = type_name.is_some_and(|type_name| type_name != name);
+                if !keep {
+                    cleared += corrections.len();
+                }
+                keep
+            });
+            !types.is_empty()
+        });
+        StateDir::global().save(store).map(|()| cleared)
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    fn correction(original: Value, corrected: Value, hint: &str) -> FormatCorrection {
+        FormatCorrection {
+            component:        "bevy_transform::components::transform::Transform".to_string(),
+            original_format:  original,
+            corrected_format: corrected,
+            hint:             hint.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_learned_corrections_carry_values_over() {
+        let mut learned = BTreeMap::new();
+        record(
+            &mut learned,
+            &correction(
+                json!({ "translation": { "x": 1.0, "y": 2.0, "z": 3.0 } }),
+                json!({ "translation": [1.0, 2.0, 3.0], "scale": [1.0, 1.0, 1.0] }),
+                "Converted object to array",
+            ),
+            true,
+        );
+        let entries = &learned["bevy_transform::components::transform::Transform"];
+        assert_eq!(entries.len(), 1);
+
+        // The scale repeats a translation value, so its source is ambiguous
+        assert_eq!(entries[0].sources, None);
+        assert_eq!(
+            entries[0].apply(&json!({ "translation": { "x": 1.0, "y": 2.0, "z": 3.0 } })),
+            Some(json!({ "translation": [1.0, 2.0, 3.0], "scale": [1.0, 1.0, 1.0] }))
+        );
+        assert_eq!(
+            entries[0].apply(&json!({ "translation": { "x": 4.0, "y": 5.0, "z": 6.0 } })),
+            None
+        );
+
+        let vector = LearnedCorrection::new(
+            &json!({ "x": 1.0, "y": 2.0, "z": 3.0 }),
+            &json!([1.0, 2.0, 3.0]),
+            "Converted object to array",
+        );
+        assert_eq!(
+            vector.apply(&json!({ "x": 7.5, "y": -1.0, "z": 0.0 })),
+            Some(json!([7.5, -1.0, 0.0]))
+        );
+        assert_eq!(vector.apply(&json!({ "x": 7.5, "y": -1.0 })), None);
+
+        // A learned correction that fails is forgotten
+        let reapplied = correction(
+            json!({ "translation": { "x": 1.0, "y": 2.0, "z": 3.0 } }),
+            json!({ "translation": [1.0, 2.0, 3.0], "scale": [1.0, 1.0, 1.0] }),
+            &format!("{LEARNED_HINT_PREFIX} for Bevy 0.16.1: Converted object to array"),
+        );
+        record(&mut learned, &reapplied, false);
+        assert!(learned["bevy_transform::components::transform::Transform"].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bevy_version_is_read_once_per_app_instance() {
+        let server = MockBrpServer::start().await;
+        server.respond(
+            BRP_METHOD_RPC_DISCOVER,
+            json!({ "info": { "title": "Bevy Remote Protocol", "version": "0.16.1" } }),
+        );
+
+        for _ in 0..3 {
+            assert_eq!(
+                bevy_version(Some(server.port())).await.as_deref(),
+                Some("0.16.1")
+            );
+        }
+        assert_eq!(server.requests_for(BRP_METHOD_RPC_DISCOVER).len(), 1);
+    }
+}
@@ -0,0 +1,393 @@
+//! Built-in knowledge of how commonly mistyped types are written in BRP JSON
+//!
+//! Agents tend to send math types as `{x, y, z}` objects, colors as `{r, g, b, a}` and `Name` as
+//! an object, all of which BRP rejects. Values of these types are rewritten before the first
+//! request, wherever they appear: as the whole value, as a field of a known struct such as
+//! `Transform`, or as a field of a type whose registry schema is already cached.
+
+use serde_json::{Map, Value};
+
+use super::path_parser::{PathSegment, parse_path};
+use super::transformers::StringTypeTransformer;
+use crate::brp_tools::support::mutation_paths::{MAX_DEPTH, field_types};
+
+/// How a known type is written in BRP JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownFormat {
+    /// Array of the named components in order, e.g. `[x, y, z]`
+    Array(&'static str, &'static [&'static str]),
+    /// Struct whose fields have known types
+    Struct(&'static [(&'static str, &'static str)]),
+    /// Plain string
+    String,
+    /// The `Color` enum, written as `{"Srgba": {"red": .., "green": .., "blue": .., "alpha": ..}}`
+    Color,
+    /// Color struct with `red`, `green`, `blue` and `alpha` fields
+    ColorChannels,
+}
+
+const VEC2: &[&str] = &["x", "y"];
+const VEC3: &[&str] = &["x", "y", "z"];
+const VEC4: &[&str] = &["x", "y", "z", "w"];
+
+/// Color variants holding `red`, `green`, `blue` and `alpha` channels
+const RGBA_VARIANTS: &[&str] = &["Srgba", "LinearRgba"];
+
+/// Short channel names agents use for colors, with the fields BRP expects
+const COLOR_CHANNELS: &[(&str, &str)] =
+    &[("r", "red"), ("g", "green"), ("b", "blue"), ("a", "alpha")];
+
+/// The BRP format of a type, if it's one of the known problematic types
+pub fn known_format(type_path: &str) -> Option<KnownFormat> {
+    match type_path {
+        "glam::Vec2" | "glam::IVec2" | "glam::UVec2" | "glam::DVec2" => {
+            Some(KnownFormat::Array("Vec2", VEC2))
+        }
+        "glam::Vec3" | "glam::Vec3A" | "glam::IVec3" | "glam::UVec3" | "glam::DVec3" => {
+            Some(KnownFormat::Array("Vec3", VEC3))
+        }
+        "glam::Vec4" | "glam::IVec4" | "glam::UVec4" | "glam::DVec4" => {
+            Some(KnownFormat::Array("Vec4", VEC4))
+        }
+        "glam::Quat" | "glam::DQuat" => Some(KnownFormat::Array("Quat", VEC4)),
+        "bevy_transform::components::transform::Transform" => Some(KnownFormat::Struct(&[
+            ("translation", "glam::Vec3"),
+            ("rotation", "glam::Quat"),
+            ("scale", "glam::Vec3"),
+        ])),
+        "bevy_ecs::name::Name" => Some(KnownFormat::String),
+        "bevy_color::color::Color" => Some(KnownFormat::Color),
+        "bevy_color::srgba::Srgba" | "bevy_color::linear_rgba::LinearRgba" => {
+            Some(KnownFormat::ColorChannels)
+        }
+        _ => None,
+    }
+}
+
+/// Rewrites values into BRP format using the knowledge base and cached schemas
+pub struct Corrector<'a> {
+    /// Cached registry schema of a type, if any
+    schema:  &'a dyn Fn(&str) -> Option<Value>,
+    /// What was changed, one entry per rewritten value
+    changes: Vec<String>,
+}
+
+impl<'a> Corrector<'a> {
+    pub fn new(schema: &'a dyn Fn(&str) -> Option<Value>) -> Self {
+        Self {
+            schema,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Rewrite a value of the type, returning the corrected value and what was changed
+    ///
+    /// Returns `None` if the value is already in BRP format or nothing is known about it.
+    pub fn correct(mut self, type_path: &str, value: &Value) -> Option<(Value, String)> {
+        let corrected = self.correct_at(type_path, value, "", 0)?;
+        Some((corrected, self.changes.join(", ")))
+    }
+
+    /// The type of the value at a mutation path below a type, if it can be resolved
+    ///
+    /// An empty path is the type itself.
+    pub fn type_at_path(&self, type_path: &str, path: &str) -> Option<String> {
+        let mut current = type_path.to_string();
+        if path.is_empty() {
+            return Some(current);
+        }
+        for segment in parse_path(path)? {
+            current = self
+                .field_types(&current)
+                .into_iter()
+                .find(|(field, _)| *field == segment)
+                .map(|(_, field_type)| field_type)?;
+        }
+        Some(current)
+    }
+
+    fn correct_at(
+        &mut self,
+        type_path: &str,
+        value: &Value,
+        at: &str,
+        depth: usize,
+    ) -> Option<Value> {
+        let location = if at.is_empty() {
+            format!("`{type_path}`")
+        } else {
+            format!("`{at}`")
+        };
+        match known_format(type_path) {
+            Some(KnownFormat::Array(name, components)) => {
+                let corrected = to_array(value, components)?;
+                self.changes.push(format!(
+                    "{location} converted to {name} array format [{}]",
+                    components.join(", ")
+                ));
+                Some(corrected)
+            }
+            Some(KnownFormat::String) if !value.is_string() => {
+                let (name, source) = StringTypeTransformer::extract_string_value(value)?;
+                self.changes
+                    .push(format!("{location} converted to a plain string {source}"));
+                Some(Value::String(name))
+            }
+            Some(KnownFormat::Color) => {
+                let corrected = to_color(value)?;
+                self.changes.push(format!(
+                    "{location} converted to the Color enum format {{\"Srgba\": {{\"red\", \"green\", \"blue\", \"alpha\"}}}}"
+                ));
+                Some(corrected)
+            }
+            Some(KnownFormat::ColorChannels) => {
+                let corrected = to_channels(value)?;
+                self.changes.push(format!(
+                    "{location} converted to {{\"red\", \"green\", \"blue\", \"alpha\"}} fields"
+                ));
+                Some(corrected)
+            }
+            Some(KnownFormat::Struct(_)) | None if depth < MAX_DEPTH => {
+                self.correct_fields(type_path, value, at, depth)
+            }
+            _ => None,
+        }
+    }
+
+    /// Rewrite the fields of a struct value, returning it if any field changed
+    fn correct_fields(
+        &mut self,
+        type_path: &str,
+        value: &Value,
+        at: &str,
+        depth: usize,
+    ) -> Option<Value> {
+        let Value::Object(fields) = value else {
+            return None;
+        };
+        let mut corrected = fields.clone();
+        let mut changed = false;
+        for (segment, field_type) in self.field_types(type_path) {
+            let PathSegment::Field(name) = segment else {
+                continue;
+            };
+            let Some(field_value) = fields.get(&name) else {
+                continue;
+            };
+            let field_at = format!("{at}.{name}");
+            if let Some(fixed) = self.correct_at(&field_type, field_value, &field_at, depth + 1) {
+                corrected.insert(name, fixed);
+                changed = true;
+            }
+        }
+        changed.then_some(Value::Object(corrected))
+    }
+
+    /// Field types of a known struct, or of a type with a cached schema
+    fn field_types(&self, type_path: &str) -> Vec<(PathSegment, String)> {
+        if let Some(KnownFormat::Struct(fields)) = known_format(type_path) {
+            return fields
+                .iter()
+                .map(|(name, field_type)| {
+                    (
+                        PathSegment::Field((*name).to_string()),
+                        (*field_type).to_string(),
+                    )
+                })
+                .collect();
+        }
+        (self.schema)(type_path)
+            .map(|schema| field_types(&schema))
+            .unwrap_or_default()
+    }
+}
+
+/// Convert a `{x, y, z}` object to an array, keeping the numbers as given
+fn to_array(value: &Value, components: &[&str]) -> Option<Value> {
+    let Value::Object(fields) = value else {
+        return None;
+    };
+    if fields.len() != components.len() {
+        return None;
+    }
+    components
+        .iter()
+        .map(|component| fields.get(*component).filter(|v| v.is_number()).cloned())
+        .collect::<Option<Vec<_>>>()
+        .map(Value::Array)
+}
+
+/// Convert `{r, g, b, a}` objects or `[r, g, b, a]` arrays to `red`/`green`/`blue`/`alpha` fields
+///
+/// Alpha defaults to 1.0 when left out.
+fn to_channels(value: &Value) -> Option<Value> {
+    let channels: Vec<Option<&Value>> = match value {
+        Value::Object(fields) if fields.keys().all(|key| short_channel(key).is_some()) => {
+            COLOR_CHANNELS
+                .iter()
+                .map(|(short, _)| fields.get(*short))
+                .collect()
+        }
+        Value::Array(items) if (3..=4).contains(&items.len()) => {
+            (0..4).map(|index| items.get(index)).collect()
+        }
+        _ => return None,
+    };
+
+    let mut corrected = Map::new();
+    for ((_, name), channel) in COLOR_CHANNELS.iter().zip(channels) {
+        let channel = match channel {
+            Some(channel) if channel.is_number() => channel.clone(),
+            None if *name == "alpha" => Value::from(1.0),
+            _ => return None,
+        };
+        corrected.insert((*name).to_string(), channel);
+    }
+    Some(Value::Object(corrected))
+}
+
+/// Wrap bare color channels in the `Srgba` variant, fixing channels of RGBA variants
+fn to_color(value: &Value) -> Option<Value> {
+    let variant = value
+        .as_object()
+        .filter(|fields| fields.len() == 1)
+        .and_then(|fields| fields.iter().next())
+        .filter(|(variant, _)| RGBA_VARIANTS.contains(&variant.as_str()));
+    if let Some((variant, channels)) = variant {
+        let corrected = to_channels(channels)?;
+        return Some(Value::Object(Map::from_iter([(
+            variant.clone(),
+            corrected,
+        )])));
+    }
+
+    let channels = to_channels(value).or_else(|| {
+        // `{red, green, blue, alpha}` without the variant is already a valid `Srgba`
+        let Value::Object(fields) = value else {
+            return None;
+        };
+        COLOR_CHANNELS
+            .iter()
+            .map(|(_, name)| *name)
+            .all(|name| fields.get(name).is_some_and(Value::is_number))
+            .then(|| value.clone())
+    })?;
+    Some(Value::Object(Map::from_iter([(
+        "Srgba".to_string(),
+        channels,
+    )])))
+}
+
+fn short_channel(key: &str) -> Option<&'static str> {
+    COLOR_CHANNELS
+        .iter()
+        .find(|(short, _)| *short == key)
+        .map(|(_, name)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_known_types_are_rewritten_before_sending() {
+        let no_schema = |_: &str| None;
+
+        let (transform, hint) = Corrector::new(&no_schema)
+            .correct(
+                "bevy_transform::components::transform::Transform",
+                &json!({
+                    "translation": { "x": 1.0, "y": 2.0, "z": 3.0 },
+                    "rotation": [0.0, 0.0, 0.0, 1.0],
+                    "scale": { "x": 1.0, "y": 1.0, "z": 1.0 }
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            transform,
+            json!({
+                "translation": [1.0, 2.0, 3.0],
+                "rotation": [0.0, 0.0, 0.0, 1.0],
+                "scale": [1.0, 1.0, 1.0]
+            })
+        );
+        assert!(hint.contains("`.translation` converted to Vec3 array format"));
+        assert!(!hint.contains("rotation"));
+
+        let (color, _) = Corrector::new(&no_schema)
+            .correct(
+                "bevy_color::color::Color",
+                &json!({ "r": 1.0, "g": 0.5, "b": 0.0 }),
+            )
+            .unwrap();
+        assert_eq!(
+            color,
+            json!({ "Srgba": { "red": 1.0, "green": 0.5, "blue": 0.0, "alpha": 1.0 } })
+        );
+
+        let (name, _) = Corrector::new(&no_schema)
+            .correct("bevy_ecs::name::Name", &json!({ "name": "Player" }))
+            .unwrap();
+        assert_eq!(name, json!("Player"));
+
+        // Values already in BRP format are left alone
+        let corrector = Corrector::new(&no_schema);
+        assert!(
+            corrector
+                .correct("bevy_ecs::name::Name", &json!("Player"))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_cached_schemas_reach_nested_fields_and_paths() {
+        let schema = |type_path: &str| {
+            (type_path == "game::Ship").then(|| {
+                json!({
+                    "kind": "Struct",
+                    "properties": {
+                        "velocity": { "type": { "$ref": "#/$defs/glam::Vec2" } },
+                        "hull": { "type": { "$ref": "#/$defs/bevy_color::color::Color" } }
+                    }
+                })
+            })
+        };
+
+        let (ship, _) = Corrector::new(&schema)
+            .correct(
+                "game::Ship",
+                &json!({ "velocity": { "x": 3, "y": 4 }, "hull": { "Srgba": [1.0, 0.0, 0.0] } }),
+            )
+            .unwrap();
+        assert_eq!(
+            ship,
+            json!({
+                "velocity": [3, 4],
+                "hull": { "Srgba": { "red": 1.0, "green": 0.0, "blue": 0.0, "alpha": 1.0 } }
+            })
+        );
+
+        let corrector = Corrector::new(&schema);
+        assert_eq!(
+            corrector.type_at_path("game::Ship", ".velocity").as_deref(),
+            Some("glam::Vec2")
+        );
+        assert_eq!(
+            corrector
+                .type_at_path(
+                    "bevy_transform::components::transform::Transform",
+                    ".rotation"
+                )
+                .as_deref(),
+            Some("glam::Quat")
+        );
+        assert!(
+            corrector
+                .type_at_path("game::Ship", ".velocity.x")
+                .is_none()
+        );
+    }
+}
@@ -5,11 +5,16 @@
 //! errors with zero boilerplate in individual tools. Works with both components and resources.
 
 mod constants;
+pub mod corrections_log;
 mod detection;
 mod engine;
+mod examples;
 mod field_mapper;
+pub mod knowledge_base;
+mod known_formats;
 mod path_parser;
 pub mod phases;
+mod schema_cache;
 mod transformers;
 pub mod types;
 mod utilities;
@@ -17,6 +22,13 @@ mod utilities;
 #[cfg(test)]
 mod tests;
 
+pub use self::detection::extract_crate_name;
 pub use self::engine::{
-    EnhancedBrpResult, FormatCorrection, execute_brp_method_with_format_discovery,
+    EnhancedBrpResult, FormatCorrection, FormatValidation,
+    execute_brp_method_with_format_discovery, format_discovery_enabled, strict_corrections_enabled,
+    validate_format,
 };
+pub use self::examples::{example_value, fetch_referenced_schemas};
+pub use self::path_parser::{PathSegment, parse_path, render_path, value_at, value_at_mut};
+pub use self::phases::initial_attempt::preflight_enabled;
+pub use self::schema_cache::{KnownTypes, known_types, registry_schema, type_schema};
@@ -1,30 +1,149 @@
 //! Path parsing logic to convert strings to `FieldAccess` structs
+//!
+//! Paths follow Bevy's reflect path grammar: `.field` and `.0` (tuple index) accessors, `[0]`
+//! list indices and `#0` field indices, nested to any depth. Enum variant field accesses like
+//! `.color.LinearRgba.red` can appear anywhere in a path and are rewritten to tuple indices in
+//! place.
 
-use super::field_mapper::parse_field_name;
+use std::fmt;
+
+use serde_json::Value;
+
+use super::field_mapper::{map_field_to_tuple_index, parse_field_name};
 use super::types::{ComponentType, FieldAccess};
 
+/// One accessor in a reflect path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// Named field access (`.field`)
+    Field(String),
+    /// Tuple or tuple struct index (`.0`)
+    TupleIndex(usize),
+    /// List, array or vec index (`[0]`)
+    ListIndex(usize),
+    /// Field index of a struct (`#0`)
+    FieldIndex(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(name) => write!(f, ".{name}"),
+            Self::TupleIndex(index) => write!(f, ".{index}"),
+            Self::ListIndex(index) => write!(f, "[{index}]"),
+            Self::FieldIndex(index) => write!(f, "#{index}"),
+        }
+    }
+}
+
+/// Parses a path string like `.items[2].color.0` into its segments
+///
+/// Returns `None` for empty or malformed paths, including paths that don't start with an
+/// accessor.
+pub fn parse_path(path: &str) -> Option<Vec<PathSegment>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        let (segment, remaining) = if let Some(body) = rest.strip_prefix('.') {
+            let (token, remaining) = split_token(body);
+            let segment = match token.parse() {
+                Ok(index) => PathSegment::TupleIndex(index),
+                Err(_) if is_identifier(token) => PathSegment::Field(token.to_string()),
+                Err(_) => return None,
+            };
+            (segment, remaining)
+        } else if let Some(body) = rest.strip_prefix('[') {
+            let (index, remaining) = body.split_once(']')?;
+            (PathSegment::ListIndex(index.parse().ok()?), remaining)
+        } else if let Some(body) = rest.strip_prefix('#') {
+            let (token, remaining) = split_token(body);
+            (PathSegment::FieldIndex(token.parse().ok()?), remaining)
+        } else {
+            return None;
+        };
+
+        segments.push(segment);
+        rest = remaining;
+    }
+
+    (!segments.is_empty()).then_some(segments)
+}
+
+/// Renders segments back into a path string
+pub fn render_path(segments: &[PathSegment]) -> String {
+    segments.iter().map(ToString::to_string).collect()
+}
+
+/// The part of a serialized value a path points at
+///
+/// Math types serialize as arrays, so `.x` through `.w` also index arrays, and newtype tuple
+/// structs serialize as their single field, so `.0` on a non-array is the value itself.
+pub fn value_at<'a>(value: &'a Value, segments: &[PathSegment]) -> Option<&'a Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match (segment, value) {
+            (PathSegment::Field(name), Value::Object(fields)) => fields.get(name),
+            (PathSegment::Field(name), Value::Array(items)) => items.get(axis_index(name)?),
+            (
+                PathSegment::TupleIndex(index) | PathSegment::ListIndex(index),
+                Value::Array(items),
+            ) => items.get(*index),
+            (PathSegment::TupleIndex(0), value) => Some(value),
+            _ => None,
+        })
+}
+
+/// The part of a serialized value a path points at, for changing it, following the same rules
+/// as `value_at`
+pub fn value_at_mut<'a>(value: &'a mut Value, segments: &[PathSegment]) -> Option<&'a mut Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match (segment, value) {
+            (PathSegment::Field(name), Value::Object(fields)) => fields.get_mut(name),
+            (PathSegment::Field(name), Value::Array(items)) => items.get_mut(axis_index(name)?),
+            (
+                PathSegment::TupleIndex(index) | PathSegment::ListIndex(index),
+                Value::Array(items),
+            ) => items.get_mut(*index),
+            (PathSegment::TupleIndex(0), value) => Some(value),
+            _ => None,
+        })
+}
+
+/// Array index of a math type axis field
+fn axis_index(name: &str) -> Option<usize> {
+    ["x", "y", "z", "w"].iter().position(|axis| *axis == name)
+}
+
+/// Splits off the token up to the next accessor
+fn split_token(body: &str) -> (&str, &str) {
+    body.split_at(body.find(['.', '[', '#']).unwrap_or(body.len()))
+}
+
+fn is_identifier(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
 /// Parses a path string like ".LinearRgba.red" into a `FieldAccess` struct
+///
+/// Only matches paths that consist of exactly a component type and one of its fields; use
+/// `parse_generic_enum_field_access` to rewrite such pairs inside longer paths.
 pub fn parse_path_to_field_access(path: &str) -> Option<FieldAccess> {
     // Simple field access (no component type) should be handled by the fallback logic
     // These remain as direct tuple indices (.0, .1, .2)
-    if path.starts_with('.') && path.matches('.').count() == 1 {
-        return None; // Let the fallback handle these
-    }
-
-    // Split the path into parts
-    let parts: Vec<&str> = path.split('.').collect();
-    if parts.len() < 3 || !parts[0].is_empty() {
-        return None;
+    match parse_path(path)?.as_slice() {
+        [
+            PathSegment::Field(component_name),
+            PathSegment::Field(field_name),
+        ] => typed_field_access(component_name, field_name),
+        _ => None,
     }
+}
 
-    // Extract component type and field name
-    let component_name = parts[1];
-    let field_name = parts[2];
-
-    // Parse component type
+/// Resolves a known component type and field name into a `FieldAccess`
+fn typed_field_access(component_name: &str, field_name: &str) -> Option<FieldAccess> {
     let component_type = parse_component_type(component_name)?;
-
-    // Parse field name
     let field = parse_field_name(field_name, component_type)?;
 
     Some(FieldAccess {
@@ -78,62 +197,110 @@ pub fn is_enum_variant(name: &str) -> bool {
     name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
 }
 
-/// Parses generic enum variant field access patterns
-/// Handles cases where we don't have a specific component type mapping
+/// Parses enum variant field access patterns at any depth in a path
+///
+/// Every `Variant.field` pair is rewritten to tuple indices, e.g. `.material.LinearRgba.red`
+/// becomes `.material.0.0` and `.shape.Custom.points[1]` becomes `.shape.0.points[1]`. Known
+/// component types use their exact field layout; other variants map common color and vector
+/// field names by position and keep any other field behind a `.0`. Returns `None` if the path
+/// can't be parsed or contains no such pair.
 pub fn parse_generic_enum_field_access(path: &str) -> Option<String> {
-    let parts: Vec<&str> = path.split('.').collect();
-    if parts.len() < 3 || !parts[0].is_empty() || parts[1].is_empty() || parts[2].is_empty() {
-        return None;
+    let mut segments = parse_path(path)?.into_iter().peekable();
+    let mut rewritten = Vec::new();
+    let mut changed = false;
+
+    while let Some(segment) = segments.next() {
+        let (variant, field_name) = match (&segment, segments.peek()) {
+            (PathSegment::Field(variant), Some(PathSegment::Field(field_name)))
+                if is_enum_variant(variant) =>
+            {
+                (variant.clone(), field_name.clone())
+            }
+            _ => {
+                rewritten.push(segment);
+                continue;
+            }
+        };
+        changed = true;
+
+        if let Some(indices) = variant_field_indices(&variant, &field_name) {
+            rewritten.extend(indices);
+            segments.next();
+        } else {
+            // Generic tuple variant: step into it and keep the field path
+            rewritten.push(PathSegment::TupleIndex(0));
+        }
     }
 
-    let variant_name = parts[1];
-    let field_name = parts[2];
+    changed.then(|| render_path(&rewritten))
+}
 
-    // Check if the second part looks like an enum variant (starts with uppercase)
-    if !is_enum_variant(variant_name) {
-        return None;
+/// Tuple indices replacing a `Variant.field` pair, if the field is known by position
+fn variant_field_indices(variant_name: &str, field_name: &str) -> Option<Vec<PathSegment>> {
+    if let Some(field_access) = typed_field_access(variant_name, field_name) {
+        let indices = parse_path(&map_field_to_tuple_index(&field_access))?;
+        if indices
+            .iter()
+            .all(|segment| matches!(segment, PathSegment::TupleIndex(_)))
+        {
+            return Some(indices);
+        }
     }
 
     // For color enum variants, try to map common field names to indices
-    match field_name {
+    let index = match field_name {
+        // Special case for 'a' - the Lab 'a' component, otherwise alpha below
+        "a" if variant_name.contains("Lab") => 1,
         // Index 0: First position fields
-        "red" | "r" | "hue" | "h" | "lightness" | "l" | "x" => Some(".0.0".to_string()),
+        "red" | "r" | "hue" | "h" | "lightness" | "l" | "x" => 0,
         // Index 1: Second position fields (including special cases)
-        "green" | "g" | "saturation" | "s" | "y" | "whiteness" | "chroma" | "c" => {
-            Some(".0.1".to_string())
-        }
+        "green" | "g" | "saturation" | "s" | "y" | "whiteness" | "chroma" | "c" => 1,
         // Index 2: Third position fields
-        "blue" | "b" | "value" | "v" | "z" | "blackness" => Some(".0.2".to_string()),
+        "blue" | "b" | "value" | "v" | "z" | "blackness" => 2,
         // Index 3: Fourth position fields
-        "alpha" | "w" => Some(".0.3".to_string()),
-        // Special case for 'a' - could be alpha or Lab 'a' component
-        "a" => {
-            if variant_name.contains("Lab") {
-                Some(".0.1".to_string()) // Lab 'a' component
-            } else {
-                Some(".0.3".to_string()) // Alpha component
-            }
-        }
-        _ => {
-            // Generic enum variant field access -> use tuple index 0 and preserve field path
-            if parts.len() > 3 {
-                let remaining = parts[2..].join(".");
-                Some(format!(".0.{remaining}"))
-            } else {
-                Some(format!(".0.{field_name}"))
-            }
-        }
-    }
+        "alpha" | "w" | "a" => 3,
+        _ => return None,
+    };
+    Some(vec![
+        PathSegment::TupleIndex(0),
+        PathSegment::TupleIndex(index),
+    ])
 }
 
 #[cfg(test)]
 #[allow(clippy::expect_used, clippy::unwrap_used)]
 mod tests {
+    use serde_json::json;
+
     use super::*;
     use crate::brp_tools::request_handler::format_discovery::types::{
         ColorField, Field, MathField,
     };
 
+    #[test]
+    fn test_value_at_follows_newtypes_lists_and_axes() {
+        let mut value = json!({ "items": [{ "weight": 0.5 }], "translation": [1.0, 2.0, 3.0] });
+        let path = [
+            PathSegment::Field("items".to_string()),
+            PathSegment::ListIndex(0),
+            PathSegment::Field("weight".to_string()),
+        ];
+        assert_eq!(value_at(&value, &path), Some(&json!(0.5)));
+        assert_eq!(
+            value_at(&json!(4), &[PathSegment::TupleIndex(0)]),
+            Some(&json!(4))
+        );
+        assert_eq!(
+            value_at(&value, &[PathSegment::Field("missing".to_string())]),
+            None
+        );
+
+        let y = parse_path(".translation.y").unwrap();
+        assert_eq!(value_at(&value, &y), Some(&json!(2.0)));
+        *value_at_mut(&mut value, &y).unwrap() = json!(5.0);
+        assert_eq!(value["translation"][1], 5.0);
+    }
+
     #[test]
     fn test_parse_path_to_field_access() {
         // Test color path parsing
@@ -230,4 +397,197 @@ mod tests {
         let field_access = parse_path_to_field_access(".Vec3.x").unwrap();
         assert_eq!(map_field_to_tuple_index(&field_access), ".0.0");
     }
+
+    #[test]
+    fn test_parse_path_grammar() {
+        assert_eq!(
+            parse_path(".items[12].color#1.0"),
+            Some(vec![
+                PathSegment::Field("items".to_string()),
+                PathSegment::ListIndex(12),
+                PathSegment::Field("color".to_string()),
+                PathSegment::FieldIndex(1),
+                PathSegment::TupleIndex(0),
+            ])
+        );
+
+        for invalid in ["", "items", ".", ".a..b", ".a[", ".a[x]", ".a#b", ".a b"] {
+            assert_eq!(parse_path(invalid), None, "{invalid:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_variant_field_access_at_any_depth() {
+        assert_eq!(
+            parse_generic_enum_field_access(".layers[3].tint.LinearRgba.red"),
+            Some(".layers[3].tint.0.0".to_string())
+        );
+        // Known component types use their own layout rather than the positional guess
+        assert_eq!(
+            parse_generic_enum_field_access(".a.b.c.d.Hsla.lightness"),
+            Some(".a.b.c.d.0.2".to_string())
+        );
+        // Several variants along one path are all rewritten
+        assert_eq!(
+            parse_generic_enum_field_access(".shape.Polygon.points[1].Custom.weight"),
+            Some(".shape.0.points[1].0.weight".to_string())
+        );
+        assert_eq!(
+            parse_generic_enum_field_access(".config.levels[0].spawn.0"),
+            None
+        );
+        // Only exact component/field pairs resolve to a typed field access
+        assert_eq!(parse_path_to_field_access(".sprite.LinearRgba.red"), None);
+    }
+
+    /// Shape of a generated reflected type
+    enum Shape {
+        Struct(Vec<(&'static str, Self)>),
+        List(Box<Self>),
+        Tuple(Vec<Self>),
+        /// Tuple variant of a generic enum wrapping a struct
+        Variant(&'static str, Box<Self>),
+        /// Color or math type accessed by field name, e.g. `.LinearRgba.red`
+        Typed(&'static str, &'static [(&'static str, usize)]),
+        Leaf,
+    }
+
+    /// Small deterministic xorshift generator, so failures are reproducible from the seed
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, bound: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            usize::try_from(self.0 % bound as u64).unwrap()
+        }
+
+        fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+            items[self.below(items.len())]
+        }
+    }
+
+    const FIELD_NAMES: &[&str] = &["config", "layers", "inner", "payload", "items", "state"];
+    const VARIANT_NAMES: &[&str] = &["Custom", "Polygon", "Active", "Weighted"];
+    const TYPED: &[(&str, &[(&str, usize)])] = &[
+        (
+            "LinearRgba",
+            &[("red", 0), ("green", 1), ("blue", 2), ("alpha", 3)],
+        ),
+        ("Hsla", &[("hue", 0), ("saturation", 1), ("lightness", 2)]),
+        ("Laba", &[("lightness", 0), ("a", 1), ("b", 2)]),
+        ("Vec3", &[("x", 0), ("y", 1), ("z", 2)]),
+    ];
+
+    fn generate_shape(rng: &mut Rng, depth: usize) -> Shape {
+        if depth == 0 {
+            return Shape::Leaf;
+        }
+        match rng.below(6) {
+            0 => Shape::Struct(
+                (0..=rng.below(3))
+                    .map(|_| (rng.pick(FIELD_NAMES), generate_shape(rng, depth - 1)))
+                    .collect(),
+            ),
+            1 => Shape::List(Box::new(generate_shape(rng, depth - 1))),
+            2 => Shape::Tuple(
+                (0..=rng.below(3))
+                    .map(|_| generate_shape(rng, depth - 1))
+                    .collect(),
+            ),
+            3 => Shape::Variant(
+                rng.pick(VARIANT_NAMES),
+                Box::new(Shape::Struct(vec![(
+                    rng.pick(FIELD_NAMES),
+                    generate_shape(rng, depth - 1),
+                )])),
+            ),
+            4 => {
+                let (name, fields) = rng.pick(TYPED);
+                Shape::Typed(name, fields)
+            }
+            _ => Shape::Leaf,
+        }
+    }
+
+    /// Walk from the root to a leaf, collecting the path as an agent would write it (with
+    /// variant and field names) and the path BRP expects
+    fn walk(
+        rng: &mut Rng,
+        shape: &Shape,
+        named: &mut Vec<PathSegment>,
+        expected: &mut Vec<PathSegment>,
+    ) {
+        let mut push_both = |segment: PathSegment| {
+            named.push(segment.clone());
+            expected.push(segment);
+        };
+        match shape {
+            Shape::Struct(fields) => {
+                let (name, field) = &fields[rng.below(fields.len())];
+                push_both(PathSegment::Field((*name).to_string()));
+                walk(rng, field, named, expected);
+            }
+            Shape::List(item) => {
+                push_both(PathSegment::ListIndex(rng.below(100)));
+                walk(rng, item, named, expected);
+            }
+            Shape::Tuple(items) => {
+                let index = rng.below(items.len());
+                push_both(PathSegment::TupleIndex(index));
+                walk(rng, &items[index], named, expected);
+            }
+            Shape::Variant(variant, inner) => {
+                named.push(PathSegment::Field((*variant).to_string()));
+                expected.push(PathSegment::TupleIndex(0));
+                walk(rng, inner, named, expected);
+            }
+            Shape::Typed(name, fields) => {
+                let (field, index) = rng.pick(fields);
+                named.push(PathSegment::Field((*name).to_string()));
+                named.push(PathSegment::Field(field.to_string()));
+                expected.extend([PathSegment::TupleIndex(0), PathSegment::TupleIndex(index)]);
+            }
+            Shape::Leaf => {}
+        }
+    }
+
+    #[test]
+    fn test_generated_paths_rewrite_to_brp_paths() {
+        let mut rng = Rng(0x5eed_1234_abcd_ef01);
+        for case in 0..2000 {
+            let shape = generate_shape(&mut rng, 2 + case % 8);
+            let (mut named_segments, mut expected_segments) = (Vec::new(), Vec::new());
+            walk(
+                &mut rng,
+                &shape,
+                &mut named_segments,
+                &mut expected_segments,
+            );
+            if named_segments.is_empty() {
+                continue;
+            }
+            let named = render_path(&named_segments);
+            let expected = render_path(&expected_segments);
+
+            // Every generated path round-trips through the parser
+            assert_eq!(parse_path(&named), Some(named_segments), "case {case}");
+            assert_eq!(
+                parse_path(&expected),
+                Some(expected_segments),
+                "case {case}"
+            );
+
+            // Paths using variant names are rewritten to the expected BRP path, and BRP
+            // paths are left alone
+            let rewritten = parse_generic_enum_field_access(&named);
+            if named == expected {
+                assert_eq!(rewritten, None, "case {case}: {named}");
+            } else {
+                assert_eq!(rewritten.as_ref(), Some(&expected), "case {case}: {named}");
+            }
+            assert_eq!(parse_generic_enum_field_access(&expected), None);
+        }
+    }
 }
@@ -2,6 +2,8 @@
 
 use serde_json::Value;
 
+use crate::brp_tools::request_handler::format_discovery::engine::FormatCorrection;
+
 /// Shared context that flows through all format discovery phases
 #[derive(Debug, Clone)]
 pub struct DiscoveryContext {
@@ -19,6 +21,12 @@ pub struct DiscoveryContext {
 
     /// The initial error that triggered discovery (if any)
     pub initial_error: Option<crate::brp_tools::support::brp_client::BrpError>,
+
+    /// Whether discovery must avoid mutating the live world (no test spawns)
+    pub dry_run: bool,
+
+    /// Corrections applied to the params before the first attempt
+    pub preflight_corrections: Vec<FormatCorrection>,
 }
 
 impl DiscoveryContext {
@@ -35,6 +43,8 @@ impl DiscoveryContext {
             port,
             debug_info: initial_debug_info,
             initial_error: None,
+            dry_run: false,
+            preflight_corrections: Vec::new(),
         }
     }
 
@@ -13,10 +13,11 @@ pub const fn is_type_format_error(error: &BrpError) -> bool {
 
 /// Check if the initial result needs format discovery
 pub fn needs_format_discovery<'a>(result: &'a BrpResult, method: &str) -> Option<&'a BrpError> {
-    if let BrpResult::Error(error) = result {
-        if FORMAT_DISCOVERY_METHODS.contains(&method) && is_type_format_error(error) {
-            return Some(error);
-        }
+    if let BrpResult::Error(error) = result
+        && FORMAT_DISCOVERY_METHODS.contains(&method)
+        && is_type_format_error(error)
+    {
+        return Some(error);
     }
     None
 }
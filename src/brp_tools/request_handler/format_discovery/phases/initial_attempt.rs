@@ -1,13 +1,102 @@
 //! Initial attempt phase for the format discovery engine
-//! This module handles the first attempt to execute a BRP method
+//! This module handles the first attempt to execute a BRP method, after rewriting values of
+//! known problematic types so common mistakes don't cost a failed round trip
+
+use serde_json::Value;
 
 use super::context::DiscoveryContext;
+use crate::brp_tools::constants::JSON_FIELD_PATH;
+use crate::brp_tools::request_handler::format_discovery::constants::FORMAT_DISCOVERY_METHODS;
+use crate::brp_tools::request_handler::format_discovery::engine::{
+    FormatCorrection, ParameterLocation,
+};
+use crate::brp_tools::request_handler::format_discovery::known_formats::Corrector;
+use crate::brp_tools::request_handler::format_discovery::schema_cache::cached_type_schema;
+use crate::brp_tools::request_handler::format_discovery::utilities::{
+    apply_corrections, extract_type_items, get_parameter_location,
+};
 use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::config;
 use crate::error::Result;
 
+/// Environment variable turning pre-flight corrections off when set to `0`, `false`, `no` or
+/// `off`
+pub const PREFLIGHT_ENV_VAR: &str = "BRP_MCP_PREFLIGHT_CORRECTIONS";
+
+/// Whether values are corrected before the first attempt
+///
+/// The environment variable wins over the `preflight_corrections` configuration key.
+pub fn preflight_enabled() -> bool {
+    std::env::var(PREFLIGHT_ENV_VAR).map_or_else(
+        |_| config::current().preflight_corrections.unwrap_or(true),
+        |value| !["0", "false", "no", "off"].contains(&value.trim().to_ascii_lowercase().as_str()),
+    )
+}
+
+/// Rewrite values of known problematic types in the params before anything is sent
+///
+/// Math types, colors and `Name` are rewritten from the built-in knowledge base, and fields of
+/// other types from their registry schema if it's already cached; nothing is fetched. The
+/// corrections are kept in the context so they're reported like discovered ones.
+pub fn apply_preflight_corrections(context: &mut DiscoveryContext) {
+    if !FORMAT_DISCOVERY_METHODS.contains(&context.method.as_str()) || !preflight_enabled() {
+        return;
+    }
+    let Some(params) = context.original_params.as_ref() else {
+        return;
+    };
+
+    let location = get_parameter_location(&context.method);
+    let path = params
+        .get(JSON_FIELD_PATH)
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let port = context.port;
+    let schema = |type_path: &str| cached_type_schema(type_path, port);
+
+    let mut corrected_items = Vec::new();
+    let mut corrections = Vec::new();
+    for (type_name, value) in extract_type_items(params, location) {
+        let corrector = Corrector::new(&schema);
+        // Mutations set the value at a path below the type
+        let value_type = match location {
+            ParameterLocation::Components | ParameterLocation::EventValue => {
+                Some(type_name.clone())
+            }
+            ParameterLocation::ComponentValue | ParameterLocation::ResourceValue => {
+                corrector.type_at_path(&type_name, path)
+            }
+        };
+        match value_type.and_then(|value_type| corrector.correct(&value_type, &value)) {
+            Some((corrected, hint)) => {
+                corrections.push(FormatCorrection {
+                    component:        type_name.clone(),
+                    original_format:  value,
+                    corrected_format: corrected.clone(),
+                    hint:             format!("Corrected before sending: {hint}"),
+                });
+                corrected_items.push((type_name, corrected));
+            }
+            None => corrected_items.push((type_name, value)),
+        }
+    }
+    if corrections.is_empty() {
+        return;
+    }
+
+    context.original_params = Some(apply_corrections(params, location, &corrected_items));
+    context.add_debug(format!(
+        "Format Discovery: Applied {} pre-flight corrections",
+        corrections.len()
+    ));
+    context.preflight_corrections = corrections;
+}
+
 /// Execute the initial BRP method attempt
 /// Returns the BRP result and updates the context with debug info
 pub async fn execute(context: &mut DiscoveryContext) -> Result<BrpResult> {
+    apply_preflight_corrections(context);
+
     context.add_debug(format!(
         "Format Discovery: FUNCTION CALLED! Executing method '{}' with params: {:?}",
         context.method, context.original_params
@@ -1,17 +1,24 @@
 //! Result building phase for the format discovery engine
 //! This module handles building the final enhanced BRP result
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use super::context::DiscoveryContext;
 use super::tier_execution::DiscoveryResultData;
+use crate::brp_tools::constants::JSON_FIELD_EXAMPLE_FORMATS;
 use crate::brp_tools::request_handler::format_discovery::detection::tier_info_to_debug_strings;
-use crate::brp_tools::request_handler::format_discovery::engine::EnhancedBrpResult;
+use crate::brp_tools::request_handler::format_discovery::engine::{
+    EnhancedBrpResult, FormatCorrection, FormatValidation,
+};
+use crate::brp_tools::request_handler::format_discovery::examples::{
+    example_value, fetch_referenced_schemas,
+};
 use crate::brp_tools::request_handler::format_discovery::utilities::{
-    apply_corrections, get_parameter_location,
+    apply_corrections, extract_type_items, get_parameter_location,
 };
 use crate::brp_tools::support::brp_client::{BrpError, BrpResult, execute_brp_method};
 use crate::error::Result;
+use crate::tools::{BRP_METHOD_MUTATE_COMPONENT, BRP_METHOD_MUTATE_RESOURCE};
 
 /// Builds the final enhanced BRP result with debug information
 pub async fn build_final_result(
@@ -26,17 +33,32 @@ pub async fn build_final_result(
     if discovery_data.format_corrections.is_empty() {
         context.add_debug("Format Discovery: No corrections were possible".to_string());
 
-        // Return the original error
-        let original_error = context.initial_error.clone().unwrap_or_else(|| BrpError {
+        // Return the original error, with examples of the values it expected
+        let mut original_error = context.initial_error.clone().unwrap_or_else(|| BrpError {
             code:    -1,
             message: "Unknown error".to_string(),
             data:    None,
         });
+        let examples = example_formats(context).await;
+        if !examples.is_empty()
+            && let Value::Object(data) = original_error
+                .data
+                .get_or_insert_with(|| Value::Object(Map::new()))
+        {
+            data.insert(
+                JSON_FIELD_EXAMPLE_FORMATS.to_string(),
+                Value::Object(examples),
+            );
+        }
 
         Ok(EnhancedBrpResult {
             result:             BrpResult::Error(original_error),
-            format_corrections: Vec::new(),
+            format_corrections: context.preflight_corrections.clone(),
             debug_info:         context.debug_info.clone(),
+            params_modified:    context
+                .preflight_corrections
+                .iter()
+                .any(FormatCorrection::changes_value),
         })
     } else {
         // Apply corrections and retry
@@ -54,14 +76,78 @@ pub async fn build_final_result(
 
         context.add_debug(format!("Format Discovery: Retry result: {result:?}"));
 
+        let format_corrections = with_preflight(context, discovery_data.format_corrections);
         Ok(EnhancedBrpResult {
             result,
-            format_corrections: discovery_data.format_corrections,
+            params_modified: format_corrections
+                .iter()
+                .any(FormatCorrection::changes_value),
+            format_corrections,
             debug_info: context.debug_info.clone(),
         })
     }
 }
 
+/// Builds a dry-run result with the corrected params, without executing the request
+pub fn build_dry_run_result(
+    context: &mut DiscoveryContext,
+    discovery_data: DiscoveryResultData,
+) -> Result<FormatValidation> {
+    context
+        .debug_info
+        .extend(tier_info_to_debug_strings(&discovery_data.all_tier_info));
+
+    let corrected_params = build_corrected_params(context, &discovery_data.corrected_items)?;
+    context.add_debug(format!(
+        "Format Discovery: Dry run found {} corrections, request not executed",
+        discovery_data.format_corrections.len()
+    ));
+
+    Ok(FormatValidation {
+        corrected_params,
+        format_corrections: with_preflight(context, discovery_data.format_corrections),
+        debug_info: context.debug_info.clone(),
+    })
+}
+
+/// Example values of the types in the params the app has registered, by type
+///
+/// Mutations set a field rather than the whole value, so an example of the type wouldn't fit.
+async fn example_formats(context: &DiscoveryContext) -> Map<String, Value> {
+    let mut examples = Map::new();
+    let Some(params) = context.original_params.as_ref() else {
+        return examples;
+    };
+    if [BRP_METHOD_MUTATE_COMPONENT, BRP_METHOD_MUTATE_RESOURCE].contains(&context.method.as_str())
+    {
+        return examples;
+    }
+    for (type_name, _) in extract_type_items(params, get_parameter_location(&context.method)) {
+        // The schemas are usually cached by the serialization check already
+        let Ok(schemas) = fetch_referenced_schemas(&type_name, context.port).await else {
+            continue;
+        };
+        let example = example_value(&type_name, &|type_path| schemas.get(type_path).cloned());
+        if !example.is_null() {
+            examples.insert(type_name, example);
+        }
+    }
+    examples
+}
+
+/// Pre-flight corrections followed by the ones discovery found
+fn with_preflight(
+    context: &DiscoveryContext,
+    format_corrections: Vec<FormatCorrection>,
+) -> Vec<FormatCorrection> {
+    context
+        .preflight_corrections
+        .iter()
+        .cloned()
+        .chain(format_corrections)
+        .collect()
+}
+
 /// Build corrected parameters from the discovered format corrections
 fn build_corrected_params(
     context: &DiscoveryContext,
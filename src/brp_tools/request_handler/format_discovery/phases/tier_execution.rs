@@ -5,7 +5,8 @@ use serde_json::{Map, Value};
 
 use super::context::DiscoveryContext;
 use crate::brp_tools::request_handler::format_discovery::constants::{
-    TIER_DETERMINISTIC, TIER_DIRECT_DISCOVERY, TIER_GENERIC_FALLBACK, TIER_SERIALIZATION,
+    TIER_DETERMINISTIC, TIER_DIRECT_DISCOVERY, TIER_GENERIC_FALLBACK, TIER_LEARNED,
+    TIER_SERIALIZATION,
 };
 use crate::brp_tools::request_handler::format_discovery::detection::{
     TierInfo, TierManager, analyze_error_pattern, check_type_serialization,
@@ -13,6 +14,7 @@ use crate::brp_tools::request_handler::format_discovery::detection::{
 use crate::brp_tools::request_handler::format_discovery::engine::{
     FormatCorrection, ParameterLocation,
 };
+use crate::brp_tools::request_handler::format_discovery::knowledge_base;
 use crate::brp_tools::request_handler::format_discovery::transformers::TransformerRegistry;
 use crate::brp_tools::request_handler::format_discovery::utilities::{
     extract_type_items, get_parameter_location,
@@ -50,6 +52,7 @@ pub async fn run_discovery_tiers(context: &mut DiscoveryContext) -> Result<Disco
         &context.method,
         context.port,
         &error,
+        context.dry_run,
         &mut context.debug_info,
     )
     .await?;
@@ -106,6 +109,7 @@ async fn process_type_items_for_corrections(
     method: &str,
     port: Option<u16>,
     original_error: &BrpError,
+    dry_run: bool,
     debug_info: &mut Vec<String>,
 ) -> Result<(Vec<FormatCorrection>, Vec<(String, Value)>, Vec<TierInfo>)> {
     let mut format_corrections = Vec::new();
@@ -120,6 +124,7 @@ async fn process_type_items_for_corrections(
             method,
             port,
             original_error,
+            dry_run,
             debug_info,
         )
         .await?;
@@ -153,6 +158,7 @@ async fn process_single_type_item(
     method: &str,
     port: Option<u16>,
     original_error: &BrpError,
+    dry_run: bool,
     debug_info: &mut Vec<String>,
 ) -> Result<(Option<(Value, String)>, Vec<TierInfo>)> {
     debug_info.push(format!(
@@ -172,8 +178,8 @@ async fn process_single_type_item(
             "Format Discovery: Found alternative for '{type_name}': {corrected_value:?}"
         ));
 
-        // For spawn, validate the format by testing; for insert, just trust it
-        let final_format = if method == BRP_METHOD_SPAWN {
+        // For spawn, validate the format by testing; for insert or a dry run, just trust it
+        let final_format = if method == BRP_METHOD_SPAWN && !dry_run {
             match test_component_format_with_spawn(type_name, &corrected_value, port).await {
                 Ok(validated_format) => validated_format,
                 Err(_) => return Ok((None, tier_info)), // Skip this type if validation fails
@@ -202,6 +208,18 @@ async fn tiered_type_format_discovery(
 ) -> (Option<(Value, String)>, Vec<TierInfo>) {
     let mut tier_manager = TierManager::new();
 
+    // ========== TIER 0: Learned Corrections ==========
+    tier_manager.start_tier(
+        TIER_LEARNED,
+        "Learned Corrections",
+        format!("Checking corrections learned earlier for type: {type_name}"),
+    );
+    if let Some(learned) = knowledge_base::lookup(type_name, original_value, port).await {
+        tier_manager.complete_tier(true, learned.1.clone());
+        return (Some(learned), tier_manager.into_vec());
+    }
+    tier_manager.complete_tier(false, "No learned correction matches".to_string());
+
     // ========== TIER 1: Serialization Diagnostics ==========
     let error_analysis = analyze_error_pattern(error);
     if method == BRP_METHOD_INSERT || method == BRP_METHOD_SPAWN {
@@ -305,20 +323,20 @@ async fn try_direct_discovery(
 
     if let Some(BrpResult::Success(Some(data))) = result {
         // Check for successful format discovery
-        if let Some(formats) = data.get("formats").and_then(|f| f.as_object()) {
-            if let Some(format_info) = formats.get(type_name) {
-                // Extract spawn_format and convert to corrected value
-                if let Some(spawn_format) = format_info
-                    .get("spawn_format")
-                    .and_then(|sf| sf.get("example"))
-                {
-                    tier_manager.complete_tier(
-                        true,
-                        format!("Direct discovery successful: found format for {type_name}"),
-                    );
-                    let hint = "Direct discovery from bevy_brp_extras".to_string();
-                    return Some((spawn_format.clone(), hint));
-                }
+        if let Some(formats) = data.get("formats").and_then(|f| f.as_object())
+            && let Some(format_info) = formats.get(type_name)
+        {
+            // Extract spawn_format and convert to corrected value
+            if let Some(spawn_format) = format_info
+                .get("spawn_format")
+                .and_then(|sf| sf.get("example"))
+            {
+                tier_manager.complete_tier(
+                    true,
+                    format!("Direct discovery successful: found format for {type_name}"),
+                );
+                let hint = "Direct discovery from bevy_brp_extras".to_string();
+                return Some((spawn_format.clone(), hint));
             }
         }
     }
@@ -0,0 +1,286 @@
+//! Cache of `bevy/registry/schema` responses per app session
+//!
+//! Format discovery queries the registry schema for every failing type, which is slow on
+//! projects with large registries. Successful responses are cached per `AppInstance` for
+//! `SCHEMA_CACHE_TTL` and reused across tool calls, so an app relaunched or re-attached through
+//! this server gets a fresh cache; restarts of unmanaged apps are picked up when the entry
+//! expires or a schema request to the port fails.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::detection::extract_crate_name;
+use crate::brp_tools::support::app_instance::AppInstance;
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::support::component_filter;
+use crate::config;
+use crate::error::Result;
+use crate::tools::BRP_METHOD_REGISTRY_SCHEMA;
+
+/// How long a cached schema response stays valid
+const SCHEMA_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Global schema cache
+static SCHEMA_CACHE: LazyLock<Mutex<HashMap<SchemaCacheKey, CachedSchema>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Identifies one schema query against one app instance
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SchemaCacheKey {
+    app:    AppInstance,
+    /// Serialized query parameters
+    params: String,
+}
+
+impl SchemaCacheKey {
+    fn new(port: u16, params: &Value) -> Self {
+        Self {
+            app:    AppInstance::on_port(port),
+            params: params.to_string(),
+        }
+    }
+
+    fn same_app(&self, other: &Self) -> bool {
+        self.app.same_connection(&other.app)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedSchema {
+    fetched: Instant,
+    data:    Value,
+}
+
+/// Query the registry schema, answering from the cache when possible
+pub async fn registry_schema(params: Value, port: Option<u16>) -> Result<BrpResult> {
+    let key = SchemaCacheKey::new(port.unwrap_or_else(config::default_port), &params);
+    if let Some(mut data) = lookup(&key, Instant::now()) {
+        // The cached response may have been fetched by a call that lifted the component filter
+        component_filter::apply(BRP_METHOD_REGISTRY_SCHEMA, &mut data);
+        return Ok(BrpResult::Success(Some(data)));
+    }
+
+    let result = execute_brp_method(BRP_METHOD_REGISTRY_SCHEMA, Some(params), port).await;
+    match &result {
+        Ok(BrpResult::Success(Some(data))) => store(key, data.clone(), Instant::now()),
+        // The app may have gone away or restarted; don't trust anything cached for it
+        _ => invalidate(&key),
+    }
+    result
+}
+
+/// Look up the registry schema of a single type
+///
+/// Queries the schema of the type's crate (through the cache) and picks the type out of it.
+/// Returns `None` if the app doesn't have the type registered.
+pub async fn type_schema(type_name: &str, port: Option<u16>) -> Result<Option<Value>> {
+    let BrpResult::Success(Some(schema_data)) =
+        registry_schema(type_params(type_name), port).await?
+    else {
+        return Ok(None);
+    };
+    Ok(pick_type(&schema_data, type_name))
+}
+
+/// Look up the schema of a single type without querying the app
+///
+/// Only answers if the schema of the type's crate is already cached.
+pub fn cached_type_schema(type_name: &str, port: Option<u16>) -> Option<Value> {
+    let key = SchemaCacheKey::new(
+        port.unwrap_or_else(config::default_port),
+        &type_params(type_name),
+    );
+    let mut data = lookup(&key, Instant::now())?;
+    component_filter::apply(BRP_METHOD_REGISTRY_SCHEMA, &mut data);
+    pick_type(&data, type_name)
+}
+
+/// Types of the app on a port known from its cached schemas
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownTypes {
+    /// Type paths of every cached schema
+    pub types:      BTreeSet<String>,
+    /// Type paths of the types reflecting `Component`
+    pub components: BTreeSet<String>,
+    /// Type paths of the types reflecting `Resource`
+    pub resources:  BTreeSet<String>,
+    /// Crates whose schema is cached in full
+    pub crates:     BTreeSet<String>,
+    /// Whether the whole registry is cached
+    pub complete:   bool,
+}
+
+impl KnownTypes {
+    /// Whether the cache can tell if a type is registered
+    pub fn covers(&self, type_name: &str) -> bool {
+        self.complete || self.crates.contains(extract_crate_name(type_name))
+    }
+}
+
+/// The types known from the cached schemas of the app on a port, without querying it
+pub fn known_types(port: Option<u16>) -> KnownTypes {
+    let app = AppInstance::on_port(port.unwrap_or_else(config::default_port));
+    let now = Instant::now();
+    let mut known = KnownTypes::default();
+    let Ok(cache) = SCHEMA_CACHE.lock() else {
+        return known;
+    };
+    for (key, cached) in cache.iter() {
+        if key.app != app || now.duration_since(cached.fetched) >= SCHEMA_CACHE_TTL {
+            continue;
+        }
+        let params: Value = serde_json::from_str(&key.params).unwrap_or_default();
+        let list = |name: &str| {
+            params
+                .get(name)
+                .and_then(Value::as_array)
+                .map(|crates| crates.iter().filter_map(Value::as_str).map(String::from))
+        };
+        // Schemas filtered by type only list some of the types of their crates
+        let by_type = params.get("with_types").is_some() || params.get("without_types").is_some();
+        match (list("with_crates"), params.get("without_crates")) {
+            _ if by_type => {}
+            (Some(crates), _) => known.crates.extend(crates),
+            (None, None) => known.complete = true,
+            (None, Some(_)) => {}
+        }
+        let schemas: Vec<(&str, &Value)> = match &cached.data {
+            Value::Object(schemas) => schemas
+                .iter()
+                .map(|(type_path, schema)| (type_path.as_str(), schema))
+                .collect(),
+            Value::Array(schemas) => schemas
+                .iter()
+                .filter_map(|schema| Some((schema.get("typePath")?.as_str()?, schema)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (type_path, schema) in schemas {
+            known.types.insert(type_path.to_string());
+            let reflects = |name: &str| {
+                schema
+                    .get("reflectTypes")
+                    .and_then(Value::as_array)
+                    .is_some_and(|traits| traits.iter().any(|reflected| reflected == name))
+            };
+            if reflects("Component") {
+                known.components.insert(type_path.to_string());
+            }
+            if reflects("Resource") {
+                known.resources.insert(type_path.to_string());
+            }
+        }
+    }
+    drop(cache);
+    known
+}
+
+/// Registry schema query for the crate of a type
+fn type_params(type_name: &str) -> Value {
+    serde_json::json!({ "with_crates": [extract_crate_name(type_name)] })
+}
+
+fn pick_type(schema_data: &Value, type_name: &str) -> Option<Value> {
+    match schema_data {
+        Value::Object(schemas) => schemas.get(type_name).cloned(),
+        Value::Array(schemas) => schemas
+            .iter()
+            .find(|schema| schema.get("typePath").and_then(Value::as_str) == Some(type_name))
+            .cloned(),
+        _ => None,
+    }
+}
+
+fn lookup(key: &SchemaCacheKey, now: Instant) -> Option<Value> {
+    let cache = SCHEMA_CACHE.lock().ok()?;
+    cache
+        .get(key)
+        .filter(|cached| now.duration_since(cached.fetched) < SCHEMA_CACHE_TTL)
+        .map(|cached| cached.data.clone())
+}
+
+fn store(key: SchemaCacheKey, data: Value, now: Instant) {
+    if let Ok(mut cache) = SCHEMA_CACHE.lock() {
+        // Drop expired entries and entries for earlier instances of the same app
+        cache.retain(|existing, cached| {
+            now.duration_since(cached.fetched) < SCHEMA_CACHE_TTL
+                && !(existing.same_app(&key) && existing.app != key.app)
+        });
+        cache.insert(key, CachedSchema { fetched: now, data });
+    }
+}
+
+fn invalidate(key: &SchemaCacheKey) {
+    if let Ok(mut cache) = SCHEMA_CACHE.lock() {
+        cache.retain(|existing, _| !existing.same_app(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::brp_tools::constants::BRP_DEFAULT_HOST;
+
+    fn key(port: u16, instance: Option<u32>) -> SchemaCacheKey {
+        SchemaCacheKey {
+            app:    AppInstance {
+                host: BRP_DEFAULT_HOST.to_string(),
+                port,
+                instance,
+            },
+            params: json!({ "with_crates": ["my_game"] }).to_string(),
+        }
+    }
+
+    #[test]
+    fn test_entries_expire_and_follow_app_instance() {
+        // Each test uses its own ports since the cache is shared
+        let now = Instant::now();
+        store(key(50_001, Some(1)), json!({ "a": 1 }), now);
+        assert_eq!(lookup(&key(50_001, Some(1)), now), Some(json!({ "a": 1 })));
+        assert_eq!(lookup(&key(50_001, Some(1)), now + SCHEMA_CACHE_TTL), None);
+
+        // A new instance on the same port replaces the old one's entries
+        store(key(50_001, Some(2)), json!({ "b": 2 }), now);
+        assert_eq!(lookup(&key(50_001, Some(1)), now), None);
+        assert_eq!(lookup(&key(50_001, Some(2)), now), Some(json!({ "b": 2 })));
+    }
+
+    #[test]
+    fn test_known_types_cover_the_cached_crates() {
+        let schemas = json!({
+            "my_game::Health": { "reflectTypes": ["Component", "Default"] },
+            "my_game::Score": { "reflectTypes": ["Resource"] },
+        });
+        store(key(50_004, None), schemas, Instant::now());
+
+        let known = known_types(Some(50_004));
+        assert!(known.covers("my_game::Mana"));
+        assert!(!known.covers("bevy_ecs::name::Name"));
+        assert!(known.types.contains("my_game::Score"));
+        assert_eq!(
+            known.components.iter().collect::<Vec<_>>(),
+            ["my_game::Health"]
+        );
+        assert_eq!(
+            known.resources.iter().collect::<Vec<_>>(),
+            ["my_game::Score"]
+        );
+    }
+
+    #[test]
+    fn test_invalidate_drops_all_entries_for_the_app() {
+        let now = Instant::now();
+        store(key(50_002, None), json!({}), now);
+        store(key(50_003, None), json!({}), now);
+
+        invalidate(&key(50_002, None));
+        assert_eq!(lookup(&key(50_002, None), now), None);
+        assert!(lookup(&key(50_003, None), now).is_some());
+    }
+}
@@ -2,13 +2,15 @@
 
 #![allow(clippy::expect_used, clippy::unwrap_used, clippy::panic)]
 
-use serde_json::json;
+use serde_json::{Value, json};
 
 use super::constants::*;
 use super::detection::{ErrorPattern, analyze_error_pattern};
+use super::execute_brp_method_with_format_discovery;
 use super::phases::error_analysis::is_type_format_error;
 use super::transformers::TransformerRegistry;
-use crate::brp_tools::support::brp_client::BrpError;
+use crate::brp_tools::support::brp_client::{BrpError, BrpResult};
+use crate::brp_tools::support::mock_brp_server::MockBrpServer;
 
 #[test]
 fn test_analyze_error_pattern_tuple_struct_access() {
@@ -318,3 +320,116 @@ fn test_fix_access_error_integration_with_pattern_matching() {
     assert!(returned_value.is_object() || returned_value.is_array());
     assert!(hint.contains("tuple") || hint.contains("path") || hint.contains("extracted"));
 }
+
+#[tokio::test]
+async fn test_spawn_of_unserializable_type_reports_serialization_diagnostic() {
+    let server = MockBrpServer::start().await;
+    server
+        .fail(
+            "bevy/spawn",
+            COMPONENT_FORMAT_ERROR_CODE,
+            "Unknown component type: `game::Body`",
+        )
+        .respond(
+            "bevy/registry/schema",
+            json!({ "game::Body": { "typePath": "game::Body", "reflectTypes": ["Component"] } }),
+        );
+
+    let params = json!({ "components": { "game::Body": { "mass": 1.0 } } });
+    let result = execute_brp_method_with_format_discovery(
+        "bevy/spawn",
+        Some(params),
+        Some(server.port()),
+        Vec::new(),
+    )
+    .await
+    .unwrap();
+
+    // Nothing about the value can fix a missing trait, so the original error stands
+    assert!(matches!(result.result, BrpResult::Error(_)));
+    assert!(
+        result
+            .debug_info
+            .iter()
+            .any(|line| line.contains("Serialization Diagnostics")
+                && line.contains("cannot be used with BRP")),
+        "{:#?}",
+        result.debug_info
+    );
+}
+
+#[tokio::test]
+async fn test_resource_format_error_is_corrected_by_direct_discovery() {
+    let server = MockBrpServer::start().await;
+    server
+        .fail(
+            "bevy/insert_resource",
+            RESOURCE_FORMAT_ERROR_CODE,
+            "invalid type: map, expected u32",
+        )
+        .respond("bevy/insert_resource", Value::Null)
+        .respond(
+            "brp_extras/discover_format",
+            json!({ "formats": { "game::Score": { "spawn_format": { "example": 42 } } } }),
+        );
+
+    let params = json!({ "resource": "game::Score", "value": { "points": 42 } });
+    let result = execute_brp_method_with_format_discovery(
+        "bevy/insert_resource",
+        Some(params),
+        Some(server.port()),
+        Vec::new(),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        matches!(result.result, BrpResult::Success(_)),
+        "{:?}",
+        result.result
+    );
+    assert_eq!(result.format_corrections[0].corrected_format, json!(42));
+    let inserts = server.requests_for("bevy/insert_resource");
+    assert_eq!(inserts.len(), 2);
+    assert_eq!(inserts[1].params.as_ref().unwrap()["value"], json!(42));
+}
+
+#[tokio::test]
+async fn test_math_type_format_error_is_corrected_by_pattern_matching() {
+    let server = MockBrpServer::start().await;
+    server
+        .fail(
+            "bevy/mutate_component",
+            COMPONENT_FORMAT_ERROR_CODE,
+            "Vec3 expects array format, got a map",
+        )
+        .respond("bevy/mutate_component", Value::Null);
+
+    let params = json!({
+        "entity": 7,
+        "component": "game::Body",
+        "path": ".offset",
+        "value": { "x": 1.0, "y": 2.0, "z": 3.0 },
+    });
+    let result = execute_brp_method_with_format_discovery(
+        "bevy/mutate_component",
+        Some(params),
+        Some(server.port()),
+        Vec::new(),
+    )
+    .await
+    .unwrap();
+
+    assert!(
+        matches!(result.result, BrpResult::Success(_)),
+        "{:?}",
+        result.result
+    );
+    assert!(!result.format_corrections.is_empty());
+    let mutations = server.requests_for("bevy/mutate_component");
+    assert_eq!(mutations.len(), 2);
+    assert_eq!(
+        mutations[1].params.as_ref().unwrap()["value"],
+        json!([1.0, 2.0, 3.0])
+    );
+}
@@ -150,14 +150,14 @@ impl EnumVariantTransformer {
         match (expected, actual) {
             // Tuple variant vs struct variant
             ("tuple", "struct") => {
-                if let Value::Object(obj) = original_value {
-                    if let Some((variant_name, value)) = extract_single_field_value(obj) {
-                        let hint = format!(
-                            "`{type_name}` VariantTypeMismatch: Expected {expected} variant access to access a {actual} variant, \
+                if let Value::Object(obj) = original_value
+                    && let Some((variant_name, value)) = extract_single_field_value(obj)
+                {
+                    let hint = format!(
+                        "`{type_name}` VariantTypeMismatch: Expected {expected} variant access to access a {actual} variant, \
                                         converted '{variant_name}' to tuple variant format"
-                        );
-                        return Some((value.clone(), hint));
-                    }
+                    );
+                    return Some((value.clone(), hint));
                 }
             }
             // Struct variant vs tuple variant
@@ -214,11 +214,10 @@ impl EnumVariantTransformer {
             .is_some_and(|c| c.is_ascii_uppercase())
         {
             // Likely an enum variant name like "LinearRgba"
-            if let Value::Object(obj) = original_value {
-                if let Some(result) = Self::try_enum_variant_extraction(type_name, field_name, obj)
-                {
-                    return Some(result);
-                }
+            if let Value::Object(obj) = original_value
+                && let Some(result) = Self::try_enum_variant_extraction(type_name, field_name, obj)
+            {
+                return Some(result);
             }
         }
 
@@ -352,11 +351,11 @@ impl FormatTransformer for EnumVariantTransformer {
             // Look for missing field patterns
             if message.contains("MissingField") {
                 // Extract field name (this is a simple heuristic)
-                if let Some(field_start) = message.find('\'') {
-                    if let Some(field_end) = message[field_start + 1..].find('\'') {
-                        let field_name = &message[field_start + 1..field_start + 1 + field_end];
-                        return Self::handle_missing_field(&type_name, value, field_name);
-                    }
+                if let Some(field_start) = message.find('\'')
+                    && let Some(field_end) = message[field_start + 1..].find('\'')
+                {
+                    let field_name = &message[field_start + 1..field_start + 1 + field_end];
+                    return Self::handle_missing_field(&type_name, value, field_name);
                 }
             }
         }
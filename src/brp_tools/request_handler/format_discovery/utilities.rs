@@ -14,6 +14,9 @@ pub fn get_parameter_location(method: &str) -> ParameterLocation {
         crate::tools::BRP_METHOD_INSERT_RESOURCE | crate::tools::BRP_METHOD_MUTATE_RESOURCE => {
             ParameterLocation::ResourceValue
         }
+        crate::tools::BRP_METHOD_SEND_EVENT | crate::tools::BRP_METHOD_EXTRAS_SEND_EVENT => {
+            ParameterLocation::EventValue
+        }
         _ => ParameterLocation::Components,
     }
 }
@@ -56,6 +59,17 @@ pub fn extract_type_items(params: &Value, location: ParameterLocation) -> Vec<(S
                 Vec::new()
             }
         }
+        ParameterLocation::EventValue => {
+            // For send_event methods
+            if let (Some(event), Some(value)) = (
+                params.get("event").and_then(|e| e.as_str()),
+                params.get("value"),
+            ) {
+                vec![(event.to_string(), value.clone())]
+            } else {
+                Vec::new()
+            }
+        }
     }
 }
 
@@ -83,18 +97,18 @@ pub fn apply_corrections(
         }
         ParameterLocation::ComponentValue => {
             // For mutate_component method - update the value field
-            if let Some(params_obj) = corrected_params.as_object_mut() {
-                if let Some((_, corrected_value)) = corrected_items.first() {
-                    params_obj.insert("value".to_string(), corrected_value.clone());
-                }
+            if let Some(params_obj) = corrected_params.as_object_mut()
+                && let Some((_, corrected_value)) = corrected_items.first()
+            {
+                params_obj.insert("value".to_string(), corrected_value.clone());
             }
         }
-        ParameterLocation::ResourceValue => {
-            // For insert_resource/mutate_resource methods - update the value field
-            if let Some(params_obj) = corrected_params.as_object_mut() {
-                if let Some((_, corrected_value)) = corrected_items.first() {
-                    params_obj.insert("value".to_string(), corrected_value.clone());
-                }
+        ParameterLocation::ResourceValue | ParameterLocation::EventValue => {
+            // For insert_resource/mutate_resource/send_event methods - update the value field
+            if let Some(params_obj) = corrected_params.as_object_mut()
+                && let Some((_, corrected_value)) = corrected_items.first()
+            {
+                params_obj.insert("value".to_string(), corrected_value.clone());
             }
         }
     }
@@ -123,6 +137,31 @@ mod tests {
             get_parameter_location("bevy/spawn"),
             ParameterLocation::Components
         ));
+        assert!(matches!(
+            get_parameter_location("brp_extras/send_event"),
+            ParameterLocation::EventValue
+        ));
+    }
+
+    #[test]
+    fn test_event_value_is_extracted_and_corrected() {
+        let params = json!({
+            "event": "my_game::SpawnWave",
+            "value": {"size": "12"}
+        });
+
+        let items = extract_type_items(&params, ParameterLocation::EventValue);
+        assert_eq!(
+            items,
+            vec![("my_game::SpawnWave".to_string(), json!({"size": "12"}))]
+        );
+        let corrected = apply_corrections(
+            &params,
+            ParameterLocation::EventValue,
+            &[("my_game::SpawnWave".to_string(), json!({"size": 12}))],
+        );
+        assert_eq!(corrected["event"], "my_game::SpawnWave");
+        assert_eq!(corrected["value"], json!({"size": 12}));
     }
 
     #[test]
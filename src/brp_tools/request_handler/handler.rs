@@ -1,5 +1,4 @@
-use std::fs;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Instant;
 
 use rmcp::model::CallToolResult;
 use rmcp::service::RequestContext;
@@ -7,21 +6,36 @@ use rmcp::{Error as McpError, RoleServer};
 use serde_json::{Value, json};
 
 use super::config::{BrpHandlerConfig, FormatterContext};
-use super::format_discovery::{
-    EnhancedBrpResult, FormatCorrection, execute_brp_method_with_format_discovery,
-};
+use super::format_discovery::{EnhancedBrpResult, execute_brp_method_with_format_discovery};
+use super::pagination::{self, PageRequest};
 use super::traits::ExtractedParams;
+use super::type_names::{self, ResolvedNames};
+use super::{broadcast, entity_check, format_discovery, query_validation, verification};
 use crate::BrpMcpService;
 use crate::brp_tools::brp_set_debug_mode;
 use crate::brp_tools::constants::{
-    JSON_FIELD_DATA, JSON_FIELD_DEBUG_INFO, JSON_FIELD_FORMAT_CORRECTIONS,
-    JSON_FIELD_ORIGINAL_ERROR, JSON_FIELD_PORT, MAX_RESPONSE_TOKENS,
+    BRP_ERROR_CODE_INVALID_REQUEST, BRP_ERROR_CODE_NO_SUCH_ENTITY, JSON_FIELD_DEBUG_INFO,
+    JSON_FIELD_DELTA, JSON_FIELD_DELTA_TOKEN, JSON_FIELD_ENTITY, JSON_FIELD_ENTITY_CHECK,
+    JSON_FIELD_ORIGINAL_ERROR, JSON_FIELD_PORT, JSON_FIELD_QUERY_ISSUES, JSON_FIELD_TAGGED_NAME,
+    JSON_FIELD_TYPE_CANDIDATES, JSON_FIELD_VERIFICATION, PARAM_CANONICAL_ORDER,
+    PARAM_MCP_SPAWNED_ONLY, PARAM_PROJECTION, PARAM_VERIFY,
+};
+use crate::brp_tools::session_recording::{self, Exchange};
+use crate::brp_tools::support::brp_client::{self, BrpError, BrpResult};
+use crate::brp_tools::support::delta_tracker::compute_delta;
+use crate::brp_tools::support::destructive_ops::{self, Confirmation, DESTRUCTIVE_METHODS};
+use crate::brp_tools::support::projection::Projection;
+use crate::brp_tools::support::response_formatter::{
+    BrpMetadata, ResponseFormatter, correction_fields,
+};
+use crate::brp_tools::support::{
+    canonical_order, component_filter, inline_image, request_policy, spawn_tagging,
 };
-use crate::brp_tools::support::brp_client::{BrpError, BrpResult};
-use crate::brp_tools::support::response_formatter::{BrpMetadata, ResponseFormatter};
 use crate::error::{Error, report_to_mcp_error};
-
-const CHARS_PER_TOKEN: usize = 4;
+use crate::support::progress;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{BRP_METHOD_QUERY, BRP_METHOD_SPAWN};
 
 /// Result of parameter extraction from a request
 pub struct RequestParams {
@@ -81,12 +95,12 @@ fn extract_request_params(
             debug_info.push("Extracted value: [Hidden for security]".to_string());
         }
 
-        if let Some(components) = params.get("components") {
-            if let Some(obj) = components.as_object() {
-                debug_info.push(format!("Extracted components: {} types", obj.len()));
-                for key in obj.keys() {
-                    debug_info.push(format!("  - Component type: {key}"));
-                }
+        if let Some(components) = params.get("components")
+            && let Some(obj) = components.as_object()
+        {
+            debug_info.push(format!("Extracted components: {} types", obj.len()));
+            for key in obj.keys() {
+                debug_info.push(format!("  - Component type: {key}"));
             }
         }
     } else {
@@ -137,111 +151,22 @@ fn resolve_brp_method(
     Ok(resolved_method)
 }
 
-/// Check if response exceeds token limit and save to file if needed
-fn handle_large_response(
-    response_data: &Value,
-    method_name: &str,
-) -> Result<Option<Value>, McpError> {
-    let response_json = serde_json::to_string(response_data).map_err(|e| -> McpError {
-        report_to_mcp_error(
-            &error_stack::Report::new(Error::General("Failed to serialize response".to_string()))
-                .attach_printable(format!("Serialization error: {e}")),
-        )
-    })?;
-
-    let estimated_tokens = response_json.len() / CHARS_PER_TOKEN;
-
-    if estimated_tokens > MAX_RESPONSE_TOKENS {
-        // Generate timestamp for unique filename
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| -> McpError {
-                report_to_mcp_error(
-                    &error_stack::Report::new(Error::General(
-                        "Failed to get timestamp".to_string(),
-                    ))
-                    .attach_printable(format!("System time error: {e}")),
-                )
-            })?
-            .as_secs();
-
-        let sanitized_method = method_name.replace('/', "_");
-        let filename = format!("brp_response_{sanitized_method}_{timestamp}.json");
-        let filepath = std::env::temp_dir().join(&filename);
-
-        // Save response to file
-        fs::write(&filepath, &response_json).map_err(|e| -> McpError {
-            report_to_mcp_error(
-                &error_stack::Report::new(Error::FileOperation(format!(
-                    "Failed to write response to {}",
-                    filepath.display()
-                )))
-                .attach_printable(format!("IO error: {e}")),
-            )
-        })?;
-
-        // Return fallback response with file information
-        let fallback_response = json!({
-            "status": "success",
-            "message": format!("Response too large ({estimated_tokens} tokens). Saved to {}", filepath.display()),
-            "filepath": filepath.to_string_lossy(),
-            "instructions": "Use Read tool to examine, Grep to search, or jq commands to filter the data."
-        });
-
-        Ok(Some(fallback_response))
-    } else {
-        Ok(None)
-    }
-}
-
-/// Add only format corrections to response data (not debug info)
-fn add_format_corrections_only(response_data: &mut Value, format_corrections: &[FormatCorrection]) {
-    if format_corrections.is_empty() {
-        return;
-    }
-
-    let corrections_value = json!(
-        format_corrections
-            .iter()
-            .map(|correction| {
-                json!({
-                    "component": correction.component,
-                    "original_format": correction.original_format,
-                    "corrected_format": correction.corrected_format,
-                    "hint": correction.hint
-                })
-            })
-            .collect::<Vec<_>>()
-    );
-
-    // If response_data is an object, add fields
-    if let Value::Object(map) = response_data {
-        map.insert(JSON_FIELD_FORMAT_CORRECTIONS.to_string(), corrections_value);
-    } else {
-        // If not an object, wrap it
-        let wrapped = json!({
-            JSON_FIELD_DATA: response_data.clone(),
-            JSON_FIELD_FORMAT_CORRECTIONS: corrections_value
-        });
-        *response_data = wrapped;
-    }
-}
-
 /// Context for processing responses
 struct ResponseContext<'a> {
     metadata:          BrpMetadata,
     formatter_factory: &'a crate::brp_tools::support::response_formatter::ResponseFormatterFactory,
     formatter_context: FormatterContext,
+    /// Fields added to the response besides those extracted from the data
+    extra_fields:      Vec<(&'static str, Value)>,
 }
 
 /// Process a successful BRP response
 fn process_success_response(
     data: Option<Value>,
     enhanced_result: &EnhancedBrpResult,
-    method_name: &str,
     context: ResponseContext<'_>,
-) -> Result<CallToolResult, McpError> {
-    let mut response_data = data.unwrap_or(Value::Null);
+) -> CallToolResult {
+    let response_data = data.unwrap_or(Value::Null);
 
     // Extract debug info for BRP MCP debug info
     let brp_mcp_debug_info =
@@ -251,9 +176,6 @@ fn process_success_response(
             None
         };
 
-    // Add format corrections only (not debug info, as it will be handled separately)
-    add_format_corrections_only(&mut response_data, &enhanced_result.format_corrections);
-
     // Create new FormatterContext with BRP MCP debug info
     let new_formatter_context = FormatterContext {
         params: context.formatter_context.params.clone(),
@@ -263,16 +185,72 @@ fn process_success_response(
     // Create new formatter with updated context
     let updated_formatter = context.formatter_factory.create(new_formatter_context);
 
-    // Check if response is too large and use file fallback if needed
-    let final_data = handle_large_response(&response_data, method_name)?
-        .map_or(response_data, |fallback_response| fallback_response);
+    updated_formatter.format_success(&response_data, context.metadata, &context.extra_fields)
+}
 
-    Ok(updated_formatter.format_success(&final_data, context.metadata))
+/// Remove the `delta_token` argument so it is not forwarded to BRP
+fn take_delta_token(request: &mut rmcp::model::CallToolRequestParam) -> Option<String> {
+    request
+        .arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(JSON_FIELD_DELTA_TOKEN))
+        .and_then(|token| token.as_str().map(String::from))
+}
+
+/// Record a result for delta tracking, returning the delta response if the caller's token
+/// refers to an earlier result of the same request
+fn track_delta(
+    service: &BrpMcpService,
+    request_key: &str,
+    delta_token: Option<&str>,
+    data: &Value,
+    extra_fields: &mut Vec<(&'static str, Value)>,
+) -> Result<Option<CallToolResult>, McpError> {
+    let recorded = service
+        .deltas
+        .lock()
+        .map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(Error::MutexPoisoned(format!(
+                "delta tracker lock: {e}"
+            ))))
+        })?
+        .record(request_key, delta_token, data);
+
+    let Some(previous) = recorded.previous else {
+        extra_fields.push((JSON_FIELD_DELTA_TOKEN, json!(recorded.token)));
+        if let Some(reset) = recorded.reset {
+            extra_fields.push(("delta_reset", json!(reset)));
+        }
+        return Ok(None);
+    };
+
+    let delta = compute_delta(&previous, data);
+    let message = if delta.is_some() {
+        "Changes since the previous result"
+    } else {
+        "No changes since the previous result"
+    };
+    let response = ResponseBuilder::success()
+        .message(message)
+        .add_field(JSON_FIELD_DELTA, &delta)
+        .and_then(|builder| builder.add_field("unchanged", delta.is_none()))
+        .and_then(|builder| builder.add_field(JSON_FIELD_DELTA_TOKEN, &recorded.token))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to build delta response")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(Some(json_response_to_result(&response)))
 }
 
 /// Process an error BRP response
 fn process_error_response(
     mut error_info: BrpError,
+    method_name: &str,
     enhanced_result: &EnhancedBrpResult,
     formatter: &ResponseFormatter,
     metadata: &BrpMetadata,
@@ -293,10 +271,8 @@ fn process_error_response(
     }
 
     // Add debug info and format corrections to error data if present
-    if !enhanced_result.debug_info.is_empty()
-        || !enhanced_result.format_corrections.is_empty()
-        || has_enhanced
-    {
+    let correction_fields = correction_fields(method_name, enhanced_result);
+    if !enhanced_result.debug_info.is_empty() || !correction_fields.is_empty() || has_enhanced {
         let mut data_obj = error_info.data.unwrap_or_else(|| json!({}));
 
         if let Value::Object(map) = &mut data_obj {
@@ -316,24 +292,9 @@ fn process_error_response(
                 );
             }
 
-            // Add format corrections
-            if !enhanced_result.format_corrections.is_empty() {
-                let corrections = enhanced_result
-                    .format_corrections
-                    .iter()
-                    .map(|c| {
-                        json!({
-                            "component": c.component,
-                            "hint": c.hint,
-                            "original_format": c.original_format,
-                            "corrected_format": c.corrected_format
-                        })
-                    })
-                    .collect::<Vec<_>>();
-                map.insert(
-                    JSON_FIELD_FORMAT_CORRECTIONS.to_string(),
-                    json!(corrections),
-                );
+            // Add format corrections, reported at the top level like those of successes
+            for (field_name, value) in correction_fields {
+                map.insert(field_name.to_string(), value);
             }
         }
 
@@ -343,43 +304,370 @@ fn process_error_response(
     formatter.format_error(error_info, metadata)
 }
 
+/// Expand short type names in the parameters and check queries, returning an error response
+/// for parameters that can't be sent
+async fn check_params(
+    method_name: &str,
+    extracted: &mut ExtractedParams,
+    config: &BrpHandlerConfig,
+) -> Result<ResolvedNames, CallToolResult> {
+    let Some(params) = extracted.params.as_mut() else {
+        return Ok(ResolvedNames::default());
+    };
+    let resolved = type_names::resolve_type_names(method_name, params, extracted.port).await;
+    if !resolved.ambiguous.is_empty() {
+        let error = BrpError {
+            code:    BRP_ERROR_CODE_INVALID_REQUEST,
+            message: resolved.ambiguity_message(),
+            data:    Some(json!({ JSON_FIELD_TYPE_CANDIDATES: resolved.ambiguous })),
+        };
+        return Err(reject_params(error, method_name, extracted, config));
+    }
+
+    if method_name == BRP_METHOD_QUERY {
+        let known = format_discovery::known_types(Some(extracted.port));
+        let issues = query_validation::validate_query(params, &known);
+        if !issues.is_empty() {
+            let error = BrpError {
+                code:    BRP_ERROR_CODE_INVALID_REQUEST,
+                message: query_validation::issues_message(&issues),
+                data:    Some(json!({ JSON_FIELD_QUERY_ISSUES: issues })),
+            };
+            return Err(reject_params(error, method_name, extracted, config));
+        }
+    }
+    Ok(resolved)
+}
+
+/// Error response for parameters rejected before sending them
+fn reject_params(
+    error: BrpError,
+    method_name: &str,
+    extracted: &ExtractedParams,
+    config: &BrpHandlerConfig,
+) -> CallToolResult {
+    let formatter = config.formatter_factory.create(FormatterContext {
+        params:             extracted.params.clone(),
+        brp_mcp_debug_info: None,
+    });
+    let metadata = BrpMetadata::new(metadata_method(method_name, extracted), extracted.port);
+    formatter.format_error(error, &metadata)
+}
+
+/// Method named in response metadata, `brp_execute` for dynamic methods for special error
+/// formatting
+const fn metadata_method<'a>(method_name: &'a str, extracted: &ExtractedParams) -> &'a str {
+    if extracted.method.is_some() {
+        "brp_execute"
+    } else {
+        method_name
+    }
+}
+
+/// Key identifying a call, shared by identical calls
+///
+/// The host is part of it since the port alone does not identify the app, and so is whether the
+/// component filter was lifted, so deltas never compare filtered with unfiltered results.
+fn request_key(method_name: &str, extracted: &ExtractedParams) -> String {
+    format!(
+        "{method_name} {}:{} {}{}",
+        brp_client::current_host(),
+        extracted.port,
+        extracted.params.as_ref().unwrap_or(&Value::Null),
+        if component_filter::is_bypassed() {
+            " unfiltered"
+        } else {
+            ""
+        }
+    )
+}
+
+/// How result rows are selected, ordered and projected, taken from the tool arguments
+struct RowOptions {
+    /// Only keep entities spawned through this server
+    spawned_only:    bool,
+    /// Page of the rows to return
+    page:            Option<PageRequest>,
+    /// Sort rows and type lists into canonical order
+    canonical_order: bool,
+    /// Parts of the result to return
+    projection:      Option<Projection>,
+}
+
+impl RowOptions {
+    /// Delta tracking key for a call, keeping different pages and projections apart
+    fn delta_key(&self, request_key: String) -> String {
+        let key = pagination::delta_key(request_key, self.page.as_ref());
+        match &self.projection {
+            Some(projection) => format!("{key} projection={}", projection.key()),
+            None => key,
+        }
+    }
+}
+
+/// Remove the row selection arguments so they are not forwarded to BRP
+fn take_row_options(
+    request: &mut rmcp::model::CallToolRequestParam,
+    config: &BrpHandlerConfig,
+) -> Result<RowOptions, McpError> {
+    let mut take_bool = |enabled: bool, name: &str| {
+        enabled
+            .then(|| request.arguments.as_mut()?.remove(name)?.as_bool())
+            .flatten()
+    };
+    let spawned_only = take_bool(config.spawned_filter, PARAM_MCP_SPAWNED_ONLY).unwrap_or(false);
+    let canonical_order = take_bool(config.canonical_order, PARAM_CANONICAL_ORDER).unwrap_or(true);
+    let projection = config
+        .projection
+        .then(|| request.arguments.as_mut()?.remove(PARAM_PROJECTION))
+        .flatten()
+        .filter(|projection| !projection.is_null())
+        .map(|projection| Projection::parse(&projection))
+        .transpose()
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let page = config
+        .pagination
+        .then(|| pagination::take_page_request(request))
+        .transpose()?
+        .flatten();
+
+    Ok(RowOptions {
+        spawned_only,
+        page,
+        canonical_order,
+        projection,
+    })
+}
+
+/// Remove the `verify` argument so it is not forwarded to BRP
+fn take_verify(request: &mut rmcp::model::CallToolRequestParam, config: &BrpHandlerConfig) -> bool {
+    config.verify
+        && request
+            .arguments
+            .as_mut()
+            .and_then(|arguments| arguments.remove(PARAM_VERIFY))
+            .is_some_and(|verify| verify.as_bool() == Some(true))
+}
+
+/// Apply canonical ordering, the spawned-only filter, the projection and the requested page to
+/// result rows
+///
+/// Returns the page fields for the response.
+fn select_rows(
+    rows: Option<&mut Value>,
+    method_name: &str,
+    strip_name: Option<bool>,
+    options: &RowOptions,
+    request_key: &str,
+) -> Result<Vec<(&'static str, Value)>, McpError> {
+    let Some(rows) = rows else {
+        return Ok(Vec::new());
+    };
+    if options.canonical_order {
+        canonical_order::canonicalize(method_name, rows);
+    }
+    if let Some(strip_name) = strip_name {
+        spawn_tagging::retain_spawned_rows(rows, strip_name);
+    }
+    if let Some(projection) = &options.projection {
+        projection.project_result(method_name, rows);
+    }
+    options.page.as_ref().map_or_else(
+        || Ok(Vec::new()),
+        |page| pagination::apply_page(rows, page, request_key),
+    )
+}
+
+/// Read back the components format discovery corrected on the spawned or inserted entity
+async fn verify_entity(
+    method_name: &str,
+    extracted: &ExtractedParams,
+    data: Option<&Value>,
+    enhanced_result: &EnhancedBrpResult,
+) -> Option<Value> {
+    let entity = if method_name == BRP_METHOD_SPAWN {
+        data?.get(JSON_FIELD_ENTITY)?.as_u64()?
+    } else {
+        extracted
+            .params
+            .as_ref()?
+            .get(JSON_FIELD_ENTITY)?
+            .as_u64()?
+    };
+    Some(
+        verification::verify_corrections(
+            entity,
+            &enhanced_result.format_corrections,
+            extracted.port,
+        )
+        .await,
+    )
+}
+
+/// Preview a destructive method instead of running it, unless the call is confirmed
+async fn preview_destructive(
+    method_name: &str,
+    extracted: &ExtractedParams,
+    confirmation: &Confirmation,
+) -> Option<CallToolResult> {
+    if !DESTRUCTIVE_METHODS.contains(&method_name) {
+        return None;
+    }
+    let operation = request_key(method_name, extracted);
+    let reason = destructive_ops::preview_reason(&operation, confirmation)?;
+    let preview =
+        destructive_ops::preview(method_name, extracted.params.as_ref(), extracted.port).await;
+    Some(destructive_ops::preview_response(
+        &operation,
+        &format!("{method_name} on port {}", extracted.port),
+        &reason,
+        &preview,
+    ))
+}
+
+/// Add whether the requested entity exists, and the entities that may have been meant, to an
+/// entity-not-found error
+async fn with_entity_check(mut error_info: BrpError, extracted: &ExtractedParams) -> BrpError {
+    let entity = (error_info.code == BRP_ERROR_CODE_NO_SUCH_ENTITY)
+        .then(|| entity_check::requested_entity(extracted.params.as_ref()))
+        .flatten();
+    if let Some(entity) = entity {
+        let check =
+            entity_check::check_missing_entity(entity, extracted.params.as_ref(), extracted.port)
+                .await;
+        if let Value::Object(map) = error_info.data.get_or_insert_with(|| json!({})) {
+            map.insert(JSON_FIELD_ENTITY_CHECK.to_string(), check);
+        }
+    }
+    error_info
+}
+
+/// Call BRP with format discovery, recording the request if a session is being recorded
+async fn execute_and_record(
+    tool: &str,
+    method_name: &str,
+    extracted: &ExtractedParams,
+    debug_info: Vec<String>,
+) -> Result<EnhancedBrpResult, McpError> {
+    let started = Instant::now();
+    let enhanced_result = execute_brp_method_with_format_discovery(
+        method_name,
+        extracted.params.clone(),
+        Some(extracted.port),
+        debug_info,
+    )
+    .await
+    .map_err(|err| report_to_mcp_error(&err))?;
+    session_recording::record(
+        &Exchange {
+            tool,
+            method: method_name,
+            port: extracted.port,
+            params: extracted.params.as_ref(),
+            duration: started.elapsed(),
+            corrections: &enhanced_result.format_corrections,
+            result: &enhanced_result.result,
+        },
+        &brp_client::current_host(),
+    );
+    Ok(enhanced_result)
+}
+
 /// Unified handler for all BRP methods (both static and dynamic)
 pub async fn handle_brp_request(
-    _service: &BrpMcpService,
-    request: rmcp::model::CallToolRequestParam,
+    service: &BrpMcpService,
+    mut request: rmcp::model::CallToolRequestParam,
     _context: RequestContext<RoleServer>,
     config: &BrpHandlerConfig,
 ) -> Result<CallToolResult, McpError> {
-    // Create debug info and log the earliest entry point
-    let mut debug_info = Vec::new();
+    let ports = if config.broadcast {
+        broadcast::take_ports(&mut request)?
+    } else {
+        None
+    };
+    let inline = if config.inline_image {
+        inline_image::take_options(&mut request).map_err(|report| report_to_mcp_error(&report))?
+    } else {
+        None
+    };
+    let policy = config
+        .request_policy
+        .with_overrides(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let call = async {
+        match ports {
+            Some(ports) => broadcast::broadcast_request(service, request, config, ports).await,
+            None => handle_single_port_request(service, request, config).await,
+        }
+    };
+    // Slow requests, such as a large registry schema, send progress while they run
+    let result = progress::heartbeat(request_policy::scope(policy, Box::pin(call))).await?;
+    Ok(match inline {
+        Some(options) => inline_image::attach(result, &options).await,
+        None => result,
+    })
+}
+
+/// Debug info logging the raw MCP request, at the earliest possible point
+fn entry_debug_info(request: &rmcp::model::CallToolRequestParam) -> Vec<String> {
+    vec![
+        format!("MCP ENTRY - Tool: {}", request.name),
+        format!(
+            "MCP ENTRY - Raw arguments: {}",
+            serde_json::to_string(&request.arguments)
+                .unwrap_or_else(|_| "SERIALIZATION_ERROR".to_string())
+        ),
+    ]
+}
+
+/// Handle a request against a single port
+pub async fn handle_single_port_request(
+    service: &BrpMcpService,
+    mut request: rmcp::model::CallToolRequestParam,
+    config: &BrpHandlerConfig,
+) -> Result<CallToolResult, McpError> {
+    let mut debug_info = entry_debug_info(&request);
+
+    let delta_token = if config.delta_tracking {
+        take_delta_token(&mut request)
+    } else {
+        None
+    };
 
-    // Log raw MCP request at the earliest possible point
-    debug_info.push(format!("MCP ENTRY - Tool: {}", request.name));
-    debug_info.push(format!(
-        "MCP ENTRY - Raw arguments: {}",
-        serde_json::to_string(&request.arguments)
-            .unwrap_or_else(|_| "SERIALIZATION_ERROR".to_string())
-    ));
+    let row_options = take_row_options(&mut request, config)?;
+    let verify = take_verify(&mut request, config);
+    let confirmation = destructive_ops::take_confirmation(&mut request)
+        .map_err(|report| report_to_mcp_error(&report))?;
 
     // Extract all parameters from the request
     let params = extract_request_params(&request, config, &mut debug_info)?;
-    let extracted = params.extracted;
+    let mut extracted = params.extracted;
 
-    // Determine the actual method to call
+    // Determine the actual method to call, and check the parameters before sending them
     let method_name = resolve_brp_method(&extracted, config, &mut debug_info)?;
+    let resolved_names = match check_params(&method_name, &mut extracted, config).await {
+        Ok(resolved_names) => resolved_names,
+        Err(rejected) => return Ok(rejected),
+    };
+    if let Some(preview) = preview_destructive(&method_name, &extracted, &confirmation).await {
+        return Ok(preview);
+    }
+
+    // Tag entities spawned through this server, and restrict queries to them on request
+    let tagged_name = (method_name == BRP_METHOD_SPAWN && spawn_tagging::is_enabled())
+        .then(|| spawn_tagging::tag_spawn_params(extracted.params.get_or_insert_with(|| json!({}))))
+        .flatten();
+    let strip_name = row_options.spawned_only.then(|| {
+        spawn_tagging::restrict_query_params(extracted.params.get_or_insert_with(|| json!({})))
+    });
 
     // Add debug info about calling BRP
     debug_info.push("Calling BRP with validated parameters".to_string());
 
     // Call BRP using format discovery
-    let enhanced_result = execute_brp_method_with_format_discovery(
-        &method_name,
-        extracted.params.clone(),
-        Some(extracted.port),
-        debug_info,
-    )
-    .await
-    .map_err(|err| crate::error::report_to_mcp_error(&err))?;
+    let enhanced_result =
+        execute_and_record(&request.name, &method_name, &extracted, debug_info).await?;
 
     // Create formatter and metadata
     // Ensure port is included in params for extractors that need it
@@ -397,26 +685,54 @@ pub async fn handle_brp_request(
     };
     let formatter = config.formatter_factory.create(formatter_context.clone());
 
-    // Use "brp_execute" for dynamic methods for special error formatting
-    let metadata_method = if extracted.method.is_some() {
-        "brp_execute"
-    } else {
-        &method_name
-    };
-    let metadata = BrpMetadata::new(metadata_method, extracted.port);
+    let metadata = BrpMetadata::new(metadata_method(&method_name, &extracted), extracted.port);
 
     // Process response using ResponseFormatter, including format corrections if present
     match &enhanced_result.result {
         BrpResult::Success(data) => {
+            let mut data = data.clone();
+            let request_key = request_key(&method_name, &extracted);
+            let mut extra_fields = select_rows(
+                data.as_mut(),
+                &method_name,
+                strip_name,
+                &row_options,
+                &request_key,
+            )?;
+            extra_fields.extend(resolved_names.response_field());
+            extra_fields.extend(correction_fields(&method_name, &enhanced_result));
+            if let Some(name) = tagged_name {
+                extra_fields.push((JSON_FIELD_TAGGED_NAME, json!(name)));
+            }
+            if verify && !enhanced_result.format_corrections.is_empty() {
+                let verification =
+                    verify_entity(&method_name, &extracted, data.as_ref(), &enhanced_result).await;
+                extra_fields.extend(verification.map(|v| (JSON_FIELD_VERIFICATION, v)));
+            }
+            if config.delta_tracking {
+                let delta = track_delta(
+                    service,
+                    &row_options.delta_key(request_key),
+                    delta_token.as_deref(),
+                    data.as_ref().unwrap_or(&Value::Null),
+                    &mut extra_fields,
+                )?;
+                if let Some(delta) = delta {
+                    return Ok(delta);
+                }
+            }
+
             let context = ResponseContext {
                 metadata,
                 formatter_factory: &config.formatter_factory,
                 formatter_context,
+                extra_fields,
             };
-            process_success_response(data.clone(), &enhanced_result, &method_name, context)
+            Ok(process_success_response(data, &enhanced_result, context))
         }
         BrpResult::Error(error_info) => Ok(process_error_response(
-            error_info.clone(),
+            with_entity_check(error_info.clone(), &extracted).await,
+            &method_name,
             &enhanced_result,
             &formatter,
             &metadata,
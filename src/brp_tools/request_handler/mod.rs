@@ -1,16 +1,32 @@
 // Module organization
+mod broadcast;
 mod config;
 mod constants;
+mod entity_check;
 mod extractors;
 mod format_discovery;
 mod handler;
+mod pagination;
+mod query_validation;
 mod traits;
+mod type_names;
+mod verification;
 
 // Public exports
 pub use config::{BrpHandlerConfig, FormatterContext};
+pub use entity_check::check_missing_entity;
 pub use extractors::{
     BrpExecuteExtractor, EntityParamExtractor, PassthroughExtractor, RegistrySchemaParamExtractor,
     ResourceParamExtractor, SimplePortExtractor,
 };
+pub use format_discovery::{
+    EnhancedBrpResult, FormatCorrection, FormatValidation, KnownTypes, PathSegment,
+    corrections_log, example_value, execute_brp_method_with_format_discovery, extract_crate_name,
+    fetch_referenced_schemas, format_discovery_enabled, knowledge_base, known_types, parse_path,
+    preflight_enabled, registry_schema, render_path, strict_corrections_enabled, type_schema,
+    validate_format, value_at, value_at_mut,
+};
 pub use handler::handle_brp_request;
+pub use query_validation::{QueryIssue, validate_query};
 pub use traits::ParamExtractor;
+pub use type_names::resolve_type_names;
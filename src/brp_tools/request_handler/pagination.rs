@@ -0,0 +1,277 @@
+//! Pagination of `bevy/query` results
+//!
+//! `limit`, `offset`, `max_response_bytes` and `cursor` arguments select a page of the query
+//! rows, ordered by entity ID so that consecutive pages line up. Responses report the total row
+//! count and, while rows remain, a `next_cursor` to pass back for the next page. Cursors are
+//! tied to the query they came from and carry no server-side state.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use rmcp::Error as McpError;
+use rmcp::model::CallToolRequestParam;
+use serde_json::{Value, json};
+
+use crate::brp_tools::constants::{
+    JSON_FIELD_ENTITY, JSON_FIELD_NEXT_CURSOR, JSON_FIELD_TOTAL_COUNT, PARAM_CURSOR, PARAM_LIMIT,
+    PARAM_MAX_RESPONSE_BYTES, PARAM_OFFSET,
+};
+use crate::error::{Error, report_to_mcp_error};
+
+/// Requested page of a query result
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageRequest {
+    pub offset:             usize,
+    pub limit:              Option<usize>,
+    pub max_response_bytes: Option<usize>,
+    /// Cursor the offset came from, checked against the query once its key is known
+    cursor:                 Option<String>,
+}
+
+/// Delta tracking key for a call, keeping different pages of one query apart
+pub fn delta_key(request_key: String, page: Option<&PageRequest>) -> String {
+    match page {
+        Some(page) => format!(
+            "{request_key} offset={} limit={:?} max_response_bytes={:?}",
+            page.offset, page.limit, page.max_response_bytes
+        ),
+        None => request_key,
+    }
+}
+
+/// Remove the pagination arguments, returning the page if any of them was given
+pub fn take_page_request(
+    request: &mut CallToolRequestParam,
+) -> Result<Option<PageRequest>, McpError> {
+    let Some(arguments) = request.arguments.as_mut() else {
+        return Ok(None);
+    };
+    let mut take = |name: &str| arguments.remove(name).filter(|value| !value.is_null());
+    let (limit, offset, max_bytes, cursor) = (
+        take(PARAM_LIMIT),
+        take(PARAM_OFFSET),
+        take(PARAM_MAX_RESPONSE_BYTES),
+        take(PARAM_CURSOR),
+    );
+    if limit.is_none() && offset.is_none() && max_bytes.is_none() && cursor.is_none() {
+        return Ok(None);
+    }
+
+    let cursor = cursor
+        .map(|cursor| {
+            cursor
+                .as_str()
+                .map(String::from)
+                .ok_or_else(|| invalid(PARAM_CURSOR, "expected a string"))
+        })
+        .transpose()?;
+    if cursor.is_some() && offset.is_some() {
+        return Err(invalid(PARAM_CURSOR, "cannot be combined with offset"));
+    }
+    let offset = match &cursor {
+        Some(cursor) => decode_cursor(cursor)?.0,
+        None => offset
+            .map(|value| as_usize(&value, PARAM_OFFSET))
+            .transpose()?
+            .unwrap_or(0),
+    };
+
+    Ok(Some(PageRequest {
+        offset,
+        limit: limit
+            .map(|value| as_usize(&value, PARAM_LIMIT))
+            .transpose()?,
+        max_response_bytes: max_bytes
+            .map(|value| as_usize(&value, PARAM_MAX_RESPONSE_BYTES))
+            .transpose()?,
+        cursor,
+    }))
+}
+
+/// Cut the query rows down to the requested page, returning the page fields for the response
+///
+/// `query_key` identifies the query; cursors handed out for other queries are rejected.
+pub fn apply_page(
+    data: &mut Value,
+    page: &PageRequest,
+    query_key: &str,
+) -> Result<Vec<(&'static str, Value)>, McpError> {
+    let query_hash = hash_query(query_key);
+    if let Some(cursor) = &page.cursor
+        && decode_cursor(cursor)?.1 != query_hash
+    {
+        return Err(invalid(
+            PARAM_CURSOR,
+            "belongs to a different query; pass the same arguments as the first page",
+        ));
+    }
+
+    let Value::Array(rows) = data else {
+        return Ok(Vec::new());
+    };
+    rows.sort_by_key(|row| row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64));
+
+    let total = rows.len();
+    let mut page_rows: Vec<Value> = rows
+        .drain(..)
+        .skip(page.offset)
+        .take(page.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    let mut truncated = false;
+    if let Some(max_bytes) = page.max_response_bytes {
+        let fitting = rows_within_bytes(&page_rows, max_bytes);
+        truncated = fitting < page_rows.len();
+        page_rows.truncate(fitting);
+    }
+
+    let next_offset = page.offset + page_rows.len();
+    *rows = page_rows;
+
+    let mut fields = vec![
+        (JSON_FIELD_TOTAL_COUNT, json!(total)),
+        (PARAM_OFFSET, json!(page.offset)),
+        (
+            JSON_FIELD_NEXT_CURSOR,
+            (next_offset < total)
+                .then(|| encode_cursor(next_offset, query_hash))
+                .into(),
+        ),
+    ];
+    if truncated {
+        fields.push(("truncated_by_max_response_bytes", json!(true)));
+    }
+    Ok(fields)
+}
+
+/// Number of leading rows whose serialized array fits in `max_bytes`
+///
+/// At least one row is always kept so that paging makes progress.
+fn rows_within_bytes(rows: &[Value], max_bytes: usize) -> usize {
+    // Array brackets plus a comma between rows
+    let mut size = 2;
+    for (index, row) in rows.iter().enumerate() {
+        size += row.to_string().len() + usize::from(index > 0);
+        if size > max_bytes {
+            return index.max(1);
+        }
+    }
+    rows.len()
+}
+
+fn hash_query(query_key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    query_key.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn encode_cursor(offset: usize, query_hash: u64) -> String {
+    format!("{offset}.{query_hash:x}")
+}
+
+fn decode_cursor(cursor: &str) -> Result<(usize, u64), McpError> {
+    cursor
+        .split_once('.')
+        .and_then(|(offset, hash)| {
+            Some((offset.parse().ok()?, u64::from_str_radix(hash, 16).ok()?))
+        })
+        .ok_or_else(|| {
+            invalid(
+                PARAM_CURSOR,
+                format!("'{cursor}' is not a cursor from bevy_query"),
+            )
+        })
+}
+
+fn as_usize(value: &Value, name: &str) -> Result<usize, McpError> {
+    value
+        .as_u64()
+        .and_then(|value| usize::try_from(value).ok())
+        .ok_or_else(|| invalid(name, format!("{value} is not a non-negative integer")))
+}
+
+fn invalid(name: &str, details: impl std::fmt::Display) -> McpError {
+    report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+        &format!("parameter '{name}'"),
+        details,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::Map;
+
+    use super::*;
+
+    fn request(arguments: Value) -> CallToolRequestParam {
+        let Value::Object(arguments) = arguments else {
+            return CallToolRequestParam {
+                name:      "bevy_query".into(),
+                arguments: Some(Map::new()),
+            };
+        };
+        CallToolRequestParam {
+            name:      "bevy_query".into(),
+            arguments: Some(arguments),
+        }
+    }
+
+    fn rows(count: u64) -> Value {
+        json!(
+            (0..count)
+                .rev()
+                .map(|entity| json!({ "entity": entity, "components": {} }))
+                .collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn test_pages_follow_cursor_until_exhausted() {
+        let mut first = request(json!({ "data": {}, "limit": 2 }));
+        let page = take_page_request(&mut first).unwrap().unwrap();
+        assert_eq!(first.arguments.unwrap().len(), 1);
+
+        let mut data = rows(5);
+        let fields: Map<String, Value> = apply_page(&mut data, &page, "query")
+            .unwrap()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        assert_eq!(data[0]["entity"], 0);
+        assert_eq!(data.as_array().unwrap().len(), 2);
+        assert_eq!(fields[JSON_FIELD_TOTAL_COUNT], 5);
+
+        let cursor = fields[JSON_FIELD_NEXT_CURSOR].clone();
+        let mut next = request(json!({ "limit": 10, "cursor": cursor }));
+        let page = take_page_request(&mut next).unwrap().unwrap();
+        assert_eq!(page.offset, 2);
+
+        let mut data = rows(5);
+        let fields = apply_page(&mut data, &page, "query").unwrap();
+        assert_eq!(data.as_array().unwrap().len(), 3);
+        assert!(
+            fields
+                .iter()
+                .any(|(name, value)| *name == JSON_FIELD_NEXT_CURSOR && value.is_null())
+        );
+
+        // A cursor from another query is rejected
+        assert!(apply_page(&mut rows(5), &page, "other query").is_err());
+    }
+
+    #[test]
+    fn test_max_response_bytes_keeps_at_least_one_row() {
+        let data = rows(10);
+        let all = data.as_array().unwrap();
+        let one_row = all[0].to_string().len();
+
+        assert_eq!(rows_within_bytes(all, 1), 1);
+        assert_eq!(rows_within_bytes(all, 2 + one_row * 2 + 1), 2);
+        assert_eq!(rows_within_bytes(all, usize::MAX), 10);
+        assert_eq!(
+            take_page_request(&mut request(json!({ "data": {} }))).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! Checks of `bevy/query` parameters before they are sent
+//!
+//! BRP answers a malformed `data` or `filter` object with a deserialization error that doesn't
+//! say which part is wrong, and ignores unknown component types unless `strict` is set, so a
+//! typo silently matches nothing. The query is checked for its structure, for combinations no
+//! entity can match, and, where the app's registry schema is already cached, for type names the
+//! app doesn't know, suggesting the names closest to them. Nothing is fetched from the app.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::format_discovery::KnownTypes;
+use crate::brp_tools::constants::{JSON_FIELD_COMPONENTS, PARAM_DATA, PARAM_FILTER};
+use crate::support::fuzzy;
+
+/// Component types whose values are returned if the entity has them
+const FIELD_OPTION: &str = "option";
+
+/// Component types reported as present or not
+const FIELD_HAS: &str = "has";
+
+/// Component types an entity must have
+const FIELD_WITH: &str = "with";
+
+/// Component types an entity must not have
+const FIELD_WITHOUT: &str = "without";
+
+/// `option` value selecting every component of the entity
+const OPTION_ALL: &str = "all";
+
+/// Fields of `data`
+const DATA_FIELDS: [&str; 3] = [JSON_FIELD_COMPONENTS, FIELD_OPTION, FIELD_HAS];
+
+/// Fields of `filter`
+const FILTER_FIELDS: [&str; 2] = [FIELD_WITH, FIELD_WITHOUT];
+
+/// A problem found in a query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryIssue {
+    /// Location of the problem, e.g. `data.components[1]`
+    pub field:       String,
+    pub message:     String,
+    /// Names that were probably meant
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+impl QueryIssue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field:       field.into(),
+            message:     message.into(),
+            suggestions: Vec::new(),
+        }
+    }
+}
+
+/// Type names of one list of a query, in order
+#[derive(Default)]
+struct TypeList {
+    names: Vec<String>,
+}
+
+impl TypeList {
+    fn set(&self) -> BTreeSet<&str> {
+        self.names.iter().map(String::as_str).collect()
+    }
+}
+
+/// Problems of `bevy/query` parameters, empty if the query can be sent
+pub fn validate_query(params: &Value, known: &KnownTypes) -> Vec<QueryIssue> {
+    let mut issues = Vec::new();
+    let Value::Object(params) = params else {
+        issues.push(QueryIssue::new(
+            "params",
+            "must be an object with `data` and `filter`",
+        ));
+        return issues;
+    };
+
+    let data = section(
+        params.get(PARAM_DATA),
+        PARAM_DATA,
+        &DATA_FIELDS,
+        &mut issues,
+    );
+    let filter = section(
+        params.get(PARAM_FILTER),
+        PARAM_FILTER,
+        &FILTER_FIELDS,
+        &mut issues,
+    );
+
+    let components = type_list(data, PARAM_DATA, JSON_FIELD_COMPONENTS, &mut issues);
+    let option = type_list(data, PARAM_DATA, FIELD_OPTION, &mut issues);
+    let has = type_list(data, PARAM_DATA, FIELD_HAS, &mut issues);
+    let with = type_list(filter, PARAM_FILTER, FIELD_WITH, &mut issues);
+    let without = type_list(filter, PARAM_FILTER, FIELD_WITHOUT, &mut issues);
+
+    let excluded = without.set();
+    let required = [
+        (PARAM_DATA, JSON_FIELD_COMPONENTS, &components),
+        (PARAM_FILTER, FIELD_WITH, &with),
+    ];
+    for (section, field, list) in required {
+        for name in list.set().intersection(&excluded) {
+            issues.push(QueryIssue::new(
+                format!("{section}.{field}"),
+                format!(
+                    "`{name}` is also in filter.without, so no entity can match; remove it from one of them"
+                ),
+            ));
+        }
+    }
+    for name in components.set().intersection(&option.set()) {
+        issues.push(QueryIssue::new(
+            format!("{PARAM_DATA}.{FIELD_OPTION}"),
+            format!("`{name}` is also in data.components; list it in only one of them"),
+        ));
+    }
+
+    let lists = [
+        (PARAM_DATA, JSON_FIELD_COMPONENTS, &components),
+        (PARAM_DATA, FIELD_OPTION, &option),
+        (PARAM_DATA, FIELD_HAS, &has),
+        (PARAM_FILTER, FIELD_WITH, &with),
+        (PARAM_FILTER, FIELD_WITHOUT, &without),
+    ];
+    for (section, field, list) in lists {
+        for (index, name) in list.names.iter().enumerate() {
+            let location = format!("{section}.{field}[{index}]");
+            issues.extend(check_type_name(name, location, known));
+        }
+    }
+    issues
+}
+
+/// The fields of `data` or `filter`, reporting a value that isn't an object and unknown fields
+fn section<'a>(
+    value: Option<&'a Value>,
+    name: &str,
+    fields: &[&str],
+    issues: &mut Vec<QueryIssue>,
+) -> Option<&'a serde_json::Map<String, Value>> {
+    match value {
+        None | Some(Value::Null) => None,
+        Some(Value::Object(map)) => {
+            for key in map.keys().filter(|key| !fields.contains(&key.as_str())) {
+                let mut issue = QueryIssue::new(
+                    format!("{name}.{key}"),
+                    format!("unknown field; {name} accepts {}", fields.join(", ")),
+                );
+                issue.suggestions = fuzzy::suggestions(key, fields.iter().copied());
+                issues.push(issue);
+            }
+            Some(map)
+        }
+        Some(other) => {
+            issues.push(QueryIssue::new(
+                name,
+                format!("must be an object with {}, not {other}", fields.join(", ")),
+            ));
+            None
+        }
+    }
+}
+
+/// The type names of a list field, reporting values that aren't lists of type names
+fn type_list(
+    section: Option<&serde_json::Map<String, Value>>,
+    section_name: &str,
+    field: &str,
+    issues: &mut Vec<QueryIssue>,
+) -> TypeList {
+    let mut list = TypeList::default();
+    let location = format!("{section_name}.{field}");
+    match section.and_then(|section| section.get(field)) {
+        None | Some(Value::Null) => {}
+        Some(Value::String(all)) if field == FIELD_OPTION && all == OPTION_ALL => {}
+        Some(Value::String(name)) => {
+            let mut issue = QueryIssue::new(location, "must be a list of type names");
+            issue.suggestions = vec![format!("[\"{name}\"]")];
+            issues.push(issue);
+        }
+        Some(Value::Array(items)) => {
+            for (index, item) in items.iter().enumerate() {
+                match item.as_str().map(str::trim) {
+                    Some(name) if !name.is_empty() && !name.contains(char::is_whitespace) => {
+                        list.names.push(name.to_string());
+                    }
+                    _ => issues.push(QueryIssue::new(
+                        format!("{location}[{index}]"),
+                        format!("{item} is not a type name"),
+                    )),
+                }
+            }
+        }
+        Some(other) => issues.push(QueryIssue::new(
+            location,
+            format!("must be a list of type names, not {other}"),
+        )),
+    }
+    list
+}
+
+/// Problem with a type name, judged by the cached schema
+///
+/// A name is unknown if the schema of its crate is cached without it. Names of crates that
+/// aren't cached are only reported when a cached component is close to them, which catches
+/// short and misspelled crate names.
+fn check_type_name(name: &str, location: String, known: &KnownTypes) -> Option<QueryIssue> {
+    if known.types.contains(name) {
+        return (!known.components.contains(name)).then(|| {
+            QueryIssue::new(
+                location,
+                format!("`{name}` is registered but is not a component, so it can't be queried"),
+            )
+        });
+    }
+    let suggestions = fuzzy::suggestions(name, known.components.iter().map(String::as_str));
+    (known.covers(name) || !suggestions.is_empty()).then(|| QueryIssue {
+        field: location,
+        message: format!("`{name}` is not a registered type"),
+        suggestions,
+    })
+}
+
+/// Message summarizing the problems of a query
+pub fn issues_message(issues: &[QueryIssue]) -> String {
+    let details = issues
+        .iter()
+        .map(|issue| {
+            let mut line = format!("{}: {}", issue.field, issue.message);
+            if !issue.suggestions.is_empty() {
+                let _ = write!(line, " (did you mean {}?)", issue.suggestions.join(" or "));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    format!("Invalid query, not sent to the app: {details}")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn known() -> KnownTypes {
+        let types = [
+            "bevy_transform::components::transform::Transform",
+            "bevy_ecs::name::Name",
+            "bevy_time::time::Time",
+        ];
+        KnownTypes {
+            types:      types.iter().map(ToString::to_string).collect(),
+            components: types[..2].iter().map(ToString::to_string).collect(),
+            resources:  BTreeSet::new(),
+            crates:     BTreeSet::from(["bevy_ecs".to_string()]),
+            complete:   false,
+        }
+    }
+
+    #[test]
+    fn test_queries_are_checked_for_structure_combinations_and_names() {
+        let valid = json!({
+            "data": { "components": ["bevy_ecs::name::Name"], "option": "all" },
+            "filter": { "with": ["my_game::Player"] },
+        });
+        assert_eq!(validate_query(&valid, &known()), []);
+
+        let query = json!({
+            "data": { "component": [], "has": "bevy_ecs::name::Name" },
+            "filter": { "with": ["bevy_ecs::name::Name", 3], "without": ["bevy_ecs::name::Name"] },
+        });
+        let fields: Vec<String> = validate_query(&query, &known())
+            .into_iter()
+            .map(|issue| issue.field)
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                "data.component",
+                "data.has",
+                "filter.with[1]",
+                "filter.with"
+            ]
+        );
+
+        let names = json!({
+            "data": { "components": ["bevy_ecs::name::Nmae", "bevy_time::time::Time"] },
+            "filter": { "without": ["bevy_transform::Transfrom"] },
+        });
+        let issues = validate_query(&names, &known());
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0].suggestions, ["bevy_ecs::name::Name"]);
+        assert!(issues[1].message.contains("not a component"));
+        assert_eq!(
+            issues[2].suggestions,
+            ["bevy_transform::components::transform::Transform"]
+        );
+        assert!(issues_message(&issues).contains("did you mean bevy_ecs::name::Name?"));
+    }
+}
@@ -0,0 +1,257 @@
+//! Expansion of short component and resource type names
+//!
+//! BRP only accepts fully-qualified type paths, but callers usually know a type by its name
+//! alone: `Transform` rather than `bevy_transform::components::transform::Transform`. Type names
+//! in the parameters of the component, resource and query methods that aren't registered as
+//! given are looked up by their trailing path segments among the registered components or
+//! resources of the app. A name matching exactly one type is replaced by its full path; a name
+//! matching several fails the call with the candidates. The registry comes from the schema
+//! cache, which is filled with the whole registry the first time a name without a path is seen.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::format_discovery::{KnownTypes, known_types, registry_schema};
+use crate::brp_tools::constants::{
+    JSON_FIELD_COMPONENT, JSON_FIELD_COMPONENTS, JSON_FIELD_RESOLVED_TYPE_NAMES,
+    JSON_FIELD_RESOURCE, PARAM_DATA, PARAM_FILTER,
+};
+use crate::tools::{
+    BRP_METHOD_GET, BRP_METHOD_GET_RESOURCE, BRP_METHOD_INSERT, BRP_METHOD_INSERT_RESOURCE,
+    BRP_METHOD_MUTATE_COMPONENT, BRP_METHOD_MUTATE_RESOURCE, BRP_METHOD_QUERY, BRP_METHOD_REMOVE,
+    BRP_METHOD_REMOVE_RESOURCE, BRP_METHOD_SPAWN,
+};
+
+/// Fields of a query's `data` and `filter` holding lists of component types
+const QUERY_LISTS: [(&str, &str); 5] = [
+    (PARAM_DATA, JSON_FIELD_COMPONENTS),
+    (PARAM_DATA, "option"),
+    (PARAM_DATA, "has"),
+    (PARAM_FILTER, "with"),
+    (PARAM_FILTER, "without"),
+];
+
+/// What a type name has to be registered as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypeKind {
+    Component,
+    Resource,
+}
+
+/// Type names of a call that were expanded or couldn't be
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ResolvedNames {
+    /// Full type paths of the names that were expanded
+    pub expanded:  BTreeMap<String, String>,
+    /// Candidates of the names matching several types
+    pub ambiguous: BTreeMap<String, Vec<String>>,
+}
+
+impl ResolvedNames {
+    /// Response field listing the expanded names, if any were
+    pub fn response_field(&self) -> Option<(&'static str, Value)> {
+        (!self.expanded.is_empty()).then(|| (JSON_FIELD_RESOLVED_TYPE_NAMES, json!(self.expanded)))
+    }
+
+    /// Message listing the ambiguous names with their candidates
+    pub fn ambiguity_message(&self) -> String {
+        let names = self
+            .ambiguous
+            .iter()
+            .map(|(name, candidates)| format!("`{name}` could be {}", candidates.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ");
+        format!("Ambiguous type names, use the full type path: {names}")
+    }
+}
+
+/// Expand the short type names of a call's parameters, fetching the registry if needed
+pub async fn resolve_type_names(method: &str, params: &mut Value, port: u16) -> ResolvedNames {
+    let mut known = known_types(Some(port));
+    if !known.complete && has_short_name(method, params) {
+        // Errors leave the names as they are; the call then fails the way it would have
+        if registry_schema(json!({}), Some(port)).await.is_ok() {
+            known = known_types(Some(port));
+        }
+    }
+    resolve_params(method, params, &known)
+}
+
+/// Whether a parameter names a type without its module path
+fn has_short_name(method: &str, params: &mut Value) -> bool {
+    let mut found = false;
+    visit_type_names(method, params, &mut |name, _| {
+        found |= !name.contains("::");
+        None
+    });
+    found
+}
+
+/// Expand the type names of a call's parameters with the types known so far
+fn resolve_params(method: &str, params: &mut Value, known: &KnownTypes) -> ResolvedNames {
+    let mut resolved = ResolvedNames::default();
+    visit_type_names(method, params, &mut |name, kind| {
+        let registered = match kind {
+            TypeKind::Component => &known.components,
+            TypeKind::Resource => &known.resources,
+        };
+        if registered.contains(name) {
+            return None;
+        }
+        let suffix = format!("::{name}");
+        let candidates: Vec<&String> = registered
+            .iter()
+            .filter(|type_path| type_path.ends_with(&suffix))
+            .collect();
+        match candidates.as_slice() {
+            [] => None,
+            [type_path] => {
+                resolved
+                    .expanded
+                    .insert(name.to_string(), (*type_path).clone());
+                Some((*type_path).clone())
+            }
+            _ => {
+                resolved
+                    .ambiguous
+                    .insert(name.to_string(), candidates.into_iter().cloned().collect());
+                None
+            }
+        }
+    });
+    resolved
+}
+
+/// Call `replace` with every type name of a method's parameters, replacing the names it
+/// returns a replacement for
+fn visit_type_names(
+    method: &str,
+    params: &mut Value,
+    replace: &mut impl FnMut(&str, TypeKind) -> Option<String>,
+) {
+    let component = TypeKind::Component;
+    match method {
+        BRP_METHOD_GET | BRP_METHOD_REMOVE => {
+            replace_in_list(params.get_mut(JSON_FIELD_COMPONENTS), component, replace);
+        }
+        BRP_METHOD_INSERT | BRP_METHOD_SPAWN => {
+            if let Some(Value::Object(values)) = params.get_mut(JSON_FIELD_COMPONENTS) {
+                let renamed = std::mem::take(values)
+                    .into_iter()
+                    .map(|(name, value)| (replace(&name, component).unwrap_or(name), value))
+                    .collect();
+                *values = renamed;
+            }
+        }
+        BRP_METHOD_MUTATE_COMPONENT => {
+            if let Some(name) = params.get_mut(JSON_FIELD_COMPONENT) {
+                replace_name(name, component, replace);
+            }
+        }
+        BRP_METHOD_GET_RESOURCE
+        | BRP_METHOD_INSERT_RESOURCE
+        | BRP_METHOD_MUTATE_RESOURCE
+        | BRP_METHOD_REMOVE_RESOURCE => {
+            if let Some(name) = params.get_mut(JSON_FIELD_RESOURCE) {
+                replace_name(name, TypeKind::Resource, replace);
+            }
+        }
+        BRP_METHOD_QUERY => {
+            for (section, field) in QUERY_LISTS {
+                let list = params
+                    .get_mut(section)
+                    .and_then(|section| section.get_mut(field));
+                replace_in_list(list, component, replace);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn replace_in_list(
+    list: Option<&mut Value>,
+    kind: TypeKind,
+    replace: &mut impl FnMut(&str, TypeKind) -> Option<String>,
+) {
+    if let Some(names) = list.and_then(Value::as_array_mut) {
+        for name in names {
+            replace_name(name, kind, replace);
+        }
+    }
+}
+
+fn replace_name(
+    name: &mut Value,
+    kind: TypeKind,
+    replace: &mut impl FnMut(&str, TypeKind) -> Option<String>,
+) {
+    if let Value::String(name) = name
+        && let Some(type_path) = replace(name, kind)
+    {
+        *name = type_path;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> KnownTypes {
+        let set = |names: &[&str]| names.iter().map(ToString::to_string).collect();
+        KnownTypes {
+            types:      set(&[]),
+            components: set(&[
+                "bevy_transform::components::transform::Transform",
+                "bevy_ecs::name::Name",
+                "my_game::Name",
+            ]),
+            resources:  set(&["bevy_time::time::Time<()>", "bevy_time::fixed::Fixed"]),
+            crates:     set(&[]),
+            complete:   true,
+        }
+    }
+
+    #[test]
+    fn test_unique_short_names_expand_and_ambiguous_ones_list_candidates() {
+        let mut params = json!({
+            "components": { "Transform": {}, "my_game::Health": 3 },
+        });
+        let resolved = resolve_params(BRP_METHOD_INSERT, &mut params, &known());
+        assert_eq!(
+            params["components"]
+                .as_object()
+                .map(|values| values.keys().cloned().collect::<Vec<_>>()),
+            Some(vec![
+                "bevy_transform::components::transform::Transform".to_string(),
+                "my_game::Health".to_string()
+            ])
+        );
+        assert_eq!(resolved.expanded.len(), 1);
+
+        let mut query = json!({
+            "data": { "components": ["transform::Transform", "Name"] },
+            "filter": { "without": ["Transform"] },
+        });
+        let resolved = resolve_params(BRP_METHOD_QUERY, &mut query, &known());
+        assert_eq!(
+            query["filter"]["without"][0],
+            "bevy_transform::components::transform::Transform"
+        );
+        assert_eq!(query["data"]["components"][1], "Name");
+        assert_eq!(
+            resolved.ambiguous["Name"],
+            ["bevy_ecs::name::Name", "my_game::Name"]
+        );
+        assert!(resolved.ambiguity_message().contains("`Name` could be"));
+
+        let mut resource = json!({ "resource": "Time<()>" });
+        resolve_params(BRP_METHOD_GET_RESOURCE, &mut resource, &known());
+        assert_eq!(resource["resource"], "bevy_time::time::Time<()>");
+        assert!(has_short_name(
+            BRP_METHOD_GET_RESOURCE,
+            &mut json!({ "resource": "Time" })
+        ));
+    }
+}
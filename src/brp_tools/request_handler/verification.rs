@@ -0,0 +1,153 @@
+//! Read-back verification of corrected spawns and inserts
+//!
+//! A format correction that BRP accepts can still carry the wrong data, for example a vector
+//! whose fields ended up in the wrong order. With `verify`, every corrected component is read
+//! back with `bevy/get` and compared against the value the caller sent, allowing for the
+//! representation differences format discovery introduces (`{x, y, z}` objects versus arrays,
+//! newtype wrapping, float rounding).
+
+use serde_json::{Map, Value, json};
+
+use super::format_discovery::FormatCorrection;
+use crate::brp_tools::constants::{JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY};
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::tools::BRP_METHOD_GET;
+
+/// Relative tolerance for comparing floats that went through `f32`
+const FLOAT_TOLERANCE: f64 = 1e-5;
+
+/// Field names of vector types, in array order
+const VECTOR_FIELDS: [&str; 4] = ["x", "y", "z", "w"];
+
+/// Read the corrected components back from the entity and compare them with the intended values
+pub async fn verify_corrections(entity: u64, corrections: &[FormatCorrection], port: u16) -> Value {
+    let components: Vec<&str> = corrections
+        .iter()
+        .map(|correction| correction.component.as_str())
+        .collect();
+    let params = json!({
+        JSON_FIELD_ENTITY: entity,
+        JSON_FIELD_COMPONENTS: components,
+        "strict": false,
+    });
+    let values = match execute_brp_method(BRP_METHOD_GET, Some(params), Some(port)).await {
+        Ok(BrpResult::Success(Some(data))) => {
+            data.get(JSON_FIELD_COMPONENTS).cloned().unwrap_or(data)
+        }
+        Ok(BrpResult::Success(None)) => Value::Null,
+        Ok(BrpResult::Error(e)) => return unverified(&e.message),
+        Err(report) => return unverified(&report.to_string()),
+    };
+
+    let mut verified = Vec::new();
+    let mut mismatches = Vec::new();
+    for correction in corrections {
+        let actual = values.get(&correction.component);
+        if actual.is_some_and(|actual| semantically_equal(&correction.original_format, actual)) {
+            verified.push(json!(correction.component));
+        } else {
+            mismatches.push(json!({
+                "component": correction.component,
+                "intended": correction.original_format,
+                "actual": actual,
+            }));
+        }
+    }
+
+    json!({
+        "status": if mismatches.is_empty() { "verified" } else { "mismatch" },
+        "verified": verified,
+        "mismatches": mismatches,
+    })
+}
+
+fn unverified(error: &str) -> Value {
+    json!({ "status": "unverified", "error": format!("Failed to read back components: {error}") })
+}
+
+/// Whether a read-back value carries the same data as the intended one
+///
+/// Fields the caller left out (and that were filled with defaults) are not compared.
+fn semantically_equal(intended: &Value, actual: &Value) -> bool {
+    match (intended, actual) {
+        (Value::Number(intended), Value::Number(actual)) => {
+            match (intended.as_f64(), actual.as_f64()) {
+                (Some(a), Some(b)) => {
+                    (a - b).abs() <= FLOAT_TOLERANCE * a.abs().max(b.abs()).max(1.0)
+                }
+                _ => intended == actual,
+            }
+        }
+        (Value::Object(intended), Value::Object(actual)) => intended.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .is_some_and(|actual| semantically_equal(value, actual))
+        }),
+        (Value::Object(fields), Value::Array(items)) if vector_fields(fields).is_some() => {
+            vector_fields(fields)
+                .is_some_and(|fields| elements_equal(&fields, &items.iter().collect::<Vec<_>>()))
+        }
+        (Value::Array(items), Value::Object(fields)) if vector_fields(fields).is_some() => {
+            vector_fields(fields)
+                .is_some_and(|fields| elements_equal(&items.iter().collect::<Vec<_>>(), &fields))
+        }
+        (Value::Array(intended), Value::Array(actual)) => elements_equal(
+            &intended.iter().collect::<Vec<_>>(),
+            &actual.iter().collect::<Vec<_>>(),
+        ),
+        // Newtypes may be written bare or as a one-element tuple
+        (intended, Value::Array(actual)) if actual.len() == 1 => {
+            semantically_equal(intended, &actual[0])
+        }
+        (Value::Array(intended), actual) if intended.len() == 1 => {
+            semantically_equal(&intended[0], actual)
+        }
+        _ => intended == actual,
+    }
+}
+
+fn elements_equal(intended: &[&Value], actual: &[&Value]) -> bool {
+    intended.len() == actual.len()
+        && intended
+            .iter()
+            .zip(actual)
+            .all(|(intended, actual)| semantically_equal(intended, actual))
+}
+
+/// The values of a vector written as an object (`{x, y}` up to `{x, y, z, w}`), in array order
+fn vector_fields(fields: &Map<String, Value>) -> Option<Vec<&Value>> {
+    let names = VECTOR_FIELDS.get(..fields.len())?;
+    names.iter().map(|name| fields.get(*name)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_representation_differences_are_equal() {
+        // Object vectors against arrays, with f32 rounding
+        assert!(semantically_equal(
+            &json!({ "translation": { "x": 0.1, "y": 2.0, "z": 3.0 } }),
+            &json!({ "translation": [0.100_000_001_490_116_12, 2.0, 3.0], "scale": [1.0, 1.0, 1.0] })
+        ));
+        // Newtypes with and without tuple wrapping
+        assert!(semantically_equal(&json!(5), &json!([5])));
+        assert!(semantically_equal(&json!(["Player"]), &json!("Player")));
+    }
+
+    #[test]
+    fn test_wrong_data_is_a_mismatch() {
+        // Fields written in the wrong order
+        assert!(!semantically_equal(
+            &json!({ "x": 1.0, "y": 2.0, "z": 3.0 }),
+            &json!([3.0, 2.0, 1.0])
+        ));
+        assert!(!semantically_equal(
+            &json!({ "speed": 2 }),
+            &json!({ "velocity": 2 })
+        ));
+        assert!(!semantically_equal(&json!([1, 2]), &json!([1, 2, 3])));
+        assert!(!semantically_equal(&json!("Idle"), &json!("Moving")));
+    }
+}
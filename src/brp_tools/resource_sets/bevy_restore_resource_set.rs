@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{PARAM_NAME, ResourceSet};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PATH, JSON_FIELD_PORT,
+    JSON_FIELD_RESOURCES, JSON_FIELD_STATUS,
+};
+use crate::brp_tools::snapshot::validate_name;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_RESTORE_RESOURCE_SET, TOOL_BEVY_RESTORE_RESOURCE_SET};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_RESTORE_RESOURCE_SET.into(),
+        description:  DESC_BEVY_RESTORE_RESOURCE_SET.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_NAME,
+                "Name the set was saved under with bevy_save_resource_set",
+                false,
+            )
+            .add_string_property(
+                JSON_FIELD_PATH,
+                "File the set was saved to, instead of a name",
+                false,
+            )
+            .add_string_array_property(
+                JSON_FIELD_RESOURCES,
+                "Fully-qualified resource types to restore (default: every resource in the set)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let name = arguments.get(PARAM_NAME).and_then(Value::as_str);
+    if let Some(name) = name {
+        validate_name(name).map_err(|report| report_to_mcp_error(&report))?;
+    }
+    let path = arguments
+        .get(JSON_FIELD_PATH)
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    let only = params::extract_optional_string_array(&arguments, JSON_FIELD_RESOURCES);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let set =
+        ResourceSet::load(name, path.as_deref()).map_err(|report| report_to_mcp_error(&report))?;
+    let results = set.restore(only.as_deref(), port).await;
+
+    let restored = results
+        .iter()
+        .filter(|result| result[JSON_FIELD_STATUS] == "success")
+        .count();
+    let failed = results.len() - restored;
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Restored {restored} of {} resources from resource set '{}', {failed} failed",
+            results.len(),
+            set.name
+        ))
+        .data(json!({
+            PARAM_NAME: set.name,
+            "created_at": set.created_at,
+            "results": results,
+            "restored": restored,
+            "failed": failed,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
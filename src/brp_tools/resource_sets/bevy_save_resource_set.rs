@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{PARAM_NAME, ResourceSet};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PATH, JSON_FIELD_PORT,
+    JSON_FIELD_RESOURCES,
+};
+use crate::brp_tools::snapshot::validate_name;
+use crate::error::{Error, ErrorCode, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_SAVE_RESOURCE_SET, TOOL_BEVY_SAVE_RESOURCE_SET};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SAVE_RESOURCE_SET.into(),
+        description:  DESC_BEVY_SAVE_RESOURCE_SET.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_NAME,
+                "Name to save the set under (letters, digits, '-', '_', '.')",
+                true,
+            )
+            .add_string_array_property(
+                JSON_FIELD_RESOURCES,
+                "Resource types to save, fully-qualified or short names",
+                true,
+            )
+            .add_string_property(
+                JSON_FIELD_PATH,
+                "File to save the set to (default: <name>.json in the resource_sets folder of the state directory)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let name = params::extract_required_string(&request, PARAM_NAME)?.to_string();
+    validate_name(&name).map_err(|report| report_to_mcp_error(&report))?;
+    let resources =
+        params::extract_optional_string_array_from_request(&request, JSON_FIELD_RESOURCES)?
+            .unwrap_or_default();
+    if resources.is_empty() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{JSON_FIELD_RESOURCES}'"),
+                "list at least one resource type",
+            ),
+        )));
+    }
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let path = arguments
+        .get(JSON_FIELD_PATH)
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let (set, failures) = ResourceSet::capture(name, &resources, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let response = if set.resources.is_empty() {
+        ResponseBuilder::error()
+            .message(format!(
+                "None of the resources could be read, resource set '{}' was not saved",
+                set.name
+            ))
+            .error_code(ErrorCode::BrpError)
+            .details(json!({ "failed": failures }))
+            .build()
+    } else {
+        let path = set
+            .save(path.as_deref())
+            .map_err(|report| report_to_mcp_error(&report))?;
+        ResponseBuilder::success()
+            .message(format!(
+                "Saved {} of {} resources as resource set '{}'",
+                set.resources.len(),
+                resources.len(),
+                set.name
+            ))
+            .data(json!({
+                PARAM_NAME: set.name,
+                JSON_FIELD_PATH: path.display().to_string(),
+                "saved": set.resources.keys().collect::<Vec<_>>(),
+                "failed": failures,
+            }))
+            .map_or_else(
+                |_| {
+                    ResponseBuilder::error()
+                        .message("Failed to serialize response data")
+                        .build()
+                },
+                ResponseBuilder::build,
+            )
+    };
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,273 @@
+//! Saved sets of resource values
+//!
+//! A resource set records the values of a list of resources at one point in time in a JSON file,
+//! by default in the state directory, so settings or game state changed while debugging can be
+//! put back in one call, in this session or after a restart. Values are inserted again with
+//! format discovery, so a set saved before a type's serialization changed can still be restored.
+
+pub mod bevy_restore_resource_set;
+pub mod bevy_save_resource_set;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::constants::{
+    JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_RESOURCE, JSON_FIELD_STATUS, JSON_FIELD_VALUE,
+};
+use super::request_handler::{execute_brp_method_with_format_discovery, resolve_type_names};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::response_formatter::corrections_json;
+use crate::error::{Error, Result};
+use crate::state::StateDir;
+use crate::tools::{BRP_METHOD_GET_RESOURCE, BRP_METHOD_INSERT_RESOURCE};
+
+/// Subdirectory of the state directory holding saved resource sets
+const RESOURCE_SETS_DIR_NAME: &str = "resource_sets";
+
+/// Parameter naming a resource set
+pub const PARAM_NAME: &str = "name";
+
+/// Values of some resources at one point in time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceSet {
+    pub name:       String,
+    pub created_at: String,
+    /// Port of the app the values were read from
+    pub port:       u16,
+    /// Values by fully-qualified resource type
+    pub resources:  BTreeMap<String, Value>,
+}
+
+/// A resource that couldn't be read or restored, with the reason
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResourceFailure {
+    pub resource: String,
+    pub error:    String,
+}
+
+impl ResourceSet {
+    /// Read the values of resources from the app
+    ///
+    /// Short type names are expanded as in `bevy_get_resource`. Resources that can't be read are
+    /// left out of the set and returned as failures.
+    pub async fn capture(
+        name: String,
+        resources: &[String],
+        port: u16,
+    ) -> Result<(Self, Vec<ResourceFailure>)> {
+        let mut values = BTreeMap::new();
+        let mut failures = Vec::new();
+        for resource in resources {
+            let mut params = json!({ JSON_FIELD_RESOURCE: resource });
+            let resolved = resolve_type_names(BRP_METHOD_GET_RESOURCE, &mut params, port).await;
+            if !resolved.ambiguous.is_empty() {
+                failures.push(ResourceFailure {
+                    resource: resource.clone(),
+                    error:    resolved.ambiguity_message(),
+                });
+                continue;
+            }
+            let type_path = params[JSON_FIELD_RESOURCE]
+                .as_str()
+                .unwrap_or(resource)
+                .to_string();
+
+            match execute_brp_method(BRP_METHOD_GET_RESOURCE, Some(params), Some(port)).await? {
+                BrpResult::Success(data) => {
+                    let value = data.map_or(Value::Null, |data| {
+                        data.get(JSON_FIELD_VALUE).cloned().unwrap_or(data)
+                    });
+                    values.insert(type_path, value);
+                }
+                BrpResult::Error(e) => failures.push(ResourceFailure {
+                    resource: type_path,
+                    error:    e.message,
+                }),
+            }
+        }
+
+        let set = Self {
+            name,
+            created_at: chrono::Local::now().to_rfc3339(),
+            port,
+            resources: values,
+        };
+        Ok((set, failures))
+    }
+
+    /// File a set is saved to by default, or `None` when persistence is disabled
+    fn default_path(name: &str) -> Option<PathBuf> {
+        StateDir::global().path().map(|dir| {
+            dir.join(RESOURCE_SETS_DIR_NAME)
+                .join(format!("{name}.json"))
+        })
+    }
+
+    /// Save the set to a file, or to the state directory, returning the file written
+    pub fn save(&self, path: Option<&Path>) -> Result<PathBuf> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| Self::default_path(&self.name))
+            .ok_or_else(|| {
+                error_stack::Report::new(Error::invalid(
+                    "parameter 'path'",
+                    "persistent state is disabled (BRP_MCP_STATE_DIR=off), give a path to save to",
+                ))
+            })?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                error_stack::Report::new(Error::io_failed("create resource set directory", dir, &e))
+            })?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(|e| {
+            error_stack::Report::new(Error::FileOperation(format!(
+                "Failed to serialize resource set '{}': {e}",
+                self.name
+            )))
+        })?;
+        std::fs::write(&path, contents).map_err(|e| {
+            error_stack::Report::new(Error::io_failed("write resource set", &path, &e))
+        })?;
+        Ok(path)
+    }
+
+    /// Load a set from a file, or by name from the state directory
+    pub fn load(name: Option<&str>, path: Option<&Path>) -> Result<Self> {
+        let path = match (path, name) {
+            (Some(path), _) => path.to_path_buf(),
+            (None, Some(name)) => Self::default_path(name)
+                .filter(|path| path.exists())
+                .ok_or_else(|| {
+                    error_stack::Report::new(Error::invalid(
+                        &format!("parameter '{PARAM_NAME}'"),
+                        format!("no resource set named '{name}' was saved"),
+                    ))
+                })?,
+            (None, None) => {
+                return Err(error_stack::Report::new(Error::invalid(
+                    "parameters",
+                    format!("give the '{PARAM_NAME}' or the 'path' of the resource set"),
+                )));
+            }
+        };
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            error_stack::Report::new(Error::io_failed("read resource set", &path, &e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| {
+            error_stack::Report::new(Error::FileOperation(format!(
+                "Failed to parse resource set {}: {e}",
+                path.display()
+            )))
+        })
+    }
+
+    /// Insert the saved values into the app, or only those of some resources
+    ///
+    /// Returns one result per resource, in type path order.
+    pub async fn restore(&self, only: Option<&[String]>, port: u16) -> Vec<Value> {
+        let mut results = Vec::new();
+        for (resource, value) in &self.resources {
+            if only.is_some_and(|only| !only.contains(resource)) {
+                continue;
+            }
+            let params = json!({ JSON_FIELD_RESOURCE: resource, JSON_FIELD_VALUE: value });
+            let outcome = execute_brp_method_with_format_discovery(
+                BRP_METHOD_INSERT_RESOURCE,
+                Some(params),
+                Some(port),
+                Vec::new(),
+            )
+            .await;
+
+            let mut result = json!({ JSON_FIELD_RESOURCE: resource });
+            match outcome {
+                Ok(enhanced) => {
+                    match enhanced.result {
+                        BrpResult::Success(_) => result[JSON_FIELD_STATUS] = json!("success"),
+                        BrpResult::Error(e) => {
+                            result[JSON_FIELD_STATUS] = json!("error");
+                            result["error"] = json!(e.message);
+                        }
+                    }
+                    if !enhanced.format_corrections.is_empty() {
+                        result[JSON_FIELD_FORMAT_CORRECTIONS] =
+                            corrections_json(&enhanced.format_corrections);
+                    }
+                }
+                Err(report) => {
+                    result[JSON_FIELD_STATUS] = json!("error");
+                    result["error"] = json!(report.to_string());
+                }
+            }
+            results.push(result);
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    const DEBUG_OVERLAY: &str = "my_game::debug::DebugOverlay";
+    const MISSING: &str = "my_game::settings::Missing";
+
+    #[tokio::test]
+    async fn test_set_is_saved_and_restored() {
+        let server = MockBrpServer::start().await;
+        server
+            .fail(BRP_METHOD_GET_RESOURCE, -23402, "Unknown resource type")
+            .respond(
+                BRP_METHOD_GET_RESOURCE,
+                json!({ "value": { "enabled": true } }),
+            );
+        let (set, failures) = ResourceSet::capture(
+            "debug".to_string(),
+            &[MISSING.to_string(), DEBUG_OVERLAY.to_string()],
+            server.port(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            failures,
+            vec![ResourceFailure {
+                resource: MISSING.to_string(),
+                error:    "Unknown resource type".to_string(),
+            }]
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!(
+                "bevy_brp_mcp_test_{}",
+                uuid::Uuid::new_v4().simple()
+            ))
+            .join("debug.json");
+        assert_eq!(set.save(Some(&path)).unwrap(), path);
+        let loaded = ResourceSet::load(None, Some(&path)).unwrap();
+        assert_eq!(loaded, set);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+
+        server.respond(BRP_METHOD_INSERT_RESOURCE, Value::Null);
+        let results = loaded.restore(None, server.port()).await;
+        assert_eq!(
+            results,
+            vec![json!({ "resource": DEBUG_OVERLAY, "status": "success" })]
+        );
+        assert_eq!(
+            server.requests_for(BRP_METHOD_INSERT_RESOURCE)[0].params,
+            Some(json!({ "resource": DEBUG_OVERLAY, "value": { "enabled": true } }))
+        );
+        assert!(
+            loaded
+                .restore(Some(&[MISSING.to_string()]), server.port())
+                .await
+                .is_empty()
+        );
+    }
+}
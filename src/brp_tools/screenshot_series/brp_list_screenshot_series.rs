@@ -0,0 +1,48 @@
+//! List the screenshot series of the session
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+
+use super::SCREENSHOT_SERIES;
+use crate::BrpMcpService;
+use crate::brp_tools::constants::JSON_FIELD_COUNT;
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_LIST_SCREENSHOT_SERIES, TOOL_BRP_LIST_SCREENSHOT_SERIES};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_LIST_SCREENSHOT_SERIES.into(),
+        description:  DESC_BRP_LIST_SCREENSHOT_SERIES.into(),
+        input_schema: schema::SchemaBuilder::new().build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    _request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    // Get the series and release lock immediately
+    let series = SCREENSHOT_SERIES.lock().await.list();
+
+    let response = ResponseBuilder::success()
+        .message(format!("Found {} screenshot series", series.len()))
+        .data(json!({
+            "series": series,
+            JSON_FIELD_COUNT: series.len(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
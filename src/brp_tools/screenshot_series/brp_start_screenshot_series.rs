@@ -0,0 +1,155 @@
+//! Start capturing a screenshot series
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{
+    DEFAULT_FRAME_COUNT, DEFAULT_INTERVAL_MS, FRAME_FORMATS, JSON_FIELD_SERIES_ID, MAX_FRAME_COUNT,
+    MIN_INTERVAL_MS, PARAM_DIRECTORY, PARAM_FORMAT, PARAM_INTERVAL_MS,
+};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COUNT, JSON_FIELD_HOST, JSON_FIELD_PORT,
+};
+use crate::brp_tools::support::capabilities;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{
+    BRP_METHOD_EXTRAS_SCREENSHOT, DESC_BRP_START_SCREENSHOT_SERIES,
+    TOOL_BRP_START_SCREENSHOT_SERIES,
+};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_START_SCREENSHOT_SERIES.into(),
+        description:  DESC_BRP_START_SCREENSHOT_SERIES.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_COUNT,
+                &format!(
+                    "Number of screenshots to capture, up to {MAX_FRAME_COUNT} (default: {DEFAULT_FRAME_COUNT})"
+                ),
+                false,
+            )
+            .add_number_property(
+                PARAM_INTERVAL_MS,
+                &format!(
+                    "Milliseconds between screenshots, at least {MIN_INTERVAL_MS} (default: {DEFAULT_INTERVAL_MS})"
+                ),
+                false,
+            )
+            .add_string_property(
+                PARAM_DIRECTORY,
+                "Absolute directory to save the screenshots and index.json to (default: a new directory under the log directory)",
+                false,
+            )
+            .add_string_property(
+                PARAM_FORMAT,
+                "Image format of the screenshots: 'png' or 'jpg' (default: png)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let count = params::extract_optional_u32(&request, JSON_FIELD_COUNT, DEFAULT_FRAME_COUNT)?;
+    let interval_ms =
+        params::extract_optional_number(&request, PARAM_INTERVAL_MS, DEFAULT_INTERVAL_MS)?;
+    let format = params::extract_optional_string(&request, PARAM_FORMAT, FRAME_FORMATS[0]);
+    let directory = params::extract_any_value(&request, PARAM_DIRECTORY)
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    let port = params::extract_optional_u16_from_request(&request, JSON_FIELD_PORT)?
+        .unwrap_or_else(config::default_port);
+
+    if !(1..=MAX_FRAME_COUNT).contains(&count) {
+        return Err(invalid(
+            JSON_FIELD_COUNT,
+            &format!("must be between 1 and {MAX_FRAME_COUNT}"),
+        ));
+    }
+    if interval_ms < MIN_INTERVAL_MS {
+        return Err(invalid(
+            PARAM_INTERVAL_MS,
+            &format!("must be at least {MIN_INTERVAL_MS}"),
+        ));
+    }
+    if !FRAME_FORMATS.contains(&format) {
+        return Err(invalid(
+            PARAM_FORMAT,
+            &format!("must be one of {}", FRAME_FORMATS.join(", ")),
+        ));
+    }
+    // Screenshot paths are resolved by the app, relative to its own working directory
+    if directory
+        .as_ref()
+        .is_some_and(|directory| !directory.is_absolute())
+    {
+        return Err(invalid(PARAM_DIRECTORY, "must be an absolute path"));
+    }
+
+    let methods = capabilities::supported_methods(port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    if capabilities::first_supported(&methods, &[BRP_METHOD_EXTRAS_SCREENSHOT]).is_none() {
+        let response = capabilities::unsupported_response(
+            "screenshots",
+            &[BRP_METHOD_EXTRAS_SCREENSHOT],
+            &methods,
+        );
+        return Ok(json_response_to_result(&response));
+    }
+
+    let series = super::start(
+        port,
+        count,
+        Duration::from_millis(interval_ms),
+        directory,
+        format,
+    )
+    .await
+    .map_err(|report| report_to_mcp_error(&report))?;
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Started screenshot series {}: {count} screenshots every {interval_ms}ms into {}",
+            series.series_id,
+            series.directory.display()
+        ))
+        .data(json!({
+            JSON_FIELD_SERIES_ID: series.series_id,
+            "series": series,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+fn invalid(field: &str, details: &str) -> McpError {
+    report_to_mcp_error(&error_stack::Report::new(Error::invalid(field, details)))
+}
@@ -0,0 +1,65 @@
+//! Stop a running screenshot series
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{JSON_FIELD_SERIES_ID, SCREENSHOT_SERIES};
+use crate::BrpMcpService;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BRP_STOP_SCREENSHOT_SERIES, TOOL_BRP_STOP_SCREENSHOT_SERIES};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_STOP_SCREENSHOT_SERIES.into(),
+        description:  DESC_BRP_STOP_SCREENSHOT_SERIES.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_SERIES_ID,
+                "The series ID returned from brp_start_screenshot_series",
+                true,
+            )
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let series_id = params::extract_required_u32(&arguments, JSON_FIELD_SERIES_ID, "series_id")?;
+
+    // Stop the series and release lock immediately
+    let stopped = SCREENSHOT_SERIES
+        .lock()
+        .await
+        .stop(series_id)
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Stopped screenshot series {series_id} after {} of {} screenshots",
+            stopped.captured + stopped.failures,
+            stopped.count
+        ))
+        .data(json!({
+            JSON_FIELD_SERIES_ID: series_id,
+            "series": stopped,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,370 @@
+//! Screenshot series: a timelapse captured through `brp_extras/screenshot`
+//!
+//! A series asks the app for a screenshot every interval until it has the requested number of
+//! frames or is stopped. Frames are saved as `frame_0001.png`, `frame_0002.png`, ... in the
+//! series directory, next to an `index.json` describing the series and every frame. The index is
+//! rewritten after each frame, so it is usable while the series is still running. Series last
+//! for the session.
+
+pub mod brp_list_screenshot_series;
+pub mod brp_start_screenshot_series;
+pub mod brp_stop_screenshot_series;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use super::constants::JSON_FIELD_PATH;
+use super::support::brp_client::{self, BrpResult, execute_brp_method};
+use crate::error::{Error, Result};
+use crate::log_tools::support::{LOG_PREFIX, create_log_directory};
+use crate::tools::BRP_METHOD_EXTRAS_SCREENSHOT;
+
+/// Global screenshot series instance
+pub static SCREENSHOT_SERIES: LazyLock<Arc<Mutex<SeriesManager>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(SeriesManager::new())));
+
+/// Field name of the series ID in tool parameters and responses
+pub const JSON_FIELD_SERIES_ID: &str = "series_id";
+
+/// Parameter giving the time between frames
+pub const PARAM_INTERVAL_MS: &str = "interval_ms";
+
+/// Parameter giving the directory frames are saved to
+pub const PARAM_DIRECTORY: &str = "directory";
+
+/// Parameter choosing the image format of the frames
+pub const PARAM_FORMAT: &str = "format";
+
+/// Frames captured when no count is given
+pub const DEFAULT_FRAME_COUNT: u32 = 10;
+
+/// Most frames one series may capture
+pub const MAX_FRAME_COUNT: u32 = 1000;
+
+/// Time between frames when no interval is given
+pub const DEFAULT_INTERVAL_MS: u64 = 1000;
+
+/// Shortest time between frames
+pub const MIN_INTERVAL_MS: u64 = 100;
+
+/// Image formats frames can be saved in, by file extension
+pub const FRAME_FORMATS: &[&str] = &["png", "jpg"];
+
+/// Name of the index file in the series directory
+const INDEX_FILE: &str = "index.json";
+
+/// Everything needed to capture a series
+#[derive(Debug, Clone)]
+struct SeriesSpec {
+    port:      u16,
+    count:     u32,
+    interval:  Duration,
+    /// Directory the frames and index are saved to, absolute
+    directory: PathBuf,
+    /// File extension of the frames, which decides their format
+    format:    String,
+}
+
+/// Where a series stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesStatus {
+    Running,
+    Completed,
+    Stopped,
+}
+
+/// Information about a screenshot series
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesInfo {
+    pub series_id:   u32,
+    pub port:        u16,
+    pub count:       u32,
+    pub interval_ms: u64,
+    pub directory:   PathBuf,
+    pub index_path:  PathBuf,
+    pub status:      SeriesStatus,
+    /// Frames the app accepted
+    pub captured:    u32,
+    /// Frames the app rejected or that failed to reach it
+    pub failures:    u32,
+    pub started:     String,
+    pub finished:    Option<String>,
+}
+
+/// One requested frame
+#[derive(Debug, Clone, Serialize)]
+pub struct Frame {
+    /// Position in the series, from 1
+    pub number:      u32,
+    pub path:        PathBuf,
+    pub captured_at: String,
+    pub success:     bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error:       Option<String>,
+}
+
+/// Contents of `index.json`
+#[derive(Debug, Clone, Serialize)]
+struct SeriesIndex<'a> {
+    #[serde(flatten)]
+    info:   &'a SeriesInfo,
+    frames: &'a [Frame],
+}
+
+/// A series with its frames and capture task
+struct Series {
+    info:   SeriesInfo,
+    frames: Vec<Frame>,
+    handle: JoinHandle<()>,
+}
+
+impl Series {
+    /// Rewrite the series' `index.json`
+    ///
+    /// The write is synchronous so that it finishes while the manager is locked: an index
+    /// written by a stop can't be overtaken by one from the aborted capture task.
+    fn write_index(&self) {
+        let index = SeriesIndex {
+            info:   &self.info,
+            frames: &self.frames,
+        };
+        let written = serde_json::to_vec_pretty(&index)
+            .map_err(std::io::Error::other)
+            .and_then(|contents| std::fs::write(&self.info.index_path, contents));
+        if let Err(e) = written {
+            warn!(
+                "Failed to write screenshot series index {}: {}",
+                self.info.index_path.display(),
+                e
+            );
+        }
+    }
+
+    fn finish(&mut self, status: SeriesStatus) {
+        self.info.status = status;
+        self.info.finished = Some(chrono::Local::now().to_rfc3339());
+        self.write_index();
+    }
+}
+
+/// Manager for screenshot series
+pub struct SeriesManager {
+    /// Monotonic counter for series IDs
+    next_series_id: AtomicU32,
+    /// Every series of the session mapped by series ID
+    series:         HashMap<u32, Series>,
+}
+
+impl SeriesManager {
+    fn new() -> Self {
+        Self {
+            next_series_id: AtomicU32::new(1),
+            series:         HashMap::new(),
+        }
+    }
+
+    /// Stop a running series by ID
+    pub fn stop(&mut self, series_id: u32) -> Result<SeriesInfo> {
+        let series = self.series.get_mut(&series_id).ok_or_else(|| {
+            error_stack::Report::new(Error::InvalidState(format!(
+                "Screenshot series {series_id} not found"
+            )))
+        })?;
+        if series.info.status != SeriesStatus::Running {
+            return Err(error_stack::Report::new(Error::InvalidState(format!(
+                "Screenshot series {series_id} is already {}",
+                json!(series.info.status).as_str().unwrap_or_default()
+            ))));
+        }
+        info!("Stopping screenshot series {}", series_id);
+        series.handle.abort();
+        series.finish(SeriesStatus::Stopped);
+        Ok(series.info.clone())
+    }
+
+    /// List every series of the session ordered by ID
+    pub fn list(&self) -> Vec<SeriesInfo> {
+        let mut series: Vec<SeriesInfo> = self
+            .series
+            .values()
+            .map(|series| series.info.clone())
+            .collect();
+        series.sort_by_key(|info| info.series_id);
+        series
+    }
+
+    /// Record a requested frame and rewrite the index
+    fn record(&mut self, series_id: u32, frame: Frame) {
+        if let Some(series) = self.series.get_mut(&series_id) {
+            if frame.success {
+                series.info.captured += 1;
+            } else {
+                series.info.failures += 1;
+            }
+            series.frames.push(frame);
+            series.write_index();
+        }
+    }
+}
+
+/// Directory a series is saved to when none is given, under the log directory
+fn default_directory(series_id: u32) -> PathBuf {
+    create_log_directory().join(format!(
+        "{LOG_PREFIX}screenshots_{series_id}_{}",
+        chrono::Local::now().timestamp()
+    ))
+}
+
+/// Path of a frame, numbered from 1
+fn frame_path(directory: &Path, frame: u32, format: &str) -> PathBuf {
+    directory.join(format!("frame_{frame:04}.{format}"))
+}
+
+/// Start capturing a series, returning its info
+///
+/// Without a directory, the series is saved under the log directory.
+pub async fn start(
+    port: u16,
+    count: u32,
+    interval: Duration,
+    directory: Option<PathBuf>,
+    format: &str,
+) -> Result<SeriesInfo> {
+    let mut manager = SCREENSHOT_SERIES.lock().await;
+    let series_id = manager.next_series_id.fetch_add(1, Ordering::SeqCst);
+    let spec = SeriesSpec {
+        port,
+        count,
+        interval,
+        directory: directory.unwrap_or_else(|| default_directory(series_id)),
+        format: format.to_string(),
+    };
+    std::fs::create_dir_all(&spec.directory).map_err(|e| {
+        error_stack::Report::new(Error::FileOperation(format!(
+            "Failed to create screenshot directory {}",
+            spec.directory.display()
+        )))
+        .attach_printable(e.to_string())
+    })?;
+
+    let info = SeriesInfo {
+        series_id,
+        port,
+        count,
+        interval_ms: u64::try_from(interval.as_millis()).unwrap_or(u64::MAX),
+        directory: spec.directory.clone(),
+        index_path: spec.directory.join(INDEX_FILE),
+        status: SeriesStatus::Running,
+        captured: 0,
+        failures: 0,
+        started: chrono::Local::now().to_rfc3339(),
+        finished: None,
+    };
+
+    // The lock is held until the series is registered, so its first frame cannot be recorded
+    // before it has been inserted
+    let handle = tokio::spawn(brp_client::with_host(
+        brp_client::current_host(),
+        run_series(series_id, spec),
+    ));
+    let series = Series {
+        info: info.clone(),
+        frames: Vec::new(),
+        handle,
+    };
+    series.write_index();
+    manager.series.insert(series_id, series);
+    drop(manager);
+
+    info!(
+        "Started screenshot series {} ({} frames every {}ms)",
+        series_id, count, info.interval_ms
+    );
+    Ok(info)
+}
+
+/// Task capturing one series until it has every frame or is stopped
+async fn run_series(series_id: u32, spec: SeriesSpec) {
+    let started = Instant::now();
+    for frame in 1..=spec.count {
+        tokio::time::sleep_until(started + spec.interval * (frame - 1)).await;
+
+        let path = frame_path(&spec.directory, frame, &spec.format);
+        let params = json!({ JSON_FIELD_PATH: path });
+        let error =
+            match execute_brp_method(BRP_METHOD_EXTRAS_SCREENSHOT, Some(params), Some(spec.port))
+                .await
+            {
+                Ok(BrpResult::Success(_)) => None,
+                Ok(BrpResult::Error(error)) => Some(error.message),
+                Err(report) => Some(report.to_string()),
+            };
+        let frame = Frame {
+            number: frame,
+            path,
+            captured_at: chrono::Local::now().to_rfc3339(),
+            success: error.is_none(),
+            error,
+        };
+        SCREENSHOT_SERIES.lock().await.record(series_id, frame);
+    }
+
+    info!("Screenshot series {} completed", series_id);
+    if let Some(series) = SCREENSHOT_SERIES.lock().await.series.get_mut(&series_id) {
+        series.finish(SeriesStatus::Completed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_lists_the_series_and_its_frames() {
+        let directory = PathBuf::from("/tmp/series");
+        assert_eq!(
+            frame_path(&directory, 7, "jpg"),
+            PathBuf::from("/tmp/series/frame_0007.jpg")
+        );
+
+        let info = SeriesInfo {
+            series_id:   3,
+            port:        15702,
+            count:       2,
+            interval_ms: 500,
+            directory:   directory.clone(),
+            index_path:  directory.join(INDEX_FILE),
+            status:      SeriesStatus::Running,
+            captured:    1,
+            failures:    0,
+            started:     "2025-01-01T00:00:00+00:00".to_string(),
+            finished:    None,
+        };
+        let frames = [Frame {
+            number:      1,
+            path:        frame_path(&directory, 1, "png"),
+            captured_at: "2025-01-01T00:00:00+00:00".to_string(),
+            success:     true,
+            error:       None,
+        }];
+        let index = json!(SeriesIndex {
+            info:   &info,
+            frames: &frames,
+        });
+        assert_eq!(index[JSON_FIELD_SERIES_ID], 3);
+        assert_eq!(index["status"], "running");
+        assert_eq!(index["frames"][0]["path"], "/tmp/series/frame_0001.png");
+        assert!(index["frames"][0].get("error").is_none());
+    }
+}
@@ -0,0 +1,129 @@
+//! Export a recorded session as one JSON document for bug reports and replay
+
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{PARAM_SESSION_FILE, Session};
+use crate::BrpMcpService;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_EXPORT_SESSION, TOOL_BRP_EXPORT_SESSION};
+
+/// Parameter giving the file the export is written to
+const PARAM_OUTPUT_PATH: &str = "output_path";
+
+/// Parameter choosing whether recorded response data is exported
+const PARAM_INCLUDE_RESULTS: &str = "include_results";
+
+/// Extension replacing `.jsonl` for exports written next to the session file
+const EXPORT_EXTENSION: &str = "export.json";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_EXPORT_SESSION.into(),
+        description:  DESC_BRP_EXPORT_SESSION.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_SESSION_FILE,
+                "Session file to export (default: the running recording, else the last one \
+                 stopped)",
+                false,
+            )
+            .add_string_property(
+                PARAM_OUTPUT_PATH,
+                "File to write the export to (default: next to the session file, as \
+                 .export.json)",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_INCLUDE_RESULTS,
+                "Include recorded response data (default: true)",
+                false,
+            )
+            .build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let session_file = arguments
+        .get(PARAM_SESSION_FILE)
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+        .or_else(super::current_session_file)
+        .ok_or_else(|| {
+            report_to_mcp_error(&error_stack::Report::new(Error::InvalidState(
+                "No session has been recorded; start one with brp_start_session_recording or \
+                 pass session_file"
+                    .to_string(),
+            )))
+        })?;
+    let output_path = arguments
+        .get(PARAM_OUTPUT_PATH)
+        .and_then(Value::as_str)
+        .map_or_else(
+            || session_file.with_extension(EXPORT_EXTENSION),
+            PathBuf::from,
+        );
+    let include_results = arguments
+        .get(PARAM_INCLUDE_RESULTS)
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+
+    let mut session =
+        Session::read(&session_file).map_err(|report| report_to_mcp_error(&report))?;
+    if !include_results {
+        for request in &mut session.requests {
+            request.result = None;
+        }
+    }
+
+    let summary = session.summary();
+    let export = json!({
+        "session": session.info,
+        "exported_at": chrono::Local::now().to_rfc3339(),
+        "summary": summary,
+        "requests": session.requests,
+        "replay": { "requests": session.replay_requests() },
+    });
+    let written = serde_json::to_vec_pretty(&export)
+        .map_err(std::io::Error::other)
+        .and_then(|contents| std::fs::write(&output_path, contents));
+    if let Err(e) = written {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::io_failed("write", &output_path, e),
+        )));
+    }
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Exported {} requests to {}",
+            session.requests.len(),
+            output_path.display()
+        ))
+        .data(json!({
+            "export_path": output_path.display().to_string(),
+            "session_file": session_file.display().to_string(),
+            "summary": summary,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,221 @@
+//! Replay the mutating requests of a recorded session, reporting divergences
+
+use std::path::PathBuf;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::replay::{self, Replay, ReplayOptions};
+use super::{PARAM_SESSION_FILE, Session};
+use crate::BrpMcpService;
+use crate::brp_tools::constants::{DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::progress::{self, Progress};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_REPLAY_SESSION, TOOL_BRP_REPLAY_SESSION};
+
+/// Parameter naming a paused step-mode replay
+const PARAM_REPLAY_ID: &str = "replay_id";
+
+/// Parameter scaling the recorded gaps between requests
+const PARAM_SPEED: &str = "speed";
+
+/// Parameter replaying read-only requests too
+const PARAM_INCLUDE_READS: &str = "include_reads";
+
+/// Parameter pausing at the first divergence
+const PARAM_STOP_ON_DIVERGENCE: &str = "stop_on_divergence";
+
+/// Parameter turning on step mode
+const PARAM_STEP: &str = "step";
+
+/// Parameter giving the requests replayed per step
+const PARAM_STEPS: &str = "steps";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_REPLAY_SESSION.into(),
+        description:  DESC_BRP_REPLAY_SESSION.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_SESSION_FILE,
+                "Session file to replay (default: the running recording, else the last one \
+                 stopped)",
+                false,
+            )
+            .add_number_property(
+                PARAM_REPLAY_ID,
+                "Continue a paused replay instead of starting one",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                "Port every request is sent to (default: each request's recorded port)",
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .add_number_property(
+                PARAM_SPEED,
+                "Speed relative to the recording: 2 halves the gaps between requests, 0 replays \
+                 without waiting (default: 1)",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_INCLUDE_READS,
+                "Replay read-only requests too, not only mutating ones (default: false)",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_STOP_ON_DIVERGENCE,
+                "Pause at the first response that differs from the recording (default: false)",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_STEP,
+                "Replay only `steps` requests and pause, returning a replay_id to continue with \
+                 (default: false)",
+                false,
+            )
+            .add_number_property(
+                PARAM_STEPS,
+                "Requests replayed per call in step mode (default: 1)",
+                false,
+            )
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let replay_id = arguments
+        .get(PARAM_REPLAY_ID)
+        .and_then(Value::as_u64)
+        .map(|id| u32::try_from(id).unwrap_or(u32::MAX));
+    let step = arguments.get(PARAM_STEP).and_then(Value::as_bool);
+    let steps = arguments
+        .get(PARAM_STEPS)
+        .and_then(Value::as_u64)
+        .map_or(1, |steps| usize::try_from(steps).unwrap_or(usize::MAX))
+        .max(1);
+
+    let mut replay = replay_id
+        .map_or_else(|| start_replay(&arguments), replay::resume)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    // A resumed replay keeps the step mode it started with unless told otherwise
+    if let Some(step) = step {
+        replay.set_stepping(step);
+    }
+
+    let limit = if replay.is_stepping() {
+        steps
+    } else {
+        usize::MAX
+    };
+    let replayed = progress::scope(Progress::new(&context), Box::pin(replay.run(limit))).await;
+
+    let mut data = replay.status();
+    let finished = replay.is_finished();
+    let divergences = replayed
+        .iter()
+        .filter(|request| request.divergence.is_some())
+        .count();
+    let skipped = replayed.iter().filter(|request| request.skipped).count();
+    // A replay that can continue is kept, whether it paused for a step or a divergence
+    let paused_id = if finished {
+        None
+    } else {
+        Some(replay::pause(replay, replay_id).map_err(|report| report_to_mcp_error(&report))?)
+    };
+    data["requests"] = json!(replayed);
+    if let Some(paused_id) = paused_id {
+        data[PARAM_REPLAY_ID] = json!(paused_id);
+    }
+
+    let message = format!(
+        "Replayed {} requests with {divergences} divergences{}{}",
+        replayed.len(),
+        if skipped > 0 {
+            format!(", {skipped} destructive requests skipped for confirmation")
+        } else {
+            String::new()
+        },
+        paused_id.map_or_else(
+            || ", replay finished".to_string(),
+            |id| format!(", paused as replay {id}")
+        )
+    );
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Read the session and set up a replay from the tool arguments
+fn start_replay(arguments: &Value) -> crate::error::Result<Replay> {
+    let session_file = arguments
+        .get(PARAM_SESSION_FILE)
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+        .or_else(super::current_session_file)
+        .ok_or_else(|| {
+            error_stack::Report::new(Error::InvalidState(
+                "No session has been recorded; record one with brp_start_session_recording or \
+                 pass session_file"
+                    .to_string(),
+            ))
+        })?;
+    let speed = arguments
+        .get(PARAM_SPEED)
+        .and_then(Value::as_f64)
+        .unwrap_or(1.0);
+    if !speed.is_finite() || speed < 0.0 {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_SPEED}'"),
+            format!("{speed} is not a non-negative number"),
+        )));
+    }
+    let flag = |name: &str| {
+        arguments
+            .get(name)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    };
+    let port = arguments
+        .get(JSON_FIELD_PORT)
+        .and_then(Value::as_u64)
+        .map(|port| {
+            u16::try_from(port).map_err(|_| {
+                error_stack::Report::new(Error::invalid(
+                    &format!("parameter '{JSON_FIELD_PORT}'"),
+                    format!("{port} is not a valid port"),
+                ))
+            })
+        })
+        .transpose()?;
+    let options = ReplayOptions {
+        port,
+        speed,
+        include_reads: flag(PARAM_INCLUDE_READS),
+        stop_on_divergence: flag(PARAM_STOP_ON_DIVERGENCE),
+        step: flag(PARAM_STEP),
+    };
+
+    Ok(Replay::new(Session::read(&session_file)?, options))
+}
@@ -0,0 +1,68 @@
+//! Start recording every BRP request to a session file
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use crate::BrpMcpService;
+use crate::brp_tools::constants::PARAM_LABEL;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_START_SESSION_RECORDING, TOOL_BRP_START_SESSION_RECORDING};
+
+/// Parameter choosing whether response data is recorded
+const PARAM_INCLUDE_RESULTS: &str = "include_results";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_START_SESSION_RECORDING.into(),
+        description:  DESC_BRP_START_SESSION_RECORDING.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(PARAM_LABEL, "Optional label for the session", false)
+            .add_boolean_property(
+                PARAM_INCLUDE_RESULTS,
+                "Record response data, not only whether each request succeeded (default: true)",
+                false,
+            )
+            .build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let label = arguments
+        .get(PARAM_LABEL)
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let include_results = arguments
+        .get(PARAM_INCLUDE_RESULTS)
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+
+    let session =
+        super::start(label, include_results).map_err(|report| report_to_mcp_error(&report))?;
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Recording BRP requests to {}",
+            session.path.display()
+        ))
+        .data(json!({ "session": session }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,50 @@
+//! Stop the running session recording
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+
+use crate::BrpMcpService;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_STOP_SESSION_RECORDING, TOOL_BRP_STOP_SESSION_RECORDING};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_STOP_SESSION_RECORDING.into(),
+        description:  DESC_BRP_STOP_SESSION_RECORDING.into(),
+        input_schema: schema::SchemaBuilder::new().build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    _request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let (session, counts) = super::stop().map_err(|report| report_to_mcp_error(&report))?;
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Stopped recording after {} requests ({} failed)",
+            counts.requests, counts.errors
+        ))
+        .data(json!({
+            "session": session,
+            "counts": counts,
+            "stopped_at": chrono::Local::now().to_rfc3339(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
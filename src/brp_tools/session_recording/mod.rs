@@ -0,0 +1,419 @@
+//! Session recording: every BRP request made through the request handler, written to a file
+//!
+//! While a recording is running, each request is appended to the session file as one JSON line
+//! with its method, port, params, duration, the format corrections applied to it and the
+//! response. The first line describes the session. Exporting a session bundles the requests with
+//! a summary and a replay list for `brp_execute_batch`, for bug reports and for reproducing a
+//! session against a fresh app, and replaying a session sends its mutating requests again,
+//! reporting responses that differ from the recording. Only one recording runs at a time.
+
+pub mod brp_export_session;
+pub mod brp_replay_session;
+pub mod brp_start_session_recording;
+pub mod brp_stop_session_recording;
+mod replay;
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::warn;
+
+use super::request_handler::FormatCorrection;
+use super::support::brp_client::BrpResult;
+use crate::error::{Error, Result};
+use crate::log_tools::support::{LOG_PREFIX, create_log_directory};
+
+/// Global session recorder
+static RECORDER: LazyLock<Mutex<Recorder>> = LazyLock::new(|| Mutex::new(Recorder::default()));
+
+/// Parameter naming a session file
+pub const PARAM_SESSION_FILE: &str = "session_file";
+
+/// File extension of session files
+const SESSION_EXTENSION: &str = "jsonl";
+
+/// One line of a session file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SessionLine {
+    Session(SessionInfo),
+    Request(RecordedRequest),
+}
+
+/// Description of a recording, the first line of its session file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub path:            PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub label:           Option<String>,
+    pub started_at:      String,
+    /// Whether response data is recorded, not only whether each request succeeded
+    pub include_results: bool,
+    pub server_version:  String,
+}
+
+/// One BRP request with its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// Position in the session, from 1
+    pub seq:         usize,
+    pub timestamp:   String,
+    /// Tool the request was made for
+    pub tool:        String,
+    pub method:      String,
+    pub host:        String,
+    pub port:        u16,
+    /// Params as given, before format corrections
+    pub params:      Option<Value>,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub corrections: Vec<Value>,
+    pub success:     bool,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub result:      Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error:       Option<Value>,
+}
+
+/// A BRP request handled while recording
+pub struct Exchange<'a> {
+    pub tool:        &'a str,
+    pub method:      &'a str,
+    pub port:        u16,
+    pub params:      Option<&'a Value>,
+    pub duration:    Duration,
+    pub corrections: &'a [FormatCorrection],
+    pub result:      &'a BrpResult,
+}
+
+/// Counts of a recording so far
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionCounts {
+    pub requests:    usize,
+    pub errors:      usize,
+    pub corrections: usize,
+}
+
+/// A running recording
+struct Recording {
+    info:   SessionInfo,
+    file:   File,
+    counts: SessionCounts,
+}
+
+#[derive(Default)]
+struct Recorder {
+    active: Option<Recording>,
+    /// Session file of the last recording that was stopped
+    last:   Option<PathBuf>,
+}
+
+/// Start recording to a new session file in the log directory
+pub fn start(label: Option<String>, include_results: bool) -> Result<SessionInfo> {
+    let mut recorder = lock()?;
+    if let Some(active) = &recorder.active {
+        return Err(error_stack::Report::new(Error::InvalidState(format!(
+            "A session is already being recorded to {}",
+            active.info.path.display()
+        ))));
+    }
+
+    let now = chrono::Local::now();
+    let path = create_log_directory().join(format!(
+        "{LOG_PREFIX}session_{}.{SESSION_EXTENSION}",
+        now.format("%Y%m%d_%H%M%S_%3f")
+    ));
+    let info = SessionInfo {
+        path: path.clone(),
+        label,
+        started_at: now.to_rfc3339(),
+        include_results,
+        server_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let mut file = OpenOptions::new()
+        .create_new(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| error_stack::Report::new(Error::io_failed("create", &path, e)))?;
+    write_line(&mut file, &SessionLine::Session(info.clone()))
+        .map_err(|e| error_stack::Report::new(Error::io_failed("write", &path, e)))?;
+
+    recorder.active = Some(Recording {
+        info: info.clone(),
+        file,
+        counts: SessionCounts::default(),
+    });
+    drop(recorder);
+    Ok(info)
+}
+
+/// Stop the running recording, returning it with its counts
+pub fn stop() -> Result<(SessionInfo, SessionCounts)> {
+    let mut recorder = lock()?;
+    let recording = recorder.active.take().ok_or_else(|| {
+        error_stack::Report::new(Error::InvalidState(
+            "No session is being recorded".to_string(),
+        ))
+    })?;
+    recorder.last = Some(recording.info.path.clone());
+    drop(recorder);
+    Ok((recording.info, recording.counts))
+}
+
+/// Session file of the running recording, or else of the last one stopped
+pub fn current_session_file() -> Option<PathBuf> {
+    let recorder = lock().ok()?;
+    recorder
+        .active
+        .as_ref()
+        .map(|recording| recording.info.path.clone())
+        .or_else(|| recorder.last.clone())
+}
+
+/// Append a request to the running recording, if any
+///
+/// Write failures are logged rather than failing the request being recorded.
+pub fn record(exchange: &Exchange<'_>, host: &str) {
+    let Ok(mut recorder) = lock() else {
+        return;
+    };
+    let Some(recording) = recorder.active.as_mut() else {
+        return;
+    };
+
+    let (success, result, error) = match exchange.result {
+        BrpResult::Success(data) => (
+            true,
+            recording
+                .info
+                .include_results
+                .then(|| data.clone().unwrap_or(Value::Null)),
+            None,
+        ),
+        BrpResult::Error(error) => (
+            false,
+            None,
+            Some(json!({
+                "code": error.code,
+                "message": error.message,
+                "data": error.data,
+            })),
+        ),
+    };
+    recording.counts.requests += 1;
+    recording.counts.errors += usize::from(!success);
+    recording.counts.corrections += exchange.corrections.len();
+
+    let request = RecordedRequest {
+        seq: recording.counts.requests,
+        timestamp: chrono::Local::now().to_rfc3339(),
+        tool: exchange.tool.to_string(),
+        method: exchange.method.to_string(),
+        host: host.to_string(),
+        port: exchange.port,
+        params: exchange.params.cloned(),
+        duration_ms: u64::try_from(exchange.duration.as_millis()).unwrap_or(u64::MAX),
+        corrections: exchange
+            .corrections
+            .iter()
+            .map(|correction| {
+                json!({
+                    "type": correction.component,
+                    "hint": correction.hint,
+                    "original_format": correction.original_format,
+                    "corrected_format": correction.corrected_format,
+                })
+            })
+            .collect(),
+        success,
+        result,
+        error,
+    };
+    if let Err(e) = write_line(&mut recording.file, &SessionLine::Request(request)) {
+        warn!(
+            "Failed to record request to session file {}: {}",
+            recording.info.path.display(),
+            e
+        );
+    }
+}
+
+/// A session file read back
+pub struct Session {
+    pub info:     SessionInfo,
+    pub requests: Vec<RecordedRequest>,
+}
+
+impl Session {
+    /// Read a session file; lines that can't be parsed, such as a partly written last line, are
+    /// skipped
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| error_stack::Report::new(Error::io_failed("read", path, e)))?;
+        let mut info = None;
+        let mut requests = Vec::new();
+        for line in contents.lines() {
+            match serde_json::from_str(line) {
+                Ok(SessionLine::Session(session)) => info = Some(session),
+                Ok(SessionLine::Request(request)) => requests.push(request),
+                Err(_) => {}
+            }
+        }
+        let info = info.ok_or_else(|| {
+            error_stack::Report::new(Error::invalid(
+                "session file",
+                format!("{} does not start with a session line", path.display()),
+            ))
+        })?;
+        Ok(Self { info, requests })
+    }
+
+    /// Request, error and correction counts, with requests per method
+    pub fn summary(&self) -> Value {
+        let mut methods: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut ports: BTreeMap<u16, usize> = BTreeMap::new();
+        for request in &self.requests {
+            *methods.entry(&request.method).or_insert(0) += 1;
+            *ports.entry(request.port).or_insert(0) += 1;
+        }
+        json!({
+            "request_count": self.requests.len(),
+            "error_count": self.requests.iter().filter(|request| !request.success).count(),
+            "correction_count": self
+                .requests
+                .iter()
+                .map(|request| request.corrections.len())
+                .sum::<usize>(),
+            "total_duration_ms": self
+                .requests
+                .iter()
+                .map(|request| request.duration_ms)
+                .sum::<u64>(),
+            "methods": methods,
+            "ports": ports,
+            "first_request_at": self.requests.first().map(|request| &request.timestamp),
+            "last_request_at": self.requests.last().map(|request| &request.timestamp),
+        })
+    }
+
+    /// The session's requests in `brp_execute_batch` form, in order
+    ///
+    /// Params are replayed as given, so format discovery applies the same corrections again.
+    pub fn replay_requests(&self) -> Vec<Value> {
+        self.requests
+            .iter()
+            .map(|request| {
+                json!({
+                    "method": request.method,
+                    "params": request.params,
+                    "port": request.port,
+                })
+            })
+            .collect()
+    }
+}
+
+fn write_line(file: &mut File, line: &SessionLine) -> std::io::Result<()> {
+    let mut json = serde_json::to_vec(line).map_err(std::io::Error::other)?;
+    json.push(b'\n');
+    file.write_all(&json)
+}
+
+fn lock() -> Result<std::sync::MutexGuard<'static, Recorder>> {
+    RECORDER.lock().map_err(|e| {
+        error_stack::Report::new(Error::MutexPoisoned(format!("session recorder lock: {e}")))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::brp_client::BrpError;
+
+    #[test]
+    fn test_records_requests_and_reads_them_back() {
+        let dir = std::env::temp_dir().join(format!("brp_session_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let info = SessionInfo {
+            path:            path.clone(),
+            label:           Some("repro".to_string()),
+            started_at:      chrono::Local::now().to_rfc3339(),
+            include_results: false,
+            server_version:  "0.0.0".to_string(),
+        };
+        let mut file = File::create(&path).unwrap();
+        write_line(&mut file, &SessionLine::Session(info.clone())).unwrap();
+        *RECORDER.lock().unwrap() = Recorder {
+            active: Some(Recording {
+                info,
+                file,
+                counts: SessionCounts::default(),
+            }),
+            last:   None,
+        };
+
+        let params = json!({ "entity": 4_294_967_298_u64, "components": ["Transform"] });
+        let corrections = [FormatCorrection {
+            component:        "Transform".to_string(),
+            original_format:  json!([1, 2, 3]),
+            corrected_format: json!({ "x": 1, "y": 2, "z": 3 }),
+            hint:             "Vec3 as object".to_string(),
+        }];
+        let success = BrpResult::Success(Some(json!({ "components": {} })));
+        let failure = BrpResult::Error(BrpError {
+            code:    -23401,
+            message: "Entity not found".to_string(),
+            data:    None,
+        });
+        for (result, corrections) in [(&success, &corrections[..]), (&failure, &[][..])] {
+            record(
+                &Exchange {
+                    tool: "bevy_get",
+                    method: "bevy/get",
+                    port: 15702,
+                    params: Some(&params),
+                    duration: Duration::from_millis(12),
+                    corrections,
+                    result,
+                },
+                "localhost",
+            );
+        }
+        let (_, counts) = stop().unwrap();
+        assert_eq!(counts.requests, 2);
+        assert_eq!(counts.errors, 1);
+        assert_eq!(counts.corrections, 1);
+        assert_eq!(current_session_file(), Some(path.clone()));
+
+        let session = Session::read(&path).unwrap();
+        assert_eq!(session.info.label.as_deref(), Some("repro"));
+        assert_eq!(session.requests.len(), 2);
+        assert_eq!(session.requests[0].seq, 1);
+        assert_eq!(session.requests[0].corrections[0]["type"], "Transform");
+        // Results are left out when the recording doesn't include them
+        assert!(session.requests[0].result.is_none());
+        assert_eq!(
+            session.requests[1].error.as_ref().unwrap()["message"],
+            "Entity not found"
+        );
+
+        let summary = session.summary();
+        assert_eq!(summary["error_count"], 1);
+        assert_eq!(summary["methods"]["bevy/get"], 2);
+        assert_eq!(
+            session.replay_requests()[0],
+            json!({ "method": "bevy/get", "params": params, "port": 15702 })
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,460 @@
+//! Replaying a recorded session against a running app
+//!
+//! The mutating requests of a session are sent again in order, optionally with the read-only
+//! ones, spaced as they were recorded (scaled by a speed factor). Entities spawned during the
+//! replay get new IDs, so the IDs a recorded spawn returned are mapped to the replayed ones and
+//! rewritten in later params and in the recorded responses they are compared with. Any response
+//! that differs from the recorded one is reported as a divergence. Replays can't confirm a
+//! destructive request, so under the `confirm` policy of `destructive_ops` those are skipped.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::{RecordedRequest, Session};
+use crate::brp_tools::constants::JSON_FIELD_ENTITY;
+use crate::brp_tools::request_handler::execute_brp_method_with_format_discovery;
+use crate::brp_tools::support::brp_client::{BrpError, BrpResult};
+use crate::brp_tools::support::destructive_ops::{self, DESTRUCTIVE_METHODS, DestructiveOpsPolicy};
+use crate::brp_tools::support::json_diff::diff_values;
+use crate::brp_tools::support::request_policy;
+use crate::error::{Error, Result};
+use crate::support::progress;
+
+/// Paused step-mode replays
+static REPLAYS: LazyLock<Mutex<ReplayManager>> = LazyLock::new(|| Mutex::new(ReplayManager::new()));
+
+/// Longest wait between two replayed requests, however far apart they were recorded
+const MAX_REPLAY_DELAY: Duration = Duration::from_secs(10);
+
+/// Fields holding entity IDs, rewritten to the entities spawned during the replay
+const ENTITY_FIELDS: &[&str] = &[JSON_FIELD_ENTITY, "entities", "parent", "children"];
+
+/// How a session is replayed
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// Port every request goes to instead of the recorded one
+    pub port:               Option<u16>,
+    /// Factor the recorded gaps between requests are divided by; 0 replays without waiting
+    pub speed:              f64,
+    /// Replay read-only requests too, not only mutating ones
+    pub include_reads:      bool,
+    /// Pause at the first request whose response differs from the recording
+    pub stop_on_divergence: bool,
+    /// Pause after a few requests each call
+    pub step:               bool,
+}
+
+/// Outcome of one replayed request
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedRequest {
+    /// Position of the request in the recorded session
+    pub seq:         usize,
+    pub method:      String,
+    pub port:        u16,
+    pub success:     bool,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error:       Option<String>,
+    /// How the response differs from the recorded one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divergence:  Option<Value>,
+    /// Not sent, because the `destructive_ops` policy requires confirmation
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skipped:     bool,
+}
+
+/// A session being replayed
+pub struct Replay {
+    session:           Session,
+    /// Indices into the session's requests of the requests to replay
+    selected:          Vec<usize>,
+    /// Position in `selected` of the next request
+    position:          usize,
+    options:           ReplayOptions,
+    /// Recorded entity IDs mapped to the ones spawned during the replay
+    entities:          BTreeMap<u64, u64>,
+    /// Recording time of the previously replayed request
+    previous_recorded: Option<DateTime<FixedOffset>>,
+    divergence_count:  usize,
+    /// Destructive requests not sent because they need confirmation
+    skipped_count:     usize,
+}
+
+impl Replay {
+    pub fn new(session: Session, options: ReplayOptions) -> Self {
+        let selected = session
+            .requests
+            .iter()
+            .enumerate()
+            .filter(|(_, request)| {
+                options.include_reads || !request_policy::is_read_only(&request.method)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        Self {
+            session,
+            selected,
+            position: 0,
+            options,
+            entities: BTreeMap::new(),
+            previous_recorded: None,
+            divergence_count: 0,
+            skipped_count: 0,
+        }
+    }
+
+    /// Replay up to `limit` requests, reporting progress after each
+    ///
+    /// With `stop_on_divergence`, stops after the first request that diverges.
+    pub async fn run(&mut self, limit: usize) -> Vec<ReplayedRequest> {
+        let mut replayed = Vec::new();
+        while replayed.len() < limit && !self.is_finished() {
+            let request = self.session.requests[self.selected[self.position]].clone();
+            self.wait_for(&request).await;
+            let outcome = self.replay(&request).await;
+            self.position += 1;
+            let diverged = outcome.divergence.is_some();
+            replayed.push(outcome);
+            progress::report(self.position, Some(self.selected.len())).await;
+            if diverged && self.options.stop_on_divergence {
+                break;
+            }
+        }
+        replayed
+    }
+
+    /// Whether each call replays a few requests and pauses
+    pub const fn is_stepping(&self) -> bool {
+        self.options.step
+    }
+
+    /// Switch step mode on or off for the following calls
+    pub const fn set_stepping(&mut self, step: bool) {
+        self.options.step = step;
+    }
+
+    pub const fn is_finished(&self) -> bool {
+        self.position >= self.selected.len()
+    }
+
+    /// Where the replay stands, for responses
+    pub fn status(&self) -> Value {
+        json!({
+            "session_file": self.session.info.path.display().to_string(),
+            "replayed": self.position,
+            "total": self.selected.len(),
+            "skipped_reads": self.session.requests.len() - self.selected.len(),
+            "finished": self.is_finished(),
+            "divergence_count": self.divergence_count,
+            "skipped_destructive": self.skipped_count,
+            "entity_map": self.entities,
+        })
+    }
+
+    /// Sleep for the recorded gap before a request, scaled by the speed
+    async fn wait_for(&mut self, request: &RecordedRequest) {
+        let recorded = DateTime::parse_from_rfc3339(&request.timestamp).ok();
+        let gap = self
+            .previous_recorded
+            .zip(recorded)
+            .and_then(|(previous, recorded)| (recorded - previous).to_std().ok());
+        self.previous_recorded = recorded.or(self.previous_recorded);
+        if let Some(gap) = gap.filter(|_| self.options.speed > 0.0) {
+            let delay = gap.div_f64(self.options.speed).min(MAX_REPLAY_DELAY);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn replay(&mut self, recorded: &RecordedRequest) -> ReplayedRequest {
+        let port = self.options.port.unwrap_or(recorded.port);
+        if let Some(skipped) = skip_destructive(recorded, port, destructive_ops::policy()) {
+            self.skipped_count += 1;
+            return skipped;
+        }
+        let mut params = recorded.params.clone();
+        if let Some(params) = params.as_mut() {
+            remap_entities(params, &self.entities);
+        }
+
+        let started = Instant::now();
+        let result =
+            execute_brp_method_with_format_discovery(&recorded.method, params, Some(port), vec![])
+                .await
+                .map_or_else(
+                    |report| {
+                        BrpResult::Error(BrpError {
+                            code:    0,
+                            message: report.to_string(),
+                            data:    None,
+                        })
+                    },
+                    |enhanced| enhanced.result,
+                );
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        if let (Some(recorded_result), BrpResult::Success(Some(actual))) =
+            (&recorded.result, &result)
+        {
+            learn_entities(recorded_result, actual, &mut self.entities);
+        }
+        let divergence = divergence(recorded, &result, &self.entities);
+        self.divergence_count += usize::from(divergence.is_some());
+
+        ReplayedRequest {
+            seq: recorded.seq,
+            method: recorded.method.clone(),
+            port,
+            success: matches!(result, BrpResult::Success(_)),
+            duration_ms,
+            error: match result {
+                BrpResult::Error(error) => Some(error.message),
+                BrpResult::Success(_) => None,
+            },
+            divergence,
+            skipped: false,
+        }
+    }
+}
+
+/// The outcome of a destructive request that the policy keeps from being replayed
+///
+/// Replays have no way to preview and confirm a request, like batches.
+fn skip_destructive(
+    recorded: &RecordedRequest,
+    port: u16,
+    policy: DestructiveOpsPolicy,
+) -> Option<ReplayedRequest> {
+    (DESTRUCTIVE_METHODS.contains(&recorded.method.as_str())
+        && policy == DestructiveOpsPolicy::Confirm)
+        .then(|| ReplayedRequest {
+            seq: recorded.seq,
+            method: recorded.method.clone(),
+            port,
+            success: false,
+            duration_ms: 0,
+            error: Some(
+                "The destructive_ops policy requires confirmation; call the method with brp_execute to preview and confirm it"
+                    .to_string(),
+            ),
+            divergence: None,
+            skipped: true,
+        })
+}
+
+/// Rewrite recorded entity IDs in entity fields to the IDs of the replay
+fn remap_entities(value: &mut Value, entities: &BTreeMap<u64, u64>) {
+    let remap = |value: &mut Value| {
+        if let Some(entity) = value.as_u64().and_then(|entity| entities.get(&entity)) {
+            *value = json!(entity);
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map {
+                if ENTITY_FIELDS.contains(&key.as_str()) {
+                    match field {
+                        Value::Array(ids) => ids.iter_mut().for_each(remap),
+                        field => remap(field),
+                    }
+                } else {
+                    remap_entities(field, entities);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                remap_entities(item, entities);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Map the entity a recorded request returned to the one the replayed request returned
+fn learn_entities(recorded: &Value, actual: &Value, entities: &mut BTreeMap<u64, u64>) {
+    let entity = |value: &Value| value.get(JSON_FIELD_ENTITY).and_then(Value::as_u64);
+    if let (Some(recorded), Some(actual)) = (entity(recorded), entity(actual)) {
+        entities.insert(recorded, actual);
+    }
+}
+
+/// How a replayed response differs from the recorded one, if it does
+///
+/// A recorded error is compared by message; a recorded result only when results were recorded,
+/// after rewriting its entity IDs to the replay's.
+fn divergence(
+    recorded: &RecordedRequest,
+    actual: &BrpResult,
+    entities: &BTreeMap<u64, u64>,
+) -> Option<Value> {
+    let recorded_error = recorded
+        .error
+        .as_ref()
+        .and_then(|error| error.get("message"))
+        .and_then(Value::as_str);
+    match (recorded.success, actual) {
+        (true, BrpResult::Error(error)) => Some(json!({
+            "kind": "now_fails",
+            "error": error.message,
+        })),
+        (false, BrpResult::Success(_)) => Some(json!({
+            "kind": "now_succeeds",
+            "recorded_error": recorded_error,
+        })),
+        (false, BrpResult::Error(error)) => {
+            (recorded_error != Some(error.message.as_str())).then(|| {
+                json!({
+                    "kind": "different_error",
+                    "recorded_error": recorded_error,
+                    "error": error.message,
+                })
+            })
+        }
+        (true, BrpResult::Success(data)) => {
+            let mut expected = recorded.result.clone()?;
+            remap_entities(&mut expected, entities);
+            let actual = data.clone().unwrap_or(Value::Null);
+            diff_values(&expected, &actual).map(|delta| {
+                json!({
+                    "kind": "different_result",
+                    "delta": delta,
+                })
+            })
+        }
+    }
+}
+
+/// Step-mode replays paused between calls
+struct ReplayManager {
+    next_replay_id: AtomicU32,
+    replays:        HashMap<u32, Replay>,
+}
+
+impl ReplayManager {
+    fn new() -> Self {
+        Self {
+            next_replay_id: AtomicU32::new(1),
+            replays:        HashMap::new(),
+        }
+    }
+}
+
+/// Keep a replay to continue later, returning its ID
+pub fn pause(replay: Replay, replay_id: Option<u32>) -> Result<u32> {
+    let mut manager = lock()?;
+    let replay_id =
+        replay_id.unwrap_or_else(|| manager.next_replay_id.fetch_add(1, Ordering::Relaxed));
+    manager.replays.insert(replay_id, replay);
+    drop(manager);
+    Ok(replay_id)
+}
+
+/// Take a paused replay to continue it
+pub fn resume(replay_id: u32) -> Result<Replay> {
+    lock()?.replays.remove(&replay_id).ok_or_else(|| {
+        error_stack::Report::new(Error::InvalidState(format!(
+            "Replay {replay_id} not found; it finished or was never started"
+        )))
+    })
+}
+
+fn lock() -> Result<std::sync::MutexGuard<'static, ReplayManager>> {
+    REPLAYS
+        .lock()
+        .map_err(|e| error_stack::Report::new(Error::MutexPoisoned(format!("replay lock: {e}"))))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn recorded(success: bool, result: Option<Value>, error: Option<&str>) -> RecordedRequest {
+        RecordedRequest {
+            seq: 1,
+            timestamp: chrono::Local::now().to_rfc3339(),
+            tool: "bevy_get".to_string(),
+            method: "bevy/get".to_string(),
+            host: "localhost".to_string(),
+            port: 15702,
+            params: None,
+            duration_ms: 1,
+            corrections: Vec::new(),
+            success,
+            result,
+            error: error.map(|message| json!({ "code": -1, "message": message })),
+        }
+    }
+
+    #[test]
+    fn test_remaps_entities_and_reports_divergences() {
+        let mut entities = BTreeMap::new();
+        learn_entities(
+            &json!({ "entity": 10 }),
+            &json!({ "entity": 42 }),
+            &mut entities,
+        );
+
+        let mut params = json!({
+            "entity": 10,
+            "components": { "Health": { "entity": 10, "value": 10 } },
+            "entities": [10, 11],
+            "parent": 10
+        });
+        remap_entities(&mut params, &entities);
+        assert_eq!(
+            params,
+            json!({
+                "entity": 42,
+                "components": { "Health": { "entity": 42, "value": 10 } },
+                "entities": [42, 11],
+                "parent": 42
+            })
+        );
+
+        let same = recorded(true, Some(json!({ "entity": 10, "hp": 5 })), None);
+        let actual = BrpResult::Success(Some(json!({ "entity": 42, "hp": 5 })));
+        assert_eq!(divergence(&same, &actual, &entities), None);
+
+        let changed = BrpResult::Success(Some(json!({ "entity": 42, "hp": 3 })));
+        let diverged = divergence(&same, &changed, &entities).unwrap();
+        assert_eq!(diverged["kind"], "different_result");
+        assert_eq!(diverged["delta"], json!({ "hp": 3 }));
+
+        let failed = BrpResult::Error(BrpError {
+            code:    -23401,
+            message: "Entity not found".to_string(),
+            data:    None,
+        });
+        assert_eq!(
+            divergence(&same, &failed, &entities).unwrap()["kind"],
+            "now_fails"
+        );
+        let recorded_failure = recorded(false, None, Some("Entity not found"));
+        assert_eq!(divergence(&recorded_failure, &failed, &entities), None);
+        // Without a recorded result only the status is compared
+        let no_result = recorded(true, None, None);
+        assert_eq!(divergence(&no_result, &changed, &entities), None);
+    }
+
+    #[test]
+    fn test_destructive_requests_are_skipped_when_confirmation_is_required() {
+        let mut destroy = recorded(true, None, None);
+        destroy.method = "bevy/destroy".to_string();
+
+        let skipped = skip_destructive(&destroy, 15702, DestructiveOpsPolicy::Confirm).unwrap();
+        assert!(skipped.skipped && !skipped.success);
+        assert_eq!(skipped.seq, destroy.seq);
+        assert!(skip_destructive(&destroy, 15702, DestructiveOpsPolicy::Allow).is_none());
+
+        let get = recorded(true, None, None);
+        assert!(skip_destructive(&get, 15702, DestructiveOpsPolicy::Confirm).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{PARAM_NAME, Snapshot, validate_name};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENTS, JSON_FIELD_HOST, JSON_FIELD_PATH,
+    JSON_FIELD_PORT, PARAM_FILTER,
+};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema, service as service_support};
+use crate::tools::{DESC_BEVY_SNAPSHOT_CREATE, TOOL_BEVY_SNAPSHOT_CREATE};
+use crate::{BrpMcpService, config};
+
+/// Parameter asking for the snapshot to be saved to disk
+const PARAM_PERSIST: &str = "persist";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SNAPSHOT_CREATE.into(),
+        description:  DESC_BEVY_SNAPSHOT_CREATE.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_NAME,
+                "Name to store the snapshot under (letters, digits, '-', '_', '.'). Default: snapshot-N",
+                false,
+            )
+            .add_any_property(
+                PARAM_FILTER,
+                "Object selecting the entities to capture, as in bevy_query. Properties: with (array), without (array). Default: all entities",
+                false,
+            )
+            .add_string_array_property(
+                JSON_FIELD_COMPONENTS,
+                "Fully-qualified component types to capture (default: every registered component)",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_PERSIST,
+                "Also save the snapshot to the state directory of the current project so it survives restarts (default: false)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+
+    let name = arguments.get(PARAM_NAME).and_then(Value::as_str);
+    if let Some(name) = name {
+        validate_name(name).map_err(|report| report_to_mcp_error(&report))?;
+    }
+    let filter = arguments
+        .get(PARAM_FILTER)
+        .filter(|filter| !filter.is_null())
+        .cloned();
+    let components = params::extract_optional_string_array(&arguments, JSON_FIELD_COMPONENTS);
+    let persist = arguments
+        .get(PARAM_PERSIST)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let lock_error = |e: std::sync::PoisonError<_>| {
+        report_to_mcp_error(&error_stack::Report::new(Error::MutexPoisoned(format!(
+            "snapshot store lock: {e}"
+        ))))
+    };
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => service.snapshots.lock().map_err(lock_error)?.next_name(),
+    };
+
+    let snapshot = Snapshot::capture(name, filter, components, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let mut data = json!({
+        PARAM_NAME: snapshot.name,
+        "created_at": snapshot.created_at,
+        "entity_count": snapshot.entities.len(),
+        "component_count": snapshot.component_count(),
+    });
+    if persist {
+        let project_root = service_support::fetch_project_root(service, context).await?;
+        let path = snapshot
+            .save(project_root.as_deref())
+            .map_err(|report| report_to_mcp_error(&report))?;
+        data[JSON_FIELD_PATH] = json!(path.display().to_string());
+    }
+    let message = format!(
+        "Captured {} entities as snapshot '{}'",
+        snapshot.entities.len(),
+        snapshot.name
+    );
+    service
+        .snapshots
+        .lock()
+        .map_err(lock_error)?
+        .insert(snapshot);
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
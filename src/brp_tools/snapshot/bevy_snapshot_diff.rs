@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{Snapshot, diff_snapshots, validate_name};
+use crate::BrpMcpService;
+use crate::brp_tools::constants::{DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema, service as service_support};
+use crate::tools::{DESC_BEVY_SNAPSHOT_DIFF, TOOL_BEVY_SNAPSHOT_DIFF};
+
+/// Parameter naming the earlier snapshot
+const PARAM_FROM: &str = "from";
+
+/// Parameter naming the later snapshot
+const PARAM_TO: &str = "to";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SNAPSHOT_DIFF.into(),
+        description:  DESC_BEVY_SNAPSHOT_DIFF.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(PARAM_FROM, "Name of the earlier snapshot", true)
+            .add_string_property(
+                PARAM_TO,
+                "Name of the later snapshot. Default: capture the live world with the filter and components of 'from'",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                "The BRP port for a live capture (default: the port 'from' was taken on)",
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let from_name = params::extract_required_string(&request, PARAM_FROM)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let to_name = arguments.get(PARAM_TO).and_then(Value::as_str);
+
+    let project_root = service_support::fetch_project_root(service, context).await?;
+    let from = find_snapshot(service, &from_name, project_root.as_deref())?;
+    let to = if let Some(to_name) = to_name {
+        find_snapshot(service, to_name, project_root.as_deref())?
+    } else {
+        let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, from.port);
+        Snapshot::capture(
+            "live".to_string(),
+            from.filter.clone(),
+            from.components.clone(),
+            port,
+        )
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    };
+
+    let mut data = diff_snapshots(&from, &to);
+    let count = |field: &str| data[field].as_array().map_or(0, Vec::len);
+    let (added, removed, changed) = (
+        count("added_entities"),
+        count("removed_entities"),
+        count("changed_entities"),
+    );
+    data[PARAM_FROM] = json!(from.name);
+    data[PARAM_TO] = json!(to.name);
+
+    let message = if added + removed + changed == 0 {
+        format!("No changes between '{}' and '{}'", from.name, to.name)
+    } else {
+        format!(
+            "'{}' to '{}': {added} entities added, {removed} removed, {changed} changed",
+            from.name, to.name
+        )
+    };
+
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Look a snapshot up in the session store or in the project's saved snapshots
+fn find_snapshot(
+    service: &BrpMcpService,
+    name: &str,
+    project_root: Option<&Path>,
+) -> Result<Snapshot, McpError> {
+    validate_name(name).map_err(|report| report_to_mcp_error(&report))?;
+    let found = service
+        .snapshots
+        .lock()
+        .map_err(|e| {
+            report_to_mcp_error(&error_stack::Report::new(Error::MutexPoisoned(format!(
+                "snapshot store lock: {e}"
+            ))))
+        })?
+        .find(name, project_root)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    found.ok_or_else(|| {
+        report_to_mcp_error(&error_stack::Report::new(Error::missing(&format!(
+            "snapshot '{name}'"
+        ))))
+    })
+}
@@ -0,0 +1,339 @@
+//! World snapshots for regression debugging
+//!
+//! A snapshot records the components of every entity matching a filter at one point in time.
+//! Snapshots are kept in memory for the session and can also be saved to the state directory
+//! so they survive restarts. Saved snapshots belong to the project the client is working in, its
+//! first root, so projects don't overwrite each other's snapshots of the same name. Diffing two
+//! snapshots (or a snapshot against the live world) reports added and removed entities and
+//! per-component changes, which answers "what changed after I pressed this key" in one call.
+
+pub mod bevy_snapshot_create;
+pub mod bevy_snapshot_diff;
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use super::bevy_export_scene::query_entities;
+use super::constants::{JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY};
+use super::support::json_diff::diff_values;
+use crate::error::{Error, Result};
+use crate::state::StateDir;
+
+/// Number of snapshots kept in memory; older ones are dropped
+const MAX_SNAPSHOTS: usize = 16;
+
+/// Subdirectory of the state directory holding saved snapshots
+const SNAPSHOTS_DIR_NAME: &str = "snapshots";
+
+/// Parameter naming a snapshot
+pub const PARAM_NAME: &str = "name";
+
+/// Components of a set of entities at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub name:       String,
+    pub created_at: String,
+    pub port:       u16,
+    /// Entity filter the snapshot was taken with, reused for live diffs
+    pub filter:     Option<Value>,
+    /// Component types the snapshot was limited to, reused for live diffs
+    pub components: Option<Vec<String>>,
+    pub entities:   BTreeMap<u64, Map<String, Value>>,
+}
+
+impl Snapshot {
+    /// Query the world and record the result
+    pub async fn capture(
+        name: String,
+        filter: Option<Value>,
+        components: Option<Vec<String>>,
+        port: u16,
+    ) -> Result<Self> {
+        let rows = query_entities(filter.as_ref(), components.clone(), port).await?;
+        let entities = rows
+            .into_iter()
+            .filter_map(|row| {
+                let entity = row.get(JSON_FIELD_ENTITY)?.as_u64()?;
+                let components = row
+                    .get(JSON_FIELD_COMPONENTS)
+                    .and_then(Value::as_object)
+                    .cloned()
+                    .unwrap_or_default();
+                Some((entity, components))
+            })
+            .collect();
+
+        Ok(Self {
+            name,
+            created_at: chrono::Local::now().to_rfc3339(),
+            port,
+            filter,
+            components,
+            entities,
+        })
+    }
+
+    /// Number of component values in the snapshot
+    pub fn component_count(&self) -> usize {
+        self.entities.values().map(Map::len).sum()
+    }
+
+    /// File a snapshot of a project is saved to, or `None` when persistence is disabled
+    ///
+    /// Without a project root the snapshot goes to the global state directory.
+    fn file_path(name: &str, project_root: Option<&Path>) -> Option<PathBuf> {
+        project_root
+            .map_or_else(StateDir::global, StateDir::for_project)
+            .path()
+            .map(|dir| dir.join(SNAPSHOTS_DIR_NAME).join(format!("{name}.json")))
+    }
+
+    /// Save the snapshot to the state directory of a project, returning the file written
+    pub fn save(&self, project_root: Option<&Path>) -> Result<PathBuf> {
+        let path = Self::file_path(&self.name, project_root).ok_or_else(|| {
+            error_stack::Report::new(Error::invalid(
+                "parameter 'persist'",
+                "persistent state is disabled (BRP_MCP_STATE_DIR=off)",
+            ))
+        })?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                error_stack::Report::new(Error::io_failed("create snapshot directory", dir, &e))
+            })?;
+        }
+        let contents = serde_json::to_string(self).map_err(|e| {
+            error_stack::Report::new(Error::FileOperation(format!(
+                "Failed to serialize snapshot '{}': {e}",
+                self.name
+            )))
+        })?;
+        std::fs::write(&path, contents)
+            .map_err(|e| error_stack::Report::new(Error::io_failed("write snapshot", &path, &e)))?;
+        Ok(path)
+    }
+
+    /// Load a saved snapshot of a project, or `None` if there is none with that name
+    ///
+    /// Snapshots saved without a project root are found from any project.
+    fn load(name: &str, project_root: Option<&Path>) -> Result<Option<Self>> {
+        let Some(path) = Self::file_path(name, project_root)
+            .filter(|path| path.exists())
+            .or_else(|| Self::file_path(name, None).filter(|path| path.exists()))
+        else {
+            return Ok(None);
+        };
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| error_stack::Report::new(Error::io_failed("read snapshot", &path, &e)))?;
+        serde_json::from_str(&contents).map(Some).map_err(|e| {
+            error_stack::Report::new(Error::FileOperation(format!(
+                "Failed to parse snapshot {}: {e}",
+                path.display()
+            )))
+        })
+    }
+}
+
+/// Check that a snapshot name is usable as a file name
+pub fn validate_name(name: &str) -> Result<()> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.')
+    {
+        Ok(())
+    } else {
+        Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_NAME}'"),
+            format!("'{name}' may only contain letters, digits, '-', '_' and '.'"),
+        )))
+    }
+}
+
+/// Per-session store of recent snapshots
+pub struct SnapshotStore {
+    snapshots: HashMap<String, Snapshot>,
+    /// Names in insertion order, for eviction
+    order:     VecDeque<String>,
+    next_id:   u64,
+}
+
+impl SnapshotStore {
+    pub fn new() -> Self {
+        Self {
+            snapshots: HashMap::new(),
+            order:     VecDeque::new(),
+            next_id:   1,
+        }
+    }
+
+    /// A name for a snapshot the caller didn't name
+    pub fn next_name(&mut self) -> String {
+        let name = format!("snapshot-{}", self.next_id);
+        self.next_id += 1;
+        name
+    }
+
+    /// Keep a snapshot, replacing any earlier one with the same name
+    pub fn insert(&mut self, snapshot: Snapshot) {
+        self.order.retain(|name| *name != snapshot.name);
+        self.order.push_back(snapshot.name.clone());
+        self.snapshots.insert(snapshot.name.clone(), snapshot);
+        while self.order.len() > MAX_SNAPSHOTS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.snapshots.remove(&oldest);
+            }
+        }
+    }
+
+    /// Find a snapshot in memory, falling back to the saved snapshots of a project
+    pub fn find(&self, name: &str, project_root: Option<&Path>) -> Result<Option<Snapshot>> {
+        self.snapshots.get(name).map_or_else(
+            || Snapshot::load(name, project_root),
+            |snapshot| Ok(Some(snapshot.clone())),
+        )
+    }
+}
+
+/// Structural diff between two snapshots
+pub fn diff_snapshots(from: &Snapshot, to: &Snapshot) -> Value {
+    let component_names =
+        |components: &Map<String, Value>| -> Vec<String> { components.keys().cloned().collect() };
+
+    let added: Vec<Value> = to
+        .entities
+        .iter()
+        .filter(|(entity, _)| !from.entities.contains_key(entity))
+        .map(|(entity, components)| {
+            json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: components })
+        })
+        .collect();
+    let removed: Vec<Value> = from
+        .entities
+        .iter()
+        .filter(|(entity, _)| !to.entities.contains_key(entity))
+        .map(|(entity, components)| {
+            json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: component_names(components) })
+        })
+        .collect();
+
+    let mut changed = Vec::new();
+    for (entity, old) in &from.entities {
+        let Some(new) = to.entities.get(entity) else {
+            continue;
+        };
+        let added_components: Map<String, Value> = new
+            .iter()
+            .filter(|(name, _)| !old.contains_key(*name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let removed_components: Vec<&String> =
+            old.keys().filter(|name| !new.contains_key(*name)).collect();
+        let changed_components: Map<String, Value> = old
+            .iter()
+            .filter_map(|(name, old_value)| {
+                let delta = diff_values(old_value, new.get(name)?)?;
+                Some((name.clone(), delta))
+            })
+            .collect();
+
+        if !added_components.is_empty()
+            || !removed_components.is_empty()
+            || !changed_components.is_empty()
+        {
+            changed.push(json!({
+                JSON_FIELD_ENTITY: entity,
+                "added_components": added_components,
+                "removed_components": removed_components,
+                "changed_components": changed_components,
+            }));
+        }
+    }
+
+    json!({
+        "added_entities": added,
+        "removed_entities": removed,
+        "changed_entities": changed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn snapshot(name: &str, entities: &Value) -> Snapshot {
+        let entities = entities
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(entity, components)| {
+                (
+                    entity.parse().unwrap(),
+                    components.as_object().unwrap().clone(),
+                )
+            })
+            .collect();
+        Snapshot {
+            name: name.to_string(),
+            created_at: String::new(),
+            port: 15702,
+            filter: None,
+            components: None,
+            entities,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_entities_and_components() {
+        let before = snapshot(
+            "before",
+            &json!({
+                "1": { "Health": { "current": 10, "max": 10 }, "Stunned": {} },
+                "2": { "Name": "Goblin" }
+            }),
+        );
+        let after = snapshot(
+            "after",
+            &json!({
+                "1": { "Health": { "current": 7, "max": 10 }, "Burning": 3 },
+                "3": { "Name": "Arrow" }
+            }),
+        );
+
+        let diff = diff_snapshots(&before, &after);
+        assert_eq!(diff["added_entities"][0]["entity"], 3);
+        assert_eq!(diff["removed_entities"][0]["components"], json!(["Name"]));
+        assert_eq!(
+            diff["changed_entities"],
+            json!([{
+                "entity": 1,
+                "added_components": { "Burning": 3 },
+                "removed_components": ["Stunned"],
+                "changed_components": { "Health": { "current": 7 } }
+            }])
+        );
+        assert_eq!(
+            diff_snapshots(&before, &before),
+            json!({ "added_entities": [], "removed_entities": [], "changed_entities": [] })
+        );
+    }
+
+    #[test]
+    fn test_store_evicts_oldest_and_names_are_validated() {
+        let mut store = SnapshotStore::new();
+        for index in 0..=MAX_SNAPSHOTS {
+            store.insert(snapshot(&format!("s{index}"), &json!({})));
+        }
+        assert!(!store.snapshots.contains_key("s0"));
+        assert!(store.snapshots.contains_key(&format!("s{MAX_SNAPSHOTS}")));
+
+        assert!(validate_name("before-jump_2").is_ok());
+        assert!(validate_name("../escape").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use futures::future::join_all;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{
+    PARAM_STATE, RegisteredState, discover_states, find_state, pending_transition, read_resource,
+};
+use crate::brp_tools::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::{Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_GET_STATES, TOOL_BEVY_GET_STATES};
+use crate::{BrpMcpService, config};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_GET_STATES.into(),
+        description:  DESC_BEVY_GET_STATES.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_STATE,
+                "Only this state, by type path or short name, e.g. AppState",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let state = arguments.get(PARAM_STATE).and_then(Value::as_str);
+
+    let response = get_states(state, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Read the current value and any pending transition of the registered states
+async fn get_states(name: Option<&str>, port: u16) -> Result<JsonResponse> {
+    let registered = discover_states(port).await?;
+    let states: Vec<&RegisteredState> = match name {
+        Some(name) => vec![find_state(&registered, name)?],
+        None => registered.iter().collect(),
+    };
+
+    let states = join_all(states.into_iter().map(|state| read_state(state, port))).await;
+    let states = states.into_iter().collect::<Result<Vec<_>>>()?;
+    let active = states
+        .iter()
+        .filter(|state| !state["current"].is_null())
+        .count();
+    let message = if states.is_empty() {
+        "No states are registered for reflection".to_string()
+    } else {
+        format!("{} states, {active} active", states.len())
+    };
+
+    Ok(ResponseBuilder::success()
+        .message(message)
+        .data(json!({ "states": states, "count": states.len() }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// A state with its current value, `null` while it doesn't exist, and its pending transition
+async fn read_state(state: &RegisteredState, port: u16) -> Result<Value> {
+    let current = read_resource(&state.state_resource(), port).await?;
+    let next = if state.settable {
+        read_resource(&state.next_state_resource(), port).await?
+    } else {
+        None
+    };
+
+    let mut entry = json!(state);
+    entry["current"] = current.unwrap_or(Value::Null);
+    entry["pending"] = next
+        .as_ref()
+        .and_then(pending_transition)
+        .cloned()
+        .unwrap_or(Value::Null);
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::{BRP_METHOD_GET_RESOURCE, BRP_METHOD_REGISTRY_SCHEMA};
+
+    #[tokio::test]
+    async fn test_states_are_read_with_pending_transitions() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_REGISTRY_SCHEMA,
+                json!({
+                    "my_game::AppState": { "kind": "Enum", "oneOf": ["Menu", "InGame"] },
+                    "bevy_state::state::resources::State<my_game::AppState>": {},
+                    "bevy_state::state::resources::NextState<my_game::AppState>": {},
+                }),
+            )
+            .respond(BRP_METHOD_GET_RESOURCE, json!({ "value": "Menu" }))
+            .respond(
+                BRP_METHOD_GET_RESOURCE,
+                json!({ "value": { "Pending": "InGame" } }),
+            );
+
+        let response = get_states(Some("AppState"), server.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        let state = &response["data"]["states"][0];
+        assert_eq!(state["state"], "my_game::AppState");
+        assert_eq!(state["current"], "Menu");
+        assert_eq!(state["pending"], "InGame");
+        assert_eq!(state["variants"], json!(["Menu", "InGame"]));
+        assert_eq!(
+            server.requests_for(BRP_METHOD_GET_RESOURCE)[1].params,
+            Some(
+                json!({ "resource": "bevy_state::state::resources::NextState<my_game::AppState>" })
+            )
+        );
+    }
+}
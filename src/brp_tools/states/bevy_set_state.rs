@@ -0,0 +1,247 @@
+use std::time::{Duration, Instant};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{PARAM_STATE, discover_states, find_state, pending, pending_transition, read_resource};
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT, JSON_FIELD_RESOURCE,
+    JSON_FIELD_VALUE,
+};
+use crate::brp_tools::request_handler::execute_brp_method_with_format_discovery;
+use crate::brp_tools::support::brp_client::BrpResult;
+use crate::brp_tools::support::response_formatter::correction_fields;
+use crate::error::{Error, ErrorCode, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_INSERT_RESOURCE, DESC_BEVY_SET_STATE, TOOL_BEVY_SET_STATE};
+use crate::{BrpMcpService, config};
+
+/// Parameter choosing whether to wait for the transition to be applied
+const PARAM_WAIT: &str = "wait";
+
+/// Longest wait for the app to apply a transition
+const TRANSITION_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SET_STATE.into(),
+        description:  DESC_BEVY_SET_STATE.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_STATE,
+                "The state, by type path or short name, e.g. AppState",
+                true,
+            )
+            .add_any_property(
+                JSON_FIELD_VALUE,
+                "The state to transition to, e.g. \"InGame\" for a variant of an enum state",
+                true,
+            )
+            .add_boolean_property(
+                PARAM_WAIT,
+                "Wait for the app to apply the transition and report the new state (default: true)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let state = params::extract_required_string(&request, PARAM_STATE)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let wait = arguments
+        .get(PARAM_WAIT)
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    let Some(value) = arguments
+        .get(JSON_FIELD_VALUE)
+        .filter(|value| !value.is_null())
+        .cloned()
+    else {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{JSON_FIELD_VALUE}'"),
+                "give the state to transition to",
+            ),
+        )));
+    };
+
+    let response = set_state(&state, value, wait, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Request a transition by setting `NextState`, then wait for the app to apply it
+async fn set_state(name: &str, value: Value, wait: bool, port: u16) -> Result<JsonResponse> {
+    let states = discover_states(port).await?;
+    let state = find_state(&states, name)?;
+    if !state.settable {
+        return Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_STATE}'"),
+            format!(
+                "{} has no NextState registered, so it can't be set; computed states never can, \
+                 other states need app.register_type_mutable_state::<S>()",
+                state.type_path
+            ),
+        )));
+    }
+    let value = state.transition_value(value)?;
+
+    let state_resource = state.state_resource();
+    let next_state_resource = state.next_state_resource();
+    let previous = read_resource(&state_resource, port).await?;
+
+    let params = json!({
+        JSON_FIELD_RESOURCE: next_state_resource,
+        JSON_FIELD_VALUE: pending(&value),
+    });
+    let enhanced = execute_brp_method_with_format_discovery(
+        BRP_METHOD_INSERT_RESOURCE,
+        Some(params.clone()),
+        Some(port),
+        Vec::new(),
+    )
+    .await?;
+
+    let mut data = json!({
+        PARAM_STATE: state.type_path,
+        "requested": value,
+        "previous": previous,
+    });
+    for (field, value) in correction_fields(BRP_METHOD_INSERT_RESOURCE, &enhanced) {
+        data[field] = value;
+    }
+    if let BrpResult::Error(e) = enhanced.result {
+        data["params"] = params;
+        return Ok(ResponseBuilder::error()
+            .message(format!(
+                "Failed to request a transition of {}: {}",
+                state.type_path, e.message
+            ))
+            .error_code(ErrorCode::from_brp_code(
+                e.code,
+                !enhanced.format_corrections.is_empty(),
+            ))
+            .details(data)
+            .build());
+    }
+
+    let message = if wait {
+        let applied = wait_for_transition(&next_state_resource, port).await?;
+        let current = read_resource(&state_resource, port).await?;
+        data["applied"] = json!(applied);
+        let message = if applied {
+            format!(
+                "{} is now {}",
+                state.type_path,
+                current
+                    .as_ref()
+                    .map_or_else(|| "inactive".to_string(), Value::to_string)
+            )
+        } else {
+            format!(
+                "Requested {value} for {}, but the app didn't apply it within {}s; is it updating?",
+                state.type_path,
+                TRANSITION_TIMEOUT.as_secs()
+            )
+        };
+        data["current"] = current.unwrap_or(Value::Null);
+        message
+    } else {
+        format!(
+            "Requested {value} for {}; it applies in the app's next StateTransition",
+            state.type_path
+        )
+    };
+
+    Ok(ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// Wait until `NextState` no longer holds a pending transition, returning whether it was applied
+/// before the timeout
+async fn wait_for_transition(next_state_resource: &str, port: u16) -> Result<bool> {
+    let deadline = Instant::now() + TRANSITION_TIMEOUT;
+    while Instant::now() < deadline {
+        let next = read_resource(next_state_resource, port).await?;
+        if next.as_ref().and_then(pending_transition).is_none() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::{BRP_METHOD_GET_RESOURCE, BRP_METHOD_REGISTRY_SCHEMA};
+
+    #[tokio::test]
+    async fn test_transition_is_requested_and_awaited() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_REGISTRY_SCHEMA,
+                json!({
+                    "my_game::AppState": { "kind": "Enum", "oneOf": ["Menu", "InGame"] },
+                    "bevy_state::state::resources::State<my_game::AppState>": {},
+                    "bevy_state::state::resources::NextState<my_game::AppState>": {},
+                }),
+            )
+            .respond(BRP_METHOD_GET_RESOURCE, json!({ "value": "Menu" }))
+            .respond(
+                BRP_METHOD_GET_RESOURCE,
+                json!({ "value": { "Pending": "InGame" } }),
+            )
+            .respond(BRP_METHOD_GET_RESOURCE, json!({ "value": "Unchanged" }))
+            .respond(BRP_METHOD_GET_RESOURCE, json!({ "value": "InGame" }))
+            .respond(BRP_METHOD_INSERT_RESOURCE, Value::Null);
+
+        let response = set_state("AppState", json!("ingame"), true, server.port())
+            .await
+            .map(|response| serde_json::to_value(response).unwrap())
+            .unwrap();
+        let data = &response["data"];
+        assert_eq!(data["previous"], "Menu");
+        assert_eq!(data["current"], "InGame");
+        assert_eq!(data["applied"], true);
+        assert_eq!(
+            server.requests_for(BRP_METHOD_INSERT_RESOURCE)[0].params,
+            Some(json!({
+                "resource": "bevy_state::state::resources::NextState<my_game::AppState>",
+                "value": { "Pending": "InGame" },
+            }))
+        );
+
+        let unknown = set_state("AppState", json!("Paused"), false, server.port()).await;
+        assert!(unknown.is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! Reading and changing Bevy states
+//!
+//! A state `S` lives in the `State<S>` resource, and a transition is requested by setting
+//! `NextState<S>` to `Pending(..)`, which the app applies in its next `StateTransition` schedule.
+//! The states of an app are the `State<S>` types in its registry schema, so they have to be
+//! registered for reflection: `app.register_type_mutable_state::<S>()` registers both resources,
+//! `app.register_type_state::<S>()` only `State<S>`, leaving the state read-only, as computed
+//! states are. Sub-states have no `State<S>` while their source state doesn't allow them.
+
+pub mod bevy_get_states;
+pub mod bevy_set_state;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::constants::{JSON_FIELD_RESOURCE, JSON_FIELD_VALUE};
+use super::request_handler::registry_schema;
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result};
+use crate::support::fuzzy;
+use crate::tools::BRP_METHOD_GET_RESOURCE;
+
+/// Module path of the state resources
+const STATE_RESOURCES_PATH: &str = "bevy_state::state::resources::";
+
+/// Parameter naming a state
+pub const PARAM_STATE: &str = "state";
+
+/// Variant of `NextState` holding a requested transition
+const PENDING: &str = "Pending";
+
+/// A state type the app has registered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegisteredState {
+    #[serde(rename = "state")]
+    pub type_path: String,
+    /// Variants of an enum state, empty for other kinds
+    pub variants:  Vec<String>,
+    /// Whether `NextState` is registered, so a transition can be requested
+    pub settable:  bool,
+}
+
+impl RegisteredState {
+    /// Type path of the `State<S>` resource
+    pub fn state_resource(&self) -> String {
+        format!("{STATE_RESOURCES_PATH}State<{}>", self.type_path)
+    }
+
+    /// Type path of the `NextState<S>` resource
+    pub fn next_state_resource(&self) -> String {
+        format!("{STATE_RESOURCES_PATH}NextState<{}>", self.type_path)
+    }
+
+    /// Variant named by a value, in any case, if the value is a string and the state an enum
+    pub fn variant(&self, value: &Value) -> Option<&str> {
+        let name = value.as_str()?;
+        self.variants
+            .iter()
+            .find(|variant| variant.eq_ignore_ascii_case(name))
+            .map(String::as_str)
+    }
+
+    /// The value to transition to, with a variant name in the case the state declares it
+    pub fn transition_value(&self, value: Value) -> Result<Value> {
+        match (value.as_str(), self.variant(&value)) {
+            (Some(_), Some(variant)) => Ok(json!(variant)),
+            (Some(text), None) if !self.variants.is_empty() => {
+                Err(error_stack::Report::new(Error::invalid(
+                    &format!("parameter '{JSON_FIELD_VALUE}'"),
+                    format!(
+                        "{} has no variant '{text}'; variants: {}",
+                        self.type_path,
+                        self.variants.join(", ")
+                    ),
+                )))
+            }
+            _ => Ok(value),
+        }
+    }
+}
+
+/// The states in a registry schema, by type path
+pub fn registered_states(schemas: &Value) -> Vec<RegisteredState> {
+    let Some(schemas) = schemas.as_object() else {
+        return Vec::new();
+    };
+    let state_type = |type_path: &str, resource: &str| {
+        type_path
+            .strip_prefix(STATE_RESOURCES_PATH)?
+            .strip_prefix(resource)?
+            .strip_prefix('<')?
+            .strip_suffix('>')
+            .map(String::from)
+    };
+
+    let mut states: Vec<RegisteredState> = schemas
+        .keys()
+        .filter_map(|type_path| state_type(type_path, "State"))
+        .map(|type_path| {
+            let settable = schemas
+                .keys()
+                .any(|other| state_type(other, "NextState").is_some_and(|next| next == type_path));
+            let variants = schemas
+                .get(&type_path)
+                .and_then(|schema| schema["oneOf"].as_array())
+                .map(|variants| variants.iter().filter_map(variant_name).collect())
+                .unwrap_or_default();
+            RegisteredState {
+                type_path,
+                variants,
+                settable,
+            }
+        })
+        .collect();
+    states.sort_by(|a, b| a.type_path.cmp(&b.type_path));
+    states
+}
+
+/// Name of an enum variant in a schema's `oneOf`: a unit variant's name, or the `shortPath` of a
+/// variant with fields
+fn variant_name(variant: &Value) -> Option<String> {
+    variant
+        .as_str()
+        .or_else(|| variant["shortPath"].as_str())
+        .map(String::from)
+}
+
+/// The states the app has registered
+pub async fn discover_states(port: u16) -> Result<Vec<RegisteredState>> {
+    match registry_schema(json!({}), Some(port)).await? {
+        BrpResult::Success(Some(schemas)) => Ok(registered_states(&schemas)),
+        BrpResult::Success(None) => Ok(Vec::new()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to read the registry schema: {}",
+            e.message
+        )))),
+    }
+}
+
+/// The state named by a parameter, by type path or by its short name if only one state has it
+pub fn find_state<'a>(states: &'a [RegisteredState], name: &str) -> Result<&'a RegisteredState> {
+    if let Some(state) = states.iter().find(|state| state.type_path == name) {
+        return Ok(state);
+    }
+    let matching: Vec<&RegisteredState> = states
+        .iter()
+        .filter(|state| fuzzy::short_name(&state.type_path).eq_ignore_ascii_case(name))
+        .collect();
+    match matching.as_slice() {
+        [state] => Ok(state),
+        [] => {
+            let registered: Vec<&str> = states
+                .iter()
+                .map(|state| state.type_path.as_str())
+                .collect();
+            let details = if registered.is_empty() {
+                "the app has no states registered for reflection; register them with \
+                 app.register_type_mutable_state::<S>()"
+                    .to_string()
+            } else {
+                let suggestions = fuzzy::suggestions(name, registered.iter().copied());
+                format!(
+                    "no registered state is named '{name}'{}; registered states: {}",
+                    suggestions
+                        .first()
+                        .map_or_else(String::new, |closest| format!(", did you mean {closest}?")),
+                    registered.join(", ")
+                )
+            };
+            Err(error_stack::Report::new(Error::invalid(
+                &format!("parameter '{PARAM_STATE}'"),
+                details,
+            )))
+        }
+        _ => Err(error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_STATE}'"),
+            format!(
+                "'{name}' names several states, give the full type path of one of: {}",
+                matching
+                    .iter()
+                    .map(|state| state.type_path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ))),
+    }
+}
+
+/// The value of a resource, or `None` if the app doesn't have it
+pub async fn read_resource(resource: &str, port: u16) -> Result<Option<Value>> {
+    let params = json!({ JSON_FIELD_RESOURCE: resource });
+    match execute_brp_method(BRP_METHOD_GET_RESOURCE, Some(params), Some(port)).await? {
+        BrpResult::Success(data) => {
+            Ok(data.map(|data| data.get(JSON_FIELD_VALUE).cloned().unwrap_or(data)))
+        }
+        BrpResult::Error(_) => Ok(None),
+    }
+}
+
+/// The transition a `NextState` value requests, if any
+pub fn pending_transition(next_state: &Value) -> Option<&Value> {
+    next_state.get(PENDING)
+}
+
+/// `NextState` value requesting a transition to a state value
+pub fn pending(value: &Value) -> Value {
+    json!({ PENDING: value })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    const APP_STATE: &str = "my_game::AppState";
+
+    #[test]
+    fn test_states_are_found_in_the_registry_schema() {
+        let schemas = json!({
+            APP_STATE: {
+                "kind": "Enum",
+                "oneOf": ["Menu", "InGame", { "shortPath": "Paused", "kind": "Tuple" }],
+            },
+            "my_game::InMenu": { "kind": "Struct" },
+            "bevy_state::state::resources::State<my_game::AppState>": { "kind": "TupleStruct" },
+            "bevy_state::state::resources::NextState<my_game::AppState>": { "kind": "Enum" },
+            "bevy_state::state::resources::State<my_game::InMenu>": { "kind": "TupleStruct" },
+            "bevy_transform::components::transform::Transform": { "kind": "Struct" },
+        });
+
+        let states = registered_states(&schemas);
+        assert_eq!(
+            states,
+            vec![
+                RegisteredState {
+                    type_path: APP_STATE.to_string(),
+                    variants:  vec!["Menu".into(), "InGame".into(), "Paused".into()],
+                    settable:  true,
+                },
+                RegisteredState {
+                    type_path: "my_game::InMenu".to_string(),
+                    variants:  Vec::new(),
+                    settable:  false,
+                },
+            ]
+        );
+        assert_eq!(
+            states[0].next_state_resource(),
+            "bevy_state::state::resources::NextState<my_game::AppState>"
+        );
+        assert_eq!(states[0].variant(&json!("ingame")), Some("InGame"));
+        assert_eq!(
+            find_state(&states, "appstate").unwrap().type_path,
+            APP_STATE
+        );
+        assert!(find_state(&states, "GameState").is_err());
+        assert_eq!(
+            pending_transition(&pending(&json!("InGame"))),
+            Some(&json!("InGame"))
+        );
+        assert_eq!(pending_transition(&json!("Unchanged")), None);
+    }
+}
@@ -0,0 +1,44 @@
+//! Identifying the app answering on a BRP port
+//!
+//! Values read from an app, such as its registry schema or Bevy version, stay valid until the
+//! app goes away. An app is told apart by its host and port, and on the local host also by the
+//! PID of the managed app on the port, so an app relaunched or re-attached through this server
+//! counts as a new instance. Restarts of unmanaged apps can't be seen this way, so caches keyed
+//! by an instance also expire their entries.
+
+use crate::app_tools::support::managed_apps;
+use crate::brp_tools::constants::BRP_DEFAULT_HOST;
+use crate::brp_tools::support::brp_client;
+
+/// One app instance on a host and port
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppInstance {
+    pub host:     String,
+    pub port:     u16,
+    /// PID of the managed app on the port, if known
+    pub instance: Option<u32>,
+}
+
+impl AppInstance {
+    /// The app currently on a port of the current host
+    pub fn on_port(port: u16) -> Self {
+        let host = brp_client::current_host();
+        let instance = is_local_host(&host)
+            .then(|| managed_apps::find_by_port(port).map(|app| app.pid))
+            .flatten();
+        Self {
+            host,
+            port,
+            instance,
+        }
+    }
+
+    /// Whether both are on the same host and port, whatever the instance
+    pub fn same_connection(&self, other: &Self) -> bool {
+        self.host == other.host && self.port == other.port
+    }
+}
+
+fn is_local_host(host: &str) -> bool {
+    [BRP_DEFAULT_HOST, "127.0.0.1", "::1"].contains(&host)
+}
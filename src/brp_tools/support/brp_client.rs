@@ -5,17 +5,18 @@
 //! communication and returns structured results that can be formatted by
 //! higher-level tools.
 
-use std::time::Duration;
-
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::{debug, warn};
 
-use super::BrpJsonRpcBuilder;
+use super::http_client::{self, ConnectionSettings};
+use super::request_policy::{self, RequestPolicy};
+use super::response_parsing::{BrpResponse, parse_response_body};
+use super::{BrpJsonRpcBuilder, component_filter, host_detection};
 use crate::brp_tools::brp_set_debug_mode::is_debug_enabled;
 use crate::brp_tools::constants::{
-    BRP_DEFAULT_HOST, BRP_HTTP_PROTOCOL, BRP_JSONRPC_PATH, DEFAULT_BRP_PORT,
+    BRP_DEFAULT_HOST, BRP_ERROR_CODE_INVALID_REQUEST, BRP_HOST_ENV_VAR, BRP_JSONRPC_PATH,
 };
+use crate::config;
 use crate::error::{Error, Result};
 use crate::tools::BRP_EXTRAS_PREFIX;
 
@@ -36,31 +37,73 @@ pub struct BrpError {
     pub data:    Option<Value>,
 }
 
-/// Raw BRP JSON-RPC response structure
-#[derive(Debug, Serialize, Deserialize)]
-struct BrpResponse {
-    jsonrpc: String,
-    id:      u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    result:  Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error:   Option<JsonRpcError>,
+tokio::task_local! {
+    /// Host for BRP requests made while handling the current tool call
+    static REQUEST_HOST: String;
+}
+
+/// Run a future with all BRP requests it makes going to `host`
+pub async fn with_host<F: Future>(host: String, future: F) -> F::Output {
+    REQUEST_HOST.scope(host, future).await
+}
+
+/// Host BRP requests currently go to
+///
+/// This is the host of the current tool call if one was given, otherwise the `BRP_MCP_HOST`
+/// environment variable, otherwise localhost.
+pub fn current_host() -> String {
+    REQUEST_HOST.try_with(Clone::clone).unwrap_or_else(|_| {
+        std::env::var(BRP_HOST_ENV_VAR)
+            .ok()
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| BRP_DEFAULT_HOST.to_string())
+    })
 }
 
-/// Raw BRP error structure from JSON-RPC response
-#[derive(Debug, Serialize, Deserialize)]
-struct JsonRpcError {
-    code:    i32,
-    message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    data:    Option<Value>,
+/// Validate a user-supplied BRP host
+///
+/// Accepts host names, IPv4 addresses and IPv6 addresses (bracketed or not), but no scheme,
+/// port or path, since those are controlled separately.
+pub fn validate_host(host: &str) -> Result<String> {
+    let host = host.trim();
+    let bare = host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .unwrap_or(host);
+    let valid = !bare.is_empty()
+        && bare
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':' | '%'))
+        && (bare.parse::<std::net::Ipv6Addr>().is_ok() || !bare.contains(':'));
+
+    if valid {
+        Ok(bare.to_string())
+    } else {
+        Err(error_stack::Report::new(Error::invalid(
+            "host",
+            format!("'{host}' is not a host name or IP address (omit scheme, port and path)"),
+        )))
+    }
 }
 
-/// Build a BRP URL for the given port
+/// Build a BRP URL for the given port on the current host
 ///
 /// Constructs the full URL using standard BRP constants for consistent formatting
 pub fn build_brp_url(port: u16) -> String {
-    format!("{BRP_HTTP_PROTOCOL}://{BRP_DEFAULT_HOST}:{port}{BRP_JSONRPC_PATH}")
+    let host = current_host();
+    let scheme = ConnectionSettings::lookup(&host, port).scheme();
+    if host.contains(':') {
+        // IPv6 literals must be bracketed in URLs
+        format!("{scheme}://[{host}]:{port}{BRP_JSONRPC_PATH}")
+    } else {
+        format!("{scheme}://{host}:{port}{BRP_JSONRPC_PATH}")
+    }
+}
+
+/// Start a POST request to a BRP URL with the TLS settings and headers of its connection
+pub fn brp_post(url: &str, port: u16) -> Result<reqwest::RequestBuilder> {
+    http_client::post(url, &ConnectionSettings::lookup(&current_host(), port))
 }
 
 /// Execute a BRP method and return structured result
@@ -69,7 +112,14 @@ pub async fn execute_brp_method(
     params: Option<Value>,
     port: Option<u16>,
 ) -> Result<BrpResult> {
-    let port = port.unwrap_or(DEFAULT_BRP_PORT);
+    let port = port.unwrap_or_else(config::default_port);
+    if let Some(message) = component_filter::refusal(method, params.as_ref()) {
+        return Ok(BrpResult::Error(BrpError {
+            code: BRP_ERROR_CODE_INVALID_REQUEST,
+            message,
+            data: None,
+        }));
+    }
     let url = build_brp_url(port);
 
     if is_debug_enabled() {
@@ -85,14 +135,15 @@ pub async fn execute_brp_method(
     // Send HTTP request
     let response = send_http_request(&url, request_body, method, port).await?;
 
-    // Check HTTP status
-    check_http_status(&response, method, port)?;
-
-    // Parse JSON-RPC response
-    let brp_response = parse_json_response(response, method, port).await?;
+    // Parse JSON-RPC response, tolerating odd shapes and classifying unusable bodies
+    let brp_response = read_response(response, method, port).await?;
 
-    // Convert to structured result
-    Ok(convert_to_brp_result(brp_response, method))
+    // Convert to structured result, hiding filtered component types
+    let mut result = convert_to_brp_result(brp_response, method);
+    if let BrpResult::Success(Some(data)) = &mut result {
+        component_filter::apply(method, data);
+    }
+    Ok(result)
 }
 
 /// Build the JSON-RPC request body
@@ -117,103 +168,167 @@ fn build_request_body(method: &str, params: Option<Value>) -> String {
     request_body
 }
 
-/// Send the HTTP request to the BRP server
+/// Send the HTTP request to the BRP server, retrying as the current request policy allows
 async fn send_http_request(
     url: &str,
     request_body: String,
     method: &str,
     port: u16,
 ) -> Result<reqwest::Response> {
-    let client = super::http_client::get_client();
-
-    if is_debug_enabled() {
-        debug!("BRP execute_brp_method: Sending HTTP request...");
-    }
-
-    let response = client
-        .post(url)
-        .header("Content-Type", "application/json")
-        .body(request_body)
-        .timeout(Duration::from_secs(30))
-        .send()
-        .await;
+    let policy = request_policy::current();
+    let mut attempt = 0;
+    loop {
+        if is_debug_enabled() {
+            debug!(
+                "BRP execute_brp_method: Sending HTTP request (attempt {})...",
+                attempt + 1
+            );
+        }
 
-    match response {
-        Ok(resp) => {
-            if is_debug_enabled() {
-                debug!(
-                    "BRP execute_brp_method: HTTP request successful - status={}",
-                    resp.status()
+        let response = brp_post(url, port)?
+            .header("Content-Type", "application/json")
+            .body(request_body.clone())
+            .timeout(policy.timeout)
+            .send()
+            .await;
+
+        match response {
+            Ok(resp) => {
+                if is_debug_enabled() {
+                    debug!(
+                        "BRP execute_brp_method: HTTP request successful - status={}",
+                        resp.status()
+                    );
+                }
+                return Ok(resp);
+            }
+            Err(e)
+                if (e.is_connect() || e.is_timeout())
+                    && policy.should_retry(attempt, e.is_timeout()) =>
+            {
+                let delay = policy.backoff_delay(attempt);
+                warn!(
+                    "BRP {} request failed ({}), retrying in {}ms",
+                    method,
+                    e,
+                    delay.as_millis()
                 );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
             }
-            Ok(resp)
-        }
-        Err(e) => {
-            if is_debug_enabled() {
-                warn!("BRP execute_brp_method: HTTP request failed - error={}", e);
+            Err(e) => {
+                if is_debug_enabled() {
+                    warn!("BRP execute_brp_method: HTTP request failed - error={}", e);
+                }
+                return Err(request_error(&e, &policy, attempt + 1, url, method, port).await);
             }
-            Err(
-                error_stack::Report::new(Error::JsonRpc("HTTP request failed".to_string()))
-                    .attach_printable(format!("Method: {method}, Port: {port}, URL: {url}"))
-                    .attach_printable(format!("Error: {e}")),
-            )
         }
     }
 }
 
-/// Check if the HTTP response status is successful
-fn check_http_status(response: &reqwest::Response, method: &str, port: u16) -> Result<()> {
-    if !response.status().is_success() {
-        if is_debug_enabled() {
-            warn!(
-                "BRP execute_brp_method: HTTP status error - status={}",
-                response.status()
-            );
+/// The error of a request that failed for good
+async fn request_error(
+    e: &reqwest::Error,
+    policy: &RequestPolicy,
+    attempts: u32,
+    url: &str,
+    method: &str,
+    port: u16,
+) -> error_stack::Report<Error> {
+    let error = if e.is_timeout() {
+        Error::Timeout {
+            message: format!(
+                "{method} got no answer within {}ms (attempts: {attempts})",
+                policy.timeout.as_millis()
+            ),
+            timeout_ms: u64::try_from(policy.timeout.as_millis()).unwrap_or(u64::MAX),
+            attempts,
         }
-        return Err(
-            error_stack::Report::new(Error::JsonRpc("HTTP error".to_string()))
-                .attach_printable(format!(
-                    "BRP server returned HTTP error {}: {}",
-                    response.status(),
-                    response
-                        .status()
-                        .canonical_reason()
-                        .unwrap_or("Unknown error")
-                ))
-                .attach_printable(format!("Method: {method}, Port: {port}")),
+    } else {
+        // When the app answers on another interface, say which one to use
+        let remediation = if e.is_connect() {
+            host_detection::remediation(port).await
+        } else {
+            None
+        };
+        let message = remediation.map_or_else(
+            || "HTTP request failed".to_string(),
+            |remediation| format!("HTTP request failed. {remediation}"),
         );
-    }
-
-    if is_debug_enabled() {
-        debug!("BRP execute_brp_method: HTTP status OK, parsing JSON response...");
-    }
-
-    Ok(())
+        if e.is_connect() {
+            Error::ConnectionRefused(message)
+        } else {
+            Error::JsonRpc(message)
+        }
+    };
+    error_stack::Report::new(error)
+        .attach_printable(format!("Method: {method}, Port: {port}, URL: {url}"))
+        .attach_printable(format!("Error: {e}"))
+        .attach_printable(format!("Attempts: {attempts}"))
 }
 
-/// Parse the JSON response from the BRP server
-async fn parse_json_response(
+/// Read the response body and parse it leniently into a JSON-RPC envelope
+///
+/// A JSON-RPC envelope is used even when the HTTP status signals an error. Bodies that cannot
+/// be used are classified (empty, HTML error page, invalid JSON, ...) with a size-limited
+/// preview attached to the error.
+async fn read_response(
     response: reqwest::Response,
     method: &str,
     port: u16,
 ) -> Result<BrpResponse> {
-    match response.json().await {
-        Ok(json_resp) => {
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+    if !status.is_success() && is_debug_enabled() {
+        warn!(
+            "BRP execute_brp_method: HTTP status error - status={}",
+            status
+        );
+    }
+
+    let body = response.bytes().await.map_err(|e| {
+        error_stack::Report::new(Error::JsonRpc("Failed to read response body".to_string()))
+            .attach_printable(format!("Method: {method}, Port: {port}"))
+            .attach_printable(format!("Error: {e}"))
+    })?;
+
+    match parse_response_body(&body, content_type.as_deref()) {
+        Ok(brp_response) => {
             if is_debug_enabled() {
                 debug!("BRP execute_brp_method: JSON parsing successful");
             }
-            Ok(json_resp)
+            Ok(brp_response)
         }
-        Err(e) => {
+        Err(malformed) => {
             if is_debug_enabled() {
-                warn!("BRP execute_brp_method: JSON parsing failed - error={}", e);
+                warn!(
+                    "BRP execute_brp_method: Malformed response - kind={}, status={}",
+                    malformed.kind(),
+                    status
+                );
             }
-            Err(
-                error_stack::Report::new(Error::JsonRpc("JSON parsing failed".to_string()))
-                    .attach_printable("Failed to parse BRP response JSON")
-                    .attach_printable(format!("Method: {method}, Port: {port}"))
-                    .attach_printable(format!("Error: {e}")),
-            )
+            let report = if status.is_success() {
+                error_stack::Report::new(Error::JsonRpc(format!(
+                    "Malformed BRP response ({})",
+                    malformed.kind()
+                )))
+            } else {
+                error_stack::Report::new(Error::JsonRpc("HTTP error".to_string())).attach_printable(
+                    format!(
+                        "BRP server returned HTTP error {}: {}",
+                        status,
+                        status.canonical_reason().unwrap_or("Unknown error")
+                    ),
+                )
+            };
+            Err(report
+                .attach_printable(malformed.describe())
+                .attach_printable(format!("Method: {method}, Port: {port}")))
         }
     }
 }
@@ -269,7 +384,133 @@ fn convert_to_brp_result(brp_response: BrpResponse, method: &str) -> BrpResult {
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use std::time::Duration;
+
+    use serde_json::json;
+
     use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    /// A policy that gives up on an answer quickly
+    const fn impatient_policy(retry_timeouts: bool) -> RequestPolicy {
+        RequestPolicy {
+            timeout: Duration::from_millis(200),
+            retries: 1,
+            backoff: Duration::from_millis(10),
+            retry_timeouts,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_brp_method_against_mock_server() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                "bevy/list",
+                json!(["bevy_transform::components::transform::Transform"]),
+            )
+            .fail("bevy/get", -23401, "Entity 42 does not exist");
+
+        let result = execute_brp_method("bevy/list", None, Some(server.port()))
+            .await
+            .unwrap();
+        assert!(
+            matches!(&result, BrpResult::Success(Some(data)) if data[0] == "bevy_transform::components::transform::Transform"),
+            "{result:?}"
+        );
+
+        let params = json!({ "entity": 42, "components": [] });
+        let result = execute_brp_method("bevy/get", Some(params.clone()), Some(server.port()))
+            .await
+            .unwrap();
+        assert!(
+            matches!(&result, BrpResult::Error(error) if error.code == -23401),
+            "{result:?}"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "bevy/get");
+        assert_eq!(requests[1].params, Some(params));
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_requests_are_retried_when_the_policy_allows() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond_after("bevy/list", Duration::from_secs(2), json!([]))
+            .respond("bevy/list", json!(["late"]));
+
+        let result = request_policy::scope(
+            impatient_policy(true),
+            execute_brp_method("bevy/list", None, Some(server.port())),
+        )
+        .await
+        .unwrap();
+        assert!(
+            matches!(&result, BrpResult::Success(Some(data)) if data[0] == "late"),
+            "{result:?}"
+        );
+        assert_eq!(server.requests_for("bevy/list").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_requests_are_not_retried_otherwise() {
+        let server = MockBrpServer::start().await;
+        server.respond_after("bevy/spawn", Duration::from_secs(2), json!({ "entity": 1 }));
+
+        let error = request_policy::scope(
+            impatient_policy(false),
+            execute_brp_method(
+                "bevy/spawn",
+                Some(json!({ "components": {} })),
+                Some(server.port()),
+            ),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(error.current_context(), Error::Timeout { attempts: 1, .. }),
+            "{error:?}"
+        );
+        assert_eq!(server.requests_for("bevy/spawn").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_build_brp_url_uses_request_host() {
+        let url = with_host("192.168.1.20".to_string(), async { build_brp_url(15702) }).await;
+        assert_eq!(url, "http://192.168.1.20:15702/jsonrpc");
+
+        let url = with_host("fe80::1".to_string(), async { build_brp_url(15702) }).await;
+        assert_eq!(url, "http://[fe80::1]:15702/jsonrpc");
+    }
+
+    #[test]
+    fn test_validate_host() {
+        for (host, expected) in [
+            ("localhost", "localhost"),
+            (" game-box.local ", "game-box.local"),
+            ("10.0.0.5", "10.0.0.5"),
+            ("[::1]", "::1"),
+        ] {
+            assert_eq!(
+                validate_host(host).ok().as_deref(),
+                Some(expected),
+                "{host}"
+            );
+        }
+        for host in [
+            "",
+            "http://localhost",
+            "localhost:15702",
+            "host/path",
+            "a b",
+        ] {
+            assert!(validate_host(host).is_err(), "'{host}' should be rejected");
+        }
+    }
 
     #[test]
     fn test_brp_error_creation() {
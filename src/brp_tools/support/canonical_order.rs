@@ -0,0 +1,86 @@
+//! Canonical ordering of BRP results
+//!
+//! Object keys always come out sorted, since `serde_json` maps are ordered by key. What is left
+//! arbitrary is the order of result rows and type lists, which follows the app's archetype and
+//! registry layout and changes from run to run. [`canonicalize`] sorts those, so identical world
+//! states produce identical responses, deltas and watch logs:
+//!
+//! - query rows by entity ID
+//! - type lists (`bevy/list`, `bevy/list_resources`, watch `added`/`removed`) by name
+//! - registry schema arrays by `typePath`
+//!
+//! Arrays inside component values are never reordered, since their order is part of the data.
+
+use serde_json::Value;
+
+use crate::brp_tools::constants::JSON_FIELD_ENTITY;
+use crate::tools::{
+    BRP_METHOD_GET_WATCH, BRP_METHOD_LIST, BRP_METHOD_LIST_RESOURCES, BRP_METHOD_LIST_WATCH,
+    BRP_METHOD_QUERY, BRP_METHOD_REGISTRY_SCHEMA,
+};
+
+/// Sort the rows and type lists of a BRP method's result
+pub fn canonicalize(method: &str, data: &mut Value) {
+    match method {
+        m if m == BRP_METHOD_QUERY => {
+            if let Value::Array(rows) = data {
+                rows.sort_by_key(|row| row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64));
+            }
+        }
+        m if m == BRP_METHOD_LIST || m == BRP_METHOD_LIST_RESOURCES => sort_names(data),
+        m if m == BRP_METHOD_LIST_WATCH || m == BRP_METHOD_GET_WATCH => {
+            for field in ["added", "removed"] {
+                if let Some(names) = data.get_mut(field) {
+                    sort_names(names);
+                }
+            }
+        }
+        m if m == BRP_METHOD_REGISTRY_SCHEMA => {
+            if let Value::Array(schemas) = data {
+                schemas.sort_by(|a, b| a["typePath"].as_str().cmp(&b["typePath"].as_str()));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sort an array of type names, leaving it alone if it holds anything else
+fn sort_names(names: &mut Value) {
+    let Value::Array(names) = names else {
+        return;
+    };
+    if names.iter().all(Value::is_string) {
+        names.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_rows_and_type_lists_are_sorted() {
+        let mut rows = json!([
+            { "entity": 7, "components": { "Path": { "points": [3, 1, 2] } } },
+            { "entity": 2, "components": {} }
+        ]);
+        canonicalize(BRP_METHOD_QUERY, &mut rows);
+        assert_eq!(rows[0]["entity"], 2);
+        // Arrays inside component values keep their order
+        assert_eq!(rows[1]["components"]["Path"]["points"], json!([3, 1, 2]));
+
+        let mut list = json!(["game::Zed", "bevy::Alpha"]);
+        canonicalize(BRP_METHOD_LIST, &mut list);
+        assert_eq!(list, json!(["bevy::Alpha", "game::Zed"]));
+
+        let mut watch = json!({ "added": ["b", "a"], "removed": [] });
+        canonicalize(BRP_METHOD_LIST_WATCH, &mut watch);
+        assert_eq!(watch, json!({ "added": ["a", "b"], "removed": [] }));
+
+        let mut other = json!(["b", "a"]);
+        canonicalize("bevy/get_resource", &mut other);
+        assert_eq!(other, json!(["b", "a"]));
+    }
+}
@@ -0,0 +1,112 @@
+//! BRP methods an app supports
+//!
+//! Read from `rpc.discover`, so tools built on optional methods, from `bevy_brp_extras` or newer
+//! Bevy versions, can check for them first and explain what's missing instead of failing with a
+//! bare "method not found".
+
+use serde_json::{Value, json};
+
+use super::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, ErrorCode, Result};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::tools::{BRP_EXTRAS_PREFIX, BRP_METHOD_RPC_DISCOVER};
+
+/// The sorted method names of an `rpc.discover` document
+pub fn method_names(document: &Value) -> Vec<String> {
+    let mut methods: Vec<String> = document
+        .get("methods")
+        .and_then(Value::as_array)
+        .map(|methods| {
+            methods
+                .iter()
+                .filter_map(|method| method.get("name").and_then(Value::as_str))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    methods.sort_unstable();
+    methods
+}
+
+/// The methods the app on a port supports
+pub async fn supported_methods(port: u16) -> Result<Vec<String>> {
+    match execute_brp_method(BRP_METHOD_RPC_DISCOVER, None, Some(port)).await? {
+        BrpResult::Success(Some(document)) => Ok(method_names(&document)),
+        BrpResult::Success(None) => Ok(Vec::new()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "rpc.discover failed on port {port}: {}",
+            e.message
+        )))),
+    }
+}
+
+/// The first of several equivalent methods the app supports
+pub fn first_supported<'a>(methods: &[String], candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .copied()
+        .find(|candidate| methods.iter().any(|method| method == candidate))
+}
+
+/// Error response for a feature none of whose methods the app supports
+pub fn unsupported_response(
+    feature: &str,
+    candidates: &[&str],
+    methods: &[String],
+) -> JsonResponse {
+    let guidance = if candidates
+        .iter()
+        .any(|candidate| candidate.starts_with(BRP_EXTRAS_PREFIX))
+    {
+        "Add the bevy_brp_extras crate to the app with the BrpExtrasPlugin, or use a Bevy version that provides one of these methods"
+    } else {
+        "Use a Bevy version that provides one of these methods"
+    };
+    ResponseBuilder::error()
+        .message(format!(
+            "The app doesn't support {feature}: none of {} is listed by rpc.discover",
+            candidates.join(", ")
+        ))
+        .error_code(ErrorCode::MethodNotSupported)
+        .details(json!({
+            "looked_for": candidates,
+            "extras_methods": methods
+                .iter()
+                .filter(|method| method.starts_with(BRP_EXTRAS_PREFIX))
+                .collect::<Vec<_>>(),
+            "guidance": guidance,
+        }))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_methods_are_read_and_looked_up() {
+        let document = json!({
+            "methods": [{ "name": "brp_extras/screenshot" }, { "name": "bevy/query" }, { "params": [] }],
+        });
+        let methods = method_names(&document);
+        assert_eq!(methods, vec!["bevy/query", "brp_extras/screenshot"]);
+
+        assert_eq!(
+            first_supported(&methods, &["bevy/screenshot", "brp_extras/screenshot"]),
+            Some("brp_extras/screenshot")
+        );
+        assert_eq!(
+            first_supported(&methods, &["brp_extras/list_systems"]),
+            None
+        );
+
+        let response =
+            unsupported_response("system listing", &["brp_extras/list_systems"], &methods);
+        assert_eq!(response.error_code, Some(ErrorCode::MethodNotSupported));
+        assert_eq!(
+            response.details.unwrap()["extras_methods"],
+            json!(["brp_extras/screenshot"])
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Component allow and deny lists applied to all outgoing data
+//!
+//! `BRP_MCP_COMPONENT_ALLOW` and `BRP_MCP_COMPONENT_DENY` hold comma-separated patterns. A pattern
+//! without `::` or `*` names a crate and matches every type in it; any other pattern is matched
+//! against the full type path, with `*` standing for any run of characters. When an allowlist is
+//! set only matching types are shown, and the denylist always wins.
+//!
+//! Query, get, list, resource list, registry schema and watch results are filtered as they arrive
+//! from BRP, so hidden types never reach the formatters. A resource value doesn't name its type,
+//! so reading a hidden resource is refused before it is requested. A tool call passing
+//! `admin_token` equal to `BRP_MCP_ADMIN_TOKEN` sees unfiltered data.
+
+use std::sync::LazyLock;
+
+use serde_json::Value;
+use tracing::info;
+
+use crate::brp_tools::constants::{JSON_FIELD_COMPONENTS, JSON_FIELD_RESOURCE};
+use crate::error::{Error, Result};
+use crate::support::secret;
+use crate::tools::{
+    BRP_METHOD_GET, BRP_METHOD_GET_RESOURCE, BRP_METHOD_GET_WATCH, BRP_METHOD_LIST,
+    BRP_METHOD_LIST_RESOURCES, BRP_METHOD_LIST_WATCH, BRP_METHOD_QUERY, BRP_METHOD_REGISTRY_SCHEMA,
+};
+
+/// Environment variable holding the component allowlist
+pub const COMPONENT_ALLOW_ENV_VAR: &str = "BRP_MCP_COMPONENT_ALLOW";
+
+/// Environment variable holding the component denylist
+pub const COMPONENT_DENY_ENV_VAR: &str = "BRP_MCP_COMPONENT_DENY";
+
+/// Environment variable holding the token that lifts the filter for a tool call
+pub const ADMIN_TOKEN_ENV_VAR: &str = "BRP_MCP_ADMIN_TOKEN";
+
+/// Tool argument carrying the admin token
+pub const PARAM_ADMIN_TOKEN: &str = "admin_token";
+
+/// Filter configured through the environment, if any
+static FILTER: LazyLock<Option<ComponentFilter>> = LazyLock::new(|| {
+    let filter = ComponentFilter::parse(
+        &std::env::var(COMPONENT_ALLOW_ENV_VAR).unwrap_or_default(),
+        &std::env::var(COMPONENT_DENY_ENV_VAR).unwrap_or_default(),
+    );
+    if filter.is_some() {
+        info!("Component allow/deny filtering is enabled");
+    }
+    filter
+});
+
+tokio::task_local! {
+    /// Whether the current tool call presented the admin token
+    static BYPASS: bool;
+}
+
+/// A type pattern from an allow or deny list
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pattern {
+    /// Every type of a crate
+    Crate(String),
+    /// A type path, where `*` matches any run of characters
+    Glob(String),
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Self {
+        if pattern.contains("::") || pattern.contains('*') {
+            Self::Glob(pattern.to_string())
+        } else {
+            Self::Crate(pattern.to_string())
+        }
+    }
+
+    fn matches(&self, type_name: &str) -> bool {
+        match self {
+            Self::Crate(name) => type_name
+                .strip_prefix(name.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::")),
+            Self::Glob(glob) => glob_matches(glob, type_name),
+        }
+    }
+}
+
+/// Whether a type matches a pattern written like the allow and deny list entries
+///
+/// A pattern without `::` or `*` names a crate; anything else is a glob over the type path.
+pub fn pattern_matches(pattern: &str, type_name: &str) -> bool {
+    Pattern::parse(pattern).matches(type_name)
+}
+
+/// Match `text` against a pattern in which `*` matches any run of characters
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let mut parts = glob.split('*');
+    let Some(first) = parts.next() else {
+        return text.is_empty();
+    };
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` at all: the whole text must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Component allow and deny lists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentFilter {
+    allow: Vec<Pattern>,
+    deny:  Vec<Pattern>,
+}
+
+impl ComponentFilter {
+    /// Build a filter from comma-separated allow and deny lists, or `None` if both are empty
+    fn parse(allow: &str, deny: &str) -> Option<Self> {
+        let patterns = |list: &str| -> Vec<Pattern> {
+            list.split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(Pattern::parse)
+                .collect()
+        };
+        let filter = Self {
+            allow: patterns(allow),
+            deny:  patterns(deny),
+        };
+        (!filter.allow.is_empty() || !filter.deny.is_empty()).then_some(filter)
+    }
+
+    /// Whether data of the type may be shown
+    pub fn allows(&self, type_name: &str) -> bool {
+        (self.allow.is_empty() || self.allow.iter().any(|pattern| pattern.matches(type_name)))
+            && !self.deny.iter().any(|pattern| pattern.matches(type_name))
+    }
+
+    /// Remove hidden types from the result of a BRP method
+    ///
+    /// Results of methods that carry no component data are left alone.
+    pub fn filter_result(&self, method: &str, data: &mut Value) {
+        match method {
+            m if m == BRP_METHOD_QUERY => {
+                for row in data.as_array_mut().into_iter().flatten() {
+                    self.retain_keys(row, &[JSON_FIELD_COMPONENTS, "has"]);
+                }
+            }
+            m if m == BRP_METHOD_GET || m == BRP_METHOD_GET_WATCH => {
+                self.retain_keys(data, &[JSON_FIELD_COMPONENTS, "errors"]);
+                self.retain_names(data, &["removed"]);
+            }
+            m if m == BRP_METHOD_LIST
+                || m == BRP_METHOD_LIST_WATCH
+                || m == BRP_METHOD_LIST_RESOURCES =>
+            {
+                if let Value::Array(types) = data {
+                    types.retain(|type_name| type_name.as_str().is_none_or(|t| self.allows(t)));
+                }
+                self.retain_names(data, &["added", "removed"]);
+            }
+            m if m == BRP_METHOD_REGISTRY_SCHEMA => match data {
+                Value::Object(schemas) => schemas.retain(|type_name, _| self.allows(type_name)),
+                Value::Array(schemas) => schemas
+                    .retain(|schema| schema["typePath"].as_str().is_none_or(|t| self.allows(t))),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Why a request is refused, if it reads a hidden resource
+    pub fn refusal(&self, method: &str, params: Option<&Value>) -> Option<String> {
+        if method != BRP_METHOD_GET_RESOURCE {
+            return None;
+        }
+        let resource = params?.get(JSON_FIELD_RESOURCE)?.as_str()?;
+        (!self.allows(resource))
+            .then(|| format!("{resource} is hidden by the component allow/deny filter"))
+    }
+
+    /// Drop hidden types from the maps under `fields`
+    fn retain_keys(&self, data: &mut Value, fields: &[&str]) {
+        for field in fields {
+            if let Some(map) = data.get_mut(*field).and_then(Value::as_object_mut) {
+                map.retain(|type_name, _| self.allows(type_name));
+            }
+        }
+    }
+
+    /// Drop hidden type names from the arrays under `fields`
+    fn retain_names(&self, data: &mut Value, fields: &[&str]) {
+        for field in fields {
+            if let Some(names) = data.get_mut(*field).and_then(Value::as_array_mut) {
+                names.retain(|name| name.as_str().is_none_or(|t| self.allows(t)));
+            }
+        }
+    }
+}
+
+/// Filter in effect for the current tool call, or `None` when nothing is filtered
+pub fn active() -> Option<&'static ComponentFilter> {
+    if is_bypassed() { None } else { FILTER.as_ref() }
+}
+
+/// Remove hidden types from a BRP result if a filter is in effect
+pub fn apply(method: &str, data: &mut Value) {
+    if let Some(filter) = active() {
+        filter.filter_result(method, data);
+    }
+}
+
+/// Why a request is refused by the filter in effect, if it reads a hidden resource
+pub fn refusal(method: &str, params: Option<&Value>) -> Option<String> {
+    active().and_then(|filter| filter.refusal(method, params))
+}
+
+/// Whether the current tool call presented the admin token
+pub fn is_bypassed() -> bool {
+    BYPASS.try_with(|bypass| *bypass).unwrap_or(false)
+}
+
+/// Run a future with the filter lifted (`bypass`) or in effect
+pub async fn scope<F: Future>(bypass: bool, future: F) -> F::Output {
+    BYPASS.scope(bypass, future).await
+}
+
+/// Check an `admin_token` argument against `BRP_MCP_ADMIN_TOKEN`
+pub fn check_admin_token(token: &Value) -> Result<()> {
+    let expected = std::env::var(ADMIN_TOKEN_ENV_VAR)
+        .ok()
+        .filter(|expected| !expected.is_empty());
+    match (expected, token.as_str()) {
+        (None, _) => Err(error_stack::Report::new(Error::invalid(
+            PARAM_ADMIN_TOKEN,
+            format!("not accepted because {ADMIN_TOKEN_ENV_VAR} is not set"),
+        ))),
+        (Some(expected), Some(token)) if secret::tokens_match(&expected, token) => Ok(()),
+        _ => Err(error_stack::Report::new(Error::invalid(
+            PARAM_ADMIN_TOKEN,
+            "does not match",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_patterns_match_crates_and_globs() {
+        let filter = ComponentFilter::parse(
+            "",
+            "game_server, *::Authority*, bevy_transform::components::global_transform::*",
+        )
+        .unwrap();
+
+        assert!(!filter.allows("game_server::health::ServerHealth"));
+        assert!(filter.allows("game_server_ui::Hud"));
+        assert!(!filter.allows("game::net::AuthorityToken"));
+        assert!(!filter.allows("bevy_transform::components::global_transform::GlobalTransform"));
+        assert!(filter.allows("bevy_transform::components::transform::Transform"));
+        assert_eq!(ComponentFilter::parse(" , ", ""), None);
+
+        let allow_only = ComponentFilter::parse("bevy_*", "bevy_render").unwrap();
+        assert!(allow_only.allows("bevy_core::name::Name"));
+        assert!(!allow_only.allows("game::Player"));
+        assert!(!allow_only.allows("bevy_render::view::Visibility"));
+    }
+
+    #[test]
+    fn test_results_are_filtered_by_method() {
+        let filter = ComponentFilter::parse("", "server").unwrap();
+
+        let mut rows = json!([{
+            "entity": 1,
+            "components": { "server::Secret": 1, "game::Health": 10 },
+            "has": { "server::Flag": true }
+        }]);
+        filter.filter_result(BRP_METHOD_QUERY, &mut rows);
+        assert_eq!(
+            rows,
+            json!([{ "entity": 1, "components": { "game::Health": 10 }, "has": {} }])
+        );
+
+        let mut list = json!(["server::Secret", "game::Health"]);
+        filter.filter_result(BRP_METHOD_LIST, &mut list);
+        assert_eq!(list, json!(["game::Health"]));
+
+        let mut watch = json!({
+            "components": { "server::Secret": 2 },
+            "removed": ["server::Secret", "game::Health"]
+        });
+        filter.filter_result(BRP_METHOD_GET_WATCH, &mut watch);
+        assert_eq!(
+            watch,
+            json!({ "components": {}, "removed": ["game::Health"] })
+        );
+
+        let mut resources = json!(["server::Config", "game::Score"]);
+        filter.filter_result(BRP_METHOD_LIST_RESOURCES, &mut resources);
+        assert_eq!(resources, json!(["game::Score"]));
+
+        let hidden = json!({ "resource": "server::Config" });
+        assert!(
+            filter
+                .refusal(BRP_METHOD_GET_RESOURCE, Some(&hidden))
+                .is_some()
+        );
+        let shown = json!({ "resource": "game::Score" });
+        assert!(
+            filter
+                .refusal(BRP_METHOD_GET_RESOURCE, Some(&shown))
+                .is_none()
+        );
+        assert!(filter.refusal(BRP_METHOD_GET, Some(&hidden)).is_none());
+    }
+}
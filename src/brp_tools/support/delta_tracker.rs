@@ -0,0 +1,236 @@
+//! Session-scoped tracking of query/get/list results for delta responses
+//!
+//! Every tracked call stores its full result under a fresh `delta_token`. Passing that token
+//! back on the next identical call (same method, parameters, host and port) returns only what
+//! changed since then instead of the full payload.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::{Map, Value, json};
+
+use super::json_diff::diff_values;
+use crate::brp_tools::constants::JSON_FIELD_ENTITY;
+
+/// Number of results kept per session; older tokens expire
+const MAX_SNAPSHOTS: usize = 32;
+
+/// A stored result
+struct Snapshot {
+    /// Identifies the request that produced the result
+    request: String,
+    data:    Value,
+}
+
+/// Outcome of recording a result
+pub struct Recorded {
+    /// Token for the result just recorded
+    pub token:    String,
+    /// The earlier result the supplied token refers to, if it is usable
+    pub previous: Option<Value>,
+    /// Why the supplied token could not be used
+    pub reset:    Option<String>,
+}
+
+/// Per-session store of recent results, keyed by delta token
+pub struct DeltaTracker {
+    snapshots:  HashMap<String, Snapshot>,
+    /// Tokens in insertion order, for eviction
+    order:      VecDeque<String>,
+    next_token: u64,
+}
+
+impl DeltaTracker {
+    pub fn new() -> Self {
+        Self {
+            snapshots:  HashMap::new(),
+            order:      VecDeque::new(),
+            next_token: 1,
+        }
+    }
+
+    /// Store `data` for `request`, resolving the token the caller supplied
+    pub fn record(&mut self, request: &str, token: Option<&str>, data: &Value) -> Recorded {
+        let (previous, reset) = match token.map(|token| (token, self.snapshots.get(token))) {
+            None => (None, None),
+            Some((token, None)) => (
+                None,
+                Some(format!(
+                    "delta_token '{token}' is unknown or expired; returning the full result"
+                )),
+            ),
+            Some((_, Some(snapshot))) if snapshot.request != request => (
+                None,
+                Some(
+                    "delta_token belongs to a different request; returning the full result"
+                        .to_string(),
+                ),
+            ),
+            Some((_, Some(snapshot))) => (Some(snapshot.data.clone()), None),
+        };
+
+        let token = format!("delta-{}", self.next_token);
+        self.next_token += 1;
+        self.snapshots.insert(
+            token.clone(),
+            Snapshot {
+                request: request.to_string(),
+                data:    data.clone(),
+            },
+        );
+        self.order.push_back(token.clone());
+        while self.order.len() > MAX_SNAPSHOTS {
+            if let Some(expired) = self.order.pop_front() {
+                self.snapshots.remove(&expired);
+            }
+        }
+
+        Recorded {
+            token,
+            previous,
+            reset,
+        }
+    }
+}
+
+/// Describe the changes from `previous` to `current`
+///
+/// - Arrays of entity rows (query results) report `added` rows, `removed` entity IDs and per-entity
+///   `changed` field deltas
+/// - Other arrays (component lists) report `added` and `removed` values
+/// - Anything else reports a merge-patch style `changed` delta
+///
+/// Returns `None` when nothing changed.
+pub fn compute_delta(previous: &Value, current: &Value) -> Option<Value> {
+    match (previous, current) {
+        (Value::Array(previous), Value::Array(current))
+            if previous.iter().chain(current).all(entity_id_present) =>
+        {
+            entity_rows_delta(previous, current)
+        }
+        (Value::Array(previous), Value::Array(current)) => {
+            let added: Vec<&Value> = current.iter().filter(|v| !previous.contains(v)).collect();
+            let removed: Vec<&Value> = previous.iter().filter(|v| !current.contains(v)).collect();
+            (!added.is_empty() || !removed.is_empty())
+                .then(|| json!({ "added": added, "removed": removed }))
+        }
+        _ => diff_values(previous, current).map(|changed| json!({ "changed": changed })),
+    }
+}
+
+fn entity_id_present(row: &Value) -> bool {
+    row.get(JSON_FIELD_ENTITY).is_some_and(Value::is_u64)
+}
+
+fn entity_rows_delta(previous: &[Value], current: &[Value]) -> Option<Value> {
+    let by_entity = |rows: &[Value]| -> HashMap<u64, Value> {
+        rows.iter()
+            .filter_map(|row| Some((row.get(JSON_FIELD_ENTITY)?.as_u64()?, row.clone())))
+            .collect()
+    };
+    let previous_rows = by_entity(previous);
+    let current_rows = by_entity(current);
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for row in current {
+        let Some(entity) = row.get(JSON_FIELD_ENTITY).and_then(Value::as_u64) else {
+            continue;
+        };
+        match previous_rows.get(&entity) {
+            None => added.push(row.clone()),
+            Some(old) => {
+                if let Some(Value::Object(delta)) = diff_values(old, row) {
+                    let mut entry = Map::new();
+                    entry.insert(JSON_FIELD_ENTITY.to_string(), Value::from(entity));
+                    entry.extend(delta);
+                    changed.push(Value::Object(entry));
+                }
+            }
+        }
+    }
+
+    let mut removed: Vec<u64> = previous_rows
+        .keys()
+        .filter(|entity| !current_rows.contains_key(entity))
+        .copied()
+        .collect();
+    removed.sort_unstable();
+
+    (!added.is_empty() || !removed.is_empty() || !changed.is_empty())
+        .then(|| json!({ "added": added, "removed": removed, "changed": changed }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_resolves_tokens() {
+        let mut tracker = DeltaTracker::new();
+        let first = tracker.record("bevy/list", None, &json!(["A"]));
+        assert!(first.previous.is_none() && first.reset.is_none());
+
+        let second = tracker.record("bevy/list", Some(&first.token), &json!(["A", "B"]));
+        assert_eq!(second.previous, Some(json!(["A"])));
+        assert_ne!(second.token, first.token);
+
+        let other = tracker.record("bevy/query", Some(&first.token), &json!([]));
+        assert!(other.previous.is_none() && other.reset.is_some());
+
+        let unknown = tracker.record("bevy/list", Some("delta-999"), &json!([]));
+        assert!(unknown.reset.is_some());
+    }
+
+    #[test]
+    fn test_old_tokens_expire() {
+        let mut tracker = DeltaTracker::new();
+        let first = tracker.record("bevy/list", None, &json!([]));
+        for _ in 0..MAX_SNAPSHOTS {
+            tracker.record("bevy/list", None, &json!([]));
+        }
+
+        assert!(
+            tracker
+                .record("bevy/list", Some(&first.token), &json!([]))
+                .reset
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_query_delta_by_entity() {
+        let previous = json!([
+            { "entity": 1, "components": { "Name": "a", "Health": 10 } },
+            { "entity": 2, "components": { "Name": "b" } }
+        ]);
+        let current = json!([
+            { "entity": 1, "components": { "Name": "a", "Health": 7 } },
+            { "entity": 3, "components": { "Name": "c" } }
+        ]);
+
+        assert_eq!(
+            compute_delta(&previous, &current),
+            Some(json!({
+                "added": [{ "entity": 3, "components": { "Name": "c" } }],
+                "removed": [2],
+                "changed": [{ "entity": 1, "components": { "Health": 7 } }]
+            }))
+        );
+        assert_eq!(compute_delta(&current, &current), None);
+    }
+
+    #[test]
+    fn test_list_and_get_deltas() {
+        assert_eq!(
+            compute_delta(&json!(["A", "B"]), &json!(["B", "C"])),
+            Some(json!({ "added": ["C"], "removed": ["A"] }))
+        );
+        assert_eq!(
+            compute_delta(
+                &json!({ "components": { "Transform": { "x": 1 } }, "errors": {} }),
+                &json!({ "components": { "Transform": { "x": 2 } }, "errors": {} })
+            ),
+            Some(json!({ "changed": { "components": { "Transform": { "x": 2 } } } }))
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Confirmation of destructive operations
+//!
+//! Destroying an entity, removing a resource and shutting down, killing or restarting an app can't
+//! be undone. The `destructive_ops` configuration key decides whether they run straight away
+//! (`allow`, the default) or need confirming (`confirm`). `brp_set_config` can only tighten the
+//! policy, so a client can't lift the confirmation it is asked for. A call that isn't confirmed
+//! gets a preview of what would be lost, such as the children destroyed with an entity, and a
+//! confirmation token; calling again with the same arguments and `confirm` set to the token runs
+//! it. A token only confirms calls from the MCP session it was issued to. `confirm: false` asks
+//! for the preview under either policy.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::bevy_get_hierarchy;
+use crate::brp_tools::constants::{JSON_FIELD_ENTITY, JSON_FIELD_RESOURCE, PARAM_CONFIRM};
+use crate::config;
+use crate::error::{Error, Result};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::session;
+use crate::tools::{BRP_METHOD_DESTROY, BRP_METHOD_GET_RESOURCE, BRP_METHOD_REMOVE_RESOURCE};
+
+/// BRP methods whose effects can't be undone
+pub const DESTRUCTIVE_METHODS: &[&str] = &[BRP_METHOD_DESTROY, BRP_METHOD_REMOVE_RESOURCE];
+
+/// Time a confirmation token stays valid
+const TOKEN_LIFETIME: Duration = Duration::from_secs(5 * 60);
+
+/// Operations previewed and awaiting confirmation, by token
+static PENDING: LazyLock<Mutex<HashMap<String, Pending>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether destructive operations run straight away or need confirming
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DestructiveOpsPolicy {
+    /// Run unless the call asks for a preview
+    #[default]
+    Allow,
+    /// Preview unless the call carries a confirmation token
+    Confirm,
+}
+
+/// The policy in effect, from the `destructive_ops` configuration key
+pub fn policy() -> DestructiveOpsPolicy {
+    config::current().destructive_ops.unwrap_or_default()
+}
+
+/// What a call said with `confirm`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Confirmation {
+    /// No `confirm` argument, or `true`
+    Unspecified,
+    /// `confirm: false`, asking for the preview only
+    PreviewOnly,
+    /// A confirmation token
+    Token(String),
+}
+
+/// An operation previewed and awaiting confirmation
+struct Pending {
+    operation:  String,
+    /// Session the token was issued to
+    session:    u64,
+    expires_at: Instant,
+}
+
+/// Remove `confirm` from a call's arguments
+pub fn take_confirmation(request: &mut CallToolRequestParam) -> Result<Confirmation> {
+    let confirm = request
+        .arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(PARAM_CONFIRM));
+    match confirm {
+        None | Some(Value::Null | Value::Bool(true)) => Ok(Confirmation::Unspecified),
+        Some(Value::Bool(false)) => Ok(Confirmation::PreviewOnly),
+        Some(Value::String(token)) => Ok(Confirmation::Token(token)),
+        Some(other) => Err(error_stack::Report::new(Error::invalid(
+            PARAM_CONFIRM,
+            format!("expected a confirmation token or false, got {other}"),
+        ))),
+    }
+}
+
+/// Why an operation is previewed instead of run, or `None` to run it
+///
+/// `operation` identifies the call, including its target and arguments, so a token only confirms
+/// the call it was issued for, made in the same session. A token is used up by the call it
+/// confirms.
+pub fn preview_reason(operation: &str, confirmation: &Confirmation) -> Option<String> {
+    match (policy(), confirmation) {
+        (_, Confirmation::PreviewOnly) => Some("confirm is false".to_string()),
+        (_, Confirmation::Token(token)) => (!redeem(token, operation)).then(|| {
+            format!("confirmation token '{token}' is unknown, expired or for a different operation")
+        }),
+        (DestructiveOpsPolicy::Allow, Confirmation::Unspecified) => None,
+        (DestructiveOpsPolicy::Confirm, Confirmation::Unspecified) => {
+            Some("the destructive_ops policy requires confirmation".to_string())
+        }
+    }
+}
+
+/// Use up a token if it confirms the operation in the current session
+fn redeem(token: &str, operation: &str) -> bool {
+    let Ok(mut pending) = PENDING.lock() else {
+        return false;
+    };
+    let now = Instant::now();
+    pending.retain(|_, entry| entry.expires_at > now);
+    let session = session::current();
+    if pending
+        .get(token)
+        .is_some_and(|entry| entry.operation == operation && entry.session == session)
+    {
+        pending.remove(token);
+        true
+    } else {
+        false
+    }
+}
+
+/// Issue a token confirming the operation in the current session
+fn issue(operation: &str) -> String {
+    let token = uuid::Uuid::new_v4().to_string();
+    if let Ok(mut pending) = PENDING.lock() {
+        pending.insert(
+            token.clone(),
+            Pending {
+                operation:  operation.to_string(),
+                session:    session::current(),
+                expires_at: Instant::now() + TOKEN_LIFETIME,
+            },
+        );
+    }
+    token
+}
+
+/// Response previewing an operation instead of running it, with a token confirming it
+pub fn preview_response(
+    operation: &str,
+    description: &str,
+    reason: &str,
+    preview: &Value,
+) -> CallToolResult {
+    let token = issue(operation);
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Preview only, {description} was not run because {reason}. Call again with the same \
+             arguments and confirm set to the confirmation_token to run it"
+        ))
+        .data(json!({
+            "dry_run": true,
+            "preview": preview,
+            "confirmation_token": token,
+            "expires_in_seconds": TOKEN_LIFETIME.as_secs(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    json_response_to_result(&response)
+}
+
+/// What a destructive BRP method would remove, read from the app
+///
+/// Destroying an entity destroys its descendants too, so they are listed with the entity.
+/// Failures to read are reported in the preview rather than failing it.
+pub async fn preview(method: &str, params: Option<&Value>, port: u16) -> Value {
+    let param = |field: &str| params.and_then(|params| params.get(field)).cloned();
+    if method == BRP_METHOD_DESTROY {
+        let Some(entity) = param(JSON_FIELD_ENTITY).as_ref().and_then(Value::as_u64) else {
+            return json!({ "error": "No entity to preview" });
+        };
+        match bevy_get_hierarchy::get_hierarchy(entity, u64::MAX, port).await {
+            Ok(hierarchy) => json!({
+                JSON_FIELD_ENTITY: entity,
+                "descendant_count": hierarchy.descendant_count(),
+                "tree": hierarchy.tree_json(entity),
+                "truncated": hierarchy.truncated(),
+            }),
+            Err(e) => json!({ JSON_FIELD_ENTITY: entity, "error": e.to_string() }),
+        }
+    } else if method == BRP_METHOD_REMOVE_RESOURCE {
+        let resource = param(JSON_FIELD_RESOURCE);
+        match execute_brp_method(
+            BRP_METHOD_GET_RESOURCE,
+            Some(json!({ JSON_FIELD_RESOURCE: resource })),
+            Some(port),
+        )
+        .await
+        {
+            Ok(BrpResult::Success(value)) => json!({
+                JSON_FIELD_RESOURCE: resource,
+                "value": value.and_then(|value| value.get("value").cloned()),
+            }),
+            Ok(BrpResult::Error(e)) => json!({ JSON_FIELD_RESOURCE: resource, "error": e.message }),
+            Err(e) => json!({ JSON_FIELD_RESOURCE: resource, "error": e.to_string() }),
+        }
+    } else {
+        params.cloned().unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_confirm_only_their_operation_once() {
+        let destroy = "bevy/destroy localhost:15702 {\"entity\":42}";
+        let token = issue(destroy);
+
+        let other = Confirmation::Token(token.clone());
+        assert!(preview_reason("bevy/destroy localhost:15702 {\"entity\":7}", &other).is_some());
+
+        let confirmed = Confirmation::Token(token);
+        assert_eq!(preview_reason(destroy, &confirmed), None);
+        assert!(preview_reason(destroy, &confirmed).is_some());
+
+        assert!(preview_reason(destroy, &Confirmation::PreviewOnly).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tokens_confirm_only_in_their_session() {
+        let destroy = "bevy/destroy localhost:15702 {\"entity\":43}";
+        let issuer = session::new_id();
+        let token = session::scope(issuer, async { issue(destroy) }).await;
+        let confirmed = Confirmation::Token(token);
+
+        let other = session::scope(session::new_id(), async {
+            preview_reason(destroy, &confirmed)
+        })
+        .await;
+        assert!(other.is_some());
+
+        let own = session::scope(issuer, async { preview_reason(destroy, &confirmed) }).await;
+        assert_eq!(own, None);
+    }
+}
@@ -0,0 +1,244 @@
+//! Detection of WSL and container environments and the hosts a BRP app may be reachable on
+//!
+//! Inside a dev container or WSL 2, `localhost` is the container or VM itself, so an app
+//! running on the machine around it is only reachable through another interface, such as
+//! `host.docker.internal` or the default gateway. [`host_candidates`] lists those hosts for the
+//! detected environment, [`probe_hosts`] checks which of them accept connections on the BRP
+//! port, and [`remediation`] turns a connection failure into advice on which host to use.
+
+use std::net::Ipv4Addr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use futures::future::join_all;
+use serde::Serialize;
+use tokio::net::TcpStream;
+
+use super::brp_client::current_host;
+use crate::brp_tools::constants::{BRP_DEFAULT_HOST, BRP_HOST_ENV_VAR};
+
+/// How long a single host may take to accept a connection
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Host name Docker Desktop (and Podman with `host.containers.internal`) resolves to the host
+const DOCKER_HOST_NAME: &str = "host.docker.internal";
+
+/// Environment the server runs in, detected once
+static ENVIRONMENT: LazyLock<RuntimeEnvironment> = LazyLock::new(|| {
+    let proc_version = std::fs::read_to_string("/proc/version").unwrap_or_default();
+    let in_wsl =
+        std::env::var_os("WSL_DISTRO_NAME").is_some() || std::env::var_os("WSL_INTEROP").is_some();
+    let in_container = ["/.dockerenv", "/run/.containerenv"]
+        .iter()
+        .any(|marker| std::path::Path::new(marker).exists())
+        || std::env::var_os("REMOTE_CONTAINERS").is_some()
+        || std::env::var_os("CODESPACES").is_some();
+    RuntimeEnvironment::classify(&proc_version, in_wsl, in_container)
+});
+
+/// Kind of environment the server runs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeEnvironment {
+    /// Directly on the machine running the app
+    Native,
+    /// Inside Windows Subsystem for Linux
+    Wsl,
+    /// Inside a Docker, Podman or dev container
+    Container,
+}
+
+impl RuntimeEnvironment {
+    fn classify(proc_version: &str, in_wsl: bool, in_container: bool) -> Self {
+        // A container can run under WSL; its own network namespace is what matters
+        if in_container {
+            Self::Container
+        } else if in_wsl || proc_version.to_ascii_lowercase().contains("microsoft") {
+            Self::Wsl
+        } else {
+            Self::Native
+        }
+    }
+}
+
+/// The detected environment
+pub fn environment() -> RuntimeEnvironment {
+    *ENVIRONMENT
+}
+
+/// Hosts a BRP app may be reachable on, starting with the configured one
+pub fn host_candidates() -> Vec<String> {
+    let mut candidates = vec![
+        current_host(),
+        BRP_DEFAULT_HOST.to_string(),
+        Ipv4Addr::LOCALHOST.to_string(),
+    ];
+    let route_table = std::fs::read_to_string("/proc/net/route").unwrap_or_default();
+    let gateway = default_gateway(&route_table).map(|gateway| gateway.to_string());
+    match environment() {
+        RuntimeEnvironment::Native => {}
+        RuntimeEnvironment::Container => {
+            candidates.push(DOCKER_HOST_NAME.to_string());
+            candidates.extend(gateway);
+        }
+        RuntimeEnvironment::Wsl => {
+            // WSL 2 points the resolver at the Windows host
+            let resolv_conf = std::fs::read_to_string("/etc/resolv.conf").unwrap_or_default();
+            candidates.extend(nameservers(&resolv_conf));
+            candidates.extend(gateway);
+        }
+    }
+
+    let mut unique = Vec::new();
+    for candidate in candidates {
+        if !unique.contains(&candidate) {
+            unique.push(candidate);
+        }
+    }
+    unique
+}
+
+/// Gateway of the default route in a `/proc/net/route` table
+fn default_gateway(route_table: &str) -> Option<Ipv4Addr> {
+    route_table.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let destination = fields.nth(1)?;
+        let gateway = fields.next()?;
+        if destination != "00000000" {
+            return None;
+        }
+        // Addresses are written as little-endian hex
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        (gateway != 0).then(|| Ipv4Addr::from(gateway.swap_bytes()))
+    })
+}
+
+/// Non-loopback nameservers in a `resolv.conf`
+fn nameservers(resolv_conf: &str) -> Vec<String> {
+    resolv_conf
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("nameserver"))
+        .map(str::trim)
+        .filter(|server| {
+            server
+                .parse::<std::net::IpAddr>()
+                .is_ok_and(|address| !address.is_loopback())
+        })
+        .map(String::from)
+        .collect()
+}
+
+/// Outcome of probing one host
+#[derive(Debug, Clone, Serialize)]
+pub struct HostProbe {
+    pub host:      String,
+    pub reachable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error:     Option<String>,
+}
+
+/// Check which hosts accept TCP connections on the BRP port
+pub async fn probe_hosts(hosts: Vec<String>, port: u16) -> Vec<HostProbe> {
+    join_all(hosts.into_iter().map(|host| async move {
+        let error = match tokio::time::timeout(
+            PROBE_TIMEOUT,
+            TcpStream::connect((host.trim_matches(['[', ']']), port)),
+        )
+        .await
+        {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("no answer within {}s", PROBE_TIMEOUT.as_secs())),
+        };
+        HostProbe {
+            host,
+            reachable: error.is_none(),
+            error,
+        }
+    }))
+    .await
+}
+
+/// Advice for a BRP port the configured host can't reach but another candidate can
+///
+/// Returns `None` when the configured host answers or no candidate does.
+pub async fn remediation(port: u16) -> Option<String> {
+    remediation_for(&probe_hosts(host_candidates(), port).await, port)
+}
+
+/// Advice from probes of [`host_candidates`], whose first entry is the configured host
+pub fn remediation_for(probes: &[HostProbe], port: u16) -> Option<String> {
+    let (configured, alternates) = probes.split_first()?;
+    if configured.reachable {
+        return None;
+    }
+    let alternate = alternates.iter().find(|probe| probe.reachable)?;
+    Some(remediation_message(
+        &configured.host,
+        &alternate.host,
+        port,
+        environment(),
+    ))
+}
+
+fn remediation_message(
+    configured: &str,
+    alternate: &str,
+    port: u16,
+    environment: RuntimeEnvironment,
+) -> String {
+    let location = match environment {
+        RuntimeEnvironment::Native => String::new(),
+        RuntimeEnvironment::Wsl => " from WSL".to_string(),
+        RuntimeEnvironment::Container => " from this container".to_string(),
+    };
+    format!(
+        "BRP port {port} is not reachable on {configured}{location} but is on {alternate}. \
+         Pass host: \"{alternate}\" or set {BRP_HOST_ENV_VAR}={alternate} in the MCP server configuration."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_classification() {
+        let wsl_kernel = "Linux version 5.15.153.1-microsoft-standard-WSL2";
+        assert_eq!(
+            RuntimeEnvironment::classify(wsl_kernel, false, false),
+            RuntimeEnvironment::Wsl
+        );
+        assert_eq!(
+            RuntimeEnvironment::classify(wsl_kernel, true, true),
+            RuntimeEnvironment::Container
+        );
+        assert_eq!(
+            RuntimeEnvironment::classify("Linux version 6.8.0-generic", false, false),
+            RuntimeEnvironment::Native
+        );
+    }
+
+    #[test]
+    fn test_gateway_and_nameservers_are_parsed() {
+        let route_table = "Iface\tDestination\tGateway \tFlags\n\
+                           eth0\t0011A8C0\t00000000\t0001\n\
+                           eth0\t00000000\t0100A8C0\t0003\n";
+        assert_eq!(
+            default_gateway(route_table),
+            Some(Ipv4Addr::new(192, 168, 0, 1))
+        );
+        assert_eq!(default_gateway(""), None);
+
+        let resolv_conf = "# generated by WSL\nnameserver 172.22.64.1\nnameserver 127.0.0.53\n";
+        assert_eq!(nameservers(resolv_conf), vec!["172.22.64.1"]);
+
+        let message = remediation_message(
+            "localhost",
+            DOCKER_HOST_NAME,
+            15702,
+            RuntimeEnvironment::Container,
+        );
+        assert!(message.contains("set BRP_MCP_HOST=host.docker.internal"));
+    }
+}
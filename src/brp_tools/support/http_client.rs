@@ -2,11 +2,43 @@
 //!
 //! This module provides a singleton HTTP client that reuses connections
 //! to prevent resource exhaustion under concurrent load.
+//!
+//! Apps behind a reverse proxy can need HTTPS, custom CA certificates or authentication headers.
+//! These are set per connection under the `connections` configuration key, keyed by `host:port`
+//! or `host`, with `*` applying to every other connection:
+//!
+//! ```json
+//! {
+//!   "connections": {
+//!     "brp.example.com": {
+//!       "tls": true,
+//!       "ca_cert": "/etc/ssl/proxy-ca.pem",
+//!       "headers": { "Authorization": "Bearer ${BRP_TOKEN}" }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Header values can refer to environment variables as `${NAME}`, so tokens don't have to be
+//! written to the configuration file. When the configuration is shown, header values other than
+//! such references (optionally after an auth scheme like `Bearer`) are masked. Connections needing
+//! other TLS settings than the default get their own client, cached for the session.
 
-use std::sync::LazyLock;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
 use std::time::Duration;
 
-use reqwest::Client;
+use regex::Regex;
+use reqwest::{Certificate, Client, ClientBuilder, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::brp_tools::constants::{BRP_HTTP_PROTOCOL, BRP_HTTPS_PROTOCOL};
+use crate::config;
+use crate::error::{Error, Result};
+
+/// Key of the connection settings applying to every connection without its own
+pub const ANY_CONNECTION: &str = "*";
 
 /// Shared HTTP client instance with optimized connection pooling
 ///
@@ -14,15 +46,104 @@ use reqwest::Client;
 /// - Connection pooling enabled for localhost connections
 /// - Reasonable timeouts for local services
 /// - Connection keep-alive for reduced overhead
-static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+static HTTP_CLIENT: LazyLock<Client> =
+    LazyLock::new(|| client_builder().build().unwrap_or_else(|_| Client::new()));
+
+/// Clients for connections with their own TLS settings
+static TLS_CLIENTS: LazyLock<Mutex<HashMap<TlsKey, Client>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// `${NAME}` references to environment variables in header values
+static ENV_REFERENCE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").ok());
+
+/// Header values shown unmasked: only environment references, optionally after an auth scheme
+static PLACEHOLDER_VALUE: LazyLock<Option<Regex>> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z]+ )?(\$\{[A-Za-z_][A-Za-z0-9_]*\})+$").ok());
+
+/// Shown in place of a masked header value
+const MASKED_HEADER_VALUE: &str = "***";
+
+/// TLS settings a client is built with
+type TlsKey = (bool, Option<PathBuf>);
+
+/// How to reach the BRP server of one connection
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConnectionSettings {
+    /// Use HTTPS instead of HTTP
+    pub tls:                  bool,
+    /// Accept certificates that don't verify, e.g. self-signed ones
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub accept_invalid_certs: bool,
+    /// PEM file of an extra CA certificate to trust
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_cert:              Option<PathBuf>,
+    /// Headers sent with every request; values may refer to environment variables as `${NAME}`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers:              BTreeMap<String, String>,
+}
+
+impl ConnectionSettings {
+    /// URL scheme of the connection
+    pub const fn scheme(&self) -> &'static str {
+        if self.tls {
+            BRP_HTTPS_PROTOCOL
+        } else {
+            BRP_HTTP_PROTOCOL
+        }
+    }
+
+    /// The settings of a connection: `host:port`, then `host`, then `*`, then the defaults
+    pub fn lookup(host: &str, port: u16) -> Self {
+        config::current()
+            .connections
+            .and_then(|mut connections| {
+                connections
+                    .remove(&format!("{host}:{port}"))
+                    .or_else(|| connections.remove(host))
+                    .or_else(|| connections.remove(ANY_CONNECTION))
+            })
+            .unwrap_or_default()
+    }
+
+    /// The settings with header values masked, unless they only refer to environment variables
+    pub fn redacted(&self) -> Self {
+        let is_placeholder = |value: &str| {
+            PLACEHOLDER_VALUE
+                .as_ref()
+                .is_some_and(|placeholder| placeholder.is_match(value))
+        };
+        Self {
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    let shown = if is_placeholder(value) {
+                        value.clone()
+                    } else {
+                        MASKED_HEADER_VALUE.to_string()
+                    };
+                    (name.clone(), shown)
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    fn tls_key(&self) -> Option<TlsKey> {
+        (self.accept_invalid_certs || self.ca_cert.is_some())
+            .then(|| (self.accept_invalid_certs, self.ca_cert.clone()))
+    }
+}
+
+fn client_builder() -> ClientBuilder {
     Client::builder()
         .pool_idle_timeout(Duration::from_secs(30))
         .pool_max_idle_per_host(10)
         .timeout(Duration::from_secs(30))
         .connect_timeout(Duration::from_secs(5))
-        .build()
-        .unwrap_or_else(|_| Client::new())
-});
+}
 
 /// Get the shared HTTP client instance
 ///
@@ -34,8 +155,81 @@ pub fn get_client() -> &'static Client {
     &HTTP_CLIENT
 }
 
+/// The client for a connection's TLS settings, built on first use
+fn client_for(settings: &ConnectionSettings) -> Result<Client> {
+    let Some(key) = settings.tls_key() else {
+        return Ok(get_client().clone());
+    };
+    let mut clients = TLS_CLIENTS.lock().map_err(|e| {
+        error_stack::Report::new(Error::MutexPoisoned(format!("HTTP client cache lock: {e}")))
+    })?;
+    if let Some(client) = clients.get(&key) {
+        return Ok(client.clone());
+    }
+
+    let mut builder = client_builder().danger_accept_invalid_certs(settings.accept_invalid_certs);
+    if let Some(path) = &settings.ca_cert {
+        let pem = std::fs::read(path).map_err(|e| {
+            error_stack::Report::new(Error::FileOperation(format!(
+                "Failed to read CA certificate {}: {e}",
+                path.display()
+            )))
+        })?;
+        let certificate = Certificate::from_pem(&pem).map_err(|e| {
+            error_stack::Report::new(Error::Configuration(format!(
+                "{} is not a PEM certificate: {e}",
+                path.display()
+            )))
+        })?;
+        builder = builder.add_root_certificate(certificate);
+    }
+    let client = builder.build().map_err(|e| {
+        error_stack::Report::new(Error::Configuration(format!(
+            "Failed to build HTTPS client: {e}"
+        )))
+    })?;
+    clients.insert(key, client.clone());
+    drop(clients);
+    Ok(client)
+}
+
+/// Start a POST request with a connection's client and headers
+pub fn post(url: &str, settings: &ConnectionSettings) -> Result<RequestBuilder> {
+    let mut request = client_for(settings)?.post(url);
+    for (name, value) in &settings.headers {
+        request = request.header(name.as_str(), expand_env(value)?);
+    }
+    Ok(request)
+}
+
+/// Replace `${NAME}` references with the values of the environment variables
+fn expand_env(value: &str) -> Result<String> {
+    let Some(reference) = ENV_REFERENCE.as_ref() else {
+        return Ok(value.to_string());
+    };
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = 0;
+    for captures in reference.captures_iter(value) {
+        let (Some(whole), Some(name)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        let variable = std::env::var(name.as_str()).map_err(|_| {
+            error_stack::Report::new(Error::Configuration(format!(
+                "Connection header refers to {}, which is not set",
+                name.as_str()
+            )))
+        })?;
+        expanded.push_str(&value[rest..whole.start()]);
+        expanded.push_str(&variable);
+        rest = whole.end();
+    }
+    expanded.push_str(&value[rest..]);
+    Ok(expanded)
+}
+
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
     use super::*;
 
     #[test]
@@ -46,4 +240,38 @@ mod tests {
         // Both references should point to the same instance
         assert!(std::ptr::eq(client1, client2));
     }
+
+    #[test]
+    fn test_header_values_expand_environment_references() {
+        let path = std::env::var("PATH").unwrap();
+        assert_eq!(
+            expand_env("Bearer ${PATH}!").unwrap(),
+            format!("Bearer {path}!")
+        );
+        assert_eq!(expand_env("plain $HOME").unwrap(), "plain $HOME");
+        assert!(expand_env("${BRP_MCP_TEST_UNSET_VARIABLE}").is_err());
+
+        let settings: ConnectionSettings =
+            serde_json::from_value(serde_json::json!({ "tls": true })).unwrap();
+        assert_eq!(settings.scheme(), "https");
+        assert!(settings.tls_key().is_none());
+    }
+
+    #[test]
+    fn test_literal_header_values_are_masked() {
+        let settings: ConnectionSettings = serde_json::from_value(serde_json::json!({
+            "headers": {
+                "Authorization": "Bearer abc123",
+                "X-Proxy-Auth": "Bearer ${BRP_TOKEN}",
+                "X-Key": "${BRP_KEY}",
+                "X-Mixed": "abc${BRP_KEY}",
+            }
+        }))
+        .unwrap();
+        let headers = settings.redacted().headers;
+        assert_eq!(headers["Authorization"], "***");
+        assert_eq!(headers["X-Proxy-Auth"], "Bearer ${BRP_TOKEN}");
+        assert_eq!(headers["X-Key"], "${BRP_KEY}");
+        assert_eq!(headers["X-Mixed"], "***");
+    }
 }
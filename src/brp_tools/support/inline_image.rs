@@ -0,0 +1,393 @@
+//! Screenshots returned as MCP image content
+//!
+//! `brp_extras/screenshot` only asks the app to save a frame; the file is written a frame or
+//! more later, by the app, on the app's machine. With `inline`, the saved file is read back once
+//! its size settles and attached to the result as image content, so clients can look at it. That
+//! only works when the app runs on this machine and the path is absolute. An image over
+//! `max_image_bytes` is re-encoded as JPEG, downscaled until it fits; the saved file is left as is.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView};
+use rmcp::model::{CallToolRequestParam, CallToolResult, Content};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::brp_client::current_host;
+use crate::brp_tools::constants::{JSON_FIELD_PATH, PARAM_INLINE, PARAM_MAX_IMAGE_BYTES};
+use crate::error::{Error, Result};
+use crate::support::response::{JsonResponse, ResponseStatus};
+use crate::support::serialization::{json_response_to_result, result_to_json};
+
+/// Largest image attached when no limit is given
+pub const DEFAULT_MAX_IMAGE_BYTES: u64 = 1024 * 1024;
+
+/// Largest limit that can be given
+pub const MAX_IMAGE_BYTES_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// How long the app gets to write the file
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause between checks of the file
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Quality of images re-encoded as JPEG to fit the limit
+const JPEG_QUALITY: u8 = 80;
+
+/// Smallest width or height an image is downscaled to before giving up on the limit
+const MIN_DIMENSION: u32 = 64;
+
+/// Response field describing the attached image, or why there is none
+const FIELD_INLINE_IMAGE: &str = "inline_image";
+
+/// Where the screenshot is saved and how large an image may be attached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineOptions {
+    pub path:      PathBuf,
+    pub max_bytes: u64,
+    /// Modification time of a file already at the path, which the screenshot has to replace
+    pub previous:  Option<SystemTime>,
+}
+
+/// An image ready to attach, with what was done to fit it under the limit
+struct InlineImage {
+    bytes:     Vec<u8>,
+    mime_type: &'static str,
+    note:      Option<String>,
+}
+
+/// Outcome of attaching an image
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct InlineReport {
+    included:   bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_type:  Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note:       Option<String>,
+}
+
+impl InlineReport {
+    fn skipped(note: impl Into<String>) -> Self {
+        Self {
+            included:   false,
+            mime_type:  None,
+            size_bytes: None,
+            note:       Some(note.into()),
+        }
+    }
+}
+
+/// Remove the `inline` and `max_image_bytes` arguments so they are not forwarded to BRP
+///
+/// Returns the options when `inline` is true.
+pub fn take_options(request: &mut CallToolRequestParam) -> Result<Option<InlineOptions>> {
+    let Some(arguments) = request.arguments.as_mut() else {
+        return Ok(None);
+    };
+    let inline = arguments
+        .remove(PARAM_INLINE)
+        .is_some_and(|inline| inline.as_bool() == Some(true));
+    let max_bytes = match arguments.remove(PARAM_MAX_IMAGE_BYTES) {
+        None | Some(Value::Null) => DEFAULT_MAX_IMAGE_BYTES,
+        Some(value) => value.as_u64().filter(|bytes| *bytes > 0).ok_or_else(|| {
+            error_stack::Report::new(Error::invalid(
+                PARAM_MAX_IMAGE_BYTES,
+                "must be a positive integer",
+            ))
+        })?,
+    };
+    let path = PathBuf::from(
+        arguments
+            .get(JSON_FIELD_PATH)
+            .and_then(Value::as_str)
+            .unwrap_or_default(),
+    );
+    Ok(inline.then(|| InlineOptions {
+        previous: modified(&path),
+        path,
+        max_bytes: max_bytes.min(MAX_IMAGE_BYTES_LIMIT),
+    }))
+}
+
+/// Attach the saved screenshot of a successful result as image content
+pub async fn attach(result: CallToolResult, options: &InlineOptions) -> CallToolResult {
+    let Ok(mut response) = serde_json::from_value::<JsonResponse>(result_to_json(&result)) else {
+        return result;
+    };
+    if !matches!(response.status, ResponseStatus::Success) {
+        return result;
+    }
+
+    let image = read_image(options).await;
+    let report = match &image {
+        Ok(image) => InlineReport {
+            included:   true,
+            mime_type:  Some(image.mime_type),
+            size_bytes: Some(image.bytes.len() as u64),
+            note:       image.note.clone(),
+        },
+        Err(note) => InlineReport::skipped(note.clone()),
+    };
+    match response.data.as_mut() {
+        Some(Value::Object(data)) => {
+            data.insert(FIELD_INLINE_IMAGE.to_string(), json!(report));
+        }
+        _ => response.data = Some(json!({ FIELD_INLINE_IMAGE: report })),
+    }
+
+    let mut result = json_response_to_result(&response);
+    if let Ok(image) = image {
+        result
+            .content
+            .push(Content::image(base64_encode(&image.bytes), image.mime_type));
+    }
+    result
+}
+
+/// Wait for the screenshot file and read it, or say why it can't be attached
+async fn read_image(options: &InlineOptions) -> std::result::Result<InlineImage, String> {
+    let host = current_host();
+    if !is_local_host(&host) {
+        return Err(format!(
+            "The app runs on {host}, so the file isn't readable here"
+        ));
+    }
+    if !options.path.is_absolute() {
+        return Err(
+            "The path is relative to the app's working directory; give an absolute path to inline the image"
+                .to_string(),
+        );
+    }
+
+    wait_for_file(&options.path, options.previous)
+        .await
+        .ok_or_else(|| {
+            format!(
+                "{} was not written within {}s",
+                options.path.display(),
+                WRITE_TIMEOUT.as_secs()
+            )
+        })?;
+
+    let bytes = tokio::fs::read(&options.path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {e}", options.path.display()))?;
+    let mime_type = mime_type(&bytes).ok_or_else(|| {
+        "The file is not a PNG, JPEG, GIF or WebP image, so it can't be shown".to_string()
+    })?;
+    if bytes.len() as u64 <= options.max_bytes {
+        return Ok(InlineImage {
+            bytes,
+            mime_type,
+            note: None,
+        });
+    }
+
+    let max_bytes = options.max_bytes;
+    tokio::task::spawn_blocking(move || shrink(&bytes, max_bytes))
+        .await
+        .map_err(|e| format!("Failed to shrink the image: {e}"))?
+}
+
+/// Re-encode an image as JPEG, downscaling it by quarters until it fits in `max_bytes`
+fn shrink(bytes: &[u8], max_bytes: u64) -> std::result::Result<InlineImage, String> {
+    let size = bytes.len();
+    let decoded = image::load_from_memory(bytes).map_err(|e| {
+        format!(
+            "The image is {size} bytes, over the {max_bytes} byte limit, and couldn't be decoded to shrink it: {e}"
+        )
+    })?;
+    // JPEG has no alpha channel
+    let image = DynamicImage::ImageRgb8(decoded.to_rgb8());
+    let (width, height) = image.dimensions();
+
+    let mut resized = image.clone();
+    loop {
+        let mut encoded = Vec::new();
+        JpegEncoder::new_with_quality(&mut encoded, JPEG_QUALITY)
+            .encode_image(&resized)
+            .map_err(|e| format!("Failed to encode the image as JPEG: {e}"))?;
+        let (resized_width, resized_height) = resized.dimensions();
+        if encoded.len() as u64 <= max_bytes {
+            let note = if (resized_width, resized_height) == (width, height) {
+                format!("Re-encoded as JPEG to fit the {max_bytes} byte limit")
+            } else {
+                format!(
+                    "Downscaled from {width}x{height} to {resized_width}x{resized_height} and re-encoded as JPEG to fit the {max_bytes} byte limit"
+                )
+            };
+            return Ok(InlineImage {
+                bytes:     encoded,
+                mime_type: "image/jpeg",
+                note:      Some(note),
+            });
+        }
+        if resized_width.max(resized_height) <= MIN_DIMENSION {
+            return Err(format!(
+                "The image is {size} bytes and doesn't fit the {max_bytes} byte limit even at {resized_width}x{resized_height}; raise {PARAM_MAX_IMAGE_BYTES}"
+            ));
+        }
+        resized = image.resize(
+            resized_width * 3 / 4,
+            resized_height * 3 / 4,
+            FilterType::Triangle,
+        );
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Wait until the file is written after `previous` and its size stops changing, returning the
+/// size
+async fn wait_for_file(path: &Path, previous: Option<SystemTime>) -> Option<u64> {
+    let started = Instant::now();
+    let mut last_size = None;
+    while started.elapsed() < WRITE_TIMEOUT {
+        let size = tokio::fs::metadata(path)
+            .await
+            .ok()
+            .filter(|metadata| {
+                previous.is_none_or(|previous| {
+                    metadata
+                        .modified()
+                        .is_ok_and(|modified| modified > previous)
+                })
+            })
+            .map(|metadata| metadata.len())
+            .filter(|size| *size > 0);
+        if size.is_some() && size == last_size {
+            return size;
+        }
+        last_size = size;
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    None
+}
+
+fn is_local_host(host: &str) -> bool {
+    matches!(
+        host.trim_matches(['[', ']']),
+        "localhost" | "127.0.0.1" | "::1"
+    )
+}
+
+/// MIME type of an image, read from its signature
+fn mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Standard base64 with padding
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3F;
+                encoded.push(char::from(ALPHABET[index as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use image::{ImageFormat, RgbImage};
+
+    use super::*;
+
+    #[test]
+    fn test_images_are_recognized_and_encoded() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+
+        assert_eq!(mime_type(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png"));
+        assert_eq!(mime_type(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(mime_type(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(mime_type(b"BM\0\0"), None);
+        assert!(is_local_host("[::1]") && !is_local_host("host.docker.internal"));
+
+        let mut request = CallToolRequestParam {
+            name:      "brp_extras_screenshot".into(),
+            arguments: json!({ "path": "/tmp/shot.png", "inline": true, "max_image_bytes": 2048 })
+                .as_object()
+                .cloned(),
+        };
+        let options = take_options(&mut request).ok().flatten();
+        assert_eq!(
+            options,
+            Some(InlineOptions {
+                path:      PathBuf::from("/tmp/shot.png"),
+                max_bytes: 2048,
+                previous:  modified(Path::new("/tmp/shot.png")),
+            })
+        );
+        assert_eq!(request.arguments.map(|arguments| arguments.len()), Some(1));
+
+        let mut request = CallToolRequestParam {
+            name:      "brp_extras_screenshot".into(),
+            arguments: json!({ "path": "/tmp/shot.png", "inline": true, "max_image_bytes": 0 })
+                .as_object()
+                .cloned(),
+        };
+        assert!(take_options(&mut request).is_err());
+    }
+
+    #[test]
+    fn test_large_images_are_downscaled_to_fit() {
+        // Noise compresses badly, so a PNG of it is far over the limit
+        let noise = RgbImage::from_fn(512, 256, |x, y| {
+            let [red, green, blue, _] = (x.wrapping_mul(7919) ^ y.wrapping_mul(104_729))
+                .wrapping_mul(2_654_435_761)
+                .to_be_bytes();
+            image::Rgb([red, green, blue])
+        });
+        let mut png = std::io::Cursor::new(Vec::new());
+        noise.write_to(&mut png, ImageFormat::Png).unwrap();
+        let png = png.into_inner();
+        assert!(png.len() > 100_000);
+
+        let image = shrink(&png, 20_000).unwrap();
+        assert_eq!(image.mime_type, "image/jpeg");
+        assert_eq!(mime_type(&image.bytes), Some("image/jpeg"));
+        assert!(image.bytes.len() <= 20_000);
+        assert!(
+            image
+                .note
+                .unwrap()
+                .starts_with("Downscaled from 512x256 to")
+        );
+
+        assert!(shrink(&png, 10).is_err());
+        assert!(shrink(b"\x89PNG\r\n\x1a\n", 10).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Structural diffing of JSON values
+
+use serde_json::{Map, Value};
+
+/// Compute a merge-patch style delta from `old` to `new`.
+///
+/// Objects are compared field by field; any other value (including arrays) is replaced as a
+/// whole. Fields missing from `new` are reported as `null`. Returns `None` if nothing changed.
+pub fn diff_values(old: &Value, new: &Value) -> Option<Value> {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut delta = Map::new();
+            for (key, new_value) in new_map {
+                let field_delta = old_map.get(key).map_or_else(
+                    || Some(new_value.clone()),
+                    |old_value| diff_values(old_value, new_value),
+                );
+                if let Some(field_delta) = field_delta {
+                    delta.insert(key.clone(), field_delta);
+                }
+            }
+            for key in old_map.keys().filter(|key| !new_map.contains_key(*key)) {
+                delta.insert(key.clone(), Value::Null);
+            }
+            (!delta.is_empty()).then_some(Value::Object(delta))
+        }
+        _ => (old != new).then(|| new.clone()),
+    }
+}
@@ -0,0 +1,218 @@
+//! Mock BRP server for testing handlers without a running Bevy app
+//!
+//! Serves JSON-RPC over HTTP on a free local port. Each method answers with the replies it was
+//! primed with, in order, repeating the last one; methods that weren't primed answer "method not
+//! found" like a BRP server without them. Every request is recorded so tests can check what was
+//! sent, including the corrected params of format discovery retries.
+
+#![allow(clippy::expect_used)]
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// JSON-RPC code for a method the server doesn't have
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// A request the server received
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    pub method: String,
+    pub params: Option<Value>,
+}
+
+/// A canned answer to one request
+#[derive(Debug, Clone)]
+struct Reply {
+    /// The JSON-RPC `result` or `error` member
+    outcome: Result<Value, Value>,
+    /// How long to wait before answering
+    delay:   Duration,
+}
+
+#[derive(Default)]
+struct State {
+    replies:  HashMap<String, VecDeque<Reply>>,
+    requests: Vec<ReceivedRequest>,
+}
+
+impl State {
+    /// The next reply for a method, keeping the last one for later requests
+    fn next_reply(&mut self, method: &str) -> Option<Reply> {
+        let queue = self.replies.get_mut(method)?;
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+    }
+}
+
+/// A JSON-RPC server standing in for a Bevy app, stopped when dropped
+pub struct MockBrpServer {
+    port:   u16,
+    state:  Arc<Mutex<State>>,
+    server: JoinHandle<()>,
+}
+
+impl MockBrpServer {
+    /// Start serving on a free local port
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("failed to bind the mock BRP server");
+        let port = listener
+            .local_addr()
+            .expect("mock BRP server has an address")
+            .port();
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let server_state = Arc::clone(&state);
+        let server = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&server_state)));
+            }
+        });
+        Self {
+            port,
+            state,
+            server,
+        }
+    }
+
+    /// Port the server listens on
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Answer the next request for a method with a result
+    pub fn respond(&self, method: &str, result: Value) -> &Self {
+        self.push(method, Ok(result), Duration::ZERO)
+    }
+
+    /// Answer the next request for a method with a result, after a delay
+    pub fn respond_after(&self, method: &str, delay: Duration, result: Value) -> &Self {
+        self.push(method, Ok(result), delay)
+    }
+
+    /// Answer the next request for a method with an error, such as a format error
+    pub fn fail(&self, method: &str, code: i32, message: &str) -> &Self {
+        self.push(
+            method,
+            Err(json!({ "code": code, "message": message })),
+            Duration::ZERO,
+        )
+    }
+
+    /// Every request received so far, in order
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// The requests received for one method, in order
+    pub fn requests_for(&self, method: &str) -> Vec<ReceivedRequest> {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|request| request.method == method)
+            .cloned()
+            .collect()
+    }
+
+    fn push(&self, method: &str, outcome: Result<Value, Value>, delay: Duration) -> &Self {
+        self.lock()
+            .replies
+            .entry(method.to_string())
+            .or_default()
+            .push_back(Reply { outcome, delay });
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("mock BRP server state poisoned")
+    }
+}
+
+impl Drop for MockBrpServer {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Answer one HTTP request on a connection, then close it
+async fn serve(mut stream: TcpStream, state: Arc<Mutex<State>>) {
+    let Some(body) = read_body(&mut stream).await else {
+        return;
+    };
+    let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    let method = request["method"].as_str().unwrap_or_default().to_string();
+
+    let reply = {
+        let mut state = state.lock().expect("mock BRP server state poisoned");
+        state.requests.push(ReceivedRequest {
+            method: method.clone(),
+            params: request.get("params").cloned(),
+        });
+        state.next_reply(&method)
+    };
+    let reply = reply.unwrap_or_else(|| Reply {
+        outcome: Err(json!({
+            "code": METHOD_NOT_FOUND,
+            "message": format!("Method `{method}` not found"),
+        })),
+        delay:   Duration::ZERO,
+    });
+    tokio::time::sleep(reply.delay).await;
+
+    let mut response = json!({ "jsonrpc": "2.0", "id": request["id"] });
+    match reply.outcome {
+        Ok(result) => response["result"] = result,
+        Err(error) => response["error"] = error,
+    }
+    let body = response.to_string();
+    let http = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: \
+         close\r\n\r\n{body}",
+        body.len()
+    );
+    // The client may have given up already
+    let _ = stream.write_all(http.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Read an HTTP request, returning its body
+async fn read_body(stream: &mut TcpStream) -> Option<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 4096];
+    let header_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let headers = String::from_utf8_lossy(&buffer[..header_end]);
+    let content_length = headers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    Some(buffer[header_end..header_end + content_length].to_vec())
+}
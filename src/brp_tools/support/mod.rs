@@ -1,8 +1,26 @@
 // Local support modules for brp_tools
 
+pub mod app_instance;
 pub mod brp_client;
+pub mod canonical_order;
+pub mod capabilities;
+pub mod component_filter;
+pub mod delta_tracker;
+pub mod destructive_ops;
+pub mod host_detection;
 pub mod http_client;
+pub mod inline_image;
+pub mod json_diff;
 mod json_rpc_builder;
+#[cfg(test)]
+pub mod mock_brp_server;
+pub mod mutation_paths;
+pub mod projection;
+pub mod request_policy;
 pub mod response_formatter;
+mod response_parsing;
+pub mod ron_reader;
+pub mod spawn_tagging;
+pub mod sse_client;
 pub use json_rpc_builder::BrpJsonRpcBuilder;
 pub use response_formatter::{FieldExtractor, ResponseFormatterFactory, extractors};
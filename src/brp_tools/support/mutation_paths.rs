@@ -0,0 +1,137 @@
+//! Mutation paths of a type, derived from the registry schema
+//!
+//! The schema of the type and, breadth first, of its field types is fetched through the schema
+//! cache. Struct fields become `.field` accessors and tuple fields `.0`, nested up to
+//! [`MAX_DEPTH`] levels, which are the paths `bevy_mutate_component` and
+//! `bevy_mutate_resource` accept.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde_json::Value;
+
+use crate::brp_tools::request_handler::{PathSegment, render_path, type_schema};
+use crate::error::Result;
+
+/// How many levels of nested fields are explored
+pub const MAX_DEPTH: usize = 4;
+
+/// Most schemas fetched for one type
+const MAX_SCHEMAS: usize = 32;
+
+/// A path below a type, with the type it points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationPath {
+    pub segments:  Vec<PathSegment>,
+    pub type_path: String,
+}
+
+impl MutationPath {
+    /// The path as passed to `bevy_mutate_component`, e.g. `.translation.x`
+    pub fn path(&self) -> String {
+        render_path(&self.segments)
+    }
+}
+
+/// Fetch the schema of the type and, breadth first, of the types of its fields
+pub async fn fetch_schemas(type_name: &str, port: Option<u16>) -> Result<HashMap<String, Value>> {
+    let mut schemas = HashMap::new();
+    let mut pending = VecDeque::from([(type_name.to_string(), 0)]);
+
+    while let Some((type_path, depth)) = pending.pop_front() {
+        if schemas.len() >= MAX_SCHEMAS
+            || primitive_kind(&type_path).is_some()
+            || schemas.contains_key(&type_path)
+        {
+            continue;
+        }
+        let Some(schema) = type_schema(&type_path, port).await? else {
+            continue;
+        };
+        if depth < MAX_DEPTH {
+            pending.extend(
+                field_types(&schema)
+                    .into_iter()
+                    .map(|(_, field_type)| (field_type, depth + 1)),
+            );
+        }
+        schemas.insert(type_path, schema);
+    }
+
+    Ok(schemas)
+}
+
+/// Types of the directly accessible fields of a struct or tuple schema
+pub fn field_types(schema: &Value) -> Vec<(PathSegment, String)> {
+    let type_ref = |field: &Value| {
+        field["type"]["$ref"]
+            .as_str()
+            .map(|reference| reference.trim_start_matches("#/$defs/").to_string())
+    };
+
+    match schema["kind"].as_str() {
+        Some("Struct") => schema["properties"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, field)| Some((PathSegment::Field(name.clone()), type_ref(field)?)))
+            .collect(),
+        Some("TupleStruct" | "Tuple") => schema["prefixItems"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(index, field)| Some((PathSegment::TupleIndex(index), type_ref(field)?)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// JSON kind of primitive types, as the registry schema names them
+pub fn primitive_kind(type_path: &str) -> Option<&'static str> {
+    match type_path {
+        "f32" | "f64" => Some("float"),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => Some("int"),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Some("uint"),
+        "bool" => Some("boolean"),
+        "char" | "String" | "alloc::string::String" | "alloc::borrow::Cow<str>" => Some("string"),
+        _ => None,
+    }
+}
+
+/// All paths below the type, depth first, up to `limit` paths
+pub fn mutation_paths(
+    type_name: &str,
+    schemas: &HashMap<String, Value>,
+    limit: usize,
+) -> Vec<MutationPath> {
+    let mut paths = Vec::new();
+    collect(type_name, &mut Vec::new(), schemas, limit, &mut paths);
+    paths
+}
+
+fn collect(
+    type_path: &str,
+    segments: &mut Vec<PathSegment>,
+    schemas: &HashMap<String, Value>,
+    limit: usize,
+    paths: &mut Vec<MutationPath>,
+) {
+    if paths.len() >= limit {
+        return;
+    }
+    if !segments.is_empty() {
+        paths.push(MutationPath {
+            segments:  segments.clone(),
+            type_path: type_path.to_string(),
+        });
+    }
+    if segments.len() >= MAX_DEPTH {
+        return;
+    }
+
+    for (segment, field_type) in schemas.get(type_path).map(field_types).unwrap_or_default() {
+        segments.push(segment);
+        collect(&field_type, segments, schemas, limit, paths);
+        segments.pop();
+    }
+}
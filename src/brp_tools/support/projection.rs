@@ -0,0 +1,283 @@
+//! Server-side projection of `bevy_query` and `bevy_get` results
+//!
+//! The `projection` argument is a path expression, or a list of them, selecting the parts of a
+//! result to return, such as `$.components["Transform"].translation`. Expressions are applied to
+//! each query row, which keeps its `entity` and gets the selection as `value`, and to the whole
+//! result of a get. The syntax is a subset of JSON path that also accepts jq-style paths:
+//! - `$` or `.` alone is the value itself, and the leading `$` may be left out
+//! - `.name` and `["name"]` select an object field; a type name also matches a field named by the
+//!   full type path ending in it, when only one does
+//! - `[0]` selects an array item
+//! - `.*` and `[*]` select every field or item
+//!
+//! An expression without wildcards gives the selected value, or `null` when nothing matches; one
+//! with wildcards gives the array of matches. A list of expressions gives an object keyed by
+//! expression.
+
+use serde_json::{Map, Value, json};
+
+use crate::brp_tools::constants::{JSON_FIELD_ENTITY, PARAM_PROJECTION};
+use crate::error::{Error, Result};
+use crate::tools::BRP_METHOD_QUERY;
+
+/// Most expressions in one projection
+const MAX_EXPRESSIONS: usize = 32;
+
+/// One step of a path expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Field(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// A parsed `projection` argument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    expressions: Vec<(String, Vec<Step>)>,
+    /// Given as one expression rather than a list
+    single:      bool,
+}
+
+impl Projection {
+    /// Parse a `projection` argument, a path expression or an array of them
+    pub fn parse(argument: &Value) -> Result<Self> {
+        let invalid =
+            |details: String| error_stack::Report::new(Error::invalid(PARAM_PROJECTION, details));
+        let (expressions, single) = match argument {
+            Value::String(expression) => (vec![expression.as_str()], true),
+            Value::Array(items) if !items.is_empty() && items.len() <= MAX_EXPRESSIONS => (
+                items
+                    .iter()
+                    .map(|item| {
+                        item.as_str().ok_or_else(|| {
+                            invalid(format!("expected path expressions, got {item}"))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                false,
+            ),
+            other => {
+                return Err(invalid(format!(
+                    "expected a path expression or an array of 1 to {MAX_EXPRESSIONS}, got {other}"
+                )));
+            }
+        };
+
+        let expressions = expressions
+            .into_iter()
+            .map(|expression| {
+                parse_expression(expression)
+                    .map(|steps| (expression.to_string(), steps))
+                    .map_err(|details| invalid(format!("'{expression}': {details}")))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            expressions,
+            single,
+        })
+    }
+
+    /// The expressions, identifying the projection in delta tracking keys
+    pub fn key(&self) -> String {
+        self.expressions
+            .iter()
+            .map(|(expression, _)| expression.as_str())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Select the projected parts of a value
+    pub fn apply(&self, value: &Value) -> Value {
+        if self.single {
+            return select(value, &self.expressions[0].1);
+        }
+        Value::Object(
+            self.expressions
+                .iter()
+                .map(|(expression, steps)| (expression.clone(), select(value, steps)))
+                .collect(),
+        )
+    }
+
+    /// Project the result of a method: each row of a query, the whole result otherwise
+    pub fn project_result(&self, method: &str, data: &mut Value) {
+        match data {
+            Value::Array(rows) if method == BRP_METHOD_QUERY => {
+                for row in rows {
+                    *row = json!({
+                        JSON_FIELD_ENTITY: row.get(JSON_FIELD_ENTITY),
+                        "value": self.apply(row),
+                    });
+                }
+            }
+            data => *data = self.apply(data),
+        }
+    }
+}
+
+/// Parse a path expression into its steps
+fn parse_expression(expression: &str) -> std::result::Result<Vec<Step>, String> {
+    let mut rest = expression.trim();
+    rest = rest.strip_prefix('$').unwrap_or(rest);
+    if rest == "." {
+        return Ok(Vec::new());
+    }
+
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            if let Some(after_star) = after_dot.strip_prefix('*') {
+                steps.push(Step::Wildcard);
+                rest = after_star;
+                continue;
+            }
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            if end == 0 {
+                return Err("expected a field name after '.'".to_string());
+            }
+            steps.push(Step::Field(after_dot[..end].to_string()));
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let (step, after_step) = parse_bracket(after_bracket)?;
+            steps.push(step);
+            rest = after_step;
+        } else {
+            return Err(format!("expected '.' or '[' at '{rest}'"));
+        }
+    }
+    Ok(steps)
+}
+
+/// Parse the inside of a `[...]` step, returning it with the text after the `]`
+fn parse_bracket(text: &str) -> std::result::Result<(Step, &str), String> {
+    let (step, after) =
+        if let Some(quote) = text.chars().next().filter(|c| ['"', '\''].contains(c)) {
+            let inner = &text[1..];
+            let end = inner
+                .find(quote)
+                .ok_or_else(|| format!("unterminated {quote} in '[{text}'"))?;
+            (Step::Field(inner[..end].to_string()), &inner[end + 1..])
+        } else {
+            let end = text
+                .find(']')
+                .ok_or_else(|| format!("missing ']' in '[{text}'"))?;
+            let inner = text[..end].trim();
+            let step = if inner == "*" {
+                Step::Wildcard
+            } else {
+                Step::Index(inner.parse().map_err(|_| {
+                    format!("expected an index, '*' or a quoted name in '[{inner}]'")
+                })?)
+            };
+            (step, &text[end..])
+        };
+    after
+        .strip_prefix(']')
+        .map(|after| (step, after))
+        .ok_or_else(|| format!("missing ']' after '[{text}'"))
+}
+
+/// Select what a path matches in a value
+fn select(value: &Value, steps: &[Step]) -> Value {
+    let mut matches = Vec::new();
+    collect(value, steps, &mut matches);
+    if steps.contains(&Step::Wildcard) {
+        Value::Array(matches.into_iter().cloned().collect())
+    } else {
+        matches.first().map_or(Value::Null, |&value| value.clone())
+    }
+}
+
+/// Collect the values a path matches, in document order
+fn collect<'a>(value: &'a Value, steps: &[Step], matches: &mut Vec<&'a Value>) {
+    let Some((step, rest)) = steps.split_first() else {
+        matches.push(value);
+        return;
+    };
+    match (step, value) {
+        (Step::Field(name), Value::Object(map)) => {
+            if let Some(field) = field(map, name) {
+                collect(field, rest, matches);
+            }
+        }
+        (Step::Index(index), Value::Array(items)) => {
+            if let Some(item) = items.get(*index) {
+                collect(item, rest, matches);
+            }
+        }
+        (Step::Wildcard, Value::Object(map)) => {
+            for field in map.values() {
+                collect(field, rest, matches);
+            }
+        }
+        (Step::Wildcard, Value::Array(items)) => {
+            for item in items {
+                collect(item, rest, matches);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The field of an object with a name, or the only field named by a type path ending in it
+fn field<'a>(map: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    map.get(name).or_else(|| {
+        let suffix = format!("::{name}");
+        let mut candidates = map
+            .iter()
+            .filter(|(key, _)| key.ends_with(&suffix))
+            .map(|(_, value)| value);
+        let candidate = candidates.next()?;
+        candidates.next().is_none().then_some(candidate)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_projection_selects_row_fields() {
+        let mut rows = json!([
+            {
+                "entity": 1,
+                "components": {
+                    "bevy_transform::components::transform::Transform": {
+                        "translation": [1.0, 2.0, 3.0],
+                        "scale": [1.0, 1.0, 1.0]
+                    },
+                    "bevy_ecs::name::Name": "player"
+                }
+            },
+            { "entity": 2, "components": { "bevy_ecs::name::Name": "enemy" } }
+        ]);
+        let projection =
+            Projection::parse(&json!("$.components[\"Transform\"].translation")).unwrap();
+        projection.project_result(BRP_METHOD_QUERY, &mut rows);
+        assert_eq!(
+            rows,
+            json!([
+                { "entity": 1, "value": [1.0, 2.0, 3.0] },
+                { "entity": 2, "value": null }
+            ])
+        );
+
+        let get = json!({ "components": { "a::Name": "player", "b::Health": { "hp": [3, 4] } } });
+        let projection =
+            Projection::parse(&json!([".components.Name", "$..", ".components.*.hp[1]"]));
+        assert!(projection.is_err());
+        let projection =
+            Projection::parse(&json!([".components.Name", ".components.*.hp[1]"])).unwrap();
+        assert_eq!(
+            projection.apply(&get),
+            json!({ ".components.Name": "player", ".components.*.hp[1]": [4] })
+        );
+
+        assert!(Projection::parse(&json!("components")).is_err());
+        assert!(Projection::parse(&json!("$.components[\"Name\"")).is_err());
+        assert_eq!(Projection::parse(&json!("$")).unwrap().apply(&get), get);
+    }
+}
@@ -0,0 +1,177 @@
+//! Timeout and retry policy of BRP requests
+//!
+//! Every BRP tool has a default policy for its method, which a call can override with
+//! `timeout_ms`, `retries` and `backoff_ms`. The policy applies to every BRP request made while
+//! handling the call, the same way a `host` argument does. Requests that never reached the app
+//! (refused connections) are always safe to retry; requests that timed out are only retried for
+//! methods that don't change the app, since the app may have applied them.
+
+use std::time::Duration;
+
+use rmcp::model::CallToolRequestParam;
+
+use crate::brp_tools::constants::{PARAM_BACKOFF_MS, PARAM_RETRIES, PARAM_TIMEOUT_MS};
+use crate::error::{Error, Result};
+use crate::tools::{
+    BRP_METHOD_EXTRAS_DISCOVER_FORMAT, BRP_METHOD_GET, BRP_METHOD_GET_RESOURCE, BRP_METHOD_LIST,
+    BRP_METHOD_LIST_RESOURCES, BRP_METHOD_QUERY, BRP_METHOD_REGISTRY_SCHEMA,
+    BRP_METHOD_RPC_DISCOVER,
+};
+
+/// Time a BRP request may take before it fails
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Retries of a failed BRP request
+pub const DEFAULT_RETRIES: u32 = 2;
+
+/// Delay before the first retry, doubled for every further one
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Methods that only read from the app, so a timed-out request can be sent again
+const READ_ONLY_METHODS: &[&str] = &[
+    BRP_METHOD_GET,
+    BRP_METHOD_GET_RESOURCE,
+    BRP_METHOD_LIST,
+    BRP_METHOD_LIST_RESOURCES,
+    BRP_METHOD_QUERY,
+    BRP_METHOD_REGISTRY_SCHEMA,
+    BRP_METHOD_RPC_DISCOVER,
+    BRP_METHOD_EXTRAS_DISCOVER_FORMAT,
+];
+
+/// Whether a BRP method only reads from the app
+pub fn is_read_only(method: &str) -> bool {
+    READ_ONLY_METHODS.contains(&method)
+}
+
+tokio::task_local! {
+    /// Policy of the BRP requests made while handling the current tool call
+    static REQUEST_POLICY: RequestPolicy;
+}
+
+/// How long BRP requests may take and how they are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestPolicy {
+    /// Time a request may take before it fails
+    pub timeout:        Duration,
+    /// Retries after the first attempt
+    pub retries:        u32,
+    /// Delay before the first retry, doubled for every further one
+    pub backoff:        Duration,
+    /// Whether timed-out requests are retried, not only refused connections
+    pub retry_timeouts: bool,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout:        DEFAULT_TIMEOUT,
+            retries:        DEFAULT_RETRIES,
+            backoff:        DEFAULT_BACKOFF,
+            retry_timeouts: false,
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// The default policy of a BRP method
+    pub fn for_method(method: &str) -> Self {
+        Self {
+            retry_timeouts: is_read_only(method),
+            ..Self::default()
+        }
+    }
+
+    /// Remove `timeout_ms`, `retries` and `backoff_ms` from a call's arguments, applying them to
+    /// this policy
+    pub fn with_overrides(mut self, request: &mut CallToolRequestParam) -> Result<Self> {
+        let Some(arguments) = request.arguments.as_mut() else {
+            return Ok(self);
+        };
+        let mut take = |name: &str| {
+            arguments
+                .remove(name)
+                .filter(|value| !value.is_null())
+                .map(|value| {
+                    value.as_u64().ok_or_else(|| {
+                        error_stack::Report::new(Error::invalid(
+                            &format!("parameter '{name}'"),
+                            format!("{value} is not a non-negative integer"),
+                        ))
+                    })
+                })
+                .transpose()
+        };
+        if let Some(timeout_ms) = take(PARAM_TIMEOUT_MS)? {
+            self.timeout = Duration::from_millis(timeout_ms);
+        }
+        if let Some(retries) = take(PARAM_RETRIES)? {
+            self.retries = u32::try_from(retries).unwrap_or(u32::MAX);
+        }
+        if let Some(backoff_ms) = take(PARAM_BACKOFF_MS)? {
+            self.backoff = Duration::from_millis(backoff_ms);
+        }
+        Ok(self)
+    }
+
+    /// Whether a request that failed on its `attempt`th try (counting from 0) is sent again
+    pub const fn should_retry(&self, attempt: u32, timed_out: bool) -> bool {
+        attempt < self.retries && (!timed_out || self.retry_timeouts)
+    }
+
+    /// Delay before retrying a request that failed on its `attempt`th try
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        self.backoff.saturating_mul(1 << attempt.min(16))
+    }
+}
+
+/// Run a future with all BRP requests it makes following `policy`
+pub async fn scope<F: Future>(policy: RequestPolicy, future: F) -> F::Output {
+    REQUEST_POLICY.scope(policy, future).await
+}
+
+/// Policy BRP requests currently follow, the default outside of a tool call
+pub fn current() -> RequestPolicy {
+    REQUEST_POLICY
+        .try_with(|policy| *policy)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_overrides_and_retry_decisions() {
+        let mut request = CallToolRequestParam {
+            name:      "bevy_spawn".into(),
+            arguments: json!({ "timeout_ms": 500, "retries": 1, "backoff_ms": null, "port": 1 })
+                .as_object()
+                .cloned(),
+        };
+        let policy = RequestPolicy::for_method("bevy/spawn")
+            .with_overrides(&mut request)
+            .unwrap();
+        assert_eq!(policy.timeout, Duration::from_millis(500));
+        assert_eq!(policy.backoff, DEFAULT_BACKOFF);
+        assert_eq!(
+            request.arguments.unwrap().keys().collect::<Vec<_>>(),
+            ["port"]
+        );
+
+        assert!(policy.should_retry(0, false));
+        assert!(!policy.should_retry(0, true));
+        assert!(!policy.should_retry(1, false));
+        assert!(RequestPolicy::for_method(BRP_METHOD_QUERY).should_retry(0, true));
+        assert_eq!(policy.backoff_delay(2), Duration::from_millis(400));
+
+        let mut invalid = CallToolRequestParam {
+            name:      "bevy_get".into(),
+            arguments: json!({ "retries": -1 }).as_object().cloned(),
+        };
+        assert!(policy.with_overrides(&mut invalid).is_err());
+    }
+}
@@ -1,15 +1,46 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use rmcp::model::CallToolResult;
 use serde_json::{Value, json};
+use tracing::warn;
 
 use super::brp_client::BrpError;
 use crate::brp_tools::constants::{
     BRP_ERROR_CODE_INVALID_REQUEST, JSON_FIELD_CODE, JSON_FIELD_DATA, JSON_FIELD_DEBUG_INFO,
-    JSON_FIELD_ERROR_CODE, JSON_FIELD_METADATA, JSON_FIELD_METHOD, JSON_FIELD_PORT,
+    JSON_FIELD_ERROR_CODE, JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_METADATA, JSON_FIELD_METHOD,
+    JSON_FIELD_ORIGINAL_PARAMS_MODIFIED, JSON_FIELD_PORT, MAX_RESPONSE_TOKENS,
 };
-use crate::brp_tools::request_handler::FormatterContext;
-use crate::error::Result;
+use crate::brp_tools::request_handler::{EnhancedBrpResult, FormatCorrection, FormatterContext};
+use crate::config;
+use crate::error::{ErrorCode, Result};
 use crate::support::response::{JsonResponse, ResponseBuilder};
-use crate::support::serialization::json_response_to_result;
+use crate::support::serialization::{json_response_to_result, result_to_json};
+use crate::tools::{
+    BRP_METHOD_DESTROY, BRP_METHOD_INSERT, BRP_METHOD_INSERT_RESOURCE, BRP_METHOD_MUTATE_COMPONENT,
+    BRP_METHOD_MUTATE_RESOURCE, BRP_METHOD_REMOVE, BRP_METHOD_REMOVE_RESOURCE, BRP_METHOD_REPARENT,
+    BRP_METHOD_SPAWN, TOOL_READ_RESPONSE,
+};
+
+/// BRP methods that change the world, whose responses always say whether their params were
+/// modified
+pub const MUTATING_METHODS: &[&str] = &[
+    BRP_METHOD_SPAWN,
+    BRP_METHOD_INSERT,
+    BRP_METHOD_INSERT_RESOURCE,
+    BRP_METHOD_MUTATE_COMPONENT,
+    BRP_METHOD_MUTATE_RESOURCE,
+    BRP_METHOD_REMOVE,
+    BRP_METHOD_REMOVE_RESOURCE,
+    BRP_METHOD_DESTROY,
+    BRP_METHOD_REPARENT,
+];
+
+/// Fields of the response envelope reporting format corrections
+const CORRECTION_FIELDS: [&str; 2] = [
+    JSON_FIELD_FORMAT_CORRECTIONS,
+    JSON_FIELD_ORIGINAL_PARAMS_MODIFIED,
+];
 
 /// Metadata about a BRP request for response formatting
 #[derive(Debug, Clone)]
@@ -27,16 +58,57 @@ impl BrpMetadata {
     }
 }
 
+/// Describe the corrections format discovery applied to a request
+pub fn corrections_json(corrections: &[FormatCorrection]) -> Value {
+    Value::Array(
+        corrections
+            .iter()
+            .map(|correction| {
+                json!({
+                    "component": correction.component,
+                    "original_format": correction.original_format,
+                    "corrected_format": correction.corrected_format,
+                    "hint": correction.hint,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// The format correction fields of a response: `format_corrections` when there are any, and
+/// `original_params_modified` for mutating methods
+pub fn correction_fields(method: &str, result: &EnhancedBrpResult) -> Vec<(&'static str, Value)> {
+    let mut fields = Vec::new();
+    if !result.format_corrections.is_empty() {
+        fields.push((
+            JSON_FIELD_FORMAT_CORRECTIONS,
+            corrections_json(&result.format_corrections),
+        ));
+    }
+    if MUTATING_METHODS.contains(&method) {
+        fields.push((
+            JSON_FIELD_ORIGINAL_PARAMS_MODIFIED,
+            json!(result.params_modified),
+        ));
+    }
+    fields
+}
+
+/// Move the format correction fields out of error data, so they're reported at the top level
+/// of the response like those of successes
+fn take_correction_fields(data: &mut Option<Value>) -> Vec<(&'static str, Value)> {
+    let Some(Value::Object(map)) = data else {
+        return Vec::new();
+    };
+    CORRECTION_FIELDS
+        .into_iter()
+        .filter_map(|field| map.remove(field).map(|value| (field, value)))
+        .collect()
+}
+
 /// Default error formatter implementation
 pub fn format_error_default(mut error: BrpError, metadata: &BrpMetadata) -> CallToolResult {
-    // Enhance error messages for common format issues
-    if error.code == BRP_ERROR_CODE_INVALID_REQUEST
-        && error.message.contains("expected a sequence of")
-    {
-        error.message.push_str(
-            "\nHint: Math types like Vec3 use array format [x,y,z], not objects {x:1,y:2,z:3}",
-        );
-    }
+    add_hints(&mut error);
 
     build_default_error_response(&error, metadata).map_or_else(
         |_| {
@@ -49,14 +121,53 @@ pub fn format_error_default(mut error: BrpError, metadata: &BrpMetadata) -> Call
     )
 }
 
+/// Enhance error messages for common format and path issues
+fn add_hints(error: &mut BrpError) {
+    let hint = if error.code == BRP_ERROR_CODE_INVALID_REQUEST
+        && error.message.contains("expected a sequence of")
+    {
+        "Hint: Math types like Vec3 use array format [x,y,z], not objects {x:1,y:2,z:3}"
+    } else if error.message.contains("Error accessing element") {
+        "Hint: Use bevy_list_mutation_paths to list the valid paths of the type"
+    } else {
+        return;
+    };
+    if !error.message.contains(hint) {
+        error.message.push('\n');
+        error.message.push_str(hint);
+    }
+}
+
+/// Error response for a BRP error, with its `error_code` category and `details`
+fn brp_error_builder(error: &BrpError, metadata: &BrpMetadata) -> ResponseBuilder {
+    let corrections_attempted = error
+        .data
+        .as_ref()
+        .and_then(|data| data.get(JSON_FIELD_FORMAT_CORRECTIONS))
+        .and_then(Value::as_array)
+        .is_some_and(|corrections| !corrections.is_empty());
+    ResponseBuilder::error()
+        .message(&error.message)
+        .error_code(ErrorCode::from_brp_code(error.code, corrections_attempted))
+        .details(json!({
+            "brp_code": error.code,
+            JSON_FIELD_METHOD: metadata.method,
+            JSON_FIELD_PORT: metadata.port,
+        }))
+}
+
 fn build_default_error_response(
     error: &BrpError,
     metadata: &BrpMetadata,
 ) -> Result<crate::support::response::JsonResponse> {
-    let response = ResponseBuilder::error()
-        .message(&error.message)
+    let mut data = error.data.clone();
+    let mut builder = brp_error_builder(error, metadata);
+    for (field_name, value) in take_correction_fields(&mut data) {
+        builder = builder.add_field(field_name, value)?;
+    }
+    let response = builder
         .add_field(JSON_FIELD_ERROR_CODE, error.code)?
-        .add_field(JSON_FIELD_DATA, &error.data)?
+        .add_field(JSON_FIELD_DATA, &data)?
         .add_field(
             JSON_FIELD_METADATA,
             json!({
@@ -96,8 +207,14 @@ impl ResponseFormatter {
         Self { config, context }
     }
 
-    pub fn format_success(&self, data: &Value, _metadata: BrpMetadata) -> CallToolResult {
-        self.build_success_response(data).map_or_else(
+    /// Format a success response, appending `extra_fields` that do not come from the data
+    pub fn format_success(
+        &self,
+        data: &Value,
+        _metadata: BrpMetadata,
+        extra_fields: &[(&str, Value)],
+    ) -> CallToolResult {
+        self.build_success_response(data, extra_fields).map_or_else(
             |_| {
                 let fallback = ResponseBuilder::error()
                     .message("Failed to build success response")
@@ -111,6 +228,7 @@ impl ResponseFormatter {
     fn build_success_response(
         &self,
         data: &Value,
+        extra_fields: &[(&str, Value)],
     ) -> Result<crate::support::response::JsonResponse> {
         let mut builder = ResponseBuilder::success();
 
@@ -122,23 +240,17 @@ impl ResponseFormatter {
             template_values.extend(params.clone());
         }
 
-        // Extract debug info and format corrections from data first
+        // Extract debug info from data first
         let mut clean_data = data.clone();
         let mut brp_extras_debug_info = None;
 
         if let Value::Object(data_map) = data {
             // Extract brp_extras_debug_info from data (if exists)
-            if let Some(debug_info) = data_map.get(JSON_FIELD_DEBUG_INFO) {
-                if !debug_info.is_null() && (debug_info.is_array() || debug_info.is_string()) {
-                    brp_extras_debug_info = Some(debug_info.clone());
-                }
-            }
-
-            // Always preserve format_corrections from the input data
-            if let Some(format_corrections) = data_map.get("format_corrections") {
-                if !format_corrections.is_null() && format_corrections.is_array() {
-                    builder = builder.add_field("format_corrections", format_corrections)?;
-                }
+            if let Some(debug_info) = data_map.get(JSON_FIELD_DEBUG_INFO)
+                && !debug_info.is_null()
+                && (debug_info.is_array() || debug_info.is_string())
+            {
+                brp_extras_debug_info = Some(debug_info.clone());
             }
 
             // Clean debug_info from data to prevent duplication
@@ -157,6 +269,10 @@ impl ResponseFormatter {
             template_values.insert(field_name.clone(), value);
         }
 
+        for (field_name, value) in extra_fields {
+            builder = builder.add_field(field_name, value)?;
+        }
+
         // Apply success template if provided (after collecting all field values)
         if let Some(template) = &self.config.success_template {
             let template_params = Value::Object(template_values);
@@ -174,14 +290,7 @@ impl ResponseFormatter {
     }
 
     pub fn format_error(&self, mut error: BrpError, metadata: &BrpMetadata) -> CallToolResult {
-        // Enhance error messages for common format issues
-        if error.code == BRP_ERROR_CODE_INVALID_REQUEST
-            && error.message.contains("expected a sequence of")
-        {
-            error.message.push_str(
-                "\nHint: Math types like Vec3 use array format [x,y,z], not objects {x:1,y:2,z:3}",
-            );
-        }
+        add_hints(&mut error);
 
         if self.config.use_default_error {
             format_error_default(error, metadata)
@@ -218,30 +327,34 @@ impl ResponseFormatter {
         metadata_obj: Value,
         metadata: &BrpMetadata,
     ) -> Result<JsonResponse> {
-        let mut builder = ResponseBuilder::error().message(&error.message);
+        let mut builder = brp_error_builder(error, metadata);
 
         // Extract debug info from error data if present
         let mut clean_error_data = error.data.clone();
         let mut brp_extras_debug_info = None;
 
-        if let Some(ref data) = error.data {
-            if let Some(data_obj) = data.as_object() {
-                // Extract brp_extras_debug_info from error data (if exists)
-                if let Some(debug_info) = data_obj.get(JSON_FIELD_DEBUG_INFO) {
-                    if !debug_info.is_null() && (debug_info.is_array() || debug_info.is_string()) {
-                        brp_extras_debug_info = Some(debug_info.clone());
-                    }
-                }
+        if let Some(ref data) = error.data
+            && let Some(data_obj) = data.as_object()
+        {
+            // Extract brp_extras_debug_info from error data (if exists)
+            if let Some(debug_info) = data_obj.get(JSON_FIELD_DEBUG_INFO)
+                && !debug_info.is_null()
+                && (debug_info.is_array() || debug_info.is_string())
+            {
+                brp_extras_debug_info = Some(debug_info.clone());
+            }
 
-                // Clean debug_info from error data to prevent duplication
-                if let Some(Value::Object(clean_map)) = &mut clean_error_data {
-                    clean_map.remove(JSON_FIELD_DEBUG_INFO);
-                }
+            // Clean debug_info from error data to prevent duplication
+            if let Some(Value::Object(clean_map)) = &mut clean_error_data {
+                clean_map.remove(JSON_FIELD_DEBUG_INFO);
             }
         }
 
         // Handle special BRP execution error format
         if metadata.method == "brp_execute" {
+            for (field_name, value) in take_correction_fields(&mut clean_error_data) {
+                builder = builder.add_field(field_name, value)?;
+            }
             builder = builder
                 .add_field(JSON_FIELD_CODE, error.code)?
                 .add_field(JSON_FIELD_DATA, clean_error_data.unwrap_or(Value::Null))?;
@@ -410,6 +523,160 @@ fn substitute_template(template: &str, params: Option<&Value>) -> String {
 
 // Response size estimation functions
 
+/// Environment variable setting the response size in bytes above which results are spilled to a
+/// file; `0` disables spilling
+pub const MAX_RESPONSE_BYTES_ENV_VAR: &str = "BRP_MCP_MAX_RESPONSE_BYTES";
+
+/// Prefix of spilled response files in the temp directory
+pub const SPILL_FILE_PREFIX: &str = "brp_response_";
+
+/// Extension of spilled response files
+pub const SPILL_FILE_EXTENSION: &str = ".json";
+
+/// Rough number of characters per token, used for the default size limit
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Response size above which results are spilled, or `None` when spilling is disabled
+///
+/// The environment variable wins over the `max_response_bytes` configuration key.
+pub fn max_response_bytes() -> Option<usize> {
+    let default = config::current()
+        .max_response_bytes
+        .unwrap_or(MAX_RESPONSE_TOKENS * CHARS_PER_TOKEN);
+    let bytes = std::env::var(MAX_RESPONSE_BYTES_ENV_VAR).map_or(default, |value| {
+        value.trim().parse::<usize>().unwrap_or_else(|_| {
+            warn!("Ignoring invalid {MAX_RESPONSE_BYTES_ENV_VAR} '{value}'; using {default}");
+            default
+        })
+    });
+    (bytes > 0).then_some(bytes)
+}
+
+/// Path of a spilled response file, or `None` if `filename` is not one
+pub fn spill_file_path(filename: &str) -> Option<PathBuf> {
+    let valid = filename.starts_with(SPILL_FILE_PREFIX)
+        && filename.ends_with(SPILL_FILE_EXTENSION)
+        && !filename.contains(['/', '\\'])
+        && !filename.contains("..");
+    valid.then(|| std::env::temp_dir().join(filename))
+}
+
+/// Replace a tool result larger than [`max_response_bytes`] with a summary of it
+///
+/// The full JSON is written, pretty-printed, to a file in the temp directory that
+/// `brp_read_response` can page through. Results of `brp_read_response` itself are never
+/// spilled, so paging always makes progress.
+pub fn spill_large_result(result: CallToolResult, tool_name: &str) -> CallToolResult {
+    let Some(max_bytes) = max_response_bytes() else {
+        return result;
+    };
+    let size: usize = result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text())
+        .map(|text| text.text.len())
+        .sum();
+    if size <= max_bytes || tool_name == TOOL_READ_RESPONSE {
+        return result;
+    }
+
+    let full = result_to_json(&result);
+    let (filepath, total_lines) = match write_spill_file(tool_name, &full) {
+        Ok(spilled) => spilled,
+        Err(e) => {
+            warn!("Failed to spill large {tool_name} response: {e}");
+            return result;
+        }
+    };
+    let filename = filepath
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Response too large ({size} bytes, limit {max_bytes}). Saved to {}",
+            filepath.display()
+        ))
+        .data(json!({
+            "filename": filename,
+            "filepath": filepath.to_string_lossy(),
+            "size_bytes": size,
+            "total_lines": total_lines,
+            "summary": summarize(&full),
+            "instructions": format!(
+                "Use {TOOL_READ_RESPONSE} with this filename to page through it, or Read, Grep or jq on the filepath"
+            ),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+    CallToolResult {
+        is_error: result.is_error,
+        ..json_response_to_result(&response)
+    }
+}
+
+/// Write a response to a new spill file, returning its path and line count
+fn write_spill_file(tool_name: &str, response: &Value) -> std::io::Result<(PathBuf, usize)> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    let sanitized_tool: String = tool_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let filepath = std::env::temp_dir().join(format!(
+        "{SPILL_FILE_PREFIX}{sanitized_tool}_{millis}{SPILL_FILE_EXTENSION}"
+    ));
+
+    let pretty = serde_json::to_string_pretty(response).map_err(std::io::Error::other)?;
+    std::fs::write(&filepath, &pretty)?;
+    Ok((filepath, pretty.lines().count()))
+}
+
+/// Describe the shape of a response without its bulk
+///
+/// Small scalars are kept as they are; arrays, objects and long strings are replaced by their
+/// size, one level below the top.
+fn summarize(value: &Value) -> Value {
+    const MAX_SUMMARY_STRING: usize = 200;
+
+    let describe = |value: &Value| match value {
+        Value::Array(items) => json!(format!("array of {} items", items.len())),
+        Value::Object(fields) => json!(format!("object with {} fields", fields.len())),
+        Value::String(text) if text.len() > MAX_SUMMARY_STRING => {
+            json!(format!("string of {} bytes", text.len()))
+        }
+        _ => value.clone(),
+    };
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, field)| match field {
+                    Value::Object(inner) => (
+                        key.clone(),
+                        Value::Object(
+                            inner
+                                .iter()
+                                .map(|(key, value)| (key.clone(), describe(value)))
+                                .collect(),
+                        ),
+                    ),
+                    _ => (key.clone(), describe(field)),
+                })
+                .collect(),
+        ),
+        _ => describe(value),
+    }
+}
+
 // Common field extractors
 
 // Helper functions for common field extractors
@@ -510,7 +777,7 @@ mod tests {
 
         let formatter = ResponseFormatter::new(config, context);
         let metadata = BrpMetadata::new("bevy/destroy", DEFAULT_BRP_PORT);
-        let result = formatter.format_success(&Value::Null, metadata);
+        let result = formatter.format_success(&Value::Null, metadata, &[]);
 
         // Verify result has content
         assert_eq!(result.content.len(), 1);
@@ -581,6 +848,46 @@ mod tests {
         // TODO: Add proper content validation once Content type is understood
     }
 
+    #[test]
+    fn test_format_corrections_share_one_envelope() {
+        let correction = FormatCorrection {
+            component:        "bevy_transform::components::transform::Transform".to_string(),
+            original_format:  json!({ "x": 1.0, "y": 2.0, "z": 3.0 }),
+            corrected_format: json!([1.0, 2.0, 3.0]),
+            hint:             "Converted object to array".to_string(),
+        };
+        let enhanced = EnhancedBrpResult {
+            result:             crate::brp_tools::support::brp_client::BrpResult::Success(None),
+            params_modified:    correction.changes_value(),
+            format_corrections: vec![correction],
+            debug_info:         vec![],
+        };
+        let fields = correction_fields("bevy/get", &enhanced);
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0, JSON_FIELD_FORMAT_CORRECTIONS);
+
+        // Errors report the fields next to those of successes, not inside the BRP error data
+        let data = Value::Object(
+            correction_fields("bevy/insert", &enhanced)
+                .into_iter()
+                .map(|(field_name, value)| (field_name.to_string(), value))
+                .collect(),
+        );
+        let error = BrpError {
+            code:    -23402,
+            message: "Test error".to_string(),
+            data:    Some(data),
+        };
+        let metadata = BrpMetadata::new("bevy/insert", DEFAULT_BRP_PORT);
+        let response = result_to_json(&format_error_default(error, &metadata));
+        assert_eq!(response["data"][JSON_FIELD_ORIGINAL_PARAMS_MODIFIED], true);
+        assert_eq!(
+            response["data"][JSON_FIELD_FORMAT_CORRECTIONS][0]["corrected_format"],
+            json!([1.0, 2.0, 3.0])
+        );
+        assert_eq!(response["data"][JSON_FIELD_DATA], json!({}));
+    }
+
     #[test]
     fn test_entity_operation_builder() {
         use crate::brp_tools::constants::JSON_FIELD_DESTROYED_ENTITY;
@@ -597,7 +904,7 @@ mod tests {
 
         let formatter = factory.create(context);
         let metadata = BrpMetadata::new("bevy/destroy", DEFAULT_BRP_PORT);
-        let result = formatter.format_success(&Value::Null, metadata);
+        let result = formatter.format_success(&Value::Null, metadata, &[]);
 
         // Verify result has content
         assert_eq!(result.content.len(), 1);
@@ -616,7 +923,7 @@ mod tests {
         let formatter = factory.create(context);
         let metadata = BrpMetadata::new("bevy/query", DEFAULT_BRP_PORT);
         let data = json!([{"entity": 1}, {"entity": 2}]);
-        let result = formatter.format_success(&data, metadata);
+        let result = formatter.format_success(&data, metadata, &[]);
 
         // Verify result has content
         assert_eq!(result.content.len(), 1);
@@ -670,4 +977,29 @@ mod tests {
             Value::Null
         );
     }
+
+    #[test]
+    fn test_spilled_responses_are_summarized() {
+        assert!(spill_file_path("brp_response_bevy_query_1.json").is_some());
+        assert!(spill_file_path("../brp_response_x.json").is_none());
+        assert!(spill_file_path("bevy_brp_mcp_app.log").is_none());
+
+        let response = json!({
+            "status": "success",
+            "message": "Query completed successfully",
+            "data": { "data": [1, 2, 3], "entity_count": 3, "query_params": { "data": {} } }
+        });
+        assert_eq!(
+            summarize(&response),
+            json!({
+                "status": "success",
+                "message": "Query completed successfully",
+                "data": {
+                    "data": "array of 3 items",
+                    "entity_count": 3,
+                    "query_params": "object with 1 fields"
+                }
+            })
+        );
+    }
 }
@@ -0,0 +1,400 @@
+//! Lenient parsing of raw BRP HTTP response bodies
+//!
+//! BRP servers normally answer with a well-formed JSON-RPC envelope, but proxies, port
+//! collisions with other services and crashing apps produce empty bodies, HTML error pages,
+//! truncated JSON or binary garbage. Instead of surfacing an opaque parse error, the body is
+//! classified and a size-limited preview (text or hex dump) is kept for diagnostics.
+
+use std::fmt::Write as _;
+
+use serde_json::Value;
+
+/// JSON-RPC "Internal error" code, used when an error object has no usable code
+const JSON_RPC_INTERNAL_ERROR: i32 = -32603;
+
+/// Maximum number of body bytes included in previews and hex dumps
+const MAX_PREVIEW_BYTES: usize = 256;
+
+/// Number of bytes per hex dump row
+const HEX_DUMP_ROW_BYTES: usize = 16;
+
+/// JSON-RPC response envelope, after lenient normalization
+#[derive(Debug)]
+pub struct BrpResponse {
+    pub result: Option<Value>,
+    pub error:  Option<JsonRpcError>,
+}
+
+/// JSON-RPC error object, after lenient normalization
+#[derive(Debug)]
+pub struct JsonRpcError {
+    pub code:    i32,
+    pub message: String,
+    pub data:    Option<Value>,
+}
+
+/// Classification of a response body that is not a usable JSON-RPC envelope
+#[derive(Debug, PartialEq, Eq)]
+pub enum MalformedResponse {
+    /// The body was empty or whitespace only
+    Empty,
+    /// An HTML page, typically an error page from a proxy or a different web server
+    Html { title: Option<String> },
+    /// The body is not valid JSON
+    InvalidJson { error: String, preview: String },
+    /// Valid JSON without a `result` or `error` member
+    NotJsonRpc { preview: String },
+}
+
+impl MalformedResponse {
+    /// Short machine-readable name of the classification
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::Html { .. } => "html",
+            Self::InvalidJson { .. } => "invalid_json",
+            Self::NotJsonRpc { .. } => "not_json_rpc",
+        }
+    }
+
+    /// Human-readable description including the body preview
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Empty => "Response body was empty".to_string(),
+            Self::Html { title } => format!(
+                "Response was an HTML page ({}) - is a proxy or another web server listening on this port?",
+                title.as_deref().unwrap_or("no title")
+            ),
+            Self::InvalidJson { error, preview } => {
+                format!("Response was not valid JSON ({error}). Body: {preview}")
+            }
+            Self::NotJsonRpc { preview } => {
+                format!("Response JSON has no 'result' or 'error' member. Body: {preview}")
+            }
+        }
+    }
+}
+
+/// Parse a raw response body into a JSON-RPC envelope
+///
+/// Missing `jsonrpc`/`id` members, error objects without a code and errors given as plain
+/// strings are accepted. Anything else is classified as a [`MalformedResponse`].
+pub fn parse_response_body(
+    body: &[u8],
+    content_type: Option<&str>,
+) -> Result<BrpResponse, MalformedResponse> {
+    let trimmed = body.trim_ascii();
+    if trimmed.is_empty() {
+        return Err(MalformedResponse::Empty);
+    }
+
+    if is_html(trimmed, content_type) {
+        return Err(MalformedResponse::Html {
+            title: html_title(trimmed),
+        });
+    }
+
+    let value: Value =
+        serde_json::from_slice(trimmed).map_err(|e| MalformedResponse::InvalidJson {
+            error:   e.to_string(),
+            preview: body_preview(body),
+        })?;
+
+    let Some(envelope) = value
+        .as_object()
+        .filter(|map| map.contains_key("result") || map.contains_key("error"))
+    else {
+        return Err(MalformedResponse::NotJsonRpc {
+            preview: body_preview(body),
+        });
+    };
+
+    let error = envelope
+        .get("error")
+        .filter(|error| !error.is_null())
+        .map(normalize_error);
+
+    Ok(BrpResponse {
+        result: envelope.get("result").cloned(),
+        error,
+    })
+}
+
+/// Build an error from whatever shape the `error` member has
+fn normalize_error(error: &Value) -> JsonRpcError {
+    match error {
+        Value::Object(map) => JsonRpcError {
+            code:    map
+                .get("code")
+                .and_then(Value::as_i64)
+                .and_then(|code| i32::try_from(code).ok())
+                .unwrap_or(JSON_RPC_INTERNAL_ERROR),
+            message: match map.get("message") {
+                Some(Value::String(message)) => message.clone(),
+                Some(other) => other.to_string(),
+                None => "Unknown error".to_string(),
+            },
+            data:    map.get("data").filter(|data| !data.is_null()).cloned(),
+        },
+        Value::String(message) => JsonRpcError {
+            code:    JSON_RPC_INTERNAL_ERROR,
+            message: message.clone(),
+            data:    None,
+        },
+        other => JsonRpcError {
+            code:    JSON_RPC_INTERNAL_ERROR,
+            message: other.to_string(),
+            data:    None,
+        },
+    }
+}
+
+/// Whether the body is an HTML page, by content type or by its opening tag
+fn is_html(body: &[u8], content_type: Option<&str>) -> bool {
+    if content_type.is_some_and(|content_type| content_type.contains("text/html")) {
+        return true;
+    }
+    let head = String::from_utf8_lossy(&body[..body.len().min(64)]).to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+/// Extract the contents of the `<title>` element, if any
+fn html_title(body: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(body);
+    let lower = text.to_ascii_lowercase();
+    let start = lower.find("<title")?;
+    let start = start + lower[start..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = text[start..end].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Size-limited preview of a body: text if it is valid UTF-8, a hex dump otherwise
+fn body_preview(body: &[u8]) -> String {
+    let limit = body.len().min(MAX_PREVIEW_BYTES);
+    let suffix = if body.len() > limit {
+        format!("... ({} bytes total)", body.len())
+    } else {
+        String::new()
+    };
+
+    std::str::from_utf8(body).map_or_else(
+        |_| format!("\n{}{suffix}", hex_dump(&body[..limit])),
+        |text| {
+            let end = (0..=limit)
+                .rev()
+                .find(|&end| text.is_char_boundary(end))
+                .unwrap_or(0);
+            format!("{}{suffix}", &text[..end])
+        },
+    )
+}
+
+/// Hex dump with offsets, hex bytes and printable ASCII columns
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+    for (row, chunk) in bytes.chunks(HEX_DUMP_ROW_BYTES).enumerate() {
+        let _ = write!(dump, "{:08x} ", row * HEX_DUMP_ROW_BYTES);
+        for byte in chunk {
+            let _ = write!(dump, " {byte:02x}");
+        }
+        dump.push_str(&"   ".repeat(HEX_DUMP_ROW_BYTES - chunk.len()));
+        dump.push_str("  |");
+        dump.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                char::from(byte)
+            } else {
+                '.'
+            }
+        }));
+        dump.push_str("|\n");
+    }
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// Expected outcome of parsing a corpus entry
+    enum Expected {
+        Success(Value),
+        Error(i32, &'static str),
+        Malformed(&'static str),
+    }
+
+    /// Odd responses seen from real servers, proxies and crashing apps
+    #[allow(clippy::too_many_lines)]
+    fn corpus() -> Vec<(&'static str, &'static [u8], Option<&'static str>, Expected)> {
+        vec![
+            (
+                "well-formed success",
+                br#"{"jsonrpc":"2.0","id":1,"result":[1,2]}"#,
+                Some("application/json"),
+                Expected::Success(json!([1, 2])),
+            ),
+            (
+                "success without jsonrpc and id",
+                br#"{"result":{"entity":7}}"#,
+                None,
+                Expected::Success(json!({ "entity": 7 })),
+            ),
+            (
+                "null result",
+                br#"{"jsonrpc":"2.0","id":1,"result":null}"#,
+                None,
+                Expected::Success(Value::Null),
+            ),
+            (
+                "surrounding whitespace",
+                b"\n\n  {\"result\": true}  \r\n",
+                None,
+                Expected::Success(json!(true)),
+            ),
+            (
+                "well-formed error",
+                br#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#,
+                None,
+                Expected::Error(-32601, "Method not found"),
+            ),
+            (
+                "error without code",
+                br#"{"error":{"message":"boom"}}"#,
+                None,
+                Expected::Error(JSON_RPC_INTERNAL_ERROR, "boom"),
+            ),
+            (
+                "error as plain string",
+                br#"{"error":"something broke"}"#,
+                None,
+                Expected::Error(JSON_RPC_INTERNAL_ERROR, "something broke"),
+            ),
+            (
+                "error code out of i32 range",
+                br#"{"error":{"code":99999999999,"message":"huge"}}"#,
+                None,
+                Expected::Error(JSON_RPC_INTERNAL_ERROR, "huge"),
+            ),
+            (
+                "null error alongside result",
+                br#"{"result":1,"error":null}"#,
+                None,
+                Expected::Success(json!(1)),
+            ),
+            ("empty body", b"", None, Expected::Malformed("empty")),
+            (
+                "whitespace body",
+                b"  \n\t",
+                None,
+                Expected::Malformed("empty"),
+            ),
+            (
+                "proxy error page",
+                b"<!DOCTYPE html><html><head><title>502 Bad Gateway</title></head></html>",
+                None,
+                Expected::Malformed("html"),
+            ),
+            (
+                "html by content type",
+                b"Service Unavailable",
+                Some("text/html; charset=utf-8"),
+                Expected::Malformed("html"),
+            ),
+            (
+                "truncated json",
+                br#"{"jsonrpc":"2.0","id":1,"result":{"compo"#,
+                None,
+                Expected::Malformed("invalid_json"),
+            ),
+            (
+                "plain text",
+                b"Not Found",
+                Some("text/plain"),
+                Expected::Malformed("invalid_json"),
+            ),
+            (
+                "binary garbage",
+                b"\x00\x01\xff\xfe\x80garbage",
+                None,
+                Expected::Malformed("invalid_json"),
+            ),
+            (
+                "json array",
+                b"[1, 2, 3]",
+                None,
+                Expected::Malformed("not_json_rpc"),
+            ),
+            (
+                "json object without envelope",
+                br#"{"status":"ok"}"#,
+                None,
+                Expected::Malformed("not_json_rpc"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_corpus() {
+        for (name, body, content_type, expected) in corpus() {
+            let parsed = parse_response_body(body, content_type);
+            match (expected, parsed) {
+                (Expected::Success(value), Ok(response)) => {
+                    assert!(response.error.is_none(), "{name}: unexpected error");
+                    assert_eq!(response.result.unwrap_or(Value::Null), value, "{name}");
+                }
+                (Expected::Error(code, message), Ok(response)) => {
+                    let error = response.error.unwrap();
+                    assert_eq!(error.code, code, "{name}");
+                    assert_eq!(error.message, message, "{name}");
+                }
+                (Expected::Malformed(kind), Err(malformed)) => {
+                    assert_eq!(malformed.kind(), kind, "{name}");
+                }
+                (_, parsed) => unreachable!("{name}: unexpected outcome {parsed:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_html_title_is_extracted() {
+        let parsed = parse_response_body(
+            b"<html><HEAD><Title> 504 Gateway Time-out </Title></HEAD></html>",
+            None,
+        );
+        assert_eq!(
+            parsed.err(),
+            Some(MalformedResponse::Html {
+                title: Some("504 Gateway Time-out".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_body_preview_is_size_limited() {
+        let text = "a".repeat(MAX_PREVIEW_BYTES * 2);
+        let preview = body_preview(text.as_bytes());
+        assert!(preview.starts_with(&"a".repeat(MAX_PREVIEW_BYTES)));
+        assert!(preview.ends_with(&format!("... ({} bytes total)", MAX_PREVIEW_BYTES * 2)));
+
+        // Truncation never splits a multi-byte character
+        let text = "é".repeat(MAX_PREVIEW_BYTES);
+        let preview = body_preview(text.as_bytes());
+        assert!(preview.starts_with(&"é".repeat(MAX_PREVIEW_BYTES / 2)));
+    }
+
+    #[test]
+    fn test_binary_body_is_hex_dumped() {
+        let body: Vec<u8> = (0..=u8::MAX).cycle().take(MAX_PREVIEW_BYTES + 10).collect();
+        let preview = body_preview(&body);
+        assert!(preview.contains("00000000  00 01 02 03"));
+        assert_eq!(
+            preview.lines().filter(|line| line.contains('|')).count(),
+            MAX_PREVIEW_BYTES / HEX_DUMP_ROW_BYTES
+        );
+        assert!(preview.ends_with(&format!("({} bytes total)", MAX_PREVIEW_BYTES + 10)));
+    }
+}
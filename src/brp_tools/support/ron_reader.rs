@@ -0,0 +1,410 @@
+//! Reading RON documents as BRP JSON
+//!
+//! Covers the subset of RON that Bevy writes for scenes: structs, tuples, lists, maps, enum
+//! variants, `Some`/`None`, strings, numbers and booleans, with comments and trailing commas.
+//! Values come out in the shape BRP expects: structs become objects, newtypes their inner
+//! value, tuples arrays and enum variants either a bare name or `{"Variant": payload}`.
+
+use serde_json::{Map, Number, Value};
+
+use crate::error::{Error, Result};
+
+/// Parse a RON document into JSON
+pub fn parse(text: &str) -> Result<Value> {
+    let mut reader = Reader {
+        chars: text.chars().collect(),
+        pos:   0,
+    };
+    reader.skip_attributes();
+    let value = reader.value();
+    let value = value.and_then(|value| {
+        reader.skip_whitespace();
+        if reader.pos < reader.chars.len() {
+            Err(reader.error("unexpected text after the document"))
+        } else {
+            Ok(value)
+        }
+    });
+    value.map_err(|message| error_stack::Report::new(Error::invalid("RON document", message)))
+}
+
+/// Contents of a parenthesized group
+enum Group {
+    /// `(name: value, ...)`
+    Fields(Map<String, Value>),
+    /// `(value, ...)`
+    Items(Vec<Value>),
+}
+
+impl Group {
+    /// The value of a struct or tuple with this content
+    fn into_value(self) -> Value {
+        match self {
+            Self::Fields(fields) => Value::Object(fields),
+            Self::Items(mut items) if items.len() == 1 => items.remove(0),
+            Self::Items(items) if items.is_empty() => Value::Object(Map::new()),
+            Self::Items(items) => Value::Array(items),
+        }
+    }
+}
+
+struct Reader {
+    chars: Vec<char>,
+    pos:   usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> String {
+        let consumed = &self.chars[..self.pos.min(self.chars.len())];
+        let line = consumed.iter().filter(|c| **c == '\n').count() + 1;
+        let column = consumed.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        format!("{message} at line {line}, column {column}")
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            match (self.peek(), self.chars.get(self.pos + 1)) {
+                (Some(c), _) if c.is_whitespace() => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    while self.pos < self.chars.len()
+                        && !(self.chars[self.pos] == '*'
+                            && self.chars.get(self.pos + 1) == Some(&'/'))
+                    {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Skip `#![enable(...)]` attributes at the start of the document
+    fn skip_attributes(&mut self) {
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('#') {
+                return;
+            }
+            while self.peek().is_some_and(|c| c != ']') {
+                self.pos += 1;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> std::result::Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{expected}'")))
+        }
+    }
+
+    /// Consume a `,` separator, returning whether the group continues
+    fn separator(&mut self, close: char) -> std::result::Result<bool, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(',') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                Ok(self.peek() != Some(close))
+            }
+            Some(c) if c == close => Ok(false),
+            _ => Err(self.error(&format!("expected ',' or '{close}'"))),
+        }
+    }
+
+    fn value(&mut self) -> std::result::Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => Ok(self.group()?.into_value()),
+            Some('[') => self.list(),
+            Some('{') => self.map(),
+            Some('"') => self.string().map(Value::String),
+            Some('\'') => self.char_literal(),
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.named(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of document")),
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// A bare identifier or a named group: booleans, `None`, `Some(..)`, enum variants
+    fn named(&mut self) -> std::result::Result<Value, String> {
+        let name = self.identifier();
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return Ok(match name.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "None" => Value::Null,
+                _ => Value::String(name),
+            });
+        }
+
+        let payload = match self.group()? {
+            Group::Items(mut items) if name == "Some" && items.len() == 1 => {
+                return Ok(items.remove(0));
+            }
+            group => group.into_value(),
+        };
+        let mut variant = Map::new();
+        variant.insert(name, payload);
+        Ok(Value::Object(variant))
+    }
+
+    fn group(&mut self) -> std::result::Result<Group, String> {
+        self.expect('(')?;
+        self.skip_whitespace();
+        if self.peek() == Some(')') {
+            self.pos += 1;
+            return Ok(Group::Items(Vec::new()));
+        }
+
+        // `name:` (but not `name::`) starts a struct
+        let start = self.pos;
+        let name = self.identifier();
+        self.skip_whitespace();
+        let is_struct = !name.is_empty()
+            && self.peek() == Some(':')
+            && self.chars.get(self.pos + 1) != Some(&':');
+        self.pos = start;
+
+        let group = if is_struct {
+            let mut fields = Map::new();
+            loop {
+                self.skip_whitespace();
+                let field = self.identifier();
+                if field.is_empty() {
+                    return Err(self.error("expected a field name"));
+                }
+                self.expect(':')?;
+                fields.insert(field, self.value()?);
+                if !self.separator(')')? {
+                    break;
+                }
+            }
+            Group::Fields(fields)
+        } else {
+            let mut items = Vec::new();
+            loop {
+                items.push(self.value()?);
+                if !self.separator(')')? {
+                    break;
+                }
+            }
+            Group::Items(items)
+        };
+        self.expect(')')?;
+        Ok(group)
+    }
+
+    fn list(&mut self) -> std::result::Result<Value, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            loop {
+                items.push(self.value()?);
+                if !self.separator(']')? {
+                    break;
+                }
+            }
+        }
+        self.expect(']')?;
+        Ok(Value::Array(items))
+    }
+
+    fn map(&mut self) -> std::result::Result<Value, String> {
+        self.expect('{')?;
+        let mut map = Map::new();
+        self.skip_whitespace();
+        if self.peek() != Some('}') {
+            loop {
+                // JSON object keys are strings, so non-string keys are written out
+                let key = match self.value()? {
+                    Value::String(key) => key,
+                    key => key.to_string(),
+                };
+                self.expect(':')?;
+                map.insert(key, self.value()?);
+                if !self.separator('}')? {
+                    break;
+                }
+            }
+        }
+        self.expect('}')?;
+        Ok(Value::Object(map))
+    }
+
+    fn string(&mut self) -> std::result::Result<String, String> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    text.push(self.escape()?);
+                }
+                Some(c) => {
+                    self.pos += 1;
+                    text.push(c);
+                }
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn char_literal(&mut self) -> std::result::Result<Value, String> {
+        self.expect('\'')?;
+        let c = match self.peek() {
+            Some('\\') => {
+                self.pos += 1;
+                self.escape()?
+            }
+            Some(c) => {
+                self.pos += 1;
+                c
+            }
+            None => return Err(self.error("unterminated character")),
+        };
+        self.expect('\'')?;
+        Ok(Value::String(c.to_string()))
+    }
+
+    /// The character of an escape sequence, after its backslash
+    fn escape(&mut self) -> std::result::Result<char, String> {
+        let escaped = self
+            .peek()
+            .ok_or_else(|| self.error("unterminated escape"))?;
+        self.pos += 1;
+        match escaped {
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            '0' => Ok('\0'),
+            'u' => {
+                self.expect('{')?;
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    self.pos += 1;
+                }
+                let hex: String = self.chars[start..self.pos].iter().collect();
+                self.expect('}')?;
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error("invalid unicode escape"))
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn number(&mut self) -> std::result::Result<Value, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.' | '_'))
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos]
+            .iter()
+            .filter(|c| **c != '_')
+            .collect();
+        let text = text.strip_prefix('+').unwrap_or(&text);
+
+        let number = text
+            .parse::<u64>()
+            .map(Number::from)
+            .or_else(|_| text.parse::<i64>().map(Number::from))
+            .ok()
+            .or_else(|| text.parse::<f64>().ok().and_then(Number::from_f64));
+        number
+            .map(Value::Number)
+            .ok_or_else(|| self.error(&format!("invalid number '{text}'")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_scene_values_become_brp_json() {
+        let scene = parse(
+            r#"#![enable(implicit_some)]
+            (
+                resources: {},
+                entities: {
+                    // The player
+                    4294967296: (
+                        components: {
+                            "bevy_ecs::name::Name": "Player \"one\"",
+                            "bevy_ecs::hierarchy::ChildOf": (8589934592),
+                            "game::Stats": (
+                                pos: (1.0, -2.5, 3e2),
+                                mode: Moving(speed: 2),
+                                target: Some(Point(1, 2)),
+                                home: None,
+                                state: Idle,
+                                tags: ['a', "b",],
+                            ),
+                        },
+                    ),
+                },
+            )"#,
+        )
+        .unwrap();
+
+        let components = &scene["entities"]["4294967296"]["components"];
+        assert_eq!(components["bevy_ecs::name::Name"], "Player \"one\"");
+        assert_eq!(
+            components["bevy_ecs::hierarchy::ChildOf"],
+            8_589_934_592_u64
+        );
+        assert_eq!(
+            components["game::Stats"],
+            json!({
+                "pos": [1.0, -2.5, 300.0],
+                "mode": { "Moving": { "speed": 2 } },
+                "target": { "Point": [1, 2] },
+                "home": null,
+                "state": "Idle",
+                "tags": ["a", "b"],
+            })
+        );
+
+        assert!(parse("(a: 1").is_err());
+        assert!(parse("(1) trailing").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Optional tagging of entities spawned through this server
+//!
+//! When `BRP_MCP_TAG_SPAWNED` is set, `bevy/spawn` requests made through the BRP tools get a
+//! `Name` of the form `mcp:spawned:<unix millis>` unless they already include one. Queries with
+//! `mcp_spawned_only` then return just the entities carrying such a name.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{Value, json};
+
+use crate::brp_tools::constants::JSON_FIELD_COMPONENTS;
+
+/// Environment variable enabling spawn tagging
+pub const TAG_SPAWNED_ENV_VAR: &str = "BRP_MCP_TAG_SPAWNED";
+
+/// Fully-qualified type name of Bevy's `Name` component
+pub const NAME_COMPONENT: &str = "bevy_ecs::name::Name";
+
+/// Prefix of the names given to tagged entities
+pub const SPAWNED_NAME_PREFIX: &str = "mcp:spawned:";
+
+/// Whether spawn tagging is enabled
+pub fn is_enabled() -> bool {
+    std::env::var(TAG_SPAWNED_ENV_VAR).is_ok_and(|value| {
+        ["1", "true", "yes", "on"].contains(&value.trim().to_ascii_lowercase().as_str())
+    })
+}
+
+/// Add a tag `Name` to `bevy/spawn` params, returning the name if one was added
+///
+/// Spawns that already set a `Name` are left alone.
+pub fn tag_spawn_params(params: &mut Value) -> Option<String> {
+    let Value::Object(params) = params else {
+        return None;
+    };
+    let components = params
+        .entry(JSON_FIELD_COMPONENTS)
+        .or_insert_with(|| json!({}))
+        .as_object_mut()?;
+    if components.contains_key(NAME_COMPONENT) {
+        return None;
+    }
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    let name = format!("{SPAWNED_NAME_PREFIX}{millis}");
+    components.insert(NAME_COMPONENT.to_string(), json!(name));
+    Some(name)
+}
+
+/// Restrict `bevy/query` params to entities with a `Name`, fetching it for filtering
+///
+/// Returns whether the `Name` was added to the fetched components and should be stripped from
+/// the rows again.
+pub fn restrict_query_params(params: &mut Value) -> bool {
+    if !params.is_object() {
+        *params = json!({});
+    }
+
+    let fetched = ["components", "option"].iter().any(|field| {
+        params["data"][field]
+            .as_array()
+            .is_some_and(|types| types.contains(&json!(NAME_COMPONENT)))
+    });
+    if !fetched {
+        push_type(&mut params["data"]["option"]);
+    }
+    push_type(&mut params["filter"]["with"]);
+
+    !fetched
+}
+
+fn push_type(types: &mut Value) {
+    match types {
+        Value::Array(types) if types.contains(&json!(NAME_COMPONENT)) => {}
+        Value::Array(types) => types.push(json!(NAME_COMPONENT)),
+        _ => *types = json!([NAME_COMPONENT]),
+    }
+}
+
+/// Keep only query rows for tagged entities, optionally removing the fetched `Name`
+pub fn retain_spawned_rows(data: &mut Value, strip_name: bool) {
+    let Value::Array(rows) = data else {
+        return;
+    };
+
+    rows.retain(|row| {
+        row[JSON_FIELD_COMPONENTS][NAME_COMPONENT]
+            .as_str()
+            .is_some_and(|name| name.starts_with(SPAWNED_NAME_PREFIX))
+    });
+    if strip_name {
+        for row in rows {
+            if let Some(components) = row
+                .get_mut(JSON_FIELD_COMPONENTS)
+                .and_then(Value::as_object_mut)
+            {
+                components.remove(NAME_COMPONENT);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_spawn_params_keeps_existing_names() {
+        let mut params = json!({ "components": { "Foo": {} } });
+        let name = tag_spawn_params(&mut params);
+        assert!(name.is_some_and(|name| name.starts_with(SPAWNED_NAME_PREFIX)));
+        assert!(params["components"][NAME_COMPONENT].is_string());
+
+        let mut params = json!({ "components": { NAME_COMPONENT: "player" } });
+        assert_eq!(tag_spawn_params(&mut params), None);
+        assert_eq!(params["components"][NAME_COMPONENT], "player");
+    }
+
+    #[test]
+    fn test_query_restricted_to_spawned_rows() {
+        let mut params = json!({ "data": { "components": ["Transform"] } });
+        assert!(restrict_query_params(&mut params));
+        assert_eq!(params["data"]["option"], json!([NAME_COMPONENT]));
+        assert_eq!(params["filter"]["with"], json!([NAME_COMPONENT]));
+
+        let mut data = json!([
+            { "entity": 1, "components": { NAME_COMPONENT: "mcp:spawned:1", "Transform": {} } },
+            { "entity": 2, "components": { NAME_COMPONENT: "enemy", "Transform": {} } }
+        ]);
+        retain_spawned_rows(&mut data, true);
+        assert_eq!(
+            data,
+            json!([{ "entity": 1, "components": { "Transform": {} } }])
+        );
+    }
+}
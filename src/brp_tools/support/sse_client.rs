@@ -0,0 +1,265 @@
+//! Client for BRP's streaming `+watch` methods
+//!
+//! Bevy answers `bevy/get+watch` and `bevy/list+watch` with a server-sent event stream over the
+//! kept-alive HTTP connection: one `data: {json-rpc response}` event per change. Apps that don't
+//! offer a method answer with a plain JSON-RPC error instead, and proxies that can't stream with
+//! an HTTP error, so `open` tells callers when to fall back to polling.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use reqwest::StatusCode;
+use reqwest::header::CONTENT_TYPE;
+use serde_json::Value;
+use tracing::{debug, error};
+
+use super::BrpJsonRpcBuilder;
+use super::brp_client::{self, build_brp_url};
+use crate::error::{Error, Result};
+
+/// Maximum size for a single chunk in the SSE stream (1MB)
+const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Maximum size for the total buffer when processing incomplete lines (10MB)
+const MAX_BUFFER_SIZE: usize = 10 * 1024 * 1024;
+
+/// JSON-RPC error code for methods the app doesn't offer
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Content type of server-sent event streams
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// What the app answered a `+watch` request with
+pub enum WatchConnection {
+    /// The app streams results
+    Streaming(SseStream),
+    /// The app or a proxy in front of it can't stream the method, with the reason
+    Unsupported(String),
+}
+
+/// Results of a server-sent event stream
+pub struct SseStream {
+    chunks:      Pin<Box<dyn Stream<Item = reqwest::Result<Vec<u8>>> + Send>>,
+    line_buffer: String,
+    results:     VecDeque<Value>,
+    ended:       bool,
+}
+
+impl SseStream {
+    fn new(response: reqwest::Response) -> Self {
+        Self {
+            chunks:      Box::pin(
+                response
+                    .bytes_stream()
+                    .map(|chunk| chunk.map(|bytes| bytes.to_vec())),
+            ),
+            line_buffer: String::new(),
+            results:     VecDeque::new(),
+            ended:       false,
+        }
+    }
+
+    /// The next streamed result, or `None` once the stream has ended
+    ///
+    /// Cancel safe: nothing received is lost when the future is dropped, so it can be raced
+    /// against timers in `tokio::select!`.
+    pub async fn next_result(&mut self) -> Result<Option<Value>> {
+        loop {
+            if let Some(result) = self.results.pop_front() {
+                return Ok(Some(result));
+            }
+            if self.ended {
+                return Ok(None);
+            }
+            match self.chunks.next().await {
+                Some(Ok(bytes)) => self.push_chunk(&bytes)?,
+                Some(Err(e)) => {
+                    error!("Error reading stream chunk: {}", e);
+                    self.finish();
+                }
+                None => self.finish(),
+            }
+        }
+    }
+
+    /// Add a chunk, queueing the results of the lines it completes
+    fn push_chunk(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.len() > MAX_CHUNK_SIZE {
+            return Err(error_stack::Report::new(Error::InvalidState(format!(
+                "Stream chunk size {} exceeds maximum {}",
+                bytes.len(),
+                MAX_CHUNK_SIZE
+            ))));
+        }
+
+        let text = match std::str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => {
+                debug!("Invalid UTF-8 in stream chunk: {}", e);
+                return Ok(());
+            }
+        };
+
+        self.line_buffer.push_str(text);
+        if self.line_buffer.len() > MAX_BUFFER_SIZE {
+            return Err(error_stack::Report::new(Error::InvalidState(format!(
+                "Stream buffer size {} exceeds maximum {}",
+                self.line_buffer.len(),
+                MAX_BUFFER_SIZE
+            ))));
+        }
+
+        while let Some(newline_pos) = self.line_buffer.find('\n') {
+            let line = self.line_buffer.drain(..=newline_pos).collect::<String>();
+            self.push_line(line.trim_end_matches('\n').trim_end_matches('\r'));
+        }
+        Ok(())
+    }
+
+    /// Process any remaining incomplete line and mark the stream ended
+    fn finish(&mut self) {
+        let rest = std::mem::take(&mut self.line_buffer);
+        if !rest.trim().is_empty() {
+            debug!("Processing remaining incomplete line: {}", rest.trim());
+            self.push_line(rest.trim());
+        }
+        self.ended = true;
+    }
+
+    /// Queue the JSON-RPC result of a `data: {json}` line
+    fn push_line(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        let Some(json_str) = line.strip_prefix("data: ") else {
+            debug!("Received non-SSE line: {}", line);
+            return;
+        };
+        let Ok(mut data) = serde_json::from_str::<Value>(json_str) else {
+            debug!("Failed to parse SSE data as JSON: {}", json_str);
+            return;
+        };
+        let Some(result) = data.get_mut("result") else {
+            debug!("No result in JSON-RPC response: {:?}", data);
+            return;
+        };
+        self.results.push_back(result.take());
+    }
+}
+
+/// Send a `+watch` request and find out whether the app streams it
+pub async fn open(method: &str, params: Value, port: u16) -> Result<WatchConnection> {
+    let url = build_brp_url(port);
+    let request_body = BrpJsonRpcBuilder::new(method)
+        .params(params)
+        .build()
+        .to_string();
+
+    let response = brp_client::brp_post(&url, port)?
+        .header("Content-Type", "application/json")
+        .body(request_body)
+        // Don't set timeout for streaming connections
+        .send()
+        .await
+        .map_err(|e| {
+            error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to connect to BRP server: {e}"
+            )))
+        })?;
+
+    let status = response.status();
+    if matches!(
+        status,
+        StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+    ) {
+        return Ok(WatchConnection::Unsupported(format!(
+            "server returned {status}"
+        )));
+    }
+    if !status.is_success() {
+        return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to process watch stream: server returned {}: {}",
+            status,
+            status.canonical_reason().unwrap_or("Unknown")
+        ))));
+    }
+
+    let is_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(EVENT_STREAM));
+    if is_stream {
+        return Ok(WatchConnection::Streaming(SseStream::new(response)));
+    }
+
+    let reply: Value = response.json().await.map_err(|e| {
+        error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to read watch response: {e}"
+        )))
+    })?;
+    unstreamed_reply(method, &reply)
+}
+
+/// Interpret a single JSON-RPC reply to a `+watch` request
+fn unstreamed_reply(method: &str, reply: &Value) -> Result<WatchConnection> {
+    let Some(error) = reply.get("error") else {
+        return Ok(WatchConnection::Unsupported(format!(
+            "{method} answered with a single response instead of a stream"
+        )));
+    };
+    let message = error
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("Unknown error");
+    if error.get("code").and_then(Value::as_i64) == Some(METHOD_NOT_FOUND) {
+        Ok(WatchConnection::Unsupported(format!(
+            "{method} is not offered by the app: {message}"
+        )))
+    } else {
+        Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "{method} failed: {message}"
+        ))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_lines_split_across_chunks_yield_results_and_unknown_methods_fall_back() {
+        let mut stream = SseStream {
+            chunks:      Box::pin(futures::stream::empty()),
+            line_buffer: String::new(),
+            results:     VecDeque::new(),
+            ended:       false,
+        };
+        assert!(
+            stream
+                .push_chunk(b"data: {\"jsonrpc\":\"2.0\",\"result\":{\"a\":1}}\n\ndata: {\"res")
+                .is_ok()
+        );
+        assert!(
+            stream
+                .push_chunk(b"ult\":{\"a\":2}}\r\n: keep-alive\n")
+                .is_ok()
+        );
+        stream.push_line("data: {\"jsonrpc\":\"2.0\",\"error\":{\"code\":-1}}");
+        assert_eq!(
+            stream.results.iter().collect::<Vec<_>>(),
+            [&json!({ "a": 1 }), &json!({ "a": 2 })]
+        );
+
+        let not_found = json!({ "error": { "code": -32601, "message": "Method not found" } });
+        assert!(matches!(
+            unstreamed_reply("bevy/get+watch", &not_found),
+            Ok(WatchConnection::Unsupported(_))
+        ));
+        let failed = json!({ "error": { "code": -23401, "message": "Entity not found" } });
+        assert!(unstreamed_reply("bevy/get+watch", &failed).is_err());
+    }
+}
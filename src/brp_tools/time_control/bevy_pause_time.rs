@@ -0,0 +1,43 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::Value;
+
+use crate::brp_tools::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::report_to_mcp_error;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_PAUSE_TIME, TOOL_BEVY_PAUSE_TIME};
+use crate::{BrpMcpService, config};
+
+/// `bevy_brp_extras` method used instead of mutating `Time<Virtual>` when available
+const EXTRAS_METHOD: &str = "brp_extras/pause_time";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_PAUSE_TIME.into(),
+        description:  DESC_BEVY_PAUSE_TIME.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let response = super::set_paused(true, EXTRAS_METHOD, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
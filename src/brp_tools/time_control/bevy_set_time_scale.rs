@@ -0,0 +1,98 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{
+    FIELD_VIA, TIME_VIRTUAL_TYPE, call_extras, extras_method, read_virtual_time, set_virtual_field,
+    time_response,
+};
+use crate::brp_tools::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::JsonResponse;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_SET_TIME_SCALE, TOOL_BEVY_SET_TIME_SCALE};
+use crate::{BrpMcpService, config};
+
+/// `bevy_brp_extras` method used instead of mutating `Time<Virtual>` when available
+const EXTRAS_METHOD: &str = "brp_extras/set_time_scale";
+
+/// Parameter giving the speed of game time relative to real time
+const PARAM_SCALE: &str = "scale";
+
+/// Fastest speed accepted, to keep a typo from racing the simulation ahead
+const MAX_SCALE: f64 = 100.0;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SET_TIME_SCALE.into(),
+        description:  DESC_BEVY_SET_TIME_SCALE.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                PARAM_SCALE,
+                &format!(
+                    "Speed of game time relative to real time: 1 is normal, 0.25 slow motion, 2 double speed (0 to {MAX_SCALE})"
+                ),
+                true,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let scale = arguments
+        .get(PARAM_SCALE)
+        .and_then(Value::as_f64)
+        .filter(|scale| (0.0..=MAX_SCALE).contains(scale))
+        .ok_or_else(|| {
+            report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+                &format!("parameter '{PARAM_SCALE}'"),
+                format!("give a number from 0 to {MAX_SCALE}"),
+            )))
+        })?;
+
+    let response = set_time_scale(scale, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+async fn set_time_scale(scale: f64, port: u16) -> Result<JsonResponse> {
+    if let Some(method) = extras_method(port, EXTRAS_METHOD).await {
+        let result = call_extras(method, Some(json!({ PARAM_SCALE: scale })), port).await?;
+        return Ok(time_response(
+            format!("Set the time scale to {scale} with {method}"),
+            json!({ PARAM_SCALE: scale, FIELD_VIA: method, "result": result }),
+        ));
+    }
+
+    let before = read_virtual_time(port).await?;
+    set_virtual_field("relative_speed", json!(scale), port).await?;
+    let message = if before.paused {
+        format!("Set the time scale to {scale}; time is paused and stays so until resumed")
+    } else {
+        format!("Set the time scale to {scale}")
+    };
+    Ok(time_response(
+        message,
+        json!({
+            PARAM_SCALE: scale,
+            "previous_scale": before.relative_speed,
+            "paused": before.paused,
+            FIELD_VIA: TIME_VIRTUAL_TYPE,
+        }),
+    ))
+}
@@ -0,0 +1,197 @@
+use std::time::{Duration, Instant};
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+use tracing::warn;
+
+use super::{
+    FIELD_VIA, TIME_VIRTUAL_TYPE, call_extras, extras_method, read_virtual_time, set_virtual_field,
+    time_response,
+};
+use crate::brp_tools::constants::{DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_HOST, JSON_FIELD_PORT};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::support::response::JsonResponse;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_STEP_FRAMES, TOOL_BEVY_STEP_FRAMES};
+use crate::{BrpMcpService, config};
+
+/// `bevy_brp_extras` method used instead of polling `Time<Virtual>` when available
+const EXTRAS_METHOD: &str = "brp_extras/step_frames";
+
+/// Parameter giving the number of frames to advance
+const PARAM_FRAMES: &str = "frames";
+
+/// Most frames one call advances
+const MAX_FRAMES: u64 = 600;
+
+/// Longest wait for the frames to run, beyond a tenth of a second per frame
+const STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_STEP_FRAMES.into(),
+        description:  DESC_BEVY_STEP_FRAMES.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                PARAM_FRAMES,
+                &format!("Frames to advance game time by (default: 1, at most {MAX_FRAMES})"),
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let frames = params::extract_optional_number(&request, PARAM_FRAMES, 1)?;
+    if !(1..=MAX_FRAMES).contains(&frames) {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{PARAM_FRAMES}'"),
+                format!("give a number from 1 to {MAX_FRAMES}"),
+            ),
+        )));
+    }
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let response = step_frames(frames, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Let game time run for some frames, then leave it paused
+///
+/// Without the extras method, time is resumed and `Time<Virtual>` is read until its elapsed time
+/// has changed once per frame, then paused again. BRP requests are answered once per frame, so
+/// this lands on the requested frame or, when requests lag, a little after it; the frames
+/// actually observed are reported.
+async fn step_frames(frames: u64, port: u16) -> Result<JsonResponse> {
+    if let Some(method) = extras_method(port, EXTRAS_METHOD).await {
+        let result = call_extras(method, Some(json!({ PARAM_FRAMES: frames })), port).await?;
+        return Ok(time_response(
+            format!("Stepped {frames} frames with {method}"),
+            json!({ PARAM_FRAMES: frames, FIELD_VIA: method, "result": result }),
+        ));
+    }
+
+    let before = read_virtual_time(port).await?;
+    set_virtual_field("paused", json!(false), port).await?;
+
+    let deadline = Instant::now() + STEP_TIMEOUT + Duration::from_millis(100 * frames);
+    let mut observed = 0;
+    let mut last_elapsed = before.elapsed_secs;
+    let mut timed_out = false;
+    while observed < frames {
+        if Instant::now() > deadline {
+            timed_out = true;
+            break;
+        }
+        let elapsed = match read_virtual_time(port).await {
+            Ok(time) => time.elapsed_secs,
+            Err(report) => {
+                // Don't leave the game running when the step can't be finished
+                if let Err(e) = set_virtual_field("paused", json!(true), port).await {
+                    warn!("Failed to pause time again after a failed step: {}", e);
+                }
+                return Err(report);
+            }
+        };
+        if elapsed > last_elapsed {
+            observed += 1;
+            last_elapsed = elapsed;
+        }
+    }
+    set_virtual_field("paused", json!(true), port).await?;
+    let after = read_virtual_time(port).await?;
+
+    let data = json!({
+        PARAM_FRAMES: frames,
+        "frames_observed": observed,
+        "was_paused": before.paused,
+        "advanced_secs": after.elapsed_secs - before.elapsed_secs,
+        "time": after.to_json(),
+        FIELD_VIA: TIME_VIRTUAL_TYPE,
+    });
+    let message = if timed_out {
+        format!(
+            "Game time advanced only {observed} of {frames} frames before timing out; is the time scale 0? Time is paused"
+        )
+    } else {
+        format!(
+            "Stepped {frames} frames, {:.3}s of game time; time is paused",
+            after.elapsed_secs - before.elapsed_secs
+        )
+    };
+    Ok(time_response(message, data))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::{BRP_METHOD_GET_RESOURCE, BRP_METHOD_MUTATE_RESOURCE};
+
+    fn time(paused: bool, elapsed: f64) -> Value {
+        json!({ "value": {
+            "context": { "paused": paused, "relative_speed": 1.0 },
+            "elapsed_secs_f64": elapsed,
+        } })
+    }
+
+    #[tokio::test]
+    async fn test_time_is_paused_after_the_frames_ran() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(BRP_METHOD_GET_RESOURCE, time(true, 1.0))
+            .respond(BRP_METHOD_GET_RESOURCE, time(false, 1.0))
+            .respond(BRP_METHOD_GET_RESOURCE, time(false, 1.5))
+            .respond(BRP_METHOD_GET_RESOURCE, time(false, 2.0))
+            .respond(BRP_METHOD_GET_RESOURCE, time(true, 2.0))
+            .respond(BRP_METHOD_MUTATE_RESOURCE, Value::Null);
+
+        let response = serde_json::to_value(step_frames(2, server.port()).await.unwrap()).unwrap();
+        assert_eq!(response["data"]["frames_observed"], 2);
+        assert_eq!(response["data"]["advanced_secs"], 1.0);
+
+        let paused: Vec<_> = server
+            .requests_for(BRP_METHOD_MUTATE_RESOURCE)
+            .into_iter()
+            .map(|request| request.params.unwrap()["value"].clone())
+            .collect();
+        assert_eq!(paused, vec![json!(false), json!(true)]);
+    }
+
+    #[tokio::test]
+    async fn test_time_is_paused_again_when_reading_it_fails() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(BRP_METHOD_GET_RESOURCE, time(true, 1.0))
+            .fail(BRP_METHOD_GET_RESOURCE, -23402, "Resource not present")
+            .respond(BRP_METHOD_MUTATE_RESOURCE, Value::Null);
+
+        assert!(step_frames(2, server.port()).await.is_err());
+
+        let paused: Vec<_> = server
+            .requests_for(BRP_METHOD_MUTATE_RESOURCE)
+            .into_iter()
+            .map(|request| request.params.unwrap()["value"].clone())
+            .collect();
+        assert_eq!(paused, vec![json!(false), json!(true)]);
+    }
+}
@@ -0,0 +1,200 @@
+//! Pausing, stepping and slowing down the simulation
+//!
+//! Bevy advances game time through the `Time<Virtual>` resource, whose `paused` and
+//! `relative_speed` fields can be mutated over BRP like any resource, so the tools work on a plain
+//! app. When `rpc.discover` lists a `bevy_brp_extras` method for the same control, that's called
+//! instead. Pausing virtual time freezes everything driven by it, including `FixedUpdate`, while
+//! systems keep running, so state can be inspected without it changing underneath.
+
+pub mod bevy_pause_time;
+pub mod bevy_resume_time;
+pub mod bevy_set_time_scale;
+pub mod bevy_step_frames;
+
+use serde_json::{Value, json};
+
+use super::constants::{JSON_FIELD_PATH, JSON_FIELD_RESOURCE, JSON_FIELD_VALUE};
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::capabilities;
+use crate::error::{Error, Result};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::tools::{BRP_METHOD_GET_RESOURCE, BRP_METHOD_MUTATE_RESOURCE};
+
+/// Fully-qualified type of the resource holding game time
+pub const TIME_VIRTUAL_TYPE: &str = "bevy_time::time::Time<bevy_time::virt::Virtual>";
+
+/// Field of the response naming how time was controlled
+pub const FIELD_VIA: &str = "via";
+
+/// The state of virtual time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VirtualTime {
+    pub paused:         bool,
+    pub relative_speed: f64,
+    /// Game time elapsed since startup, in seconds
+    pub elapsed_secs:   f64,
+}
+
+impl VirtualTime {
+    /// Read a serialized `Time<Virtual>`
+    fn from_value(value: &Value) -> Option<Self> {
+        let context = value.get("context")?;
+        Some(Self {
+            paused:         context.get("paused")?.as_bool()?,
+            relative_speed: context.get("relative_speed")?.as_f64()?,
+            elapsed_secs:   value
+                .get("elapsed_secs_f64")
+                .or_else(|| value.get("elapsed_secs"))
+                .and_then(Value::as_f64)
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({
+            "paused": self.paused,
+            "relative_speed": self.relative_speed,
+            "elapsed_secs": self.elapsed_secs,
+        })
+    }
+}
+
+/// Read `Time<Virtual>` from the app
+pub async fn read_virtual_time(port: u16) -> Result<VirtualTime> {
+    let params = json!({ JSON_FIELD_RESOURCE: TIME_VIRTUAL_TYPE });
+    let value = match execute_brp_method(BRP_METHOD_GET_RESOURCE, Some(params), Some(port)).await? {
+        BrpResult::Success(data) => data.unwrap_or_default(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to read {TIME_VIRTUAL_TYPE}: {}",
+                e.message
+            ))));
+        }
+    };
+    VirtualTime::from_value(value.get(JSON_FIELD_VALUE).unwrap_or(&value)).ok_or_else(|| {
+        error_stack::Report::new(Error::BrpCommunication(format!(
+            "{TIME_VIRTUAL_TYPE} has no context.paused and context.relative_speed fields"
+        )))
+    })
+}
+
+/// Set a field of the `Virtual` clock, `paused` or `relative_speed`
+pub async fn set_virtual_field(field: &str, value: Value, port: u16) -> Result<()> {
+    let params = json!({
+        JSON_FIELD_RESOURCE: TIME_VIRTUAL_TYPE,
+        JSON_FIELD_PATH: format!(".context.{field}"),
+        JSON_FIELD_VALUE: value,
+    });
+    match execute_brp_method(BRP_METHOD_MUTATE_RESOURCE, Some(params), Some(port)).await? {
+        BrpResult::Success(_) => Ok(()),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "Failed to set {field} of {TIME_VIRTUAL_TYPE}: {}",
+            e.message
+        )))),
+    }
+}
+
+/// The `bevy_brp_extras` method for a control, if the app lists it in `rpc.discover`
+///
+/// Apps that can't answer `rpc.discover` are treated as having none.
+pub async fn extras_method(port: u16, method: &'static str) -> Option<&'static str> {
+    let methods = capabilities::supported_methods(port).await.ok()?;
+    capabilities::first_supported(&methods, &[method])
+}
+
+/// Call a `bevy_brp_extras` time method, returning its result
+pub async fn call_extras(method: &str, params: Option<Value>, port: u16) -> Result<Value> {
+    match execute_brp_method(method, params, Some(port)).await? {
+        BrpResult::Success(result) => Ok(result.unwrap_or(Value::Null)),
+        BrpResult::Error(e) => Err(error_stack::Report::new(Error::BrpCommunication(format!(
+            "{method} failed: {}",
+            e.message
+        )))),
+    }
+}
+
+/// Pause or resume virtual time, through `bevy_brp_extras` when the app has the method
+pub async fn set_paused(paused: bool, extras: &'static str, port: u16) -> Result<JsonResponse> {
+    let action = if paused { "Paused" } else { "Resumed" };
+    if let Some(method) = extras_method(port, extras).await {
+        let result = call_extras(method, None, port).await?;
+        return Ok(time_response(
+            format!("{action} time with {method}"),
+            json!({ "paused": paused, FIELD_VIA: method, "result": result }),
+        ));
+    }
+
+    let before = read_virtual_time(port).await?;
+    set_virtual_field("paused", json!(paused), port).await?;
+    Ok(time_response(
+        format!("{action} time at {:.3}s of game time", before.elapsed_secs),
+        json!({
+            "paused": paused,
+            "was_paused": before.paused,
+            "relative_speed": before.relative_speed,
+            "elapsed_secs": before.elapsed_secs,
+            FIELD_VIA: TIME_VIRTUAL_TYPE,
+        }),
+    ))
+}
+
+/// Success response of a time tool
+pub fn time_response(message: String, data: Value) -> JsonResponse {
+    ResponseBuilder::success()
+        .message(message)
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    #[tokio::test]
+    async fn test_virtual_time_is_read_and_set() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_GET_RESOURCE,
+                json!({ "value": {
+                    "context": { "paused": true, "relative_speed": 0.5, "effective_speed": 0.0 },
+                    "elapsed_secs_f64": 12.25,
+                } }),
+            )
+            .respond(BRP_METHOD_MUTATE_RESOURCE, Value::Null);
+
+        assert_eq!(
+            read_virtual_time(server.port()).await.unwrap(),
+            VirtualTime {
+                paused:         true,
+                relative_speed: 0.5,
+                elapsed_secs:   12.25,
+            }
+        );
+        set_virtual_field("paused", json!(false), server.port())
+            .await
+            .unwrap();
+        assert_eq!(
+            server.requests_for(BRP_METHOD_MUTATE_RESOURCE)[0].params,
+            Some(json!({
+                "resource": TIME_VIRTUAL_TYPE,
+                "path": ".context.paused",
+                "value": false,
+            }))
+        );
+        assert_eq!(
+            extras_method(server.port(), "brp_extras/pause_time").await,
+            None
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+
+use super::TransformChange;
+use crate::BrpMcpService;
+use crate::tools::{DESC_BEVY_ROTATE_ENTITY, TOOL_BEVY_ROTATE_ENTITY};
+
+pub fn register_tool() -> Tool {
+    super::register(
+        TOOL_BEVY_ROTATE_ENTITY,
+        DESC_BEVY_ROTATE_ENTITY,
+        "Rotation applied after the current one: Euler angles in degrees [x, y, z], applied about x then y then z, or a quaternion [x, y, z, w]",
+        "New rotation: Euler angles in degrees [x, y, z] or a quaternion [x, y, z, w]",
+    )
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    super::handle(request, TransformChange::Rotate).await
+}
@@ -0,0 +1,24 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+
+use super::TransformChange;
+use crate::BrpMcpService;
+use crate::tools::{DESC_BEVY_SCALE_ENTITY, TOOL_BEVY_SCALE_ENTITY};
+
+pub fn register_tool() -> Tool {
+    super::register(
+        TOOL_BEVY_SCALE_ENTITY,
+        DESC_BEVY_SCALE_ENTITY,
+        "Factor the scale is multiplied by: a number for every axis, or [x, y, z]",
+        "New scale: a number for every axis, or [x, y, z]",
+    )
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    super::handle(request, TransformChange::Scale).await
+}
@@ -0,0 +1,24 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+
+use super::TransformChange;
+use crate::BrpMcpService;
+use crate::tools::{DESC_BEVY_TRANSLATE_ENTITY, TOOL_BEVY_TRANSLATE_ENTITY};
+
+pub fn register_tool() -> Tool {
+    super::register(
+        TOOL_BEVY_TRANSLATE_ENTITY,
+        DESC_BEVY_TRANSLATE_ENTITY,
+        "Offset added to the translation: [x, y, z] or {x, y, z}",
+        "New translation: [x, y, z] or {x, y, z}",
+    )
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    super::handle(request, TransformChange::Translate).await
+}
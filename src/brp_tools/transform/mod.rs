@@ -0,0 +1,415 @@
+//! Moving, rotating and scaling entities without writing out a `Transform`
+//!
+//! Each tool gets the entity's `Transform`, changes one field and mutates it back, so callers give
+//! only the change: `by` for one relative to the current value, or `to` for an absolute one.
+//! Vectors may be arrays or `{x, y, z}` objects; the tools always send the array form Bevy
+//! serializes math types as, with format discovery as a fallback.
+
+pub mod bevy_rotate_entity;
+pub mod bevy_scale_entity;
+pub mod bevy_translate_entity;
+
+use rmcp::Error as McpError;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use serde_json::{Value, json};
+
+use super::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_COMPONENT, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY,
+    JSON_FIELD_FORMAT_CORRECTIONS, JSON_FIELD_HOST, JSON_FIELD_PATH, JSON_FIELD_PORT,
+    JSON_FIELD_VALUE,
+};
+use super::request_handler::execute_brp_method_with_format_discovery;
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use super::support::response_formatter::corrections_json;
+use crate::config;
+use crate::error::{Error, ErrorCode, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{BRP_METHOD_GET, BRP_METHOD_MUTATE_COMPONENT};
+
+/// Fully-qualified type of the component the tools change
+pub const TRANSFORM_TYPE: &str = "bevy_transform::components::transform::Transform";
+
+/// Parameter giving a change relative to the current value
+pub const PARAM_BY: &str = "by";
+
+/// Parameter giving an absolute value
+pub const PARAM_TO: &str = "to";
+
+/// Field names of vector components, in order
+const AXES: [&str; 4] = ["x", "y", "z", "w"];
+
+/// The change a tool makes to a `Transform`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformChange {
+    Translate,
+    Rotate,
+    Scale,
+}
+
+impl TransformChange {
+    /// Field of the `Transform` the change applies to
+    const fn field(self) -> &'static str {
+        match self {
+            Self::Translate => "translation",
+            Self::Rotate => "rotation",
+            Self::Scale => "scale",
+        }
+    }
+
+    /// Number of components of the field: a quaternion for rotations, a `Vec3` otherwise
+    const fn len(self) -> usize {
+        match self {
+            Self::Rotate => 4,
+            Self::Translate | Self::Scale => 3,
+        }
+    }
+
+    /// Past tense for messages
+    const fn done(self) -> &'static str {
+        match self {
+            Self::Translate => "Moved",
+            Self::Rotate => "Rotated",
+            Self::Scale => "Scaled",
+        }
+    }
+
+    /// The new value of the field, from its current value and `by` or `to`
+    ///
+    /// Translations add `by`. Scales multiply by `by`, a number scaling every axis alike.
+    /// Rotations take Euler angles in degrees, applied about x, then y, then z, or a quaternion
+    /// `[x, y, z, w]`; `by` is applied after the current rotation, like `Transform::rotate`.
+    fn apply(self, current: &[f64], change: &Value, relative: bool) -> Option<Vec<f64>> {
+        match self {
+            Self::Translate => {
+                let change = vector(change, 3)?;
+                Some(if relative {
+                    current.iter().zip(&change).map(|(a, b)| a + b).collect()
+                } else {
+                    change
+                })
+            }
+            Self::Scale => {
+                let change = change
+                    .as_f64()
+                    .map_or_else(|| vector(change, 3), |factor| Some(vec![factor; 3]))?;
+                Some(if relative {
+                    current.iter().zip(&change).map(|(a, b)| a * b).collect()
+                } else {
+                    change
+                })
+            }
+            Self::Rotate => {
+                let change = vector(change, 4)
+                    .map(|quat| normalize(&quat))
+                    .or_else(|| vector(change, 3).map(|degrees| from_euler_degrees(&degrees)))?;
+                Some(if relative {
+                    normalize(&multiply(&change, current))
+                } else {
+                    change
+                })
+            }
+        }
+    }
+}
+
+/// Schema shared by the tools, with descriptions of `by` and `to`
+pub fn register(name: &'static str, description: &'static str, by: &str, to: &str) -> Tool {
+    Tool {
+        name:         name.into(),
+        description:  description.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(JSON_FIELD_ENTITY, "The entity ID to change", true)
+            .add_any_property(PARAM_BY, by, false)
+            .add_any_property(PARAM_TO, to, false)
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+/// Handle a call of one of the tools
+pub async fn handle(
+    request: CallToolRequestParam,
+    change: TransformChange,
+) -> std::result::Result<CallToolResult, McpError> {
+    let entity = params::extract_required_number(&request, JSON_FIELD_ENTITY)?;
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let given = |field: &str| arguments.get(field).filter(|value| !value.is_null());
+    let (value, relative) = match (given(PARAM_BY), given(PARAM_TO)) {
+        (Some(by), None) => (by, true),
+        (None, Some(to)) => (to, false),
+        _ => {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::invalid(
+                    "parameters",
+                    format!("give either '{PARAM_BY}' or '{PARAM_TO}'"),
+                ),
+            )));
+        }
+    };
+
+    let response = change_transform(entity, change, value, relative, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Get the entity's `Transform`, change one field and mutate it back
+async fn change_transform(
+    entity: u64,
+    change: TransformChange,
+    value: &Value,
+    relative: bool,
+    port: u16,
+) -> Result<JsonResponse> {
+    let field = change.field();
+    let params = json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: [TRANSFORM_TYPE] });
+    let transform = match execute_brp_method(BRP_METHOD_GET, Some(params), Some(port)).await? {
+        BrpResult::Success(data) => data.unwrap_or_default(),
+        BrpResult::Error(e) => {
+            return Ok(ResponseBuilder::error()
+                .message(format!(
+                    "Failed to get the Transform of entity {entity}: {}",
+                    e.message
+                ))
+                .error_code(ErrorCode::from_brp_code(e.code, false))
+                .details(json!({ JSON_FIELD_ENTITY: entity }))
+                .build());
+        }
+    };
+    let Some(current) = transform[JSON_FIELD_COMPONENTS][TRANSFORM_TYPE]
+        .get(field)
+        .and_then(|current| vector(current, change.len()))
+    else {
+        return Ok(ResponseBuilder::error()
+            .message(format!("Entity {entity} has no Transform"))
+            .error_code(ErrorCode::InvalidParameters)
+            .details(json!({
+                JSON_FIELD_ENTITY: entity,
+                "errors": transform.get("errors").cloned().unwrap_or_default(),
+            }))
+            .build());
+    };
+
+    let updated = change.apply(&current, value, relative).ok_or_else(|| {
+        error_stack::Report::new(Error::invalid(
+            &format!("parameter '{}'", if relative { PARAM_BY } else { PARAM_TO }),
+            format!("{value} is not a valid {field}"),
+        ))
+    })?;
+
+    let params = json!({
+        JSON_FIELD_ENTITY: entity,
+        JSON_FIELD_COMPONENT: TRANSFORM_TYPE,
+        JSON_FIELD_PATH: format!(".{field}"),
+        JSON_FIELD_VALUE: updated,
+    });
+    let enhanced = execute_brp_method_with_format_discovery(
+        BRP_METHOD_MUTATE_COMPONENT,
+        Some(params),
+        Some(port),
+        Vec::new(),
+    )
+    .await?;
+    if let BrpResult::Error(e) = enhanced.result {
+        return Ok(ResponseBuilder::error()
+            .message(format!(
+                "Failed to set the {field} of entity {entity}: {}",
+                e.message
+            ))
+            .error_code(ErrorCode::from_brp_code(
+                e.code,
+                !enhanced.format_corrections.is_empty(),
+            ))
+            .details(json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_VALUE: updated }))
+            .build());
+    }
+
+    let mut data = json!({
+        JSON_FIELD_ENTITY: entity,
+        "previous": { field: current },
+        field: updated,
+    });
+    if !enhanced.format_corrections.is_empty() {
+        data[JSON_FIELD_FORMAT_CORRECTIONS] = corrections_json(&enhanced.format_corrections);
+    }
+
+    Ok(ResponseBuilder::success()
+        .message(format!("{} entity {entity}", change.done()))
+        .data(data)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// Numbers of a vector given as an array or as an `{x, y, z, w}` object, if it has `len` of them
+pub fn vector(value: &Value, len: usize) -> Option<Vec<f64>> {
+    match value {
+        Value::Array(items) if items.len() == len => items.iter().map(Value::as_f64).collect(),
+        Value::Object(fields) if fields.len() == len => AXES[..len]
+            .iter()
+            .map(|axis| fields.get(*axis).and_then(Value::as_f64))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Hamilton product of two `[x, y, z, w]` quaternions
+fn multiply(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let [ax, ay, az, aw] = [a[0], a[1], a[2], a[3]];
+    let [bx, by, bz, bw] = [b[0], b[1], b[2], b[3]];
+    vec![
+        aw.mul_add(bx, ax.mul_add(bw, ay.mul_add(bz, -az * by))),
+        aw.mul_add(by, (-ax).mul_add(bz, ay.mul_add(bw, az * bx))),
+        aw.mul_add(bz, ax.mul_add(by, (-ay).mul_add(bx, az * bw))),
+        aw.mul_add(bw, (-ax).mul_add(bx, (-ay).mul_add(by, -az * bz))),
+    ]
+}
+
+fn normalize(quat: &[f64]) -> Vec<f64> {
+    let length = quat.iter().map(|c| c * c).sum::<f64>().sqrt();
+    if length == 0.0 {
+        return vec![0.0, 0.0, 0.0, 1.0];
+    }
+    quat.iter().map(|c| c / length).collect()
+}
+
+/// Quaternion of rotations about x, then y, then z, in degrees, as `Quat::from_euler(XYZ, ..)`
+fn from_euler_degrees(degrees: &[f64]) -> Vec<f64> {
+    let about = |axis: usize| {
+        let half = degrees[axis].to_radians() / 2.0;
+        let mut quat = vec![0.0, 0.0, 0.0, half.cos()];
+        quat[axis] = half.sin();
+        quat
+    };
+    multiply(&multiply(&about(0), &about(1)), &about(2))
+}
+
+/// Rotations about x, then y, then z, in degrees, of an `[x, y, z, w]` quaternion, as
+/// `Quat::to_euler(XYZ)`
+pub fn to_euler_degrees(quat: &[f64]) -> Vec<f64> {
+    let [x, y, z, w] = [quat[0], quat[1], quat[2], quat[3]];
+    // Elements of the rotation matrix Rx * Ry * Rz
+    let m02 = 2.0 * x.mul_add(z, y * w);
+    let y_angle = m02.clamp(-1.0, 1.0).asin();
+    if m02.abs() > 0.999_999 {
+        // Gimbal lock: x and z rotate about the same axis, so all of it is put on z
+        let m10 = 2.0 * x.mul_add(y, z * w);
+        let m11 = 2.0f64.mul_add(-x.mul_add(x, z * z), 1.0);
+        return vec![0.0, y_angle.to_degrees(), m10.atan2(m11).to_degrees()];
+    }
+    let m12 = 2.0 * y.mul_add(z, -x * w);
+    let m22 = 2.0f64.mul_add(-x.mul_add(x, y * y), 1.0);
+    let m01 = 2.0 * x.mul_add(y, -z * w);
+    let m00 = 2.0f64.mul_add(-y.mul_add(y, z * z), 1.0);
+    vec![
+        (-m12).atan2(m22).to_degrees(),
+        y_angle.to_degrees(),
+        (-m01).atan2(m00).to_degrees(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+
+    fn rounded(values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .map(|v| (v * 1e6).round() / 1e6 + 0.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_changes_are_applied_to_the_current_value() {
+        let translate = TransformChange::Translate;
+        assert_eq!(
+            translate.apply(&[1.0, 2.0, 3.0], &json!({ "x": 1, "y": 0, "z": -3 }), true),
+            Some(vec![2.0, 2.0, 0.0])
+        );
+        assert_eq!(
+            translate.apply(&[1.0, 2.0, 3.0], &json!([1, 2]), true),
+            None
+        );
+
+        let scale = TransformChange::Scale;
+        assert_eq!(
+            scale.apply(&[1.0, 2.0, 1.0], &json!(2), true),
+            Some(vec![2.0, 4.0, 2.0])
+        );
+        assert_eq!(
+            scale.apply(&[1.0, 2.0, 1.0], &json!([1, 1, 3]), false),
+            Some(vec![1.0, 1.0, 3.0])
+        );
+
+        // Two quarter turns about y make a half turn
+        let rotate = TransformChange::Rotate;
+        let quarter = rotate
+            .apply(&[0.0, 0.0, 0.0, 1.0], &json!([0, 90, 0]), true)
+            .unwrap();
+        let half = rotate.apply(&quarter, &json!([0, 90, 0]), true).unwrap();
+        assert_eq!(rounded(&half), vec![0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(
+            rounded(&rotate.apply(&half, &json!([0, 0, 0, 2]), false).unwrap()),
+            vec![0.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_field_is_mutated_back() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_GET,
+                json!({
+                    "components": { TRANSFORM_TYPE: {
+                        "translation": [1.0, 0.0, 0.0],
+                        "rotation": [0.0, 0.0, 0.0, 1.0],
+                        "scale": [1.0, 1.0, 1.0],
+                    } },
+                    "errors": {},
+                }),
+            )
+            .respond(BRP_METHOD_MUTATE_COMPONENT, Value::Null);
+
+        let response = change_transform(
+            7,
+            TransformChange::Translate,
+            &json!([0, 2, 0]),
+            true,
+            server.port(),
+        )
+        .await
+        .map(|response| serde_json::to_value(response).unwrap())
+        .unwrap();
+        assert_eq!(response["data"]["translation"], json!([1.0, 2.0, 0.0]));
+        assert_eq!(
+            response["data"]["previous"]["translation"],
+            json!([1.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            server.requests_for(BRP_METHOD_MUTATE_COMPONENT)[0].params,
+            Some(json!({
+                "entity": 7,
+                "component": TRANSFORM_TYPE,
+                "path": ".translation",
+                "value": [1.0, 2.0, 0.0],
+            }))
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Map, Value, json};
+
+use super::{
+    HIDDEN, Isolation, insert_visibility, remember_isolation, root_visibilities, take_isolation,
+};
+use crate::brp_tools::bevy_get_hierarchy;
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_ENTITY, JSON_FIELD_HOST, JSON_FIELD_PORT,
+};
+use crate::error::{ErrorCode, Result, report_to_mcp_error};
+use crate::support::response::{JsonResponse, ResponseBuilder};
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_ISOLATE_ENTITY, TOOL_BEVY_ISOLATE_ENTITY};
+use crate::{BrpMcpService, config};
+
+/// Parameter asking for the hidden entities to be shown again
+const PARAM_RESTORE: &str = "restore";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_ISOLATE_ENTITY.into(),
+        description:  DESC_BEVY_ISOLATE_ENTITY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_ENTITY,
+                "The entity ID to leave visible, with the rest of its tree",
+                false,
+            )
+            .add_boolean_property(
+                PARAM_RESTORE,
+                "Put back the Visibility the hidden entities had instead of isolating",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let restore = arguments
+        .get(PARAM_RESTORE)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let response = if restore {
+        restore_isolation(port).await
+    } else {
+        let entity = params::extract_required_u64(&arguments, JSON_FIELD_ENTITY, "entity")?;
+        isolate(entity, port).await
+    }
+    .map_err(|report| report_to_mcp_error(&report))?;
+    Ok(json_response_to_result(&response))
+}
+
+/// Hide every root entity but the one holding `entity`, remembering their `Visibility`
+///
+/// An isolation already in progress on the connection is undone first, so the values remembered
+/// are always those from before any isolation.
+async fn isolate(entity: u64, port: u16) -> Result<JsonResponse> {
+    let mut failed = Map::new();
+    if let Some(previous) = take_isolation(port)? {
+        failed.extend(put_back(previous.previous, port).await.1);
+    }
+
+    let root = bevy_get_hierarchy::get_hierarchy(entity, 0, port)
+        .await?
+        .root(entity);
+    let others: BTreeMap<u64, Value> = root_visibilities(port)
+        .await?
+        .into_iter()
+        .filter(|(other, visibility)| *other != root && visibility != HIDDEN)
+        .collect();
+    let hidden: Vec<(u64, Value)> = others.keys().map(|other| (*other, json!(HIDDEN))).collect();
+    let (updated, hide_failed) = insert_visibility(&hidden, port).await;
+    failed.extend(hide_failed);
+
+    let previous = others
+        .into_iter()
+        .filter(|(other, _)| updated.contains(other))
+        .collect();
+    remember_isolation(port, Isolation { entity, previous })?;
+
+    Ok(ResponseBuilder::success()
+        .message(format!(
+            "Isolated entity {entity}, hid {} other root entities; call again with restore to show them",
+            updated.len()
+        ))
+        .data(json!({
+            JSON_FIELD_ENTITY: entity,
+            "root": root,
+            "hidden": updated,
+            "failed": failed,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+/// Put back the `Visibility` of the entities hidden by the isolation on the connection
+async fn restore_isolation(port: u16) -> Result<JsonResponse> {
+    let Some(isolation) = take_isolation(port)? else {
+        return Ok(ResponseBuilder::error()
+            .message("No entity is isolated on this connection")
+            .error_code(ErrorCode::InvalidParameters)
+            .build());
+    };
+    let (restored, failed) = put_back(isolation.previous, port).await;
+
+    Ok(ResponseBuilder::success()
+        .message(format!(
+            "Restored the visibility of {} entities hidden to isolate entity {}",
+            restored.len(),
+            isolation.entity
+        ))
+        .data(json!({
+            JSON_FIELD_ENTITY: isolation.entity,
+            "restored": restored,
+            "failed": failed,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        ))
+}
+
+async fn put_back(previous: BTreeMap<u64, Value>, port: u16) -> (Vec<u64>, Map<String, Value>) {
+    let values: Vec<(u64, Value)> = previous.into_iter().collect();
+    insert_visibility(&values, port).await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::brp_tools::visibility::VISIBILITY_TYPE;
+    use crate::tools::{BRP_METHOD_INSERT, BRP_METHOD_LIST, BRP_METHOD_QUERY};
+
+    #[tokio::test]
+    async fn test_other_roots_are_hidden_and_restored() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(BRP_METHOD_LIST, json!([VISIBILITY_TYPE]))
+            .respond(
+                BRP_METHOD_QUERY,
+                json!([
+                    { "entity": 1, "components": { VISIBILITY_TYPE: "Inherited" } },
+                    { "entity": 2, "components": { VISIBILITY_TYPE: "Visible" } },
+                    { "entity": 3, "components": { VISIBILITY_TYPE: "Hidden" } },
+                ]),
+            )
+            .respond(BRP_METHOD_INSERT, Value::Null);
+
+        let response = serde_json::to_value(isolate(1, server.port()).await.unwrap()).unwrap();
+        assert_eq!(response["data"]["hidden"], json!([2]));
+        assert_eq!(
+            server.requests_for(BRP_METHOD_INSERT)[0].params,
+            Some(json!({ "entity": 2, "components": { VISIBILITY_TYPE: "Hidden" } }))
+        );
+
+        let response =
+            serde_json::to_value(restore_isolation(server.port()).await.unwrap()).unwrap();
+        assert_eq!(response["data"]["restored"], json!([2]));
+        assert_eq!(
+            server.requests_for(BRP_METHOD_INSERT)[1].params,
+            Some(json!({ "entity": 2, "components": { VISIBILITY_TYPE: "Visible" } }))
+        );
+        let response =
+            serde_json::to_value(restore_isolation(server.port()).await.unwrap()).unwrap();
+        assert_eq!(response["status"], "error");
+    }
+}
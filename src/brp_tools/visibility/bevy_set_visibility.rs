@@ -0,0 +1,113 @@
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{insert_visibility, parse_visibility};
+use crate::brp_tools::bevy_get_hierarchy;
+use crate::brp_tools::constants::{
+    DEFAULT_BRP_PORT, DESC_HOST, JSON_FIELD_ENTITY, JSON_FIELD_HOST, JSON_FIELD_PORT,
+};
+use crate::error::{Error, ErrorCode, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BEVY_SET_VISIBILITY, TOOL_BEVY_SET_VISIBILITY};
+use crate::{BrpMcpService, config};
+
+/// Parameter naming the `Visibility` to set
+const PARAM_VISIBILITY: &str = "visibility";
+
+/// Parameter asking for the descendants to be set too
+const PARAM_RECURSIVE: &str = "recursive";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BEVY_SET_VISIBILITY.into(),
+        description:  DESC_BEVY_SET_VISIBILITY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(JSON_FIELD_ENTITY, "The entity ID to show or hide", true)
+            .add_string_property(
+                PARAM_VISIBILITY,
+                "Visibility to set: 'visible', 'hidden' or 'inherited'",
+                true,
+            )
+            .add_boolean_property(
+                PARAM_RECURSIVE,
+                "Also set it on every descendant (default: false)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_PORT,
+                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
+                false,
+            )
+            .add_string_property(JSON_FIELD_HOST, DESC_HOST, false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let entity = params::extract_required_number(&request, JSON_FIELD_ENTITY)?;
+    let name = params::extract_required_string(&request, PARAM_VISIBILITY)?;
+    let visibility = parse_visibility(name).ok_or_else(|| {
+        report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+            &format!("parameter '{PARAM_VISIBILITY}'"),
+            format!("'{name}' is not one of 'visible', 'hidden' or 'inherited'"),
+        )))
+    })?;
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let recursive = arguments
+        .get(PARAM_RECURSIVE)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let mut truncated = false;
+    let entities = if recursive {
+        let hierarchy = bevy_get_hierarchy::get_hierarchy(entity, u64::MAX, port)
+            .await
+            .map_err(|report| report_to_mcp_error(&report))?;
+        truncated = hierarchy.truncated();
+        hierarchy.levels_bottom_up(entity).concat()
+    } else {
+        vec![entity]
+    };
+    let values: Vec<(u64, Value)> = entities
+        .iter()
+        .map(|entity| (*entity, json!(visibility)))
+        .collect();
+    let (updated, failed) = insert_visibility(&values, port).await;
+
+    let response = if updated.is_empty() {
+        ResponseBuilder::error()
+            .message(format!("Failed to set the visibility of entity {entity}"))
+            .error_code(ErrorCode::BrpError)
+            .details(json!({ JSON_FIELD_ENTITY: entity, "failed": failed }))
+            .build()
+    } else {
+        ResponseBuilder::success()
+            .message(format!("Set {} entities to {visibility}", updated.len()))
+            .data(json!({
+                JSON_FIELD_ENTITY: entity,
+                PARAM_VISIBILITY: visibility,
+                "updated": updated,
+                "failed": failed,
+                "truncated": truncated,
+            }))
+            .map_or_else(
+                |_| {
+                    ResponseBuilder::error()
+                        .message("Failed to serialize response data")
+                        .build()
+                },
+                ResponseBuilder::build,
+            )
+    };
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,187 @@
+//! Showing, hiding and isolating entities
+//!
+//! `bevy_set_visibility` inserts a `Visibility` on an entity, and optionally on its descendants.
+//! `bevy_isolate_entity` hides every other root entity to narrow down a rendering issue,
+//! remembering the `Visibility` each had so it can be put back. The remembered values last for
+//! the session, one isolation per app instance (host, port and managed app).
+
+pub mod bevy_isolate_entity;
+pub mod bevy_set_visibility;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+
+use futures::future::join_all;
+use serde_json::{Map, Value, json};
+
+use super::bevy_get_hierarchy::registered_parent_components;
+use super::constants::{JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, PARAM_DATA, PARAM_FILTER};
+use super::support::app_instance::AppInstance;
+use super::support::brp_client::{BrpResult, execute_brp_method};
+use crate::error::{Error, Result};
+use crate::tools::{BRP_METHOD_INSERT, BRP_METHOD_QUERY};
+
+/// Fully-qualified type of the component hiding and showing entities
+pub const VISIBILITY_TYPE: &str = "bevy_render::view::visibility::Visibility";
+
+/// Value of `Visibility` hiding an entity and its descendants
+pub const HIDDEN: &str = "Hidden";
+
+/// Isolations in progress, by app instance
+static ISOLATIONS: LazyLock<Mutex<HashMap<AppInstance, Isolation>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The entity left visible and the `Visibility` the hidden roots had before
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Isolation {
+    pub entity:   u64,
+    pub previous: BTreeMap<u64, Value>,
+}
+
+/// `Visibility` variant named by a parameter value, in any case
+pub fn parse_visibility(name: &str) -> Option<&'static str> {
+    ["Inherited", "Visible", HIDDEN]
+        .into_iter()
+        .find(|variant| variant.eq_ignore_ascii_case(name))
+}
+
+/// Insert a `Visibility` value on each entity concurrently
+///
+/// Returns the entities updated and the errors of the others, by entity ID.
+pub async fn insert_visibility(
+    values: &[(u64, Value)],
+    port: u16,
+) -> (Vec<u64>, Map<String, Value>) {
+    let outcomes = join_all(values.iter().map(|(entity, value)| {
+        execute_brp_method(
+            BRP_METHOD_INSERT,
+            Some(json!({
+                JSON_FIELD_ENTITY: entity,
+                JSON_FIELD_COMPONENTS: { VISIBILITY_TYPE: value },
+            })),
+            Some(port),
+        )
+    }))
+    .await;
+
+    let mut updated = Vec::new();
+    let mut failed = Map::new();
+    for ((entity, _), outcome) in values.iter().zip(outcomes) {
+        match outcome {
+            Ok(BrpResult::Success(_)) => updated.push(*entity),
+            Ok(BrpResult::Error(e)) => {
+                failed.insert(entity.to_string(), json!(e.message));
+            }
+            Err(e) => {
+                failed.insert(entity.to_string(), json!(e.to_string()));
+            }
+        }
+    }
+    (updated, failed)
+}
+
+/// The `Visibility` of every root entity that has one
+///
+/// Roots are the entities without any of the parent link components registered in the app,
+/// `ChildOf` or `Parent` depending on the Bevy version.
+pub async fn root_visibilities(port: u16) -> Result<BTreeMap<u64, Value>> {
+    let parent_components = registered_parent_components(port).await?;
+    let mut query = json!({ PARAM_DATA: { JSON_FIELD_COMPONENTS: [VISIBILITY_TYPE] } });
+    if !parent_components.is_empty() {
+        query[PARAM_FILTER] = json!({ "without": parent_components });
+    }
+    let rows = match execute_brp_method(BRP_METHOD_QUERY, Some(query), Some(port)).await? {
+        BrpResult::Success(Some(Value::Array(rows))) => rows,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(error_stack::Report::new(Error::BrpCommunication(format!(
+                "Failed to query root entities: {}",
+                e.message
+            ))));
+        }
+    };
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let entity = row.get(JSON_FIELD_ENTITY)?.as_u64()?;
+            let visibility = row.get(JSON_FIELD_COMPONENTS)?.get(VISIBILITY_TYPE)?;
+            Some((entity, visibility.clone()))
+        })
+        .collect())
+}
+
+/// Remember an isolation on the app on a port
+pub fn remember_isolation(port: u16, isolation: Isolation) -> Result<()> {
+    ISOLATIONS
+        .lock()
+        .map_err(|e| {
+            error_stack::Report::new(Error::MutexPoisoned(format!("isolation lock: {e}")))
+        })?
+        .insert(AppInstance::on_port(port), isolation);
+    Ok(())
+}
+
+/// Forget the isolation on the app on a port, returning it if there was one
+pub fn take_isolation(port: u16) -> Result<Option<Isolation>> {
+    Ok(ISOLATIONS
+        .lock()
+        .map_err(|e| {
+            error_stack::Report::new(Error::MutexPoisoned(format!("isolation lock: {e}")))
+        })?
+        .remove(&AppInstance::on_port(port)))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::brp_tools::support::mock_brp_server::MockBrpServer;
+    use crate::tools::BRP_METHOD_LIST;
+
+    #[tokio::test]
+    async fn test_root_visibilities_are_read_and_inserted() {
+        let server = MockBrpServer::start().await;
+        server
+            .respond(
+                BRP_METHOD_LIST,
+                json!([
+                    "bevy_hierarchy::components::parent::Parent",
+                    VISIBILITY_TYPE
+                ]),
+            )
+            .respond(
+                BRP_METHOD_QUERY,
+                json!([
+                    { "entity": 1, "components": { VISIBILITY_TYPE: "Inherited" } },
+                    { "entity": 2, "components": { VISIBILITY_TYPE: "Visible" } },
+                ]),
+            )
+            .respond(BRP_METHOD_INSERT, Value::Null)
+            .fail(BRP_METHOD_INSERT, -23404, "Entity 2 does not exist");
+
+        let roots = root_visibilities(server.port()).await.unwrap();
+        assert_eq!(
+            roots,
+            BTreeMap::from([(1, json!("Inherited")), (2, json!("Visible"))])
+        );
+        assert_eq!(
+            server.requests_for(BRP_METHOD_QUERY)[0].params,
+            Some(json!({
+                "data": { "components": [VISIBILITY_TYPE] },
+                "filter": { "without": ["bevy_hierarchy::components::parent::Parent"] },
+            }))
+        );
+
+        let hidden: Vec<(u64, Value)> = roots
+            .keys()
+            .map(|entity| (*entity, json!(HIDDEN)))
+            .collect();
+        let (updated, failed) = insert_visibility(&hidden, server.port()).await;
+        assert_eq!(updated.len() + failed.len(), 2);
+        assert_eq!(failed.len(), 1);
+
+        assert_eq!(parse_visibility("hidden"), Some(HIDDEN));
+        assert_eq!(parse_visibility("shown"), None);
+    }
+}
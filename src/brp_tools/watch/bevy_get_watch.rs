@@ -1,47 +1,49 @@
 //! Start watching an entity for component changes
 
-use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use std::time::Duration;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 use serde_json::Value;
 
-use crate::BrpMcpService;
-use crate::brp_tools::constants::{
-    DEFAULT_BRP_PORT, JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_PORT,
-};
-use crate::support::{params, schema};
-use crate::tools::{DESC_BEVY_GET_WATCH, TOOL_BEVY_GET_WATCH};
-
-pub fn register_tool() -> Tool {
-    Tool {
-        name: TOOL_BEVY_GET_WATCH.into(),
-        description: DESC_BEVY_GET_WATCH.into(),
-        input_schema: schema::SchemaBuilder::new()
-            .add_number_property(JSON_FIELD_ENTITY, "The entity ID to watch for component changes", true)
-            .add_any_property(
-                JSON_FIELD_COMPONENTS,
-                "Required array of component types to watch. Must contain at least one component. Without this, the watch will not detect any changes.",
-                true
-            )
-            .add_number_property(JSON_FIELD_PORT, &format!("The BRP port (default: {DEFAULT_BRP_PORT})"), false)
-            .build()
-    }
-}
+use super::support::Coalescing;
+use super::support::manager::WatchTarget;
+use crate::brp_tools::constants::{JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_PORT};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::{BrpMcpService, config};
+
+/// Parameter giving the least time between two logged updates
+pub const PARAM_THROTTLE_MS: &str = "throttle_ms";
+
+/// Parameter giving the quiet time before an update is logged
+pub const PARAM_DEBOUNCE_MS: &str = "debounce_ms";
+
+/// Longest allowed throttle or debounce
+pub const MAX_COALESCE_MS: u64 = 60_000;
+
+/// Description of the throttle parameter
+pub const DESC_THROTTLE_MS: &str = "Log at most one update per this many milliseconds, merging the changes in between (default: 0, log every change)";
+
+/// Description of the debounce parameter
+pub const DESC_DEBOUNCE_MS: &str = "Log an update only once no changes have arrived for this many milliseconds, merging the changes in between (default: 0, log every change)";
 
 pub async fn handle(
     _service: &BrpMcpService,
     request: CallToolRequestParam,
     _context: RequestContext<RoleServer>,
 ) -> Result<CallToolResult, McpError> {
+    let coalescing = extract_coalescing(&request)?;
     let arguments = Value::Object(request.arguments.unwrap_or_default());
 
     // Extract parameters
     let entity_id = params::extract_required_u64(&arguments, JSON_FIELD_ENTITY, "entity")?;
     let components = params::extract_optional_string_array(&arguments, JSON_FIELD_COMPONENTS);
-    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, DEFAULT_BRP_PORT);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
 
     // Start the watch task
-    let result = super::support::start_entity_watch_task(entity_id, components, port)
+    let result = super::support::start_entity_watch_task(entity_id, components, coalescing, port)
         .await
         .map_err(|e| {
             crate::error::Error::WatchOperation(format!(
@@ -51,6 +53,24 @@ pub async fn handle(
     Ok(super::support::format_watch_start_response(
         result,
         "entity watch",
-        entity_id,
+        &WatchTarget::Entity(entity_id),
     ))
 }
+
+/// Extract and validate the throttle and debounce of a streamed watch
+pub fn extract_coalescing(request: &CallToolRequestParam) -> Result<Coalescing, McpError> {
+    let duration = |name: &str| {
+        let ms = params::extract_optional_number(request, name, 0)?;
+        if ms > MAX_COALESCE_MS {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::invalid(name, format!("{ms} is more than {MAX_COALESCE_MS}")),
+            )));
+        }
+        Ok((ms > 0).then(|| Duration::from_millis(ms)))
+    };
+
+    Ok(Coalescing {
+        throttle: duration(PARAM_THROTTLE_MS)?,
+        debounce: duration(PARAM_DEBOUNCE_MS)?,
+    })
+}
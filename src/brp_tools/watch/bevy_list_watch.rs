@@ -1,47 +1,30 @@
 //! Start watching an entity for component list changes
 
-use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::model::{CallToolRequestParam, CallToolResult};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 use serde_json::Value;
 
-use crate::BrpMcpService;
-use crate::brp_tools::constants::{DEFAULT_BRP_PORT, JSON_FIELD_ENTITY, JSON_FIELD_PORT};
-use crate::support::{params, schema};
-use crate::tools::{DESC_BEVY_LIST_WATCH, TOOL_BEVY_LIST_WATCH};
-
-pub fn register_tool() -> Tool {
-    Tool {
-        name:         TOOL_BEVY_LIST_WATCH.into(),
-        description:  DESC_BEVY_LIST_WATCH.into(),
-        input_schema: schema::SchemaBuilder::new()
-            .add_number_property(
-                JSON_FIELD_ENTITY,
-                "The entity ID to watch for component list changes",
-                true,
-            )
-            .add_number_property(
-                JSON_FIELD_PORT,
-                &format!("The BRP port (default: {DEFAULT_BRP_PORT})"),
-                false,
-            )
-            .build(),
-    }
-}
+use super::bevy_get_watch::extract_coalescing;
+use super::support::manager::WatchTarget;
+use crate::brp_tools::constants::{JSON_FIELD_ENTITY, JSON_FIELD_PORT};
+use crate::support::params;
+use crate::{BrpMcpService, config};
 
 pub async fn handle(
     _service: &BrpMcpService,
     request: CallToolRequestParam,
     _context: RequestContext<RoleServer>,
 ) -> Result<CallToolResult, McpError> {
+    let coalescing = extract_coalescing(&request)?;
     let arguments = Value::Object(request.arguments.unwrap_or_default());
 
     // Extract parameters
     let entity_id = params::extract_required_u64(&arguments, JSON_FIELD_ENTITY, "entity")?;
-    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, DEFAULT_BRP_PORT);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
 
     // Start the watch task
-    let result = super::support::start_list_watch_task(entity_id, port)
+    let result = super::support::start_list_watch_task(entity_id, coalescing, port)
         .await
         .map_err(|e| {
             crate::error::Error::WatchOperation(format!(
@@ -51,6 +34,6 @@ pub async fn handle(
     Ok(super::support::format_watch_start_response(
         result,
         "list watch",
-        entity_id,
+        &WatchTarget::Entity(entity_id),
     ))
 }
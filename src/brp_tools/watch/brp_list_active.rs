@@ -1,11 +1,11 @@
-//! List all active watches
+//! List all active watches with their activity, cleaning up dead ones first
 
 use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 use serde_json::{Value, json};
 
-use super::support::manager::WATCH_MANAGER;
+use super::support::manager::{self, DeadWatch, WATCH_MANAGER, WatchInfo, WatchTarget};
 use crate::BrpMcpService;
 use crate::brp_tools::constants::{JSON_FIELD_COUNT, JSON_FIELD_WATCHES};
 use crate::error::Result;
@@ -27,27 +27,22 @@ pub async fn handle(
     _request: CallToolRequestParam,
     _context: RequestContext<RoleServer>,
 ) -> std::result::Result<CallToolResult, McpError> {
+    let cleaned_up = manager::cleanup_dead_watches().await;
+
     // Get active watches from manager and release lock immediately
-    let active_watches = {
+    let mut active_watches = {
         let manager = WATCH_MANAGER.lock().await;
         manager.list_active_watches()
     };
+    active_watches.sort_by_key(|watch| watch.watch_id);
 
-    // Convert to JSON format
-    let watches_json: Vec<Value> = active_watches
+    let watches_json: Vec<Value> = active_watches.iter().map(watch_json).collect();
+    let ended_json: Vec<Value> = manager::ended_watches()
         .iter()
-        .map(|watch| {
-            json!({
-                "watch_id": watch.watch_id,
-                "entity_id": watch.entity_id,
-                "watch_type": watch.watch_type,
-                "log_path": watch.log_path.to_string_lossy(),
-                "port": watch.port,
-            })
-        })
+        .map(dead_watch_json)
         .collect();
 
-    let response = match build_response(&watches_json) {
+    let response = match build_response(&watches_json, &ended_json, cleaned_up.len()) {
         Ok(resp) => resp,
         Err(err) => return Err(crate::error::report_to_mcp_error(&err)),
     };
@@ -55,11 +50,68 @@ pub async fn handle(
     Ok(json_response_to_result(&response))
 }
 
-fn build_response(watches_json: &[Value]) -> Result<crate::support::response::JsonResponse> {
+/// An active watch with its activity
+fn watch_json(watch: &WatchInfo) -> Value {
+    let mut watch_json = target_json(watch);
+    if let Some(stats) = manager::stats(watch.watch_id) {
+        watch_json["started_at"] = json!(stats.started_at.to_rfc3339());
+        watch_json["updates"] = json!(stats.updates);
+        watch_json["errors"] = json!(stats.errors);
+        watch_json["last_update"] = json!(stats.last_update.map(|time| time.to_rfc3339()));
+    }
+    watch_json["log_size_bytes"] = json!(
+        std::fs::metadata(&watch.log_path)
+            .ok()
+            .map(|metadata| metadata.len())
+    );
+    watch_json
+}
+
+/// A watch stopped by cleanup, and why
+fn dead_watch_json(watch: &DeadWatch) -> Value {
+    let mut watch_json = target_json(&watch.info);
+    watch_json["reason"] = json!(watch.reason);
+    watch_json["ended_at"] = json!(watch.ended_at.to_rfc3339());
+    watch_json
+}
+
+/// The fields identifying a watch and what it observes
+fn target_json(watch: &WatchInfo) -> Value {
+    let mut watch_json = json!({
+        "watch_id": watch.watch_id,
+        "watch_type": watch.watch_type,
+        "log_path": watch.log_path.to_string_lossy(),
+        "host": watch.host,
+        "port": watch.port,
+    });
+    if let Some(label) = &watch.label {
+        watch_json["label"] = json!(label);
+    }
+    match &watch.target {
+        WatchTarget::Entity(entity_id) => watch_json["entity_id"] = json!(entity_id),
+        WatchTarget::Resource(resource) => watch_json["resource"] = json!(resource),
+    }
+    watch_json
+}
+
+fn build_response(
+    watches_json: &[Value],
+    ended_json: &[Value],
+    cleaned_up: usize,
+) -> Result<crate::support::response::JsonResponse> {
+    let message = if cleaned_up == 0 {
+        format!("Found {} active watches", watches_json.len())
+    } else {
+        format!(
+            "Found {} active watches after cleaning up {cleaned_up} dead ones",
+            watches_json.len()
+        )
+    };
     let response = ResponseBuilder::success()
-        .message(format!("Found {} active watches", watches_json.len()))
+        .message(message)
         .add_field(JSON_FIELD_WATCHES, watches_json)?
         .add_field(JSON_FIELD_COUNT, watches_json.len())?
+        .add_field("cleaned_up", ended_json)?
         .auto_inject_debug_info(None::<&serde_json::Value>, None::<&serde_json::Value>)
         .build();
 
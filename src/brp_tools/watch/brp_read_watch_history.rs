@@ -0,0 +1,288 @@
+//! Read the recent events of watches
+//!
+//! Every watch keeps its last events in memory next to its log file. This tool queries them
+//! across watches, including ones that have ended, filtered by watch, entity, component or
+//! resource and time range.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, FixedOffset};
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::support::manager::{self, HISTORY_CAPACITY, WatchEvent, WatchHistory, WatchTarget};
+use crate::BrpMcpService;
+use crate::brp_tools::constants::{
+    JSON_FIELD_COMPONENT, JSON_FIELD_ENTITY, JSON_FIELD_WATCH_ID, PARAM_LIMIT,
+};
+use crate::brp_tools::support::component_filter;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_READ_WATCH_HISTORY, TOOL_BRP_READ_WATCH_HISTORY};
+
+/// Parameter giving the earliest event time
+const PARAM_SINCE: &str = "since";
+
+/// Parameter giving the latest event time
+const PARAM_UNTIL: &str = "until";
+
+/// Events returned when no limit is given
+const DEFAULT_LIMIT: usize = 100;
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_READ_WATCH_HISTORY.into(),
+        description:  DESC_BRP_READ_WATCH_HISTORY.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_WATCH_ID,
+                "Only events of this watch (default: all recent watches)",
+                false,
+            )
+            .add_number_property(
+                JSON_FIELD_ENTITY,
+                "Only events of watches on this entity",
+                false,
+            )
+            .add_string_property(
+                JSON_FIELD_COMPONENT,
+                "Only events mentioning this component or resource: a type path, short type name, crate name or glob such as *::Transform",
+                false,
+            )
+            .add_string_property(
+                PARAM_SINCE,
+                "Only events at or after this RFC 3339 time, e.g. 2025-06-01T12:00:00+02:00",
+                false,
+            )
+            .add_string_property(
+                PARAM_UNTIL,
+                "Only events at or before this RFC 3339 time",
+                false,
+            )
+            .add_number_property(
+                PARAM_LIMIT,
+                &format!(
+                    "Most recent matching events to return (default: {DEFAULT_LIMIT}). Each watch keeps its last {HISTORY_CAPACITY} events"
+                ),
+                false,
+            )
+            .build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let filter =
+        HistoryFilter::from_arguments(&arguments).map_err(|report| report_to_mcp_error(&report))?;
+    let limit = arguments
+        .get(PARAM_LIMIT)
+        .and_then(Value::as_u64)
+        .and_then(|limit| usize::try_from(limit).ok())
+        .unwrap_or(DEFAULT_LIMIT);
+
+    let histories = manager::histories();
+    let mut events = filter.select(&histories);
+    let matched = events.len();
+    events.drain(..matched.saturating_sub(limit));
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "{} of {matched} matching events from {} recorded watches",
+            events.len(),
+            histories.len()
+        ))
+        .data(json!({
+            "events": events.iter().map(WatchEvent::to_json).collect::<Vec<_>>(),
+            "matched": matched,
+            "truncated": matched > events.len(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Which events to read
+#[derive(Debug, Default)]
+struct HistoryFilter {
+    watch_id:  Option<u32>,
+    entity:    Option<u64>,
+    component: Option<String>,
+    since:     Option<DateTime<FixedOffset>>,
+    until:     Option<DateTime<FixedOffset>>,
+}
+
+impl HistoryFilter {
+    fn from_arguments(arguments: &Value) -> crate::error::Result<Self> {
+        let time = |name: &str| {
+            arguments
+                .get(name)
+                .and_then(Value::as_str)
+                .map(|time| {
+                    DateTime::parse_from_rfc3339(time).map_err(|e| {
+                        error_stack::Report::new(Error::invalid(
+                            &format!("parameter '{name}'"),
+                            format!("'{time}' is not an RFC 3339 time: {e}"),
+                        ))
+                    })
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            watch_id:  arguments
+                .get(JSON_FIELD_WATCH_ID)
+                .and_then(Value::as_u64)
+                .and_then(|id| u32::try_from(id).ok()),
+            entity:    arguments.get(JSON_FIELD_ENTITY).and_then(Value::as_u64),
+            component: arguments
+                .get(JSON_FIELD_COMPONENT)
+                .and_then(Value::as_str)
+                .map(String::from),
+            since:     time(PARAM_SINCE)?,
+            until:     time(PARAM_UNTIL)?,
+        })
+    }
+
+    /// Matching events of all watches, oldest first
+    fn select(&self, histories: &BTreeMap<u32, WatchHistory>) -> Vec<WatchEvent> {
+        let mut events: Vec<WatchEvent> = histories
+            .iter()
+            .filter(|(watch_id, _)| self.watch_id.is_none_or(|id| id == **watch_id))
+            .filter(|(_, history)| {
+                self.entity
+                    .is_none_or(|entity| history.target == WatchTarget::Entity(entity))
+            })
+            .flat_map(|(_, history)| {
+                history
+                    .events
+                    .iter()
+                    .filter(|event| self.matches(&history.target, event))
+            })
+            .cloned()
+            .collect();
+        events.sort_by_key(|event| event.timestamp);
+        events
+    }
+
+    fn matches(&self, target: &WatchTarget, event: &WatchEvent) -> bool {
+        self.since.is_none_or(|since| event.timestamp >= since)
+            && self.until.is_none_or(|until| event.timestamp <= until)
+            && self.component.as_deref().is_none_or(|component| {
+                mentioned_types(target, &event.data)
+                    .iter()
+                    .any(|type_name| type_matches(component, type_name))
+            })
+    }
+}
+
+/// The component or resource types an event is about
+fn mentioned_types<'a>(target: &'a WatchTarget, data: &'a Value) -> Vec<&'a str> {
+    let mut types = Vec::new();
+    if let WatchTarget::Resource(resource) = target {
+        types.push(resource.as_str());
+    }
+    for field in ["changes", "components", "errors"] {
+        if let Some(fields) = data.get(field).and_then(Value::as_object) {
+            types.extend(fields.keys().map(String::as_str));
+        }
+    }
+    for field in ["added", "removed", "components"] {
+        if let Some(names) = data.get(field).and_then(Value::as_array) {
+            types.extend(names.iter().filter_map(Value::as_str));
+        }
+    }
+    types
+}
+
+/// Whether a type is named by a type path, short name, crate name or glob
+fn type_matches(component: &str, type_name: &str) -> bool {
+    component == type_name
+        || type_name.rsplit("::").next() == Some(component)
+        || component_filter::pattern_matches(component, type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::collections::VecDeque;
+
+    use chrono::{Local, TimeZone};
+
+    use super::*;
+
+    fn event(watch_id: u32, second: u32, data: Value) -> WatchEvent {
+        WatchEvent {
+            watch_id,
+            update_type: "COMPONENT_UPDATE".to_string(),
+            timestamp: Local.with_ymd_and_hms(2025, 6, 1, 12, 0, second).unwrap(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_events_are_filtered_by_entity_component_and_time() {
+        let transform = "bevy_transform::components::transform::Transform";
+        let histories = BTreeMap::from([
+            (
+                1,
+                WatchHistory {
+                    target: WatchTarget::Entity(42),
+                    events: VecDeque::from([
+                        event(1, 0, json!({ "changes": { transform: {} } })),
+                        event(1, 20, json!({ "changes": { "game::Health": {} } })),
+                        event(1, 40, json!({ "removed": [transform] })),
+                    ]),
+                },
+            ),
+            (
+                2,
+                WatchHistory {
+                    target: WatchTarget::Resource("game::Score".to_string()),
+                    events: VecDeque::from([event(2, 10, json!({ "changes": 3 }))]),
+                },
+            ),
+        ]);
+        let seconds = |events: Vec<WatchEvent>| {
+            events
+                .iter()
+                .map(|event| event.timestamp.format("%S").to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            seconds(HistoryFilter::default().select(&histories)),
+            vec!["00", "10", "20", "40"]
+        );
+
+        let by_component = HistoryFilter {
+            entity: Some(42),
+            component: Some("Transform".to_string()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(seconds(by_component.select(&histories)), vec!["00", "40"]);
+
+        let by_time = HistoryFilter::from_arguments(&json!({
+            "component": "game",
+            "since": Local.with_ymd_and_hms(2025, 6, 1, 12, 0, 5).unwrap().to_rfc3339(),
+        }))
+        .unwrap();
+        assert_eq!(seconds(by_time.select(&histories)), vec!["10", "20"]);
+
+        assert!(HistoryFilter::from_arguments(&json!({ "until": "yesterday" })).is_err());
+    }
+}
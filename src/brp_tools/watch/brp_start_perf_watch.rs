@@ -0,0 +1,88 @@
+//! Start sampling performance diagnostics, alerting when they cross thresholds
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::Value;
+
+use super::brp_watch_resource::extract_interval;
+use super::support::manager::WatchTarget;
+use super::support::{PerfWatch, Threshold};
+use crate::brp_tools::bevy_get_diagnostics::DIAGNOSTICS_STORE;
+use crate::brp_tools::constants::{JSON_FIELD_PORT, JSON_FIELD_RESOURCE, PARAM_LABEL};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::{BrpMcpService, config};
+
+/// Parameter listing the diagnostics to log
+pub const PARAM_DIAGNOSTICS: &str = "diagnostics";
+
+/// Parameter listing the alert thresholds
+pub const PARAM_THRESHOLDS: &str = "thresholds";
+
+/// Diagnostics logged when none are given
+const DEFAULT_DIAGNOSTICS: &[&str] = &["fps", "frame_time"];
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let interval = extract_interval(&request)?;
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+    let resource = arguments
+        .get(JSON_FIELD_RESOURCE)
+        .and_then(Value::as_str)
+        .unwrap_or(DIAGNOSTICS_STORE)
+        .to_string();
+    let label = arguments.get(PARAM_LABEL).and_then(Value::as_str);
+
+    let thresholds = match arguments.get(PARAM_THRESHOLDS) {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(thresholds)) => thresholds
+            .iter()
+            .map(Threshold::from_json)
+            .collect::<Result<_, _>>()
+            .map_err(|report| report_to_mcp_error(&report))?,
+        Some(_) => {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::invalid(
+                    &format!("parameter '{PARAM_THRESHOLDS}'"),
+                    "expected an array of thresholds",
+                ),
+            )));
+        }
+    };
+
+    // Threshold diagnostics are always logged, so alerts can be read against the samples
+    let mut diagnostics = params::extract_optional_string_array(&arguments, PARAM_DIAGNOSTICS)
+        .filter(|diagnostics| !diagnostics.is_empty())
+        .unwrap_or_else(|| {
+            DEFAULT_DIAGNOSTICS
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        });
+    for threshold in &thresholds {
+        if !diagnostics.contains(&threshold.diagnostic) {
+            diagnostics.push(threshold.diagnostic.clone());
+        }
+    }
+
+    let target = WatchTarget::Resource(resource.clone());
+    let watch = PerfWatch {
+        resource,
+        diagnostics,
+        thresholds,
+        interval,
+    };
+    let result = super::support::start_perf_watch_task(watch, label, port)
+        .await
+        .map_err(|e| Error::WatchOperation(format!("Failed to start performance watch: {e}")));
+    Ok(super::support::format_watch_start_response(
+        result,
+        "performance watch",
+        &target,
+    ))
+}
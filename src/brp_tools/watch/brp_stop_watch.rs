@@ -1,29 +1,17 @@
-//! Stop an active watch
+//! Stop an active watch, or every watch sharing a label
 
-use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::model::{CallToolRequestParam, CallToolResult};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
-use serde_json::Value;
+use serde_json::{Value, json};
 
 use super::support::manager::WATCH_MANAGER;
 use crate::BrpMcpService;
-use crate::brp_tools::constants::JSON_FIELD_WATCH_ID;
-use crate::support::{params, schema};
-use crate::tools::{DESC_BRP_STOP_WATCH, TOOL_BRP_STOP_WATCH};
-
-pub fn register_tool() -> Tool {
-    Tool {
-        name:         TOOL_BRP_STOP_WATCH.into(),
-        description:  DESC_BRP_STOP_WATCH.into(),
-        input_schema: schema::SchemaBuilder::new()
-            .add_number_property(
-                JSON_FIELD_WATCH_ID,
-                "The watch ID returned from bevy_start_entity_watch or bevy_start_list_watch",
-                true,
-            )
-            .build(),
-    }
-}
+use crate::brp_tools::constants::{JSON_FIELD_WATCH_ID, PARAM_LABEL};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
 
 pub async fn handle(
     _service: &BrpMcpService,
@@ -32,6 +20,11 @@ pub async fn handle(
 ) -> Result<CallToolResult, McpError> {
     let arguments = Value::Object(request.arguments.unwrap_or_default());
 
+    let label = arguments.get(PARAM_LABEL).and_then(Value::as_str);
+    if let Some(label) = label.filter(|_| arguments.get(JSON_FIELD_WATCH_ID).is_none()) {
+        return stop_label(label).await;
+    }
+
     // Extract watch ID
     let watch_id = params::extract_required_u32(&arguments, JSON_FIELD_WATCH_ID, "watch_id")?;
 
@@ -44,3 +37,31 @@ pub async fn handle(
     };
     Ok(super::support::format_watch_stop_response(result, watch_id))
 }
+
+/// Stop every watch with a label
+async fn stop_label(label: &str) -> Result<CallToolResult, McpError> {
+    let watch_ids = WATCH_MANAGER.lock().await.stop_label(label);
+    if watch_ids.is_empty() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::WatchOperation(format!(
+                "Failed to stop watches labelled '{label}': no active watch has this label"
+            )),
+        )));
+    }
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Stopped {} watches labelled '{label}'",
+            watch_ids.len()
+        ))
+        .data(json!({ "watch_ids": watch_ids }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+    Ok(json_response_to_result(&response))
+}
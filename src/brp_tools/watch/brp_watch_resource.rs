@@ -0,0 +1,68 @@
+//! Start polling a resource for value changes
+
+use std::time::Duration;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::Value;
+
+use super::support::manager::WatchTarget;
+use crate::brp_tools::constants::{JSON_FIELD_PORT, JSON_FIELD_RESOURCE};
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::{BrpMcpService, config};
+
+/// Parameter name of the polling interval
+pub const PARAM_INTERVAL_MS: &str = "interval_ms";
+
+/// Polling interval used when none is given
+pub const DEFAULT_INTERVAL_MS: u64 = 1000;
+
+/// Allowed polling intervals
+pub const INTERVAL_RANGE_MS: std::ops::RangeInclusive<u64> = 100..=60_000;
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let resource = params::extract_required_string(&request, JSON_FIELD_RESOURCE)?.to_string();
+    let interval = extract_interval(&request)?;
+
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    // Start the polling task
+    let result = super::support::start_resource_watch_task(&resource, interval, None, port)
+        .await
+        .map_err(|e| {
+            Error::WatchOperation(format!(
+                "Failed to start resource watch for {resource}: {e}"
+            ))
+        });
+    Ok(super::support::format_watch_start_response(
+        result,
+        "resource watch",
+        &WatchTarget::Resource(resource),
+    ))
+}
+
+/// Extract and validate the polling interval
+pub fn extract_interval(request: &CallToolRequestParam) -> Result<Duration, McpError> {
+    let interval_ms =
+        params::extract_optional_number(request, PARAM_INTERVAL_MS, DEFAULT_INTERVAL_MS)?;
+    if !INTERVAL_RANGE_MS.contains(&interval_ms) {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                PARAM_INTERVAL_MS,
+                format!(
+                    "{interval_ms} is outside {}-{}",
+                    INTERVAL_RANGE_MS.start(),
+                    INTERVAL_RANGE_MS.end()
+                ),
+            ),
+        )));
+    }
+    Ok(Duration::from_millis(interval_ms))
+}
@@ -0,0 +1,179 @@
+//! Start polling every resource matching a set of crate or type patterns
+//!
+//! Matching resources are resolved once with `bevy/list_resources`. Each one gets its own
+//! resource watch, so value diffs are logged per resource, and all of them share a label that
+//! `brp_stop_watch` and `brp_list_active_watches` understand.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::brp_watch_resource::extract_interval;
+use crate::brp_tools::constants::{
+    JSON_FIELD_LOG_PATH, JSON_FIELD_PORT, JSON_FIELD_RESOURCE, JSON_FIELD_WATCH_ID, PARAM_LABEL,
+};
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::support::component_filter;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::BRP_METHOD_LIST_RESOURCES;
+use crate::{BrpMcpService, config};
+
+/// Parameter listing the crate or type patterns
+pub const PARAM_PATTERNS: &str = "patterns";
+
+/// Upper bound on the number of resources watched by one call
+const MAX_RESOURCES: usize = 32;
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let interval = extract_interval(&request)?;
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let patterns = params::extract_optional_string_array(&arguments, PARAM_PATTERNS)
+        .filter(|patterns| !patterns.is_empty())
+        .ok_or_else(|| {
+            report_to_mcp_error(&error_stack::Report::new(Error::missing(&format!(
+                "parameter '{PARAM_PATTERNS}'"
+            ))))
+        })?;
+    let label = arguments
+        .get(PARAM_LABEL)
+        .and_then(Value::as_str)
+        .map_or_else(|| patterns.join(","), String::from);
+    let port = params::extract_optional_u16(&arguments, JSON_FIELD_PORT, config::default_port());
+
+    let resources = matching_resources(&patterns, port).await?;
+
+    // Resources that can't be read (e.g. not reflected) are reported rather than failing the call
+    let mut watches = Vec::new();
+    let mut skipped = Vec::new();
+    for resource in &resources {
+        match super::support::start_resource_watch_task(resource, interval, Some(&label), port)
+            .await
+        {
+            Ok((watch_id, log_path)) => watches.push(json!({
+                JSON_FIELD_RESOURCE: resource,
+                JSON_FIELD_WATCH_ID: watch_id,
+                JSON_FIELD_LOG_PATH: log_path.to_string_lossy(),
+            })),
+            Err(e) => skipped.push(json!({
+                JSON_FIELD_RESOURCE: resource,
+                "error": e.current_context().to_string(),
+            })),
+        }
+    }
+
+    let message = format!(
+        "Started {} resource watches labelled '{label}'{}",
+        watches.len(),
+        if skipped.is_empty() {
+            String::new()
+        } else {
+            format!(", skipped {} unreadable resources", skipped.len())
+        }
+    );
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            PARAM_LABEL: label,
+            "watches": watches,
+            "skipped": skipped,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Registered resources matching any of the patterns, in type path order
+async fn matching_resources(patterns: &[String], port: u16) -> Result<Vec<String>, McpError> {
+    let listed = match execute_brp_method(BRP_METHOD_LIST_RESOURCES, None, Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    {
+        BrpResult::Success(Some(Value::Array(names))) => names,
+        BrpResult::Success(_) => Vec::new(),
+        BrpResult::Error(e) => {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::BrpCommunication(format!("Failed to list resources: {}", e.message)),
+            )));
+        }
+    };
+
+    let resources = select(&listed, patterns);
+    if resources.is_empty() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{PARAM_PATTERNS}'"),
+                format!("no resource on port {port} matches {}", patterns.join(", ")),
+            ),
+        )));
+    }
+    if resources.len() > MAX_RESOURCES {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                &format!("parameter '{PARAM_PATTERNS}'"),
+                format!(
+                    "{} resources match, more than the {MAX_RESOURCES} one call may watch. Use narrower patterns",
+                    resources.len()
+                ),
+            ),
+        )));
+    }
+    Ok(resources)
+}
+
+/// The listed type paths matching any pattern, sorted
+fn select(listed: &[Value], patterns: &[String]) -> Vec<String> {
+    let mut resources: Vec<String> = listed
+        .iter()
+        .filter_map(Value::as_str)
+        .filter(|resource| {
+            patterns
+                .iter()
+                .any(|pattern| component_filter::pattern_matches(pattern, resource))
+        })
+        .map(String::from)
+        .collect();
+    resources.sort_unstable();
+    resources
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resources_are_selected_by_crate_and_glob() {
+        let listed = [
+            json!("game_config::Difficulty"),
+            json!("game_config::audio::Volume"),
+            json!("game_configurator::Wizard"),
+            json!("bevy_time::time::Time<()>"),
+            json!("game::settings::Graphics"),
+        ];
+        assert_eq!(
+            select(&listed, &["game_config".to_string()]),
+            vec!["game_config::Difficulty", "game_config::audio::Volume"]
+        );
+        assert_eq!(
+            select(
+                &listed,
+                &["game::settings::*".to_string(), "*::Time*".to_string()]
+            ),
+            vec!["bevy_time::time::Time<()>", "game::settings::Graphics"]
+        );
+    }
+}
@@ -3,5 +3,9 @@
 pub mod bevy_get_watch;
 pub mod bevy_list_watch;
 pub mod brp_list_active;
+pub mod brp_read_watch_history;
+pub mod brp_start_perf_watch;
 pub mod brp_stop_watch;
+pub mod brp_watch_resource;
+pub mod brp_watch_resources;
 pub mod support;
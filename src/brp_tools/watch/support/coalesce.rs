@@ -0,0 +1,170 @@
+//! Coalescing of streamed watch results
+//!
+//! Components that change every frame, such as `Transform`, make `bevy/get+watch` send a result
+//! per frame. With a throttle or debounce, results are merged while they're held back and only
+//! the merged result is diffed and logged: at most once per throttle period, and only once the
+//! stream has been quiet for the debounce period.
+
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::time::Instant;
+
+/// How streamed results are held back before being logged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Coalescing {
+    /// Least time between two logged updates
+    pub throttle: Option<Duration>,
+    /// Time without new results before an update is logged
+    pub debounce: Option<Duration>,
+}
+
+/// A merged result ready to be logged
+#[derive(Debug)]
+pub struct Coalesced {
+    pub result: Value,
+    /// Number of streamed results merged into it
+    pub count:  u64,
+}
+
+/// Merges streamed results until they're due
+pub struct Coalescer {
+    coalescing:    Coalescing,
+    pending:       Option<Coalesced>,
+    last_received: Option<Instant>,
+    last_emitted:  Option<Instant>,
+}
+
+impl Coalescer {
+    pub const fn new(coalescing: Coalescing) -> Self {
+        Self {
+            coalescing,
+            pending: None,
+            last_received: None,
+            last_emitted: None,
+        }
+    }
+
+    /// Add a streamed result, returning the merged result if it's due now
+    pub fn push(&mut self, result: Value, now: Instant) -> Option<Coalesced> {
+        match &mut self.pending {
+            Some(pending) => {
+                merge(&mut pending.result, result);
+                pending.count += 1;
+            }
+            None => self.pending = Some(Coalesced { result, count: 1 }),
+        }
+        self.last_received = Some(now);
+        self.take_due(now)
+    }
+
+    /// When the pending result will be due, if there is one
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending.as_ref()?;
+        let throttled = self
+            .coalescing
+            .throttle
+            .zip(self.last_emitted)
+            .map(|(throttle, emitted)| emitted + throttle);
+        let debounced = self
+            .coalescing
+            .debounce
+            .zip(self.last_received)
+            .map(|(debounce, received)| received + debounce);
+        match (throttled, debounced) {
+            (Some(throttled), Some(debounced)) => Some(throttled.max(debounced)),
+            (deadline, None) | (None, deadline) => deadline.or(self.last_received),
+        }
+    }
+
+    /// Take the pending result if it's due
+    pub fn take_due(&mut self, now: Instant) -> Option<Coalesced> {
+        if self.deadline().is_some_and(|deadline| deadline <= now) {
+            self.last_emitted = Some(now);
+            self.pending.take()
+        } else {
+            None
+        }
+    }
+
+    /// Take the pending result regardless of timing, e.g. when the stream ends
+    pub const fn take_pending(&mut self) -> Option<Coalesced> {
+        self.pending.take()
+    }
+}
+
+/// Merge a newer result into an older one
+///
+/// Entries of object fields such as `components` are replaced by the newer ones, so the latest
+/// value of each component wins, and array fields such as `removed` are combined.
+fn merge(older: &mut Value, newer: Value) {
+    match (older, newer) {
+        (Value::Object(older), Value::Object(newer)) => {
+            for (key, value) in newer {
+                match (older.get_mut(&key), value) {
+                    (Some(Value::Object(existing)), Value::Object(value)) => existing.extend(value),
+                    (Some(Value::Array(existing)), Value::Array(value)) => {
+                        for item in value {
+                            if !existing.contains(&item) {
+                                existing.push(item);
+                            }
+                        }
+                    }
+                    (_, value) => {
+                        older.insert(key, value);
+                    }
+                }
+            }
+        }
+        (older, newer) => *older = newer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_throttled_and_debounced_results_are_merged() {
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let transform =
+            |x: f64| json!({ "components": { "Transform": { "translation": [x, 0.0, 0.0] } } });
+
+        // The first result passes a throttle, the next ones are held until the period ends
+        let mut throttled = Coalescer::new(Coalescing {
+            throttle: Some(Duration::from_millis(100)),
+            debounce: None,
+        });
+        assert_eq!(throttled.push(transform(1.0), ms(0)).unwrap().count, 1);
+        assert!(throttled.push(transform(2.0), ms(16)).is_none());
+        assert!(
+            throttled
+                .push(json!({ "components": {}, "removed": ["Sprite"] }), ms(33))
+                .is_none()
+        );
+        assert_eq!(throttled.deadline(), Some(ms(100)));
+        let merged = throttled.take_due(ms(100)).unwrap();
+        assert_eq!(merged.count, 2);
+        assert_eq!(
+            merged.result,
+            json!({ "components": { "Transform": { "translation": [2.0, 0.0, 0.0] } }, "removed": ["Sprite"] })
+        );
+
+        // A debounce waits for the stream to go quiet
+        let mut debounced = Coalescer::new(Coalescing {
+            throttle: None,
+            debounce: Some(Duration::from_millis(50)),
+        });
+        assert!(debounced.push(transform(1.0), ms(0)).is_none());
+        assert!(debounced.push(transform(3.0), ms(40)).is_none());
+        assert!(debounced.take_due(ms(60)).is_none());
+        let merged = debounced.take_due(ms(90)).unwrap();
+        assert_eq!(merged.count, 2);
+        assert_eq!(merged.result, transform(3.0));
+        assert_eq!(debounced.deadline(), None);
+    }
+}
@@ -0,0 +1,238 @@
+//! Per-field diffing of consecutive watch updates
+//!
+//! `bevy/get+watch` re-sends the full value of every changed component, which makes watching
+//! large components like `GlobalTransform` very noisy. The differ keeps the last known value of
+//! each component and reduces every update to the fields that actually changed. Polled resource
+//! watches are diffed the same way, against the previously fetched value.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::brp_tools::support::json_diff::diff_values;
+
+/// Computes deltas between consecutive watch updates and numbers them
+pub struct WatchDiffer {
+    /// Whether component values should be diffed (only meaningful for `get` watches)
+    diff_components: bool,
+    /// Whether whole values should be diffed (`resource` watches)
+    diff_values:     bool,
+    /// Last known value of each watched component
+    components:      HashMap<String, Value>,
+    /// Last known value of a watched resource
+    last_value:      Option<Value>,
+    /// Sequence number of the last emitted update
+    sequence:        u64,
+}
+
+impl WatchDiffer {
+    /// Create a differ for the given watch type
+    pub fn new(watch_type: &str) -> Self {
+        Self {
+            diff_components: watch_type == "get",
+            diff_values:     watch_type == "resource",
+            components:      HashMap::new(),
+            last_value:      None,
+            sequence:        0,
+        }
+    }
+
+    /// Reduce a watch result to the fields that changed since the previous update.
+    ///
+    /// Returns `None` when the update carries no changes at all.
+    pub fn next_update(&mut self, result: Value) -> Option<Value> {
+        let mut update = if self.diff_components {
+            self.diff_update(result)?
+        } else if self.diff_values {
+            self.diff_value(result)?
+        } else {
+            match result {
+                Value::Object(map) => map,
+                other => {
+                    let mut map = Map::new();
+                    map.insert("result".to_string(), other);
+                    map
+                }
+            }
+        };
+
+        self.sequence += 1;
+        update.insert("sequence".to_string(), Value::from(self.sequence));
+        Some(Value::Object(update))
+    }
+
+    /// Build the delta for a polled resource value
+    fn diff_value(&mut self, value: Value) -> Option<Map<String, Value>> {
+        let changes = self.last_value.as_ref().map_or_else(
+            || Some(value.clone()),
+            |previous| diff_values(previous, &value),
+        )?;
+        self.last_value = Some(value);
+
+        let mut update = Map::new();
+        update.insert("changes".to_string(), changes);
+        Some(update)
+    }
+
+    /// Build the delta for a `bevy/get+watch` result
+    fn diff_update(&mut self, result: Value) -> Option<Map<String, Value>> {
+        let Value::Object(mut result) = result else {
+            return None;
+        };
+
+        let mut changes = Map::new();
+        if let Some(Value::Object(components)) = result.remove("components") {
+            for (name, value) in components {
+                let delta = self.components.get(&name).map_or_else(
+                    || Some(value.clone()),
+                    |previous| diff_values(previous, &value),
+                );
+                if let Some(delta) = delta {
+                    changes.insert(name.clone(), delta);
+                }
+                self.components.insert(name, value);
+            }
+        }
+
+        let removed = match result.remove("removed") {
+            Some(Value::Array(removed)) if !removed.is_empty() => {
+                for name in removed.iter().filter_map(Value::as_str) {
+                    self.components.remove(name);
+                }
+                Some(removed)
+            }
+            _ => None,
+        };
+
+        let errors = match result.remove("errors") {
+            Some(Value::Object(errors)) if !errors.is_empty() => Some(errors),
+            _ => None,
+        };
+
+        if changes.is_empty() && removed.is_none() && errors.is_none() {
+            return None;
+        }
+
+        let mut update = Map::new();
+        update.insert("changes".to_string(), Value::Object(changes));
+        if let Some(removed) = removed {
+            update.insert("removed".to_string(), Value::Array(removed));
+        }
+        if let Some(errors) = errors {
+            update.insert("errors".to_string(), Value::Object(errors));
+        }
+        Some(update)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_first_update_contains_full_value() {
+        let mut differ = WatchDiffer::new("get");
+        let update = differ.next_update(json!({
+            "components": { "Transform": { "translation": [0.0, 1.0, 2.0], "scale": [1.0, 1.0, 1.0] } },
+            "removed": []
+        }));
+
+        assert_eq!(
+            update,
+            Some(json!({
+                "sequence": 1,
+                "changes": {
+                    "Transform": { "translation": [0.0, 1.0, 2.0], "scale": [1.0, 1.0, 1.0] }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn test_subsequent_update_contains_only_changed_fields() {
+        let mut differ = WatchDiffer::new("get");
+        differ.next_update(json!({
+            "components": { "Transform": { "translation": [0.0, 1.0, 2.0], "scale": [1.0, 1.0, 1.0] } }
+        }));
+        let update = differ.next_update(json!({
+            "components": { "Transform": { "translation": [5.0, 1.0, 2.0], "scale": [1.0, 1.0, 1.0] } }
+        }));
+
+        assert_eq!(
+            update,
+            Some(json!({
+                "sequence": 2,
+                "changes": { "Transform": { "translation": [5.0, 1.0, 2.0] } }
+            }))
+        );
+    }
+
+    #[test]
+    fn test_unchanged_update_is_skipped() {
+        let mut differ = WatchDiffer::new("get");
+        let value = json!({ "components": { "Name": "player" } });
+        differ.next_update(value.clone());
+
+        assert_eq!(differ.next_update(value), None);
+    }
+
+    #[test]
+    fn test_removed_component_is_reported_and_forgotten() {
+        let mut differ = WatchDiffer::new("get");
+        differ.next_update(json!({ "components": { "Name": "player" } }));
+        let removed = differ.next_update(json!({ "components": {}, "removed": ["Name"] }));
+        let readded = differ.next_update(json!({ "components": { "Name": "player" } }));
+
+        assert_eq!(
+            removed,
+            Some(json!({ "sequence": 2, "changes": {}, "removed": ["Name"] }))
+        );
+        assert_eq!(
+            readded,
+            Some(json!({ "sequence": 3, "changes": { "Name": "player" } }))
+        );
+    }
+
+    #[test]
+    fn test_missing_field_is_reported_as_null() {
+        let delta = diff_values(
+            &json!({ "a": 1, "b": { "c": 2 } }),
+            &json!({ "b": { "c": 3 } }),
+        );
+
+        assert_eq!(delta, Some(json!({ "a": null, "b": { "c": 3 } })));
+    }
+
+    #[test]
+    fn test_resource_watch_diffs_whole_value() {
+        let mut differ = WatchDiffer::new("resource");
+        let first = differ.next_update(json!({ "elapsed": 1.0, "paused": false }));
+        let second = differ.next_update(json!({ "elapsed": 2.5, "paused": false }));
+
+        assert_eq!(
+            first,
+            Some(json!({ "sequence": 1, "changes": { "elapsed": 1.0, "paused": false } }))
+        );
+        assert_eq!(
+            second,
+            Some(json!({ "sequence": 2, "changes": { "elapsed": 2.5 } }))
+        );
+        assert_eq!(
+            differ.next_update(json!({ "elapsed": 2.5, "paused": false })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_list_watch_passes_through_with_sequence() {
+        let mut differ = WatchDiffer::new("list");
+        let update = differ.next_update(json!({ "added": ["Name"], "removed": [] }));
+
+        assert_eq!(
+            update,
+            Some(json!({ "added": ["Name"], "removed": [], "sequence": 1 }))
+        );
+    }
+}
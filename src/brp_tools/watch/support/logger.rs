@@ -1,13 +1,16 @@
 //! Optimized watch logging with buffering and batching
 
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, error};
 
+use super::manager::{self, WatchTarget};
+use crate::log_tools::support::create_log_directory;
+
 /// Log entry to be written
 #[derive(Debug)]
 pub struct LogEntry {
@@ -18,13 +21,15 @@ pub struct LogEntry {
 
 /// Buffered logger for watch updates
 pub struct BufferedWatchLogger {
+    /// Watch whose history also receives every entry
+    watch_id:    u32,
     tx:          mpsc::Sender<LogEntry>,
     shutdown_tx: Option<oneshot::Sender<()>>,
 }
 
 impl BufferedWatchLogger {
     /// Create a new buffered logger and spawn the writer task
-    pub fn new(log_path: PathBuf) -> Self {
+    pub fn new(watch_id: u32, log_path: PathBuf) -> Self {
         let (tx, rx) = mpsc::channel(1000); // Buffer up to 1000 messages
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
@@ -36,6 +41,7 @@ impl BufferedWatchLogger {
         });
 
         Self {
+            watch_id,
             tx,
             shutdown_tx: Some(shutdown_tx),
         }
@@ -52,6 +58,12 @@ impl BufferedWatchLogger {
             data,
             timestamp: chrono::Local::now(),
         };
+        manager::record_event(
+            self.watch_id,
+            &entry.update_type,
+            &entry.data,
+            entry.timestamp,
+        );
 
         self.tx
             .send(entry)
@@ -153,15 +165,53 @@ async fn write_task(
     Ok(())
 }
 
-/// Get the log file path for a watch (same as before)
-pub fn get_watch_log_path(watch_id: u32, entity_id: u64, watch_type: &str) -> PathBuf {
+/// Append one entry to a log file outside of its logger, e.g. once the watch task was stopped
+pub async fn append_entry(
+    log_path: &Path,
+    update_type: &str,
+    data: &serde_json::Value,
+    timestamp: chrono::DateTime<chrono::Local>,
+) {
+    let line = format!(
+        "[{}] {}: {}\n",
+        timestamp.format("%Y-%m-%d %H:%M:%S%.3f"),
+        update_type,
+        data
+    );
+    let written = async {
+        let mut file = OpenOptions::new().append(true).open(log_path).await?;
+        file.write_all(line.as_bytes()).await?;
+        // Tokio writes in the background, so flush before the file is dropped
+        file.flush().await
+    }
+    .await;
+    if let Err(e) = written {
+        error!(
+            "Failed to append to watch log {}: {}",
+            log_path.display(),
+            e
+        );
+    }
+}
+
+/// Get the log file path for a watch
+pub fn get_watch_log_path(watch_id: u32, target: &WatchTarget, watch_type: &str) -> PathBuf {
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
-
-    let filename =
-        format!("bevy_brp_mcp_watch_{watch_id}_{watch_type}_{entity_id}_{timestamp}.log");
-
-    std::env::temp_dir().join(filename)
+        .map_or(0, |d| d.as_secs());
+
+    // Resources are named by their short type name, e.g. `Time` for `bevy_time::time::Time`
+    let label = match target {
+        WatchTarget::Entity(entity_id) => entity_id.to_string(),
+        WatchTarget::Resource(resource) => resource
+            .rsplit("::")
+            .next()
+            .unwrap_or(resource)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect(),
+    };
+    let filename = format!("bevy_brp_mcp_watch_{watch_id}_{watch_type}_{label}_{timestamp}.log");
+
+    create_log_directory().join(filename)
 }
@@ -1,28 +1,151 @@
 //! Watch manager for coordinating file-based watch logging
+//!
+//! Besides the log file, every event a watch logs is kept in a per-watch ring buffer so recent
+//! history can be queried with `brp_read_watch_history`, including after the watch has ended.
+//! Active watches also keep counts of the updates and errors they logged.
+//!
+//! Watches whose app went away are cleaned up every `watch_liveness_interval_ms` and whenever
+//! the active watches are listed: the app of every watch is probed once per host and port, and
+//! watches whose app refuses the connection are stopped with a final `WATCH_ENDED` log entry, as
+//! are watches whose task already ended. With `watch_notifications` the client that started a
+//! watch is sent a log notification for each, and for the alerts of performance watches.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
 use std::path::PathBuf;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 
+use chrono::{DateTime, Local};
+use futures::future::join_all;
+use rmcp::RoleServer;
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::service::Peer;
+use serde_json::{Value, json};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
-use tracing::info;
+use tracing::{info, warn};
 
+use super::logger;
+use crate::brp_tools::support::brp_client::{self, execute_brp_method};
+use crate::brp_tools::support::request_policy::{self, RequestPolicy};
+use crate::config;
 use crate::error::{Error, Result};
+use crate::tools::BRP_METHOD_RPC_DISCOVER;
 
 /// Global watch manager instance
 pub static WATCH_MANAGER: std::sync::LazyLock<Arc<Mutex<WatchManager>>> =
     std::sync::LazyLock::new(|| Arc::new(Mutex::new(WatchManager::new())));
 
+/// Number of events kept per watch
+pub const HISTORY_CAPACITY: usize = 500;
+
+/// Number of watches whose history is kept, oldest watches are forgotten first
+const MAX_RECORDED_WATCHES: usize = 32;
+
+/// Event history of every recent watch
+///
+/// Kept apart from the manager, whose async lock is held while a watch logs its first entry.
+static WATCH_HISTORY: LazyLock<std::sync::Mutex<BTreeMap<u32, WatchHistory>>> =
+    LazyLock::new(|| std::sync::Mutex::new(BTreeMap::new()));
+
+/// Activity of every active watch, kept apart from the manager like the history
+static WATCH_STATS: LazyLock<std::sync::Mutex<HashMap<u32, WatchStats>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+/// Watches stopped by cleanup, oldest first
+static ENDED_WATCHES: LazyLock<std::sync::Mutex<VecDeque<DeadWatch>>> =
+    LazyLock::new(|| std::sync::Mutex::new(VecDeque::new()));
+
+tokio::task_local! {
+    /// Client of the tool call being handled, and of the watches it starts
+    static CALLING_PEER: Option<Peer<RoleServer>>;
+}
+
+/// Time an app has to answer the probe of watch cleanup
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Interval at which the apps of active watches are probed, unless configured
+const DEFAULT_LIVENESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Interval at which the configuration is checked again while probing is disabled
+const DISABLED_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Name of the logger of watch notifications
+const NOTIFICATION_LOGGER: &str = "bevy_brp_mcp::watch";
+
+/// What a watch observes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchTarget {
+    /// Components of an entity, streamed by the app
+    Entity(u64),
+    /// A resource, polled at an interval
+    Resource(String),
+}
+
+impl WatchTarget {
+    /// Log field identifying the target
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Entity(entity_id) => serde_json::json!({ "entity": entity_id }),
+            Self::Resource(resource) => serde_json::json!({ "resource": resource }),
+        }
+    }
+}
+
+impl fmt::Display for WatchTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entity(entity_id) => write!(f, "entity {entity_id}"),
+            Self::Resource(resource) => write!(f, "resource {resource}"),
+        }
+    }
+}
+
 /// Information about an active watch
 #[derive(Debug, Clone)]
 pub struct WatchInfo {
     pub watch_id:   u32,
-    pub entity_id:  u64,
+    pub target:     WatchTarget,
     pub watch_type: String,
+    /// Label shared by watches started together, such as by `brp_watch_resources`
+    pub label:      Option<String>,
     pub log_path:   PathBuf,
+    pub host:       String,
     pub port:       u16,
+    /// Client that started the watch, sent its notifications
+    pub peer:       Option<Peer<RoleServer>>,
+}
+
+/// What an active watch has logged so far
+#[derive(Debug, Clone)]
+pub struct WatchStats {
+    pub started_at:  DateTime<Local>,
+    /// Component and resource updates logged
+    pub updates:     u64,
+    /// Poll and connection errors logged
+    pub errors:      u64,
+    pub last_update: Option<DateTime<Local>>,
+}
+
+impl WatchStats {
+    fn record(&mut self, update_type: &str, timestamp: DateTime<Local>) {
+        if update_type.ends_with("_UPDATE") {
+            self.updates += 1;
+            self.last_update = Some(timestamp);
+        } else if update_type.ends_with("_ERROR") {
+            self.errors += 1;
+        }
+    }
+}
+
+/// A watch removed by [`cleanup_dead_watches`], and why
+#[derive(Debug, Clone)]
+pub struct DeadWatch {
+    pub info:     WatchInfo,
+    pub reason:   String,
+    pub ended_at: DateTime<Local>,
 }
 
 /// Manager for watch subscriptions
@@ -47,10 +170,33 @@ impl WatchManager {
         self.next_watch_id.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Remove a watch from the active watches, forgetting its stats
+    pub fn remove(&mut self, watch_id: u32) -> Option<(WatchInfo, JoinHandle<()>)> {
+        if let Ok(mut stats) = WATCH_STATS.lock() {
+            stats.remove(&watch_id);
+        }
+        self.active_watches.remove(&watch_id)
+    }
+
+    /// Remove the watches whose task already ended without removing them, e.g. by panicking
+    fn remove_finished(&mut self) -> Vec<WatchInfo> {
+        let finished: Vec<WatchInfo> = self
+            .active_watches
+            .extract_if(|_, (_, handle)| handle.is_finished())
+            .map(|(_, (info, _))| info)
+            .collect();
+        if let Ok(mut stats) = WATCH_STATS.lock() {
+            for info in &finished {
+                stats.remove(&info.watch_id);
+            }
+        }
+        finished
+    }
+
     /// Stop a watch by ID
     pub fn stop_watch(&mut self, watch_id: u32) -> Result<()> {
-        if let Some((info, handle)) = self.active_watches.remove(&watch_id) {
-            info!("Stopping watch {} for entity {}", watch_id, info.entity_id);
+        if let Some((info, handle)) = self.remove(watch_id) {
+            info!("Stopping watch {} for {}", watch_id, info.target);
             handle.abort();
             Ok(())
         } else {
@@ -60,6 +206,24 @@ impl WatchManager {
         }
     }
 
+    /// Stop every watch with a label, returning their IDs in ascending order
+    pub fn stop_label(&mut self, label: &str) -> Vec<u32> {
+        let mut watch_ids: Vec<u32> = self
+            .active_watches
+            .iter()
+            .filter(|(_, (info, _))| info.label.as_deref() == Some(label))
+            .map(|(watch_id, _)| *watch_id)
+            .collect();
+        watch_ids.sort_unstable();
+        for watch_id in &watch_ids {
+            if let Some((info, handle)) = self.remove(*watch_id) {
+                info!("Stopping watch {} for {}", watch_id, info.target);
+                handle.abort();
+            }
+        }
+        watch_ids
+    }
+
     /// List all active watches
     pub fn list_active_watches(&self) -> Vec<WatchInfo> {
         self.active_watches
@@ -69,9 +233,405 @@ impl WatchManager {
     }
 }
 
-/// Initialize the watch manager
+/// Initialize the watch manager and start probing the apps of active watches
 pub async fn initialize_watch_manager() {
     info!("Watch manager initialized");
     // Force initialization of the lazy static
     let _ = WATCH_MANAGER.lock().await;
+    tokio::spawn(probe_liveness());
+}
+
+/// Interval at which the apps of active watches are probed, `None` when disabled
+pub fn liveness_interval() -> Option<Duration> {
+    match config::current().watch_liveness_interval_ms {
+        Some(0) => None,
+        Some(interval_ms) => Some(Duration::from_millis(interval_ms)),
+        None => Some(DEFAULT_LIVENESS_INTERVAL),
+    }
+}
+
+/// Whether the client is notified of watches stopped by cleanup
+pub fn notifications_enabled() -> bool {
+    config::current().watch_notifications.unwrap_or(false)
+}
+
+/// Run a future with the watches it starts notifying `peer`
+///
+/// Over SSE every session connects its own client, so each watch notifies the session that
+/// started it.
+pub async fn with_calling_peer<F: Future>(peer: Option<Peer<RoleServer>>, future: F) -> F::Output {
+    CALLING_PEER.scope(peer, future).await
+}
+
+/// Client of the current tool call, or of the watch whose task is running
+pub fn calling_peer() -> Option<Peer<RoleServer>> {
+    CALLING_PEER.try_with(Clone::clone).ok().flatten()
+}
+
+/// Clean up dead watches at the configured interval, for as long as the server runs
+async fn probe_liveness() {
+    loop {
+        let Some(interval) = liveness_interval() else {
+            tokio::time::sleep(DISABLED_RECHECK_INTERVAL).await;
+            continue;
+        };
+        tokio::time::sleep(interval).await;
+        let any_active = !WATCH_MANAGER.lock().await.active_watches.is_empty();
+        if any_active {
+            cleanup_dead_watches().await;
+        }
+    }
+}
+
+/// An event logged by a watch
+#[derive(Debug, Clone)]
+pub struct WatchEvent {
+    pub watch_id:    u32,
+    pub update_type: String,
+    pub timestamp:   DateTime<Local>,
+    pub data:        Value,
+}
+
+/// The most recent events of one watch
+#[derive(Debug, Clone)]
+pub struct WatchHistory {
+    pub target: WatchTarget,
+    pub events: VecDeque<WatchEvent>,
+}
+
+impl WatchEvent {
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "watch_id": self.watch_id,
+            "update_type": self.update_type,
+            "timestamp": self.timestamp.to_rfc3339(),
+            "data": self.data,
+        })
+    }
+}
+
+impl WatchHistory {
+    fn push(&mut self, event: WatchEvent) {
+        if self.events.len() >= HISTORY_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
+
+/// Start keeping the history and stats of a new watch
+pub fn register_history(watch_id: u32, target: WatchTarget) {
+    if let Ok(mut stats) = WATCH_STATS.lock() {
+        stats.insert(
+            watch_id,
+            WatchStats {
+                started_at:  Local::now(),
+                updates:     0,
+                errors:      0,
+                last_update: None,
+            },
+        );
+    }
+    if let Ok(mut histories) = WATCH_HISTORY.lock() {
+        while histories.len() >= MAX_RECORDED_WATCHES {
+            histories.pop_first();
+        }
+        histories.insert(
+            watch_id,
+            WatchHistory {
+                target,
+                events: VecDeque::new(),
+            },
+        );
+    }
+}
+
+/// Record an event in the history and stats of a watch
+pub fn record_event(watch_id: u32, update_type: &str, data: &Value, timestamp: DateTime<Local>) {
+    if let Some(stats) = WATCH_STATS
+        .lock()
+        .as_mut()
+        .ok()
+        .and_then(|stats| stats.get_mut(&watch_id))
+    {
+        stats.record(update_type, timestamp);
+    }
+    let Ok(mut histories) = WATCH_HISTORY.lock() else {
+        return;
+    };
+    if let Some(history) = histories.get_mut(&watch_id) {
+        history.push(WatchEvent {
+            watch_id,
+            update_type: update_type.to_string(),
+            timestamp,
+            data: data.clone(),
+        });
+    }
+}
+
+/// The recorded histories, by watch ID
+pub fn histories() -> BTreeMap<u32, WatchHistory> {
+    WATCH_HISTORY
+        .lock()
+        .map(|histories| histories.clone())
+        .unwrap_or_default()
+}
+
+/// The stats of an active watch
+pub fn stats(watch_id: u32) -> Option<WatchStats> {
+    WATCH_STATS.lock().ok()?.get(&watch_id).cloned()
+}
+
+/// Stop the watches whose task ended or whose app went away, returning them
+///
+/// An app has gone away when it refuses the connection; one that is slow to answer is left alone.
+pub async fn cleanup_dead_watches() -> Vec<DeadWatch> {
+    let (mut dead, mut targets) = {
+        let mut manager = WATCH_MANAGER.lock().await;
+        let dead: Vec<DeadWatch> = manager
+            .remove_finished()
+            .into_iter()
+            .map(|info| DeadWatch {
+                info,
+                reason: "watch task ended".to_string(),
+                ended_at: Local::now(),
+            })
+            .collect();
+        let targets: Vec<(String, u16)> = manager
+            .active_watches
+            .values()
+            .map(|(info, _)| (info.host.clone(), info.port))
+            .collect();
+        drop(manager);
+        (dead, targets)
+    };
+    targets.sort_unstable();
+    targets.dedup();
+
+    let probes = join_all(targets.iter().map(|(host, port)| app_gone(host, *port))).await;
+    let gone: Vec<&(String, u16)> = targets
+        .iter()
+        .zip(probes)
+        .filter_map(|(target, gone)| gone.then_some(target))
+        .collect();
+    if !gone.is_empty() {
+        let mut manager = WATCH_MANAGER.lock().await;
+        let watch_ids: Vec<u32> = manager
+            .active_watches
+            .iter()
+            .filter(|(_, (info, _))| gone.contains(&&(info.host.clone(), info.port)))
+            .map(|(watch_id, _)| *watch_id)
+            .collect();
+        for watch_id in watch_ids {
+            if let Some((info, handle)) = manager.remove(watch_id) {
+                handle.abort();
+                let reason = format!("app on {}:{} went away", info.host, info.port);
+                end_log(&info, &reason).await;
+                dead.push(DeadWatch {
+                    info,
+                    reason,
+                    ended_at: Local::now(),
+                });
+            }
+        }
+    }
+
+    dead.sort_by_key(|watch| watch.info.watch_id);
+    for watch in &dead {
+        info!(
+            "Cleaned up watch {} for {}: {}",
+            watch.info.watch_id, watch.info.target, watch.reason
+        );
+        notify(watch).await;
+    }
+    if let Ok(mut ended) = ENDED_WATCHES.lock() {
+        ended.extend(dead.iter().cloned());
+        let excess = ended.len().saturating_sub(MAX_RECORDED_WATCHES);
+        ended.drain(..excess);
+    }
+    dead
+}
+
+/// The watches stopped by cleanup recently, oldest first
+pub fn ended_watches() -> Vec<DeadWatch> {
+    ENDED_WATCHES
+        .lock()
+        .map(|ended| ended.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Tell the client a watch was stopped by cleanup, if enabled
+async fn notify(watch: &DeadWatch) {
+    let mut data = watch.info.target.to_json();
+    data["event"] = json!("watch_ended");
+    data["watch_id"] = json!(watch.info.watch_id);
+    data["reason"] = json!(watch.reason);
+    data["log_path"] = json!(watch.info.log_path.to_string_lossy());
+    send_notification(watch.info.peer.as_ref(), LoggingLevel::Warning, data).await;
+}
+
+/// Send a watch event to the client of the running watch as a log message, if watch
+/// notifications are enabled
+pub async fn notify_client(level: LoggingLevel, data: Value) {
+    send_notification(calling_peer().as_ref(), level, data).await;
+}
+
+async fn send_notification(peer: Option<&Peer<RoleServer>>, level: LoggingLevel, data: Value) {
+    if !notifications_enabled() {
+        return;
+    }
+    let Some(peer) = peer else {
+        return;
+    };
+    let notification = LoggingMessageNotificationParam {
+        level,
+        logger: Some(NOTIFICATION_LOGGER.to_string()),
+        data,
+    };
+    if let Err(e) = peer.notify_logging_message(notification).await {
+        warn!("Failed to send a watch notification to the client: {}", e);
+    }
+}
+
+/// Whether the app on a host and port refuses connections
+async fn app_gone(host: &str, port: u16) -> bool {
+    let policy = RequestPolicy {
+        timeout: PROBE_TIMEOUT,
+        ..RequestPolicy::for_method(BRP_METHOD_RPC_DISCOVER)
+    };
+    let probe = execute_brp_method(BRP_METHOD_RPC_DISCOVER, None, Some(port));
+    let outcome =
+        request_policy::scope(policy, brp_client::with_host(host.to_string(), probe)).await;
+    outcome.is_err_and(|report| matches!(report.current_context(), Error::ConnectionRefused(_)))
+}
+
+/// Log the end of a watch whose task was stopped before it could
+async fn end_log(info: &WatchInfo, reason: &str) {
+    let mut log_data = info.target.to_json();
+    log_data["reason"] = json!(reason);
+    let timestamp = Local::now();
+    log_data["timestamp"] = json!(timestamp.to_rfc3339());
+    record_event(info.watch_id, "WATCH_ENDED", &log_data, timestamp);
+    logger::append_entry(&info.log_path, "WATCH_ENDED", &log_data, timestamp).await;
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_stats_count_updates_and_errors() {
+        let watch_id = 900_001;
+        register_history(watch_id, WatchTarget::Entity(42));
+        let now = Local::now();
+        record_event(watch_id, "WATCH_STARTED", &json!({}), now);
+        record_event(watch_id, "COMPONENT_UPDATE", &json!({}), now);
+        record_event(watch_id, "POLL_ERROR", &json!({}), now);
+        record_event(watch_id, "COMPONENT_UPDATE", &json!({}), now);
+
+        let stats = stats(watch_id).unwrap();
+        assert_eq!((stats.updates, stats.errors), (2, 1));
+        assert_eq!(stats.last_update, Some(now));
+
+        WatchManager::new().remove(watch_id);
+        assert!(super::stats(watch_id).is_none());
+    }
+    #[test]
+    fn test_liveness_interval_defaults_to_five_seconds_and_zero_disables_it() {
+        let original = config::current();
+        config::replace(config::ServerConfig {
+            watch_liveness_interval_ms: None,
+            ..original.clone()
+        });
+        assert_eq!(liveness_interval(), Some(Duration::from_secs(5)));
+        config::replace(config::ServerConfig {
+            watch_liveness_interval_ms: Some(0),
+            ..original.clone()
+        });
+        assert_eq!(liveness_interval(), None);
+        config::replace(config::ServerConfig {
+            watch_liveness_interval_ms: Some(250),
+            ..original.clone()
+        });
+        assert_eq!(liveness_interval(), Some(Duration::from_millis(250)));
+        config::replace(original);
+    }
+
+    fn watch_info(watch_id: u32, port: u16) -> WatchInfo {
+        WatchInfo {
+            watch_id,
+            target: WatchTarget::Entity(u64::from(watch_id)),
+            watch_type: "get".to_string(),
+            label: None,
+            log_path: std::env::temp_dir().join(format!("bevy_brp_mcp_watch_{watch_id}.log")),
+            host: "127.0.0.1".to_string(),
+            port,
+            peer: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_stops_watches_of_refusing_apps_and_caps_ended_watches() {
+        // A port nothing listens on once the listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let mut finished = Vec::new();
+        for watch_id in 910_000..910_040 {
+            let handle = tokio::spawn(async {});
+            while !handle.is_finished() {
+                tokio::task::yield_now().await;
+            }
+            finished.push((watch_info(watch_id, port), handle));
+        }
+        let refused_id = 910_100;
+        let refused_log = watch_info(refused_id, port).log_path;
+        std::fs::write(&refused_log, "").unwrap();
+        {
+            let mut manager = WATCH_MANAGER.lock().await;
+            for (info, handle) in finished {
+                manager.active_watches.insert(info.watch_id, (info, handle));
+            }
+            manager.active_watches.insert(
+                refused_id,
+                (
+                    watch_info(refused_id, port),
+                    tokio::spawn(std::future::pending()),
+                ),
+            );
+        }
+
+        let dead = cleanup_dead_watches().await;
+        assert_eq!(dead.len(), 41);
+        let refused = dead
+            .iter()
+            .find(|watch| watch.info.watch_id == refused_id)
+            .unwrap();
+        assert_eq!(refused.reason, format!("app on 127.0.0.1:{port} went away"));
+        assert!(
+            !WATCH_MANAGER
+                .lock()
+                .await
+                .active_watches
+                .contains_key(&refused_id)
+        );
+
+        let ended = ended_watches();
+        assert_eq!(ended.len(), MAX_RECORDED_WATCHES);
+        assert_eq!(
+            ended.last().map(|watch| watch.info.watch_id),
+            Some(refused_id)
+        );
+        assert!(
+            std::fs::read_to_string(&refused_log)
+                .unwrap()
+                .contains("WATCH_ENDED")
+        );
+        std::fs::remove_file(&refused_log).unwrap();
+    }
 }
@@ -1,9 +1,14 @@
 // Watch support modules
 
+mod coalesce;
+mod diff;
 mod logger;
 pub mod manager;
+mod perf;
 mod response;
 mod task;
 
+pub use coalesce::Coalescing;
+pub use perf::{PerfWatch, Threshold, start_perf_watch_task};
 pub use response::{format_watch_start_response, format_watch_stop_response};
-pub use task::{start_entity_watch_task, start_list_watch_task};
+pub use task::{start_entity_watch_task, start_list_watch_task, start_resource_watch_task};
@@ -0,0 +1,334 @@
+//! Performance watches: diagnostics sampled at an interval, with threshold alerts
+//!
+//! Every poll logs a `PERF_UPDATE` with the latest value of each watched diagnostic. A threshold
+//! such as "fps below 30 for 5 seconds" logs a `PERF_ALERT` once the value has stayed past it for
+//! the whole duration, and a `PERF_RECOVERED` when it comes back; alerts are also sent to the
+//! client with `watch_notifications`.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rmcp::model::LoggingLevel;
+use serde::Serialize;
+use serde_json::{Value, json};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{info, warn};
+
+use super::logger::BufferedWatchLogger;
+use super::manager::{self, WatchTarget};
+use super::task::{MAX_POLL_FAILURES, finish_watch, log_update, start_watch_task};
+use crate::brp_tools::bevy_get_diagnostics::read_diagnostics;
+use crate::brp_tools::constants::JSON_FIELD_RESOURCE;
+use crate::brp_tools::support::brp_client::{self, BrpResult};
+use crate::error::{Error, Result};
+use crate::tools::BRP_METHOD_GET_RESOURCE;
+
+/// A limit on a diagnostic, breached while the value is below or above it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Threshold {
+    pub diagnostic: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub below:      Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub above:      Option<f64>,
+    /// How long the value must stay past the limit before alerting
+    #[serde(rename = "for_ms", serialize_with = "serialize_millis")]
+    pub sustain:    Duration,
+}
+
+fn serialize_millis<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_millis())
+}
+
+impl Threshold {
+    /// A threshold from `{"diagnostic", "below" and/or "above", "for_ms"}`
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let invalid = |details: &str| {
+            error_stack::Report::new(Error::invalid("parameter 'thresholds'", details))
+        };
+        let diagnostic = value
+            .get("diagnostic")
+            .and_then(Value::as_str)
+            .filter(|diagnostic| !diagnostic.is_empty())
+            .ok_or_else(|| invalid("each threshold needs a diagnostic, e.g. \"fps\""))?;
+        let below = value.get("below").and_then(Value::as_f64);
+        let above = value.get("above").and_then(Value::as_f64);
+        if below.is_none() && above.is_none() {
+            return Err(invalid(&format!(
+                "threshold on {diagnostic} needs a number for below or above"
+            )));
+        }
+        let sustain = value.get("for_ms").and_then(Value::as_u64).unwrap_or(0);
+        Ok(Self {
+            diagnostic: diagnostic.to_string(),
+            below,
+            above,
+            sustain: Duration::from_millis(sustain),
+        })
+    }
+
+    fn breached(&self, value: f64) -> bool {
+        self.below.is_some_and(|below| value < below)
+            || self.above.is_some_and(|above| value > above)
+    }
+
+    /// The condition in words, e.g. `fps < 30 for 5000ms`
+    fn condition(&self) -> String {
+        let limits: Vec<String> = [
+            self.below
+                .map(|below| format!("{} < {below}", self.diagnostic)),
+            self.above
+                .map(|above| format!("{} > {above}", self.diagnostic)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let limits = limits.join(" or ");
+        if self.sustain.is_zero() {
+            limits
+        } else {
+            format!("{limits} for {}ms", self.sustain.as_millis())
+        }
+    }
+}
+
+/// A change of a threshold's alert
+#[derive(Debug, PartialEq)]
+enum Transition {
+    Alert,
+    Recovered,
+}
+
+/// Whether a threshold is breached and alerting
+struct ThresholdState {
+    threshold:      Threshold,
+    breached_since: Option<Instant>,
+    alerting:       bool,
+}
+
+impl ThresholdState {
+    const fn new(threshold: Threshold) -> Self {
+        Self {
+            threshold,
+            breached_since: None,
+            alerting: false,
+        }
+    }
+
+    /// Take the latest value, returning a change of the alert; missing values change nothing
+    fn check(&mut self, value: Option<f64>, now: Instant) -> Option<Transition> {
+        let value = value?;
+        if !self.threshold.breached(value) {
+            self.breached_since = None;
+            return std::mem::take(&mut self.alerting).then_some(Transition::Recovered);
+        }
+        let since = *self.breached_since.get_or_insert(now);
+        if self.alerting || now.duration_since(since) < self.threshold.sustain {
+            return None;
+        }
+        self.alerting = true;
+        Some(Transition::Alert)
+    }
+}
+
+/// What a performance watch samples
+pub struct PerfWatch {
+    /// Resource holding the diagnostics
+    pub resource:    String,
+    /// Diagnostics logged on every poll; threshold diagnostics are always included
+    pub diagnostics: Vec<String>,
+    pub thresholds:  Vec<Threshold>,
+    pub interval:    Duration,
+}
+
+/// Start a background task sampling diagnostics and checking thresholds
+///
+/// The resource is read once up front so that an app without readable diagnostics fails here
+/// instead of only showing up in the log.
+pub async fn start_perf_watch_task(
+    watch: PerfWatch,
+    label: Option<&str>,
+    port: u16,
+) -> Result<(u32, PathBuf)> {
+    let params = json!({ JSON_FIELD_RESOURCE: watch.resource });
+    if let BrpResult::Error(e) =
+        brp_client::execute_brp_method(BRP_METHOD_GET_RESOURCE, Some(params.clone()), Some(port))
+            .await?
+    {
+        return Err(error_stack::Report::new(Error::WatchOperation(format!(
+            "Failed to read diagnostics from {}: {}",
+            watch.resource, e.message
+        ))));
+    }
+
+    let mut log_params = params;
+    log_params["interval_ms"] = json!(watch.interval.as_millis());
+    log_params["diagnostics"] = json!(watch.diagnostics);
+    log_params["thresholds"] = json!(watch.thresholds);
+
+    start_watch_task(
+        WatchTarget::Resource(watch.resource.clone()),
+        "perf",
+        label,
+        log_params,
+        port,
+        move |watch_id, logger| run_perf_poll(watch_id, watch, port, logger),
+    )
+    .await
+}
+
+/// Poll the diagnostics at the watch's interval, logging samples and alerts
+async fn run_perf_poll(watch_id: u32, watch: PerfWatch, port: u16, logger: BufferedWatchLogger) {
+    info!(
+        "Starting performance watch task for {} on port {} every {}ms",
+        watch.resource,
+        port,
+        watch.interval.as_millis()
+    );
+
+    let params = json!({ JSON_FIELD_RESOURCE: watch.resource });
+    let mut thresholds: Vec<ThresholdState> = watch
+        .thresholds
+        .iter()
+        .cloned()
+        .map(ThresholdState::new)
+        .collect();
+    let mut ticker = tokio::time::interval(watch.interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut failures = 0;
+
+    while failures < MAX_POLL_FAILURES {
+        ticker.tick().await;
+        let error = match brp_client::execute_brp_method(
+            BRP_METHOD_GET_RESOURCE,
+            Some(params.clone()),
+            Some(port),
+        )
+        .await
+        {
+            Ok(BrpResult::Success(data)) => {
+                failures = 0;
+                let value = data.map_or(Value::Null, |data| {
+                    data.get("value").cloned().unwrap_or(data)
+                });
+                if log_sample(watch_id, &watch, &mut thresholds, &value, &logger)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+                continue;
+            }
+            Ok(BrpResult::Error(e)) => e.message,
+            Err(e) => e.to_string(),
+        };
+
+        failures += 1;
+        warn!("Polling diagnostics {} failed: {}", watch.resource, error);
+        let _ = logger
+            .write_update(
+                "POLL_ERROR",
+                json!({
+                    "error": error,
+                    "consecutive_failures": failures,
+                    "timestamp": chrono::Local::now().to_rfc3339()
+                }),
+            )
+            .await;
+    }
+
+    finish_watch(watch_id, &WatchTarget::Resource(watch.resource), &logger).await;
+}
+
+/// Log the watched diagnostics of one poll and any alert changes
+async fn log_sample(
+    watch_id: u32,
+    watch: &PerfWatch,
+    thresholds: &mut [ThresholdState],
+    value: &Value,
+    logger: &BufferedWatchLogger,
+) -> Result<()> {
+    let readings = read_diagnostics(value);
+    let latest = |path: &str| {
+        readings
+            .iter()
+            .find(|reading| reading.path == path)
+            .and_then(|reading| reading.value)
+    };
+    let timestamp = chrono::Local::now().to_rfc3339();
+
+    let diagnostics: serde_json::Map<String, Value> = watch
+        .diagnostics
+        .iter()
+        .map(|path| (path.clone(), json!(latest(path))))
+        .collect();
+    log_update(
+        logger,
+        "PERF_UPDATE",
+        json!({ "diagnostics": diagnostics, "timestamp": timestamp }),
+    )
+    .await?;
+
+    let now = Instant::now();
+    for state in thresholds {
+        let value = latest(&state.threshold.diagnostic);
+        let Some(transition) = state.check(value, now) else {
+            continue;
+        };
+        let (update_type, level) = match transition {
+            Transition::Alert => ("PERF_ALERT", LoggingLevel::Warning),
+            Transition::Recovered => ("PERF_RECOVERED", LoggingLevel::Info),
+        };
+        let data = json!({
+            "diagnostic": state.threshold.diagnostic,
+            "value": value,
+            "condition": state.threshold.condition(),
+            "timestamp": timestamp,
+        });
+        log_update(logger, update_type, data.clone()).await?;
+
+        let mut notification = data;
+        notification["event"] = json!(update_type.to_lowercase());
+        notification["watch_id"] = json!(watch_id);
+        manager::notify_client(level, notification).await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_threshold_alerts_after_sustained_breach() {
+        let threshold =
+            Threshold::from_json(&json!({ "diagnostic": "fps", "below": 30, "for_ms": 5000 }))
+                .unwrap();
+        assert_eq!(threshold.condition(), "fps < 30 for 5000ms");
+
+        let start = Instant::now();
+        let at = |seconds: u64| start + Duration::from_secs(seconds);
+        let mut state = ThresholdState::new(threshold);
+        assert_eq!(state.check(Some(25.0), at(0)), None);
+        assert_eq!(state.check(Some(24.0), at(3)), None);
+        assert_eq!(state.check(None, at(4)), None);
+        assert_eq!(state.check(Some(20.0), at(5)), Some(Transition::Alert));
+        assert_eq!(state.check(Some(20.0), at(6)), None);
+        assert_eq!(state.check(Some(60.0), at(7)), Some(Transition::Recovered));
+        assert_eq!(state.check(Some(25.0), at(8)), None);
+        assert_eq!(state.check(Some(60.0), at(9)), None);
+
+        let mut spikes = ThresholdState::new(
+            Threshold::from_json(&json!({ "diagnostic": "frame_time", "above": 50 })).unwrap(),
+        );
+        assert_eq!(spikes.check(Some(80.0), at(0)), Some(Transition::Alert));
+
+        assert!(Threshold::from_json(&json!({ "diagnostic": "fps" })).is_err());
+        assert!(Threshold::from_json(&json!({ "below": 30 })).is_err());
+    }
+}
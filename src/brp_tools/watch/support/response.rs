@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use rmcp::model::CallToolResult;
 
+use super::manager::WatchTarget;
 use crate::brp_tools::constants::{JSON_FIELD_LOG_PATH, JSON_FIELD_WATCH_ID};
 use crate::error::{Error, Result};
 use crate::support::response::ResponseBuilder;
@@ -10,11 +11,11 @@ use crate::support::serialization::json_response_to_result;
 pub fn format_watch_start_response(
     result: std::result::Result<(u32, PathBuf), Error>,
     operation_name: &str,
-    entity_id: u64,
+    target: &WatchTarget,
 ) -> CallToolResult {
     match result {
         Ok((watch_id, log_path)) => {
-            build_watch_start_success_response(operation_name, entity_id, watch_id, &log_path)
+            build_watch_start_success_response(operation_name, target, watch_id, &log_path)
                 .map_or_else(
                     |_| {
                         let fallback_response = ResponseBuilder::error()
@@ -30,8 +31,7 @@ pub fn format_watch_start_response(
                 )
         }
         Err(e) => {
-            let response = ResponseBuilder::error()
-                .message(e.to_string())
+            let response = ResponseBuilder::from_error(&e)
                 .auto_inject_debug_info(None::<&serde_json::Value>, None::<&serde_json::Value>)
                 .build();
             json_response_to_result(&response)
@@ -41,14 +41,12 @@ pub fn format_watch_start_response(
 
 fn build_watch_start_success_response(
     operation_name: &str,
-    entity_id: u64,
+    target: &WatchTarget,
     watch_id: u32,
     log_path: &Path,
 ) -> Result<crate::support::response::JsonResponse> {
     let response = ResponseBuilder::success()
-        .message(format!(
-            "Started {operation_name} {watch_id} for entity {entity_id}"
-        ))
+        .message(format!("Started {operation_name} {watch_id} for {target}"))
         .add_field(JSON_FIELD_WATCH_ID, watch_id)?
         .add_field(JSON_FIELD_LOG_PATH, log_path.to_string_lossy())?
         .auto_inject_debug_info(None::<&serde_json::Value>, None::<&serde_json::Value>)
@@ -69,8 +67,7 @@ pub fn format_watch_stop_response(
             json_response_to_result(&response)
         }
         Err(e) => {
-            let response = ResponseBuilder::error()
-                .message(e.to_string())
+            let response = ResponseBuilder::from_error(&e)
                 .auto_inject_debug_info(None::<&serde_json::Value>, None::<&serde_json::Value>)
                 .build();
             json_response_to_result(&response)
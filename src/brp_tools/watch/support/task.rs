@@ -1,48 +1,102 @@
 //! Background task management for watch connections
 
 use std::path::PathBuf;
+use std::time::Duration;
 
-use futures::StreamExt;
 use serde_json::Value;
+use tokio::time::{Instant, MissedTickBehavior};
 use tracing::{debug, error, info, warn};
 
-/// Maximum size for a single chunk in the SSE stream (1MB)
-const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+/// Consecutive failed polls after which a polled watch ends (e.g. the app has exited)
+pub(super) const MAX_POLL_FAILURES: u32 = 5;
 
-/// Maximum size for the total buffer when processing incomplete lines (10MB)
-const MAX_BUFFER_SIZE: usize = 10 * 1024 * 1024;
+/// Interval at which entity watches are polled when the app can't stream them
+const FALLBACK_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
+use super::coalesce::{Coalesced, Coalescer, Coalescing};
+use super::diff::WatchDiffer;
 use super::logger::{self as watch_logger, BufferedWatchLogger};
-use super::manager::{WATCH_MANAGER, WatchInfo};
-use crate::brp_tools::support::BrpJsonRpcBuilder;
+use super::manager::{WATCH_MANAGER, WatchInfo, WatchTarget};
+use crate::brp_tools::constants::JSON_FIELD_RESOURCE;
+use crate::brp_tools::support::brp_client::BrpResult;
+use crate::brp_tools::support::sse_client::{self, SseStream, WatchConnection};
+use crate::brp_tools::support::{brp_client, canonical_order, component_filter};
 use crate::error::{Error, Result};
-use crate::tools::{BRP_METHOD_GET_WATCH, BRP_METHOD_LIST_WATCH};
-
-/// Process a single SSE line and log the update if valid
-async fn parse_sse_line(line: &str, entity_id: u64, logger: &BufferedWatchLogger) -> Result<()> {
-    // Handle SSE format: "data: {json}"
-    if let Some(json_str) = line.strip_prefix("data: ") {
-        if let Ok(data) = serde_json::from_str::<Value>(json_str) {
-            debug!("Received watch update for entity {}: {:?}", entity_id, data);
-
-            // Extract the result from JSON-RPC response
-            if let Some(result) = data.get("result") {
-                log_update(logger, result.clone()).await?;
-            } else {
-                debug!("No result in JSON-RPC response: {:?}", data);
-            }
-        } else {
-            debug!("Failed to parse SSE data as JSON: {}", json_str);
+use crate::tools::{
+    BRP_METHOD_GET, BRP_METHOD_GET_RESOURCE, BRP_METHOD_GET_WATCH, BRP_METHOD_LIST,
+    BRP_METHOD_LIST_WATCH,
+};
+
+/// Turns streamed results into logged updates
+struct StreamUpdates {
+    watch_type: String,
+    differ:     WatchDiffer,
+    coalescer:  Coalescer,
+}
+
+impl StreamUpdates {
+    fn new(watch_type: &str, coalescing: Coalescing) -> Self {
+        Self {
+            watch_type: watch_type.to_string(),
+            differ:     WatchDiffer::new(watch_type),
+            coalescer:  Coalescer::new(coalescing),
         }
-    } else {
-        debug!("Received non-SSE line: {}", line);
     }
-    Ok(())
+
+    /// Take a streamed result, logging it once it's due
+    async fn receive(
+        &mut self,
+        result: Value,
+        entity_id: u64,
+        logger: &BufferedWatchLogger,
+    ) -> Result<()> {
+        match self.coalescer.push(result, Instant::now()) {
+            Some(coalesced) => self.log(coalesced, entity_id, logger).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Log the held back results if they're due
+    async fn flush_due(&mut self, entity_id: u64, logger: &BufferedWatchLogger) -> Result<()> {
+        match self.coalescer.take_due(Instant::now()) {
+            Some(coalesced) => self.log(coalesced, entity_id, logger).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Log the held back results, e.g. when the stream ends
+    async fn flush(&mut self, entity_id: u64, logger: &BufferedWatchLogger) -> Result<()> {
+        match self.coalescer.take_pending() {
+            Some(coalesced) => self.log(coalesced, entity_id, logger).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Log the fields that changed since the previous update
+    async fn log(
+        &mut self,
+        coalesced: Coalesced,
+        entity_id: u64,
+        logger: &BufferedWatchLogger,
+    ) -> Result<()> {
+        let Some(mut update) = self.differ.next_update(coalesced.result) else {
+            debug!("Watch update for entity {} had no changes", entity_id);
+            return Ok(());
+        };
+        if coalesced.count > 1 {
+            update["coalesced"] = serde_json::json!(coalesced.count);
+        }
+        log_update(logger, "COMPONENT_UPDATE", update).await
+    }
 }
 
 /// Log a watch update with error handling
-async fn log_update(logger: &BufferedWatchLogger, result: Value) -> Result<()> {
-    if let Err(e) = logger.write_update("COMPONENT_UPDATE", result).await {
+pub(super) async fn log_update(
+    logger: &BufferedWatchLogger,
+    update_type: &str,
+    result: Value,
+) -> Result<()> {
+    if let Err(e) = logger.write_update(update_type, result).await {
         error!("Failed to write watch update to log: {}", e);
         return Err(error_stack::Report::new(Error::failed_to(
             "write watch update to log",
@@ -52,153 +106,187 @@ async fn log_update(logger: &BufferedWatchLogger, result: Value) -> Result<()> {
     Ok(())
 }
 
-/// Process a single chunk from the stream
-async fn process_chunk(
-    bytes: &[u8],
-    line_buffer: &mut String,
-    total_buffer_size: &mut usize,
-    entity_id: u64,
-    logger: &BufferedWatchLogger,
-) -> Result<()> {
-    // Check chunk size limit
-    if bytes.len() > MAX_CHUNK_SIZE {
-        return Err(error_stack::Report::new(Error::InvalidState(format!(
-            "Stream chunk size {} exceeds maximum {}",
-            bytes.len(),
-            MAX_CHUNK_SIZE
-        ))));
+/// Sleep until the deadline, or forever without one
+async fn wait_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
     }
+}
 
-    // Convert bytes to string
-    let text = match std::str::from_utf8(bytes) {
-        Ok(text) => text,
-        Err(e) => {
-            debug!("Invalid UTF-8 in stream chunk: {}", e);
-            return Ok(());
-        }
-    };
-
-    // Add to line buffer and check total buffer size
-    line_buffer.push_str(text);
-    *total_buffer_size += text.len();
+/// Filter and order a watch result the way every other result is
+fn prepare_result(brp_method: &str, mut result: Value) -> Value {
+    component_filter::apply(brp_method, &mut result);
+    canonical_order::canonicalize(brp_method, &mut result);
+    result
+}
 
-    if *total_buffer_size > MAX_BUFFER_SIZE {
-        return Err(error_stack::Report::new(Error::InvalidState(format!(
-            "Stream buffer size {} exceeds maximum {}",
-            *total_buffer_size, MAX_BUFFER_SIZE
-        ))));
+/// Log the results streamed by the BRP server
+async fn process_watch_stream(
+    mut stream: SseStream,
+    brp_method: &str,
+    entity_id: u64,
+    logger: &BufferedWatchLogger,
+    updates: &mut StreamUpdates,
+) -> Result<()> {
+    loop {
+        // Held back results are logged when due even if nothing else arrives
+        let deadline = updates.coalescer.deadline();
+        let result = tokio::select! {
+            result = stream.next_result() => result?,
+            () = wait_until(deadline) => {
+                updates.flush_due(entity_id, logger).await?;
+                continue;
+            }
+        };
+        let Some(result) = result else {
+            break;
+        };
+        debug!(
+            "Received watch update for entity {}: {:?}",
+            entity_id, result
+        );
+        updates
+            .receive(prepare_result(brp_method, result), entity_id, logger)
+            .await?;
     }
+    updates.flush(entity_id, logger).await?;
 
-    // Process complete lines from the buffer
-    while let Some(newline_pos) = line_buffer.find('\n') {
-        let line = line_buffer.drain(..=newline_pos).collect::<String>();
-        let line = line.trim_end_matches('\n').trim_end_matches('\r');
-
-        // Update buffer size tracking
-        *total_buffer_size = line_buffer.len();
-
-        if line.trim().is_empty() {
-            continue;
-        }
+    info!("Watch stream ended for entity {}", entity_id);
+    Ok(())
+}
 
-        parse_sse_line(line, entity_id, logger).await?;
+/// The plain method polled in place of a `+watch` method the app can't stream
+fn polled_method(brp_method: &str) -> Option<&'static str> {
+    match brp_method {
+        BRP_METHOD_GET_WATCH => Some(BRP_METHOD_GET),
+        BRP_METHOD_LIST_WATCH => Some(BRP_METHOD_LIST),
+        _ => None,
     }
+}
 
-    Ok(())
+/// Turn a polled `bevy/list` result into the `added`/`removed` result `bevy/list+watch` streams
+fn list_changes(previous: Option<&Value>, current: &Value) -> Value {
+    let names = |value: Option<&Value>| -> Vec<Value> {
+        value.and_then(Value::as_array).cloned().unwrap_or_default()
+    };
+    let previous = names(previous);
+    let current = names(Some(current));
+    serde_json::json!({
+        "added": current.iter().filter(|name| !previous.contains(name)).collect::<Vec<_>>(),
+        "removed": previous.iter().filter(|name| !current.contains(name)).collect::<Vec<_>>(),
+    })
 }
 
-/// Process the watch stream from the BRP server
-async fn process_watch_stream(
-    response: reqwest::Response,
+/// Poll the plain method of a watch the app can't stream, logging the changes like a stream
+async fn poll_watch(
+    brp_method: &str,
+    params: Value,
     entity_id: u64,
+    port: u16,
     logger: &BufferedWatchLogger,
+    updates: &mut StreamUpdates,
 ) -> Result<()> {
-    if !response.status().is_success() {
-        let error_msg = format!(
-            "server returned {}: {}",
-            response.status(),
-            response.status().canonical_reason().unwrap_or("Unknown")
-        );
-        error!("Failed to process watch stream: {}", error_msg);
-        return Err(error_stack::Report::new(Error::BrpCommunication(format!(
-            "Failed to process watch stream: {error_msg}"
+    let Some(method) = polled_method(brp_method) else {
+        return Err(error_stack::Report::new(Error::WatchOperation(format!(
+            "{brp_method} has no method to poll instead"
         ))));
-    }
-
-    // Read the streaming response with bounded memory usage
-    let mut stream = response.bytes_stream();
-    let mut line_buffer = String::new();
-    let mut total_buffer_size = 0;
-
-    while let Some(chunk) = stream.next().await {
-        match chunk {
-            Ok(bytes) => {
-                process_chunk(
-                    &bytes,
-                    &mut line_buffer,
-                    &mut total_buffer_size,
-                    entity_id,
-                    logger,
-                )
-                .await?;
-            }
-            Err(e) => {
-                error!("Error reading stream chunk: {}", e);
-                break;
+    };
+    let mut ticker = tokio::time::interval(FALLBACK_POLL_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut previous: Option<Value> = None;
+    let mut failures = 0;
+
+    while failures < MAX_POLL_FAILURES {
+        // Held back results are logged when due even between polls
+        let deadline = updates.coalescer.deadline();
+        tokio::select! {
+            _ = ticker.tick() => {}
+            () = wait_until(deadline) => {
+                updates.flush_due(entity_id, logger).await?;
+                continue;
             }
         }
-    }
 
-    // Process any remaining incomplete line in the buffer
-    if !line_buffer.trim().is_empty() {
-        debug!(
-            "Processing remaining incomplete line: {}",
-            line_buffer.trim()
+        let error =
+            match brp_client::execute_brp_method(method, Some(params.clone()), Some(port)).await {
+                Ok(BrpResult::Success(data)) => {
+                    failures = 0;
+                    let current = prepare_result(brp_method, data.unwrap_or(Value::Null));
+                    if previous.as_ref() == Some(&current) {
+                        continue;
+                    }
+                    let result = if method == BRP_METHOD_LIST {
+                        list_changes(previous.as_ref(), &current)
+                    } else {
+                        current.clone()
+                    };
+                    previous = Some(current);
+                    updates.receive(result, entity_id, logger).await?;
+                    continue;
+                }
+                Ok(BrpResult::Error(e)) => e.message,
+                Err(e) => e.to_string(),
+            };
+
+        failures += 1;
+        warn!(
+            "Polling {} for entity {} failed: {}",
+            method, entity_id, error
         );
-        parse_sse_line(line_buffer.trim(), entity_id, logger).await?;
+        let _ = logger
+            .write_update(
+                "POLL_ERROR",
+                serde_json::json!({
+                    "error": error,
+                    "consecutive_failures": failures,
+                    "timestamp": chrono::Local::now().to_rfc3339()
+                }),
+            )
+            .await;
     }
-
-    info!("Watch stream ended for entity {}", entity_id);
-    Ok(())
+    updates.flush(entity_id, logger).await
 }
 
 /// Run the watch connection in a spawned task
+///
+/// The watch streams when the app offers the `+watch` method and falls back to polling the
+/// plain method when it doesn't.
 async fn run_watch_connection(
     watch_id: u32,
     entity_id: u64,
-    watch_type: String,
     brp_method: String,
     params: Value,
+    mut updates: StreamUpdates,
     port: u16,
     logger: BufferedWatchLogger,
 ) {
     info!(
         "Starting {} watch task for entity {} on port {}",
-        watch_type, entity_id, port
+        updates.watch_type, entity_id, port
     );
 
-    // Create HTTP client for streaming
-    let url = crate::brp_tools::support::brp_client::build_brp_url(port);
-    let client = crate::brp_tools::support::http_client::get_client();
-
-    // Build JSON-RPC request for watching
-    let request_body = BrpJsonRpcBuilder::new(&brp_method)
-        .params(params)
-        .build()
-        .to_string();
-
-    match client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .body(request_body)
-        // Don't set timeout for streaming connections
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if let Err(e) = process_watch_stream(response, entity_id, &logger).await {
-                error!("Watch stream processing failed: {}", e);
-            }
+    let outcome = match sse_client::open(&brp_method, params.clone(), port).await {
+        Ok(WatchConnection::Streaming(stream)) => {
+            process_watch_stream(stream, &brp_method, entity_id, &logger, &mut updates).await
+        }
+        Ok(WatchConnection::Unsupported(reason)) => {
+            info!(
+                "{} can't be streamed ({}), polling entity {} instead",
+                brp_method, reason, entity_id
+            );
+            let _ = logger
+                .write_update(
+                    "STREAM_UNSUPPORTED",
+                    serde_json::json!({
+                        "reason": reason,
+                        "fallback": "polling",
+                        "interval_ms": FALLBACK_POLL_INTERVAL.as_millis(),
+                        "timestamp": chrono::Local::now().to_rfc3339()
+                    }),
+                )
+                .await;
+            poll_watch(&brp_method, params, entity_id, port, &logger, &mut updates).await
         }
         Err(e) => {
             error!("Failed to connect to BRP server: {}", e);
@@ -211,49 +299,127 @@ async fn run_watch_connection(
                     }),
                 )
                 .await;
+            Ok(())
         }
+    };
+    if let Err(e) = outcome {
+        error!("Watch stream processing failed: {}", e);
     }
 
-    // Write final log entry
-    let _ = logger
-        .write_update(
-            "WATCH_ENDED",
-            serde_json::json!({
-                "entity": entity_id,
-                "timestamp": chrono::Local::now().to_rfc3339()
-            }),
+    finish_watch(watch_id, &WatchTarget::Entity(entity_id), &logger).await;
+}
+
+/// Poll a resource at a fixed interval, logging the fields that changed
+async fn run_resource_poll(
+    watch_id: u32,
+    resource: String,
+    interval: Duration,
+    port: u16,
+    logger: BufferedWatchLogger,
+) {
+    info!(
+        "Starting resource watch task for {} on port {} every {}ms",
+        resource,
+        port,
+        interval.as_millis()
+    );
+
+    let params = serde_json::json!({ JSON_FIELD_RESOURCE: resource });
+    let mut differ = WatchDiffer::new("resource");
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut failures = 0;
+
+    while failures < MAX_POLL_FAILURES {
+        ticker.tick().await;
+        let error = match brp_client::execute_brp_method(
+            BRP_METHOD_GET_RESOURCE,
+            Some(params.clone()),
+            Some(port),
         )
-        .await;
+        .await
+        {
+            Ok(BrpResult::Success(data)) => {
+                failures = 0;
+                let value = data.map_or(Value::Null, |data| {
+                    data.get("value").cloned().unwrap_or(data)
+                });
+                let Some(update) = differ.next_update(value) else {
+                    debug!("Resource {} unchanged", resource);
+                    continue;
+                };
+                if log_update(&logger, "RESOURCE_UPDATE", update)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+                continue;
+            }
+            Ok(BrpResult::Error(e)) => e.message,
+            Err(e) => e.to_string(),
+        };
+
+        failures += 1;
+        warn!("Polling resource {} failed: {}", resource, error);
+        let _ = logger
+            .write_update(
+                "POLL_ERROR",
+                serde_json::json!({
+                    "error": error,
+                    "consecutive_failures": failures,
+                    "timestamp": chrono::Local::now().to_rfc3339()
+                }),
+            )
+            .await;
+    }
+
+    finish_watch(watch_id, &WatchTarget::Resource(resource), &logger).await;
+}
+
+/// Log the end of a watch and remove it from the active watches
+pub(super) async fn finish_watch(
+    watch_id: u32,
+    target: &WatchTarget,
+    logger: &BufferedWatchLogger,
+) {
+    // Write final log entry
+    let mut log_data = target.to_json();
+    log_data["timestamp"] = serde_json::json!(chrono::Local::now().to_rfc3339());
+    let _ = logger.write_update("WATCH_ENDED", log_data).await;
 
     // Remove this watch from the active watches with defensive checks
     {
         let mut manager = WATCH_MANAGER.lock().await;
-        if manager.active_watches.remove(&watch_id).is_some() {
+        if manager.remove(watch_id).is_some() {
             info!(
-                "Watch {} for entity {} automatically cleaned up after connection ended",
-                watch_id, entity_id
+                "Watch {} for {} automatically cleaned up after connection ended",
+                watch_id, target
             );
         } else {
             warn!(
-                "Watch {} for entity {} attempted to clean up but was not found in active watches - possible phantom watch removal",
-                watch_id, entity_id
+                "Watch {} for {} attempted to clean up but was not found in active watches - possible phantom watch removal",
+                watch_id, target
             );
         }
     }
 }
 
 /// Generic function to start a watch task
-async fn start_watch_task(
-    entity_id: u64,
+///
+/// `run` builds the task future from the watch ID and logger once the watch is set up.
+pub(super) async fn start_watch_task<F, Fut>(
+    target: WatchTarget,
     watch_type: &str,
-    brp_method: &str,
+    label: Option<&str>,
     params: Value,
     port: u16,
-) -> Result<(u32, PathBuf)> {
-    // Prepare all data that doesn't require the watch_id
-    let watch_type_owned = watch_type.to_string();
-    let brp_method_owned = brp_method.to_string();
-
+    run: F,
+) -> Result<(u32, PathBuf)>
+where
+    F: FnOnce(u32, BufferedWatchLogger) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
     // Perform all operations within a single lock to ensure atomicity
     let mut manager = WATCH_MANAGER.lock().await;
 
@@ -261,44 +427,41 @@ async fn start_watch_task(
     let watch_id = manager.next_id();
 
     // Create log path and logger
-    let log_path = watch_logger::get_watch_log_path(watch_id, entity_id, watch_type);
-    let logger = BufferedWatchLogger::new(log_path.clone());
+    let log_path = watch_logger::get_watch_log_path(watch_id, &target, watch_type);
+    let logger = BufferedWatchLogger::new(watch_id, log_path.clone());
+    super::manager::register_history(watch_id, target.clone());
 
     // Create initial log entry
-    let log_data = match params.clone() {
-        Value::Object(mut map) => {
-            map.insert("port".to_string(), serde_json::json!(port));
-            map.insert(
-                "timestamp".to_string(),
-                serde_json::json!(chrono::Local::now().to_rfc3339()),
-            );
-            Value::Object(map)
-        }
-        _ => serde_json::json!({
-            "entity": entity_id,
-            "port": port,
-            "timestamp": chrono::Local::now().to_rfc3339()
-        }),
+    let mut log_data = if params.is_object() {
+        params
+    } else {
+        target.to_json()
     };
+    log_data["port"] = serde_json::json!(port);
+    if let Some(label) = label {
+        log_data["label"] = serde_json::json!(label);
+    }
+    log_data["timestamp"] = serde_json::json!(chrono::Local::now().to_rfc3339());
 
     // If logging fails, we haven't registered anything yet
     let log_result = logger.write_update("WATCH_STARTED", log_data).await;
 
     if let Err(e) = log_result {
         return Err(error_stack::Report::new(Error::WatchOperation(format!(
-            "Failed to log initial entry for entity {entity_id}: {e}"
+            "Failed to log initial entry for {target}: {e}"
         ))));
     }
 
-    // Spawn task
-    let handle = tokio::spawn(run_watch_connection(
-        watch_id,
-        entity_id,
-        watch_type_owned,
-        brp_method_owned,
-        params,
-        port,
-        logger,
+    // Spawn task, keeping the host, component filter and client of the tool call that started
+    // the watch
+    let host = brp_client::current_host();
+    let peer = super::manager::calling_peer();
+    let handle = tokio::spawn(component_filter::scope(
+        component_filter::is_bypassed(),
+        super::manager::with_calling_peer(
+            peer.clone(),
+            brp_client::with_host(host.clone(), run(watch_id, logger)),
+        ),
     ));
 
     // Register immediately while still holding the lock
@@ -307,10 +470,13 @@ async fn start_watch_task(
         (
             WatchInfo {
                 watch_id,
-                entity_id,
+                target,
                 watch_type: watch_type.to_string(),
+                label: label.map(String::from),
                 log_path: log_path.clone(),
+                host,
                 port,
+                peer,
             },
             handle,
         ),
@@ -322,10 +488,53 @@ async fn start_watch_task(
     Ok((watch_id, log_path))
 }
 
+/// Start a watch streamed by a BRP `+watch` method
+async fn start_stream_watch_task(
+    entity_id: u64,
+    watch_type: &str,
+    brp_method: &str,
+    params: Value,
+    coalescing: Coalescing,
+    port: u16,
+) -> Result<(u32, PathBuf)> {
+    let updates = StreamUpdates::new(watch_type, coalescing);
+    let brp_method_owned = brp_method.to_string();
+    let stream_params = params.clone();
+
+    let mut log_params = params;
+    if let Some(throttle) = coalescing.throttle {
+        log_params["throttle_ms"] = serde_json::json!(throttle.as_millis());
+    }
+    if let Some(debounce) = coalescing.debounce {
+        log_params["debounce_ms"] = serde_json::json!(debounce.as_millis());
+    }
+
+    start_watch_task(
+        WatchTarget::Entity(entity_id),
+        watch_type,
+        None,
+        log_params,
+        port,
+        move |watch_id, logger| {
+            run_watch_connection(
+                watch_id,
+                entity_id,
+                brp_method_owned,
+                stream_params,
+                updates,
+                port,
+                logger,
+            )
+        },
+    )
+    .await
+}
+
 /// Start a background task for entity component watching
 pub async fn start_entity_watch_task(
     entity_id: u64,
     components: Option<Vec<String>>,
+    coalescing: Coalescing,
     port: u16,
 ) -> Result<(u32, PathBuf)> {
     // Validate components parameter
@@ -346,14 +555,70 @@ pub async fn start_entity_watch_task(
         "components": components
     });
 
-    start_watch_task(entity_id, "get", BRP_METHOD_GET_WATCH, params, port).await
+    start_stream_watch_task(
+        entity_id,
+        "get",
+        BRP_METHOD_GET_WATCH,
+        params,
+        coalescing,
+        port,
+    )
+    .await
 }
 
 /// Start a background task for entity list watching
-pub async fn start_list_watch_task(entity_id: u64, port: u16) -> Result<(u32, PathBuf)> {
+pub async fn start_list_watch_task(
+    entity_id: u64,
+    coalescing: Coalescing,
+    port: u16,
+) -> Result<(u32, PathBuf)> {
     let params = serde_json::json!({
         "entity": entity_id
     });
 
-    start_watch_task(entity_id, "list", BRP_METHOD_LIST_WATCH, params, port).await
+    start_stream_watch_task(
+        entity_id,
+        "list",
+        BRP_METHOD_LIST_WATCH,
+        params,
+        coalescing,
+        port,
+    )
+    .await
+}
+
+/// Start a background task polling a resource for changes
+///
+/// The resource is fetched once up front so that unknown or unreflected resources fail here
+/// instead of only showing up in the log.
+pub async fn start_resource_watch_task(
+    resource: &str,
+    interval: Duration,
+    label: Option<&str>,
+    port: u16,
+) -> Result<(u32, PathBuf)> {
+    let params = serde_json::json!({ JSON_FIELD_RESOURCE: resource });
+    if let BrpResult::Error(e) =
+        brp_client::execute_brp_method(BRP_METHOD_GET_RESOURCE, Some(params.clone()), Some(port))
+            .await?
+    {
+        return Err(error_stack::Report::new(Error::WatchOperation(format!(
+            "Failed to get resource {resource}: {}",
+            e.message
+        ))));
+    }
+
+    let mut log_params = params;
+    log_params["interval_ms"] = serde_json::json!(interval.as_millis());
+    let resource_owned = resource.to_string();
+
+    start_watch_task(
+        WatchTarget::Resource(resource.to_string()),
+        "resource",
+        label,
+        log_params,
+        port,
+        move |watch_id, logger| run_resource_poll(watch_id, resource_owned, interval, port, logger),
+    )
+    .await
 }
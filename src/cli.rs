@@ -0,0 +1,278 @@
+//! Command line of the server
+//!
+//! Without arguments the server speaks MCP over stdio, and with `--sse [address]` over HTTP with
+//! Server-Sent Events for web-based clients and remote agents. `--manifest` prints the tool
+//! manifest, and `call <tool>` runs one tool through the same registry and handlers as MCP calls,
+//! printing its JSON response to stdout:
+//!
+//! ```text
+//! bevy_brp_mcp call bevy_query --port 15702 --data '{"components": ["bevy_transform::components::transform::Transform"]}' --filter '{}'
+//! ```
+//!
+//! Each `--name value` pair becomes the tool argument `name`. Values are parsed as JSON when they
+//! are valid JSON and passed as strings otherwise; a flag without a value is `true`.
+
+use std::io::Write;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use rmcp::model::{
+    CallToolRequestParam, ClientCapabilities, ClientInfo, Implementation, ProtocolVersion,
+    RequestId,
+};
+use rmcp::service::{AtomicU32RequestIdProvider, RequestContext};
+use rmcp::{Peer, RoleServer};
+use serde_json::{Map, Value};
+use tokio_util::sync::CancellationToken;
+
+use crate::support::serialization::result_to_json;
+use crate::{BrpMcpService, registry, sse_server, tool_manifest};
+
+/// Usage printed by `--help`
+pub const USAGE: &str = "\
+Usage:
+  bevy_brp_mcp                              Serve MCP over stdio
+  bevy_brp_mcp --sse [address|port]         Serve MCP over HTTP+SSE (default 127.0.0.1:15700)
+  bevy_brp_mcp --manifest [json|openapi]    Print the manifest of every tool
+  bevy_brp_mcp call <tool> [--<name> <value>]...
+                                            Run one tool and print its JSON response
+
+Tool arguments are given as --name value; values are parsed as JSON when valid, so
+--port 15702 is a number and --data '{\"components\": []}' an object. A flag without
+a value is true.
+
+Over SSE, clients open GET /sse and post messages to the endpoint it announces, sending
+BRP_MCP_SSE_TOKEN as a bearer token; when it is unset a token is generated and printed.";
+
+/// What the server was asked to do
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Serve MCP over stdio
+    Serve,
+    /// Serve MCP over HTTP+SSE on an address
+    ServeSse { address: SocketAddr },
+    /// Print the usage
+    Help,
+    /// Print the tool manifest in a format
+    Manifest { format: String },
+    /// Run one tool with arguments
+    Call {
+        tool:      String,
+        arguments: Map<String, Value>,
+    },
+}
+
+/// Parse the arguments after the program name
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    let Some(first) = args.next() else {
+        return Ok(Command::Serve);
+    };
+
+    match first.as_str() {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "--sse" => {
+            let address = args.next().map_or_else(
+                || Ok(default_sse_address()),
+                |address| parse_address(&address),
+            )?;
+            if let Some(extra) = args.next() {
+                return Err(format!("unexpected argument '{extra}'"));
+            }
+            Ok(Command::ServeSse { address })
+        }
+        "--manifest" => {
+            let format = args
+                .next()
+                .unwrap_or_else(|| tool_manifest::FORMAT_JSON.to_string());
+            if !tool_manifest::MANIFEST_FORMATS.contains(&format.as_str()) {
+                return Err(format!(
+                    "unknown manifest format '{format}', expected one of: {}",
+                    tool_manifest::MANIFEST_FORMATS.join(", ")
+                ));
+            }
+            if let Some(extra) = args.next() {
+                return Err(format!("unexpected argument '{extra}'"));
+            }
+            Ok(Command::Manifest { format })
+        }
+        "call" => {
+            let tool = args
+                .next()
+                .filter(|tool| !tool.starts_with("--"))
+                .ok_or_else(|| "call needs a tool name, e.g. call bevy_list".to_string())?;
+
+            let mut arguments = Map::new();
+            while let Some(arg) = args.next() {
+                let name = arg
+                    .strip_prefix("--")
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| format!("expected --<name> before '{arg}'"))?;
+                let value = args
+                    .next_if(|next| !next.starts_with("--"))
+                    .map_or(Value::Bool(true), |value| {
+                        serde_json::from_str(&value).unwrap_or(Value::String(value))
+                    });
+                arguments.insert(name.to_string(), value);
+            }
+            Ok(Command::Call { tool, arguments })
+        }
+        other => Err(format!("unknown command '{other}'")),
+    }
+}
+
+/// Address to serve SSE on: `host:port`, or a bare port on the loopback address
+fn parse_address(address: &str) -> Result<SocketAddr, String> {
+    if let Ok(port) = address.parse::<u16>() {
+        return Ok(SocketAddr::from(([127, 0, 0, 1], port)));
+    }
+    address
+        .parse()
+        .map_err(|_| format!("invalid address '{address}', expected host:port or a port"))
+}
+
+fn default_sse_address() -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], sse_server::DEFAULT_SSE_PORT))
+}
+
+/// Run one tool and print its JSON response, returning whether it succeeded
+///
+/// The call has no MCP client behind it: the project roots are the current directory, and
+/// requests the handler makes to the client, such as progress notifications, fail at once.
+pub async fn call(tool: String, arguments: Map<String, Value>) -> Result<bool, String> {
+    let service = BrpMcpService::new();
+    if let Ok(mut roots) = service.roots.lock() {
+        *roots = std::env::current_dir()
+            .map(|dir| vec![dir])
+            .unwrap_or_default();
+    }
+
+    let request = CallToolRequestParam {
+        name:      tool.into(),
+        arguments: Some(arguments),
+    };
+    let (response, succeeded) =
+        match registry::handle_tool_call(&service, request, detached_context()).await {
+            Ok(result) => {
+                let response = result_to_json(&result);
+                let failed = result.is_error == Some(true) || response["status"] == "error";
+                (response, !failed)
+            }
+            Err(error) => (
+                serde_json::to_value(&error).map_err(|e| e.to_string())?,
+                false,
+            ),
+        };
+
+    let text = serde_json::to_string_pretty(&response).map_err(|e| e.to_string())?;
+    writeln!(std::io::stdout(), "{text}").map_err(|e| e.to_string())?;
+    Ok(succeeded)
+}
+
+/// Context for a tool call made without an MCP client
+fn detached_context() -> RequestContext<RoleServer> {
+    let client = ClientInfo {
+        protocol_version: ProtocolVersion::default(),
+        capabilities:     ClientCapabilities::default(),
+        client_info:      Implementation {
+            name:    env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+    };
+    // Dropping the receiving end makes every request to the client fail instead of waiting
+    let (peer, _) = Peer::new(Arc::new(AtomicU32RequestIdProvider::default()), client);
+    RequestContext {
+        ct: CancellationToken::new(),
+        id: RequestId::Number(0),
+        peer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn test_parse_call_arguments() {
+        let command = parse_args(&[
+            "call",
+            "bevy_query",
+            "--port",
+            "15702",
+            "--data",
+            r#"{"components": ["Transform"]}"#,
+            "--host",
+            "game-box.local",
+            "--strict",
+        ]);
+
+        let expected = json!({
+            "port": 15702,
+            "data": { "components": ["Transform"] },
+            "host": "game-box.local",
+            "strict": true,
+        });
+        assert_eq!(
+            command,
+            Ok(Command::Call {
+                tool:      "bevy_query".to_string(),
+                arguments: expected.as_object().cloned().unwrap_or_default(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_other_commands() {
+        assert_eq!(parse_args(&[]), Ok(Command::Serve));
+        assert_eq!(parse_args(&["--help"]), Ok(Command::Help));
+        assert_eq!(
+            parse_args(&["--manifest"]),
+            Ok(Command::Manifest {
+                format: "json".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_args(&["--manifest", "openapi"]),
+            Ok(Command::Manifest {
+                format: "openapi".to_string(),
+            })
+        );
+
+        assert_eq!(
+            parse_args(&["--sse"]),
+            Ok(Command::ServeSse {
+                address: SocketAddr::from(([127, 0, 0, 1], 15700)),
+            })
+        );
+        assert_eq!(
+            parse_args(&["--sse", "8080"]),
+            Ok(Command::ServeSse {
+                address: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            })
+        );
+        assert_eq!(
+            parse_args(&["--sse", "0.0.0.0:9000"]),
+            Ok(Command::ServeSse {
+                address: SocketAddr::from(([0, 0, 0, 0], 9000)),
+            })
+        );
+
+        for args in [
+            &["--manifest", "yaml"][..],
+            &["call"],
+            &["call", "--port", "15702"],
+            &["call", "bevy_list", "15702"],
+            &["serve"],
+            &["--sse", "localhost"],
+            &["--sse", "15700", "extra"],
+        ] {
+            assert!(parse_args(args).is_err(), "{args:?} should be rejected");
+        }
+    }
+}
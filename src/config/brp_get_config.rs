@@ -0,0 +1,77 @@
+//! Show the server configuration
+
+use rmcp::RoleServer;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use serde_json::{Value, json};
+
+use super::{ServerConfig, config_path, current};
+use crate::BrpMcpService;
+use crate::brp_tools::request_handler::{
+    format_discovery_enabled, preflight_enabled, strict_corrections_enabled,
+};
+use crate::brp_tools::support::destructive_ops;
+use crate::brp_tools::support::response_formatter::max_response_bytes;
+use crate::brp_tools::watch::support::manager;
+use crate::log_tools::support::get_log_directory;
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_GET_CONFIG, TOOL_BRP_GET_CONFIG};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_GET_CONFIG.into(),
+        description:  DESC_BRP_GET_CONFIG.into(),
+        input_schema: schema::SchemaBuilder::new().build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    _request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> CallToolResult {
+    let config = current();
+    let path = config_path();
+
+    let response = ResponseBuilder::success()
+        .message(path.as_ref().map_or_else(
+            || "No configuration file location; using built-in defaults".to_string(),
+            |path| format!("Configuration file: {}", path.display()),
+        ))
+        .data(json!({
+            "path": path,
+            "file_exists": path.as_ref().is_some_and(|path| path.exists()),
+            "config": config.redacted(),
+            "effective": effective(),
+            "keys": ServerConfig::KEYS,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    json_response_to_result(&response)
+}
+
+/// The value in effect for every key, after environment variables and built-in defaults
+pub fn effective() -> Value {
+    json!({
+        "default_port": super::default_port(),
+        "log_dir": get_log_directory(),
+        "default_profile": super::default_profile(),
+        "max_response_bytes": max_response_bytes().unwrap_or(0),
+        "format_discovery": format_discovery_enabled(),
+        "preflight_corrections": preflight_enabled(),
+        "strict_format_corrections": strict_corrections_enabled(),
+        "destructive_ops": destructive_ops::policy(),
+        "watch_liveness_interval_ms": manager::liveness_interval().map_or(0, |interval| interval.as_millis()),
+        "watch_notifications": manager::notifications_enabled(),
+        "connections": super::current().redacted().connections.unwrap_or_default(),
+    })
+}
@@ -0,0 +1,99 @@
+//! Change the server configuration at runtime
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::brp_get_config::effective;
+use super::{ServerConfig, current, replace, save};
+use crate::BrpMcpService;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BRP_SET_CONFIG, TOOL_BRP_SET_CONFIG};
+
+/// Parameter naming the configuration key
+const PARAM_KEY: &str = "key";
+
+/// Parameter holding the new value
+const PARAM_VALUE: &str = "value";
+
+/// Parameter writing the configuration back to its file
+const PARAM_SAVE: &str = "save";
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_SET_CONFIG.into(),
+        description:  DESC_BRP_SET_CONFIG.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property(
+                PARAM_KEY,
+                &format!("The key to set: {}", ServerConfig::KEYS.join(", ")),
+                true,
+            )
+            .add_any_property(
+                PARAM_VALUE,
+                "The new value, or null to go back to the built-in default",
+                true,
+            )
+            .add_boolean_property(
+                PARAM_SAVE,
+                "Also write the configuration to its file so it applies after a restart (default: false)",
+                false,
+            )
+            .build(),
+    }
+}
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let key = params::extract_required_string(&request, PARAM_KEY)?.to_string();
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let value = arguments.get(PARAM_VALUE).cloned().unwrap_or(Value::Null);
+    let persist = arguments
+        .get(PARAM_SAVE)
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut config = current();
+    config
+        .set(&key, &value)
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let saved_to = persist
+        .then(|| save(&config))
+        .transpose()
+        .map_err(|report| report_to_mcp_error(&report))?;
+    replace(config.clone());
+
+    let message = saved_to.as_ref().map_or_else(
+        || format!("Set {key} for this session"),
+        |path| {
+            format!(
+                "Set {key} and saved the configuration to {}",
+                path.display()
+            )
+        },
+    );
+    let response = ResponseBuilder::success()
+        .message(message)
+        .data(json!({
+            "config": config.redacted(),
+            "effective": effective(),
+            "saved_to": saved_to,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
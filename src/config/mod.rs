@@ -0,0 +1,335 @@
+//! Server defaults from a configuration file
+//!
+//! The file is `config.json` in the platform config directory joined with `bevy_brp_mcp`, e.g.
+//! `~/.config/bevy_brp_mcp/config.json` on Linux, or the file named by `BRP_MCP_CONFIG`. It's
+//! JSON like every other file the server reads and writes, and every key is optional:
+//!
+//! ```json
+//! {
+//!   "default_port": 15702,
+//!   "log_dir": "/home/user/bevy_logs",
+//!   "default_profile": "release",
+//!   "max_response_bytes": 40000,
+//!   "format_discovery": true,
+//!   "preflight_corrections": true,
+//!   "strict_format_corrections": false,
+//!   "destructive_ops": "confirm",
+//!   "watch_liveness_interval_ms": 5000,
+//!   "watch_notifications": true,
+//!   "connections": { "brp.example.com": { "tls": true } }
+//! }
+//! ```
+//!
+//! Environment variables for the same setting take precedence over the file. The file is read
+//! once at startup; `brp_set_config` changes the loaded values and can write them back.
+
+pub mod brp_get_config;
+pub mod brp_set_config;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::brp_tools::constants::DEFAULT_BRP_PORT;
+use crate::brp_tools::support::destructive_ops::DestructiveOpsPolicy;
+use crate::brp_tools::support::http_client::ConnectionSettings;
+use crate::constants::{DEFAULT_PROFILE, PROFILE_DEBUG, PROFILE_RELEASE};
+use crate::error::{Error, Result};
+
+/// Environment variable naming the configuration file
+pub const CONFIG_ENV_VAR: &str = "BRP_MCP_CONFIG";
+
+/// Name of the configuration file inside the application config directory
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// Name of the application directory inside the platform config directory
+const APP_DIR_NAME: &str = "bevy_brp_mcp";
+
+/// The loaded configuration
+static CONFIG: LazyLock<std::sync::Mutex<ServerConfig>> =
+    LazyLock::new(|| std::sync::Mutex::new(ServerConfig::default()));
+
+/// Server defaults; unset keys keep the built-in default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// BRP port used when a tool isn't given one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_port:               Option<u16>,
+    /// Directory of the server debug log and the app, watch and schedule logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_dir:                    Option<PathBuf>,
+    /// Build profile used when a launch tool isn't given one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile:            Option<String>,
+    /// Response size in bytes above which results are spilled to a file; `0` disables spilling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_response_bytes:         Option<usize>,
+    /// Whether rejected payloads are corrected by format discovery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format_discovery:           Option<bool>,
+    /// Whether known problematic values are corrected before the first attempt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preflight_corrections:      Option<bool>,
+    /// Whether payloads needing corrections fail with the corrections instead of being corrected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_format_corrections:  Option<bool>,
+    /// Whether destroying entities, removing resources and shutting down, killing or restarting
+    /// apps need confirming
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destructive_ops:            Option<DestructiveOpsPolicy>,
+    /// Interval at which the apps of active watches are probed; `0` disables probing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_liveness_interval_ms: Option<u64>,
+    /// Whether the client is sent a log notification when a watch ends because its app went away
+    /// or a performance watch alerts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_notifications:        Option<bool>,
+    /// TLS and header settings by `host:port`, `host` or `*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connections:                Option<BTreeMap<String, ConnectionSettings>>,
+}
+
+impl ServerConfig {
+    /// Names of the configuration keys
+    pub const KEYS: &[&str] = &[
+        "default_port",
+        "log_dir",
+        "default_profile",
+        "max_response_bytes",
+        "format_discovery",
+        "preflight_corrections",
+        "strict_format_corrections",
+        "destructive_ops",
+        "watch_liveness_interval_ms",
+        "watch_notifications",
+        "connections",
+    ];
+
+    /// The configuration as shown to clients, with connection header values masked
+    pub fn redacted(&self) -> Self {
+        Self {
+            connections: self.connections.as_ref().map(|connections| {
+                connections
+                    .iter()
+                    .map(|(key, settings)| (key.clone(), settings.redacted()))
+                    .collect()
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Set one key from a JSON value, or reset it to the built-in default with `null`
+    pub fn set(&mut self, key: &str, value: &Value) -> Result<()> {
+        let mut updated = serde_json::to_value(&*self).map_err(|e| {
+            error_stack::Report::new(Error::Configuration(format!(
+                "Failed to serialize configuration: {e}"
+            )))
+        })?;
+        match (updated.as_object_mut(), value) {
+            (Some(fields), Value::Null) => {
+                fields.remove(key);
+            }
+            (Some(fields), value) => {
+                fields.insert(key.to_string(), value.clone());
+            }
+            (None, _) => {}
+        }
+        let updated = Self::from_value(updated, &format!("key '{key}'"))?;
+        // Clients can ask for confirmation but not lift it, only the file can
+        if self.destructive_ops == Some(DestructiveOpsPolicy::Confirm)
+            && updated.destructive_ops != Some(DestructiveOpsPolicy::Confirm)
+        {
+            return Err(error_stack::Report::new(Error::invalid(
+                "configuration key 'destructive_ops'",
+                "confirm can't be lifted at runtime; edit the configuration file and restart \
+                 the server to allow destructive operations again",
+            )));
+        }
+        *self = updated;
+        Ok(())
+    }
+
+    /// Read and validate a configuration
+    fn from_value(value: Value, what: &str) -> Result<Self> {
+        let config: Self = serde_json::from_value(value).map_err(|e| {
+            error_stack::Report::new(Error::invalid(
+                &format!("configuration {what}"),
+                format!("{e}; valid keys are {}", Self::KEYS.join(", ")),
+            ))
+        })?;
+        if let Some(profile) = config
+            .default_profile
+            .as_deref()
+            .filter(|profile| ![PROFILE_DEBUG, PROFILE_RELEASE].contains(profile))
+        {
+            return Err(error_stack::Report::new(Error::invalid(
+                &format!("configuration {what}"),
+                format!("default_profile '{profile}' is not {PROFILE_DEBUG} or {PROFILE_RELEASE}"),
+            )));
+        }
+        Ok(config)
+    }
+}
+
+/// Path of the configuration file, from the environment or the platform config directory
+pub fn config_path() -> Option<PathBuf> {
+    match std::env::var(CONFIG_ENV_VAR) {
+        Ok(value) if !value.is_empty() => Some(PathBuf::from(value)),
+        _ => platform_config_dir().map(|dir| dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME)),
+    }
+}
+
+/// Load the configuration file at startup, returning what happened for the log
+///
+/// Runs before logging is set up, since the log directory is configurable. A missing file leaves
+/// the built-in defaults; an unreadable or invalid one is reported as the error and ignored so it
+/// never prevents the server from starting.
+pub fn initialize() -> std::result::Result<String, String> {
+    let Some(path) = config_path() else {
+        return Ok("No configuration directory; using built-in defaults".to_string());
+    };
+    match load(&path) {
+        Ok(Some(config)) => {
+            replace(config);
+            Ok(format!("Loaded configuration from {}", path.display()))
+        }
+        Ok(None) => Ok(format!("No configuration file at {}", path.display())),
+        Err(e) => Err(format!(
+            "Ignoring configuration file {}: {e}",
+            path.display()
+        )),
+    }
+}
+
+/// Read a configuration file, or `None` if it doesn't exist
+fn load(path: &Path) -> Result<Option<ServerConfig>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        error_stack::Report::new(Error::FileOperation(format!(
+            "Failed to read {}: {e}",
+            path.display()
+        )))
+    })?;
+    let value: Value = serde_json::from_str(&contents).map_err(|e| {
+        error_stack::Report::new(Error::Configuration(format!(
+            "{} is not valid JSON: {e}",
+            path.display()
+        )))
+    })?;
+    ServerConfig::from_value(value, "file").map(Some)
+}
+
+/// Write a configuration to the configuration file, returning its path
+pub fn save(config: &ServerConfig) -> Result<PathBuf> {
+    let path = config_path().ok_or_else(|| {
+        error_stack::Report::new(Error::Configuration(format!(
+            "No configuration directory; set {CONFIG_ENV_VAR} to a file path"
+        )))
+    })?;
+    let write_failed = |e: &dyn std::fmt::Display| {
+        error_stack::Report::new(Error::FileOperation(format!(
+            "Failed to write {}: {e}",
+            path.display()
+        )))
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| write_failed(&e))?;
+    }
+    let contents = serde_json::to_string_pretty(config).map_err(|e| write_failed(&e))?;
+    std::fs::write(&path, contents).map_err(|e| write_failed(&e))?;
+    Ok(path)
+}
+
+/// The current configuration
+pub fn current() -> ServerConfig {
+    CONFIG
+        .lock()
+        .map(|config| config.clone())
+        .unwrap_or_default()
+}
+
+/// Replace the current configuration
+pub fn replace(config: ServerConfig) {
+    if let Ok(mut current) = CONFIG.lock() {
+        *current = config;
+    }
+}
+
+/// BRP port used when a tool isn't given one
+pub fn default_port() -> u16 {
+    current().default_port.unwrap_or(DEFAULT_BRP_PORT)
+}
+
+/// Build profile used when a launch tool isn't given one
+pub fn default_profile() -> String {
+    current()
+        .default_profile
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// The platform specific directory for user configuration
+fn platform_config_dir() -> Option<PathBuf> {
+    let env_path = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if cfg!(target_os = "windows") {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_path("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_path("XDG_CONFIG_HOME").or_else(|| env_path("HOME").map(|home| home.join(".config")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_keys_are_validated_set_and_reset() {
+        let mut config = ServerConfig::from_value(
+            json!({ "default_port": 15710, "format_discovery": false }),
+            "file",
+        )
+        .unwrap();
+        assert_eq!(config.default_port, Some(15710));
+
+        config.set("default_profile", &json!("release")).unwrap();
+        config.set("default_port", &Value::Null).unwrap();
+        assert_eq!(config.default_profile.as_deref(), Some("release"));
+        assert_eq!(config.default_port, None);
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            json!({ "default_profile": "release", "format_discovery": false })
+        );
+
+        assert!(config.set("default_profile", &json!("fast")).is_err());
+        assert!(config.set("default_port", &json!(-1)).is_err());
+        assert!(config.set("colour", &json!(true)).is_err());
+        assert_eq!(config.default_profile.as_deref(), Some("release"));
+    }
+
+    #[test]
+    fn test_destructive_ops_can_only_be_tightened() {
+        let mut config = ServerConfig::default();
+        config.set("destructive_ops", &json!("allow")).unwrap();
+        config.set("destructive_ops", &json!("confirm")).unwrap();
+
+        assert!(config.set("destructive_ops", &json!("allow")).is_err());
+        assert!(config.set("destructive_ops", &Value::Null).is_err());
+        config.set("default_port", &json!(15710)).unwrap();
+        assert_eq!(config.destructive_ops, Some(DestructiveOpsPolicy::Confirm));
+    }
+}
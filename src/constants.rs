@@ -9,9 +9,28 @@ pub const PROFILE_DEBUG: &str = "debug";
 pub const PROFILE_RELEASE: &str = "release";
 pub const DEFAULT_PROFILE: &str = PROFILE_DEBUG;
 
+// Log format constants (used by the launch tools and read_log)
+pub const LOG_FORMAT_TEXT: &str = "text";
+pub const LOG_FORMAT_JSON: &str = "json";
+
 // Parameter name constants (used across multiple modules)
 pub const PARAM_PROFILE: &str = "profile";
 pub const PARAM_PORT: &str = "port";
 pub const PARAM_APP_NAME: &str = "app_name";
 pub const PARAM_EXAMPLE_NAME: &str = "example_name";
 pub const PARAM_WORKSPACE: &str = "workspace";
+pub const PARAM_PID: &str = "pid";
+pub const PARAM_LOG_FILE: &str = "log_file";
+pub const PARAM_GROUP: &str = "group";
+pub const PARAM_FORCE: &str = "force";
+pub const PARAM_FEATURES: &str = "features";
+pub const PARAM_NO_DEFAULT_FEATURES: &str = "no_default_features";
+pub const PARAM_ENV: &str = "env";
+pub const PARAM_RUST_LOG: &str = "rust_log";
+pub const PARAM_LOG_FORMAT: &str = "log_format";
+pub const PARAM_WRAPPER: &str = "wrapper";
+pub const PARAM_PACKAGE: &str = "package";
+pub const PARAM_WITH_EXTRAS: &str = "with_extras";
+pub const PARAM_DRY_RUN: &str = "dry_run";
+pub const PARAM_WAIT_FOR_BRP: &str = "wait_for_brp";
+pub const PARAM_WAIT_TIMEOUT_MS: &str = "wait_timeout_ms";
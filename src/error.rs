@@ -1,4 +1,7 @@
+use error_stack::{AttachmentKind, FrameKind};
 use rmcp::Error as McpError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use thiserror::Error;
 
 // Error message prefixes
@@ -8,6 +11,66 @@ const MSG_INVALID_PREFIX: &str = "Invalid";
 const MSG_MISSING_PREFIX: &str = "Missing";
 const MSG_UNEXPECTED_PREFIX: &str = "Unexpected";
 
+// JSON-RPC and Bevy remote error codes with a dedicated error code
+const BRP_CODE_INVALID_REQUEST: i32 = -32600;
+const BRP_CODE_METHOD_NOT_FOUND: i32 = -32601;
+const BRP_CODE_INVALID_PARAMS: i32 = -32602;
+const BRP_CODE_NO_SUCH_ENTITY: i32 = -23401;
+
+/// Machine-readable category of a failure, reported as `error_code` next to `details` in error
+/// responses so agents can branch on the kind of failure instead of parsing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Nothing accepted a connection on the BRP host and port
+    ConnectionRefused,
+    /// The BRP server didn't answer within the request timeout
+    Timeout,
+    /// The app does not implement the BRP method, e.g. without `BrpExtrasPlugin`
+    MethodNotSupported,
+    /// BRP rejected the payload and format discovery could not correct it
+    FormatCorrectionFailed,
+    /// The app or example could not be started
+    ProcessSpawnError,
+    /// A tool or BRP parameter is missing or invalid
+    InvalidParameters,
+    /// The entity does not exist
+    EntityNotFound,
+    /// Any other error returned by the BRP server
+    BrpError,
+    /// The BRP server answered with something other than a usable JSON-RPC response
+    BrpCommunication,
+    /// The app or example exists in several workspaces
+    WorkspaceDisambiguation,
+    /// Managing an already running process failed
+    ProcessError,
+    /// Reading or writing a file failed
+    FileError,
+    /// Starting or stopping a watch failed
+    WatchError,
+    /// The server or the request is misconfigured
+    ConfigurationError,
+    /// The operation does not fit the current state
+    InvalidState,
+    /// An unexpected failure inside the server
+    Internal,
+}
+
+impl ErrorCode {
+    /// Categorize an error returned by the BRP server
+    ///
+    /// `corrections_attempted` tells whether format discovery tried to fix the payload first.
+    pub const fn from_brp_code(code: i32, corrections_attempted: bool) -> Self {
+        match code {
+            BRP_CODE_METHOD_NOT_FOUND => Self::MethodNotSupported,
+            BRP_CODE_NO_SUCH_ENTITY => Self::EntityNotFound,
+            _ if corrections_attempted => Self::FormatCorrectionFailed,
+            BRP_CODE_INVALID_REQUEST | BRP_CODE_INVALID_PARAMS => Self::InvalidParameters,
+            _ => Self::BrpError,
+        }
+    }
+}
+
 // Internal error types for detailed error categorization
 #[derive(Error, Debug, Clone)]
 pub enum Error {
@@ -49,6 +112,19 @@ pub enum Error {
         available_workspaces: Vec<String>,
     },
 
+    #[error("Connection refused: {0}")]
+    ConnectionRefused(String),
+
+    #[error("Request timed out: {message}")]
+    Timeout {
+        message:    String,
+        timeout_ms: u64,
+        attempts:   u32,
+    },
+
+    #[error("Process spawn failed: {0}")]
+    ProcessSpawn(String),
+
     #[error("Log operation failed: {0}")]
     LogOperation(String),
 
@@ -144,6 +220,58 @@ impl Error {
         ))
     }
 
+    /// Machine-readable category of the error
+    pub const fn code(&self) -> ErrorCode {
+        match self {
+            Self::ConnectionRefused(_) => ErrorCode::ConnectionRefused,
+            Self::Timeout { .. } => ErrorCode::Timeout,
+            Self::ProcessSpawn(_) => ErrorCode::ProcessSpawnError,
+            Self::ParameterExtraction(_) => ErrorCode::InvalidParameters,
+            Self::FormatDiscovery(_) => ErrorCode::FormatCorrectionFailed,
+            Self::BrpCommunication(_) | Self::JsonRpc(_) => ErrorCode::BrpCommunication,
+            Self::WorkspaceDisambiguation { .. } => ErrorCode::WorkspaceDisambiguation,
+            Self::ProcessManagement(_) => ErrorCode::ProcessError,
+            Self::FileOperation(_) | Self::LogOperation(_) => ErrorCode::FileError,
+            Self::WatchOperation(_) => ErrorCode::WatchError,
+            Self::Configuration(_) => ErrorCode::ConfigurationError,
+            Self::InvalidState(_) => ErrorCode::InvalidState,
+            Self::MutexPoisoned(_) | Self::General(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// Structured details of the error, an empty object for variants that only carry a message
+    pub fn details(&self) -> Value {
+        match self {
+            Self::WorkspaceDisambiguation {
+                item_type,
+                item_name,
+                available_workspaces,
+                ..
+            } => json!({
+                "item_type": item_type,
+                "item_name": item_name,
+                "available_workspaces": available_workspaces,
+            }),
+            Self::Timeout {
+                timeout_ms,
+                attempts,
+                ..
+            } => json!({
+                "timeout_ms": timeout_ms,
+                "attempts": attempts,
+            }),
+            _ => json!({}),
+        }
+    }
+
+    /// The `error_code` and `details` carried in the data of an `McpError`
+    fn error_data(&self) -> Value {
+        json!({
+            "error_code": self.code(),
+            "details": self.details(),
+        })
+    }
+
     /// Categorize error based on content
     fn categorize_error(message: &str) -> Self {
         // Simple heuristic categorization
@@ -174,29 +302,104 @@ impl Error {
 // Conversion to McpError for API boundaries
 impl From<Error> for McpError {
     fn from(err: Error) -> Self {
+        let data = Some(err.error_data());
         match err {
             Error::BrpCommunication(msg)
             | Error::JsonRpc(msg)
             | Error::FormatDiscovery(msg)
             | Error::Configuration(msg)
-            | Error::ParameterExtraction(msg) => Self::invalid_params(msg, None),
+            | Error::ConnectionRefused(msg)
+            | Error::ParameterExtraction(msg) => Self::invalid_params(msg, data),
+            Error::Timeout { message, .. } => Self::internal_error(message, data),
             Error::WorkspaceDisambiguation { message, .. } => {
                 // For workspace disambiguation, we want to preserve the detailed message
                 // as an invalid_params error since it's a parameter issue that can be resolved
-                Self::invalid_params(message, None)
+                Self::invalid_params(message, data)
             }
             Error::MutexPoisoned(msg)
             | Error::FileOperation(msg)
             | Error::InvalidState(msg)
             | Error::WatchOperation(msg)
             | Error::ProcessManagement(msg)
+            | Error::ProcessSpawn(msg)
             | Error::LogOperation(msg)
-            | Error::General(msg) => Self::internal_error(msg, None),
+            | Error::General(msg) => Self::internal_error(msg, data),
         }
     }
 }
 
 // Helper function to convert error-stack Report to McpError
+//
+// The printable attachments of the report are kept as `details.context`, oldest first.
 pub fn report_to_mcp_error(report: &error_stack::Report<Error>) -> McpError {
-    (*report.current_context()).clone().into()
+    let mut error: McpError = (*report.current_context()).clone().into();
+    let mut context: Vec<String> = report
+        .frames()
+        .filter_map(|frame| match frame.kind() {
+            FrameKind::Attachment(AttachmentKind::Printable(printable)) => {
+                Some(printable.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    context.reverse();
+    if let Some(Value::Object(details)) = error
+        .data
+        .as_mut()
+        .and_then(|data| data.get_mut("details"))
+        .filter(|_| !context.is_empty())
+    {
+        details.insert("context".to_string(), json!(context));
+    }
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_carry_codes_and_details() {
+        let report = error_stack::Report::new(Error::ConnectionRefused(
+            "Nothing listens on port 15702".to_string(),
+        ))
+        .attach_printable("Method: bevy/list, Port: 15702");
+        let error = report_to_mcp_error(&report);
+        assert_eq!(
+            error.data,
+            Some(json!({
+                "error_code": "connection_refused",
+                "details": { "context": ["Method: bevy/list, Port: 15702"] },
+            }))
+        );
+
+        let ambiguous = Error::WorkspaceDisambiguation {
+            message:              "Found 2 apps".to_string(),
+            item_type:            "app".to_string(),
+            item_name:            "game".to_string(),
+            available_workspaces: vec!["a".to_string(), "b".to_string()],
+        };
+        assert_eq!(ambiguous.code(), ErrorCode::WorkspaceDisambiguation);
+        assert_eq!(
+            ambiguous.details()["available_workspaces"],
+            json!(["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_brp_codes_are_categorized() {
+        assert_eq!(
+            ErrorCode::from_brp_code(-32601, false),
+            ErrorCode::MethodNotSupported
+        );
+        assert_eq!(
+            ErrorCode::from_brp_code(-23402, true),
+            ErrorCode::FormatCorrectionFailed
+        );
+        assert_eq!(
+            ErrorCode::from_brp_code(-32602, false),
+            ErrorCode::InvalidParameters
+        );
+        assert_eq!(ErrorCode::from_brp_code(-23402, false), ErrorCode::BrpError);
+    }
 }
@@ -62,12 +62,12 @@ fn cleanup_log_files(
         }
 
         // Apply age filter if provided
-        if let Some(cutoff) = cutoff_time {
-            if let Ok(modified) = entry.metadata.modified() {
-                // Skip if file is newer than cutoff
-                if modified > cutoff {
-                    return false;
-                }
+        if let Some(cutoff) = cutoff_time
+            && let Ok(modified) = entry.metadata.modified()
+        {
+            // Skip if file is newer than cutoff
+            if modified > cutoff {
+                return false;
             }
         }
 
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::constants::PARAM_FILE_PATH;
+use super::log_analysis::{self, ErrorKind};
+use super::support;
+use crate::BrpMcpService;
+use crate::app_tools::support::managed_apps;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+
+/// Entries returned when no limit is given
+const DEFAULT_LIMIT: u64 = 50;
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: &rmcp::model::CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let filename = params::extract_optional_string(request, "filename", "");
+    let app_name = params::extract_optional_string(request, "app_name", "");
+    let include_warnings = params::extract_any_value(request, "include_warnings")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let limit = usize::try_from(params::extract_optional_number(
+        request,
+        "limit",
+        DEFAULT_LIMIT,
+    )?)
+    .unwrap_or(usize::MAX);
+
+    let log_path = find_log(filename, app_name)?;
+    let bytes = std::fs::read(&log_path).map_err(|e| {
+        report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
+            "read log file",
+            &log_path,
+            &e,
+        )))
+    })?;
+    let mut errors = log_analysis::analyze(&String::from_utf8_lossy(&bytes), include_warnings);
+
+    let count = |kind: ErrorKind| errors.iter().filter(|error| error.kind == kind).count();
+    let by_kind = json!({
+        "panic": count(ErrorKind::Panic),
+        "wgpu_validation": count(ErrorKind::WgpuValidation),
+        "error": count(ErrorKind::Error),
+        "warning": count(ErrorKind::Warning),
+    });
+    let unique = errors.len();
+    let occurrences: usize = errors.iter().map(|error| error.count).sum();
+    errors.truncate(limit);
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Found {unique} distinct errors ({occurrences} occurrences) in {}",
+            log_path.display()
+        ))
+        .data(json!({
+            PARAM_FILE_PATH: log_path.display().to_string(),
+            "errors": errors,
+            "unique": unique,
+            "occurrences": occurrences,
+            "by_kind": by_kind,
+            "truncated": unique > errors.len(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// The log named by `filename`, or else the log of a managed app or the newest log of an app
+fn find_log(filename: &str, app_name: &str) -> Result<PathBuf, McpError> {
+    if !filename.is_empty() {
+        // Validate filename format for security
+        if !support::is_valid_log_filename(filename) {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::invalid("filename", "only bevy_brp_mcp log files can be read"),
+            )));
+        }
+        let log_path = support::get_log_file_path(filename);
+        if !log_path.exists() {
+            return Err(report_to_mcp_error(&error_stack::Report::new(
+                Error::missing(&format!("log file '{filename}'")),
+            )));
+        }
+        return Ok(log_path);
+    }
+    if app_name.is_empty() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid("filename", "give either filename or app_name"),
+        )));
+    }
+
+    if let Some(log_file) = managed_apps::get(app_name).and_then(|app| app.log_file) {
+        return Ok(log_file);
+    }
+    // Logs of launches with a port are named after the app and the port
+    let port_prefix = format!("{app_name}_port");
+    let newest = support::iterate_log_files(|entry| {
+        entry.app_name == app_name || entry.app_name.starts_with(&port_prefix)
+    })?
+    .into_iter()
+    .max_by_key(|entry| entry.timestamp.parse::<u128>().unwrap_or(0));
+    newest.map(|entry| entry.path).ok_or_else(|| {
+        report_to_mcp_error(&error_stack::Report::new(Error::missing(&format!(
+            "log file for app '{app_name}'"
+        ))))
+    })
+}
@@ -0,0 +1,50 @@
+//! Log entries written as JSON lines
+//!
+//! An app launched with `log_format: json` writes one JSON object per line with
+//! `tracing_subscriber`'s JSON formatter: `timestamp`, `level`, `target` and the event's `fields`.
+//! Cargo's build output before the app starts is still plain text.
+
+use serde_json::{Map, Value};
+
+/// Parse a line written by a JSON log layer; other lines give `None`
+pub fn parse_entry(line: &str) -> Option<Map<String, Value>> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(Value::Object(entry)) if entry.get("level").is_some_and(Value::is_string) => Some(entry),
+        _ => None,
+    }
+}
+
+/// Level of an entry, such as `INFO`
+pub fn level(entry: &Map<String, Value>) -> Option<&str> {
+    entry.get("level").and_then(Value::as_str)
+}
+
+/// Timestamp of an entry, as the layer wrote it
+pub fn timestamp(entry: &Map<String, Value>) -> Option<&str> {
+    entry.get("timestamp").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_parse_json_entries() {
+        let entry = parse_entry(
+            r#"{"timestamp":"2025-06-23T10:00:01.5Z","level":"WARN","fields":{"message":"slow frame"},"target":"bevy_diagnostic"}"#,
+        )
+        .unwrap();
+        assert_eq!(level(&entry), Some("WARN"));
+        assert_eq!(timestamp(&entry), Some("2025-06-23T10:00:01.5Z"));
+
+        assert!(parse_entry("2025-06-23T10:00:00Z  INFO bevy_winit: window created").is_none());
+        assert!(parse_entry(r#"{"reason":"compiler-artifact"}"#).is_none());
+        assert!(parse_entry("{ not json").is_none());
+    }
+}
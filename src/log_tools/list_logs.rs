@@ -24,7 +24,7 @@ pub fn handle(
         .message(format!("Found {} log files", logs.len()))
         .data(json!({
             "logs": logs,
-            "temp_directory": support::get_log_directory().display().to_string(),
+            "log_directory": support::get_log_directory().display().to_string(),
         }))
         .map_or_else(
             |_| {
@@ -0,0 +1,364 @@
+//! Finding errors in app logs
+//!
+//! Recognizes three kinds of error in the output of a launched app:
+//! - Rust panics, `thread 'main' panicked at src/main.rs:10:5:` with the message on the lines after
+//!   it (or, before Rust 1.73, in quotes on the same line) and any `stack backtrace:`
+//! - `ERROR` lines of Bevy's log output, with an optional timestamp and target, and `WARN` lines
+//!   when asked for
+//! - wgpu validation errors, logged or panicked with `Validation Error` and a `Caused by:` chain
+//!
+//! Color codes are stripped first. Repeats of an error with the same kind, location and message
+//! are merged into one entry with a count.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Most lines of a panic message or a logged error's continuation kept in an entry
+const MAX_MESSAGE_LINES: usize = 40;
+
+/// Most backtrace lines kept in an entry
+const MAX_BACKTRACE_LINES: usize = 100;
+
+/// Log levels of Bevy's log output, most severe first
+pub const LEVELS: &[&str] = &["ERROR", "WARN", "INFO", "DEBUG", "TRACE"];
+
+/// What kind of error an entry is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Panic,
+    WgpuValidation,
+    Error,
+    Warning,
+}
+
+/// One distinct error found in a log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppError {
+    pub kind:       ErrorKind,
+    pub level:      String,
+    pub message:    String,
+    /// Timestamp of the first occurrence, as the log wrote it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp:  Option<String>,
+    /// Log target, such as `bevy_render::renderer`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target:     Option<String>,
+    /// Thread of a panic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread:     Option<String>,
+    /// Source location of a panic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location:   Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub backtrace:  Vec<String>,
+    pub count:      usize,
+    /// Line numbers of the first and last occurrence, from 1
+    pub first_line: usize,
+    pub last_line:  usize,
+}
+
+/// A line of Bevy's log output
+pub struct LogLine<'a> {
+    pub timestamp: Option<&'a str>,
+    pub level:     &'a str,
+    pub target:    Option<&'a str>,
+    pub message:   &'a str,
+}
+
+/// Find the errors in a log, in order of first occurrence
+pub fn analyze(content: &str, include_warnings: bool) -> Vec<AppError> {
+    let lines: Vec<String> = content.lines().map(strip_ansi).collect();
+    let mut errors: Vec<AppError> = Vec::new();
+    let mut seen: HashMap<(ErrorKind, Option<String>, String), usize> = HashMap::new();
+
+    let mut index = 0;
+    while index < lines.len() {
+        let (found, next) = if let Some((thread, rest)) = panic_header(&lines[index]) {
+            let (error, next) = parse_panic(&lines, index, thread, rest);
+            (Some(error), next)
+        } else if let Some(log) = parse_log_line(&lines[index]) {
+            parse_logged_error(&lines, index, &log, include_warnings)
+        } else {
+            (None, index + 1)
+        };
+        index = next;
+
+        let Some(error) = found else {
+            continue;
+        };
+        let key = (error.kind, error.location.clone(), error.message.clone());
+        if let Some(&existing) = seen.get(&key) {
+            errors[existing].count += 1;
+            errors[existing].last_line = error.first_line;
+        } else {
+            seen.insert(key, errors.len());
+            errors.push(error);
+        }
+    }
+    errors
+}
+
+/// The thread and the rest of a panic's first line
+pub fn panic_header(line: &str) -> Option<(&str, &str)> {
+    let after = &line[line.find("thread '")? + "thread '".len()..];
+    let (thread, rest) = after.split_once('\'')?;
+    Some((thread, rest.strip_prefix(" panicked at ")?))
+}
+
+/// Parse a panic starting at a line, returning it with the index of the line after it
+fn parse_panic(lines: &[String], start: usize, thread: &str, rest: &str) -> (AppError, usize) {
+    let mut index = start + 1;
+    let (location, mut message) = rest.strip_prefix('\'').map_or_else(
+        || {
+            let location = rest.strip_suffix(':').unwrap_or(rest).to_string();
+            (location, panic_message(lines, &mut index))
+        },
+        |quoted| {
+            // Before Rust 1.73: panicked at 'message', src/main.rs:10:5
+            let (message, location) = quoted.rsplit_once("', ").unwrap_or((quoted, ""));
+            (location.to_string(), vec![message.to_string()])
+        },
+    );
+    while message.last().is_some_and(|line| line.trim().is_empty()) {
+        message.pop();
+    }
+
+    let mut backtrace = Vec::new();
+    while index < lines.len() {
+        let line = &lines[index];
+        if line.starts_with("note: ") {
+            index += 1;
+        } else if line.trim() == "stack backtrace:" {
+            index += 1;
+            while index < lines.len() && lines[index].starts_with(' ') {
+                if backtrace.len() < MAX_BACKTRACE_LINES {
+                    backtrace.push(lines[index].trim().to_string());
+                }
+                index += 1;
+            }
+        } else {
+            break;
+        }
+    }
+
+    let message = message.join("\n");
+    let kind = if is_wgpu_validation(&message) {
+        ErrorKind::WgpuValidation
+    } else {
+        ErrorKind::Panic
+    };
+    let error = AppError {
+        kind,
+        level: "PANIC".to_string(),
+        message,
+        timestamp: None,
+        target: None,
+        thread: Some(thread.to_string()),
+        location: Some(location).filter(|location| !location.is_empty()),
+        backtrace,
+        count: 1,
+        first_line: start + 1,
+        last_line: start + 1,
+    };
+    (error, index)
+}
+
+/// The message lines of a panic from a line on, advancing past them
+fn panic_message(lines: &[String], index: &mut usize) -> Vec<String> {
+    let mut message = Vec::new();
+    while *index < lines.len() && !ends_panic_message(&lines[*index]) {
+        if message.len() < MAX_MESSAGE_LINES {
+            message.push(lines[*index].clone());
+        }
+        *index += 1;
+    }
+    message
+}
+
+/// Whether a line ends the message of a panic
+fn ends_panic_message(line: &str) -> bool {
+    line.starts_with("note: ")
+        || line.trim() == "stack backtrace:"
+        || panic_header(line).is_some()
+        || parse_log_line(line).is_some()
+}
+
+/// Parse an `ERROR` or `WARN` line and the lines continuing it
+fn parse_logged_error(
+    lines: &[String],
+    start: usize,
+    log: &LogLine,
+    include_warnings: bool,
+) -> (Option<AppError>, usize) {
+    let kind = match log.level {
+        "ERROR" => ErrorKind::Error,
+        "WARN" if include_warnings => ErrorKind::Warning,
+        _ => return (None, start + 1),
+    };
+
+    // wgpu errors go on with a blank line and an indented `Caused by:` chain
+    let mut message = vec![log.message.to_string()];
+    let mut index = start + 1;
+    while index < lines.len() {
+        let line = &lines[index];
+        let continues = line.trim().is_empty()
+            || line.starts_with(char::is_whitespace)
+            || line.starts_with("Caused by:");
+        if !continues || panic_header(line).is_some() || parse_log_line(line).is_some() {
+            break;
+        }
+        if message.len() < MAX_MESSAGE_LINES {
+            message.push(line.clone());
+        }
+        index += 1;
+    }
+    while message.last().is_some_and(|line| line.trim().is_empty()) {
+        message.pop();
+    }
+
+    let message = message.join("\n");
+    let kind = if is_wgpu_validation(&message) {
+        ErrorKind::WgpuValidation
+    } else {
+        kind
+    };
+    let error = AppError {
+        kind,
+        level: log.level.to_string(),
+        message,
+        timestamp: log.timestamp.map(String::from),
+        target: log.target.map(String::from),
+        thread: None,
+        location: None,
+        backtrace: Vec::new(),
+        count: 1,
+        first_line: start + 1,
+        last_line: start + 1,
+    };
+    (Some(error), index)
+}
+
+fn is_wgpu_validation(message: &str) -> bool {
+    message.contains("Validation Error")
+}
+
+/// Parse a line of Bevy's log output: `[timestamp] LEVEL [target:] message`
+pub fn parse_log_line(line: &str) -> Option<LogLine<'_>> {
+    let line = line.trim_start();
+    let (first, rest) = line.split_once(char::is_whitespace)?;
+    let (timestamp, level, rest) = if LEVELS.contains(&first) {
+        (None, first, rest)
+    } else if first.starts_with(|c: char| c.is_ascii_digit()) && first.contains(':') {
+        let (level, rest) = rest.trim_start().split_once(char::is_whitespace)?;
+        (Some(first), level, rest)
+    } else {
+        return None;
+    };
+    if !LEVELS.contains(&level) {
+        return None;
+    }
+
+    let rest = rest.trim_start();
+    let (target, message) = match rest.split_once(": ") {
+        Some((target, message)) if !target.contains(char::is_whitespace) => (Some(target), message),
+        _ => (None, rest),
+    };
+    Some(LogLine {
+        timestamp,
+        level,
+        target,
+        message,
+    })
+}
+
+/// Remove ANSI escape sequences, such as colors
+pub fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip `ESC [` parameters up to the final letter
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_finds_panics_logged_errors_and_wgpu_validation() {
+        let log = "\
+=== Bevy BRP MCP Launch Log ===
+2025-06-23T10:00:00.100000Z  INFO bevy_render::renderer: AdapterInfo { name: \"gpu\" }
+\u{1b}[2m2025-06-23T10:00:01.000000Z\u{1b}[0m \u{1b}[31mERROR\u{1b}[0m \u{1b}[2mbevy_asset::server\u{1b}[0m: Path not found: textures/missing.png
+2025-06-23T10:00:02.000000Z ERROR bevy_asset::server: Path not found: textures/missing.png
+2025-06-23T10:00:02.500000Z  WARN bevy_ecs::world: entity 4v1 has no Transform
+2025-06-23T10:00:03.000000Z ERROR wgpu::backend::wgpu_core: Handling wgpu errors as fatal by default
+thread 'main' panicked at /cargo/wgpu-24.0.0/src/backend/wgpu_core.rs:2996:5:
+wgpu error: Validation Error
+
+Caused by:
+    In Device::create_render_pipeline
+      Vertex buffer layout is invalid
+
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+thread 'Compute Task Pool (0)' panicked at src/systems.rs:42:9:
+called `Option::unwrap()` on a `None` value
+stack backtrace:
+   0: rust_begin_unwind
+   1: game::systems::move_player
+             at ./src/systems.rs:42:9
+thread 'main' panicked at 'explicit panic', src/main.rs:7:5
+";
+        let errors = analyze(log, false);
+        assert_eq!(errors.len(), 5);
+
+        assert_eq!(errors[0].kind, ErrorKind::Error);
+        assert_eq!(errors[0].target.as_deref(), Some("bevy_asset::server"));
+        assert_eq!(errors[0].message, "Path not found: textures/missing.png");
+        assert_eq!(
+            errors[0].timestamp.as_deref(),
+            Some("2025-06-23T10:00:01.000000Z")
+        );
+        assert_eq!(
+            (errors[0].count, errors[0].first_line, errors[0].last_line),
+            (2, 3, 4)
+        );
+
+        assert_eq!(errors[2].kind, ErrorKind::WgpuValidation);
+        assert_eq!(errors[2].thread.as_deref(), Some("main"));
+        assert!(
+            errors[2]
+                .message
+                .ends_with("Vertex buffer layout is invalid")
+        );
+
+        assert_eq!(errors[3].kind, ErrorKind::Panic);
+        assert_eq!(errors[3].location.as_deref(), Some("src/systems.rs:42:9"));
+        assert_eq!(
+            errors[3].message,
+            "called `Option::unwrap()` on a `None` value"
+        );
+        assert_eq!(errors[3].backtrace.len(), 3);
+
+        assert_eq!(errors[4].message, "explicit panic");
+        assert_eq!(errors[4].location.as_deref(), Some("src/main.rs:7:5"));
+
+        let with_warnings = analyze(log, true);
+        assert_eq!(with_warnings[1].kind, ErrorKind::Warning);
+        assert_eq!(with_warnings[1].message, "entity 4v1 has no Transform");
+    }
+}
@@ -0,0 +1,203 @@
+//! Filtering log lines by level, time range, keyword and pattern
+//!
+//! Level and time filters apply to whole records: a line of Bevy's log output starts a record
+//! with its level and timestamp, and the lines after it that aren't log lines, such as the rest
+//! of a multi-line message, belong to it. A panic starts an `ERROR` record. Lines before the first
+//! record, like cargo's build output, have no level or time, so those filters leave them out.
+//! Entries of a JSON log are records too.
+
+use chrono::{DateTime, FixedOffset};
+use regex::Regex;
+
+use super::json_log;
+use super::log_analysis::{LEVELS, panic_header, parse_log_line, strip_ansi};
+use crate::error::{Error, Result};
+
+/// Which lines of a log to return
+#[derive(Debug, Default)]
+pub struct LogFilter {
+    /// Lowercased keyword a line must contain
+    keyword:      String,
+    pattern:      Option<Regex>,
+    /// Least severe level kept, as an index into `LEVELS`
+    level:        Option<usize>,
+    since:        Option<DateTime<FixedOffset>>,
+    until:        Option<DateTime<FixedOffset>>,
+    /// Level of the record the last line belongs to
+    record_level: Option<usize>,
+    /// Timestamp of the last record that had one
+    record_time:  Option<DateTime<FixedOffset>>,
+    /// Whether the last line started a record
+    new_record:   bool,
+}
+
+impl LogFilter {
+    /// A filter from the `keyword`, `regex`, `level`, `since` and `until` arguments; empty
+    /// arguments filter nothing
+    pub fn new(
+        keyword: &str,
+        pattern: &str,
+        level: &str,
+        since: &str,
+        until: &str,
+    ) -> Result<Self> {
+        let pattern = (!pattern.is_empty())
+            .then(|| Regex::new(pattern))
+            .transpose()
+            .map_err(|e| error_stack::Report::new(Error::invalid("regex", e.to_string())))?;
+        let level = (!level.is_empty())
+            .then(|| {
+                LEVELS
+                    .iter()
+                    .position(|known| known.eq_ignore_ascii_case(level))
+                    .ok_or_else(|| {
+                        error_stack::Report::new(Error::invalid(
+                            "level",
+                            format!(
+                                "expected one of error, warn, info, debug or trace, got '{level}'"
+                            ),
+                        ))
+                    })
+            })
+            .transpose()?;
+        Ok(Self {
+            keyword: keyword.to_lowercase(),
+            pattern,
+            level,
+            since: parse_time("since", since)?,
+            until: parse_time("until", until)?,
+            ..Self::default()
+        })
+    }
+
+    /// Whether any filter is set
+    pub const fn is_active(&self) -> bool {
+        !self.keyword.is_empty()
+            || self.pattern.is_some()
+            || self.level.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+
+    /// Whether the level or time filters are set, which judge lines by the record they belong to
+    pub const fn filters_records(&self) -> bool {
+        self.level.is_some() || self.since.is_some() || self.until.is_some()
+    }
+
+    /// Whether the last line passed to `matches` started a record
+    pub const fn starts_record(&self) -> bool {
+        self.new_record
+    }
+
+    /// Whether the next line of the log passes, keeping track of the record it belongs to
+    pub fn matches(&mut self, line: &str) -> bool {
+        let clean = strip_ansi(line);
+        self.new_record = true;
+        if let Some(log) = parse_log_line(&clean) {
+            self.start_record(log.level, log.timestamp);
+        } else if let Some(entry) = json_log::parse_entry(&clean) {
+            self.start_record(
+                json_log::level(&entry).unwrap_or_default(),
+                json_log::timestamp(&entry),
+            );
+        } else if panic_header(&clean).is_some() {
+            self.record_level = Some(0);
+        } else {
+            self.new_record = false;
+        }
+
+        if let Some(level) = self.level
+            && self.record_level.is_none_or(|record| record > level)
+        {
+            return false;
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Some(time) = self.record_time else {
+                return false;
+            };
+            if self.since.is_some_and(|since| time < since)
+                || self.until.is_some_and(|until| time > until)
+            {
+                return false;
+            }
+        }
+        (self.keyword.is_empty() || clean.to_lowercase().contains(&self.keyword))
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&clean))
+    }
+
+    /// Start a record at a log line, keeping the last timestamp when the line has none
+    fn start_record(&mut self, level: &str, timestamp: Option<&str>) {
+        self.record_level = LEVELS
+            .iter()
+            .position(|known| known.eq_ignore_ascii_case(level));
+        if let Some(time) =
+            timestamp.and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        {
+            self.record_time = Some(time);
+        }
+    }
+}
+
+/// Parse an RFC 3339 timestamp argument
+fn parse_time(field: &str, value: &str) -> Result<Option<DateTime<FixedOffset>>> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    DateTime::parse_from_rfc3339(value).map(Some).map_err(|e| {
+        error_stack::Report::new(Error::invalid(
+            field,
+            format!("expected an RFC 3339 timestamp such as 2025-06-23T10:00:00Z: {e}"),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_filter_by_level_time_and_pattern() {
+        let log = [
+            "   Compiling game v0.1.0",
+            "2025-06-23T10:00:00.000000Z  INFO bevy_winit: window created",
+            "2025-06-23T10:00:01.000000Z  WARN bevy_ecs: entity 4v1 has no Transform",
+            "2025-06-23T10:00:02.000000Z ERROR wgpu_core: Validation Error",
+            "",
+            "Caused by:",
+            "    In Device::create_render_pipeline",
+            "2025-06-23T10:00:03.000000Z DEBUG game::player: speed 4.0",
+            "thread 'main' panicked at src/main.rs:7:5:",
+        ];
+        let kept = |filter: &mut LogFilter| -> Vec<usize> {
+            (0..log.len()).filter(|&i| filter.matches(log[i])).collect()
+        };
+
+        let mut warnings = LogFilter::new("", "", "warn", "", "").unwrap();
+        assert_eq!(kept(&mut warnings), vec![2, 3, 4, 5, 6, 8]);
+
+        let mut window =
+            LogFilter::new("", "", "", "2025-06-23T10:00:01Z", "2025-06-23T10:00:02.5Z").unwrap();
+        assert_eq!(kept(&mut window), vec![2, 3, 4, 5, 6]);
+
+        let mut pattern = LogFilter::new("ENTITY", r"\d+v\d+", "", "", "").unwrap();
+        assert_eq!(kept(&mut pattern), vec![2]);
+
+        let mut json = LogFilter::new("", "", "error", "2025-06-23T10:00:01Z", "").unwrap();
+        assert!(json.matches(
+            r#"{"timestamp":"2025-06-23T10:00:02Z","level":"ERROR","fields":{"message":"lost"}}"#
+        ));
+        assert!(!json.matches(
+            r#"{"timestamp":"2025-06-23T10:00:02Z","level":"INFO","fields":{"message":"ok"}}"#
+        ));
+
+        assert!(!LogFilter::new("", "", "", "", "").unwrap().is_active());
+        assert!(LogFilter::new("", "(", "", "", "").is_err());
+        assert!(LogFilter::new("", "", "fatal", "", "").is_err());
+        assert!(LogFilter::new("", "", "", "yesterday", "").is_err());
+    }
+}
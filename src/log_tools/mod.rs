@@ -2,6 +2,11 @@
 
 pub mod cleanup_logs;
 pub mod constants;
+pub mod get_app_errors;
+pub mod json_log;
 pub mod list_logs;
+pub mod log_analysis;
+pub mod log_filter;
 pub mod read_log;
-mod support;
+pub mod read_response;
+pub mod support;
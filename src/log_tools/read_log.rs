@@ -1,20 +1,26 @@
+use std::collections::VecDeque;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::Path;
 
 use rmcp::model::CallToolResult;
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
-use serde_json::json;
+use serde_json::{Value, json};
 
 use super::constants::PARAM_FILE_PATH;
-use super::support;
+use super::log_filter::LogFilter;
+use super::{json_log, support};
 use crate::BrpMcpService;
+use crate::constants::{LOG_FORMAT_JSON, LOG_FORMAT_TEXT};
 use crate::error::{Error, report_to_mcp_error};
 use crate::support::params;
 use crate::support::response::ResponseBuilder;
 use crate::support::serialization::json_response_to_result;
 
+/// Lines a call returns when no `max_lines` is given; 0 means all of them
+const DEFAULT_MAX_LINES: u64 = 0;
+
 pub fn handle(
     _service: &BrpMcpService,
     request: &rmcp::model::CallToolRequestParam,
@@ -23,13 +29,17 @@ pub fn handle(
     // Extract parameters
     let filename = params::extract_required_string(request, "filename")?;
     let keyword = params::extract_optional_string(request, "keyword", "");
-    let tail_lines = usize::try_from(params::extract_optional_number(request, "tail_lines", 0)?)
-        .map_err(|_| -> McpError {
-            report_to_mcp_error(&error_stack::Report::new(Error::invalid(
-                "tail_lines",
-                "value too large",
-            )))
-        })?;
+    let mut filter = LogFilter::new(
+        keyword,
+        params::extract_optional_string(request, "regex", ""),
+        params::extract_optional_string(request, "level", ""),
+        params::extract_optional_string(request, "since", ""),
+        params::extract_optional_string(request, "until", ""),
+    )
+    .map_err(|report| report_to_mcp_error(&report))?;
+    let tail_lines = usize_param(request, "tail_lines", 0)?;
+    let max_lines = usize_param(request, "max_lines", DEFAULT_MAX_LINES)?;
+    let offset = params::extract_optional_number(request, "offset", 0)?;
     // Validate filename format for security
     if !support::is_valid_log_filename(filename) {
         return Err(report_to_mcp_error(&error_stack::Report::new(
@@ -48,7 +58,17 @@ pub fn handle(
     }
 
     // Read the log file
-    let (content, metadata) = read_log_file(&log_path, keyword, tail_lines)?;
+    let (read, metadata) = read_log_file(
+        &log_path,
+        &mut filter,
+        ReadWindow {
+            offset,
+            tail_lines,
+            max_lines,
+        },
+    )?;
+    let content = read.lines.join("\n");
+    let entries = json_entries(&read.lines);
 
     let response = ResponseBuilder::success()
         .message(format!("Successfully read log file: {filename}"))
@@ -57,10 +77,17 @@ pub fn handle(
             PARAM_FILE_PATH: log_path.display().to_string(),
             "size_bytes": metadata.len(),
             "size_human": support::format_bytes(metadata.len()),
-            "lines_read": content.lines().count(),
+            "lines_read": read.lines.len(),
             "content": content,
             "filtered_by_keyword": !keyword.is_empty(),
+            "filtered": filter.is_active(),
             "tail_mode": tail_lines > 0,
+            "match_count": read.match_count,
+            "start_offset": offset,
+            "next_offset": read.next_offset,
+            "end_offset": read.end_offset,
+            "format": if entries.is_some() { LOG_FORMAT_JSON } else { LOG_FORMAT_TEXT },
+            "entries": entries,
         }))
         .map_or_else(
             |_| {
@@ -74,11 +101,68 @@ pub fn handle(
     Ok(json_response_to_result(&response))
 }
 
+/// The lines as structured entries when any of them were written as JSON lines
+///
+/// Lines that aren't JSON, such as cargo's output before the app starts, become
+/// `{"text": line}`.
+fn json_entries(lines: &[String]) -> Option<Vec<Value>> {
+    let entries: Vec<_> = lines
+        .iter()
+        .map(|line| json_log::parse_entry(line))
+        .collect();
+    entries.iter().any(Option::is_some).then(|| {
+        entries
+            .into_iter()
+            .zip(lines)
+            .map(|(entry, line)| entry.map_or_else(|| json!({ "text": line }), Value::Object))
+            .collect()
+    })
+}
+
+fn usize_param(
+    request: &rmcp::model::CallToolRequestParam,
+    name: &str,
+    default: u64,
+) -> Result<usize, McpError> {
+    usize::try_from(params::extract_optional_number(request, name, default)?).map_err(
+        |_| -> McpError {
+            report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+                name,
+                "value too large",
+            )))
+        },
+    )
+}
+
+/// Which of the matching lines to return
+#[derive(Debug, Clone, Copy)]
+struct ReadWindow {
+    /// Byte offset to start reading at
+    offset:     u64,
+    /// Return only the last matching lines; 0 returns them from the start
+    tail_lines: usize,
+    /// Most lines to return; 0 returns them all
+    max_lines:  usize,
+}
+
+/// The lines read from a log
+#[derive(Debug, Default)]
+struct LogRead {
+    lines:       Vec<String>,
+    /// Lines from the offset on that passed the filter, returned or not
+    match_count: usize,
+    /// Byte offset to read on from: the first matching line not returned, or with level or time
+    /// filters the start of its record
+    next_offset: Option<u64>,
+    /// Byte offset of the end of the file when read
+    end_offset:  u64,
+}
+
 fn read_log_file(
     path: &Path,
-    keyword: &str,
-    tail_lines: usize,
-) -> Result<(String, std::fs::Metadata), McpError> {
+    filter: &mut LogFilter,
+    window: ReadWindow,
+) -> Result<(LogRead, std::fs::Metadata), McpError> {
     // Get file metadata
     let metadata = std::fs::metadata(path).map_err(|e| {
         report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
@@ -89,41 +173,158 @@ fn read_log_file(
     })?;
 
     // Open the file
-    let file = File::open(path).map_err(|e| {
+    let mut file = File::open(path).map_err(|e| {
         report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
             "open log file",
             path,
             &e,
         )))
     })?;
+    file.seek(SeekFrom::Start(window.offset)).map_err(|e| {
+        report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
+            "seek in log file",
+            path,
+            &e,
+        )))
+    })?;
 
-    let reader = BufReader::new(file);
-    let mut lines: Vec<String> = Vec::new();
+    let mut reader = BufReader::new(file);
+    let mut read = LogRead::default();
+    let mut tail = VecDeque::new();
+    let mut position = window.offset;
+    let mut buffer = Vec::new();
+    // Level and time filters judge a line by its record, which a read starting inside the record
+    // can't see. So with them a full page still takes the rest of the record it ends in, and
+    // `next_offset` points at the start of a record.
+    let finish_records = filter.filters_records();
+    let mut record_start = None;
+    let mut record_in_page = false;
 
-    // Read lines with optional keyword filtering
-    for line_result in reader.lines() {
-        let line = line_result.map_err(|e| {
+    // Read lines with filtering, tracking where each one starts
+    loop {
+        buffer.clear();
+        let length = reader.read_until(b'\n', &mut buffer).map_err(|e| {
             report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
                 "read line from log",
                 path,
                 &e,
             )))
         })?;
+        if length == 0 {
+            break;
+        }
+        let line_start = position;
+        position += length as u64;
+        let line = String::from_utf8_lossy(&buffer);
+        let line = line.trim_end_matches(['\n', '\r']);
+        let matched = filter.matches(line);
+        let page_full = window.max_lines > 0 && read.lines.len() >= window.max_lines;
+        if filter.starts_record() {
+            record_start = Some(line_start);
+            record_in_page = !page_full;
+        }
+        if !matched {
+            continue;
+        }
+
+        read.match_count += 1;
+        if window.tail_lines > 0 {
+            // Apply tail mode if requested
+            tail.push_back(line.to_string());
+            if tail.len() > window.tail_lines {
+                tail.pop_front();
+            }
+        } else if !page_full || (finish_records && record_in_page && read.next_offset.is_none()) {
+            read.lines.push(line.to_string());
+        } else if read.next_offset.is_none() {
+            read.next_offset = if finish_records {
+                record_start.or(Some(line_start))
+            } else {
+                Some(line_start)
+            };
+        }
+    }
+
+    if window.tail_lines > 0 {
+        read.lines = tail.into();
+    }
+    read.end_offset = position;
+    Ok((read, metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    /// Read a log page by page, following `next_offset`
+    fn read_pages(log: &str, filter: impl Fn() -> LogFilter, max_lines: usize) -> Vec<Vec<String>> {
+        let path = std::env::temp_dir().join(format!(
+            "bevy_brp_mcp_test_{}.log",
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::write(&path, log).unwrap();
 
-        // Apply keyword filter if provided
-        if keyword.is_empty() || line.to_lowercase().contains(&keyword.to_lowercase()) {
-            lines.push(line);
+        let mut pages = Vec::new();
+        let mut offset = Some(0);
+        while let Some(start) = offset {
+            let window = ReadWindow {
+                offset: start,
+                tail_lines: 0,
+                max_lines,
+            };
+            let (read, _) = read_log_file(&path, &mut filter(), window).unwrap();
+            pages.push(read.lines);
+            offset = read.next_offset;
         }
+        let _ = std::fs::remove_file(&path);
+        pages
     }
 
-    // Apply tail mode if requested
-    let final_lines = if tail_lines > 0 && tail_lines < lines.len() {
-        let skip_amount = lines.len() - tail_lines;
-        lines.into_iter().skip(skip_amount).collect()
-    } else {
-        lines
-    };
+    #[test]
+    fn test_pages_keep_continuation_lines_with_their_record() {
+        let log = "\
+   Compiling game v0.1.0
+2025-06-23T10:00:00.000000Z  INFO bevy_winit: window created
+2025-06-23T10:00:01.000000Z ERROR wgpu_core: Validation Error
+Caused by:
+    In Device::create_render_pipeline
+2025-06-23T10:00:02.000000Z  WARN bevy_ecs: entity 4v1 has no Transform
+2025-06-23T10:00:03.000000Z ERROR game: lost connection
+Caused by: timeout
+";
+        let warnings = || LogFilter::new("", "", "warn", "", "").unwrap();
+        assert_eq!(
+            read_pages(log, warnings, 1),
+            vec![
+                vec![
+                    "2025-06-23T10:00:01.000000Z ERROR wgpu_core: Validation Error",
+                    "Caused by:",
+                    "    In Device::create_render_pipeline",
+                ],
+                vec!["2025-06-23T10:00:02.000000Z  WARN bevy_ecs: entity 4v1 has no Transform"],
+                vec![
+                    "2025-06-23T10:00:03.000000Z ERROR game: lost connection",
+                    "Caused by: timeout",
+                ],
+            ]
+        );
+
+        // A page continues at the start of the record whose continuation line matched
+        let causes = || LogFilter::new("caused", "", "error", "", "").unwrap();
+        assert_eq!(
+            read_pages(log, causes, 1),
+            vec![vec!["Caused by:"], vec!["Caused by: timeout"]]
+        );
 
-    let content = final_lines.join("\n");
-    Ok((content, metadata))
+        // Without level or time filters pages end at max_lines exactly
+        let keyword = || LogFilter::new("caused", "", "", "", "").unwrap();
+        assert_eq!(
+            read_pages(log, keyword, 1),
+            vec![vec!["Caused by:"], vec!["Caused by: timeout"]]
+        );
+        let all = || LogFilter::new("", "", "", "", "").unwrap();
+        assert_eq!(read_pages(log, all, 4).concat().len(), 8);
+    }
 }
@@ -0,0 +1,92 @@
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+
+use super::constants::PARAM_FILE_PATH;
+use super::support;
+use crate::BrpMcpService;
+use crate::brp_tools::support::response_formatter::spill_file_path;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+
+/// Lines returned when no limit is given
+const DEFAULT_LIMIT: u64 = 200;
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: &rmcp::model::CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let filename = params::extract_required_string(request, "filename")?;
+    let offset = to_usize(
+        params::extract_optional_number(request, "offset", 0)?,
+        "offset",
+    )?;
+    let limit = to_usize(
+        params::extract_optional_number(request, "limit", DEFAULT_LIMIT)?,
+        "limit",
+    )?;
+
+    // Only spilled responses can be read, for security
+    let Some(path) = spill_file_path(filename) else {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::invalid(
+                "filename",
+                "only spilled bevy_brp_mcp responses can be read",
+            ),
+        )));
+    };
+    if !path.exists() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::missing(&format!("response file '{filename}'")),
+        )));
+    }
+
+    let text = std::fs::read_to_string(&path).map_err(|e| {
+        report_to_mcp_error(&error_stack::Report::new(Error::io_failed(
+            "read response file",
+            &path,
+            &e,
+        )))
+    })?;
+    let total_lines = text.lines().count();
+    let content: Vec<&str> = text.lines().skip(offset).take(limit).collect();
+    let next_offset = offset + content.len();
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Read lines {offset}..{next_offset} of {total_lines} from {filename}"
+        ))
+        .data(json!({
+            "filename": filename,
+            PARAM_FILE_PATH: path.display().to_string(),
+            "size_human": support::format_bytes(text.len() as u64),
+            "total_lines": total_lines,
+            "offset": offset,
+            "lines_read": content.len(),
+            "content": content.join("\n"),
+            "next_offset": (next_offset < total_lines).then_some(next_offset),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+fn to_usize(value: u64, name: &str) -> Result<usize, McpError> {
+    usize::try_from(value).map_err(|_| -> McpError {
+        report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+            name,
+            "value too large",
+        )))
+    })
+}
@@ -5,7 +5,9 @@ use std::time::SystemTime;
 use error_stack::Report;
 use rmcp::Error as McpError;
 use serde_json::json;
+use tracing::warn;
 
+use crate::config;
 use crate::error::{Error, report_to_mcp_error};
 use crate::log_tools::constants::PARAM_FILE_PATH;
 
@@ -13,6 +15,9 @@ use crate::log_tools::constants::PARAM_FILE_PATH;
 pub const LOG_PREFIX: &str = "bevy_brp_mcp_";
 pub const LOG_EXTENSION: &str = ".log";
 
+/// Environment variable setting the directory all logs are written to
+pub const LOG_DIR_ENV_VAR: &str = "BRP_MCP_LOG_DIR";
+
 /// Validates if a filename follows the `bevy_brp_mcp` log naming convention
 pub fn is_valid_log_filename(filename: &str) -> bool {
     filename.starts_with(LOG_PREFIX) && filename.ends_with(LOG_EXTENSION)
@@ -62,9 +67,29 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
-/// Gets the log directory (system temp directory)
+/// Gets the log directory: `BRP_MCP_LOG_DIR`, else the `log_dir` configuration key, else the
+/// system temp directory
 pub fn get_log_directory() -> PathBuf {
-    std::env::temp_dir()
+    std::env::var_os(LOG_DIR_ENV_VAR)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| config::current().log_dir)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Gets the log directory for a new log file, creating it if it doesn't exist yet
+///
+/// A directory that can't be created is returned anyway, so creating the file reports the error.
+pub fn create_log_directory() -> PathBuf {
+    let log_dir = get_log_directory();
+    if let Err(e) = fs::create_dir_all(&log_dir) {
+        warn!(
+            "Failed to create log directory {}: {}",
+            log_dir.display(),
+            e
+        );
+    }
+    log_dir
 }
 
 /// Gets the full path for a log file given its filename
@@ -117,22 +142,27 @@ impl LogFileEntry {
     }
 }
 
-/// Iterates over log files in the temp directory with optional filtering
+/// Iterates over log files in the log directory with optional filtering
 /// The filter function receives a `LogFileEntry` and returns true to include it
 pub fn iterate_log_files<F>(filter: F) -> Result<Vec<LogFileEntry>, McpError>
 where
     F: Fn(&LogFileEntry) -> bool,
 {
-    let temp_dir = get_log_directory();
+    let log_dir = get_log_directory();
     let mut log_entries = Vec::new();
 
-    // Read the temp directory
-    let entries = fs::read_dir(&temp_dir).map_err(|e| {
+    // A configured directory that nothing has logged to yet holds no logs
+    if !log_dir.exists() {
+        return Ok(log_entries);
+    }
+
+    // Read the log directory
+    let entries = fs::read_dir(&log_dir).map_err(|e| {
         report_to_mcp_error(
             &Report::new(Error::FileOperation(
-                "Failed to read temp directory".to_string(),
+                "Failed to read log directory".to_string(),
             ))
-            .attach_printable(format!("Path: {}", temp_dir.display()))
+            .attach_printable(format!("Path: {}", log_dir.display()))
             .attach_printable(format!("Error: {e}")),
         )
     })?;
@@ -144,7 +174,7 @@ where
                 &Report::new(Error::FileOperation(
                     "Failed to read directory entry".to_string(),
                 ))
-                .attach_printable(format!("Directory: {}", temp_dir.display()))
+                .attach_printable(format!("Directory: {}", log_dir.display()))
                 .attach_printable(format!("Error: {e}")),
             )
         })?;
@@ -154,8 +184,8 @@ where
 
         // Parse the filename
         if let Some((app_name, timestamp)) = parse_log_filename(filename) {
-            // Get file metadata
-            let metadata = entry.metadata().map_err(|e| {
+            // Get file metadata, following links to logs of attached apps
+            let metadata = fs::metadata(&path).map_err(|e| {
                 report_to_mcp_error(
                     &Report::new(Error::FileOperation(
                         "Failed to get file metadata".to_string(),
@@ -7,26 +7,43 @@
 //! Bevy applications at runtime through a standardized MCP interface.
 
 use std::error::Error;
+use std::io::Write;
 use std::path::PathBuf;
+use std::process::ExitCode;
 use std::sync::{Arc, Mutex};
 
 use rmcp::model::{
-    CallToolRequestParam, CallToolResult, ListToolsResult, PaginatedRequestParam,
-    ServerCapabilities,
+    CallToolRequestParam, CallToolResult, GetPromptRequestParam, GetPromptResult,
+    ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListToolsResult,
+    PaginatedRequestParam, ReadResourceRequestParam, ReadResourceResult, ServerCapabilities,
+    SetLevelRequestParam,
 };
 use rmcp::service::RequestContext;
 use rmcp::transport::stdio;
 use rmcp::{Error as McpError, RoleServer, ServerHandler, ServiceExt};
 
+use crate::brp_tools::bookmarks::BookmarkStore;
+use crate::brp_tools::snapshot::SnapshotStore;
+use crate::brp_tools::support::delta_tracker::DeltaTracker;
+use crate::brp_tools::support::response_formatter;
+
 mod app_tools;
 mod brp_tools;
+mod cli;
+mod config;
 mod constants;
 mod error;
 mod log_tools;
+mod prompts;
 mod registry;
+mod resources;
+mod scheduler;
+mod sse_server;
+mod state;
 mod support;
 mod tool_definitions;
 mod tool_generator;
+mod tool_manifest;
 mod tools;
 
 /// MCP service implementation for Bevy Remote Protocol integration.
@@ -40,12 +57,28 @@ pub struct BrpMcpService {
     /// These paths are used to locate Bevy applications and projects
     /// for scanning and launching operations.
     pub roots: Arc<Mutex<Vec<PathBuf>>>,
+
+    /// Recent query/get/list results of this session, for `delta_token` requests.
+    pub deltas: Arc<Mutex<DeltaTracker>>,
+
+    /// World snapshots taken this session, for `bevy_snapshot_diff`.
+    pub snapshots: Arc<Mutex<SnapshotStore>>,
+
+    /// Entity bookmarks of this session, resolved from `@label` entity parameters.
+    pub bookmarks: Arc<Mutex<BookmarkStore>>,
+
+    /// ID of this session, scoping the state kept outside the service to it.
+    pub session: u64,
 }
 
 impl BrpMcpService {
     fn new() -> Self {
         Self {
-            roots: Arc::new(Mutex::new(Vec::new())),
+            roots:     Arc::new(Mutex::new(Vec::new())),
+            deltas:    Arc::new(Mutex::new(DeltaTracker::new())),
+            snapshots: Arc::new(Mutex::new(SnapshotStore::new())),
+            bookmarks: Arc::new(Mutex::new(BookmarkStore::new())),
+            session:   support::session::new_id(),
         }
     }
 }
@@ -53,7 +86,12 @@ impl BrpMcpService {
 impl ServerHandler for BrpMcpService {
     fn get_info(&self) -> rmcp::model::ServerInfo {
         rmcp::model::ServerInfo {
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .enable_resources()
+                .enable_logging()
+                .build(),
             ..Default::default()
         }
     }
@@ -71,7 +109,59 @@ impl ServerHandler for BrpMcpService {
         request: CallToolRequestParam,
         context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
-        registry::handle_tool_call(self, request, context).await
+        let tool_name = request.name.clone();
+        registry::handle_tool_call(self, request, context)
+            .await
+            .map(|result| response_formatter::spill_large_result(result, &tool_name))
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(prompts::list_prompts())
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        prompts::get_prompt(request).await
+    }
+
+    async fn list_resources(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(resources::list_resources())
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(resources::list_resource_templates())
+    }
+
+    /// Accepted for the log notifications of ended watches, which are always sent at warning level
+    async fn set_level(
+        &self,
+        _request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        Ok(())
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        resources::read_resource(request).await
     }
 }
 
@@ -118,12 +208,11 @@ impl BrpMcpService {
                     .collect();
 
                 // Update our roots
-                let mut roots = self
+                tracing::debug!("Processed roots: {:?}", paths);
+                *self
                     .roots
                     .lock()
-                    .map_err(|e| format!("Failed to acquire roots lock: {e}"))?;
-                *roots = paths;
-                tracing::debug!("Processed roots: {:?}", *roots);
+                    .map_err(|e| format!("Failed to acquire roots lock: {e}"))? = paths;
             }
             Err(e) => {
                 tracing::error!("Failed to send roots/list request: {}", e);
@@ -135,48 +224,108 @@ impl BrpMcpService {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            writeln!(std::io::stderr(), "error: {e}\n\n{}", cli::USAGE)?;
+            return Ok(ExitCode::from(2));
+        }
+    };
+
+    match command {
+        cli::Command::Serve => serve().await?,
+        cli::Command::ServeSse { address } => {
+            let _guard = initialize(None).await;
+            sse_server::serve(address).await?;
+        }
+        cli::Command::Help => writeln!(std::io::stdout(), "{}", cli::USAGE)?,
+        cli::Command::Manifest { format } => {
+            let manifest = serde_json::to_string_pretty(&tool_manifest::build(&format))?;
+            writeln!(std::io::stdout(), "{manifest}")?;
+        }
+        cli::Command::Call { tool, arguments } => {
+            // Keep stderr quiet unless asked, the response is the output
+            let _guard = initialize(Some("bevy_brp_mcp=warn")).await;
+            if !cli::call(tool, arguments).await? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Serve MCP over stdio until the client disconnects
+async fn serve() -> Result<(), Box<dyn Error>> {
+    let _guard = initialize(None).await;
+
+    let service = BrpMcpService::new();
+
+    tracing::info!("Starting stdio server");
+    let server = service.serve(stdio()).await?;
+    server.waiting().await?;
+
+    Ok(())
+}
+
+/// Load the configuration, start logging and open the server's state
+///
+/// Logs go to stderr and a file, filtered by `RUST_LOG` if set, otherwise by `stderr_filter` on
+/// stderr and at debug level in the file. The returned guard flushes the log file when dropped.
+async fn initialize(stderr_filter: Option<&str>) -> tracing_appender::non_blocking::WorkerGuard {
     // Initialize logging to both stderr and a file
+    use tracing_subscriber::Layer;
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::util::SubscriberInitExt;
 
+    // Load the configuration first, it may move the log directory
+    let config_status = config::initialize();
+
     let log_file_name = "mcp_server_debug.log";
 
     // Create file appender
-    let file_appender = tracing_appender::rolling::never("/tmp", log_file_name);
-    let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
+    let file_appender =
+        tracing_appender::rolling::never(log_tools::support::create_log_directory(), log_file_name);
+    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+    // Use RUST_LOG if set, otherwise default to debug level for bevy_brp_mcp
+    let env_filter = |default: &str| {
+        if std::env::var("RUST_LOG").is_ok() {
+            tracing_subscriber::EnvFilter::from_default_env()
+        } else {
+            tracing_subscriber::EnvFilter::new(default)
+        }
+    };
 
     // Create layers
     let file_layer = tracing_subscriber::fmt::layer()
         .with_writer(non_blocking)
-        .with_ansi(false);
+        .with_ansi(false)
+        .with_filter(env_filter("bevy_brp_mcp=debug,info"));
 
-    let stderr_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
-
-    // Use RUST_LOG if set, otherwise default to debug level for bevy_brp_mcp
-    let env_filter = if std::env::var("RUST_LOG").is_ok() {
-        tracing_subscriber::EnvFilter::from_default_env()
-    } else {
-        tracing_subscriber::EnvFilter::new("bevy_brp_mcp=debug,info")
-    };
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(env_filter(
+            stderr_filter.unwrap_or("bevy_brp_mcp=debug,info"),
+        ));
 
     // Combine layers
     tracing_subscriber::registry()
-        .with(env_filter)
         .with(file_layer)
         .with(stderr_layer)
         .init();
 
     tracing::debug!("MCP Server starting with logging enabled");
+    match config_status {
+        Ok(status) => tracing::info!("{status}"),
+        Err(warning) => tracing::warn!("{warning}"),
+    }
 
     // Initialize the watch manager
     brp_tools::watch::support::manager::initialize_watch_manager().await;
 
-    let service = BrpMcpService::new();
-
-    tracing::info!("Starting stdio server");
-    let server = service.serve(stdio()).await?;
-    server.waiting().await?;
+    // Open the persistent state directory
+    state::initialize_state_dir();
 
-    Ok(())
+    guard
 }
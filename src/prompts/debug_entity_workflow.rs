@@ -0,0 +1,325 @@
+//! Guided debugging of one entity
+//!
+//! Given an entity ID or `Name`, the workflow finds the entity and its components, asks the app
+//! which methods it supports with `rpc.discover` and lays out the steps to debug it: check the
+//! connection, read the components, watch them change and mutate them. Each step uses the tool
+//! the app can serve, and steps it can't are named with what's missing, so the assistant doesn't
+//! try methods that will fail.
+
+use std::fmt::Write as _;
+
+use rmcp::Error as McpError;
+use rmcp::model::{
+    GetPromptRequestParam, GetPromptResult, Prompt, PromptArgument, PromptMessage,
+    PromptMessageRole,
+};
+use serde_json::{Value, json};
+
+use super::{invalid_argument, mutation_wizard, port_argument, port_definition, string_argument};
+use crate::brp_tools::bevy_find_entity_by_name::{list_components, named_entities};
+use crate::brp_tools::constants::{JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITY, JSON_FIELD_PORT};
+use crate::brp_tools::support::capabilities;
+use crate::error::{Error, report_to_mcp_error};
+use crate::tools::{
+    BRP_METHOD_EXTRAS_SCREENSHOT, BRP_METHOD_GET, BRP_METHOD_GET_WATCH, BRP_METHOD_INSERT,
+    BRP_METHOD_MUTATE_COMPONENT, TOOL_BEVY_FIND_ENTITY_BY_NAME, TOOL_BEVY_GET,
+    TOOL_BEVY_GET_HIERARCHY, TOOL_BEVY_GET_WATCH, TOOL_BEVY_INSERT, TOOL_BEVY_MUTATE_COMPONENT,
+    TOOL_BRP_EXTRAS_SCREENSHOT, TOOL_BRP_STATUS, TOOL_BRP_STOP_WATCH,
+};
+
+/// Name of the prompt
+pub const PROMPT_NAME: &str = "debug_entity_workflow";
+
+/// Most components written out in the invocations
+const MAX_COMPONENTS: usize = 20;
+
+/// The entity being debugged and what the app can do with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Workflow {
+    entity:     u64,
+    name:       Option<String>,
+    /// Other entities with the same name
+    also_named: Vec<u64>,
+    components: Vec<String>,
+    /// Methods the app reports in `rpc.discover`, `None` if it didn't answer
+    methods:    Option<Vec<String>>,
+    port:       u16,
+}
+
+impl Workflow {
+    fn supports(&self, method: &str) -> bool {
+        self.methods
+            .as_ref()
+            .is_some_and(|methods| methods.iter().any(|supported| supported == method))
+    }
+}
+
+pub fn prompt() -> Prompt {
+    Prompt::new(
+        PROMPT_NAME,
+        Some(
+            "Step-by-step debugging of one entity: checks the connection, reads its components, \
+             watches them and mutates them, using the methods the connected app supports",
+        ),
+        Some(vec![
+            PromptArgument {
+                name:        JSON_FIELD_ENTITY.to_string(),
+                description: Some("Entity ID, or the entity's Name".to_string()),
+                required:    Some(true),
+            },
+            port_definition(),
+        ]),
+    )
+}
+
+pub async fn get(
+    request: &GetPromptRequestParam,
+) -> std::result::Result<GetPromptResult, McpError> {
+    let entity = string_argument(request, JSON_FIELD_ENTITY)
+        .map(String::from)
+        .or_else(|| {
+            // Clients may send the ID as a number
+            request
+                .arguments
+                .as_ref()
+                .and_then(|args| args.get(JSON_FIELD_ENTITY))
+                .and_then(Value::as_u64)
+                .map(|entity| entity.to_string())
+        })
+        .ok_or_else(|| {
+            report_to_mcp_error(&error_stack::Report::new(Error::missing(JSON_FIELD_ENTITY)))
+        })?;
+    let port = port_argument(request)?;
+
+    let methods = capabilities::supported_methods(port).await.ok();
+    let (entity, name, also_named) = if let Ok(id) = entity.parse::<u64>() {
+        (id, None, Vec::new())
+    } else {
+        let named = named_entities(port)
+            .await
+            .map_err(|report| report_to_mcp_error(&report))?;
+        let ids: Vec<u64> = named
+            .into_iter()
+            .filter(|(_, name)| *name == entity)
+            .map(|(id, _)| id)
+            .collect();
+        let Some((id, also_named)) = ids.split_first() else {
+            return Err(invalid_argument(
+                JSON_FIELD_ENTITY,
+                format!(
+                    "no entity is named '{entity}'; search with {TOOL_BEVY_FIND_ENTITY_BY_NAME} \
+                     and regex: true, or give the entity ID"
+                ),
+            ));
+        };
+        (*id, Some(entity), also_named.to_vec())
+    };
+    let components = list_components(entity, port).await;
+
+    let workflow = Workflow {
+        entity,
+        name,
+        also_named,
+        components,
+        methods,
+        port,
+    };
+    Ok(GetPromptResult {
+        description: Some(format!("Debugging workflow for entity {entity}")),
+        messages:    vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            render_workflow(&workflow),
+        )],
+    })
+}
+
+/// Render the workflow as instructions for the assistant
+fn render_workflow(workflow: &Workflow) -> String {
+    let mut text = render_entity(workflow);
+    let steps = if workflow.methods.is_some() {
+        let mut steps = inspect_steps(workflow);
+        steps.extend(change_steps(workflow));
+        steps
+    } else {
+        vec![format!(
+            "The app didn't answer rpc.discover on port {}. Find out why with `{TOOL_BRP_STATUS}` before anything else, and get this prompt again once it's reachable:\n{}",
+            workflow.port,
+            status_call(workflow.port)
+        )]
+    };
+    for (index, step) in steps.iter().enumerate() {
+        let _ = writeln!(text, "{}. {step}\n", index + 1);
+    }
+    text
+}
+
+/// The request and what's known about the entity
+fn render_entity(workflow: &Workflow) -> String {
+    let entity = workflow.entity;
+    let mut text = String::new();
+    let label = workflow.name.as_ref().map_or_else(
+        || format!("entity {entity}"),
+        |name| format!("`{name}` (entity {entity})"),
+    );
+    let _ = writeln!(
+        text,
+        "Help me debug {label} in the Bevy app on port {}.\n",
+        workflow.port
+    );
+    if !workflow.also_named.is_empty() {
+        let _ = writeln!(
+            text,
+            "Other entities have the same name: {}. Check with me that {entity} is the right one.\n",
+            join(&workflow.also_named)
+        );
+    }
+    if workflow.components.is_empty() {
+        let _ = writeln!(
+            text,
+            "No components could be listed for entity {entity}; it may not exist (any more).\n"
+        );
+    } else {
+        let _ = writeln!(
+            text,
+            "It has {} components:\n{}\n",
+            workflow.components.len(),
+            workflow
+                .components
+                .iter()
+                .map(|component| format!("- `{component}`"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    text
+}
+
+/// Steps checking the connection, reading the components and watching them
+fn inspect_steps(workflow: &Workflow) -> Vec<String> {
+    let entity = workflow.entity;
+    let port = workflow.port;
+    let arguments = json!({
+        JSON_FIELD_ENTITY: entity,
+        JSON_FIELD_COMPONENTS: workflow.components.iter().take(MAX_COMPONENTS).collect::<Vec<_>>(),
+        JSON_FIELD_PORT: port,
+    });
+
+    let mut steps = vec![format!(
+        "Check the app is still running with `{TOOL_BRP_STATUS}` (it answered rpc.discover with {} methods):\n{}",
+        workflow.methods.as_ref().map_or(0, Vec::len),
+        status_call(port)
+    )];
+    steps.push(if workflow.supports(BRP_METHOD_GET) {
+        format!(
+            "Read the components and look for values that explain the problem:\n{}",
+            call(TOOL_BEVY_GET, &arguments)
+        )
+    } else {
+        format!(
+            "The app doesn't support `{BRP_METHOD_GET}`, so components can't be read; check that it adds RemotePlugin."
+        )
+    });
+    steps.push(format!(
+        "Check where it sits in the hierarchy with `{TOOL_BEVY_GET_HIERARCHY}` on entity {entity}."
+    ));
+    steps.push(if workflow.supports(BRP_METHOD_GET_WATCH) {
+        format!(
+            "Watch the components while the problem happens, then stop the watch with `{TOOL_BRP_STOP_WATCH}`:\n{}",
+            call(TOOL_BEVY_GET_WATCH, &arguments)
+        )
+    } else {
+        format!(
+            "The app doesn't support `{BRP_METHOD_GET_WATCH}`; to see values change, repeat the `{TOOL_BEVY_GET}` call instead."
+        )
+    });
+    steps
+}
+
+/// Steps trying a fix and confirming it
+fn change_steps(workflow: &Workflow) -> Vec<String> {
+    let mutate = if workflow.supports(BRP_METHOD_MUTATE_COMPONENT) {
+        format!(
+            "Try a fix by changing one field with `{TOOL_BEVY_MUTATE_COMPONENT}`; the `{}` prompt lists each component's paths with example values.",
+            mutation_wizard::PROMPT_NAME
+        )
+    } else if workflow.supports(BRP_METHOD_INSERT) {
+        format!(
+            "The app doesn't support `{BRP_METHOD_MUTATE_COMPONENT}`; try a fix by inserting a whole new component value with `{TOOL_BEVY_INSERT}`."
+        )
+    } else {
+        format!(
+            "The app supports neither `{BRP_METHOD_MUTATE_COMPONENT}` nor `{BRP_METHOD_INSERT}`, so components can't be changed remotely."
+        )
+    };
+    let confirm = if workflow.supports(BRP_METHOD_EXTRAS_SCREENSHOT) {
+        format!(
+            "Confirm the fix by reading the components again, and look at the result with `{TOOL_BRP_EXTRAS_SCREENSHOT}`."
+        )
+    } else {
+        "Confirm the fix by reading the components again. Screenshots need bevy_brp_extras, which this app doesn't have.".to_string()
+    };
+    vec![mutate, confirm]
+}
+
+/// A tool call written out as JSON
+fn call(tool: &str, arguments: &Value) -> String {
+    format!(
+        "```json\n{}\n```",
+        serde_json::to_string_pretty(&json!({ "tool": tool, "arguments": arguments }))
+            .unwrap_or_default()
+    )
+}
+
+fn status_call(port: u16) -> String {
+    call(
+        TOOL_BRP_STATUS,
+        &json!({ "app_name": "<app name>", JSON_FIELD_PORT: port }),
+    )
+}
+
+fn join(entities: &[u64]) -> String {
+    entities
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(methods: Option<&[&str]>) -> Workflow {
+        Workflow {
+            entity:     12,
+            name:       Some("Player".to_string()),
+            also_named: Vec::new(),
+            components: vec!["bevy_transform::components::transform::Transform".to_string()],
+            methods:    methods.map(|methods| methods.iter().map(|m| (*m).to_string()).collect()),
+            port:       15702,
+        }
+    }
+
+    #[test]
+    fn test_steps_follow_the_app_capabilities() {
+        let full = render_workflow(&workflow(Some(&[
+            BRP_METHOD_GET,
+            BRP_METHOD_GET_WATCH,
+            BRP_METHOD_MUTATE_COMPONENT,
+            BRP_METHOD_EXTRAS_SCREENSHOT,
+        ])));
+        assert!(full.contains("`Player` (entity 12)"));
+        assert!(full.contains(&format!("\"tool\": \"{TOOL_BEVY_GET_WATCH}\"")));
+        assert!(full.contains(TOOL_BEVY_MUTATE_COMPONENT));
+        assert!(full.contains(TOOL_BRP_EXTRAS_SCREENSHOT));
+
+        let plain = render_workflow(&workflow(Some(&[BRP_METHOD_GET, BRP_METHOD_INSERT])));
+        assert!(!plain.contains(&format!("\"tool\": \"{TOOL_BEVY_GET_WATCH}\"")));
+        assert!(plain.contains(TOOL_BEVY_INSERT));
+        assert!(plain.contains("doesn't have"));
+
+        let unreachable = render_workflow(&workflow(None));
+        assert!(unreachable.contains("didn't answer rpc.discover"));
+        assert!(!unreachable.contains(TOOL_BEVY_GET));
+    }
+}
@@ -0,0 +1,104 @@
+//! MCP prompts
+//!
+//! Prompts are guided workflows a client can offer its user. Each prompt module exposes
+//! `prompt()` describing the prompt and its arguments and `get()` rendering it, usually after
+//! gathering live data from the app over BRP. Arguments arrive as strings or JSON values and are
+//! read with the helpers below; prompts that read from the app take a `port` argument through
+//! `port_definition()` and `port_argument()`, which falls back to the configured default port.
+
+mod debug_entity_workflow;
+mod mutation_wizard;
+mod query_builder;
+
+use rmcp::Error as McpError;
+use rmcp::model::{GetPromptRequestParam, GetPromptResult, ListPromptsResult, PromptArgument};
+use serde_json::Value;
+
+use crate::brp_tools::constants::JSON_FIELD_PORT;
+use crate::config;
+use crate::error::{Error, report_to_mcp_error};
+
+/// List all prompts offered by the server
+pub fn list_prompts() -> ListPromptsResult {
+    ListPromptsResult {
+        next_cursor: None,
+        prompts:     vec![
+            mutation_wizard::prompt(),
+            query_builder::prompt(),
+            debug_entity_workflow::prompt(),
+        ],
+    }
+}
+
+/// Render a prompt by name
+pub async fn get_prompt(request: GetPromptRequestParam) -> Result<GetPromptResult, McpError> {
+    match request.name.as_str() {
+        mutation_wizard::PROMPT_NAME => mutation_wizard::get(&request).await,
+        query_builder::PROMPT_NAME => query_builder::get(&request).await,
+        debug_entity_workflow::PROMPT_NAME => debug_entity_workflow::get(&request).await,
+        name => Err(invalid_argument(
+            "prompt",
+            format!("unknown prompt '{name}'"),
+        )),
+    }
+}
+
+/// Get a string argument
+fn string_argument<'a>(request: &'a GetPromptRequestParam, name: &str) -> Option<&'a str> {
+    request
+        .arguments
+        .as_ref()
+        .and_then(|args| args.get(name))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Get a numeric argument
+///
+/// Clients usually send prompt arguments as strings, so numeric strings are accepted too.
+fn number_argument(request: &GetPromptRequestParam, name: &str) -> Result<Option<u64>, McpError> {
+    let Some(value) = request.arguments.as_ref().and_then(|args| args.get(name)) else {
+        return Ok(None);
+    };
+    match value {
+        Value::Null => Ok(None),
+        Value::String(text) if text.trim().is_empty() => Ok(None),
+        Value::String(text) => text
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| invalid_argument(name, format!("'{text}' is not a number"))),
+        other => other
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| invalid_argument(name, format!("{other} is not a number"))),
+    }
+}
+
+/// The `port` argument of prompts that read from the app
+fn port_definition() -> PromptArgument {
+    PromptArgument {
+        name:        JSON_FIELD_PORT.to_string(),
+        description: Some(format!(
+            "The BRP port (default: {})",
+            config::default_port()
+        )),
+        required:    Some(false),
+    }
+}
+
+/// Get the `port` argument, or the configured default port
+fn port_argument(request: &GetPromptRequestParam) -> Result<u16, McpError> {
+    number_argument(request, JSON_FIELD_PORT)?.map_or_else(
+        || Ok(config::default_port()),
+        |port| {
+            u16::try_from(port)
+                .map_err(|_| invalid_argument(JSON_FIELD_PORT, "must be below 65536"))
+        },
+    )
+}
+
+fn invalid_argument(name: &str, details: impl std::fmt::Display) -> McpError {
+    report_to_mcp_error(&error_stack::Report::new(Error::invalid(name, details)))
+}
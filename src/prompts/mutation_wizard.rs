@@ -0,0 +1,342 @@
+//! Guided construction of a `bevy_mutate_component` call
+//!
+//! Given a component type, the wizard fetches the registry schema of the type and of its field
+//! types, reads the component's current value from an entity, lists the mutable paths with
+//! their types and current values, proposes an example new value for each and finishes with a
+//! ready-to-run invocation, so agents don't have to find paths and value formats by trial and
+//! error.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use rmcp::Error as McpError;
+use rmcp::model::{
+    GetPromptRequestParam, GetPromptResult, Prompt, PromptArgument, PromptMessage,
+    PromptMessageRole,
+};
+use serde_json::{Value, json};
+
+use super::{invalid_argument, number_argument, port_argument, port_definition, string_argument};
+use crate::brp_tools::constants::{
+    JSON_FIELD_COMPONENT, JSON_FIELD_COMPONENTS, JSON_FIELD_DATA, JSON_FIELD_ENTITY,
+    JSON_FIELD_PATH, JSON_FIELD_PORT, JSON_FIELD_VALUE,
+};
+use crate::brp_tools::request_handler::value_at;
+use crate::brp_tools::support::brp_client::{BrpResult, execute_brp_method};
+use crate::brp_tools::support::mutation_paths::{fetch_schemas, mutation_paths, primitive_kind};
+use crate::error::{Error, Result, report_to_mcp_error};
+use crate::tools::{
+    BRP_METHOD_GET, BRP_METHOD_QUERY, TOOL_BEVY_GET, TOOL_BEVY_MUTATE_COMPONENT,
+    TOOL_BRP_VALIDATE_FORMAT,
+};
+
+/// Name of the prompt
+pub const PROMPT_NAME: &str = "mutation_wizard";
+
+/// Argument naming the component type
+const ARG_TYPE_NAME: &str = "type_name";
+
+/// Most paths listed in the prompt
+const MAX_CANDIDATES: usize = 40;
+
+/// A path that can be passed to `bevy_mutate_component`
+#[derive(Debug, Clone, PartialEq)]
+struct Candidate {
+    path:      String,
+    type_path: String,
+    current:   Option<Value>,
+    example:   Value,
+}
+
+pub fn prompt() -> Prompt {
+    Prompt::new(
+        PROMPT_NAME,
+        Some(
+            "Walk through building a valid bevy_mutate_component call for a component type: \
+             lists mutable paths with their types, current values and example new values",
+        ),
+        Some(vec![
+            PromptArgument {
+                name:        ARG_TYPE_NAME.to_string(),
+                description: Some("Fully-qualified type name of the component".to_string()),
+                required:    Some(true),
+            },
+            PromptArgument {
+                name:        JSON_FIELD_ENTITY.to_string(),
+                description: Some(
+                    "Entity to mutate (default: the first entity with the component)".to_string(),
+                ),
+                required:    Some(false),
+            },
+            port_definition(),
+        ]),
+    )
+}
+
+pub async fn get(
+    request: &GetPromptRequestParam,
+) -> std::result::Result<GetPromptResult, McpError> {
+    let type_name = string_argument(request, ARG_TYPE_NAME).ok_or_else(|| {
+        report_to_mcp_error(&error_stack::Report::new(Error::missing(ARG_TYPE_NAME)))
+    })?;
+    let entity = number_argument(request, JSON_FIELD_ENTITY)?;
+    let port = port_argument(request)?;
+
+    let schemas = fetch_schemas(type_name, Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    if !schemas.contains_key(type_name) {
+        return Err(invalid_argument(
+            ARG_TYPE_NAME,
+            format!("`{type_name}` is not in the registry schema; check the name with bevy_list"),
+        ));
+    }
+
+    let (entity, current) = fetch_current_value(type_name, entity, port)
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?;
+    let candidates = candidate_paths(type_name, &schemas, current.as_ref());
+    let text = render_wizard(type_name, entity, current.as_ref(), &candidates, port);
+
+    Ok(GetPromptResult {
+        description: Some(format!("Mutation wizard for {type_name}")),
+        messages:    vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+/// Entity to mutate and the component's current value on it
+///
+/// Without an entity, the first entity with the component is used.
+async fn fetch_current_value(
+    type_name: &str,
+    entity: Option<u64>,
+    port: u16,
+) -> Result<(Option<u64>, Option<Value>)> {
+    let (method, params) = entity.map_or_else(
+        || {
+            (
+                BRP_METHOD_QUERY,
+                json!({ JSON_FIELD_DATA: { JSON_FIELD_COMPONENTS: [type_name] } }),
+            )
+        },
+        |entity| {
+            (
+                BRP_METHOD_GET,
+                json!({ JSON_FIELD_ENTITY: entity, JSON_FIELD_COMPONENTS: [type_name] }),
+            )
+        },
+    );
+    let BrpResult::Success(Some(data)) =
+        execute_brp_method(method, Some(params), Some(port)).await?
+    else {
+        return Ok((entity, None));
+    };
+
+    let row = match entity {
+        Some(_) => Some(&data),
+        None => data.as_array().and_then(|rows| rows.first()),
+    };
+    let entity = entity.or_else(|| row?.get(JSON_FIELD_ENTITY)?.as_u64());
+    let current = row.and_then(|row| row[JSON_FIELD_COMPONENTS].get(type_name).cloned());
+    Ok((entity, current))
+}
+
+/// All paths below the component, depth first
+fn candidate_paths(
+    type_name: &str,
+    schemas: &HashMap<String, Value>,
+    current: Option<&Value>,
+) -> Vec<Candidate> {
+    mutation_paths(type_name, schemas, MAX_CANDIDATES)
+        .into_iter()
+        .map(|path| {
+            let current = current
+                .and_then(|root| value_at(root, &path.segments))
+                .cloned();
+            Candidate {
+                path: path.path(),
+                example: example_value(
+                    &path.type_path,
+                    schemas.get(&path.type_path),
+                    current.as_ref(),
+                ),
+                type_path: path.type_path,
+                current,
+            }
+        })
+        .collect()
+}
+
+/// Propose a new value that differs from the current one where possible
+fn example_value(type_path: &str, schema: Option<&Value>, current: Option<&Value>) -> Value {
+    // Enums with unit variants: switch to another variant
+    if let Some(variants) = schema.and_then(|schema| schema["oneOf"].as_array()) {
+        return variants
+            .iter()
+            .find(|variant| variant.is_string() && Some(*variant) != current)
+            .or(current)
+            .cloned()
+            .unwrap_or(Value::Null);
+    }
+
+    match primitive_kind(type_path).or_else(|| schema?["type"].as_str()) {
+        Some("float") => json!(
+            current
+                .and_then(Value::as_f64)
+                .map_or(1.0, |value| value + 1.0)
+        ),
+        Some("int" | "uint") => json!(
+            current
+                .and_then(Value::as_i64)
+                .map_or(1, |value| value.saturating_add(1))
+        ),
+        Some("boolean") => json!(!current.and_then(Value::as_bool).unwrap_or(false)),
+        Some("string") => json!("example"),
+        _ => current.cloned().unwrap_or(Value::Null),
+    }
+}
+
+/// Render the wizard as instructions for the assistant
+fn render_wizard(
+    type_name: &str,
+    entity: Option<u64>,
+    current: Option<&Value>,
+    candidates: &[Candidate],
+    port: u16,
+) -> String {
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "Help me mutate the `{type_name}` component with `{TOOL_BEVY_MUTATE_COMPONENT}`.\n"
+    );
+
+    match (entity, current) {
+        (Some(entity), Some(current)) => {
+            let _ = writeln!(
+                text,
+                "Current value on entity {entity}:\n```json\n{}\n```\n",
+                serde_json::to_string_pretty(current).unwrap_or_default()
+            );
+        }
+        (Some(entity), None) => {
+            let _ = writeln!(
+                text,
+                "Entity {entity} has no `{type_name}` value that could be read; the paths below come from the schema only.\n"
+            );
+        }
+        (None, _) => {
+            let _ = writeln!(
+                text,
+                "No entity has this component yet; replace `<entity>` below with the entity to mutate.\n"
+            );
+        }
+    }
+
+    let _ = writeln!(
+        text,
+        "Candidate paths:\n\n| path | type | current | example |\n|---|---|---|---|"
+    );
+    for candidate in candidates {
+        let _ = writeln!(
+            text,
+            "| `{}` | `{}` | {} | `{}` |",
+            candidate.path,
+            candidate.type_path,
+            candidate
+                .current
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |value| format!("`{value}`")),
+            candidate.example
+        );
+    }
+    if candidates.len() >= MAX_CANDIDATES {
+        let _ = writeln!(text, "\n(list truncated to {MAX_CANDIDATES} paths)");
+    }
+
+    let _ = writeln!(
+        text,
+        "\nReady-to-run invocation:\n```json\n{}\n```\n",
+        serde_json::to_string_pretty(&invocation(type_name, entity, candidates, port))
+            .unwrap_or_default()
+    );
+    let _ = writeln!(
+        text,
+        "Steps:\n\
+         1. Ask me which path to change and what the new value should be, suggesting the example above.\n\
+         2. Adjust `path` and `value` in the invocation and call `{TOOL_BEVY_MUTATE_COMPONENT}`.\n\
+         3. Confirm the change with `{TOOL_BEVY_GET}`.\n\
+         4. If the call fails with a format error, check the value with `{TOOL_BRP_VALIDATE_FORMAT}` and retry with its corrected payload."
+    );
+    text
+}
+
+/// The `bevy_mutate_component` call for the first path whose example changes something
+fn invocation(type_name: &str, entity: Option<u64>, candidates: &[Candidate], port: u16) -> Value {
+    let candidate = candidates
+        .iter()
+        .find(|candidate| candidate.current.as_ref() != Some(&candidate.example))
+        .or_else(|| candidates.first());
+
+    let arguments = json!({
+        JSON_FIELD_ENTITY: entity.map_or_else(|| json!("<entity>"), |entity| json!(entity)),
+        JSON_FIELD_COMPONENT: type_name,
+        JSON_FIELD_PATH: candidate.map_or("", |candidate| candidate.path.as_str()),
+        JSON_FIELD_VALUE: candidate.map_or(Value::Null, |candidate| candidate.example.clone()),
+        JSON_FIELD_PORT: port,
+    });
+
+    json!({ "tool": TOOL_BEVY_MUTATE_COMPONENT, "arguments": arguments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schemas() -> HashMap<String, Value> {
+        let field = |type_path: &str| json!({ "type": { "$ref": format!("#/$defs/{type_path}") } });
+        HashMap::from([
+            (
+                "game::Player".to_string(),
+                json!({
+                    "kind": "Struct",
+                    "properties": { "position": field("glam::Vec3"), "mode": field("game::Mode") }
+                }),
+            ),
+            (
+                "glam::Vec3".to_string(),
+                json!({
+                    "kind": "Struct",
+                    "properties": { "x": field("f32"), "y": field("f32"), "z": field("f32") }
+                }),
+            ),
+            (
+                "game::Mode".to_string(),
+                json!({ "kind": "Enum", "type": "string", "oneOf": ["Idle", "Running"] }),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_candidates_list_nested_paths_with_values() {
+        let current = json!({ "position": [1.0, 2.0, 3.0], "mode": "Idle" });
+        let candidates = candidate_paths("game::Player", &schemas(), Some(&current));
+
+        let find = |path: &str| candidates.iter().find(|candidate| candidate.path == path);
+        let y = find(".position.y");
+        assert_eq!(y.and_then(|c| c.current.clone()), Some(json!(2.0)));
+        assert_eq!(y.map(|c| c.example.clone()), Some(json!(3.0)));
+        assert_eq!(
+            find(".position").and_then(|c| c.current.clone()),
+            Some(json!([1.0, 2.0, 3.0]))
+        );
+        assert_eq!(
+            find(".mode").map(|c| c.example.clone()),
+            Some(json!("Running"))
+        );
+
+        let call = invocation("game::Player", Some(7), &candidates, 15702);
+        assert_eq!(call["arguments"][JSON_FIELD_ENTITY], 7);
+        assert_ne!(call["arguments"][JSON_FIELD_PATH], "");
+        assert_eq!(call["arguments"][JSON_FIELD_PORT], 15702);
+    }
+}
@@ -0,0 +1,274 @@
+//! Guided construction of a `bevy_query` call
+//!
+//! Given the component types to read and to filter by, the builder fetches the app's registry
+//! schema, checks the names and the combination the way queries are checked before sending,
+//! replaces misspelled names with the registered type closest to them and finishes with a
+//! ready-to-run invocation and a short reference of the query language.
+
+use std::fmt::Write as _;
+
+use rmcp::Error as McpError;
+use rmcp::model::{
+    GetPromptRequestParam, GetPromptResult, Prompt, PromptArgument, PromptMessage,
+    PromptMessageRole,
+};
+use serde_json::{Value, json};
+
+use super::{port_argument, port_definition, string_argument};
+use crate::brp_tools::constants::{
+    JSON_FIELD_COMPONENTS, JSON_FIELD_PORT, PARAM_DATA, PARAM_FILTER, PARAM_STRICT,
+};
+use crate::brp_tools::request_handler::{
+    KnownTypes, QueryIssue, known_types, registry_schema, validate_query,
+};
+use crate::brp_tools::support::brp_client::BrpResult;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::fuzzy;
+use crate::tools::{TOOL_BEVY_LIST, TOOL_BEVY_QUERY};
+
+/// Name of the prompt
+pub const PROMPT_NAME: &str = "query_builder";
+
+/// Argument naming the component types whose values are returned
+const ARG_COMPONENTS: &str = JSON_FIELD_COMPONENTS;
+
+/// Argument naming the component types entities must have
+const ARG_WITH: &str = "with";
+
+/// Argument naming the component types entities must not have
+const ARG_WITHOUT: &str = "without";
+
+/// How a name given to the builder was resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NameCheck {
+    /// A registered component
+    Registered,
+    /// Replaced by the closest registered component
+    Replaced(String),
+    /// Not a registered component, and nothing close is
+    Unknown,
+}
+
+pub fn prompt() -> Prompt {
+    Prompt::new(
+        PROMPT_NAME,
+        Some(
+            "Build a valid bevy_query call: checks component names against the registry, \
+             corrects near misses and rejects filters no entity can match",
+        ),
+        Some(vec![
+            PromptArgument {
+                name:        ARG_COMPONENTS.to_string(),
+                description: Some(
+                    "Comma-separated type names of the components to read".to_string(),
+                ),
+                required:    Some(true),
+            },
+            PromptArgument {
+                name:        ARG_WITH.to_string(),
+                description: Some(
+                    "Comma-separated type names of components entities must have".to_string(),
+                ),
+                required:    Some(false),
+            },
+            PromptArgument {
+                name:        ARG_WITHOUT.to_string(),
+                description: Some(
+                    "Comma-separated type names of components entities must not have".to_string(),
+                ),
+                required:    Some(false),
+            },
+            port_definition(),
+        ]),
+    )
+}
+
+pub async fn get(
+    request: &GetPromptRequestParam,
+) -> std::result::Result<GetPromptResult, McpError> {
+    let components = list_argument(request, ARG_COMPONENTS);
+    if components.is_empty() {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::missing(ARG_COMPONENTS),
+        )));
+    }
+    let with = list_argument(request, ARG_WITH);
+    let without = list_argument(request, ARG_WITHOUT);
+    let port = port_argument(request)?;
+
+    // The whole registry, so every name can be checked and corrected
+    let BrpResult::Success(Some(_)) = registry_schema(json!({}), Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    else {
+        return Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::BrpCommunication("Failed to fetch the registry schema".to_string()),
+        )));
+    };
+    let known = known_types(Some(port));
+
+    let lists = [
+        (ARG_COMPONENTS, components),
+        (ARG_WITH, with),
+        (ARG_WITHOUT, without),
+    ];
+    let checks: Vec<(&str, String, NameCheck)> = lists
+        .iter()
+        .flat_map(|(list, names)| {
+            names
+                .iter()
+                .map(|name| (*list, name.clone(), check_name(name, &known)))
+        })
+        .collect();
+    let query = build_query(&checks);
+    let issues = validate_query(&query, &known);
+    let text = render_builder(&checks, &query, &issues, port);
+
+    Ok(GetPromptResult {
+        description: Some("Query builder".to_string()),
+        messages:    vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+/// Names of a comma-separated argument
+fn list_argument(request: &GetPromptRequestParam, name: &str) -> Vec<String> {
+    string_argument(request, name)
+        .map(|names| {
+            names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a name is a registered component, or the component it was probably meant to be
+fn check_name(name: &str, known: &KnownTypes) -> NameCheck {
+    if known.components.contains(name) {
+        return NameCheck::Registered;
+    }
+    fuzzy::suggestions(name, known.components.iter().map(String::as_str))
+        .into_iter()
+        .next()
+        .map_or(NameCheck::Unknown, NameCheck::Replaced)
+}
+
+/// `bevy/query` parameters with the resolved names, strict so unknown names fail loudly
+fn build_query(checks: &[(&str, String, NameCheck)]) -> Value {
+    let names = |list: &str| -> Vec<&str> {
+        checks
+            .iter()
+            .filter(|(check_list, ..)| *check_list == list)
+            .map(|(_, name, check)| match check {
+                NameCheck::Replaced(replacement) => replacement.as_str(),
+                NameCheck::Registered | NameCheck::Unknown => name.as_str(),
+            })
+            .collect()
+    };
+    let mut filter = serde_json::Map::new();
+    for list in [ARG_WITH, ARG_WITHOUT] {
+        let list_names = names(list);
+        if !list_names.is_empty() {
+            filter.insert(list.to_string(), json!(list_names));
+        }
+    }
+    json!({
+        PARAM_DATA: { JSON_FIELD_COMPONENTS: names(ARG_COMPONENTS) },
+        PARAM_FILTER: filter,
+        PARAM_STRICT: true,
+    })
+}
+
+/// Render the builder as instructions for the assistant
+fn render_builder(
+    checks: &[(&str, String, NameCheck)],
+    query: &Value,
+    issues: &[QueryIssue],
+    port: u16,
+) -> String {
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "Help me query entities with `{TOOL_BEVY_QUERY}`.\n\nComponent names checked against the registry:\n\n| list | name | result |\n|---|---|---|"
+    );
+    for (list, name, check) in checks {
+        let result = match check {
+            NameCheck::Registered => "registered component".to_string(),
+            NameCheck::Replaced(replacement) => format!("replaced by `{replacement}`"),
+            NameCheck::Unknown => "not a registered component".to_string(),
+        };
+        let _ = writeln!(text, "| {list} | `{name}` | {result} |");
+    }
+
+    if !issues.is_empty() {
+        let _ = writeln!(text, "\nProblems the query still has:\n");
+        for issue in issues {
+            let _ = writeln!(text, "- `{}`: {}", issue.field, issue.message);
+        }
+    }
+
+    let mut arguments = query.clone();
+    arguments[JSON_FIELD_PORT] = json!(port);
+    let _ = writeln!(
+        text,
+        "\nReady-to-run invocation:\n```json\n{}\n```\n",
+        serde_json::to_string_pretty(&json!({ "tool": TOOL_BEVY_QUERY, "arguments": arguments }))
+            .unwrap_or_default()
+    );
+    let _ = writeln!(
+        text,
+        "Query reference:\n\
+         - `data.components`: types whose values are returned; entities without all of them are skipped\n\
+         - `data.option`: types whose values are returned if the entity has them, or `\"all\"`\n\
+         - `data.has`: types reported as present or not, without values\n\
+         - `filter.with` / `filter.without`: types entities must have / must not have\n\
+         - `strict`: fail on unknown type names instead of ignoring them\n\n\
+         Steps:\n\
+         1. Confirm the replacements above with me, and resolve unknown names with `{TOOL_BEVY_LIST}` on an entity that has them.\n\
+         2. Fix the problems listed, if any, then call `{TOOL_BEVY_QUERY}` with the invocation."
+    );
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_names_are_corrected_and_the_query_is_checked() {
+        let components = [
+            "bevy_ecs::name::Name",
+            "bevy_transform::components::transform::Transform",
+        ];
+        let known = KnownTypes {
+            types:      components.iter().map(ToString::to_string).collect(),
+            components: components.iter().map(ToString::to_string).collect(),
+            resources:  BTreeSet::new(),
+            crates:     BTreeSet::new(),
+            complete:   true,
+        };
+        let checks: Vec<(&str, String, NameCheck)> = [
+            (ARG_COMPONENTS, "bevy_transform::Transfrom"),
+            (ARG_WITH, "bevy_ecs::name::Name"),
+            (ARG_WITHOUT, "bevy_ecs::name::Name"),
+        ]
+        .into_iter()
+        .map(|(list, name)| (list, name.to_string(), check_name(name, &known)))
+        .collect();
+        assert_eq!(checks[0].2, NameCheck::Replaced(components[1].to_string()));
+
+        let query = build_query(&checks);
+        assert_eq!(query["data"]["components"], json!([components[1]]));
+        let issues = validate_query(&query, &known);
+        assert_eq!(issues.len(), 1);
+
+        let text = render_builder(&checks, &query, &issues, 15703);
+        assert!(text.contains("replaced by `bevy_transform::components::transform::Transform`"));
+        assert!(text.contains("\"port\": 15703"));
+        assert_eq!(check_name("my_game::Unrelated", &known), NameCheck::Unknown);
+    }
+}
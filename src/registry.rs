@@ -1,13 +1,43 @@
-use rmcp::model::{CallToolRequestParam, CallToolResult, ListToolsResult};
+use futures::future::BoxFuture;
+use rmcp::model::{CallToolRequestParam, CallToolResult, ListToolsResult, Tool};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 
-use crate::brp_tools::{brp_set_debug_mode, brp_status, watch};
+use crate::brp_tools::constants::JSON_FIELD_HOST;
+use crate::brp_tools::support::brp_client;
+use crate::brp_tools::support::component_filter::{self, PARAM_ADMIN_TOKEN};
+use crate::brp_tools::watch::support::manager;
+use crate::brp_tools::{
+    assets, bevy_copy_components, bevy_destroy_recursive, bevy_explain_entity, bevy_export_scene,
+    bevy_find_entity_by_name, bevy_get_diagnostics, bevy_get_hierarchy, bevy_has_components,
+    bevy_list_mutation_paths, bevy_list_systems, bevy_schedule_graph, bevy_send_event,
+    bevy_spawn_from_file, bevy_type_schema, bevy_world_stats, bookmarks, brp_check_extras,
+    brp_corrections_report, brp_describe_tool, brp_discover_ports, brp_execute_batch,
+    brp_list_format_corrections, brp_probe_hosts, brp_run_assertions, brp_set_debug_mode,
+    brp_status, brp_validate_format, bulk, resource_sets, screenshot_series, session_recording,
+    snapshot, states, time_control, transform, visibility, watch,
+};
 // Imports removed - using fully qualified paths in match statement to avoid naming conflicts
+use crate::config::{brp_get_config, brp_set_config};
 use crate::error::{Error, report_to_mcp_error};
+use crate::scheduler::{brp_cancel_scheduled, brp_list_scheduled, brp_schedule_operation};
+use crate::support::session;
 use crate::{BrpMcpService, tool_definitions, tool_generator};
 
 pub fn register_tools() -> ListToolsResult {
+    let mut tools = all_tools();
+    if brp_describe_tool::compact_descriptions_enabled() {
+        brp_describe_tool::compact(&mut tools);
+    }
+
+    ListToolsResult {
+        next_cursor: None,
+        tools,
+    }
+}
+
+/// Every tool with its full description, in alphabetical order
+pub fn all_tools() -> Vec<Tool> {
     let mut tools = vec![];
 
     // Generate tools from declarative definitions
@@ -19,29 +49,141 @@ pub fn register_tools() -> ListToolsResult {
     tools.extend(vec![
         // Core BRP tools (with custom logic)
         brp_status::register_tool(),
-        // Streaming/watch tools (custom logic)
-        watch::bevy_get_watch::register_tool(),
-        watch::bevy_list_watch::register_tool(),
-        watch::brp_stop_watch::register_tool(),
+        brp_describe_tool::register_tool(),
+        bevy_get_hierarchy::register_tool(),
+        bevy_destroy_recursive::register_tool(),
+        bevy_list_mutation_paths::register_tool(),
+        bevy_type_schema::register_tool(),
+        bevy_find_entity_by_name::register_tool(),
+        bevy_copy_components::register_tool(),
+        bevy_has_components::register_tool(),
+        bevy_get_diagnostics::register_tool(),
+        bevy_list_systems::register_tool(),
+        assets::bevy_list_assets::register_tool(),
+        assets::bevy_get_asset::register_tool(),
+        bevy_schedule_graph::register_tool(),
+        bevy_send_event::register_tool(),
+        bevy_export_scene::register_tool(),
+        bevy_explain_entity::register_tool(),
+        bevy_spawn_from_file::register_tool(),
+        bevy_world_stats::register_tool(),
+        bulk::bevy_spawn_many::register_tool(),
+        bulk::bevy_insert_many::register_tool(),
+        bulk::bevy_destroy_many::register_tool(),
+        transform::bevy_translate_entity::register_tool(),
+        transform::bevy_rotate_entity::register_tool(),
+        transform::bevy_scale_entity::register_tool(),
+        visibility::bevy_set_visibility::register_tool(),
+        visibility::bevy_isolate_entity::register_tool(),
+        time_control::bevy_pause_time::register_tool(),
+        time_control::bevy_resume_time::register_tool(),
+        time_control::bevy_step_frames::register_tool(),
+        time_control::bevy_set_time_scale::register_tool(),
+        states::bevy_get_states::register_tool(),
+        states::bevy_set_state::register_tool(),
+        snapshot::bevy_snapshot_create::register_tool(),
+        snapshot::bevy_snapshot_diff::register_tool(),
+        resource_sets::bevy_save_resource_set::register_tool(),
+        resource_sets::bevy_restore_resource_set::register_tool(),
+        bookmarks::bevy_bookmark_entity::register_tool(),
+        bookmarks::bevy_list_bookmarks::register_tool(),
+        bookmarks::bevy_resolve_bookmark::register_tool(),
+        brp_execute_batch::register_tool(),
+        brp_validate_format::register_tool(),
+        brp_probe_hosts::register_tool(),
+        brp_discover_ports::register_tool(),
+        brp_check_extras::register_tool(),
+        brp_run_assertions::register_tool(),
+        brp_corrections_report::register_tool(),
+        brp_list_format_corrections::register_tool(),
+        // Watch history tools (custom logic)
         watch::brp_list_active::register_tool(),
+        watch::brp_read_watch_history::register_tool(),
         // Debug tools
         brp_set_debug_mode::register_tool(),
+        // Scheduler tools
+        brp_schedule_operation::register_tool(),
+        brp_list_scheduled::register_tool(),
+        brp_cancel_scheduled::register_tool(),
+        // Screenshot series tools
+        screenshot_series::brp_start_screenshot_series::register_tool(),
+        screenshot_series::brp_stop_screenshot_series::register_tool(),
+        screenshot_series::brp_list_screenshot_series::register_tool(),
+        session_recording::brp_start_session_recording::register_tool(),
+        session_recording::brp_stop_session_recording::register_tool(),
+        session_recording::brp_export_session::register_tool(),
+        session_recording::brp_replay_session::register_tool(),
+        // Configuration tools
+        brp_get_config::register_tool(),
+        brp_set_config::register_tool(),
     ]);
 
     // Sort all tools alphabetically by name for consistent ordering
     tools.sort_by(|a, b| a.name.cmp(&b.name));
-
-    ListToolsResult {
-        next_cursor: None,
-        tools,
-    }
+    tools
 }
 
 pub async fn handle_tool_call(
     service: &BrpMcpService,
-    request: CallToolRequestParam,
+    mut request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    // A host argument applies to every BRP request made while handling this call. It is removed
+    // so that tools passing their arguments through to BRP don't forward it.
+    let host = request
+        .arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(JSON_FIELD_HOST))
+        .filter(|host| !host.is_null());
+
+    // An admin token lifts the component allow/deny filter for this call and the calls it makes
+    let bypass_filter = request
+        .arguments
+        .as_mut()
+        .and_then(|arguments| arguments.remove(PARAM_ADMIN_TOKEN))
+        .filter(|token| !token.is_null())
+        .map(|token| component_filter::check_admin_token(&token))
+        .transpose()
+        .map_err(|report| report_to_mcp_error(&report))?
+        .is_some();
+
+    // Watches started by this call notify the client that made it
+    let peer = Some(context.peer.clone());
+    let call = async {
+        match host {
+            Some(host) => {
+                let host = host
+                    .as_str()
+                    .ok_or_else(|| Error::invalid(JSON_FIELD_HOST, "must be a string"))
+                    .map_err(error_stack::Report::new)
+                    .and_then(brp_client::validate_host)
+                    .map_err(|report| report_to_mcp_error(&report))?;
+                brp_client::with_host(host, route_tool_call(service, request, context)).await
+            }
+            None => route_tool_call(service, request, context).await,
+        }
+    };
+    let call = manager::with_calling_peer(peer, call);
+    let call = component_filter::scope(
+        bypass_filter || component_filter::is_bypassed(),
+        Box::pin(call),
+    );
+    session::scope(service.session, call).await
+}
+
+/// Route a tool call to its handler
+#[allow(clippy::too_many_lines)]
+async fn route_tool_call(
+    service: &BrpMcpService,
+    mut request: CallToolRequestParam,
     context: RequestContext<RoleServer>,
 ) -> Result<CallToolResult, McpError> {
+    // Bookmark labels stand in for entity IDs, so every tool's parameters see plain IDs
+    if let Some(arguments) = request.arguments.as_mut() {
+        bookmarks::resolve_labels(&service.bookmarks, arguments)
+            .map_err(|report| report_to_mcp_error(&report))?;
+    }
+
     // Check if this is one of the declaratively defined tools
     let all_tools = tool_definitions::get_all_tools();
     if let Some(def) = all_tools.iter().find(|d| d.name == request.name) {
@@ -54,26 +196,203 @@ pub async fn handle_tool_call(
         name if name == crate::tools::TOOL_BRP_STATUS => {
             brp_status::handle(service, request, context).await
         }
-
-        // Streaming/watch tools (custom logic)
-        name if name == crate::tools::TOOL_BEVY_GET_WATCH => {
-            watch::bevy_get_watch::handle(service, request, context).await
+        name if name == crate::tools::TOOL_BEVY_GET_HIERARCHY => {
+            bevy_get_hierarchy::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_DESTROY_RECURSIVE => {
+            bevy_destroy_recursive::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_LIST_MUTATION_PATHS => {
+            bevy_list_mutation_paths::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_TYPE_SCHEMA => {
+            bevy_type_schema::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_FIND_ENTITY_BY_NAME => {
+            bevy_find_entity_by_name::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_COPY_COMPONENTS => {
+            bevy_copy_components::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_LIST_ASSETS => {
+            assets::bevy_list_assets::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_GET_ASSET => {
+            assets::bevy_get_asset::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_HAS_COMPONENTS => {
+            bevy_has_components::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_GET_DIAGNOSTICS => {
+            bevy_get_diagnostics::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_LIST_SYSTEMS => {
+            bevy_list_systems::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SCHEDULE_GRAPH => {
+            bevy_schedule_graph::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SEND_EVENT => {
+            bevy_send_event::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_WORLD_STATS => {
+            bevy_world_stats::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_EXPORT_SCENE => {
+            bevy_export_scene::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_EXPLAIN_ENTITY => {
+            bevy_explain_entity::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SPAWN_FROM_FILE => {
+            bevy_spawn_from_file::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SPAWN_MANY => {
+            bulk::bevy_spawn_many::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_INSERT_MANY => {
+            bulk::bevy_insert_many::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_DESTROY_MANY => {
+            bulk::bevy_destroy_many::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_TRANSLATE_ENTITY => {
+            transform::bevy_translate_entity::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_ROTATE_ENTITY => {
+            transform::bevy_rotate_entity::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SCALE_ENTITY => {
+            transform::bevy_scale_entity::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SET_VISIBILITY => {
+            visibility::bevy_set_visibility::handle(service, request, context).await
         }
-        name if name == crate::tools::TOOL_BEVY_LIST_WATCH => {
-            watch::bevy_list_watch::handle(service, request, context).await
+        name if name == crate::tools::TOOL_BEVY_ISOLATE_ENTITY => {
+            visibility::bevy_isolate_entity::handle(service, request, context).await
         }
-        name if name == crate::tools::TOOL_BRP_STOP_WATCH => {
-            watch::brp_stop_watch::handle(service, request, context).await
+        name if name == crate::tools::TOOL_BEVY_PAUSE_TIME => {
+            time_control::bevy_pause_time::handle(service, request, context).await
         }
+        name if name == crate::tools::TOOL_BEVY_RESUME_TIME => {
+            time_control::bevy_resume_time::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_STEP_FRAMES => {
+            time_control::bevy_step_frames::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SET_TIME_SCALE => {
+            time_control::bevy_set_time_scale::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_GET_STATES => {
+            states::bevy_get_states::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SET_STATE => {
+            states::bevy_set_state::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SNAPSHOT_CREATE => {
+            snapshot::bevy_snapshot_create::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SNAPSHOT_DIFF => {
+            snapshot::bevy_snapshot_diff::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_SAVE_RESOURCE_SET => {
+            resource_sets::bevy_save_resource_set::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_RESTORE_RESOURCE_SET => {
+            resource_sets::bevy_restore_resource_set::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BEVY_BOOKMARK_ENTITY => {
+            bookmarks::bevy_bookmark_entity::handle(service, request, context)
+        }
+        name if name == crate::tools::TOOL_BEVY_LIST_BOOKMARKS => {
+            bookmarks::bevy_list_bookmarks::handle(service, request, context)
+        }
+        name if name == crate::tools::TOOL_BEVY_RESOLVE_BOOKMARK => {
+            bookmarks::bevy_resolve_bookmark::handle(service, request, context)
+        }
+        name if name == crate::tools::TOOL_BRP_DESCRIBE_TOOL => {
+            brp_describe_tool::handle(service, &request, context)
+        }
+        name if name == crate::tools::TOOL_BRP_EXECUTE_BATCH => {
+            brp_execute_batch::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_VALIDATE_FORMAT => {
+            brp_validate_format::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_PROBE_HOSTS => {
+            brp_probe_hosts::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_DISCOVER_PORTS => {
+            brp_discover_ports::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_CHECK_EXTRAS => {
+            brp_check_extras::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_RUN_ASSERTIONS => {
+            brp_run_assertions::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_CORRECTIONS_REPORT => {
+            brp_corrections_report::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_LIST_FORMAT_CORRECTIONS => {
+            brp_list_format_corrections::handle(service, request, context)
+        }
+
+        // Watch history tools (custom logic)
         name if name == crate::tools::TOOL_BRP_LIST_ACTIVE_WATCHES => {
             watch::brp_list_active::handle(service, request, context).await
         }
+        name if name == crate::tools::TOOL_BRP_READ_WATCH_HISTORY => {
+            watch::brp_read_watch_history::handle(service, request, context)
+        }
 
         // Debug tools
         name if name == crate::tools::TOOL_BRP_SET_DEBUG_MODE => {
             brp_set_debug_mode::handle_set_debug_mode(service, request, context).await
         }
 
+        // Scheduler tools
+        name if name == crate::tools::TOOL_BRP_SCHEDULE_OPERATION => {
+            brp_schedule_operation::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_LIST_SCHEDULED => {
+            brp_list_scheduled::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_CANCEL_SCHEDULED => {
+            brp_cancel_scheduled::handle(service, request, context).await
+        }
+
+        // Screenshot series tools
+        name if name == crate::tools::TOOL_BRP_START_SCREENSHOT_SERIES => {
+            screenshot_series::brp_start_screenshot_series::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_STOP_SCREENSHOT_SERIES => {
+            screenshot_series::brp_stop_screenshot_series::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_LIST_SCREENSHOT_SERIES => {
+            screenshot_series::brp_list_screenshot_series::handle(service, request, context).await
+        }
+        name if name == crate::tools::TOOL_BRP_START_SESSION_RECORDING => {
+            session_recording::brp_start_session_recording::handle(service, request, context)
+        }
+        name if name == crate::tools::TOOL_BRP_STOP_SESSION_RECORDING => {
+            session_recording::brp_stop_session_recording::handle(service, request, context)
+        }
+        name if name == crate::tools::TOOL_BRP_EXPORT_SESSION => {
+            session_recording::brp_export_session::handle(service, request, context)
+        }
+        name if name == crate::tools::TOOL_BRP_REPLAY_SESSION => {
+            session_recording::brp_replay_session::handle(service, request, context).await
+        }
+
+        // Configuration tools
+        name if name == crate::tools::TOOL_BRP_GET_CONFIG => {
+            Ok(brp_get_config::handle(service, request, context))
+        }
+        name if name == crate::tools::TOOL_BRP_SET_CONFIG => {
+            brp_set_config::handle(service, request, context)
+        }
+
         _ => {
             let tool_name = &request.name;
             Err(report_to_mcp_error(
@@ -86,6 +405,18 @@ pub async fn handle_tool_call(
     }
 }
 
+/// Boxed, owned variant of [`handle_tool_call`] for tools invoked from background tasks
+///
+/// Tasks spawned while handling a tool call (such as the scheduler) cannot hold the
+/// `handle_tool_call` future directly, because its `Send`-ness would depend on itself.
+pub fn dispatch_tool_call(
+    service: BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> BoxFuture<'static, Result<CallToolResult, McpError>> {
+    Box::pin(async move { handle_tool_call(&service, request, context).await })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +440,25 @@ mod tests {
         assert!(len > 20, "Expected at least 20 tools, got {len}");
     }
 
+    #[test]
+    fn test_tools_are_registered_once() {
+        // A tool defined both declaratively and by hand would be listed twice, and calls would
+        // only ever reach the declarative definition
+        let tools = all_tools();
+        let mut names: Vec<&str> = tools.iter().map(|t| t.name.as_ref()).collect();
+        names.sort_unstable();
+        let duplicates: Vec<&str> = names
+            .windows(2)
+            .filter(|pair| pair[0] == pair[1])
+            .map(|pair| pair[0])
+            .collect();
+
+        assert!(
+            duplicates.is_empty(),
+            "Tools registered more than once: {duplicates:?}"
+        );
+    }
+
     #[test]
     fn test_tool_names_have_proper_prefixes() {
         let result = register_tools();
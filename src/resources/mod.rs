@@ -0,0 +1,105 @@
+//! MCP resources
+//!
+//! Resources are read-only documents a client can fetch and cache instead of calling a tool.
+//! URIs have the form `brp://{port}/...`. Each resource module exposes the resources and
+//! templates it serves and `read()`, which returns `None` for paths it doesn't own.
+
+mod registry_schema;
+
+use rmcp::Error as McpError;
+use rmcp::model::{
+    ListResourceTemplatesResult, ListResourcesResult, ReadResourceRequestParam, ReadResourceResult,
+    ResourceContents,
+};
+use serde_json::Value;
+
+use crate::error::{Error, report_to_mcp_error};
+
+/// Scheme of the URIs served by this server
+const URI_SCHEME: &str = "brp://";
+
+/// MIME type of JSON resources
+const MIME_TYPE_JSON: &str = "application/json";
+
+/// List the concrete resources offered by the server
+pub fn list_resources() -> ListResourcesResult {
+    ListResourcesResult {
+        next_cursor: None,
+        resources:   registry_schema::resources(),
+    }
+}
+
+/// List the URI templates of the resources offered by the server
+pub fn list_resource_templates() -> ListResourceTemplatesResult {
+    ListResourceTemplatesResult {
+        next_cursor:        None,
+        resource_templates: registry_schema::templates(),
+    }
+}
+
+/// Read a resource by URI
+pub async fn read_resource(
+    request: ReadResourceRequestParam,
+) -> Result<ReadResourceResult, McpError> {
+    let (port, path) = parse_uri(&request.uri)?;
+    let contents = registry_schema::read(port, &path)
+        .await?
+        .ok_or_else(|| unknown_resource(&request.uri))?;
+    Ok(ReadResourceResult {
+        contents: vec![json_contents(&request.uri, &contents)?],
+    })
+}
+
+/// Split a `brp://{port}/...` URI into its port and path segments
+fn parse_uri(uri: &str) -> Result<(u16, Vec<&str>), McpError> {
+    let rest = uri
+        .strip_prefix(URI_SCHEME)
+        .ok_or_else(|| unknown_resource(uri))?;
+    let mut segments = rest.split('/').filter(|segment| !segment.is_empty());
+    let port = segments
+        .next()
+        .and_then(|port| port.parse().ok())
+        .ok_or_else(|| unknown_resource(uri))?;
+    Ok((port, segments.collect()))
+}
+
+fn json_contents(uri: &str, value: &Value) -> Result<ResourceContents, McpError> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| {
+        report_to_mcp_error(&error_stack::Report::new(Error::General(format!(
+            "Failed to serialize resource {uri}: {e}"
+        ))))
+    })?;
+    Ok(ResourceContents::TextResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(MIME_TYPE_JSON.to_string()),
+        text,
+    })
+}
+
+fn unknown_resource(uri: &str) -> McpError {
+    report_to_mcp_error(&error_stack::Report::new(Error::invalid(
+        "resource URI",
+        format!("'{uri}' does not match any resource template"),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_uris_are_split_into_port_and_path() {
+        assert_eq!(
+            parse_uri("brp://15702/schema").unwrap(),
+            (15702, vec!["schema"])
+        );
+        assert_eq!(
+            parse_uri("brp://15703/schema/bevy_transform/").unwrap(),
+            (15703, vec!["schema", "bevy_transform"])
+        );
+        assert!(parse_uri("brp://localhost/schema").is_err());
+        assert!(parse_uri("file:///schema").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Live registry schema resources
+//!
+//! - `brp://{port}/schema`: the full `bevy/registry/schema` result, keyed by type path
+//! - `brp://{port}/schema/{crate}`: only the types of one crate
+//! - `brp://{port}/crates`: the crates in the registry with their type counts
+//!
+//! Large registries run to megabytes, so clients can read the crate index first and then
+//! fetch only the crates they need. Responses go through the shared schema cache and the
+//! component allow/deny lists, like the `bevy_registry_schema` tool.
+
+use std::collections::BTreeMap;
+
+use rmcp::Error as McpError;
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+use serde_json::{Map, Value, json};
+
+use super::{MIME_TYPE_JSON, URI_SCHEME};
+use crate::brp_tools::constants::DEFAULT_BRP_PORT;
+use crate::brp_tools::request_handler::{extract_crate_name, registry_schema};
+use crate::brp_tools::support::brp_client::BrpResult;
+use crate::error::{Error, report_to_mcp_error};
+
+/// Path segment of the schema resources
+const SCHEMA_SEGMENT: &str = "schema";
+
+/// Path segment of the crate index
+const CRATES_SEGMENT: &str = "crates";
+
+/// The schema and crate index of the default port
+pub fn resources() -> Vec<Resource> {
+    vec![
+        RawResource {
+            uri:         format!("{URI_SCHEME}{DEFAULT_BRP_PORT}/{SCHEMA_SEGMENT}"),
+            name:        "Registry schema".to_string(),
+            description: Some(format!(
+                "Registry schema of every reflected type of the app on port {DEFAULT_BRP_PORT}"
+            )),
+            mime_type:   Some(MIME_TYPE_JSON.to_string()),
+            size:        None,
+        }
+        .no_annotation(),
+        RawResource {
+            uri:         format!("{URI_SCHEME}{DEFAULT_BRP_PORT}/{CRATES_SEGMENT}"),
+            name:        "Registry crates".to_string(),
+            description: Some(format!(
+                "Crates in the type registry of the app on port {DEFAULT_BRP_PORT}, with type counts"
+            )),
+            mime_type:   Some(MIME_TYPE_JSON.to_string()),
+            size:        None,
+        }
+        .no_annotation(),
+    ]
+}
+
+/// Templates for any port and crate
+pub fn templates() -> Vec<ResourceTemplate> {
+    let template = |uri_template: String, name: &str, description: &str| {
+        RawResourceTemplate {
+            uri_template,
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            mime_type: Some(MIME_TYPE_JSON.to_string()),
+        }
+        .no_annotation()
+    };
+    vec![
+        template(
+            format!("{URI_SCHEME}{{port}}/{SCHEMA_SEGMENT}"),
+            "Registry schema",
+            "Registry schema of every reflected type, keyed by type path",
+        ),
+        template(
+            format!("{URI_SCHEME}{{port}}/{SCHEMA_SEGMENT}/{{crate}}"),
+            "Registry schema of one crate",
+            "Registry schema of the types of one crate, e.g. bevy_transform",
+        ),
+        template(
+            format!("{URI_SCHEME}{{port}}/{CRATES_SEGMENT}"),
+            "Registry crates",
+            "Crates in the type registry with their type counts, for fetching the schema crate by crate",
+        ),
+    ]
+}
+
+/// Read a schema resource, or `None` if the path is not one
+pub async fn read(port: u16, path: &[&str]) -> Result<Option<Value>, McpError> {
+    match path {
+        [SCHEMA_SEGMENT] => fetch(port, json!({})).await.map(Some),
+        [SCHEMA_SEGMENT, crate_name] => fetch(port, json!({ "with_crates": [crate_name] }))
+            .await
+            .map(Some),
+        [CRATES_SEGMENT] => Ok(Some(crate_index(&fetch(port, json!({})).await?))),
+        _ => Ok(None),
+    }
+}
+
+async fn fetch(port: u16, params: Value) -> Result<Value, McpError> {
+    match registry_schema(params, Some(port))
+        .await
+        .map_err(|report| report_to_mcp_error(&report))?
+    {
+        BrpResult::Success(data) => Ok(data.unwrap_or_else(|| json!({}))),
+        BrpResult::Error(e) => Err(report_to_mcp_error(&error_stack::Report::new(
+            Error::BrpCommunication(format!(
+                "Failed to read the registry schema on port {port}: {}",
+                e.message
+            )),
+        ))),
+    }
+}
+
+/// Number of types per crate in a schema keyed by type path
+fn crate_index(schemas: &Value) -> Value {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for type_path in schemas.as_object().into_iter().flat_map(Map::keys) {
+        *counts.entry(extract_crate_name(type_path)).or_default() += 1;
+    }
+    json!(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crate_index_counts_types() {
+        let schemas = json!({
+            "bevy_transform::components::transform::Transform": {},
+            "bevy_transform::components::global_transform::GlobalTransform": {},
+            "game::Player": {},
+        });
+        assert_eq!(
+            crate_index(&schemas),
+            json!({ "bevy_transform": 2, "game": 1 })
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Cancel a scheduled operation
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::{Value, json};
+
+use super::{JSON_FIELD_SCHEDULE_ID, SCHEDULER};
+use crate::BrpMcpService;
+use crate::error::report_to_mcp_error;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::support::{params, schema};
+use crate::tools::{DESC_BRP_CANCEL_SCHEDULED, TOOL_BRP_CANCEL_SCHEDULED};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_CANCEL_SCHEDULED.into(),
+        description:  DESC_BRP_CANCEL_SCHEDULED.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_number_property(
+                JSON_FIELD_SCHEDULE_ID,
+                "The schedule ID returned from brp_schedule_operation",
+                true,
+            )
+            .build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let arguments = Value::Object(request.arguments.unwrap_or_default());
+    let schedule_id =
+        params::extract_required_u32(&arguments, JSON_FIELD_SCHEDULE_ID, "schedule_id")?;
+
+    // Cancel the operation and release lock immediately
+    let cancelled = SCHEDULER
+        .lock()
+        .await
+        .cancel(schedule_id)
+        .map_err(|report| report_to_mcp_error(&report))?;
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Cancelled scheduled operation {schedule_id} after {} runs",
+            cancelled.runs
+        ))
+        .data(json!({
+            JSON_FIELD_SCHEDULE_ID: schedule_id,
+            "operation": cancelled,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,48 @@
+//! List pending scheduled operations
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde_json::json;
+
+use super::SCHEDULER;
+use crate::BrpMcpService;
+use crate::brp_tools::constants::JSON_FIELD_COUNT;
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{DESC_BRP_LIST_SCHEDULED, TOOL_BRP_LIST_SCHEDULED};
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_LIST_SCHEDULED.into(),
+        description:  DESC_BRP_LIST_SCHEDULED.into(),
+        input_schema: schema::SchemaBuilder::new().build(),
+    }
+}
+
+pub async fn handle(
+    _service: &BrpMcpService,
+    _request: CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    // Get scheduled operations and release lock immediately
+    let operations = SCHEDULER.lock().await.list();
+
+    let response = ResponseBuilder::success()
+        .message(format!("Found {} scheduled operations", operations.len()))
+        .data(json!({
+            "operations": operations,
+            JSON_FIELD_COUNT: operations.len(),
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
@@ -0,0 +1,203 @@
+//! Schedule a delayed or periodic tool invocation
+
+use std::time::Duration;
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use rmcp::{Error as McpError, RoleServer};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use super::{CronSchedule, JSON_FIELD_SCHEDULE_ID, ScheduleSpec, Trigger};
+use crate::BrpMcpService;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::response::ResponseBuilder;
+use crate::support::schema;
+use crate::support::serialization::json_response_to_result;
+use crate::tools::{
+    DESC_BRP_SCHEDULE_OPERATION, TOOL_BRP_CANCEL_SCHEDULED, TOOL_BRP_LIST_SCHEDULED,
+    TOOL_BRP_SCHEDULE_OPERATION,
+};
+
+#[derive(Debug, Deserialize)]
+struct ScheduleOperationParams {
+    tool:             String,
+    #[serde(default)]
+    arguments:        Map<String, Value>,
+    interval_seconds: Option<u64>,
+    cron:             Option<String>,
+    #[serde(default)]
+    delay_seconds:    u64,
+    duration_seconds: Option<u64>,
+    max_runs:         Option<u64>,
+}
+
+pub fn register_tool() -> Tool {
+    Tool {
+        name:         TOOL_BRP_SCHEDULE_OPERATION.into(),
+        description:  DESC_BRP_SCHEDULE_OPERATION.into(),
+        input_schema: schema::SchemaBuilder::new()
+            .add_string_property("tool", "Name of the tool to invoke (e.g., 'bevy_spawn')", true)
+            .add_any_property("arguments", "Arguments to pass to the tool", false)
+            .add_number_property(
+                "interval_seconds",
+                "Run every N seconds (mutually exclusive with cron)",
+                false,
+            )
+            .add_string_property(
+                "cron",
+                "Five-field cron expression 'minute hour day-of-month month day-of-week' (mutually exclusive with interval_seconds)",
+                false,
+            )
+            .add_number_property(
+                "delay_seconds",
+                "Seconds to wait before the first run (default: 0)",
+                false,
+            )
+            .add_number_property(
+                "duration_seconds",
+                "Stop scheduling new runs after this many seconds",
+                false,
+            )
+            .add_number_property("max_runs", "Stop after this many runs", false)
+            .build(),
+    }
+}
+
+pub async fn handle(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let args = request.arguments.unwrap_or_default();
+    let params: ScheduleOperationParams =
+        serde_json::from_value(Value::Object(args)).map_err(|e| -> McpError {
+            report_to_mcp_error(
+                &error_stack::Report::new(Error::ParameterExtraction(
+                    "Invalid parameters for brp_schedule_operation".to_string(),
+                ))
+                .attach_printable(format!("Deserialization error: {e}")),
+            )
+        })?;
+
+    let spec = build_spec(params).map_err(|report| report_to_mcp_error(&report))?;
+    let info = super::schedule(spec, service, &context).await;
+
+    let response = ResponseBuilder::success()
+        .message(format!(
+            "Scheduled {} ({}) as operation {}",
+            info.tool, info.trigger, info.schedule_id
+        ))
+        .data(json!({
+            JSON_FIELD_SCHEDULE_ID: info.schedule_id,
+            "operation": info,
+        }))
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    Ok(json_response_to_result(&response))
+}
+
+/// Validate the parameters and turn them into a schedule
+fn build_spec(params: ScheduleOperationParams) -> crate::error::Result<ScheduleSpec> {
+    if [
+        TOOL_BRP_SCHEDULE_OPERATION,
+        TOOL_BRP_LIST_SCHEDULED,
+        TOOL_BRP_CANCEL_SCHEDULED,
+    ]
+    .contains(&params.tool.as_str())
+    {
+        return Err(error_stack::Report::new(Error::invalid(
+            "tool",
+            "scheduler tools cannot be scheduled",
+        )));
+    }
+
+    let trigger = match (params.interval_seconds, params.cron) {
+        (Some(_), Some(_)) => {
+            return Err(error_stack::Report::new(Error::invalid(
+                "schedule",
+                "specify either interval_seconds or cron, not both",
+            )));
+        }
+        (Some(0), None) => {
+            return Err(error_stack::Report::new(Error::invalid(
+                "interval_seconds",
+                "must be greater than 0",
+            )));
+        }
+        (Some(interval), None) => Trigger::Interval(Duration::from_secs(interval)),
+        (None, Some(expression)) => {
+            let schedule = CronSchedule::parse(&expression)?;
+            if schedule.next_after(chrono::Local::now()).is_none() {
+                return Err(error_stack::Report::new(Error::invalid(
+                    "cron",
+                    format!("'{expression}' never matches"),
+                )));
+            }
+            Trigger::Cron(schedule)
+        }
+        (None, None) => Trigger::Once,
+    };
+
+    Ok(ScheduleSpec {
+        tool: params.tool,
+        arguments: params.arguments,
+        trigger,
+        delay: Duration::from_secs(params.delay_seconds),
+        duration: params.duration_seconds.map(Duration::from_secs),
+        max_runs: params.max_runs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn params(value: Value) -> ScheduleOperationParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_build_spec_selects_trigger() {
+        let spec = build_spec(params(json!({ "tool": "bevy_list" }))).unwrap();
+        assert!(matches!(spec.trigger, Trigger::Once));
+
+        let spec = build_spec(params(json!({
+            "tool": "bevy_spawn",
+            "arguments": { "components": {} },
+            "interval_seconds": 30,
+            "duration_seconds": 45
+        })))
+        .unwrap();
+        assert!(matches!(spec.trigger, Trigger::Interval(interval) if interval.as_secs() == 30));
+        assert_eq!(spec.duration, Some(Duration::from_secs(45)));
+
+        let spec = build_spec(params(
+            json!({ "tool": "bevy_list", "cron": "*/5 * * * *" }),
+        ))
+        .unwrap();
+        assert!(matches!(spec.trigger, Trigger::Cron(_)));
+    }
+
+    #[test]
+    fn test_build_spec_rejects_invalid_schedules() {
+        for value in [
+            json!({ "tool": "bevy_list", "interval_seconds": 0 }),
+            json!({ "tool": "bevy_list", "interval_seconds": 5, "cron": "* * * * *" }),
+            json!({ "tool": "bevy_list", "cron": "not a cron" }),
+            json!({ "tool": "bevy_list", "cron": "0 0 30 2 *" }),
+            json!({ "tool": TOOL_BRP_SCHEDULE_OPERATION }),
+        ] {
+            assert!(build_spec(params(value.clone())).is_err(), "{value}");
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Minimal five-field cron expressions
+//!
+//! Supports `minute hour day-of-month month day-of-week` with `*`, single values, ranges
+//! (`1-5`), lists (`1,15,30`) and steps (`*/5`, `10-40/10`). Day-of-week is 0-6 with Sunday as
+//! 0 (7 is accepted as Sunday too). As in standard cron, when both day fields are restricted a
+//! time matches if either of them matches.
+
+use chrono::{DateTime, Datelike, Duration, Local, Timelike};
+
+use crate::error::{Error, Result};
+
+/// How far ahead to search for the next matching minute (a little over four years, so that
+/// schedules like "Feb 29" are still found)
+const MAX_SEARCH_MINUTES: i64 = 5 * 366 * 24 * 60;
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression:   String,
+    minutes:      u64,
+    hours:        u64,
+    days:         u64,
+    months:       u64,
+    weekdays:     u64,
+    days_any:     bool,
+    weekdays_any: bool,
+}
+
+impl CronSchedule {
+    /// Parse a five-field cron expression
+    pub fn parse(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(error_stack::Report::new(Error::invalid(
+                "cron expression",
+                format!(
+                    "expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                    fields.len()
+                ),
+            )));
+        };
+
+        // Sunday may be written as 7; fold it onto 0
+        let mut weekdays = parse_field(weekday, 0, 7, "day-of-week")?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            expression: fields.join(" "),
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days: parse_field(day, 1, 31, "day-of-month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            weekdays,
+            days_any: *day == "*",
+            weekdays_any: *weekday == "*",
+        })
+    }
+
+    /// The normalized expression
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The first matching minute strictly after `after`
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after
+            .with_second(0)
+            .and_then(|time| time.with_nanosecond(0))?
+            + Duration::minutes(1);
+
+        (0..MAX_SEARCH_MINUTES)
+            .map(|offset| start + Duration::minutes(offset))
+            .find(|time| self.matches(time))
+    }
+
+    fn matches(&self, time: &DateTime<Local>) -> bool {
+        let day_matches = bit(self.days, time.day());
+        let weekday_matches = bit(self.weekdays, time.weekday().num_days_from_sunday());
+        let date_matches = match (self.days_any, self.weekdays_any) {
+            (false, false) => day_matches || weekday_matches,
+            _ => day_matches && weekday_matches,
+        };
+
+        date_matches
+            && bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+    }
+}
+
+const fn bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parse one field into a bit set of the allowed values
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64> {
+    let invalid = |details: &str| {
+        error_stack::Report::new(Error::invalid(
+            "cron expression",
+            format!("{name} field '{field}': {details}"),
+        ))
+    };
+    let number = |text: &str| {
+        text.parse::<u32>()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+            .ok_or_else(|| invalid(&format!("'{text}' is not a number between {min} and {max}")))
+    };
+
+    let mut set = 0_u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| invalid(&format!("invalid step '{step}'")))?,
+            ),
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (number(start)?, number(end)?)
+        } else {
+            let value = number(range)?;
+            // "5/15" means every 15 starting at 5
+            (value, if step > 1 { max } else { value })
+        };
+        if start > end {
+            return Err(invalid(&format!("range {start}-{end} is reversed")));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let schedule = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        assert_eq!(
+            schedule.minutes,
+            (1 << 0) | (1 << 15) | (1 << 30) | (1 << 45)
+        );
+        assert_eq!(
+            schedule.hours,
+            (9..=17).fold(0, |set, hour| set | (1 << hour))
+        );
+        assert_eq!(schedule.expression(), "*/15 9-17 * * 1-5");
+
+        // Sunday as 7 is folded onto 0
+        let schedule = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert_eq!(schedule.weekdays, 1);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(
+                CronSchedule::parse(expression).is_err(),
+                "'{expression}' should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_after() {
+        let schedule = CronSchedule::parse("30 * * * *").unwrap();
+        assert_eq!(
+            schedule.next_after(at(2025, 1, 1, 10, 15)),
+            Some(at(2025, 1, 1, 10, 30))
+        );
+        // Strictly after: a matching minute is not returned again
+        assert_eq!(
+            schedule.next_after(at(2025, 1, 1, 10, 30)),
+            Some(at(2025, 1, 1, 11, 30))
+        );
+
+        // 2025-01-04 is a Saturday; the next weekday is Monday the 6th
+        let schedule = CronSchedule::parse("0 9 * * 1-5").unwrap();
+        assert_eq!(
+            schedule.next_after(at(2025, 1, 4, 12, 0)),
+            Some(at(2025, 1, 6, 9, 0))
+        );
+    }
+
+    #[test]
+    fn test_restricted_day_fields_match_either() {
+        // The 1st of the month or any Friday; 2025-01-03 is a Friday
+        let schedule = CronSchedule::parse("0 0 1 * 5").unwrap();
+        assert_eq!(
+            schedule.next_after(at(2025, 1, 1, 12, 0)),
+            Some(at(2025, 1, 3, 0, 0))
+        );
+    }
+}
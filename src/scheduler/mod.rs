@@ -0,0 +1,364 @@
+//! Scheduler for delayed and periodic tool invocations
+//!
+//! A scheduled operation stores a tool name and its arguments and replays them through the
+//! normal tool pipeline (`registry::handle_tool_call`) once after a delay, at a fixed interval
+//! or on a cron schedule. Every run is appended to a per-operation log file.
+
+pub mod brp_cancel_scheduled;
+pub mod brp_list_scheduled;
+pub mod brp_schedule_operation;
+mod cron;
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use rmcp::RoleServer;
+use rmcp::model::{CallToolRequestParam, CallToolResult};
+use rmcp::service::RequestContext;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+pub use self::cron::CronSchedule;
+use crate::BrpMcpService;
+use crate::brp_tools::constants::JSON_FIELD_STATUS;
+use crate::brp_tools::support::brp_client;
+use crate::error::{Error, Result};
+use crate::log_tools::support::{LOG_EXTENSION, LOG_PREFIX, create_log_directory};
+use crate::support::serialization::result_to_json;
+
+/// Global scheduler instance
+pub static SCHEDULER: LazyLock<Arc<Mutex<Scheduler>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(Scheduler::new())));
+
+/// Field name of the schedule ID in tool parameters and responses
+pub const JSON_FIELD_SCHEDULE_ID: &str = "schedule_id";
+
+/// When a scheduled operation runs
+#[derive(Debug, Clone)]
+pub enum Trigger {
+    /// Run once after the delay
+    Once,
+    /// Run every interval, starting after the delay
+    Interval(Duration),
+    /// Run at every time matching the cron expression
+    Cron(CronSchedule),
+}
+
+impl Trigger {
+    fn describe(&self) -> String {
+        match self {
+            Self::Once => "once".to_string(),
+            Self::Interval(interval) => format!("every {}s", interval.as_secs()),
+            Self::Cron(schedule) => format!("cron '{}'", schedule.expression()),
+        }
+    }
+}
+
+/// Everything needed to run a scheduled operation
+#[derive(Debug, Clone)]
+pub struct ScheduleSpec {
+    /// Tool to invoke
+    pub tool:      String,
+    /// Arguments passed to the tool
+    pub arguments: Map<String, Value>,
+    /// When to run
+    pub trigger:   Trigger,
+    /// Wait before the first run (interval and one-shot triggers)
+    pub delay:     Duration,
+    /// Stop scheduling new runs after this long
+    pub duration:  Option<Duration>,
+    /// Stop after this many runs
+    pub max_runs:  Option<u64>,
+}
+
+/// Information about a scheduled operation
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleInfo {
+    pub schedule_id: u32,
+    pub tool:        String,
+    pub arguments:   Map<String, Value>,
+    pub trigger:     String,
+    pub max_runs:    Option<u64>,
+    pub runs:        u64,
+    pub failures:    u64,
+    pub last_run:    Option<String>,
+    pub last_status: Option<String>,
+    pub created:     String,
+    pub log_path:    PathBuf,
+}
+
+/// Manager for scheduled operations
+pub struct Scheduler {
+    /// Monotonic counter for schedule IDs
+    next_schedule_id: AtomicU32,
+    /// Pending or running operations mapped by schedule ID
+    operations:       HashMap<u32, (ScheduleInfo, JoinHandle<()>)>,
+}
+
+impl Scheduler {
+    fn new() -> Self {
+        Self {
+            next_schedule_id: AtomicU32::new(1),
+            operations:       HashMap::new(),
+        }
+    }
+
+    /// Cancel a scheduled operation by ID
+    pub fn cancel(&mut self, schedule_id: u32) -> Result<ScheduleInfo> {
+        let (info, handle) = self.operations.remove(&schedule_id).ok_or_else(|| {
+            error_stack::Report::new(Error::InvalidState(format!(
+                "Scheduled operation {schedule_id} not found"
+            )))
+        })?;
+        info!(
+            "Cancelling scheduled operation {} ({})",
+            schedule_id, info.tool
+        );
+        handle.abort();
+        Ok(info)
+    }
+
+    /// List all pending operations ordered by ID
+    pub fn list(&self) -> Vec<ScheduleInfo> {
+        let mut operations: Vec<ScheduleInfo> = self
+            .operations
+            .values()
+            .map(|(info, _)| info.clone())
+            .collect();
+        operations.sort_by_key(|info| info.schedule_id);
+        operations
+    }
+}
+
+/// Schedule an operation, returning its info
+///
+/// The service and request context are cloned into the task so that every run goes through
+/// the same tool pipeline as a direct call.
+pub async fn schedule(
+    spec: ScheduleSpec,
+    service: &BrpMcpService,
+    context: &RequestContext<RoleServer>,
+) -> ScheduleInfo {
+    let mut scheduler = SCHEDULER.lock().await;
+    let schedule_id = scheduler.next_schedule_id.fetch_add(1, Ordering::SeqCst);
+
+    let timestamp = chrono::Local::now();
+    let info = ScheduleInfo {
+        schedule_id,
+        tool: spec.tool.clone(),
+        arguments: spec.arguments.clone(),
+        trigger: spec.trigger.describe(),
+        max_runs: spec.max_runs,
+        runs: 0,
+        failures: 0,
+        last_run: None,
+        last_status: None,
+        created: timestamp.to_rfc3339(),
+        log_path: create_log_directory().join(format!(
+            "{LOG_PREFIX}schedule_{schedule_id}_{}{LOG_EXTENSION}",
+            timestamp.timestamp()
+        )),
+    };
+
+    // The lock is held until the task is registered, so a task that finishes immediately
+    // cannot try to remove itself before it has been inserted
+    let handle = tokio::spawn(brp_client::with_host(
+        brp_client::current_host(),
+        run_schedule(
+            schedule_id,
+            spec,
+            info.log_path.clone(),
+            service.clone(),
+            context.clone(),
+        ),
+    ));
+    scheduler
+        .operations
+        .insert(schedule_id, (info.clone(), handle));
+    drop(scheduler);
+
+    info!(
+        "Scheduled operation {} ({}, {})",
+        schedule_id, info.tool, info.trigger
+    );
+    info
+}
+
+/// Time until the next run, or `None` when the schedule is exhausted
+fn next_wait(spec: &ScheduleSpec, started: Instant, runs: u64) -> Option<Duration> {
+    let now = Instant::now();
+    let next = match &spec.trigger {
+        Trigger::Once if runs > 0 => return None,
+        Trigger::Once => started + spec.delay,
+        Trigger::Interval(interval) => {
+            let periods = u32::try_from(runs).ok()?;
+            started + spec.delay + interval.checked_mul(periods)?
+        }
+        Trigger::Cron(schedule) => {
+            let local = chrono::Local::now();
+            let next = schedule.next_after(local)?;
+            now + (next - local).to_std().ok()?
+        }
+    };
+
+    if spec.max_runs.is_some_and(|max_runs| runs >= max_runs)
+        || spec
+            .duration
+            .is_some_and(|duration| next > started + duration)
+    {
+        return None;
+    }
+    Some(next.saturating_duration_since(now))
+}
+
+/// Task driving one scheduled operation until it is exhausted or cancelled
+async fn run_schedule(
+    schedule_id: u32,
+    spec: ScheduleSpec,
+    log_path: PathBuf,
+    service: BrpMcpService,
+    context: RequestContext<RoleServer>,
+) {
+    let started = Instant::now();
+    let mut runs = 0;
+
+    while let Some(wait) = next_wait(&spec, started, runs) {
+        tokio::time::sleep(wait).await;
+        runs += 1;
+
+        let request = CallToolRequestParam {
+            name:      spec.tool.clone().into(),
+            arguments: Some(spec.arguments.clone()),
+        };
+        let result =
+            crate::registry::dispatch_tool_call(service.clone(), request, context.clone()).await;
+        let (status, output) = run_outcome(result);
+
+        append_run_log(&log_path, runs, &status, &output).await;
+        record_run(schedule_id, &status).await;
+    }
+
+    info!(
+        "Scheduled operation {} finished after {} runs",
+        schedule_id, runs
+    );
+    SCHEDULER.lock().await.operations.remove(&schedule_id);
+}
+
+/// Status and output of one run
+///
+/// Tools report failures either as MCP errors or as a JSON response with an error status.
+fn run_outcome(result: std::result::Result<CallToolResult, rmcp::Error>) -> (String, Value) {
+    match result {
+        Ok(result) => {
+            let output = result_to_json(&result);
+            let failed = result.is_error == Some(true)
+                || output.get(JSON_FIELD_STATUS).and_then(Value::as_str) == Some("error");
+            let status = if failed { "error" } else { "success" };
+            (status.to_string(), output)
+        }
+        Err(e) => ("error".to_string(), json!({ "message": e.message })),
+    }
+}
+
+/// Record a completed run in the scheduler
+async fn record_run(schedule_id: u32, status: &str) {
+    let mut scheduler = SCHEDULER.lock().await;
+    if let Some((info, _)) = scheduler.operations.get_mut(&schedule_id) {
+        info.runs += 1;
+        if status != "success" {
+            info.failures += 1;
+        }
+        info.last_run = Some(chrono::Local::now().to_rfc3339());
+        info.last_status = Some(status.to_string());
+    }
+}
+
+/// Append one run to the operation's log file
+async fn append_run_log(log_path: &PathBuf, run: u64, status: &str, output: &Value) {
+    let mut line = String::new();
+    let _ = writeln!(
+        line,
+        "[{}] RUN {run} {status}: {output}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f")
+    );
+
+    let written = async {
+        tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)
+            .await?
+            .write_all(line.as_bytes())
+            .await
+    }
+    .await;
+    if let Err(e) = written {
+        warn!(
+            "Failed to write scheduled run to {}: {}",
+            log_path.display(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(trigger: Trigger) -> ScheduleSpec {
+        ScheduleSpec {
+            tool: "bevy_list".to_string(),
+            arguments: Map::new(),
+            trigger,
+            delay: Duration::ZERO,
+            duration: None,
+            max_runs: None,
+        }
+    }
+
+    #[test]
+    fn test_once_runs_a_single_time() {
+        let started = Instant::now();
+        assert!(next_wait(&spec(Trigger::Once), started, 0).is_some());
+        assert!(next_wait(&spec(Trigger::Once), started, 1).is_none());
+    }
+
+    #[test]
+    fn test_interval_respects_duration_and_max_runs() {
+        let started = Instant::now();
+        let mut interval = spec(Trigger::Interval(Duration::from_secs(30)));
+        interval.duration = Some(Duration::from_secs(135));
+
+        // Runs at 0s, 30s, ... 120s: 5 runs fit in the window
+        assert!(next_wait(&interval, started, 4).is_some());
+        assert!(next_wait(&interval, started, 5).is_none());
+
+        interval.max_runs = Some(3);
+        assert!(next_wait(&interval, started, 2).is_some());
+        assert!(next_wait(&interval, started, 3).is_none());
+    }
+
+    #[test]
+    fn test_run_outcome_detects_error_status() {
+        let result = CallToolResult::success(vec![rmcp::model::Content::text(
+            r#"{"status":"error","message":"boom"}"#,
+        )]);
+        let (status, output) = run_outcome(Ok(result));
+        assert_eq!(status, "error");
+        assert_eq!(output["message"], "boom");
+
+        let result =
+            CallToolResult::success(vec![rmcp::model::Content::text(r#"{"status":"success"}"#)]);
+        assert_eq!(run_outcome(Ok(result)).0, "success");
+    }
+}
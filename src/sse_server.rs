@@ -0,0 +1,584 @@
+//! MCP over HTTP with Server-Sent Events
+//!
+//! Implements the HTTP+SSE transport of the MCP specification (2024-11-05) for web-based clients
+//! and remote agents that can't spawn the server over stdio:
+//!
+//! - `GET /sse` opens a session. The first event, `endpoint`, gives the URL to post messages to;
+//!   every server message then arrives as a `message` event.
+//! - `POST /message?sessionId=<id>` sends one client message to the session, answered with `202
+//!   Accepted`.
+//!
+//! Each session is served by its own `BrpMcpService`, so roots, deltas, snapshots, bookmarks and
+//! the corrections log are per client, and a confirmation token only confirms calls from the
+//! session it was issued to. Session recordings, isolations, scheduled operations and watches
+//! belong to the server process and are shared by every client. Every request must carry the token
+//! in `BRP_MCP_SSE_TOKEN` as `Authorization: Bearer <token>`; when it is unset the server generates
+//! one and prints it on stderr. Requests sent from a web page on another origin are rejected, and
+//! so are requests for a host name other than loopback while listening on a loopback address, so
+//! pages can't reach the server through DNS rebinding. No CORS headers are sent.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::SinkExt;
+use rmcp::ServiceExt;
+use rmcp::model::{ClientJsonRpcMessage, ServerJsonRpcMessage};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::PollSender;
+use tracing::{debug, info, warn};
+
+use crate::BrpMcpService;
+use crate::brp_tools::request_handler::corrections_log;
+use crate::support::secret;
+
+/// Environment variable holding the bearer token required by the SSE server
+pub const SSE_TOKEN_ENV_VAR: &str = "BRP_MCP_SSE_TOKEN";
+
+/// Loopback port served when `--sse` is given without an address, below the ports BRP apps use
+pub const DEFAULT_SSE_PORT: u16 = 15700;
+
+/// Path that opens a session
+const SSE_PATH: &str = "/sse";
+
+/// Path client messages are posted to
+const MESSAGE_PATH: &str = "/message";
+
+/// Query parameter naming the session of a posted message
+const SESSION_ID_PARAM: &str = "sessionId";
+
+/// Largest request head accepted
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Largest message body accepted
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Messages buffered per direction of a session
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Interval of the comments that keep idle event streams open through proxies
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Scheme prefix of the bearer token in the `Authorization` header
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// Senders of client messages to the open sessions, by session ID
+type Sessions = Arc<Mutex<HashMap<String, mpsc::Sender<ClientJsonRpcMessage>>>>;
+
+/// A parsed HTTP request
+struct Request {
+    method:        String,
+    path:          String,
+    query:         String,
+    authorization: Option<String>,
+    host:          Option<String>,
+    origin:        Option<String>,
+    body:          Vec<u8>,
+}
+
+/// Serve MCP over HTTP+SSE on an address until the process ends
+pub async fn serve(address: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(address).await?;
+    let token = match std::env::var(SSE_TOKEN_ENV_VAR) {
+        Ok(token) if !token.trim().is_empty() => token,
+        _ => {
+            let token = generate_token();
+            writeln!(
+                std::io::stderr(),
+                "{SSE_TOKEN_ENV_VAR} is not set, requests must carry `Authorization: Bearer \
+                 {token}`"
+            )?;
+            token
+        }
+    };
+    info!(
+        "Serving MCP over SSE at http://{}{}",
+        listener.local_addr()?,
+        SSE_PATH
+    );
+
+    accept(listener, token.into()).await
+}
+
+/// A random token for servers started without one
+fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Answer the connections to a listener, each on its own task
+async fn accept(listener: TcpListener, token: Arc<str>) -> std::io::Result<()> {
+    let loopback = listener.local_addr()?.ip().is_loopback();
+    let sessions = Sessions::default();
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        debug!("SSE connection from {}", peer);
+        tokio::spawn(handle_connection(
+            stream,
+            Arc::clone(&sessions),
+            Arc::clone(&token),
+            loopback,
+        ));
+    }
+}
+
+/// Answer one HTTP request, keeping the connection open for event streams
+async fn handle_connection(
+    mut stream: TcpStream,
+    sessions: Sessions,
+    token: Arc<str>,
+    loopback: bool,
+) {
+    let request = match read_request(&mut stream).await {
+        Ok(request) => request,
+        Err(status) => {
+            let _ = respond(&mut stream, status, "").await;
+            return;
+        }
+    };
+
+    if let Some(reason) = foreign_request(&request, loopback) {
+        warn!("Rejected an SSE request: {}", reason);
+        let _ = respond(&mut stream, "403 Forbidden", reason).await;
+        return;
+    }
+    let presented = request
+        .authorization
+        .as_deref()
+        .and_then(|authorization| authorization.strip_prefix(BEARER_PREFIX))
+        .unwrap_or_default();
+    if !secret::tokens_match(&token, presented) {
+        let _ = respond(
+            &mut stream,
+            "401 Unauthorized",
+            "missing or wrong bearer token",
+        )
+        .await;
+        return;
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", SSE_PATH) => open_session(stream, sessions).await,
+        ("POST", MESSAGE_PATH) => {
+            let (status, body) = post_message(&request, &sessions).await;
+            let _ = respond(&mut stream, status, body).await;
+        }
+        _ => {
+            let _ = respond(&mut stream, "404 Not Found", "not found").await;
+        }
+    }
+}
+
+/// Why a request must be refused because it doesn't come from a local client, if it must
+///
+/// Browsers send `Origin` with cross-origin requests, which is only allowed from pages served on
+/// loopback. While listening on loopback, the `Host` must name loopback as well, otherwise a page
+/// could rebind its own domain to 127.0.0.1 and call the server as same-origin.
+fn foreign_request(request: &Request, loopback: bool) -> Option<&'static str> {
+    if let Some(origin) = request.origin.as_deref() {
+        let authority = origin
+            .strip_prefix("http://")
+            .or_else(|| origin.strip_prefix("https://"));
+        if !authority.is_some_and(|authority| is_loopback_host(host_name(authority))) {
+            return Some("origin is not loopback");
+        }
+    }
+    if loopback
+        && !request
+            .host
+            .as_deref()
+            .is_some_and(|host| is_loopback_host(host_name(host)))
+    {
+        return Some("host is not loopback");
+    }
+    None
+}
+
+/// The host name of an authority, without its port and IPv6 brackets
+fn host_name(authority: &str) -> &str {
+    if let Some(bracketed) = authority.strip_prefix('[') {
+        return bracketed
+            .split_once(']')
+            .map_or(bracketed, |(host, _)| host);
+    }
+    authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _)| host)
+}
+
+/// Whether a host name is `localhost` or a loopback address
+fn is_loopback_host(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+}
+
+/// Start a session and stream the server's messages to the client until either side ends it
+async fn open_session(mut stream: TcpStream, sessions: Sessions) {
+    let session_id = uuid::Uuid::new_v4().simple().to_string();
+    let (to_server, from_client) = mpsc::channel::<ClientJsonRpcMessage>(CHANNEL_CAPACITY);
+    let (to_client, mut from_server) = mpsc::channel::<ServerJsonRpcMessage>(CHANNEL_CAPACITY);
+
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: \
+         no-cache\r\nConnection: keep-alive\r\n\r\nevent: endpoint\ndata: \
+         {MESSAGE_PATH}?{SESSION_ID_PARAM}={session_id}\n\n"
+    );
+    if stream.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+    if let Ok(mut sessions) = sessions.lock() {
+        sessions.insert(session_id.clone(), to_server);
+    }
+    info!("SSE session {} opened", session_id);
+
+    // rmcp needs a sink whose error converts from I/O errors
+    let sink = PollSender::new(to_client).sink_map_err(std::io::Error::other);
+    let transport = (sink, ReceiverStream::new(from_client));
+    let service_session = session_id.clone();
+    tokio::spawn(async move {
+        let service = BrpMcpService::new();
+        let session = service.session;
+        match service.serve(transport).await {
+            Ok(server) => {
+                if let Err(e) = server.waiting().await {
+                    warn!("SSE session {} ended with an error: {}", service_session, e);
+                }
+            }
+            Err(e) => warn!("SSE session {} failed to start: {}", service_session, e),
+        }
+        corrections_log::forget_session(session);
+    });
+
+    let mut keep_alive = tokio::time::interval(KEEP_ALIVE_INTERVAL);
+    loop {
+        let event = tokio::select! {
+            message = from_server.recv() => match message {
+                Some(message) => match serde_json::to_string(&message) {
+                    Ok(json) => format!("event: message\ndata: {json}\n\n"),
+                    Err(e) => {
+                        warn!("Failed to serialize a message for SSE session {}: {}", session_id, e);
+                        continue;
+                    }
+                },
+                None => break,
+            },
+            _ = keep_alive.tick() => ": keep-alive\n\n".to_string(),
+        };
+        if stream.write_all(event.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+
+    // Dropping the sender ends the client message stream, which stops the service
+    if let Ok(mut sessions) = sessions.lock() {
+        sessions.remove(&session_id);
+    }
+    info!("SSE session {} closed", session_id);
+}
+
+/// Hand a posted client message to its session, returning the response status and body
+async fn post_message(request: &Request, sessions: &Sessions) -> (&'static str, &'static str) {
+    let Some(session_id) = query_param(&request.query, SESSION_ID_PARAM) else {
+        return ("400 Bad Request", "missing sessionId");
+    };
+    let sender = sessions
+        .lock()
+        .ok()
+        .and_then(|sessions| sessions.get(session_id).cloned());
+    let Some(sender) = sender else {
+        return ("404 Not Found", "unknown session");
+    };
+    let Ok(message) = serde_json::from_slice::<ClientJsonRpcMessage>(&request.body) else {
+        return ("400 Bad Request", "body is not a JSON-RPC message");
+    };
+    if sender.send(message).await.is_err() {
+        return ("410 Gone", "session closed");
+    }
+    ("202 Accepted", "")
+}
+
+/// Value of a query parameter
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+/// Read an HTTP request, or the status to reject it with
+async fn read_request(stream: &mut TcpStream) -> Result<Request, &'static str> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 8192];
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err("431 Request Header Fields Too Large");
+        }
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err("400 Bad Request"),
+            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    let mut host = None;
+    let mut origin = None;
+    for (name, value) in lines.filter_map(|line| line.split_once(':')) {
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().map_err(|_| "400 Bad Request")?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.trim().to_string());
+        } else if name.eq_ignore_ascii_case("host") {
+            host = Some(value.trim().to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            origin = Some(value.trim().to_string());
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err("413 Payload Too Large");
+    }
+
+    while buffer.len() < head_end + content_length {
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err("400 Bad Request"),
+            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+        }
+    }
+
+    Ok(Request {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        authorization,
+        host,
+        origin,
+        body: buffer[head_end..head_end + content_length].to_vec(),
+    })
+}
+
+/// Write a complete response and close the connection
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: \
+         close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+
+    use serde_json::{Value, json};
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::tcp::OwnedReadHalf;
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    /// Start a server requiring [`TOKEN`] on a free loopback port, returning its address
+    async fn start() -> SocketAddr {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("failed to bind the SSE server");
+        let address = listener.local_addr().expect("SSE server has an address");
+        tokio::spawn(accept(listener, TOKEN.into()));
+        address
+    }
+
+    /// Send one request and return the status line and body of the response
+    async fn send(address: SocketAddr, request: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(address)
+            .await
+            .expect("failed to connect to the SSE server");
+        stream
+            .write_all(request.as_bytes())
+            .await
+            .expect("failed to send the request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("failed to read the response");
+        let status = response.lines().next().unwrap_or_default().to_string();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.to_string())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    async fn post(address: SocketAddr, endpoint: &str, message: &Value) -> String {
+        let body = message.to_string();
+        let request = format!(
+            "POST {endpoint} HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer \
+             {TOKEN}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        send(address, &request).await.0
+    }
+
+    /// The data of the next event of a type on an event stream
+    async fn next_event(events: &mut BufReader<OwnedReadHalf>, event: &str) -> String {
+        let mut current = String::new();
+        loop {
+            let mut line = String::new();
+            let read = events
+                .read_line(&mut line)
+                .await
+                .expect("failed to read the event stream");
+            assert!(read > 0, "event stream closed before a {event} event");
+            let line = line.trim_end();
+            if let Some(name) = line.strip_prefix("event: ") {
+                current = name.to_string();
+            } else if let Some(data) = line.strip_prefix("data: ")
+                && current == event
+            {
+                return data.to_string();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_answers_posted_messages_on_the_event_stream() {
+        let address = start().await;
+        let stream = TcpStream::connect(address)
+            .await
+            .expect("failed to connect to the SSE server");
+        let (read, mut write) = stream.into_split();
+        write
+            .write_all(
+                format!(
+                    "GET /sse HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {TOKEN}\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .await
+            .expect("failed to open the event stream");
+        let mut events = BufReader::new(read);
+
+        let endpoint = next_event(&mut events, "endpoint").await;
+        assert!(endpoint.starts_with("/message?sessionId="));
+
+        let initialize = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "sse-test", "version": "0.0.0" },
+            },
+        });
+        assert!(post(address, &endpoint, &initialize).await.contains("202"));
+        let response: Value = serde_json::from_str(&next_event(&mut events, "message").await)
+            .expect("message is JSON");
+        assert_eq!(response["id"], 1);
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+
+        // Messages for unknown sessions and bodies that aren't JSON-RPC are rejected
+        let unknown = post(address, "/message?sessionId=nope", &initialize).await;
+        assert!(unknown.contains("404"), "{unknown}");
+        let invalid = post(address, &endpoint, &json!({ "not": "json-rpc" })).await;
+        assert!(invalid.contains("400"), "{invalid}");
+    }
+
+    #[tokio::test]
+    async fn test_token_is_required() {
+        let address = start().await;
+        let (status, _) = send(address, "GET /sse HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(status.contains("401"), "{status}");
+        let (status, _) = send(
+            address,
+            "GET /sse HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer wrong\r\n\r\n",
+        )
+        .await;
+        assert!(status.contains("401"), "{status}");
+
+        let (status, _) = send(
+            address,
+            "POST /message?sessionId=x HTTP/1.1\r\nHost: 127.0.0.1:15700\r\nAuthorization: \
+             Bearer secret\r\nContent-Length: 2\r\n\r\n{}",
+        )
+        .await;
+        assert!(status.contains("404"), "{status}");
+    }
+
+    #[tokio::test]
+    async fn test_foreign_origins_and_hosts_are_forbidden() {
+        let address = start().await;
+        for headers in [
+            "Host: localhost\r\nOrigin: https://evil.example",
+            "Host: localhost\r\nOrigin: null",
+            "Host: rebound.example:15700",
+            "Origin: http://localhost:3000",
+        ] {
+            let (status, _) = send(
+                address,
+                &format!("GET /sse HTTP/1.1\r\n{headers}\r\nAuthorization: Bearer {TOKEN}\r\n\r\n"),
+            )
+            .await;
+            assert!(status.contains("403"), "{headers}: {status}");
+        }
+
+        // CORS preflights from other origins are refused as well
+        let (status, _) = send(
+            address,
+            "OPTIONS /sse HTTP/1.1\r\nHost: localhost\r\nOrigin: https://evil.example\r\n\r\n",
+        )
+        .await;
+        assert!(status.contains("403"), "{status}");
+    }
+
+    #[test]
+    fn test_loopback_hosts() {
+        for host in ["localhost:15700", "127.0.0.1", "[::1]:15700", "LOCALHOST"] {
+            assert!(is_loopback_host(host_name(host)), "{host}");
+        }
+        for host in [
+            "example.com",
+            "0.0.0.0:15700",
+            "192.168.1.2",
+            "[fe80::1]:80",
+        ] {
+            assert!(!is_loopback_host(host_name(host)), "{host}");
+        }
+    }
+
+    #[test]
+    fn test_query_param() {
+        assert_eq!(
+            query_param("sessionId=abc&x=1", SESSION_ID_PARAM),
+            Some("abc")
+        );
+        assert_eq!(
+            query_param("x=1&sessionId=def", SESSION_ID_PARAM),
+            Some("def")
+        );
+        assert_eq!(query_param("x=1", SESSION_ID_PARAM), None);
+        assert_eq!(query_param("", SESSION_ID_PARAM), None);
+    }
+}
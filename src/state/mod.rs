@@ -0,0 +1,194 @@
+//! Persistent server state directory
+//!
+//! All features that persist data across server restarts (aliases, presets, caches, histories,
+//! ...) store it beneath a single state directory so they share one location, one on-disk
+//! envelope format, and one migration mechanism.
+//!
+//! Layout:
+//! ```text
+//! <state root>/
+//!   state.json                 server state manifest
+//!   <global files>.json
+//!   projects/<name>-<hash>/    per-project state
+//! ```
+//!
+//! The state root is the platform data directory joined with `bevy_brp_mcp`. It can be
+//! overridden with `BRP_MCP_STATE_DIR`, and persistence is disabled entirely when that variable is
+//! set to `off`.
+
+mod versioned;
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+pub use versioned::StateFormat;
+
+use crate::error::Result;
+
+/// Environment variable that overrides (or disables) the state directory
+pub const STATE_DIR_ENV_VAR: &str = "BRP_MCP_STATE_DIR";
+
+/// Value of [`STATE_DIR_ENV_VAR`] that disables persistence
+const STATE_DIR_DISABLED: &str = "off";
+
+/// Name of the application directory inside the platform data directory
+const APP_DIR_NAME: &str = "bevy_brp_mcp";
+
+/// Subdirectory holding per-project state
+const PROJECTS_DIR_NAME: &str = "projects";
+
+/// A directory that versioned state files are read from and written to
+#[derive(Debug, Clone)]
+pub struct StateDir {
+    /// Directory path, or `None` when persistence is disabled
+    path: Option<PathBuf>,
+}
+
+impl StateDir {
+    /// The global (not project specific) state directory
+    pub fn global() -> Self {
+        Self {
+            path: resolve_state_root(),
+        }
+    }
+
+    /// The state directory for a specific project, keyed by its root path
+    pub fn for_project(project_root: &Path) -> Self {
+        Self {
+            path: resolve_state_root()
+                .map(|root| root.join(PROJECTS_DIR_NAME).join(project_key(project_root))),
+        }
+    }
+
+    /// Directory path, or `None` when persistence is disabled
+    pub const fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Load a state file, migrating it to the current version if needed.
+    ///
+    /// Returns the default value when persistence is disabled or the file does not exist yet.
+    pub fn load<T: StateFormat>(&self) -> Result<T> {
+        self.path.as_ref().map_or_else(
+            || Ok(T::default()),
+            |dir| versioned::load(&dir.join(T::FILE_NAME)),
+        )
+    }
+
+    /// Save a state file at the current version. Does nothing when persistence is disabled.
+    pub fn save<T: StateFormat>(&self, value: &T) -> Result<()> {
+        self.path.as_ref().map_or(Ok(()), |dir| {
+            versioned::save(&dir.join(T::FILE_NAME), value)
+        })
+    }
+}
+
+/// Server state manifest, written on every startup
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ServerState {
+    /// Version of the server that last used this state directory
+    last_server_version: Option<String>,
+    /// When the server last started
+    last_started:        Option<String>,
+}
+
+impl StateFormat for ServerState {
+    const FILE_NAME: &'static str = "state.json";
+    const VERSION: u32 = 1;
+}
+
+/// Open the global state directory and record this server start in its manifest.
+///
+/// Failures are logged rather than returned so a broken state directory never prevents the
+/// server from starting.
+pub fn initialize_state_dir() {
+    let state_dir = StateDir::global();
+    let Some(path) = state_dir.path() else {
+        info!("Persistent state is disabled");
+        return;
+    };
+
+    let mut state = match state_dir.load::<ServerState>() {
+        Ok(state) => state,
+        Err(e) => {
+            warn!("Failed to load server state from {}: {}", path.display(), e);
+            return;
+        }
+    };
+    state.last_server_version = Some(env!("CARGO_PKG_VERSION").to_string());
+    state.last_started = Some(chrono::Local::now().to_rfc3339());
+
+    match state_dir.save(&state) {
+        Ok(()) => debug!("Using state directory {}", path.display()),
+        Err(e) => warn!("Failed to save server state to {}: {}", path.display(), e),
+    }
+}
+
+/// Resolve the state root from the environment, falling back to the platform data directory
+fn resolve_state_root() -> Option<PathBuf> {
+    match std::env::var(STATE_DIR_ENV_VAR) {
+        Ok(value) if value.eq_ignore_ascii_case(STATE_DIR_DISABLED) => None,
+        Ok(value) if !value.is_empty() => Some(PathBuf::from(value)),
+        _ => platform_data_dir().map(|dir| dir.join(APP_DIR_NAME)),
+    }
+}
+
+/// The platform specific directory for user application data
+fn platform_data_dir() -> Option<PathBuf> {
+    let env_path = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if cfg!(target_os = "windows") {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_path("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_path("XDG_DATA_HOME")
+            .or_else(|| env_path("HOME").map(|home| home.join(".local").join("share")))
+    }
+}
+
+/// Stable directory name for a project: its sanitized folder name plus a hash of the full path
+fn project_key(project_root: &Path) -> String {
+    let name: String = project_root
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    // FNV-1a, so keys stay stable across Rust versions (unlike `DefaultHasher`)
+    let hash = project_root
+        .to_string_lossy()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{name}-{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_key_is_stable_and_sanitized() {
+        let key = project_key(Path::new("/home/user/my game"));
+
+        assert!(key.starts_with("my_game-"));
+        assert_eq!(key, project_key(Path::new("/home/user/my game")));
+        assert_ne!(key, project_key(Path::new("/other/my game")));
+    }
+}
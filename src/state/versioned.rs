@@ -0,0 +1,252 @@
+//! Versioned on-disk state files with migration support
+//!
+//! Every state file is stored in an envelope recording the format version:
+//! ```json
+//! { "version": 2, "data": { ... } }
+//! ```
+//! When a file with an older version is loaded, [`StateFormat::migrate`] is applied one version at
+//! a time until the current version is reached. The pre-migration file is kept next to the new one
+//! as `<file>.v<old version>.bak`.
+
+use std::path::{Path, PathBuf};
+
+use error_stack::Report;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::error::{Error, Result};
+
+/// Envelope field holding the format version
+const FIELD_VERSION: &str = "version";
+
+/// Envelope field holding the state data
+const FIELD_DATA: &str = "data";
+
+/// A type that can be persisted in the state directory
+pub trait StateFormat: Serialize + DeserializeOwned + Default {
+    /// File name within the state directory
+    const FILE_NAME: &'static str;
+
+    /// Current on-disk format version. Bump it whenever the serialized shape changes and handle
+    /// the previous version in [`StateFormat::migrate`].
+    const VERSION: u32;
+
+    /// Upgrade raw data stored at `from_version` to `from_version + 1`
+    fn migrate(from_version: u32, data: Value) -> Result<Value> {
+        let _ = data;
+        Err(Report::new(Error::Configuration(format!(
+            "No migration for {} from version {from_version}",
+            Self::FILE_NAME
+        ))))
+    }
+}
+
+/// Load and, if necessary, migrate a state file
+pub fn load<T: StateFormat>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Report::new(Error::FileOperation(format!(
+            "Failed to read state file {}: {e}",
+            path.display()
+        )))
+    })?;
+
+    let mut envelope: Value = serde_json::from_str(&contents).map_err(|e| {
+        Report::new(Error::FileOperation(format!(
+            "Failed to parse state file {}: {e}",
+            path.display()
+        )))
+    })?;
+
+    let stored_version = envelope
+        .get(FIELD_VERSION)
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| {
+            Report::new(Error::FileOperation(format!(
+                "State file {} has no valid version",
+                path.display()
+            )))
+        })?;
+
+    if stored_version > T::VERSION {
+        return Err(Report::new(Error::Configuration(format!(
+            "State file {} has version {stored_version}, but this server only supports up to \
+             version {}. It was probably written by a newer bevy_brp_mcp",
+            path.display(),
+            T::VERSION
+        ))));
+    }
+
+    let mut data = envelope
+        .get_mut(FIELD_DATA)
+        .map_or(Value::Null, Value::take);
+
+    if stored_version < T::VERSION {
+        for version in stored_version..T::VERSION {
+            data = T::migrate(version, data)?;
+        }
+
+        backup(path, stored_version)?;
+        write_envelope(path, T::VERSION, &data)?;
+        info!(
+            "Migrated state file {} from version {} to {}",
+            path.display(),
+            stored_version,
+            T::VERSION
+        );
+    }
+
+    serde_json::from_value(data).map_err(|e| {
+        Report::new(Error::FileOperation(format!(
+            "Failed to deserialize state file {}: {e}",
+            path.display()
+        )))
+    })
+}
+
+/// Save a state file at the current version
+pub fn save<T: StateFormat>(path: &Path, value: &T) -> Result<()> {
+    let data = serde_json::to_value(value).map_err(|e| {
+        Report::new(Error::FileOperation(format!(
+            "Failed to serialize state for {}: {e}",
+            path.display()
+        )))
+    })?;
+
+    write_envelope(path, T::VERSION, &data)
+}
+
+/// Write the envelope atomically (temp file + rename) so a crash never leaves a partial file
+fn write_envelope(path: &Path, version: u32, data: &Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            Report::new(Error::FileOperation(format!(
+                "Failed to create state directory {}: {e}",
+                parent.display()
+            )))
+        })?;
+    }
+
+    let envelope = json!({ FIELD_VERSION: version, FIELD_DATA: data });
+    let contents = serde_json::to_string_pretty(&envelope).map_err(|e| {
+        Report::new(Error::FileOperation(format!(
+            "Failed to serialize state file {}: {e}",
+            path.display()
+        )))
+    })?;
+
+    let temp_path = with_suffix(path, ".tmp");
+    std::fs::write(&temp_path, contents)
+        .and_then(|()| std::fs::rename(&temp_path, path))
+        .map_err(|e| {
+            Report::new(Error::FileOperation(format!(
+                "Failed to write state file {}: {e}",
+                path.display()
+            )))
+        })
+}
+
+/// Keep a copy of a state file before it is migrated
+fn backup(path: &Path, version: u32) -> Result<()> {
+    let backup_path = with_suffix(path, &format!(".v{version}.bak"));
+    std::fs::copy(path, &backup_path).map_err(|e| {
+        Report::new(Error::FileOperation(format!(
+            "Failed to back up state file {} to {}: {e}",
+            path.display(),
+            backup_path.display()
+        )))
+    })?;
+    Ok(())
+}
+
+/// Append a suffix to the full file name (`state.json` -> `state.json.tmp`)
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.as_os_str().to_owned();
+    file_name.push(suffix);
+    PathBuf::from(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    struct Aliases {
+        entries: Vec<String>,
+    }
+
+    impl StateFormat for Aliases {
+        const FILE_NAME: &'static str = "aliases.json";
+        const VERSION: u32 = 2;
+
+        // Version 1 stored a bare array
+        fn migrate(from_version: u32, data: Value) -> Result<Value> {
+            match from_version {
+                1 => Ok(json!({ "entries": data })),
+                _ => Err(Report::new(Error::Configuration(
+                    "unknown version".to_string(),
+                ))),
+            }
+        }
+    }
+
+    fn temp_state_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_brp_mcp_state_test_{name}_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join(Aliases::FILE_NAME)
+    }
+
+    #[test]
+    fn test_missing_file_loads_default() {
+        let path = temp_state_file("missing");
+
+        assert_eq!(load::<Aliases>(&path).unwrap(), Aliases::default());
+    }
+
+    #[test]
+    fn test_save_then_load_round_trips() {
+        let path = temp_state_file("round_trip");
+        let aliases = Aliases {
+            entries: vec!["player".to_string()],
+        };
+
+        save(&path, &aliases).unwrap();
+
+        assert_eq!(load::<Aliases>(&path).unwrap(), aliases);
+    }
+
+    #[test]
+    fn test_old_version_is_migrated_and_backed_up() {
+        let path = temp_state_file("migrate");
+        write_envelope(&path, 1, &json!(["player"])).unwrap();
+
+        let aliases = load::<Aliases>(&path).unwrap();
+
+        assert_eq!(aliases.entries, vec!["player".to_string()]);
+        assert!(with_suffix(&path, ".v1.bak").exists());
+        let rewritten: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(rewritten[FIELD_VERSION], 2);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let path = temp_state_file("newer");
+        write_envelope(&path, 3, &json!({ "entries": [] })).unwrap();
+
+        assert!(load::<Aliases>(&path).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Near-miss suggestions for mistyped names
+//!
+//! Type paths are long and their module paths easy to get wrong, so a name is compared with each
+//! candidate both as a whole and by its last segment: `bevy_transform::Transfrom` and
+//! `Transfrom` both find `bevy_transform::components::transform::Transform`.
+
+/// Most suggestions returned for a name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Levenshtein distance between two strings, counted in characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Last `::` segment of a type path, ignoring generic arguments
+pub fn short_name(type_path: &str) -> &str {
+    let base = type_path.split('<').next().unwrap_or(type_path);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+/// The candidates closest to a name, best first
+///
+/// A candidate matches when its short name or full path is within a third of the compared
+/// length of the name (case-insensitively), so unrelated names aren't suggested.
+pub fn suggestions<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let name_lower = name.to_lowercase();
+    let short_lower = short_name(name).to_lowercase();
+    let mut scored: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .filter_map(|candidate| {
+            let whole = edit_distance(&name_lower, &candidate.to_lowercase());
+            let short = edit_distance(&short_lower, &short_name(candidate).to_lowercase());
+            let whole_ok = whole <= name_lower.chars().count() / 3;
+            let short_ok = short <= short_lower.chars().count().max(3) / 3;
+            (whole_ok || short_ok).then_some((whole.min(short), candidate))
+        })
+        .collect();
+    scored.sort_unstable();
+    scored.dedup_by_key(|(_, candidate)| *candidate);
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_near_misses_are_suggested_by_path_or_short_name() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(short_name("bevy_ecs::Foo<bevy_ecs::Bar>"), "Foo");
+
+        let types = [
+            "bevy_transform::components::transform::Transform",
+            "bevy_transform::components::global_transform::GlobalTransform",
+            "bevy_ecs::name::Name",
+        ];
+        assert_eq!(
+            suggestions("bevy_transform::Transfrom", types),
+            ["bevy_transform::components::transform::Transform"]
+        );
+        assert_eq!(
+            suggestions("bevy_ecs::name::Nmae", types),
+            ["bevy_ecs::name::Name"]
+        );
+        assert!(suggestions("my_game::Health", types).is_empty());
+    }
+}
@@ -1,7 +1,11 @@
 // Shared support modules
 
+pub mod fuzzy;
 pub mod params;
+pub mod progress;
 pub mod response;
 pub mod schema;
+pub mod secret;
 pub mod serialization;
 pub mod service;
+pub mod session;
@@ -0,0 +1,148 @@
+//! Progress notifications for long-running tool calls
+//!
+//! The tool generator runs every handler in a progress scope, so code deep inside a call can
+//! report how far it got without being handed the request context. Outside a scope, reporting
+//! does nothing.
+//!
+//! Tool calls don't expose the client's progress token, so notifications carry the ID of the
+//! call they belong to.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+use rmcp::model::{
+    LoggingLevel, LoggingMessageNotificationParam, ProgressNotificationParam, ProgressToken,
+};
+use rmcp::service::RequestContext;
+use rmcp::{Peer, RoleServer};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+/// Time between progress notifications of an operation that can't count its steps
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+tokio::task_local! {
+    /// Where progress of the current tool call is reported
+    static PROGRESS: Progress;
+}
+
+/// Where progress of a tool call is reported
+#[derive(Clone)]
+pub struct Progress {
+    peer:      Peer<RoleServer>,
+    token:     ProgressToken,
+    cancelled: CancellationToken,
+    /// Highest progress reported, as notifications must never go backwards
+    reported:  Arc<AtomicU32>,
+}
+
+impl Progress {
+    pub fn new(context: &RequestContext<RoleServer>) -> Self {
+        Self {
+            peer:      context.peer.clone(),
+            token:     context.id.clone(),
+            cancelled: context.ct.clone(),
+            reported:  Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// Whether the client cancelled the call
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.is_cancelled()
+    }
+
+    /// Report progress so far, out of a total when it's known
+    ///
+    /// Progress lower than what was already reported is skipped.
+    pub async fn report(&self, progress: u32, total: Option<u32>) {
+        if self.reported.fetch_max(progress, Ordering::Relaxed) > progress {
+            return;
+        }
+        let notification = ProgressNotificationParam {
+            progress_token: self.token.clone(),
+            progress,
+            total,
+        };
+        if let Err(e) = self.peer.notify_progress(notification).await {
+            tracing::debug!("Failed to send progress: {}", e);
+        }
+    }
+
+    /// Send a line of output, such as a cargo build line, as an info log message
+    pub async fn log(&self, logger: &str, line: &str) {
+        let notification = LoggingMessageNotificationParam {
+            level:  LoggingLevel::Info,
+            logger: Some(logger.to_string()),
+            data:   json!(line),
+        };
+        if let Err(e) = self.peer.notify_logging_message(notification).await {
+            tracing::debug!("Failed to send progress output: {}", e);
+        }
+    }
+}
+
+/// Run a future with the progress it reports going to `progress`
+pub async fn scope<F: Future>(progress: Progress, future: F) -> F::Output {
+    PROGRESS.scope(progress, future).await
+}
+
+/// Where progress of the current tool call is reported, if anywhere
+pub fn current() -> Option<Progress> {
+    PROGRESS.try_with(Clone::clone).ok()
+}
+
+/// Report progress of the current tool call, out of a total when it's known
+pub async fn report(progress: usize, total: Option<usize>) {
+    if let Some(current) = current() {
+        current
+            .report(saturate(progress), total.map(saturate))
+            .await;
+    }
+}
+
+/// Run a future, reporting the milliseconds it has taken every second until it finishes
+///
+/// For operations that can't count their steps, such as fetching a large registry schema; calls
+/// that finish within a second send nothing.
+pub async fn heartbeat<F: Future>(future: F) -> F::Output {
+    let Some(progress) = current() else {
+        return future.await;
+    };
+    let started = Instant::now();
+    let mut ticks = tokio::time::interval_at(
+        tokio::time::Instant::from_std(started + HEARTBEAT_INTERVAL),
+        HEARTBEAT_INTERVAL,
+    );
+    let mut future = std::pin::pin!(future);
+    loop {
+        tokio::select! {
+            output = &mut future => return output,
+            _ = ticks.tick() => {
+                let elapsed = u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX);
+                progress.report(elapsed, None).await;
+            }
+        }
+    }
+}
+
+fn saturate(value: usize) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reporting_outside_a_scope_does_nothing() {
+        assert!(current().is_none());
+        report(3, Some(10)).await;
+        let output = heartbeat(async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            42
+        })
+        .await;
+        assert_eq!(output, 42);
+    }
+}
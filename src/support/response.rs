@@ -5,13 +5,19 @@ use serde_json::Value;
 
 use crate::app_tools::support::scanning::extract_workspace_name;
 use crate::brp_tools::brp_set_debug_mode::is_debug_enabled;
-use crate::error::{Error, Result};
+use crate::error::{Error, ErrorCode, Result};
 
 /// Standard JSON response structure for all tools
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonResponse {
     pub status:                ResponseStatus,
     pub message:               String,
+    /// Category of the failure, set on every error response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code:            Option<ErrorCode>,
+    /// Structured details of the failure, set on every error response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details:               Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data:                  Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,7 +47,7 @@ impl JsonResponse {
     /// Convert to JSON string with fallback on error
     pub fn to_json_fallback(&self) -> String {
         self.to_json().unwrap_or_else(|_| {
-            r#"{"status":"error","message":"Failed to serialize response"}"#.to_string()
+            r#"{"status":"error","message":"Failed to serialize response","error_code":"internal","details":{}}"#.to_string()
         })
     }
 }
@@ -50,6 +56,8 @@ impl JsonResponse {
 pub struct ResponseBuilder {
     status:                ResponseStatus,
     message:               String,
+    error_code:            Option<ErrorCode>,
+    details:               Option<Value>,
     data:                  Option<Value>,
     brp_mcp_debug_info:    Option<Value>,
     brp_extras_debug_info: Option<Value>,
@@ -60,6 +68,8 @@ impl ResponseBuilder {
         Self {
             status:                ResponseStatus::Success,
             message:               String::new(),
+            error_code:            None,
+            details:               None,
             data:                  None,
             brp_mcp_debug_info:    None,
             brp_extras_debug_info: None,
@@ -70,17 +80,37 @@ impl ResponseBuilder {
         Self {
             status:                ResponseStatus::Error,
             message:               String::new(),
+            error_code:            None,
+            details:               None,
             data:                  None,
             brp_mcp_debug_info:    None,
             brp_extras_debug_info: None,
         }
     }
 
+    /// Error response for an internal error, with its message, code and details
+    pub fn from_error(error: &Error) -> Self {
+        Self::error()
+            .message(error.to_string())
+            .error_code(error.code())
+            .details(error.details())
+    }
+
     pub fn message(mut self, message: impl Into<String>) -> Self {
         self.message = message.into();
         self
     }
 
+    pub const fn error_code(mut self, error_code: ErrorCode) -> Self {
+        self.error_code = Some(error_code);
+        self
+    }
+
+    pub fn details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
     pub fn data(mut self, data: impl Serialize) -> Result<Self> {
         use error_stack::ResultExt;
 
@@ -120,24 +150,32 @@ impl ResponseBuilder {
         }
 
         // Only inject BRP MCP debug info if debug mode is enabled and debug info is provided
-        if let Some(debug_info) = brp_mcp_debug {
-            if let Ok(serialized) = serde_json::to_value(&debug_info) {
-                self.brp_mcp_debug_info = Some(serialized);
-            }
+        if let Some(debug_info) = brp_mcp_debug
+            && let Ok(serialized) = serde_json::to_value(&debug_info)
+        {
+            self.brp_mcp_debug_info = Some(serialized);
         }
 
         // Only inject BRP extras debug info if debug mode is enabled and debug info is provided
-        if let Some(debug_info) = brp_extras_debug {
-            if let Ok(serialized) = serde_json::to_value(&debug_info) {
-                self.brp_extras_debug_info = Some(serialized);
-            }
+        if let Some(debug_info) = brp_extras_debug
+            && let Ok(serialized) = serde_json::to_value(&debug_info)
+        {
+            self.brp_extras_debug_info = Some(serialized);
         }
 
         self
     }
 
+    /// Error responses without an explicit code are reported as internal errors
     pub fn build(self) -> JsonResponse {
+        let is_error = matches!(self.status, ResponseStatus::Error);
         JsonResponse {
+            error_code:            self
+                .error_code
+                .or_else(|| is_error.then_some(ErrorCode::Internal)),
+            details:               self
+                .details
+                .or_else(|| is_error.then(|| Value::Object(serde_json::Map::new()))),
             status:                self.status,
             message:               self.message,
             data:                  self.data,
@@ -152,9 +190,36 @@ pub fn add_workspace_info_to_response(
     response_data: &mut serde_json::Value,
     workspace_root: Option<&PathBuf>,
 ) {
-    if let Some(root) = workspace_root {
-        if let Some(workspace_name) = extract_workspace_name(root) {
-            response_data["workspace"] = serde_json::Value::String(workspace_name);
-        }
+    if let Some(root) = workspace_root
+        && let Some(workspace_name) = extract_workspace_name(root)
+    {
+        response_data["workspace"] = serde_json::Value::String(workspace_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_error_responses_always_have_a_code() {
+        let response =
+            serde_json::to_value(ResponseBuilder::error().message("oops").build()).unwrap();
+        assert_eq!(response["error_code"], "internal");
+        assert_eq!(response["details"], json!({}));
+
+        let response = serde_json::to_value(
+            ResponseBuilder::from_error(&Error::ConnectionRefused("port 15702".to_string()))
+                .build(),
+        )
+        .unwrap();
+        assert_eq!(response["error_code"], "connection_refused");
+
+        let response = serde_json::to_value(ResponseBuilder::success().build()).unwrap();
+        assert!(response.get("error_code").is_none());
     }
 }
@@ -54,6 +54,30 @@ impl SchemaBuilder {
         self
     }
 
+    /// Add a number array property to the schema
+    pub fn add_number_array_property(
+        mut self,
+        name: &str,
+        description: &str,
+        required: bool,
+    ) -> Self {
+        let mut prop = Map::new();
+        prop.insert("type".to_string(), "array".into());
+
+        let mut items = Map::new();
+        items.insert("type".to_string(), "number".into());
+        prop.insert("items".to_string(), items.into());
+
+        prop.insert("description".to_string(), description.into());
+        self.properties.insert(name.to_string(), prop.into());
+
+        if required {
+            self.required.push(name.to_string());
+        }
+
+        self
+    }
+
     /// Add a number property to the schema
     pub fn add_number_property(mut self, name: &str, description: &str, required: bool) -> Self {
         let mut prop = Map::new();
@@ -96,6 +120,34 @@ impl SchemaBuilder {
         self
     }
 
+    /// Add bounds to a previously added number property, or to the items of a number array
+    pub fn with_range(mut self, name: &str, min: u64, max: Option<u64>) -> Self {
+        let prop = self.properties.get_mut(name).map(|prop| {
+            if prop.get("items").is_some() {
+                &mut prop["items"]
+            } else {
+                prop
+            }
+        });
+        if let Some(Value::Object(prop)) = prop {
+            prop.insert("minimum".to_string(), min.into());
+            if let Some(max) = max {
+                prop.insert("maximum".to_string(), max.into());
+            }
+        }
+
+        self
+    }
+
+    /// Restrict a previously added string property to a fixed set of values
+    pub fn with_enum(mut self, name: &str, values: &[&str]) -> Self {
+        if let Some(Value::Object(prop)) = self.properties.get_mut(name) {
+            prop.insert("enum".to_string(), values.into());
+        }
+
+        self
+    }
+
     /// Build the final schema
     pub fn build(self) -> Arc<Map<String, Value>> {
         let mut schema = Map::new();
@@ -0,0 +1,28 @@
+//! Comparing secrets
+//!
+//! Tokens are compared in constant time, so how long a wrong guess takes to be rejected doesn't
+//! tell how much of it was right.
+
+/// Whether `given` equals `expected`, taking the same time wherever they differ
+pub fn tokens_match(expected: &str, given: &str) -> bool {
+    let (expected, given) = (expected.as_bytes(), given.as_bytes());
+    expected.len() == given.len()
+        && expected
+            .iter()
+            .zip(given)
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match_only_when_equal() {
+        assert!(tokens_match("s3cret", "s3cret"));
+        assert!(!tokens_match("s3cret", "s3creT"));
+        assert!(!tokens_match("s3cret", "s3cret!"));
+        assert!(!tokens_match("s3cret", ""));
+    }
+}
@@ -1,6 +1,7 @@
 //! Shared serialization utilities
 
 use rmcp::model::{CallToolResult, Content};
+use serde_json::{Value, json};
 
 use crate::support::response::JsonResponse;
 
@@ -8,3 +9,16 @@ use crate::support::response::JsonResponse;
 pub fn json_response_to_result(response: &JsonResponse) -> CallToolResult {
     CallToolResult::success(vec![Content::text(response.to_json_fallback())])
 }
+
+/// Reads back the JSON payload of a `CallToolResult`
+///
+/// Text that isn't JSON is returned as a string, and a result without text content as null.
+pub fn result_to_json(result: &CallToolResult) -> Value {
+    result
+        .content
+        .iter()
+        .find_map(|content| content.as_text())
+        .map_or(Value::Null, |text| {
+            serde_json::from_str(&text.text).unwrap_or_else(|_| json!(text.text))
+        })
+}
@@ -31,6 +31,17 @@ pub async fn fetch_roots_and_get_paths(
         .clone())
 }
 
+/// Fetch roots from the client and return the project it is working in, its first root
+pub async fn fetch_project_root(
+    service: &BrpMcpService,
+    context: RequestContext<RoleServer>,
+) -> Result<Option<PathBuf>, McpError> {
+    Ok(fetch_roots_and_get_paths(service, context)
+        .await?
+        .into_iter()
+        .next())
+}
+
 /// Generic handler wrapper that fetches search paths and calls the provided handler
 /// This eliminates the repetitive pattern of fetching roots in every tool handler
 pub async fn handle_with_paths<F, Fut>(
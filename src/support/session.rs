@@ -0,0 +1,35 @@
+//! The MCP session a tool call belongs to
+//!
+//! Every `BrpMcpService`, the one serving stdio or one per SSE session, has its own session ID.
+//! Tool calls run in a scope holding it, so state kept outside the service, such as the
+//! corrections log, can be told apart per session. Outside a scope the session is
+//! [`NO_SESSION`].
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Session of code running outside any tool call
+pub const NO_SESSION: u64 = 0;
+
+/// ID given to the next session
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+tokio::task_local! {
+    /// Session of the current tool call
+    static SESSION: u64;
+}
+
+/// A fresh session ID
+pub fn new_id() -> u64 {
+    NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Run a future as part of a session
+pub async fn scope<F: Future>(session: u64, future: F) -> F::Output {
+    SESSION.scope(session, future).await
+}
+
+/// Session of the current tool call
+pub fn current() -> u64 {
+    SESSION.try_with(|session| *session).unwrap_or(NO_SESSION)
+}
@@ -10,47 +10,87 @@
 //! - **Special Tools**: Tools requiring custom extractors or response handling (query, spawn,
 //!   execute)
 //! - **Local Tools**: Execute within MCP server (log management, app lifecycle)
+//! - **Watch Tools**: Start or stop background watches held by the watch manager
+//! - **Server Tools**: Describe the server itself (tool manifest)
 //!
 //! # Handler Types
 //!
 //! - `HandlerType::Brp`: Execute remote BRP method calls over network
 //! - `HandlerType::Local`: Execute local functions within MCP server
+//! - `HandlerType::Watch`: Start a background watch task, or stop one, through the watch manager
 //!
 //! # Adding New Tools
 //!
 //! For standard BRP tools, add to `get_standard_tools()` with `HandlerType::Brp`.
 //! For local tools, add to `get_log_tools()` or `get_app_tools()` with `HandlerType::Local`.
 //! For complex tools needing custom behavior, add to `get_special_tools()`.
+//! For tools that start or stop a watch, add to `get_watch_tools()` with `HandlerType::Watch`;
+//! start tools get the port and host parameters appended.
+//! For local tools describing the server itself, add to `get_server_tools()`.
 //!
 //! Use `FormatterDef::default()` for simple responses, custom formatters for structured output.
 
+use serde_json::Value;
+
+use crate::app_tools::support::brp_readiness::MAX_WAIT_TIMEOUT_MS;
 use crate::brp_tools::constants::{
-    DESC_PORT, JSON_FIELD_COMPONENT, JSON_FIELD_COMPONENTS, JSON_FIELD_COUNT, JSON_FIELD_DATA,
-    JSON_FIELD_DESTROYED_ENTITY, JSON_FIELD_ENTITY, JSON_FIELD_METADATA, JSON_FIELD_PATH,
-    JSON_FIELD_PORT, JSON_FIELD_RESOURCE, JSON_FIELD_RESOURCES, JSON_FIELD_VALUE,
-    PARAM_COMPONENT_COUNT, PARAM_DATA, PARAM_ENTITIES, PARAM_ENTITY_COUNT, PARAM_FILTER,
-    PARAM_FORMATS, PARAM_METHOD, PARAM_PARAMS, PARAM_PARENT, PARAM_QUERY_PARAMS, PARAM_RESULT,
-    PARAM_SPAWNED_ENTITY, PARAM_STRICT, PARAM_TYPES, PARAM_WITH_CRATES, PARAM_WITH_TYPES,
-    PARAM_WITHOUT_CRATES, PARAM_WITHOUT_TYPES,
+    DESC_BACKOFF_MS, DESC_CANONICAL_ORDER, DESC_CONFIRM, DESC_DELTA_TOKEN, DESC_HOST, DESC_PORT,
+    DESC_PORTS, DESC_PROJECTION, DESC_RETRIES, DESC_TIMEOUT_MS, DESC_VERIFY, JSON_FIELD_COMPONENT,
+    JSON_FIELD_COMPONENTS, JSON_FIELD_COUNT, JSON_FIELD_DATA, JSON_FIELD_DELTA_TOKEN,
+    JSON_FIELD_DESTROYED_ENTITY, JSON_FIELD_ENTITY, JSON_FIELD_HOST, JSON_FIELD_METADATA,
+    JSON_FIELD_PATH, JSON_FIELD_PORT, JSON_FIELD_RESOURCE, JSON_FIELD_RESOURCES, JSON_FIELD_VALUE,
+    JSON_FIELD_WATCH_ID, PARAM_BACKOFF_MS, PARAM_CANONICAL_ORDER, PARAM_COMPONENT_COUNT,
+    PARAM_CONFIRM, PARAM_CURSOR, PARAM_DATA, PARAM_ENTITIES, PARAM_ENTITY_COUNT, PARAM_FILTER,
+    PARAM_FORMATS, PARAM_INLINE, PARAM_LABEL, PARAM_LIMIT, PARAM_MAX_IMAGE_BYTES,
+    PARAM_MAX_RESPONSE_BYTES, PARAM_MCP_SPAWNED_ONLY, PARAM_METHOD, PARAM_OFFSET, PARAM_PARAMS,
+    PARAM_PARENT, PARAM_PORTS, PARAM_PROJECTION, PARAM_QUERY_PARAMS, PARAM_RESULT, PARAM_RETRIES,
+    PARAM_SPAWNED_ENTITY, PARAM_STRICT, PARAM_TIMEOUT_MS, PARAM_TYPES, PARAM_VERIFY,
+    PARAM_WITH_CRATES, PARAM_WITH_TYPES, PARAM_WITHOUT_CRATES, PARAM_WITHOUT_TYPES,
+};
+use crate::brp_tools::support::inline_image::MAX_IMAGE_BYTES_LIMIT;
+use crate::brp_tools::watch::bevy_get_watch::{
+    DESC_DEBOUNCE_MS, DESC_THROTTLE_MS, MAX_COALESCE_MS, PARAM_DEBOUNCE_MS, PARAM_THROTTLE_MS,
 };
-use crate::constants::PARAM_WORKSPACE;
+use crate::brp_tools::watch::brp_start_perf_watch::{PARAM_DIAGNOSTICS, PARAM_THRESHOLDS};
+use crate::brp_tools::watch::brp_watch_resource::{INTERVAL_RANGE_MS, PARAM_INTERVAL_MS};
+use crate::brp_tools::watch::brp_watch_resources::PARAM_PATTERNS;
+use crate::constants::{
+    LOG_FORMAT_JSON, LOG_FORMAT_TEXT, PARAM_APP_NAME, PARAM_DRY_RUN, PARAM_ENV, PARAM_FEATURES,
+    PARAM_FORCE, PARAM_GROUP, PARAM_LOG_FILE, PARAM_LOG_FORMAT, PARAM_NO_DEFAULT_FEATURES,
+    PARAM_PACKAGE, PARAM_PID, PARAM_PROFILE, PARAM_RUST_LOG, PARAM_WAIT_FOR_BRP,
+    PARAM_WAIT_TIMEOUT_MS, PARAM_WITH_EXTRAS, PARAM_WORKSPACE, PARAM_WRAPPER, PROFILE_DEBUG,
+    PROFILE_RELEASE,
+};
+use crate::tool_manifest;
 use crate::tools::{
     BRP_METHOD_DESTROY, BRP_METHOD_EXTRAS_DISCOVER_FORMAT, BRP_METHOD_EXTRAS_SCREENSHOT,
-    BRP_METHOD_EXTRAS_SEND_KEYS, BRP_METHOD_EXTRAS_SET_DEBUG_MODE, BRP_METHOD_GET,
-    BRP_METHOD_GET_RESOURCE, BRP_METHOD_INSERT, BRP_METHOD_INSERT_RESOURCE, BRP_METHOD_LIST,
-    BRP_METHOD_LIST_RESOURCES, BRP_METHOD_MUTATE_COMPONENT, BRP_METHOD_MUTATE_RESOURCE,
-    BRP_METHOD_REMOVE, BRP_METHOD_REMOVE_RESOURCE, BRP_METHOD_RPC_DISCOVER, DESC_BEVY_DESTROY,
-    DESC_BEVY_GET, DESC_BEVY_GET_RESOURCE, DESC_BEVY_INSERT, DESC_BEVY_INSERT_RESOURCE,
-    DESC_BEVY_LIST, DESC_BEVY_LIST_RESOURCES, DESC_BEVY_MUTATE_COMPONENT,
+    BRP_METHOD_EXTRAS_SEND_GAMEPAD, BRP_METHOD_EXTRAS_SEND_KEYS, BRP_METHOD_EXTRAS_SEND_MOUSE,
+    BRP_METHOD_EXTRAS_SET_DEBUG_MODE, BRP_METHOD_GET, BRP_METHOD_GET_RESOURCE, BRP_METHOD_INSERT,
+    BRP_METHOD_INSERT_RESOURCE, BRP_METHOD_LIST, BRP_METHOD_LIST_RESOURCES,
+    BRP_METHOD_MUTATE_COMPONENT, BRP_METHOD_MUTATE_RESOURCE, BRP_METHOD_REMOVE,
+    BRP_METHOD_REMOVE_RESOURCE, BRP_METHOD_RPC_DISCOVER, DESC_BEVY_DESTROY, DESC_BEVY_GET,
+    DESC_BEVY_GET_RESOURCE, DESC_BEVY_GET_WATCH, DESC_BEVY_INSERT, DESC_BEVY_INSERT_RESOURCE,
+    DESC_BEVY_LIST, DESC_BEVY_LIST_RESOURCES, DESC_BEVY_LIST_WATCH, DESC_BEVY_MUTATE_COMPONENT,
     DESC_BEVY_MUTATE_RESOURCE, DESC_BEVY_REMOVE, DESC_BEVY_REMOVE_RESOURCE, DESC_BEVY_RPC_DISCOVER,
-    DESC_BRP_EXTRAS_DISCOVER_FORMAT, DESC_BRP_EXTRAS_SCREENSHOT, DESC_BRP_EXTRAS_SEND_KEYS,
-    DESC_BRP_EXTRAS_SET_DEBUG_MODE, TOOL_BEVY_DESTROY, TOOL_BEVY_GET, TOOL_BEVY_GET_RESOURCE,
-    TOOL_BEVY_INSERT, TOOL_BEVY_INSERT_RESOURCE, TOOL_BEVY_LIST, TOOL_BEVY_LIST_RESOURCES,
-    TOOL_BEVY_MUTATE_COMPONENT, TOOL_BEVY_MUTATE_RESOURCE, TOOL_BEVY_REMOVE,
-    TOOL_BEVY_REMOVE_RESOURCE, TOOL_BEVY_RPC_DISCOVER, TOOL_BRP_EXTRAS_DISCOVER_FORMAT,
-    TOOL_BRP_EXTRAS_SCREENSHOT, TOOL_BRP_EXTRAS_SEND_KEYS, TOOL_BRP_EXTRAS_SET_DEBUG_MODE,
+    DESC_BRP_EXPORT_TOOL_MANIFEST, DESC_BRP_EXTRAS_DISCOVER_FORMAT, DESC_BRP_EXTRAS_SCREENSHOT,
+    DESC_BRP_EXTRAS_SEND_GAMEPAD, DESC_BRP_EXTRAS_SEND_KEYS, DESC_BRP_EXTRAS_SEND_MOUSE,
+    DESC_BRP_EXTRAS_SET_DEBUG_MODE, DESC_BRP_START_PERF_WATCH, DESC_BRP_STOP_WATCH,
+    DESC_BRP_WATCH_RESOURCE, DESC_BRP_WATCH_RESOURCES, TOOL_BEVY_DESTROY, TOOL_BEVY_GET,
+    TOOL_BEVY_GET_RESOURCE, TOOL_BEVY_GET_WATCH, TOOL_BEVY_INSERT, TOOL_BEVY_INSERT_RESOURCE,
+    TOOL_BEVY_LIST, TOOL_BEVY_LIST_RESOURCES, TOOL_BEVY_LIST_WATCH, TOOL_BEVY_MUTATE_COMPONENT,
+    TOOL_BEVY_MUTATE_RESOURCE, TOOL_BEVY_REMOVE, TOOL_BEVY_REMOVE_RESOURCE, TOOL_BEVY_RPC_DISCOVER,
+    TOOL_BRP_EXPORT_TOOL_MANIFEST, TOOL_BRP_EXTRAS_DISCOVER_FORMAT, TOOL_BRP_EXTRAS_SCREENSHOT,
+    TOOL_BRP_EXTRAS_SEND_GAMEPAD, TOOL_BRP_EXTRAS_SEND_KEYS, TOOL_BRP_EXTRAS_SEND_MOUSE,
+    TOOL_BRP_EXTRAS_SET_DEBUG_MODE, TOOL_BRP_START_PERF_WATCH, TOOL_BRP_STOP_WATCH,
+    TOOL_BRP_WATCH_RESOURCE, TOOL_BRP_WATCH_RESOURCES,
 };
 
+/// Highest valid port number
+const MAX_PORT: u64 = 65_535;
+
+/// Highest value of parameters that handlers read as `u32` (PIDs, ages in seconds)
+const MAX_U32_PARAM: u64 = 4_294_967_295;
+
 /// Represents a parameter definition for a BRP tool
 #[derive(Clone)]
 pub struct ParamDef {
@@ -62,6 +102,8 @@ pub struct ParamDef {
     pub required:    bool,
     /// Type of the parameter
     pub param_type:  ParamType,
+    /// Valid values of the parameter, enforced before the tool runs
+    pub constraint:  Option<ParamConstraint>,
 }
 
 impl ParamDef {
@@ -72,9 +114,77 @@ impl ParamDef {
             description: DESC_PORT,
             required:    false,
             param_type:  ParamType::Number,
+            constraint:  Some(ParamConstraint::Range {
+                min: 1,
+                max: MAX_PORT,
+            }),
+        }
+    }
+
+    /// Standard host parameter (accompanies every port parameter of a BRP tool)
+    pub const fn host() -> Self {
+        Self {
+            name:        JSON_FIELD_HOST,
+            description: DESC_HOST,
+            required:    false,
+            param_type:  ParamType::String,
+            constraint:  None,
+        }
+    }
+
+    /// Delta token parameter (accepted by tools whose results can be diffed between calls)
+    pub const fn delta_token() -> Self {
+        Self {
+            name:        JSON_FIELD_DELTA_TOKEN,
+            description: DESC_DELTA_TOKEN,
+            required:    false,
+            param_type:  ParamType::String,
+            constraint:  None,
         }
     }
 
+    /// Canonical ordering opt-out (accepted by tools returning rows or type lists)
+    pub const fn canonical_order() -> Self {
+        Self {
+            name:        PARAM_CANONICAL_ORDER,
+            description: DESC_CANONICAL_ORDER,
+            required:    false,
+            param_type:  ParamType::Boolean,
+            constraint:  None,
+        }
+    }
+
+    /// Read-back verification of corrected components (accepted by spawn and insert)
+    pub const fn verify() -> Self {
+        Self {
+            name:        PARAM_VERIFY,
+            description: DESC_VERIFY,
+            required:    false,
+            param_type:  ParamType::Boolean,
+            constraint:  None,
+        }
+    }
+
+    /// Confirmation of an operation that can't be undone (accepted by destroy, resource removal
+    /// and shutdown)
+    pub const fn confirm() -> Self {
+        Self::any(PARAM_CONFIRM, DESC_CONFIRM, false)
+    }
+
+    /// Projection of the result (accepted by query and get)
+    pub const fn projection() -> Self {
+        Self::any(PARAM_PROJECTION, DESC_PROJECTION, false)
+    }
+
+    /// Timeout and retry parameters (appended to every BRP tool)
+    pub const fn request_policy() -> [Self; 3] {
+        [
+            Self::number(PARAM_TIMEOUT_MS, DESC_TIMEOUT_MS, false).with_clamp(100, 600_000),
+            Self::number(PARAM_RETRIES, DESC_RETRIES, false).with_clamp(0, 10),
+            Self::number(PARAM_BACKOFF_MS, DESC_BACKOFF_MS, false).with_clamp(0, 60_000),
+        ]
+    }
+
     /// Entity ID parameter with custom description
     pub const fn entity(description: &'static str, required: bool) -> Self {
         Self {
@@ -82,6 +192,10 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::Number,
+            constraint: Some(ParamConstraint::Range {
+                min: 0,
+                max: u64::MAX,
+            }),
         }
     }
 
@@ -92,6 +206,7 @@ impl ParamDef {
             description,
             required: true,
             param_type: ParamType::String,
+            constraint: None,
         }
     }
 
@@ -102,6 +217,7 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::Any,
+            constraint: None,
         }
     }
 
@@ -112,6 +228,7 @@ impl ParamDef {
             description,
             required: true,
             param_type: ParamType::String,
+            constraint: None,
         }
     }
 
@@ -122,21 +239,17 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::Any,
+            constraint: None,
         }
     }
 
-    /// Entity + port (used in destroy, get, insert, remove, `mutate_component`)
-    pub const fn entity_with_port(entity_desc: &'static str) -> [Self; 2] {
-        [Self::entity(entity_desc, true), Self::port()]
+    /// Resource + port + host (used in `get_resource`, `insert_resource`)
+    pub const fn resource_with_port(resource_desc: &'static str) -> [Self; 3] {
+        [Self::resource(resource_desc), Self::port(), Self::host()]
     }
 
-    /// Resource + port (used in `get_resource`, `insert_resource`, `remove_resource`)
-    pub const fn resource_with_port(resource_desc: &'static str) -> [Self; 2] {
-        [Self::resource(resource_desc), Self::port()]
-    }
-
-    /// Resource + path + value + port (used in `mutate_resource`)
-    pub const fn resource_mutation_params() -> [Self; 4] {
+    /// Resource + path + value + port + host (used in `mutate_resource`)
+    pub const fn resource_mutation_params() -> [Self; 5] {
         [
             Self::resource("The fully-qualified type name of the resource to mutate"),
             Self::path("The path to the field within the resource (e.g., 'settings.volume')"),
@@ -145,6 +258,7 @@ impl ParamDef {
                 true,
             ),
             Self::port(),
+            Self::host(),
         ]
     }
 
@@ -155,6 +269,7 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::Boolean,
+            constraint: None,
         }
     }
 
@@ -165,6 +280,7 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::String,
+            constraint: None,
         }
     }
 
@@ -179,6 +295,21 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::StringArray,
+            constraint: None,
+        }
+    }
+
+    /// Ports parameter for read-only tools that can broadcast to several apps
+    pub const fn ports() -> Self {
+        Self {
+            name:        PARAM_PORTS,
+            description: DESC_PORTS,
+            required:    false,
+            param_type:  ParamType::NumberArray,
+            constraint:  Some(ParamConstraint::Range {
+                min: 1,
+                max: MAX_PORT,
+            }),
         }
     }
 
@@ -189,6 +320,7 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::Number,
+            constraint: None,
         }
     }
 
@@ -199,6 +331,31 @@ impl ParamDef {
             description,
             required,
             param_type: ParamType::Any,
+            constraint: None,
+        }
+    }
+
+    /// Reject numeric values outside `min..=max`
+    pub const fn with_range(self, min: u64, max: u64) -> Self {
+        Self {
+            constraint: Some(ParamConstraint::Range { min, max }),
+            ..self
+        }
+    }
+
+    /// Clamp numeric values into `min..=max`
+    pub const fn with_clamp(self, min: u64, max: u64) -> Self {
+        Self {
+            constraint: Some(ParamConstraint::Clamp { min, max }),
+            ..self
+        }
+    }
+
+    /// Reject string values other than the given ones
+    pub const fn one_of(self, values: &'static [&'static str]) -> Self {
+        Self {
+            constraint: Some(ParamConstraint::OneOf(values)),
+            ..self
         }
     }
 
@@ -229,8 +386,8 @@ impl ParamDef {
         )
     }
 
-    /// Entity + component + path + value + port (for `mutate_component`)
-    pub const fn component_mutation_params() -> [Self; 5] {
+    /// Entity + component + path + value + port + host (for `mutate_component`)
+    pub const fn component_mutation_params() -> [Self; 6] {
         [
             Self::entity("The entity ID containing the component to mutate", true),
             Self::string(
@@ -244,11 +401,12 @@ impl ParamDef {
                 true,
             ),
             Self::port(),
+            Self::host(),
         ]
     }
 
-    /// Data + filter + strict + port (for query)
-    pub const fn query_params() -> [Self; 4] {
+    /// Data + filter + strict + port + host (for query)
+    pub const fn query_params() -> [Self; 5] {
         [
             Self::any(
                 PARAM_DATA,
@@ -262,6 +420,7 @@ impl ParamDef {
             ),
             Self::strict(),
             Self::port(),
+            Self::host(),
         ]
     }
 }
@@ -277,10 +436,129 @@ pub enum ParamType {
     Boolean,
     /// An array of strings
     StringArray,
+    /// An array of numbers
+    NumberArray,
     /// Any JSON value (object, array, etc.)
     Any,
 }
 
+impl ParamType {
+    /// Check that a supplied value has this type
+    ///
+    /// Array elements are checked one by one, so the error names the first offending element.
+    pub fn check(&self, name: &str, value: &Value) -> Result<(), String> {
+        let (array, expected, is_element): (Option<&str>, &str, fn(&Value) -> bool) = match self {
+            Self::Number => (None, "a number", Value::is_number),
+            Self::String => (None, "a string", Value::is_string),
+            Self::Boolean => (None, "a boolean", Value::is_boolean),
+            Self::StringArray => (Some("an array of strings"), "a string", Value::is_string),
+            Self::NumberArray => (Some("an array of numbers"), "a number", Value::is_number),
+            Self::Any => return Ok(()),
+        };
+
+        if let Some(array) = array {
+            let Value::Array(items) = value else {
+                return Err(format!(
+                    "{name} must be {array}, got {}",
+                    describe_value(value)
+                ));
+            };
+            return items
+                .iter()
+                .enumerate()
+                .find(|(_, item)| !is_element(item))
+                .map_or(Ok(()), |(index, item)| {
+                    Err(format!(
+                        "{name}[{index}] must be {expected}, got {}",
+                        describe_value(item)
+                    ))
+                });
+        }
+
+        if is_element(value) {
+            Ok(())
+        } else {
+            Err(format!(
+                "{name} must be {expected}, got {}",
+                describe_value(value)
+            ))
+        }
+    }
+}
+
+/// A supplied value as shown in validation errors: scalars verbatim, containers by kind
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+        scalar => scalar.to_string(),
+    }
+}
+
+/// Valid values of a parameter
+///
+/// Constraints are checked by the tool generator before a declarative tool runs and are
+/// reflected in the generated input schema.
+#[derive(Clone, Copy)]
+pub enum ParamConstraint {
+    /// Integer in `min..=max`; other values are rejected
+    Range { min: u64, max: u64 },
+    /// Integer clamped into `min..=max`; non-integers are rejected
+    Clamp { min: u64, max: u64 },
+    /// One of a fixed set of strings
+    OneOf(&'static [&'static str]),
+}
+
+impl ParamConstraint {
+    /// Check a supplied value, clamping it in place for `Clamp` constraints
+    ///
+    /// Constraints on array parameters apply to each element. The error names the offending
+    /// parameter or element.
+    pub fn apply(&self, name: &str, value: &mut Value) -> Result<(), String> {
+        if let Value::Array(items) = value {
+            return items
+                .iter_mut()
+                .enumerate()
+                .try_for_each(|(index, item)| self.apply(&format!("{name}[{index}]"), item));
+        }
+
+        let valid = match *self {
+            Self::Range { min, max } => value.as_u64().is_some_and(|n| (min..=max).contains(&n)),
+            Self::Clamp { min, max } => {
+                let clamped = if value.as_i64().is_some_and(|n| n < 0) {
+                    Some(min)
+                } else {
+                    value.as_u64().map(|n| n.clamp(min, max))
+                };
+                if let Some(clamped) = clamped {
+                    *value = Value::from(clamped);
+                }
+                clamped.is_some()
+            }
+            Self::OneOf(values) => value.as_str().is_some_and(|v| values.contains(&v)),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("{name} must be {}, got {value}", self.describe()))
+        }
+    }
+
+    /// Human-readable description of the accepted values
+    fn describe(&self) -> String {
+        match *self {
+            Self::Range { min, max: u64::MAX } | Self::Clamp { min, max: u64::MAX } => {
+                format!("an integer of at least {min}")
+            }
+            Self::Range { min, max } | Self::Clamp { min, max } => {
+                format!("an integer between {min} and {max}")
+            }
+            Self::OneOf(values) => format!("one of: {}", values.join(", ")),
+        }
+    }
+}
+
 /// Defines how to format the response for a tool
 #[derive(Clone)]
 pub struct FormatterDef {
@@ -378,6 +656,35 @@ pub enum HandlerType {
         /// Handler function name (e.g., "`list_logs`", "`read_log`")
         handler: &'static str,
     },
+    /// Watch handler - starts or stops a background watch
+    Watch {
+        /// Whether the tool starts a watch or stops one
+        operation: WatchOperation,
+    },
+}
+
+/// Operation of a watch tool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchOperation {
+    /// Spawn a watch task and register it with the watch manager, returning its ID and log path
+    Start(WatchKind),
+    /// Stop watches registered with the watch manager, by ID or by label
+    Stop,
+}
+
+/// What a started watch follows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Component values of an entity, streamed by `bevy/get+watch`
+    Entity,
+    /// Component list of an entity, streamed by `bevy/list+watch`
+    List,
+    /// Value of one resource, polled
+    Resource,
+    /// Values of every resource matching a set of patterns, polled under a shared label
+    Resources,
+    /// Performance diagnostics, sampled and checked against thresholds
+    Perf,
 }
 
 /// Complete definition of a BRP tool
@@ -387,7 +694,7 @@ pub struct BrpToolDef {
     pub name:            &'static str,
     /// Tool description
     pub description:     &'static str,
-    /// Handler type (BRP or Local)
+    /// Handler type (BRP, Local or Watch)
     pub handler:         HandlerType,
     /// Parameters for the tool
     pub params:          Vec<ParamDef>,
@@ -408,7 +715,12 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             handler:         HandlerType::Brp {
                 method: BRP_METHOD_DESTROY,
             },
-            params:          ParamDef::entity_with_port("The entity ID to destroy").to_vec(),
+            params:          vec![
+                ParamDef::entity("The entity ID to destroy", true),
+                ParamDef::port(),
+                ParamDef::host(),
+                ParamDef::confirm(),
+            ],
             param_extractor: ParamExtractorType::Entity { required: true },
             formatter:       FormatterDef {
                 formatter_type:  FormatterType::EntityOperation(JSON_FIELD_DESTROYED_ENTITY),
@@ -432,7 +744,15 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                     "Array of component types to retrieve. Each component must be a fully-qualified type name",
                     true,
                 ),
+                ParamDef::boolean(
+                    PARAM_STRICT,
+                    "If true, fails the call when any component is missing or can't be read; otherwise they are reported per component under errors (default: false)",
+                    false,
+                ),
                 ParamDef::port(),
+                ParamDef::host(),
+                ParamDef::delta_token(),
+                ParamDef::projection(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -460,6 +780,10 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             params:          vec![
                 ParamDef::entity("Optional entity ID to list components for", false),
                 ParamDef::port(),
+                ParamDef::ports(),
+                ParamDef::host(),
+                ParamDef::delta_token(),
+                ParamDef::canonical_order(),
             ],
             param_extractor: ParamExtractorType::Entity { required: false },
             formatter:       FormatterDef {
@@ -488,6 +812,7 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                 ParamDef::entity("The entity ID to remove components from", true),
                 ParamDef::components("Array of component type names to remove", true),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -512,7 +837,9 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                     "Object containing component data to insert. Keys are component types, values are component data. Note: Math types use array format - Vec2: [x,y], Vec3: [x,y,z], Vec4/Quat: [x,y,z,w], not objects with named fields.",
                     true,
                 ),
+                ParamDef::verify(),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -531,10 +858,14 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             handler:         HandlerType::Brp {
                 method: BRP_METHOD_GET_RESOURCE,
             },
-            params:          ParamDef::resource_with_port(
-                "The fully-qualified type name of the resource to get",
-            )
-            .to_vec(),
+            params:          [
+                ParamDef::resource_with_port(
+                    "The fully-qualified type name of the resource to get",
+                )
+                .as_slice(),
+                &[ParamDef::ports()],
+            ]
+            .concat(),
             param_extractor: ParamExtractorType::Resource,
             formatter:       FormatterDef {
                 formatter_type:  FormatterType::ResourceOperation,
@@ -567,6 +898,7 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                     true,
                 ),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -585,10 +917,12 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             handler:         HandlerType::Brp {
                 method: BRP_METHOD_REMOVE_RESOURCE,
             },
-            params:          ParamDef::resource_with_port(
-                "The fully-qualified type name of the resource to remove",
-            )
-            .to_vec(),
+            params:          vec![
+                ParamDef::resource("The fully-qualified type name of the resource to remove"),
+                ParamDef::port(),
+                ParamDef::host(),
+                ParamDef::confirm(),
+            ],
             param_extractor: ParamExtractorType::Resource,
             formatter:       FormatterDef {
                 formatter_type:  FormatterType::ResourceOperation,
@@ -642,7 +976,11 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             handler:         HandlerType::Brp {
                 method: BRP_METHOD_LIST_RESOURCES,
             },
-            params:          vec![ParamDef::port()],
+            params:          vec![
+                ParamDef::port(),
+                ParamDef::host(),
+                ParamDef::canonical_order(),
+            ],
             param_extractor: ParamExtractorType::EmptyParams,
             formatter:       FormatterDef {
                 formatter_type:  FormatterType::Simple,
@@ -666,7 +1004,7 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             handler:         HandlerType::Brp {
                 method: BRP_METHOD_RPC_DISCOVER,
             },
-            params:          vec![ParamDef::port()],
+            params:          vec![ParamDef::port(), ParamDef::host()],
             param_extractor: ParamExtractorType::EmptyParams,
             formatter:       FormatterDef {
                 formatter_type:  FormatterType::Simple,
@@ -691,6 +1029,7 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                     true,
                 ),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -711,7 +1050,19 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
             },
             params:          vec![
                 ParamDef::path("File path where the screenshot should be saved"),
+                ParamDef::boolean(
+                    PARAM_INLINE,
+                    "Also return the saved image as image content; needs a local app and an absolute path (default: false)",
+                    false,
+                ),
+                ParamDef::number(
+                    PARAM_MAX_IMAGE_BYTES,
+                    "Largest image returned with inline, in bytes; larger images are downscaled and re-encoded as JPEG to fit (default: 1048576)",
+                    false,
+                )
+                .with_range(1, MAX_IMAGE_BYTES_LIMIT),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -740,10 +1091,12 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                 ParamDef::string_array("keys", "Array of key code names to send", true),
                 ParamDef::number(
                     "duration_ms",
-                    "Duration in milliseconds to hold the keys before releasing (default: 100ms, max: 60000ms/1 minute)",
+                    "Duration in milliseconds to hold the keys before releasing (default: 100ms, max: 60000ms/1 minute, longer durations are clamped)",
                     false,
-                ),
+                )
+                .with_clamp(0, 60_000),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -761,6 +1114,119 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                 ],
             },
         },
+        // brp_extras/send_mouse
+        BrpToolDef {
+            name:            TOOL_BRP_EXTRAS_SEND_MOUSE,
+            description:     DESC_BRP_EXTRAS_SEND_MOUSE,
+            handler:         HandlerType::Brp {
+                method: BRP_METHOD_EXTRAS_SEND_MOUSE,
+            },
+            params:          vec![
+                ParamDef::string(
+                    "action",
+                    "Pointer action: move, click, drag or scroll",
+                    true,
+                )
+                .one_of(&["move", "click", "drag", "scroll"]),
+                ParamDef::any(
+                    "position",
+                    "Window position [x, y] in logical pixels to move to, click at or start dragging from (default: the current cursor position)",
+                    false,
+                ),
+                ParamDef::any(
+                    "to",
+                    "Window position [x, y] in logical pixels to drag to (required for drag)",
+                    false,
+                ),
+                ParamDef::string("button", "Mouse button to click or drag with (default: left)", false)
+                    .one_of(&["left", "right", "middle"]),
+                ParamDef::any(
+                    "delta",
+                    "Scroll amount [x, y] in lines; positive y scrolls up (required for scroll)",
+                    false,
+                ),
+                ParamDef::number(
+                    "duration_ms",
+                    "Duration in milliseconds to hold the button or spread the move, drag or scroll over (default: 100ms, max: 60000ms/1 minute, longer durations are clamped)",
+                    false,
+                )
+                .with_clamp(0, 60_000),
+                ParamDef::port(),
+                ParamDef::host(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef {
+                formatter_type:  FormatterType::Simple,
+                template:        "Successfully sent mouse input",
+                response_fields: vec![
+                    ResponseField {
+                        name:      "action",
+                        extractor: ExtractorType::PassThroughData,
+                    },
+                    ResponseField {
+                        name:      "position",
+                        extractor: ExtractorType::PassThroughData,
+                    },
+                    ResponseField {
+                        name:      "duration_ms",
+                        extractor: ExtractorType::PassThroughData,
+                    },
+                ],
+            },
+        },
+        // brp_extras/send_gamepad
+        BrpToolDef {
+            name:            TOOL_BRP_EXTRAS_SEND_GAMEPAD,
+            description:     DESC_BRP_EXTRAS_SEND_GAMEPAD,
+            handler:         HandlerType::Brp {
+                method: BRP_METHOD_EXTRAS_SEND_GAMEPAD,
+            },
+            params:          vec![
+                ParamDef::string_array(
+                    "buttons",
+                    "Array of gamepad button names to press, e.g. South, East, LeftTrigger, DPadUp",
+                    false,
+                ),
+                ParamDef::any(
+                    "axes",
+                    "Object of axis values from -1.0 to 1.0, e.g. {\"LeftStickX\": 1.0}; axes return to 0 on release",
+                    false,
+                ),
+                ParamDef::number(
+                    "gamepad",
+                    "Index of the gamepad to send input as (default: 0, the first gamepad)",
+                    false,
+                )
+                .with_range(0, 15),
+                ParamDef::number(
+                    "duration_ms",
+                    "Duration in milliseconds to hold the buttons and axes before releasing (default: 100ms, max: 60000ms/1 minute, longer durations are clamped)",
+                    false,
+                )
+                .with_clamp(0, 60_000),
+                ParamDef::port(),
+                ParamDef::host(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef {
+                formatter_type:  FormatterType::Simple,
+                template:        "Successfully sent gamepad input",
+                response_fields: vec![
+                    ResponseField {
+                        name:      "buttons_sent",
+                        extractor: ExtractorType::PassThroughData,
+                    },
+                    ResponseField {
+                        name:      "axes_sent",
+                        extractor: ExtractorType::PassThroughData,
+                    },
+                    ResponseField {
+                        name:      "duration_ms",
+                        extractor: ExtractorType::PassThroughData,
+                    },
+                ],
+            },
+        },
         BrpToolDef {
             name:            TOOL_BRP_EXTRAS_SET_DEBUG_MODE,
             description:     DESC_BRP_EXTRAS_SET_DEBUG_MODE,
@@ -774,6 +1240,7 @@ pub fn get_standard_tools() -> Vec<BrpToolDef> {
                     true,
                 ),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -805,7 +1272,40 @@ pub fn get_special_tools() -> Vec<BrpToolDef> {
             handler:         HandlerType::Brp {
                 method: crate::tools::BRP_METHOD_QUERY,
             },
-            params:          ParamDef::query_params().to_vec(),
+            params:          [
+                ParamDef::query_params().as_slice(),
+                &[
+                    ParamDef::ports(),
+                    ParamDef::delta_token(),
+                    ParamDef::canonical_order(),
+                    ParamDef::projection(),
+                    ParamDef::boolean(
+                        PARAM_MCP_SPAWNED_ONLY,
+                        "Only return entities spawned through this server (requires BRP_MCP_TAG_SPAWNED when spawning)",
+                        false,
+                    ),
+                    ParamDef::number(
+                        PARAM_LIMIT,
+                        "Maximum number of entities to return; rows are ordered by entity ID",
+                        false,
+                    )
+                    .with_range(1, 10_000),
+                    ParamDef::number(PARAM_OFFSET, "Number of entities to skip (default 0)", false)
+                        .with_range(0, u64::MAX),
+                    ParamDef::number(
+                        PARAM_MAX_RESPONSE_BYTES,
+                        "Drop trailing entities once the serialized rows exceed this many bytes (at least one entity is always returned)",
+                        false,
+                    )
+                    .with_range(256, 100_000_000),
+                    ParamDef::string(
+                        PARAM_CURSOR,
+                        "next_cursor from the previous page of the same query (instead of offset)",
+                        false,
+                    ),
+                ],
+            ]
+            .concat(),
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
                 formatter_type:  FormatterType::Simple,
@@ -842,7 +1342,9 @@ pub fn get_special_tools() -> Vec<BrpToolDef> {
                     "Object containing component data to spawn with. Keys are component types, values are component data. Note: Math types use array format - Vec2: [x,y], Vec3: [x,y,z], Vec4/Quat: [x,y,z,w], not objects with named fields.",
                     false,
                 ),
+                ParamDef::verify(),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -869,6 +1371,8 @@ pub fn get_special_tools() -> Vec<BrpToolDef> {
                 ParamDef::method(),
                 ParamDef::optional_params(),
                 ParamDef::port(),
+                ParamDef::host(),
+                ParamDef::confirm(),
             ],
             param_extractor: ParamExtractorType::BrpExecute,
             formatter:       FormatterDef {
@@ -909,6 +1413,8 @@ pub fn get_special_tools() -> Vec<BrpToolDef> {
                     false,
                 ),
                 ParamDef::port(),
+                ParamDef::host(),
+                ParamDef::canonical_order(),
             ],
             param_extractor: ParamExtractorType::RegistrySchema,
             formatter:       FormatterDef {
@@ -933,8 +1439,10 @@ pub fn get_special_tools() -> Vec<BrpToolDef> {
                     PARAM_PARENT,
                     "The new parent entity ID (omit to remove parent)",
                     false,
-                ),
+                )
+                .with_range(0, u64::MAX),
                 ParamDef::port(),
+                ParamDef::host(),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef {
@@ -956,6 +1464,7 @@ pub fn get_special_tools() -> Vec<BrpToolDef> {
 }
 
 /// Get log tool definitions
+#[allow(clippy::too_many_lines)]
 pub fn get_log_tools() -> Vec<BrpToolDef> {
     vec![
         // list_logs
@@ -991,11 +1500,71 @@ pub fn get_log_tools() -> Vec<BrpToolDef> {
                     "Optional keyword to filter lines (case-insensitive)",
                     false,
                 ),
+                ParamDef::string(
+                    "regex",
+                    "Optional regular expression lines must match",
+                    false,
+                ),
+                ParamDef::string(
+                    "level",
+                    "Optional least severe log level to keep; lines of a multi-line record share its \
+                     level",
+                    false,
+                )
+                .one_of(&["error", "warn", "info", "debug", "trace"]),
+                ParamDef::string(
+                    "since",
+                    "Optional RFC 3339 timestamp; keeps records logged at or after it",
+                    false,
+                ),
+                ParamDef::string(
+                    "until",
+                    "Optional RFC 3339 timestamp; keeps records logged at or before it",
+                    false,
+                ),
                 ParamDef::number(
                     "tail_lines",
                     "Optional number of lines to read from the end of file",
                     false,
+                )
+                .with_range(0, u64::MAX),
+                ParamDef::number(
+                    "offset",
+                    "Byte offset to start reading at, such as a previous next_offset (default 0)",
+                    false,
+                )
+                .with_range(0, u64::MAX),
+                ParamDef::number(
+                    "max_lines",
+                    "Optional maximum number of matching lines to return (default all)",
+                    false,
+                )
+                .with_range(0, u64::MAX),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // read_response
+        BrpToolDef {
+            name:            crate::tools::TOOL_READ_RESPONSE,
+            description:     crate::tools::DESC_READ_RESPONSE,
+            handler:         HandlerType::Local {
+                handler: "read_response",
+            },
+            params:          vec![
+                ParamDef::string(
+                    "filename",
+                    "The spilled response filename (e.g., brp_response_bevy_query_1234567890.json)",
+                    true,
                 ),
+                ParamDef::number("offset", "Line to start reading from (default 0)", false)
+                    .with_range(0, u64::MAX),
+                ParamDef::number(
+                    "limit",
+                    "Maximum number of lines to read (default 200)",
+                    false,
+                )
+                .with_range(1, 5000),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef::default(),
@@ -1017,7 +1586,41 @@ pub fn get_log_tools() -> Vec<BrpToolDef> {
                     "older_than_seconds",
                     "Optional filter to delete logs older than N seconds",
                     false,
+                )
+                .with_range(0, MAX_U32_PARAM),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // get_app_errors
+        BrpToolDef {
+            name:            crate::tools::TOOL_GET_APP_ERRORS,
+            description:     crate::tools::DESC_GET_APP_ERRORS,
+            handler:         HandlerType::Local {
+                handler: "get_app_errors",
+            },
+            params:          vec![
+                ParamDef::string(
+                    "filename",
+                    "The log filename (e.g., bevy_brp_mcp_myapp_1234567890.log)",
+                    false,
+                ),
+                ParamDef::string(
+                    "app_name",
+                    "App whose log to analyze when no filename is given: its current log if it's managed, else its newest log",
+                    false,
+                ),
+                ParamDef::boolean(
+                    "include_warnings",
+                    "Also return WARN lines (default: false)",
+                    false,
                 ),
+                ParamDef::number(
+                    "limit",
+                    "Most distinct errors to return (default: 50)",
+                    false,
+                )
+                .with_range(1, MAX_U32_PARAM),
             ],
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef::default(),
@@ -1026,6 +1629,7 @@ pub fn get_log_tools() -> Vec<BrpToolDef> {
 }
 
 /// Get app tool definitions
+#[allow(clippy::too_many_lines)]
 pub fn get_app_tools() -> Vec<BrpToolDef> {
     vec![
         // list_bevy_apps
@@ -1039,6 +1643,53 @@ pub fn get_app_tools() -> Vec<BrpToolDef> {
             param_extractor: ParamExtractorType::EmptyParams,
             formatter:       FormatterDef::default(),
         },
+        // analyze_bevy_project
+        BrpToolDef {
+            name:            crate::tools::TOOL_ANALYZE_BEVY_PROJECT,
+            description:     crate::tools::DESC_ANALYZE_BEVY_PROJECT,
+            handler:         HandlerType::Local {
+                handler: "analyze_bevy_project",
+            },
+            params:          vec![ParamDef::string(
+                PARAM_WORKSPACE,
+                "Only analyze the workspace with this name",
+                false,
+            )],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // enable_brp_in_project
+        BrpToolDef {
+            name:            crate::tools::TOOL_ENABLE_BRP_IN_PROJECT,
+            description:     crate::tools::DESC_ENABLE_BRP_IN_PROJECT,
+            handler:         HandlerType::Local {
+                handler: "enable_brp_in_project",
+            },
+            params:          vec![
+                ParamDef::string(
+                    PARAM_PACKAGE,
+                    "Package to set up (optional if only one Bevy package has binaries)",
+                    false,
+                ),
+                ParamDef::string(
+                    PARAM_WORKSPACE,
+                    "Workspace name to use when the package name is ambiguous",
+                    false,
+                ),
+                ParamDef::boolean(
+                    PARAM_WITH_EXTRAS,
+                    "Add bevy_brp_extras and its BrpExtrasPlugin instead of the plain remote plugins (default: false)",
+                    false,
+                ),
+                ParamDef::boolean(
+                    PARAM_DRY_RUN,
+                    "Only return the diff of the edits without writing them (default: false)",
+                    false,
+                ),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
         // list_brp_apps
         BrpToolDef {
             name:            crate::tools::TOOL_LIST_BRP_APPS,
@@ -1086,6 +1737,151 @@ pub fn get_app_tools() -> Vec<BrpToolDef> {
             param_extractor: ParamExtractorType::Passthrough,
             formatter:       FormatterDef::default(),
         },
+        // build_bevy_app
+        BrpToolDef {
+            name:            crate::tools::TOOL_BUILD_BEVY_APP,
+            description:     crate::tools::DESC_BUILD_BEVY_APP,
+            handler:         HandlerType::Local {
+                handler: "build_bevy_app",
+            },
+            params:          create_build_params("app_name", "Name of the Bevy app to build"),
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // build_bevy_example
+        BrpToolDef {
+            name:            crate::tools::TOOL_BUILD_BEVY_EXAMPLE,
+            description:     crate::tools::DESC_BUILD_BEVY_EXAMPLE,
+            handler:         HandlerType::Local {
+                handler: "build_bevy_example",
+            },
+            params:          create_build_params(
+                "example_name",
+                "Name of the Bevy example to build",
+            ),
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // attach_app
+        BrpToolDef {
+            name:            crate::tools::TOOL_ATTACH_APP,
+            description:     crate::tools::DESC_ATTACH_APP,
+            handler:         HandlerType::Local {
+                handler: "attach_app",
+            },
+            params:          vec![
+                ParamDef::string(
+                    PARAM_APP_NAME,
+                    "Name to manage the app under (also used to find the process if pid is omitted)",
+                    true,
+                ),
+                ParamDef::number(
+                    PARAM_PID,
+                    "PID of the running process (default: find by app_name)",
+                    false,
+                )
+                .with_range(1, MAX_U32_PARAM),
+                ParamDef::number(
+                    JSON_FIELD_PORT,
+                    "BRP port the app listens on (default: 15702)",
+                    false,
+                )
+                .with_range(1, MAX_PORT),
+                ParamDef::string(
+                    PARAM_LOG_FILE,
+                    "Path to an existing log file of the app to make available to list_logs/read_log",
+                    false,
+                ),
+                ParamDef::string(PARAM_GROUP, "Optional group to add the app to", false),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // list_launched_processes
+        BrpToolDef {
+            name:            crate::tools::TOOL_LIST_LAUNCHED_PROCESSES,
+            description:     crate::tools::DESC_LIST_LAUNCHED_PROCESSES,
+            handler:         HandlerType::Local {
+                handler: "list_launched_processes",
+            },
+            params:          vec![ParamDef::string(
+                PARAM_GROUP,
+                "Only list the apps of this group",
+                false,
+            )],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // kill_process
+        BrpToolDef {
+            name:            crate::tools::TOOL_KILL_PROCESS,
+            description:     crate::tools::DESC_KILL_PROCESS,
+            handler:         HandlerType::Local {
+                handler: "kill_process",
+            },
+            params:          vec![
+                ParamDef::string(
+                    PARAM_APP_NAME,
+                    "Name of the managed app to kill (required unless group is given)",
+                    false,
+                ),
+                ParamDef::string(
+                    PARAM_GROUP,
+                    "Kill every managed app of this group instead of one app",
+                    false,
+                ),
+                ParamDef::boolean(
+                    PARAM_FORCE,
+                    "Send SIGKILL instead of SIGTERM (default: false)",
+                    false,
+                ),
+                ParamDef::confirm(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // restart_app
+        BrpToolDef {
+            name:            crate::tools::TOOL_RESTART_APP,
+            description:     crate::tools::DESC_RESTART_APP,
+            handler:         HandlerType::Local {
+                handler: "restart_app",
+            },
+            params:          vec![
+                ParamDef::string(
+                    PARAM_APP_NAME,
+                    "Name of the launched app or example to restart",
+                    true,
+                ),
+                ParamDef::confirm(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // wait_for_brp
+        BrpToolDef {
+            name:            crate::tools::TOOL_WAIT_FOR_BRP,
+            description:     crate::tools::DESC_WAIT_FOR_BRP,
+            handler:         HandlerType::Local {
+                handler: "wait_for_brp",
+            },
+            params:          vec![
+                ParamDef::string(
+                    PARAM_APP_NAME,
+                    "Managed app to wait for; its port is used and waiting stops if it exits",
+                    false,
+                ),
+                ParamDef::number(
+                    JSON_FIELD_PORT,
+                    "BRP port to wait for (default: the managed app's port, otherwise 15702)",
+                    false,
+                )
+                .with_range(1, MAX_PORT),
+                wait_timeout_param(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
         // brp_extras_shutdown
         BrpToolDef {
             name:            crate::tools::TOOL_BRP_EXTRAS_SHUTDOWN,
@@ -1099,6 +1895,165 @@ pub fn get_app_tools() -> Vec<BrpToolDef> {
                     JSON_FIELD_PORT,
                     "BRP port to connect to (default: 15702)",
                     false,
+                )
+                .with_range(1, MAX_PORT),
+                ParamDef::host(),
+                ParamDef::confirm(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+    ]
+}
+
+/// Get watch tool definitions
+///
+/// Start tools get the port and host parameters from `get_all_tools()`.
+#[allow(clippy::too_many_lines)]
+pub fn get_watch_tools() -> Vec<BrpToolDef> {
+    let interval_min = *INTERVAL_RANGE_MS.start();
+    let interval_max = *INTERVAL_RANGE_MS.end();
+
+    vec![
+        // bevy_get_watch
+        BrpToolDef {
+            name:            TOOL_BEVY_GET_WATCH,
+            description:     DESC_BEVY_GET_WATCH,
+            handler:         HandlerType::Watch {
+                operation: WatchOperation::Start(WatchKind::Entity),
+            },
+            params:          vec![
+                ParamDef::entity("The entity ID to watch for component changes", true),
+                ParamDef::components(
+                    "Required array of component types to watch. Must contain at least one \
+                     component. Without this, the watch will not detect any changes.",
+                    true,
+                ),
+                throttle_param(),
+                debounce_param(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // bevy_list_watch
+        BrpToolDef {
+            name:            TOOL_BEVY_LIST_WATCH,
+            description:     DESC_BEVY_LIST_WATCH,
+            handler:         HandlerType::Watch {
+                operation: WatchOperation::Start(WatchKind::List),
+            },
+            params:          vec![
+                ParamDef::entity("The entity ID to watch for component list changes", true),
+                throttle_param(),
+                debounce_param(),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // brp_watch_resource
+        BrpToolDef {
+            name:            TOOL_BRP_WATCH_RESOURCE,
+            description:     DESC_BRP_WATCH_RESOURCE,
+            handler:         HandlerType::Watch {
+                operation: WatchOperation::Start(WatchKind::Resource),
+            },
+            params:          vec![
+                ParamDef::resource("The fully-qualified type name of the resource to watch"),
+                ParamDef::number(
+                    PARAM_INTERVAL_MS,
+                    "Polling interval in milliseconds (default: 1000)",
+                    false,
+                )
+                .with_range(interval_min, interval_max),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // brp_watch_resources
+        BrpToolDef {
+            name:            TOOL_BRP_WATCH_RESOURCES,
+            description:     DESC_BRP_WATCH_RESOURCES,
+            handler:         HandlerType::Watch {
+                operation: WatchOperation::Start(WatchKind::Resources),
+            },
+            params:          vec![
+                ParamDef::string_array(
+                    PARAM_PATTERNS,
+                    "Crate names (e.g. game_config) or type paths where * matches any characters \
+                     (e.g. game::settings::*)",
+                    true,
+                ),
+                ParamDef::string(
+                    PARAM_LABEL,
+                    "Label shared by the started watches (default: the patterns joined with ',')",
+                    false,
+                ),
+                ParamDef::number(
+                    PARAM_INTERVAL_MS,
+                    "Polling interval in milliseconds (default: 1000)",
+                    false,
+                )
+                .with_range(interval_min, interval_max),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // brp_start_perf_watch
+        BrpToolDef {
+            name:            TOOL_BRP_START_PERF_WATCH,
+            description:     DESC_BRP_START_PERF_WATCH,
+            handler:         HandlerType::Watch {
+                operation: WatchOperation::Start(WatchKind::Perf),
+            },
+            params:          vec![
+                ParamDef::string_array(
+                    PARAM_DIAGNOSTICS,
+                    "Diagnostics to log on every sample, by path (default: [\"fps\", \
+                     \"frame_time\"])",
+                    false,
+                ),
+                ParamDef::any(
+                    PARAM_THRESHOLDS,
+                    "Alert thresholds, e.g. [{\"diagnostic\": \"fps\", \"below\": 30, \
+                     \"for_ms\": 5000}]",
+                    false,
+                ),
+                ParamDef::number(
+                    PARAM_INTERVAL_MS,
+                    "Sampling interval in milliseconds (default: 1000)",
+                    false,
+                )
+                .with_range(interval_min, interval_max),
+                ParamDef::string(
+                    JSON_FIELD_RESOURCE,
+                    "Resource holding the diagnostics (default: \
+                     bevy_diagnostic::diagnostic::DiagnosticsStore)",
+                    false,
+                ),
+                ParamDef::string(PARAM_LABEL, "Optional label for the watch", false),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
+        // brp_stop_watch
+        BrpToolDef {
+            name:            TOOL_BRP_STOP_WATCH,
+            description:     DESC_BRP_STOP_WATCH,
+            handler:         HandlerType::Watch {
+                operation: WatchOperation::Stop,
+            },
+            params:          vec![
+                ParamDef::number(
+                    JSON_FIELD_WATCH_ID,
+                    "The watch ID returned from bevy_get_watch, bevy_list_watch or \
+                     brp_watch_resource",
+                    false,
+                )
+                .with_range(0, MAX_U32_PARAM),
+                ParamDef::string(
+                    PARAM_LABEL,
+                    "Stop every watch with this label, as started by brp_watch_resources",
+                    false,
                 ),
             ],
             param_extractor: ParamExtractorType::Passthrough,
@@ -1111,17 +2066,119 @@ pub fn get_app_tools() -> Vec<BrpToolDef> {
 fn create_launch_params(name_param: &'static str, name_desc: &'static str) -> Vec<ParamDef> {
     vec![
         ParamDef::string(name_param, name_desc, true),
-        ParamDef::string("profile", "Build profile to use (debug or release)", false),
+        ParamDef::string(
+            PARAM_PROFILE,
+            "Build profile to use (debug or release)",
+            false,
+        )
+        .one_of(&[PROFILE_DEBUG, PROFILE_RELEASE]),
         ParamDef::string(
             PARAM_WORKSPACE,
             "Workspace name to use when multiple apps/examples with the same name exist",
             false,
         ),
-        ParamDef::number(JSON_FIELD_PORT, "BRP port to use (default: 15702)", false),
+        ParamDef::number(JSON_FIELD_PORT, "BRP port to use (default: 15702)", false)
+            .with_range(1, MAX_PORT),
+        ParamDef::string_array(PARAM_FEATURES, "Cargo features to enable", false),
+        ParamDef::boolean(
+            PARAM_NO_DEFAULT_FEATURES,
+            "Disable the package's default features",
+            false,
+        ),
+        ParamDef::any(
+            PARAM_ENV,
+            "Environment variables for the launched process, as an object of names to values",
+            false,
+        ),
+        ParamDef::string(
+            PARAM_RUST_LOG,
+            "RUST_LOG filter for the launched process, e.g. \"warn,my_game=debug\"",
+            false,
+        ),
+        ParamDef::string(
+            PARAM_LOG_FORMAT,
+            "Log format to ask the app for (text or json), passed as BRP_LOG_FORMAT",
+            false,
+        )
+        .one_of(&[LOG_FORMAT_TEXT, LOG_FORMAT_JSON]),
+        ParamDef::string(
+            PARAM_WRAPPER,
+            "Run the app under a debugger or profiler: lldb, gdb, perf, flamegraph, or the name of \
+             a custom wrapper defined in the server's BRP_MCP_LAUNCH_WRAPPERS",
+            false,
+        ),
+        ParamDef::boolean(
+            PARAM_WAIT_FOR_BRP,
+            "Wait until the BRP server answers rpc.discover before returning (default: false)",
+            false,
+        ),
+        wait_timeout_param(),
+    ]
+}
+
+/// Parameters of the build tools: the launch parameters that decide what gets built
+fn create_build_params(name_param: &'static str, name_desc: &'static str) -> Vec<ParamDef> {
+    create_launch_params(name_param, name_desc)
+        .into_iter()
+        .filter(|param| {
+            ![
+                JSON_FIELD_PORT,
+                PARAM_WAIT_FOR_BRP,
+                PARAM_WAIT_TIMEOUT_MS,
+                PARAM_RUST_LOG,
+                PARAM_LOG_FORMAT,
+                PARAM_WRAPPER,
+            ]
+            .contains(&param.name)
+        })
+        .collect()
+}
+
+/// Throttle parameter of the streamed watch tools
+const fn throttle_param() -> ParamDef {
+    ParamDef::number(PARAM_THROTTLE_MS, DESC_THROTTLE_MS, false).with_range(0, MAX_COALESCE_MS)
+}
+
+/// Debounce parameter of the streamed watch tools
+const fn debounce_param() -> ParamDef {
+    ParamDef::number(PARAM_DEBOUNCE_MS, DESC_DEBOUNCE_MS, false).with_range(0, MAX_COALESCE_MS)
+}
+
+/// Timeout parameter of the tools that wait for BRP
+const fn wait_timeout_param() -> ParamDef {
+    ParamDef::number(
+        PARAM_WAIT_TIMEOUT_MS,
+        "How long to wait for BRP, in milliseconds (default: 60000)",
+        false,
+    )
+    .with_range(0, MAX_WAIT_TIMEOUT_MS)
+}
+
+/// Get definitions of local tools describing the server itself
+pub fn get_server_tools() -> Vec<BrpToolDef> {
+    vec![
+        // export_tool_manifest
+        BrpToolDef {
+            name:            TOOL_BRP_EXPORT_TOOL_MANIFEST,
+            description:     DESC_BRP_EXPORT_TOOL_MANIFEST,
+            handler:         HandlerType::Local {
+                handler: "export_tool_manifest",
+            },
+            params:          vec![
+                ParamDef::string(
+                    tool_manifest::PARAM_FORMAT,
+                    "Manifest format: 'json' (default) or 'openapi'",
+                    false,
+                )
+                .one_of(&tool_manifest::MANIFEST_FORMATS),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        },
     ]
 }
 
-/// Get all tool definitions - combines standard, special, log, and app tools
+/// Get all tool definitions - combines standard, special, log, app, watch, and server tools
 pub fn get_all_tools() -> Vec<BrpToolDef> {
     let mut tools = Vec::new();
 
@@ -1137,5 +2194,30 @@ pub fn get_all_tools() -> Vec<BrpToolDef> {
     // Add app tools
     tools.extend(get_app_tools());
 
+    // Add watch tools
+    tools.extend(get_watch_tools());
+
+    // Add server tools
+    tools.extend(get_server_tools());
+
+    // Every BRP tool takes the timeout and retry parameters
+    for tool in &mut tools {
+        if matches!(tool.handler, HandlerType::Brp { .. }) {
+            tool.params.extend(ParamDef::request_policy());
+        }
+    }
+
+    // Watch tasks run against one app, so every start tool takes its port and host
+    for tool in &mut tools {
+        if matches!(
+            tool.handler,
+            HandlerType::Watch {
+                operation: WatchOperation::Start(_),
+            }
+        ) {
+            tool.params.extend([ParamDef::port(), ParamDef::host()]);
+        }
+    }
+
     tools
 }
@@ -30,10 +30,15 @@
 //! ```
 //!
 //! The handler generator:
-//! 1. Selects appropriate parameter extractor based on tool definition
-//! 2. Builds response formatter from formatter definition
-//! 3. Configures BRP request handling pipeline
-//! 4. Executes the BRP request and formats the response
+//! 1. Validates the arguments against the parameter definitions (required, type, constraint),
+//!    reporting every invalid field at once
+//! 2. Selects appropriate parameter extractor based on tool definition
+//! 3. Builds response formatter from formatter definition
+//! 4. Configures BRP request handling pipeline
+//! 5. Executes the BRP request and formats the response
+//!
+//! Local handlers are routed by name, and watch handlers by their `WatchOperation`: start
+//! operations spawn a task registered with the watch manager, stop operations end one.
 //!
 //! # Parameter Extractors
 //!
@@ -75,25 +80,34 @@
 //!
 //! All errors are converted to appropriate MCP error responses with helpful messages.
 
+use futures::future::BoxFuture;
 use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
 use rmcp::service::RequestContext;
 use rmcp::{Error as McpError, RoleServer};
 
 use crate::BrpMcpService;
 use crate::brp_tools::constants::{
-    JSON_FIELD_COMPONENTS, JSON_FIELD_ENTITIES, JSON_FIELD_ENTITY, JSON_FIELD_PARENT,
-    JSON_FIELD_PATH, JSON_FIELD_PORT,
+    JSON_FIELD_COMPONENTS, JSON_FIELD_DELTA_TOKEN, JSON_FIELD_ENTITIES, JSON_FIELD_ENTITY,
+    JSON_FIELD_PARENT, JSON_FIELD_PATH, JSON_FIELD_PORT, PARAM_CANONICAL_ORDER, PARAM_INLINE,
+    PARAM_LIMIT, PARAM_MCP_SPAWNED_ONLY, PARAM_PORTS, PARAM_PROJECTION, PARAM_TIMEOUT_MS,
+    PARAM_VERIFY,
 };
 use crate::brp_tools::request_handler::{
     BrpExecuteExtractor, BrpHandlerConfig, EntityParamExtractor, FormatterContext, ParamExtractor,
     PassthroughExtractor, RegistrySchemaParamExtractor, ResourceParamExtractor,
     SimplePortExtractor, handle_brp_request,
 };
+use crate::brp_tools::support::request_policy::RequestPolicy;
 use crate::brp_tools::support::{ResponseFormatterFactory, extractors};
+use crate::brp_tools::watch;
+use crate::error::{Error, report_to_mcp_error};
+use crate::support::progress::{self, Progress};
 use crate::support::schema;
 use crate::tool_definitions::{
-    BrpToolDef, ExtractorType, FormatterType, HandlerType, ParamExtractorType, ParamType,
+    BrpToolDef, ExtractorType, FormatterType, HandlerType, ParamConstraint, ParamExtractorType,
+    ParamType, WatchKind, WatchOperation,
 };
+use crate::tools::DESC_REQUEST_POLICY;
 
 /// Generate tool registration from a declarative definition
 pub fn generate_tool_registration(def: &BrpToolDef) -> Tool {
@@ -114,15 +128,37 @@ pub fn generate_tool_registration(def: &BrpToolDef) -> Tool {
             ParamType::StringArray => {
                 builder.add_string_array_property(param.name, param.description, param.required)
             }
+            ParamType::NumberArray => {
+                builder.add_number_array_property(param.name, param.description, param.required)
+            }
             ParamType::Any => {
                 builder.add_any_property(param.name, param.description, param.required)
             }
         };
+
+        builder = match param.constraint {
+            Some(ParamConstraint::Range { min, max } | ParamConstraint::Clamp { min, max }) => {
+                builder.with_range(param.name, min, (max != u64::MAX).then_some(max))
+            }
+            Some(ParamConstraint::OneOf(values)) => builder.with_enum(param.name, values),
+            None => builder,
+        };
     }
 
+    // Tools taking the timeout and retry parameters share one description of them
+    let description = if def
+        .params
+        .iter()
+        .any(|param| param.name == PARAM_TIMEOUT_MS)
+    {
+        format!("{}\n\n{DESC_REQUEST_POLICY}", def.description.trim_end()).into()
+    } else {
+        def.description.into()
+    };
+
     Tool {
-        name:         def.name.into(),
-        description:  def.description.into(),
+        name: def.name.into(),
+        description,
         input_schema: builder.build(),
     }
 }
@@ -134,16 +170,71 @@ pub async fn generate_tool_handler(
     request: CallToolRequestParam,
     context: RequestContext<RoleServer>,
 ) -> Result<CallToolResult, McpError> {
-    match &def.handler {
-        HandlerType::Brp { method } => {
-            // Handle BRP method calls
-            generate_brp_handler(def, service, request, context, method).await
+    let request = validate_params(def, request).map_err(|report| report_to_mcp_error(&report))?;
+
+    // Handlers report progress of long operations through the scope
+    let progress = Progress::new(&context);
+    let handle: BoxFuture<'_, Result<CallToolResult, McpError>> = Box::pin(async {
+        match &def.handler {
+            HandlerType::Brp { method } => {
+                // Handle BRP method calls
+                generate_brp_handler(def, service, request, context, method).await
+            }
+            HandlerType::Local { handler } => {
+                // Handle local method calls
+                generate_local_handler(def, service, request, context, handler).await
+            }
+            HandlerType::Watch { operation } => {
+                // Start or stop a watch through the watch manager
+                generate_watch_handler(service, request, context, *operation).await
+            }
         }
-        HandlerType::Local { handler } => {
-            // Handle local method calls
-            generate_local_handler(def, service, request, context, handler).await
+    });
+    progress::scope(progress, handle).await
+}
+
+/// Check the supplied arguments against the parameter definitions of a tool
+///
+/// Required parameters must be present, and supplied values must have the declared type and
+/// satisfy the declared constraint. Every failing parameter is reported, not just the first.
+fn validate_params(
+    def: &BrpToolDef,
+    mut request: CallToolRequestParam,
+) -> crate::error::Result<CallToolRequestParam> {
+    let mut no_arguments = serde_json::Map::new();
+    let arguments = request.arguments.as_mut().unwrap_or(&mut no_arguments);
+
+    let mut issues = Vec::new();
+    for param in &def.params {
+        // Absent and null values fall back to the handler defaults
+        let Some(value) = arguments
+            .get_mut(param.name)
+            .filter(|value| !value.is_null())
+        else {
+            if param.required {
+                issues.push(format!("{} is required", param.name));
+            }
+            continue;
+        };
+
+        let checked = param.param_type.check(param.name, value).and_then(|()| {
+            param
+                .constraint
+                .map_or(Ok(()), |constraint| constraint.apply(param.name, value))
+        });
+        if let Err(issue) = checked {
+            issues.push(issue);
         }
     }
+
+    if issues.is_empty() {
+        Ok(request)
+    } else {
+        Err(error_stack::Report::new(Error::invalid(
+            &format!("arguments for {}", def.name),
+            issues.join("; "),
+        )))
+    }
 }
 
 /// Generate a BRP handler
@@ -191,6 +282,27 @@ async fn generate_brp_handler(
         method: Some(method),
         param_extractor,
         formatter_factory: formatter_builder.build(),
+        delta_tracking: def
+            .params
+            .iter()
+            .any(|param| param.name == JSON_FIELD_DELTA_TOKEN),
+        spawned_filter: def
+            .params
+            .iter()
+            .any(|param| param.name == PARAM_MCP_SPAWNED_ONLY),
+        broadcast: def.params.iter().any(|param| param.name == PARAM_PORTS),
+        pagination: def.params.iter().any(|param| param.name == PARAM_LIMIT),
+        canonical_order: def
+            .params
+            .iter()
+            .any(|param| param.name == PARAM_CANONICAL_ORDER),
+        projection: def
+            .params
+            .iter()
+            .any(|param| param.name == PARAM_PROJECTION),
+        verify: def.params.iter().any(|param| param.name == PARAM_VERIFY),
+        inline_image: def.params.iter().any(|param| param.name == PARAM_INLINE),
+        request_policy: RequestPolicy::for_method(method),
     };
 
     handle_brp_request(service, request, context, &config).await
@@ -208,8 +320,16 @@ async fn generate_local_handler(
     match handler {
         "list_logs" => crate::log_tools::list_logs::handle(service, &request, context),
         "read_log" => crate::log_tools::read_log::handle(service, &request, context),
+        "read_response" => crate::log_tools::read_response::handle(service, &request, context),
         "cleanup_logs" => crate::log_tools::cleanup_logs::handle(service, &request, context),
+        "get_app_errors" => crate::log_tools::get_app_errors::handle(service, &request, context),
         "list_bevy_apps" => crate::app_tools::brp_list_bevy_apps::handle(service, context).await,
+        "analyze_bevy_project" => {
+            crate::app_tools::brp_analyze_bevy_project::handle(service, request, context).await
+        }
+        "enable_brp_in_project" => {
+            crate::app_tools::brp_enable_brp_in_project::handle(service, request, context).await
+        }
         "list_brp_apps" => crate::app_tools::brp_list_brp_apps::handle(service, context).await,
         "list_bevy_examples" => {
             crate::app_tools::brp_list_bevy_examples::handle(service, context).await
@@ -220,9 +340,27 @@ async fn generate_local_handler(
         "launch_bevy_example" => {
             crate::app_tools::brp_launch_bevy_example::handle(service, request, context).await
         }
+        "build_bevy_app" => {
+            crate::app_tools::brp_build_bevy_app::handle(service, request, context).await
+        }
+        "build_bevy_example" => {
+            crate::app_tools::brp_build_bevy_example::handle(service, request, context).await
+        }
+        "attach_app" => crate::app_tools::brp_attach_app::handle(service, request, context).await,
+        "list_launched_processes" => Ok(crate::app_tools::brp_list_launched_processes::handle(
+            service, &request, context,
+        )),
+        "kill_process" => {
+            crate::app_tools::brp_kill_process::handle(service, request, context).await
+        }
+        "restart_app" => crate::app_tools::brp_restart_app::handle(service, request, context).await,
+        "wait_for_brp" => {
+            crate::app_tools::brp_wait_for_brp::handle(service, request, context).await
+        }
         "shutdown" => {
             crate::app_tools::brp_extras_shutdown::handle(service, request, context).await
         }
+        "export_tool_manifest" => Ok(crate::tool_manifest::handle(service, &request, context)),
         _ => Err(crate::error::report_to_mcp_error(
             &error_stack::Report::new(crate::error::Error::ParameterExtraction(format!(
                 "unknown local handler: {handler}"
@@ -232,6 +370,36 @@ async fn generate_local_handler(
     }
 }
 
+/// Generate a watch handler
+///
+/// Start handlers spawn their task through the watch manager, which assigns the watch ID and log
+/// file returned to the caller; the stop handler looks watches up there by ID or label.
+async fn generate_watch_handler(
+    service: &BrpMcpService,
+    request: CallToolRequestParam,
+    context: RequestContext<RoleServer>,
+    operation: WatchOperation,
+) -> Result<CallToolResult, McpError> {
+    match operation {
+        WatchOperation::Start(WatchKind::Entity) => {
+            watch::bevy_get_watch::handle(service, request, context).await
+        }
+        WatchOperation::Start(WatchKind::List) => {
+            watch::bevy_list_watch::handle(service, request, context).await
+        }
+        WatchOperation::Start(WatchKind::Resource) => {
+            watch::brp_watch_resource::handle(service, request, context).await
+        }
+        WatchOperation::Start(WatchKind::Resources) => {
+            watch::brp_watch_resources::handle(service, request, context).await
+        }
+        WatchOperation::Start(WatchKind::Perf) => {
+            watch::brp_start_perf_watch::handle(service, request, context).await
+        }
+        WatchOperation::Stop => watch::brp_stop_watch::handle(service, request, context).await,
+    }
+}
+
 /// Convert our `ExtractorType` enum to the actual extractor function
 fn convert_extractor_type(
     extractor_type: &ExtractorType,
@@ -323,6 +491,8 @@ fn extract_field_from_context(
 
 #[cfg(test)]
 mod tests {
+    #![allow(clippy::unwrap_used)]
+
     use serde_json::json;
 
     use super::*;
@@ -342,12 +512,14 @@ mod tests {
                     description: "Entity ID",
                     required:    true,
                     param_type:  ParamType::Number,
+                    constraint:  None,
                 },
                 ParamDef {
                     name:        "optional_param",
                     description: "Optional parameter",
                     required:    false,
                     param_type:  ParamType::String,
+                    constraint:  None,
                 },
             ],
             param_extractor: ParamExtractorType::Passthrough,
@@ -366,6 +538,158 @@ mod tests {
         assert_eq!(tool.input_schema.get("type"), Some(&"object".into()));
     }
 
+    #[test]
+    fn test_request_policy_help_is_appended_to_brp_tools() {
+        let mut def = constrained_tool();
+        let tool = generate_tool_registration(&def);
+        assert!(!tool.description.contains(DESC_REQUEST_POLICY));
+
+        def.params.extend(ParamDef::request_policy());
+        let tool = generate_tool_registration(&def);
+        assert!(tool.description.starts_with(def.description));
+        assert!(tool.description.ends_with(DESC_REQUEST_POLICY));
+    }
+
+    fn constrained_tool() -> BrpToolDef {
+        BrpToolDef {
+            name:            "test_tool",
+            description:     "A test tool",
+            handler:         HandlerType::Brp {
+                method: "test/method",
+            },
+            params:          vec![
+                ParamDef::port(),
+                ParamDef::number("duration_ms", "Duration", false).with_clamp(0, 1000),
+                ParamDef::string("profile", "Profile", false).one_of(&["debug", "release"]),
+            ],
+            param_extractor: ParamExtractorType::Passthrough,
+            formatter:       FormatterDef::default(),
+        }
+    }
+
+    fn request(arguments: &serde_json::Value) -> CallToolRequestParam {
+        CallToolRequestParam {
+            name:      "test_tool".into(),
+            arguments: arguments.as_object().cloned(),
+        }
+    }
+
+    #[test]
+    fn test_generate_tool_registration_reflects_constraints() {
+        let tool = generate_tool_registration(&constrained_tool());
+        let properties = &tool.input_schema["properties"];
+
+        assert_eq!(properties["port"]["minimum"], 1);
+        assert_eq!(properties["port"]["maximum"], 65_535);
+        assert_eq!(properties["duration_ms"]["maximum"], 1000);
+        assert_eq!(properties["profile"]["enum"], json!(["debug", "release"]));
+    }
+
+    #[test]
+    fn test_validate_params_enforces_constraints() {
+        let def = constrained_tool();
+
+        let validated = validate_params(
+            &def,
+            request(&json!({ "port": 15702, "duration_ms": 5000, "profile": "release" })),
+        )
+        .unwrap();
+        assert_eq!(validated.arguments.unwrap()["duration_ms"], 1000);
+
+        let validated = validate_params(&def, request(&json!({ "duration_ms": -5 }))).unwrap();
+        assert_eq!(validated.arguments.unwrap()["duration_ms"], 0);
+
+        for arguments in [
+            json!({ "port": 0 }),
+            json!({ "port": 70000 }),
+            json!({ "port": "15702" }),
+            json!({ "duration_ms": 1.5 }),
+            json!({ "profile": "fast" }),
+        ] {
+            assert!(
+                validate_params(&def, request(&arguments)).is_err(),
+                "{arguments}"
+            );
+        }
+
+        // Absent and null values are left to the handler defaults
+        assert!(validate_params(&def, request(&json!({ "port": null }))).is_ok());
+    }
+
+    #[test]
+    fn test_validate_params_reports_every_invalid_field() {
+        let def = BrpToolDef {
+            params: vec![
+                ParamDef::entity("Entity", true),
+                ParamDef::string_array("keys", "Keys", true),
+                ParamDef::boolean("enabled", "Enabled", false),
+                ParamDef::any("data", "Data", false),
+                ParamDef::port(),
+            ],
+            ..constrained_tool()
+        };
+
+        let valid = json!({ "entity": 7, "keys": ["KeyA"], "enabled": true, "data": "anything" });
+        assert!(validate_params(&def, request(&valid)).is_ok());
+
+        let invalid = json!({ "keys": ["KeyA", 5], "enabled": "yes", "port": 0 });
+        let message = validate_params(&def, request(&invalid))
+            .unwrap_err()
+            .current_context()
+            .to_string();
+        for issue in [
+            "entity is required",
+            "keys[1] must be a string, got 5",
+            "enabled must be a boolean, got \"yes\"",
+            "port must be an integer between 1 and 65535, got 0",
+        ] {
+            assert!(message.contains(issue), "{message}");
+        }
+
+        let message = validate_params(&def, request(&json!({ "entity": 7, "keys": "KeyA" })))
+            .unwrap_err()
+            .current_context()
+            .to_string();
+        assert!(
+            message.contains("keys must be an array of strings, got \"KeyA\""),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_watch_tools_are_generated_with_connection_params() {
+        let tools = crate::tool_definitions::get_all_tools();
+        let watch_tools: Vec<_> = tools
+            .iter()
+            .filter(|def| matches!(def.handler, HandlerType::Watch { .. }))
+            .collect();
+        assert_eq!(watch_tools.len(), 6);
+
+        for def in watch_tools {
+            let properties = generate_tool_registration(def).input_schema["properties"].clone();
+            let starts = matches!(
+                def.handler,
+                HandlerType::Watch {
+                    operation: WatchOperation::Start(_),
+                }
+            );
+            assert_eq!(
+                properties.get(JSON_FIELD_PORT).is_some(),
+                starts,
+                "{}",
+                def.name
+            );
+            assert_eq!(properties.get("host").is_some(), starts, "{}", def.name);
+        }
+
+        let resource_watch = tools
+            .iter()
+            .find(|def| def.name == crate::tools::TOOL_BRP_WATCH_RESOURCE)
+            .unwrap();
+        let arguments = json!({ "resource": "game::Score", "interval_ms": 50 });
+        assert!(validate_params(resource_watch, request(&arguments)).is_err());
+    }
+
     #[test]
     fn test_convert_extractor_type_pass_through_result() {
         let extractor = convert_extractor_type(&ExtractorType::PassThroughResult);
@@ -0,0 +1,342 @@
+//! Machine-readable manifest of every tool the server registers
+//!
+//! The manifest lists each tool with its description, its parameters (type, whether required,
+//! description and valid values) and the JSON schema sent to MCP clients, so downstream clients
+//! can generate typed bindings. Declarative tools are described from their definitions, including
+//! the BRP method or watch operation behind them; hand-written tools from their input schema.
+//! It is served by `brp_export_tool_manifest` and printed by `bevy_brp_mcp --manifest`.
+
+use std::collections::HashMap;
+
+use rmcp::RoleServer;
+use rmcp::model::{CallToolRequestParam, CallToolResult, Tool};
+use rmcp::service::RequestContext;
+use serde_json::{Map, Value, json};
+
+use crate::support::params;
+use crate::support::response::ResponseBuilder;
+use crate::support::serialization::json_response_to_result;
+use crate::tool_definitions::{
+    self, BrpToolDef, HandlerType, ParamConstraint, ParamDef, ParamType, WatchKind, WatchOperation,
+};
+use crate::{BrpMcpService, registry};
+
+/// Parameter selecting the manifest format
+pub const PARAM_FORMAT: &str = "format";
+
+/// Manifest format listing tools with their parameters and schemas
+pub const FORMAT_JSON: &str = "json";
+
+/// Manifest format describing each tool as an `OpenAPI` operation
+pub const FORMAT_OPENAPI: &str = "openapi";
+
+/// Formats the manifest can be exported in
+pub const MANIFEST_FORMATS: [&str; 2] = [FORMAT_JSON, FORMAT_OPENAPI];
+
+/// `OpenAPI` version of the `openapi` format
+const OPENAPI_VERSION: &str = "3.1.0";
+
+pub fn handle(
+    _service: &BrpMcpService,
+    request: &CallToolRequestParam,
+    _context: RequestContext<RoleServer>,
+) -> CallToolResult {
+    let format = params::extract_optional_string(request, PARAM_FORMAT, FORMAT_JSON);
+    let manifest = build(format);
+    let tool_count = registry::all_tools().len();
+
+    let response = ResponseBuilder::success()
+        .message(format!("Manifest of {tool_count} tools ({format})"))
+        .data(manifest)
+        .map_or_else(
+            |_| {
+                ResponseBuilder::error()
+                    .message("Failed to serialize response data")
+                    .build()
+            },
+            ResponseBuilder::build,
+        );
+
+    json_response_to_result(&response)
+}
+
+/// The manifest in a format, `json` unless `openapi` is asked for
+pub fn build(format: &str) -> Value {
+    if format == FORMAT_OPENAPI {
+        openapi_manifest()
+    } else {
+        json_manifest()
+    }
+}
+
+/// Every tool with its handler, parameters and input schema
+fn json_manifest() -> Value {
+    let definitions = declarative_definitions();
+    let tools: Vec<Value> = registry::all_tools()
+        .iter()
+        .map(|tool| describe_tool(tool, definitions.get(tool.name.as_ref())))
+        .collect();
+
+    json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "tools": tools,
+    })
+}
+
+/// Every tool as a `POST /tools/{name}` operation taking its arguments as the request body
+fn openapi_manifest() -> Value {
+    let definitions = declarative_definitions();
+    let paths: Map<String, Value> = registry::all_tools()
+        .iter()
+        .map(|tool| {
+            let handler = definitions.get(tool.name.as_ref()).map_or_else(
+                || json!({ "type": "custom" }),
+                |def| describe_handler(&def.handler),
+            );
+            let operation = json!({
+                "post": {
+                    "operationId": tool.name,
+                    "description": tool.description,
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/json": { "schema": tool.input_schema.as_ref() },
+                        },
+                    },
+                    "responses": {
+                        "200": { "description": "The tool's JSON response" },
+                    },
+                    "x-handler": handler,
+                },
+            });
+            (format!("/tools/{}", tool.name), operation)
+        })
+        .collect();
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": env!("CARGO_PKG_DESCRIPTION"),
+        },
+        "paths": paths,
+    })
+}
+
+/// Declarative definitions by tool name
+fn declarative_definitions() -> HashMap<&'static str, BrpToolDef> {
+    tool_definitions::get_all_tools()
+        .into_iter()
+        .map(|def| (def.name, def))
+        .collect()
+}
+
+/// Manifest entry of one tool
+fn describe_tool(tool: &Tool, definition: Option<&BrpToolDef>) -> Value {
+    let (handler, parameters) = definition.map_or_else(
+        || (json!({ "type": "custom" }), schema_parameters(tool)),
+        |def| {
+            let parameters = def.params.iter().map(describe_param).collect();
+            (describe_handler(&def.handler), parameters)
+        },
+    );
+
+    json!({
+        "name": tool.name,
+        "description": tool.description,
+        "handler": handler,
+        "parameters": parameters,
+        "input_schema": tool.input_schema.as_ref(),
+    })
+}
+
+/// What runs when a declarative tool is called
+fn describe_handler(handler: &HandlerType) -> Value {
+    match handler {
+        HandlerType::Brp { method } => json!({ "type": "brp", "method": method }),
+        HandlerType::Local { .. } => json!({ "type": "local" }),
+        HandlerType::Watch {
+            operation: WatchOperation::Start(kind),
+        } => json!({ "type": "watch", "operation": "start", "kind": watch_kind_name(*kind) }),
+        HandlerType::Watch {
+            operation: WatchOperation::Stop,
+        } => json!({ "type": "watch", "operation": "stop" }),
+    }
+}
+
+const fn watch_kind_name(kind: WatchKind) -> &'static str {
+    match kind {
+        WatchKind::Entity => "entity",
+        WatchKind::List => "list",
+        WatchKind::Resource => "resource",
+        WatchKind::Resources => "resources",
+        WatchKind::Perf => "perf",
+    }
+}
+
+/// Manifest entry of a declarative parameter
+fn describe_param(param: &ParamDef) -> Value {
+    let mut entry = json!({
+        "name": param.name,
+        "type": param_type_name(&param.param_type),
+        "required": param.required,
+        "description": param.description,
+    });
+    if let Some(constraint) = param.constraint {
+        entry["constraint"] = describe_constraint(constraint);
+    }
+    entry
+}
+
+const fn param_type_name(param_type: &ParamType) -> &'static str {
+    match param_type {
+        ParamType::Number => "number",
+        ParamType::String => "string",
+        ParamType::Boolean => "boolean",
+        ParamType::StringArray => "string_array",
+        ParamType::NumberArray => "number_array",
+        ParamType::Any => "any",
+    }
+}
+
+fn describe_constraint(constraint: ParamConstraint) -> Value {
+    let (kind, min, max) = match constraint {
+        ParamConstraint::OneOf(values) => return json!({ "kind": "one_of", "values": values }),
+        ParamConstraint::Range { min, max } => ("range", min, max),
+        ParamConstraint::Clamp { min, max } => ("clamp", min, max),
+    };
+    let mut entry = json!({ "kind": kind, "min": min });
+    if max != u64::MAX {
+        entry["max"] = max.into();
+    }
+    entry
+}
+
+/// Manifest entries of the parameters of a hand-written tool, read from its input schema
+fn schema_parameters(tool: &Tool) -> Vec<Value> {
+    let required: Vec<&str> = tool
+        .input_schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(properties) = tool
+        .input_schema
+        .get("properties")
+        .and_then(Value::as_object)
+    else {
+        return Vec::new();
+    };
+
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let mut entry = json!({
+                "name": name,
+                "type": schema_type_name(property),
+                "required": required.contains(&name.as_str()),
+                "description": property.get("description").and_then(Value::as_str).unwrap_or_default(),
+            });
+            if let Some(values) = property.get("enum") {
+                entry["constraint"] = json!({ "kind": "one_of", "values": values });
+            } else if let Some(min) = property.get("minimum") {
+                entry["constraint"] = json!({ "kind": "range", "min": min });
+                if let Some(max) = property.get("maximum") {
+                    entry["constraint"]["max"] = max.clone();
+                }
+            }
+            entry
+        })
+        .collect()
+}
+
+/// Parameter type of a schema property, in the vocabulary of declarative parameters
+fn schema_type_name(property: &Value) -> String {
+    match property.get("type").and_then(Value::as_str) {
+        Some("array") => property
+            .pointer("/items/type")
+            .and_then(Value::as_str)
+            .map_or_else(|| "any".to_string(), |items| format!("{items}_array")),
+        Some(single) => single.to_string(),
+        None => "any".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::panic)]
+
+    use super::*;
+    use crate::tools::{TOOL_BEVY_GET, TOOL_BRP_DESCRIBE_TOOL, TOOL_BRP_STOP_WATCH};
+
+    fn entry<'a>(manifest: &'a Value, name: &str) -> &'a Value {
+        manifest["tools"]
+            .as_array()
+            .and_then(|tools| tools.iter().find(|tool| tool["name"] == name))
+            .unwrap_or_else(|| panic!("{name} is missing from the manifest"))
+    }
+
+    #[test]
+    fn test_manifest_describes_every_registered_tool() {
+        let manifest = build(FORMAT_JSON);
+        let tools = registry::all_tools();
+        assert_eq!(
+            manifest["tools"].as_array().map(Vec::len),
+            Some(tools.len())
+        );
+        assert_eq!(manifest["version"], env!("CARGO_PKG_VERSION"));
+
+        // Declarative tools carry their handler and constraints
+        let get = entry(&manifest, TOOL_BEVY_GET);
+        assert_eq!(
+            get["handler"],
+            json!({ "type": "brp", "method": "bevy/get" })
+        );
+        let port = get["parameters"]
+            .as_array()
+            .and_then(|params| params.iter().find(|param| param["name"] == "port"))
+            .cloned()
+            .unwrap_or_default();
+        assert_eq!(port["type"], "number");
+        assert_eq!(port["required"], false);
+        assert_eq!(
+            port["constraint"],
+            json!({ "kind": "range", "min": 1, "max": 65_535 })
+        );
+        assert_eq!(
+            entry(&manifest, TOOL_BRP_STOP_WATCH)["handler"],
+            json!({ "type": "watch", "operation": "stop" })
+        );
+
+        // Hand-written tools are described from their schema
+        let describe = entry(&manifest, TOOL_BRP_DESCRIBE_TOOL);
+        assert_eq!(describe["handler"]["type"], "custom");
+        assert_eq!(
+            describe["parameters"],
+            json!([{
+                "name": "tool",
+                "type": "string",
+                "required": true,
+                "description": "Name of the tool to describe",
+            }])
+        );
+    }
+
+    #[test]
+    fn test_openapi_manifest_has_an_operation_per_tool() {
+        let manifest = build(FORMAT_OPENAPI);
+        assert_eq!(manifest["openapi"], OPENAPI_VERSION);
+        let paths = manifest["paths"].as_object().cloned().unwrap_or_default();
+        assert_eq!(paths.len(), registry::all_tools().len());
+
+        let get = &paths[&format!("/tools/{TOOL_BEVY_GET}")]["post"];
+        assert_eq!(get["operationId"], TOOL_BEVY_GET);
+        assert_eq!(
+            get["requestBody"]["content"]["application/json"]["schema"]["type"],
+            "object"
+        );
+        assert_eq!(get["x-handler"]["method"], "bevy/get");
+    }
+}
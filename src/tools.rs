@@ -10,6 +10,9 @@ macro_rules! include_help_text {
     };
 }
 
+/// Help on the timeout and retry parameters, appended to the description of every BRP tool
+pub const DESC_REQUEST_POLICY: &str = include_help_text!("brp_tools/request_policy.txt");
+
 // Macro to define BRP methods with consistent naming
 macro_rules! define_method {
     // For Bevy protocol methods (bevy/*)
@@ -30,6 +33,14 @@ macro_rules! define_method {
         }
     };
 
+    // For server-side tools composed from several Bevy protocol calls (no single BRP method)
+    (bevy_composite, $method:ident) => {
+        paste::paste! {
+            pub const [<TOOL_BEVY_ $method:upper>]: &str = concat!("bevy_", stringify!($method));
+            pub const [<DESC_BEVY_ $method:upper>]: &str = include_help_text!(concat!("brp_tools/bevy_", stringify!($method), ".txt"));
+        }
+    };
+
     // For BRP extras methods (brp_extras/*)
     (brp_extras, $method:ident) => {
         paste::paste! {
@@ -71,6 +82,12 @@ macro_rules! define_method {
 /// `bevy_brp_extras` prefix
 pub const BRP_EXTRAS_PREFIX: &str = "brp_extras/";
 
+/// BRP method sending an event, in Bevy versions that have one
+pub const BRP_METHOD_SEND_EVENT: &str = "bevy/send_event";
+
+/// `bevy_brp_extras` method sending an event
+pub const BRP_METHOD_EXTRAS_SEND_EVENT: &str = "brp_extras/send_event";
+
 // ============================================================================
 // MCP TOOL NAMES AND DESCRIPTIONS - Generated by macros
 // ============================================================================
@@ -99,8 +116,58 @@ define_method!(bevy, reparent);
 define_method!(bevy, get_watch => "bevy/get+watch");
 define_method!(bevy, list_watch => "bevy/list+watch");
 
+// Generate tool constants for tools composed from several Bevy protocol calls
+define_method!(bevy_composite, bookmark_entity);
+define_method!(bevy_composite, copy_components);
+define_method!(bevy_composite, destroy_many);
+define_method!(bevy_composite, destroy_recursive);
+define_method!(bevy_composite, export_scene);
+define_method!(bevy_composite, explain_entity);
+define_method!(bevy_composite, find_entity_by_name);
+define_method!(bevy_composite, get_asset);
+define_method!(bevy_composite, get_diagnostics);
+define_method!(bevy_composite, get_hierarchy);
+define_method!(bevy_composite, get_states);
+define_method!(bevy_composite, has_components);
+define_method!(bevy_composite, insert_many);
+define_method!(bevy_composite, isolate_entity);
+define_method!(bevy_composite, list_assets);
+define_method!(bevy_composite, list_bookmarks);
+define_method!(bevy_composite, list_mutation_paths);
+define_method!(bevy_composite, list_systems);
+define_method!(bevy_composite, pause_time);
+define_method!(bevy_composite, resolve_bookmark);
+define_method!(bevy_composite, restore_resource_set);
+define_method!(bevy_composite, resume_time);
+define_method!(bevy_composite, rotate_entity);
+define_method!(bevy_composite, save_resource_set);
+define_method!(bevy_composite, scale_entity);
+define_method!(bevy_composite, schedule_graph);
+define_method!(bevy_composite, send_event);
+define_method!(bevy_composite, set_state);
+define_method!(bevy_composite, set_time_scale);
+define_method!(bevy_composite, set_visibility);
+define_method!(bevy_composite, snapshot_create);
+define_method!(bevy_composite, snapshot_diff);
+define_method!(bevy_composite, spawn_from_file);
+define_method!(bevy_composite, spawn_many);
+define_method!(bevy_composite, step_frames);
+define_method!(bevy_composite, translate_entity);
+define_method!(bevy_composite, type_schema);
+define_method!(bevy_composite, world_stats);
+
 // BRP execute tool (not a direct Bevy method, server-only)
+define_method!(brp, describe_tool);
+define_method!(brp, export_tool_manifest);
 define_method!(brp, execute);
+define_method!(brp, execute_batch);
+define_method!(brp, validate_format);
+define_method!(brp, probe_hosts);
+define_method!(brp, discover_ports);
+define_method!(brp, check_extras);
+define_method!(brp, run_assertions);
+define_method!(brp, corrections_report);
+define_method!(brp, list_format_corrections);
 
 // -----------------------------------------------------------------------------
 // BRP Extras Tools (bevy_brp_extras plugin methods)
@@ -111,6 +178,8 @@ define_method!(brp_extras, shutdown);
 define_method!(brp_extras, screenshot);
 define_method!(brp_extras, discover_format);
 define_method!(brp_extras, send_keys);
+define_method!(brp_extras, send_mouse);
+define_method!(brp_extras, send_gamepad);
 define_method!(brp_extras, set_debug_mode);
 
 // -----------------------------------------------------------------------------
@@ -121,7 +190,23 @@ define_method!(brp_extras, set_debug_mode);
 define_method!(brp, status);
 define_method!(brp, stop_watch);
 define_method!(brp, list_active_watches);
+define_method!(brp, read_watch_history);
+define_method!(brp, watch_resource);
+define_method!(brp, watch_resources);
+define_method!(brp, start_perf_watch);
 define_method!(brp, set_debug_mode);
+define_method!(brp, schedule_operation);
+define_method!(brp, list_scheduled);
+define_method!(brp, cancel_scheduled);
+define_method!(brp, start_screenshot_series);
+define_method!(brp, stop_screenshot_series);
+define_method!(brp, list_screenshot_series);
+define_method!(brp, start_session_recording);
+define_method!(brp, stop_session_recording);
+define_method!(brp, export_session);
+define_method!(brp, replay_session);
+define_method!(brp, get_config);
+define_method!(brp, set_config);
 
 // -----------------------------------------------------------------------------
 // Application Management Tools
@@ -129,10 +214,19 @@ define_method!(brp, set_debug_mode);
 
 // Generate tool constants for app management tools
 define_method!(app, list_bevy_apps);
+define_method!(app, analyze_bevy_project);
+define_method!(app, enable_brp_in_project);
 define_method!(app, list_bevy_examples);
 define_method!(app, list_brp_apps);
 define_method!(app, launch_bevy_app);
 define_method!(app, launch_bevy_example);
+define_method!(app, build_bevy_app);
+define_method!(app, build_bevy_example);
+define_method!(app, attach_app);
+define_method!(app, list_launched_processes);
+define_method!(app, kill_process);
+define_method!(app, restart_app);
+define_method!(app, wait_for_brp);
 
 // -----------------------------------------------------------------------------
 // Log Management Tools
@@ -141,4 +235,6 @@ define_method!(app, launch_bevy_example);
 // Generate tool constants for log management tools
 define_method!(log, list_logs);
 define_method!(log, read_log);
+define_method!(log, read_response);
 define_method!(log, cleanup_logs);
+define_method!(log, get_app_errors);
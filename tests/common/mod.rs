@@ -0,0 +1,180 @@
+//! Harness for the integration tests: the `headless_fixture` example and an MCP client that
+//! drives the server binary over stdio
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+/// How long the fixture may take to build and start serving BRP
+const FIXTURE_STARTUP_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// MCP protocol version announced by the client
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// The `headless_fixture` example, serving BRP until dropped
+pub struct Fixture {
+    child:    Child,
+    /// Port the fixture serves BRP on
+    pub port: u16,
+}
+
+impl Fixture {
+    /// Build and start the fixture on a port, waiting until it accepts connections
+    pub fn launch(port: u16) -> Self {
+        let child = Command::new(env!("CARGO"))
+            .args(["run", "--quiet", "--example", "headless_fixture"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .env("BRP_PORT", port.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to run cargo for the fixture");
+        let mut fixture = Self { child, port };
+
+        let address = SocketAddr::from(([127, 0, 0, 1], port));
+        let started = Instant::now();
+        while TcpStream::connect_timeout(&address, Duration::from_millis(200)).is_err() {
+            let exited = fixture
+                .child
+                .try_wait()
+                .expect("failed to poll the fixture");
+            assert!(exited.is_none(), "fixture exited with {exited:?}");
+            assert!(
+                started.elapsed() < FIXTURE_STARTUP_TIMEOUT,
+                "fixture did not serve BRP on port {port} in time"
+            );
+            std::thread::sleep(Duration::from_millis(200));
+        }
+        fixture
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The MCP server binary, initialized and ready for tool calls
+pub struct McpServer {
+    child:   Child,
+    stdin:   ChildStdin,
+    stdout:  BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl McpServer {
+    /// Start the server and complete the MCP handshake
+    pub fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_bevy_brp_mcp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start the MCP server");
+        let stdin = child.stdin.take().expect("server stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("server stdout is piped"));
+        let mut server = Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+        };
+
+        server.request(
+            "initialize",
+            &json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "roots": {} },
+                "clientInfo": { "name": "integration-tests", "version": "0.0.0" },
+            }),
+        );
+        server.send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }));
+        server
+    }
+
+    /// Call a tool, returning the JSON response it wrote as text content
+    pub fn call_tool(&mut self, name: &str, arguments: &Value) -> Value {
+        let result = self.request(
+            "tools/call",
+            &json!({ "name": name, "arguments": arguments }),
+        );
+        let text = result["content"][0]["text"]
+            .as_str()
+            .unwrap_or_else(|| panic!("{name} returned no text content: {result}"));
+        serde_json::from_str(text).unwrap_or_else(|_| json!(text))
+    }
+
+    /// Call a tool that must succeed, returning its `data`
+    pub fn call_tool_ok(&mut self, name: &str, arguments: &Value) -> Value {
+        let response = self.call_tool(name, arguments);
+        assert_eq!(response["status"], "success", "{name} failed: {response}");
+        response["data"].clone()
+    }
+
+    /// Send a request and wait for its result, answering requests from the server meanwhile
+    fn request(&mut self, method: &str, params: &Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        loop {
+            let mut line = String::new();
+            let read = self
+                .stdout
+                .read_line(&mut line)
+                .expect("failed to read from the server");
+            assert!(read > 0, "server closed stdout while waiting for {method}");
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+
+            if message.get("method").is_some() {
+                if let Some(request_id) = message.get("id") {
+                    self.answer(request_id, &message["method"]);
+                }
+                continue;
+            }
+            if message["id"] == json!(id) {
+                assert!(
+                    message.get("error").is_none(),
+                    "{method} failed: {}",
+                    message["error"]
+                );
+                return message["result"].clone();
+            }
+        }
+    }
+
+    /// Answer a request from the server: no roots, nothing else supported
+    fn answer(&mut self, id: &Value, method: &Value) {
+        let reply = if method == "roots/list" {
+            json!({ "jsonrpc": "2.0", "id": id, "result": { "roots": [] } })
+        } else {
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": "method not found" },
+            })
+        };
+        self.send(&reply);
+    }
+
+    fn send(&mut self, message: &Value) {
+        writeln!(self.stdin, "{message}").expect("failed to write to the server");
+        self.stdin
+            .flush()
+            .expect("failed to flush the server stdin");
+    }
+}
+
+impl Drop for McpServer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
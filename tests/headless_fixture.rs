@@ -0,0 +1,111 @@
+//! End-to-end tests against the `headless_fixture` example
+//!
+//! Each test builds and launches the fixture on its own port, then drives the server binary over
+//! stdio. They are ignored by default since they compile and run a Bevy app; run them with
+//! `cargo test --test headless_fixture -- --ignored`.
+
+#![allow(clippy::expect_used, clippy::panic)]
+
+mod common;
+
+use common::{Fixture, McpServer};
+use serde_json::json;
+
+/// Fully-qualified name of the fixture's component
+const HEALTH: &str = "headless_fixture::Health";
+
+/// Fully-qualified name of the fixture's resource
+const SCORE: &str = "headless_fixture::Score";
+
+/// Fully-qualified name of `Transform`
+const TRANSFORM: &str = "bevy_transform::components::transform::Transform";
+
+#[test]
+#[ignore = "launches the headless_fixture example; run with -- --ignored"]
+fn test_spawn_get_and_mutate_component() {
+    let fixture = Fixture::launch(15791);
+    let mut server = McpServer::start();
+    let port = fixture.port;
+
+    let spawned = server.call_tool_ok(
+        "bevy_spawn",
+        &json!({ "components": { HEALTH: { "current": 50.0, "max": 80.0 } }, "port": port }),
+    );
+    let entity = spawned["spawned_entity"]
+        .as_u64()
+        .expect("spawn returns the entity");
+
+    let get = json!({ "entity": entity, "components": [HEALTH], "port": port });
+    let health = &server.call_tool_ok("bevy_get", &get)["components"]["components"][HEALTH];
+    assert_eq!(*health, json!({ "current": 50.0, "max": 80.0 }));
+
+    server.call_tool_ok(
+        "bevy_mutate_component",
+        &json!({
+            "entity": entity,
+            "component": HEALTH,
+            "path": ".current",
+            "value": 10.0,
+            "port": port,
+        }),
+    );
+    let health = &server.call_tool_ok("bevy_get", &get)["components"]["components"][HEALTH];
+    assert_eq!(health["current"], json!(10.0));
+}
+
+#[test]
+#[ignore = "launches the headless_fixture example; run with -- --ignored"]
+fn test_mutate_resource() {
+    let fixture = Fixture::launch(15792);
+    let mut server = McpServer::start();
+    let port = fixture.port;
+
+    server.call_tool_ok(
+        "bevy_mutate_resource",
+        &json!({ "resource": SCORE, "path": ".points", "value": 42, "port": port }),
+    );
+    let score = server.call_tool_ok(
+        "bevy_get_resource",
+        &json!({ "resource": SCORE, "port": port }),
+    );
+    assert_eq!(score["data"]["value"], json!({ "points": 42 }), "{score}");
+}
+
+#[test]
+#[ignore = "launches the headless_fixture example; run with -- --ignored"]
+fn test_format_discovery_corrects_object_vectors() {
+    let fixture = Fixture::launch(15793);
+    let mut server = McpServer::start();
+    let port = fixture.port;
+
+    // Vec3 serializes as an array, so the object form needs a format correction
+    let response = server.call_tool(
+        "bevy_spawn",
+        &json!({
+            "components": {
+                TRANSFORM: {
+                    "translation": { "x": 1.0, "y": 2.0, "z": 3.0 },
+                    "rotation": [0.0, 0.0, 0.0, 1.0],
+                    "scale": [1.0, 1.0, 1.0],
+                },
+            },
+            "port": port,
+        }),
+    );
+    assert_eq!(response["status"], "success", "{response}");
+    assert!(
+        response["data"]["format_corrections"]
+            .as_array()
+            .is_some_and(|corrections| !corrections.is_empty()),
+        "{response}"
+    );
+
+    let entity = response["data"]["spawned_entity"]
+        .as_u64()
+        .expect("spawn returns the entity");
+    let transform = &server.call_tool_ok(
+        "bevy_get",
+        &json!({ "entity": entity, "components": [TRANSFORM], "port": port }),
+    )["components"]["components"][TRANSFORM];
+    assert_eq!(transform["translation"], json!([1.0, 2.0, 3.0]));
+}